@@ -0,0 +1,188 @@
+//! Core throughput benchmarks: opcode dispatch, PPU frame rendering (both
+//! renderers), APU sample generation and full-frame emulation, all on a
+//! hand-assembled homebrew ROM so they run without any test-ROM checkout.
+//!
+//!   cargo bench -p rustyboi-core --bench core
+//!   cargo bench -p rustyboi-core --bench core -- ppu   # one group
+//!
+//! The ROM turns the LCD off, fills tile data and both tile maps with a byte
+//! ramp, scatters all 40 sprites, starts all four sound channels, then turns
+//! the LCD back on with BG, window and sprites enabled. Its main loop is one
+//! of two:
+//!
+//! - busy: a mixed ALU / load / stack / CB / call loop that also bumps SCX
+//!   every pass, for dispatch and whole-frame numbers;
+//! - idle: `halt` forever (IE = 0, so it never wakes), which leaves the PPU
+//!   and APU with most of the time, for their groups.
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use rustyboi_core_lib::cartridge::Cartridge;
+use rustyboi_core_lib::gb::{GB, Hardware};
+use std::hint::black_box;
+
+/// Register writes (`FF00+reg`, value) applied in order once VRAM is filled.
+/// NR52 goes first (the APU ignores writes while powered off) and LCDC last.
+const REGISTERS: &[(u8, u8)] = &[
+    (0x26, 0x80), // NR52: APU on
+    (0x24, 0x77), // NR50: full volume both sides
+    (0x25, 0xFF), // NR51: every channel to both sides
+    (0x10, 0x00), // NR10: no sweep
+    (0x11, 0x80), // NR11: 50% duty
+    (0x12, 0xF0), // NR12: volume 15, no envelope
+    (0x13, 0x00), // NR13
+    (0x14, 0x87), // NR14: trigger, no length
+    (0x16, 0x40), // NR21: 25% duty
+    (0x17, 0xF0), // NR22
+    (0x18, 0x80), // NR23
+    (0x19, 0x86), // NR24: trigger
+    (0x1A, 0x80), // NR30: wave DAC on
+    (0x1C, 0x20), // NR32: full volume
+    (0x1D, 0x00), // NR33
+    (0x1E, 0x87), // NR34: trigger
+    (0x21, 0xF0), // NR42
+    (0x22, 0x55), // NR43
+    (0x23, 0x80), // NR44: trigger
+    (0x47, 0xE4), // BGP
+    (0x48, 0xE4), // OBP0
+    (0x49, 0x1B), // OBP1
+    (0x4A, 0x40), // WY
+    (0x4B, 0x57), // WX
+    (0x40, 0xF3), // LCDC: on, window (9C00) + BG (9800, 8000 data) + OBJ
+];
+
+/// Mixed-opcode main loop: 256 passes over C000-C0FF, then SCX += 1.
+const BUSY_LOOP: &[u8] = &[
+    0x21, 0x00, 0xC0, // main: ld hl, C000
+    0x06, 0x00, // ld b, 0
+    0x7E, // inner: ld a, (hl)
+    0x80, // add a, b
+    0x07, // rlca
+    0xAD, // xor l
+    0x22, // ld (hl+), a
+    0xCB, 0x37, // swap a
+    0xCB, 0x5F, // bit 3, a
+    0xC5, // push bc
+    0xD1, // pop de
+    0xCD, 0x10, 0x03, // call 0310 (ret)
+    0x05, // dec b
+    0x20, 0xEF, // jr nz, inner
+    0xF0, 0x43, // ldh a, (SCX)
+    0x3C, // inc a
+    0xE0, 0x43, // ldh (SCX), a
+    0x18, 0xE3, // jr main
+];
+
+const IDLE_LOOP: &[u8] = &[
+    0x76, // halt
+    0x18, 0xFD, // jr halt
+];
+
+fn homebrew_rom(main_loop: &[u8]) -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // nop; jp 0150
+    let mut code = vec![
+        0xF3, // di
+        0x31, 0xFE, 0xFF, // ld sp, FFFE
+        0xF0, 0x44, // wait: ldh a, (LY)
+        0xFE, 0x90, // cp 144
+        0x20, 0xFA, // jr nz, wait
+        0xAF, // xor a
+        0xE0, 0x40, // ldh (LCDC), a
+        0x21, 0x00, 0x80, // ld hl, 8000 (tile data + both maps)
+        0x01, 0x00, 0x20, // ld bc, 2000
+        0xCD, 0x00, 0x03, // call fill
+        0x21, 0x00, 0xFE, // ld hl, FE00 (OAM)
+        0x01, 0xA0, 0x00, // ld bc, 00A0
+        0xCD, 0x00, 0x03, // call fill
+        0x21, 0x30, 0xFF, // ld hl, FF30 (wave RAM)
+        0x01, 0x10, 0x00, // ld bc, 0010
+        0xCD, 0x00, 0x03, // call fill
+        0x21, 0x00, 0x02, // ld hl, 0200 (REGISTERS)
+        0x06, REGISTERS.len() as u8, // ld b, len
+        0x2A, // next: ld a, (hl+)
+        0x4F, // ld c, a
+        0x2A, // ld a, (hl+)
+        0xE2, // ld (FF00+c), a
+        0x05, // dec b
+        0x20, 0xF9, // jr nz, next
+    ];
+    code.extend_from_slice(main_loop);
+    rom[0x150..0x150 + code.len()].copy_from_slice(&code);
+    for (i, &(reg, value)) in REGISTERS.iter().enumerate() {
+        rom[0x200 + 2 * i] = reg;
+        rom[0x201 + 2 * i] = value;
+    }
+    rom[0x300..0x307].copy_from_slice(&[
+        0x7D, // fill: ld a, l
+        0x22, // ld (hl+), a
+        0x0B, // dec bc
+        0x78, // ld a, b
+        0xB1, // or c
+        0x20, 0xF9, // jr nz, fill
+    ]);
+    rom[0x307] = 0xC9; // ret
+    rom[0x310] = 0xC9; // ret (the busy loop's call target)
+    rom
+}
+
+/// A machine past the ROM's setup, with the LCD back on and sound playing.
+fn booted(hardware: Hardware, main_loop: &[u8]) -> GB {
+    let mut gb = GB::new(hardware);
+    gb.insert(Cartridge::from_bytes(&homebrew_rom(main_loop)).unwrap());
+    gb.skip_bios();
+    // The fills take ~4 LCD-off frames; settle a few more with the LCD on.
+    for _ in 0..10 {
+        gb.run_until_frame(false);
+    }
+    assert_eq!(gb.read_memory(0xFF40), 0xF3, "homebrew ROM setup did not finish");
+    gb
+}
+
+fn cpu(c: &mut Criterion) {
+    const STEPS: u64 = 1000;
+    let mut group = c.benchmark_group("cpu");
+    group.throughput(Throughput::Elements(STEPS));
+    let mut gb = booted(Hardware::DMG, BUSY_LOOP);
+    group.bench_function("step_instruction", |b| {
+        b.iter(|| {
+            for _ in 0..STEPS {
+                black_box(gb.step_instruction(false));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn ppu(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ppu");
+    group.throughput(Throughput::Elements(1));
+    for (name, scanline) in [("dot", false), ("scanline", true)] {
+        let mut gb = booted(Hardware::DMG, IDLE_LOOP);
+        gb.set_scanline_ppu(scanline);
+        group.bench_function(name, |b| b.iter(|| black_box(gb.run_until_frame(false))));
+    }
+    group.finish();
+}
+
+fn apu(c: &mut Criterion) {
+    // About one frame's worth at the default host rate.
+    const SAMPLES: usize = 800;
+    let mut group = c.benchmark_group("apu");
+    group.throughput(Throughput::Elements(SAMPLES as u64));
+    let mut gb = booted(Hardware::DMG, IDLE_LOOP);
+    group.bench_function("samples", |b| b.iter(|| black_box(gb.run_until_audio_samples(SAMPLES))));
+    group.finish();
+}
+
+fn frame(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frame");
+    group.throughput(Throughput::Elements(1));
+    for (name, hardware) in [("dmg", Hardware::DMG), ("cgb", Hardware::CGB)] {
+        let mut gb = booted(hardware, BUSY_LOOP);
+        group.bench_function(name, |b| b.iter(|| black_box(gb.run_until_frame(true))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, cpu, ppu, apu, frame);
+criterion_main!(benches);
@@ -23,6 +23,7 @@
 //! 0.999958^(4194304/rate)".
 
 use crate::audio::controller::HOST_SAMPLE_RATE;
+use crate::audio::output::OVERSAMPLE;
 use crate::gb::DMG_CPU_HZ;
 use serde::{Deserialize, Serialize};
 
@@ -88,15 +89,15 @@ impl AnalogModel {
         }
     }
 
-    /// The per-host-sample factor: `base^(cycles per output sample)`. The RC is
-    /// a real-time network and the host rate is fixed, so the exponent is
-    /// always the DMG cycle rate over [`HOST_SAMPLE_RATE`] — an SGB1's slower
-    /// crystal repitches the machine, not the filter.
-    fn charge_per_sample(self) -> f32 {
+    /// The per-step factor for a stage stepped `rate` times a second:
+    /// `base^(cycles per step)`. The RC is a real-time network, so the exponent
+    /// is always the DMG cycle rate over `rate` — an SGB1's slower crystal
+    /// repitches the machine, not the filter.
+    fn charge_per_step(self, rate: f32) -> f32 {
         // Deterministic pure-Rust `libm::powf` (not `f32::powf`, which routes to
         // the platform libm and is not bit-identical across targets), mirroring
         // color_mix.rs's gamma path so the audio hash is portable.
-        libm::powf(self.charge_per_cycle(), DMG_CPU_HZ as f32 / HOST_SAMPLE_RATE)
+        libm::powf(self.charge_per_cycle(), DMG_CPU_HZ as f32 / rate)
     }
 }
 
@@ -121,10 +122,14 @@ const FLUSH: f32 = 1e-9;
 /// stage that somehow escapes reseeding filters rather than collapsing.
 #[derive(Clone, Serialize, Deserialize)]
 pub(super) struct AnalogStage {
-    /// Per-host-sample charge factor for this model, shared by the fade and
-    /// the high-pass (one RC family per machine).
-    #[serde(skip, default = "dmg_charge_per_sample")]
+    /// Per-step charge factor for this model, shared by the fade and the
+    /// high-pass (one RC family per machine).
+    #[serde(skip, default = "dmg_charge_per_step")]
     charge: f32,
+    /// Steps per second: the mixer-point rate, `OVERSAMPLE` times the host
+    /// rate. A host setting re-applied after a load, like the rate itself.
+    #[serde(skip, default = "default_step_rate")]
+    step_rate: f32,
     /// Which family this stage is modelling. Derived from the machine's
     /// serialized `hardware` identity by `set_model`, exactly like `charge`,
     /// so it is skipped rather than stored.
@@ -139,36 +144,57 @@ pub(super) struct AnalogStage {
     cap_l: f32,
     #[serde(default)]
     cap_r: f32,
+    /// Hand the raw mix through instead of the high-passed one (a host
+    /// preference, not machine state, so never serialized). The capacitors
+    /// keep charging either way, so turning the filter back on is seamless.
+    #[serde(skip)]
+    bypass_high_pass: bool,
 }
 
 /// The `charge` stand-in for a stage deserialized before `set_analog_model`
 /// re-derives it. Matches [`AnalogStage::default`].
-fn dmg_charge_per_sample() -> f32 {
-    AnalogModel::Dmg.charge_per_sample()
+fn dmg_charge_per_step() -> f32 {
+    AnalogModel::Dmg.charge_per_step(default_step_rate())
+}
+
+fn default_step_rate() -> f32 {
+    HOST_SAMPLE_RATE * OVERSAMPLE as f32
 }
 
 impl Default for AnalogStage {
     fn default() -> Self {
         AnalogStage {
-            charge: AnalogModel::Dmg.charge_per_sample(),
+            charge: dmg_charge_per_step(),
+            step_rate: default_step_rate(),
             model: AnalogModel::Dmg,
             fade: [0.0; 4],
             cap_l: 0.0,
             cap_r: 0.0,
+            bypass_high_pass: false,
         }
     }
 }
 
 impl AnalogStage {
     pub(super) fn set_model(&mut self, model: AnalogModel) {
-        self.charge = model.charge_per_sample();
+        self.charge = model.charge_per_step(self.step_rate);
         self.model = model;
     }
 
+    /// How many times a second the stage is stepped (re-derives the charge).
+    pub(super) fn set_step_rate(&mut self, rate: f32) {
+        self.step_rate = rate;
+        self.charge = self.model.charge_per_step(rate);
+    }
+
     pub(super) fn model(&self) -> AnalogModel {
         self.model
     }
 
+    pub(super) fn set_high_pass(&mut self, on: bool) {
+        self.bypass_high_pass = !on;
+    }
+
     /// Apply the DAC-off fade. `raw` is each channel's post-DAC analog level
     /// (0.0 where the DAC is off — the endpoint, not the instantaneous value);
     /// a live DAC drives its node directly, a dead one coasts toward 0 instead
@@ -212,6 +238,9 @@ impl AnalogStage {
         if self.cap_r.abs() < FLUSH {
             self.cap_r = 0.0;
         }
+        if self.bypass_high_pass {
+            return (left, right);
+        }
         (out_l, out_r)
     }
 }
@@ -227,7 +256,7 @@ mod tests {
     /// you'd use a charge factor of 0.996".
     #[test]
     fn dmg_charge_factor_matches_the_published_44100_hz_value() {
-        let f = AnalogModel::Dmg.charge_per_sample();
+        let f = AnalogModel::Dmg.charge_per_step(44_100.0);
         assert!((f - 0.996).abs() < 5e-4, "DMG charge at 44.1 kHz was {f}");
     }
 
@@ -306,6 +335,21 @@ mod tests {
         }
     }
 
+    /// With the filter off the raw mix goes straight out, but the capacitor
+    /// still tracks it.
+    #[test]
+    fn bypassed_high_pass_passes_the_raw_mix_and_keeps_charging() {
+        let mut stage = AnalogStage::default();
+        stage.set_high_pass(false);
+        for _ in 0..20_000 {
+            assert_eq!(stage.high_pass(0.5, -0.25), (0.5, -0.25));
+        }
+        // Re-enabled, the capacitor is already charged: no restart transient.
+        stage.set_high_pass(true);
+        let (l, r) = stage.high_pass(0.5, -0.25);
+        assert!(l.abs() < 0.01 && r.abs() < 0.01, "re-enabled output was ({l}, {r})");
+    }
+
     /// A constant input is a pure DC bias, which the high-pass must remove.
     #[test]
     fn high_pass_removes_a_constant_bias() {
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::audio::{analog, wave, square, noise};
+use crate::audio::output::{Resampler, OVERSAMPLE};
 use crate::memory::Addressable;
 
 pub(crate) const NR10: u16 = 0xFF10; // Channel 1 sweep register
@@ -50,12 +51,20 @@ pub struct Audio {
     // Sample generation timing
     fractional_cycles: f32,
 
-    // Dots per 44.1 kHz host sample = cpu_hz / 44100. Host resampling only, not
+    // Dots per host sample = cpu_hz / sample_rate. Host resampling only, not
     // machine state (an SGB1's dot timeline is identical to a DMG's — only the
     // wall-clock rate those dots are played back at differs), so it is skipped
     // in the savestate and re-seeded from the model by `GB::set_region`.
+    // `fractional_cycles` counts in mixer points, `OVERSAMPLE` to a sample.
     #[serde(skip, default = "default_cycles_per_sample")]
     cycles_per_sample: f32,
+    // The two inputs to `cycles_per_sample`, kept so either setter can
+    // re-derive it. The host rate is a frontend preference re-applied after a
+    // restore, like `GB`'s region.
+    #[serde(skip, default = "default_cpu_hz")]
+    cpu_hz: u32,
+    #[serde(skip, default = "default_sample_rate")]
+    sample_rate: u32,
 
     // APU master clock — an absolute 2 MHz counter (mod 0x8000_0000) anchored
     // at boot. Driven from the timer's absolute `abs_cc`: each `sync_cc`
@@ -131,6 +140,11 @@ pub struct Audio {
     // never serialized
     #[serde(skip)]
     channel_tap: Option<Vec<ChannelSample>>,
+    // Host-side mixer mutes (channel-ripping / debugging), applied after the
+    // tap so a recording still captures what the machine played. Not machine
+    // state, never serialized.
+    #[serde(skip)]
+    muted: [bool; 4],
     // The analog output stage (DAC-off fade + output high-pass). Serialized so
     // a load / rewind step resumes the filter where it was instead of ringing
     // out a restart transient; its model-derived charge factor is the one part
@@ -148,18 +162,48 @@ pub struct Audio {
 /// value is always one of the 16 DAC levels or 0.0 for an unpowered DAC.
 pub type ChannelSample = ([f32; 4], u8, u8, bool);
 
+/// One channel as a debugger shows it: what its registers and units hold
+/// right now. Built by [`Audio::channel_states`]; never read back by the APU.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelState {
+    /// NR52's status bit: triggered and not since stopped by length, sweep or
+    /// its DAC.
+    pub enabled: bool,
+    pub dac_on: bool,
+    /// The tone's pitch (square, wave) or the LFSR clock (noise; 0 when the
+    /// shift stalls it).
+    pub frequency_hz: f32,
+    /// Envelope volume 0-15; for the wave channel, NR32's output level code
+    /// (0 mute, 1 100%, 2 50%, 3 25%).
+    pub volume: u8,
+    /// Duty code 0-3 (12.5%, 25%, 50%, 75%); `None` off the square channels.
+    pub duty: Option<u8>,
+    /// Length steps (256 Hz) left before the length unit stops the channel.
+    pub length_remaining: u16,
+    pub length_enabled: bool,
+}
+
 fn default_ctl_lf_div() -> u32 {
     1
 }
 
-/// The host output rate every backend consumes. Fixed: the machine's clock
-/// changes how many dots fill one sample, never the samples-per-second.
+/// The default host output rate. A frontend may pick another
+/// ([`Audio::set_sample_rate`]); the machine's clock only changes how many
+/// dots fill one sample, never the samples-per-second.
 pub const HOST_SAMPLE_RATE: f32 = 44100.0;
 
 fn default_cycles_per_sample() -> f32 {
     crate::gb::DMG_CPU_HZ as f32 / HOST_SAMPLE_RATE
 }
 
+fn default_cpu_hz() -> u32 {
+    crate::gb::DMG_CPU_HZ
+}
+
+fn default_sample_rate() -> u32 {
+    HOST_SAMPLE_RATE as u32
+}
+
 impl Default for Audio {
     fn default() -> Self {
         Self::new()
@@ -180,6 +224,8 @@ impl Audio {
             audio_enabled: false,
             fractional_cycles: 0.0,
             cycles_per_sample: default_cycles_per_sample(),
+            cpu_hz: default_cpu_hz(),
+            sample_rate: default_sample_rate(),
             cc: 0,
             last_update: 0,
             last_div_resets: 0,
@@ -193,6 +239,7 @@ impl Audio {
             skip_div_event: 0,
             cgb_de: false,
             channel_tap: None,
+            muted: [false; 4],
             analog: analog::AnalogStage::default(),
         }
     }
@@ -204,6 +251,24 @@ impl Audio {
         self.analog.set_model(model);
     }
 
+    /// Turn the output high-pass on (the default, as on hardware) or off for
+    /// the raw mix. The DAC-off fade is unaffected.
+    pub fn set_high_pass(&mut self, on: bool) {
+        self.analog.set_high_pass(on);
+    }
+
+    /// Choose which of CH1..CH4 reach the mixer (`false` mutes). A muted
+    /// channel keeps running and still shows in NR52 and the channel tap; only
+    /// its contribution to the host output is dropped.
+    pub fn set_audible_channels(&mut self, audible: [bool; 4]) {
+        self.muted = audible.map(|on| !on);
+    }
+
+    /// Each channel's state for a debugger readout, CH1 to CH4.
+    pub fn channel_states(&self) -> [ChannelState; 4] {
+        [self.channel1.state(), self.channel2.state(), self.channel3.state(), self.channel4.state()]
+    }
+
     /// Engage/disengage the per-sample channel tap (recording/measurement).
     pub fn set_channel_tap(&mut self, on: bool) {
         self.channel_tap = on.then(Vec::new);
@@ -834,14 +899,29 @@ impl Audio {
     }
 
     /// Set the machine's real-time CPU clock, which fixes how many dots make one
-    /// 44.1 kHz host sample. Affects only the downsample ratio in
-    /// `generate_samples` — no channel timer, length counter, or frame-sequencer
-    /// step reads it, so the dot-domain APU state stays byte-identical.
+    /// host sample. Affects only the downsample ratio in `generate_samples` —
+    /// no channel timer, length counter, or frame-sequencer step reads it, so
+    /// the dot-domain APU state stays byte-identical.
     pub fn set_cpu_hz(&mut self, hz: u32) {
-        self.cycles_per_sample = hz as f32 / HOST_SAMPLE_RATE;
+        self.cpu_hz = hz;
+        self.cycles_per_sample = hz as f32 / self.sample_rate as f32;
+    }
+
+    /// Set the host output rate in Hz (44100 by default). Like `set_cpu_hz`,
+    /// only the downsample ratio and the analog stage's per-step charge move;
+    /// the channels never see it.
+    pub fn set_sample_rate(&mut self, rate: u32) {
+        self.sample_rate = rate;
+        self.cycles_per_sample = self.cpu_hz as f32 / rate as f32;
+        self.analog.set_step_rate((rate as usize * OVERSAMPLE) as f32);
     }
 
-    /// Dots per host sample (`cpu_hz / 44100`).
+    /// The host output rate in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Dots per host sample (`cpu_hz / sample_rate`).
     pub fn cycles_per_sample(&self) -> f32 {
         self.cycles_per_sample
     }
@@ -933,14 +1013,6 @@ impl Audio {
         self.channel4.set_length_counter(0);
     }
 
-    /// True while the APU is powered (NR52 bit 7). The min-event idle fast path
-    /// only bulk-skips dots when audio is OFF, because a powered APU steps its
-    /// channel duty/freq counters per dot (`step`), which is not
-    /// span-collapsible like the frame sequencer.
-    pub(crate) fn is_powered(&self) -> bool {
-        self.audio_enabled
-    }
-
     /// CGB PCM12 register (0xFF76): low nibble = channel 1 digital output, high
     /// nibble = channel 2. Returns 0 when the APU is powered off; the CGB-only
     /// / power gating is applied by the caller in `mmio.rs`.
@@ -1046,8 +1118,12 @@ impl Audio {
     }
 
 
-    pub(crate) fn generate_samples(&mut self, cpu_cycles: u32) -> Vec<(f32, f32)> {
-        let mut samples = Vec::new();
+    /// Down-sample `cpu_cycles` worth of mixer output to host samples through
+    /// `resampler` (which the caller owns, so it is serialized with the
+    /// machine; see `output.rs`), appending them to `samples`. Called once per
+    /// instruction, so the caller keeps one buffer and reuses it rather than
+    /// this allocating a fresh one for every sample it emits.
+    pub(crate) fn generate_samples(&mut self, cpu_cycles: u32, resampler: &mut Resampler, samples: &mut Vec<(f32, f32)>) {
 
         // Channels are caught up lazily via `sync_cc` (the caller syncs the
         // APU to the current cc first), so here we only down-sample the live
@@ -1056,38 +1132,54 @@ impl Audio {
         // The divisor is the machine's own clock (an SGB1's is the host SNES's
         // / 5), so a fixed 70224-dot frame yields fewer host samples and every
         // tone comes out at `cpu_hz / period` — pitched up 2.4% on an NTSC SGB1.
-        let cycles_per_sample = self.cycles_per_sample;
-
-        self.fractional_cycles += cpu_cycles as f32;
+        let cycles_per_point = self.cycles_per_sample / OVERSAMPLE as f32;
 
-        while self.fractional_cycles >= cycles_per_sample {
-            samples.push(self.analog_sample());
-            self.fractional_cycles -= cycles_per_sample;
+        // The channels held `raw` for the whole of `cpu_cycles`; split that
+        // span across the point boundaries it crosses, box-averaging each
+        // point's interval rather than sampling its last instant.
+        let raw = self.channel_outputs();
+        let mut cycles = cpu_cycles as f32;
+        while self.fractional_cycles + cycles >= cycles_per_point {
+            let take = (cycles_per_point - self.fractional_cycles).max(0.0);
+            resampler.integrate(raw, take);
+            cycles -= take;
+            self.fractional_cycles = 0.0;
+            let mean = resampler.take_mean(cycles_per_point);
+            let point = self.analog_point(raw, mean, resampler.emits_next());
+            if let Some(sample) = resampler.push(point) {
+                samples.push(sample);
+            }
         }
-
-        samples
+        resampler.integrate(raw, cycles);
+        self.fractional_cycles += cycles;
     }
 
-    /// One host sample, taken all the way through the analog stage: the DACs'
-    /// discrete levels are tapped, then faded (for any DAC that has gone
+    /// One mixer point, taken all the way through the analog stage: the DACs'
+    /// current discrete levels (`raw`) are tapped (on the points that complete
+    /// a host sample, so the tap stays one entry per sample), then the levels
+    /// averaged over the point (`mean`) are faded (for any DAC that has gone
     /// unpowered), mixed, and high-passed.
     ///
-    /// The tap is deliberately taken BEFORE the fade and the high-pass. Both
-    /// are continuous, so tapping downstream of them would hand the `.rba`
-    /// per-plane encoder an unbounded value alphabet — its palette is a `u16`,
-    /// and building one over a fade ramp would both overflow it and make
-    /// encoding quadratic.
+    /// The tap is deliberately taken from `raw`, BEFORE the averaging, the
+    /// fade and the high-pass. All three are continuous, so tapping downstream
+    /// of them would hand the `.rba` per-plane encoder an unbounded value
+    /// alphabet — its palette is a `u16`, and building one over a fade ramp
+    /// would both overflow it and make encoding quadratic.
     ///
     /// On an SGB this is still the whole output: the SGB's own effects come
     /// from the SNES APU, which is decoded but not synthesised
     /// ([`crate::sgb::SgbSound`]); adding them later means summing into this
     /// stream here or at a downstream sink, with no change to the channels.
-    fn analog_sample(&mut self) -> (f32, f32) {
-        let raw = self.channel_outputs();
-        if let Some(tap) = &mut self.channel_tap {
+    fn analog_point(&mut self, raw: [f32; 4], mean: [f32; 4], tap_point: bool) -> (f32, f32) {
+        if tap_point && let Some(tap) = &mut self.channel_tap {
             tap.push((raw, self.nr50, self.nr51, self.audio_enabled));
         }
-        let faded = self.analog.fade(raw, self.channel_dacs_on());
+        let mut faded = self.analog.fade(mean, self.channel_dacs_on());
+        for (level, &muted) in faded.iter_mut().zip(&self.muted) {
+            if muted {
+                *level = 0.0;
+            }
+        }
         let agb = self.analog.model().is_agb();
         let (left, right) =
             rustyboi_mix::mix_stereo(faded, self.nr50, self.nr51, self.audio_enabled, agb);
@@ -1095,8 +1187,22 @@ impl Audio {
     }
 }
 
-impl Addressable for Audio {
-    fn read(&self, addr: u16) -> u8 {
+/// Bits of NR10-NR52 that read back as 1 whatever was written, indexed from
+/// NR10: write-only fields (the NRx1 length loads, the NRx3 period lows, the
+/// NRx4 trigger and period highs) and unused bits. The two gaps in the block
+/// (0xFF15, 0xFF1F) read as all ones. Pan Docs, Audio Registers; pinned by
+/// blargg's `01-registers`, so do not widen them.
+const READ_MASK: [u8; (NR52 - NR10 + 1) as usize] = [
+    0x80, 0x3F, 0x00, 0xFF, 0xBF, // NR10-NR14
+    0xFF, 0x3F, 0x00, 0xFF, 0xBF, // 0xFF15, NR21-NR24
+    0x7F, 0xFF, 0x9F, 0xFF, 0xBF, // NR30-NR34
+    0xFF, 0xFF, 0x00, 0x00, 0xBF, // 0xFF1F, NR41-NR44
+    0x00, 0x00, 0x70, // NR50-NR52
+];
+
+impl Audio {
+    /// The bits an NR10-NR52 register holds, before [`READ_MASK`].
+    fn register_bits(&self, addr: u16) -> u8 {
         match addr {
             NR10..=NR14 => self.channel1.read(addr),
             NR21..=NR24 => self.channel2.read(addr),
@@ -1113,11 +1219,20 @@ impl Addressable for Audio {
                 if self.channel3.is_enabled() { value |= 0x04; }
                 if self.channel4.is_enabled() { value |= 0x08; }
 
-                value | 0x70 // Bits 4-6 always read as 1
+                value
             }
+            _ => 0xFF,
+        }
+    }
+}
+
+impl Addressable for Audio {
+    fn read(&self, addr: u16) -> u8 {
+        match addr {
+            NR10..=NR52 => self.register_bits(addr) | READ_MASK[(addr - NR10) as usize],
             WAV_START..=WAV_END => self.channel3.read(addr),
-            // Unused gaps in the APU register block (0xFF15, 0xFF1F,
-            // 0xFF27-0xFF2F) read back as open bus.
+            // Unused tail of the APU register block (0xFF27-0xFF2F) reads
+            // back as open bus.
             _ => 0xFF,
         }
     }
@@ -1330,6 +1445,33 @@ mod tests {
         n
     }
 
+    /// The frame sequencer is clocked by DIV, not a free-running counter: a
+    /// DIV write while the DIV-APU bit is high is a falling edge and steps it
+    /// once extra, while one in the low half only restarts the period.
+    /// Pan Docs: Audio details, DIV-APU — https://gbdev.io/pandocs/Audio_details.html
+    #[test]
+    fn div_write_in_the_high_half_fires_an_extra_div_apu_event() {
+        for (offset, extra) in [(0xA00u32, 1u16), (0x400, 0)] {
+            let mut audio = Audio::new();
+            audio.set_boot_cgb(false);
+            dmg_sync(&mut audio, 0);
+            let mut abs = 0x400;
+            dmg_sync(&mut audio, abs);
+            audio.write(NR52, 0x80);
+            let target = (((audio.cc >> 12) + 1) << 12) + offset;
+            advance_to_cc(&mut audio, &mut abs, target);
+
+            let before = audio.div_divider;
+            audio.sync_cc(abs, 1, abs, false, false, false);
+            assert_eq!(
+                audio.div_divider,
+                before.wrapping_add(extra),
+                "DIV write at period offset {offset:#x}"
+            );
+            assert_eq!(audio.cc & 0xFFF, 0, "the write restarts the DIV-APU period");
+        }
+    }
+
     /// The APU master clock `cc` is kept mod 2^31 and wraps every ~17 emulated
     /// minutes. Channel state anchored on absolute cc (duty countdown anchor,
     /// wave fetch counter, noise ripple anchor, scheduled length expiries) must
@@ -1725,6 +1867,160 @@ mod tests {
         );
     }
 
+    /// Every register reads back what was stored with [`READ_MASK`]'s bits
+    /// forced on: exactly the mask after writing 0x00, and 0x7F plus the mask
+    /// after writing 0x7F. Bit 7 stays clear so no NRx4 write triggers a
+    /// channel and flips NR52's status bits.
+    #[test]
+    fn registers_read_back_through_the_mask_table() {
+        let (mut audio, _) = powered_apu();
+        for addr in NR10..NR52 {
+            let mask = READ_MASK[(addr - NR10) as usize];
+            audio.write(addr, 0x00);
+            assert_eq!(audio.read(addr), mask, "{addr:#06X} after writing 0x00");
+            audio.write(addr, 0x7F);
+            assert_eq!(audio.read(addr), 0x7F | mask, "{addr:#06X} after writing 0x7F");
+        }
+        assert_eq!(audio.read(NR52), 0xF0, "powered, no channel running");
+        for addr in 0xFF27..WAV_START {
+            assert_eq!(audio.read(addr), 0xFF, "{addr:#06X} is unmapped");
+        }
+    }
+
+    /// Pan Docs (Obscure Behavior): "Clearing the sweep negate mode bit in NR10
+    /// after at least one sweep calculation has been made using the negate
+    /// mode since the last trigger causes the channel to be immediately
+    /// disabled." A trigger with a nonzero shift makes that first calculation
+    /// itself; a trigger that calculated without negate leaves nothing to
+    /// undo, so toggling negate on and off again is harmless.
+    #[test]
+    fn clearing_sweep_negate_after_a_negated_calculation_kills_channel_one() {
+        fn ch1_alive_after(nr10_at_trigger: u8, writes: &[u8]) -> bool {
+            let (mut audio, _) = powered_apu();
+            audio.write(NR10, nr10_at_trigger);
+            audio.write(NR12, 0xF0);
+            audio.write(NR13, 0x00);
+            audio.write(NR14, 0x84);
+            assert!(audio.read(NR52) & 0x01 != 0, "CH1 did not start");
+            for &nr10 in writes {
+                audio.write(NR10, nr10);
+            }
+            audio.read(NR52) & 0x01 != 0
+        }
+        assert!(!ch1_alive_after(0x19, &[0x11]), "negate cleared after a negated calculation");
+        assert!(ch1_alive_after(0x19, &[0x19]), "negate left set");
+        assert!(ch1_alive_after(0x11, &[0x19, 0x11]), "no negated calculation since the trigger");
+    }
+
+    /// The debugger readout reports what the registers program: pitch from
+    /// the period (A440 is period 1750 on a square, 1899 on the wave
+    /// channel), duty, volume, and a length counter that counts down while
+    /// the channel plays.
+    #[test]
+    fn channel_states_read_out_pitch_duty_volume_and_length() {
+        let (mut audio, mut abs) = powered_apu();
+        audio.write(NR21, 0x80 | 0x20); // 50% duty, length 64 - 32
+        audio.write(NR22, 0xA0);
+        audio.write(NR23, (1750u16 & 0xFF) as u8);
+        audio.write(NR24, 0xC0 | (1750u16 >> 8) as u8); // trigger, length enabled
+        audio.write(NR30, 0x80);
+        audio.write(NR32, 0x40); // 50%
+        audio.write(NR33, (1899u16 & 0xFF) as u8);
+        audio.write(NR34, 0x80 | (1899u16 >> 8) as u8);
+        audio.write(NR42, 0xF0);
+        audio.write(NR43, 0x21); // divisor code 1, shift 2
+        audio.write(NR44, 0x80);
+
+        let [ch1, ch2, ch3, ch4] = audio.channel_states();
+        assert!(!ch1.enabled && !ch1.dac_on);
+        assert!(ch2.enabled && ch2.dac_on && ch2.length_enabled);
+        assert!((ch2.frequency_hz - 440.0).abs() < 1.0, "{}", ch2.frequency_hz);
+        assert_eq!((ch2.duty, ch2.volume), (Some(2), 10));
+        // 32 loaded, less the extra clock enabling length can cost on trigger.
+        assert!((31..=32).contains(&ch2.length_remaining), "{}", ch2.length_remaining);
+        assert!((ch3.frequency_hz - 440.0).abs() < 1.0, "{}", ch3.frequency_hz);
+        assert_eq!((ch3.duty, ch3.volume, ch3.length_enabled), (None, 2, false));
+        assert_eq!((ch4.frequency_hz, ch4.volume), (65_536.0, 15));
+
+        // A tenth of a second (abs cc run at 4 MiHz) is 25-26 length steps.
+        abs += 4_194_304 / 10;
+        sync(&mut audio, abs);
+        let ch2 = audio.channel_states()[1];
+        assert!(ch2.enabled && (5..=7).contains(&ch2.length_remaining), "{}", ch2.length_remaining);
+    }
+
+    /// Retriggering CH3 on the cc its next wave-RAM fetch lands corrupts wave
+    /// RAM on DMG: the first four bytes are overwritten with the aligned block
+    /// the fetch was reading (just byte 0 while it is inside the first block).
+    /// CGB fixed the glitch, so the same sweep of retrigger timings must leave
+    /// its wave RAM intact (blargg `10-wave trigger while on`).
+    #[test]
+    fn a_wave_retrigger_on_a_fetch_corrupts_dmg_wave_ram_but_not_cgb() {
+        fn corrupts(cgb: bool) -> bool {
+            let sync = |audio: &mut Audio, abs| audio.sync_cc(abs, 0, 0, false, cgb, false);
+            let mut audio = Audio::new();
+            audio.set_boot_cgb(cgb);
+            sync(&mut audio, 0);
+            sync(&mut audio, 0x400);
+            audio.write(NR52, 0x80);
+            let ram: [u8; 16] = std::array::from_fn(|i| 0x11 * i as u8);
+            for (addr, &byte) in (WAV_START..).zip(&ram) {
+                audio.write(addr, byte);
+            }
+            audio.write(NR30, 0x80);
+            audio.write(NR33, 0xF0); // a short period, so the sweep crosses many fetches
+            audio.write(NR34, 0x87);
+            (1..512u64).any(|offset| {
+                let mut audio = audio.clone();
+                sync(&mut audio, 0x400 + offset);
+                audio.write(NR34, 0x87);
+                audio.write(NR30, 0x00); // stop the channel so reads see the raw RAM
+                (WAV_START..).zip(&ram).any(|(addr, &byte)| audio.read(addr) != byte)
+            })
+        }
+        assert!(corrupts(false), "no DMG retrigger timing hit a fetch and corrupted wave RAM");
+        assert!(!corrupts(true), "a CGB retrigger corrupted wave RAM");
+    }
+
+    /// CH4's pitch and timbre come from how often its LFSR steps and how long
+    /// the sequence runs before repeating. Pan Docs: the LFSR is clocked at
+    /// 262144 / (divisor × 2^shift) Hz with divisor code 0 meaning 0.5, so
+    /// codes 0 and 1 step every 4 and 8 APU cc at shift 0; width bit 3 makes
+    /// the sequence repeat after 127 steps instead of 32767; and shifts 14 and
+    /// 15 never clock it at all. Sampling the output once per step, the
+    /// sequence's period pins both the rate (sampling a slower clock would
+    /// double it) and the width.
+    #[test]
+    fn noise_lfsr_period_tracks_its_width_divisor_and_shift() {
+        fn sequence(nr43: u8, stride: u64, len: usize) -> Vec<bool> {
+            let (mut audio, mut abs) = powered_apu();
+            audio.write(NR42, 0xF0); // volume 15, no envelope: DAC on
+            audio.write(NR43, nr43);
+            audio.write(NR44, 0x80);
+            (0..len)
+                .map(|_| {
+                    abs += stride;
+                    sync(&mut audio, abs);
+                    audio.channel4.pcm_nibble() != 0
+                })
+                .collect()
+        }
+        fn period(seq: &[bool]) -> Option<usize> {
+            // Skip the start so the first steps after the trigger do not count.
+            let seq = &seq[64..];
+            (1..seq.len() / 2).find(|&p| seq[p..].iter().zip(seq).all(|(a, b)| a == b))
+        }
+
+        // abs cc tick at twice the APU cc, so divisor code 0 steps every 8.
+        assert_eq!(period(&sequence(0x08, 8, 600)), Some(127), "7-bit, divisor code 0");
+        assert_eq!(period(&sequence(0x09, 16, 600)), Some(127), "7-bit, divisor code 1");
+        assert_eq!(period(&sequence(0x00, 8, 66_000)), Some(32_767), "15-bit, divisor code 0");
+        for shift in [14u8, 15] {
+            let seq = sequence(shift << 4, 8, 600);
+            assert!(seq.iter().all(|&b| b == seq[0]), "shift {shift} clocked the LFSR");
+        }
+    }
+
     /// The high-pass and the DAC-off fade are the analog stage's continuous
     /// state, and both are deliberately absent from the tap: the `.rba`
     /// per-plane encoder builds a `u16` palette of DISTINCT values, so a
@@ -1757,10 +2053,11 @@ mod tests {
         let mut allowed: Vec<f32> = (0..=15).map(super::analog::dac_analog).collect();
         allowed.push(0.0);
 
+        let mut resampler = Resampler::default();
         for step in 0..600 {
             abs += 128;
             sync(&mut audio, abs);
-            audio.generate_samples(64);
+            audio.generate_samples(64, &mut resampler, &mut Vec::new());
             match step {
                 200 => audio.write(NR12, 0x00),
                 300 => audio.write(NR30, 0x00),
@@ -1821,19 +2118,79 @@ mod tests {
     }
 
     /// Exactly `n` host samples (left side) pulled through the real output
-    /// path, advancing the APU clock in step with the sample generator.
+    /// path, advancing the APU clock in step with the sample generator. The
+    /// resampler point-samples, so what comes out is the analog stage's own
+    /// response (the FIR is tested in `output.rs`).
     fn emit_samples(audio: &mut Audio, abs: &mut u64, n: usize) -> Vec<f32> {
+        let mut resampler = Resampler::point_sampling();
         let mut out = Vec::with_capacity(n);
+        let mut samples = Vec::new();
         while out.len() < n {
             *abs += 128;
             sync(audio, *abs);
-            out.extend(audio.generate_samples(128).into_iter().map(|(l, _)| l));
+            samples.clear();
+            audio.generate_samples(128, &mut resampler, &mut samples);
+            out.extend(samples.iter().map(|&(l, _)| l));
         }
         out.truncate(n);
         out
     }
 
-    /// The output high-pass must be wired INTO `analog_sample`, with the right
+    /// A muted channel drops out of the host mix but not out of the tap,
+    /// which keeps recording what the machine itself played.
+    #[test]
+    fn a_muted_channel_leaves_the_mix_but_not_the_tap() {
+        let (mut live, mut live_abs) = dc_biased_apu(analog::AnalogModel::Dmg);
+        assert!(emit_samples(&mut live, &mut live_abs, 64).iter().any(|&l| l != 0.0));
+
+        let (mut muted, mut abs) = dc_biased_apu(analog::AnalogModel::Dmg);
+        muted.set_audible_channels([false, true, true, true]);
+        muted.set_channel_tap(true);
+        assert!(emit_samples(&mut muted, &mut abs, 64).iter().all(|&l| l == 0.0));
+        let tap = muted.drain_channel_tap();
+        assert!(tap.iter().all(|(raw, ..)| raw[0] != 0.0), "CH1's DAC level is still tapped");
+    }
+
+    /// A wave-channel note above the host Nyquist: point sampling folds it
+    /// down into the audible band; the box average and windowed-sinc resampler
+    /// cut that alias by well over 30 dB.
+    ///
+    /// Period $7FE plays the 32-step wave at 65536 / 2 = 32768 Hz; with a
+    /// half-high, half-low pattern that is a square at 32.8 kHz, which 44.1 kHz
+    /// point sampling aliases to 11.3 kHz at nearly full level.
+    #[test]
+    fn a_wave_note_above_nyquist_does_not_alias() {
+        fn rms_of_high_wave(mut resampler: Resampler) -> f32 {
+            let (mut audio, mut abs) = powered_apu();
+            for i in 0..16u16 {
+                audio.write(WAV_START + i, if i < 8 { 0xFF } else { 0x00 });
+            }
+            audio.write(NR30, 0x80);
+            audio.write(NR32, 0x20);
+            audio.write(NR33, 0xFE);
+            audio.write(NR34, 0x87);
+            // Synced every 4 dots, like a stream of 1-cycle instructions:
+            // the mixer only moves when the APU is caught up.
+            let mut out = Vec::new();
+            let mut samples = Vec::new();
+            while out.len() < 4_000 {
+                abs += 4;
+                sync(&mut audio, abs);
+                samples.clear();
+                audio.generate_samples(4, &mut resampler, &mut samples);
+                out.extend(samples.iter().map(|&(l, _)| l));
+            }
+            // Past the high-pass settling, so only the tone's own energy counts.
+            let tail = &out[2_000..];
+            (tail.iter().map(|s| s * s).sum::<f32>() / tail.len() as f32).sqrt()
+        }
+        let aliased = rms_of_high_wave(Resampler::point_sampling());
+        let filtered = rms_of_high_wave(Resampler::default());
+        assert!(aliased > 0.1, "point sampling should alias audibly ({aliased})");
+        assert!(filtered < aliased / 50.0, "the resampler let {filtered} through (point sampling: {aliased})");
+    }
+
+    /// The output high-pass must be wired INTO `analog_point`, with the right
     /// model's charge factor.
     ///
     /// `AnalogStage::high_pass` has its own unit test, but that test exercises
@@ -1891,7 +2248,7 @@ mod tests {
         );
     }
 
-    /// The DAC-off fade must be wired INTO `analog_sample`.
+    /// The DAC-off fade must be wired INTO `analog_point`.
     ///
     /// Same failure mode as the high-pass above, and worse: with
     /// `AnalogStage::fade` unplugged at its call site the entire core suite
@@ -2171,4 +2528,51 @@ mod tests {
             );
         }
     }
+
+    /// NR52 power-off: every register from NR10 to NR51 reads back as bare
+    /// [`READ_MASK`], NR52 drops its channel-status bits, and wave RAM survives.
+    /// While off, writes are ignored except the DMG's NRx1 length loads, and
+    /// powering back on does not restore anything.
+    #[test]
+    fn power_off_clears_the_registers_and_ignores_writes_except_dmg_length_loads() {
+        for cgb in [false, true] {
+            let model = if cgb { "CGB" } else { "DMG" };
+            let mut audio = Audio::new();
+            audio.set_boot_cgb(cgb);
+            audio.sync_cc(0, 0, 0, false, cgb, false);
+            audio.sync_cc(0x400, 0, 0, false, cgb, false);
+            audio.write(NR52, 0x80);
+            audio.write(NR50, 0x77);
+            audio.write(NR51, 0xFF);
+            for addr in WAV_START..=WAV_END {
+                audio.write(addr, 0x5A);
+            }
+            audio.write(NR22, 0xF0);
+            audio.write(NR21, 0x80);
+            audio.write(NR24, 0x87);
+            audio.write(NR30, 0x80);
+            audio.write(NR34, 0x87);
+            assert_eq!(audio.read(NR52), 0xF6, "CH2 and CH3 playing ({model})");
+
+            audio.write(NR52, 0x00);
+            assert_eq!(audio.read(NR52), 0x70, "off, nothing playing ({model})");
+            for addr in NR10..NR52 {
+                let mask = READ_MASK[(addr - NR10) as usize];
+                assert_eq!(audio.read(addr), mask, "{addr:#06X} after power-off ({model})");
+            }
+            assert!((WAV_START..=WAV_END).all(|a| audio.read(a) == 0x5A), "wave RAM kept ({model})");
+
+            audio.write(NR22, 0xF0);
+            audio.write(NR50, 0x77);
+            audio.write(NR21, 0x30);
+            assert_eq!(audio.read(NR22), 0x00, "NR22 write ignored while off ({model})");
+            assert_eq!(audio.read(NR50), 0x00, "NR50 write ignored while off ({model})");
+            let loaded = if cgb { 0 } else { 16 };
+            assert_eq!(audio.channel2.length_counter(), loaded, "NR21 length load while off ({model})");
+
+            audio.write(NR52, 0x80);
+            assert_eq!(audio.read(NR52), 0xF0, "powered back on, nothing playing ({model})");
+            assert_eq!(audio.read(NR51), 0x00, "power-on restores nothing ({model})");
+        }
+    }
 }
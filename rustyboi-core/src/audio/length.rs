@@ -52,6 +52,17 @@ macro_rules! impl_length_unit {
             self.length_counter = value;
         }
 
+        /// Length steps left, for the debugger: counted down to the
+        /// scheduled expiry while it is armed (the stored counter is only
+        /// re-derived on an NRx4 write), the stored counter otherwise.
+        pub(super) fn length_remaining(&self) -> u16 {
+            if self.len_counter == crate::audio::length::COUNTER_DISABLED {
+                self.length_counter as u16
+            } else {
+                (self.len_counter >> 13).saturating_sub(self.len_cc >> 13) as u16
+            }
+        }
+
         /// Length-counter expiry: disarms the schedule and tears the channel
         /// down through the per-channel hook.
         pub(super) fn length_event(&mut self) {
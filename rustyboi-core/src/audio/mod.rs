@@ -8,7 +8,7 @@ mod square;
 mod wave;
 
 pub(crate) use analog::AnalogModel;
-pub use controller::{Audio, ChannelSample, HOST_SAMPLE_RATE, NR52};
+pub use controller::{Audio, ChannelSample, ChannelState, HOST_SAMPLE_RATE, NR52};
 
 /// The stereo mixer and DAC transfer function, which this crate shares verbatim
 /// with the `.rba` replay decoder — see [`rustyboi_mix`] for why they live in a
@@ -21,3 +21,4 @@ pub(crate) use controller::{
     NR43, NR44, NR50, NR51, WAV_END, WAV_START,
 };
 pub use output::AudioOutput;
+pub(crate) use output::Resampler;
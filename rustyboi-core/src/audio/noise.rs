@@ -836,6 +836,26 @@ impl Noise {
         self.enabled
     }
 
+    /// Debugger readout (see [`ChannelState`](super::ChannelState)). The
+    /// frequency is the LFSR clock, 262144 / (divisor × 2^shift) with divisor
+    /// code 0 as 0.5; shifts 14 and 15 never clock it.
+    pub(super) fn state(&self) -> super::ChannelState {
+        let shift = self.nr43 >> 4;
+        let divisor = match self.nr43 & 0x07 {
+            0 => 0.5,
+            d => f32::from(d),
+        };
+        super::ChannelState {
+            enabled: self.enabled,
+            dac_on: self.dac_on(),
+            frequency_hz: if shift < 14 { 262_144.0 / (divisor * f32::from(1u16 << shift)) } else { 0.0 },
+            volume: self.volume & 0x0F,
+            duty: None,
+            length_remaining: self.length_remaining(),
+            length_enabled: self.nr4() & 0x40 != 0,
+        }
+    }
+
     /// CGB PCM34 high nibble for the noise channel: the latched LFSR output
     /// bit times the envelope volume while the channel is active.
     pub(super) fn pcm_nibble(&self) -> u8 {
@@ -903,15 +923,15 @@ impl Noise {
 }
 
 impl Addressable for Noise {
+    /// The register as last written; the controller ORs in the read-back mask.
     fn read(&self, addr: u16) -> u8 {
         match addr {
             NR41..=NR44 => {
                 match addr {
-                    NR41 => 0xFF, // Write-only
+                    NR41 => self.nr41,
                     NR42 => self.nr42,
                     NR43 => self.nr43,
-                    NR44 => self.nr44 | 0xBF, // Only bit 6 readable
-                    _ => 0xFF,
+                    _ => self.nr44,
                 }
             }
             _ => panic!("Invalid address for Noise: {:#X}", addr)
@@ -1,4 +1,183 @@
+//! The host-facing end of the APU: the sink trait every backend implements, and
+//! the band-limiting resampler between the mixer and that sink.
+//!
+//! The mixer is a sum of hard-edged square, wave, and noise steps, so it has
+//! energy far above any host rate. Point-sampling it once per host sample (what
+//! the core used to do) folds everything above the host Nyquist back into the
+//! audible band; a high wave-channel note comes out with a ghost tone under it.
+//! Instead each channel's level is averaged over [`OVERSAMPLE`] intervals per
+//! host sample (a box filter, which already knocks the harmonics near the
+//! point rate down hard), and those points go through a Blackman-windowed sinc
+//! low-pass that decimates them back down, so what reaches the host is
+//! band-limited to just under its Nyquist.
+
+use serde::{Deserialize, Serialize};
+
 pub trait AudioOutput {
     fn start(&mut self) -> Result<(), Box<dyn std::error::Error>>;
     fn add_samples(&mut self, samples: &[(f32, f32)]);
 }
+
+/// Mixer points taken per host sample.
+pub(crate) const OVERSAMPLE: usize = 4;
+
+/// FIR length in mixer points (32 host samples of support, a 0.36 ms group
+/// delay at 44.1 kHz).
+const TAPS: usize = 128;
+
+/// Low-pass corner as a fraction of the host rate. Below Nyquist (0.5) by
+/// about half the window's transition band, so the stopband starts near it.
+const CUTOFF: f32 = 0.42;
+
+/// The windowed-sinc decimator: a ring of the last [`TAPS`] mixer points, one
+/// host sample out per [`OVERSAMPLE`] points in.
+///
+/// It also holds the box integrator in front of the FIR: the channel levels
+/// weighted by the cycles they were held for since the last point.
+///
+/// It belongs to the host's output, not the machine, so savestates leave it
+/// out and a restore starts a fresh one; `GB::carry_output_from` moves the
+/// live ring, integrator and phase across a restore over a running machine,
+/// so the filter does not restart from silence at the seam. The derives only
+/// serve the upgraders of older formats, which did store it. The kernel is a
+/// pure function of the constants above, so it is never stored.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Resampler {
+    #[serde(skip, default = "kernel")]
+    kernel: Vec<f32>,
+    history: Vec<(f32, f32)>,
+    head: usize,
+    phase: usize,
+    integral: [f32; 4],
+}
+
+/// The FIR coefficients, normalized to unity DC gain. Uses `libm` rather than
+/// the platform's `sin`/`cos` so the output is bit-identical on every target.
+fn kernel() -> Vec<f32> {
+    use std::f32::consts::PI;
+    let fc = CUTOFF / OVERSAMPLE as f32;
+    let mid = (TAPS - 1) as f32 / 2.0;
+    let mut k: Vec<f32> = (0..TAPS)
+        .map(|i| {
+            let x = i as f32 - mid;
+            let sinc = if x == 0.0 { 2.0 * fc } else { libm::sinf(2.0 * PI * fc * x) / (PI * x) };
+            let t = 2.0 * PI * i as f32 / (TAPS - 1) as f32;
+            let window = 0.42 - 0.5 * libm::cosf(t) + 0.08 * libm::cosf(2.0 * t);
+            sinc * window
+        })
+        .collect();
+    let sum: f32 = k.iter().sum();
+    for c in &mut k {
+        *c /= sum;
+    }
+    k
+}
+
+impl Default for Resampler {
+    fn default() -> Self {
+        Resampler { kernel: kernel(), history: vec![(0.0, 0.0); TAPS], head: 0, phase: 0, integral: [0.0; 4] }
+    }
+}
+
+impl Resampler {
+    /// A "resampler" that just picks the first point of each group, i.e. point
+    /// sampling at the host rate (of the box-averaged points). Lets the analog stage's tests see
+    /// its own response without the FIR's delay and smoothing on top.
+    #[cfg(test)]
+    pub(crate) fn point_sampling() -> Self {
+        let mut kernel = vec![0.0; TAPS];
+        kernel[TAPS - OVERSAMPLE] = 1.0;
+        Resampler { kernel, ..Resampler::default() }
+    }
+
+    /// Hold the four channel levels for `cycles` CPU cycles.
+    pub(crate) fn integrate(&mut self, levels: [f32; 4], cycles: f32) {
+        for (acc, level) in self.integral.iter_mut().zip(levels) {
+            *acc += level * cycles;
+        }
+    }
+
+    /// The mean channel levels over the `cycles` just integrated, clearing the
+    /// integrator for the next point.
+    pub(crate) fn take_mean(&mut self, cycles: f32) -> [f32; 4] {
+        std::mem::take(&mut self.integral).map(|acc| acc / cycles)
+    }
+
+    /// Whether the next [`push`](Self::push) completes a host sample.
+    pub(crate) fn emits_next(&self) -> bool {
+        self.phase == OVERSAMPLE - 1
+    }
+
+    /// Feed one mixer point; returns a host sample every [`OVERSAMPLE`]th call.
+    pub(crate) fn push(&mut self, point: (f32, f32)) -> Option<(f32, f32)> {
+        self.history[self.head] = point;
+        self.head = (self.head + 1) % TAPS;
+        self.phase += 1;
+        if self.phase < OVERSAMPLE {
+            return None;
+        }
+        self.phase = 0;
+        // `head` now indexes the oldest point; the kernel is symmetric, so the
+        // walk direction does not matter.
+        let (older, newer) = self.history.split_at(self.head);
+        let (mut l, mut r) = (0.0, 0.0);
+        for (&c, &(pl, pr)) in self.kernel.iter().zip(newer.iter().chain(older)) {
+            l += c * pl;
+            r += c * pr;
+        }
+        Some((l, r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run a unit sine at `freq` (a fraction of the host rate) through the
+    /// resampler and return the steady-state output amplitude (RMS-derived,
+    /// so it does not depend on where the host samples land on the wave).
+    fn amplitude_for(freq: f32) -> f32 {
+        let mut r = Resampler::default();
+        let (mut sum, mut count) = (0.0f32, 0);
+        for n in 0..OVERSAMPLE * 4_000 {
+            let x = libm::sinf(2.0 * std::f32::consts::PI * freq * n as f32 / OVERSAMPLE as f32);
+            if let Some((l, _)) = r.push((x, x))
+                && n > OVERSAMPLE * 1_000
+            {
+                sum += l * l;
+                count += 1;
+            }
+        }
+        (2.0 * sum / count as f32).sqrt()
+    }
+
+    #[test]
+    fn emits_one_sample_per_oversample_points() {
+        let mut r = Resampler::default();
+        let out = (0..OVERSAMPLE * 10).filter_map(|_| r.push((1.0, 1.0))).count();
+        assert_eq!(out, 10);
+    }
+
+    #[test]
+    fn passes_dc_at_unity_gain() {
+        let mut r = Resampler::default();
+        let mut last = (0.0, 0.0);
+        for _ in 0..TAPS * 2 {
+            if let Some(s) = r.push((0.5, -0.25)) {
+                last = s;
+            }
+        }
+        assert!((last.0 - 0.5).abs() < 1e-5 && (last.1 + 0.25).abs() < 1e-5, "{last:?}");
+    }
+
+    /// A tone well inside the band comes through; one above the host Nyquist
+    /// (which naive point sampling would fold down to an audible alias) is
+    /// stopped.
+    #[test]
+    fn passes_the_band_and_rejects_what_would_alias() {
+        let passband = amplitude_for(0.1);
+        assert!((passband - 1.0).abs() < 0.01, "0.1 fs came through at {passband}");
+        let alias = amplitude_for(0.7);
+        assert!(alias < 0.001, "0.7 fs (aliasing to 0.3 fs) leaked through at {alias}");
+    }
+}
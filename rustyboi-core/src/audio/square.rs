@@ -973,6 +973,20 @@ impl SquareWave {
         self.enabled
     }
 
+    /// Debugger readout (see [`ChannelState`](super::ChannelState)). The tone
+    /// is one duty cycle per 8 period ticks at 1 MHz: 131072 / (2048 - period).
+    pub(super) fn state(&self) -> super::ChannelState {
+        super::ChannelState {
+            enabled: self.enabled,
+            dac_on: self.dac_on(),
+            frequency_hz: 131_072.0 / (2048 - u32::from(self.freq())) as f32,
+            volume: self.volume & 0x0F,
+            duty: Some(self.duty()),
+            length_remaining: self.length_remaining(),
+            length_enabled: self.nr4() & 0x40 != 0,
+        }
+    }
+
     /// The hidden length counter (blocks-remaining before length expiry). Test
     /// probe for the NR52 power-cycle length state.
     #[cfg(test)]
@@ -1062,15 +1076,10 @@ impl SquareWave {
 }
 
 impl Addressable for SquareWave {
+    /// The register as last written; the controller ORs in the read-back
+    /// mask (see `READ_MASK` in controller.rs).
     fn read(&self, addr: u16) -> u8 {
-        // Read-back masks, identical for both channels: NRx0 reads with bit 7
-        // set, NRx1's length load is write-only (only the duty bits read
-        // back), NRx2 reads raw, NRx3 is entirely write-only, and NRx4 reads
-        // back only its length-enable bit. These are pinned by the suites; do
-        // not widen them.
-        const READ_MASK: [u8; 5] = [0x80, 0x3F, 0x00, 0xFF, 0xBF];
-        let i = self.reg_index(addr);
-        self.regs[i] | READ_MASK[i]
+        self.regs[self.reg_index(addr)]
     }
 
     fn write(&mut self, addr: u16, value: u8) {
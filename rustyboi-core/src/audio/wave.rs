@@ -330,6 +330,20 @@ impl Wave {
         self.enabled
     }
 
+    /// Debugger readout (see [`ChannelState`](super::ChannelState)). The tone
+    /// is one pass over the 32 samples: 65536 / (2048 - period).
+    pub(super) fn state(&self) -> super::ChannelState {
+        super::ChannelState {
+            enabled: self.enabled,
+            dac_on: self.dac_on(),
+            frequency_hz: 65_536.0 / to_period(self.nr33, self.nr34) as f32,
+            volume: self.get_output_level(),
+            duty: None,
+            length_remaining: self.length_remaining(),
+            length_enabled: self.nr4() & 0x40 != 0,
+        }
+    }
+
     /// CGB PCM34 low nibble for the wave channel: while the channel master is
     /// on, the selected nibble (`pos` even -> high nibble, odd -> low nibble) is
     /// right-shifted by the output-level attenuation, where the shift is
@@ -393,15 +407,16 @@ impl Wave {
 }
 
 impl Addressable for Wave {
+    /// NR30-NR34 as last written (the controller ORs in the read-back mask);
+    /// wave RAM as the CPU sees it, playback quirks included.
     fn read(&self, addr: u16) -> u8 {
         match addr {
             NR30..=NR34 => match addr {
-                NR30 => self.nr30 | 0x7F,
-                NR31 => 0xFF,
-                NR32 => self.nr32 | 0x9F,
-                NR33 => 0xFF,
-                NR34 => self.nr34 | 0xBF,
-                _ => 0xFF,
+                NR30 => self.nr30,
+                NR31 => self.nr31,
+                NR32 => self.nr32,
+                NR33 => self.nr33,
+                _ => self.nr34,
             },
             WAV_START..=WAV_END => self.read_wave_ram(addr),
             _ => panic!("Invalid address for Wave: {:#X}", addr),
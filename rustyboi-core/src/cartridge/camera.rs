@@ -137,13 +137,9 @@ impl Cartridge {
         {
             self.ram_data[CAM_RAM_IMAGE_OFFSET..CAM_RAM_IMAGE_OFFSET + CAM_TILE_BYTES]
                 .copy_from_slice(&pending);
-            // Stream the block to the battery .sav (single bulk write, not
-            // 3584 per-byte writes).
+            // Stage the block for the battery .sav in one go.
             if let Some(file) = &mut self.save_file {
-                let _ = file
-                    .seek(SeekFrom::Start(CAM_RAM_IMAGE_OFFSET as u64))
-                    .and_then(|_| file.write_all(&pending))
-                    .and_then(|_| file.flush());
+                file.write(CAM_RAM_IMAGE_OFFSET, &pending);
             }
         }
     }
@@ -36,6 +36,13 @@ pub(super) const MBC1: u8 = 0x01;
 pub(super) const MBC1_RAM: u8 = 0x02;
 pub(super) const MBC1_RAM_BATTERY: u8 = 0x03;
 
+// MMM01: the multicart board of the Momotarou Collection compilations. Boots a
+// menu from the last 32 KiB, then locks down into an MBC1 confined to the
+// chosen game's slice of ROM/RAM (see `mmm01.rs`).
+pub(super) const MMM01: u8 = 0x0B;
+pub(super) const MMM01_RAM: u8 = 0x0C;
+pub(super) const MMM01_RAM_BATTERY: u8 = 0x0D;
+
 // Cartridge types for MBC2
 pub(super) const MBC2: u8 = 0x05;
 pub(super) const MBC2_BATTERY: u8 = 0x06;
@@ -105,8 +112,7 @@ pub(super) const POCKET_CAMERA: u8 = 0xFC;
 pub(super) const TAMA5: u8 = 0xFD;
 
 /// Whether `$0147` holds a value the Pan Docs cartridge-type table defines,
-/// implemented or not (MMM01 $0B-$0D is listed here even though rustyboi has no
-/// board for it — the byte still names a real board, so it is not evidence of a
+/// implemented or not (the byte names a real board, so it is not evidence of a
 /// garbage header).
 ///
 /// Anything outside this set is not a cartridge type at all: the header was
@@ -133,7 +139,7 @@ pub(super) fn is_documented_type(cartridge_type: u8) -> bool {
 pub(super) fn header_type_has_external_ram(cartridge_type: u8) -> bool {
     matches!(cartridge_type,
         MBC1_RAM | MBC1_RAM_BATTERY | ROM_RAM | ROM_RAM_BATTERY
-        | 0x0C | 0x0D // MMM01+RAM, MMM01+RAM+BATTERY
+        | MMM01_RAM | MMM01_RAM_BATTERY
         | MBC3_TIMER_RAM_BATTERY | MBC3_RAM | MBC3_RAM_BATTERY
         | MBC5_RAM | MBC5_RAM_BATTERY | MBC5_RUMBLE_RAM | MBC5_RUMBLE_RAM_BATTERY
         // MBC6's suffix-less type byte still names a board with a 32 KiB SRAM
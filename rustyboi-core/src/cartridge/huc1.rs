@@ -37,8 +37,8 @@ pub(super) struct HuC1State {
     /// RAM bank register, "at least 2 bits" (Pan Docs); stored raw and reduced
     /// modulo the cart's bank count like HuC-3.
     pub(super) ram_bank: u8,
-    /// IR LED output latch (bit 0 of writes in IR mode). No IR transport is
-    /// modeled: reads always see "no light" (0xC0), the documented idle.
+    /// IR LED output latch (bit 0 of writes in IR mode). Published to the IR
+    /// partner attached to Mmio; with none, reads see "no light" (0xC0).
     pub(super) ir_led: bool,
 }
 
@@ -15,6 +15,7 @@ use super::huc3::HuC3State;
 use super::mbc5::Mbc5State;
 use super::mbc6::Mbc6State;
 use super::mbc7::Mbc7State;
+use super::mmm01::Mmm01State;
 use super::tama5::Tama5State;
 use super::unlicensed::{M161State, NtState, RocketState, SachenState};
 use super::UnlMapper;
@@ -23,12 +24,12 @@ use super::{
     HUC1_RAM_BATTERY, HUC3, MBC1, MBC1_RAM, MBC1_RAM_BATTERY, MBC2, MBC2_BATTERY, MBC3, MBC3_RAM,
     MBC3_RAM_BATTERY, MBC3_TIMER_BATTERY, MBC3_TIMER_RAM_BATTERY, MBC5, MBC5_RAM, MBC5_RAM_BATTERY,
     MBC5_RUMBLE, MBC5_RUMBLE_RAM, MBC5_RUMBLE_RAM_BATTERY, MBC6, MBC7_SENSOR_RUMBLE_RAM_BATTERY,
-    POCKET_CAMERA, ROM_ONLY, ROM_RAM, ROM_RAM_BATTERY, TAMA5,
+    MMM01, MMM01_RAM, MMM01_RAM_BATTERY, POCKET_CAMERA, ROM_ONLY, ROM_RAM, ROM_RAM_BATTERY, TAMA5,
 };
 use serde::{Deserialize, Serialize};
 use super::{
     camera::Camera, huc1::HuC1, huc3::HuC3, mbc1::Mbc1, mbc2::Mbc2, mbc3::Mbc3, mbc5::Mbc5,
-    mbc6::Mbc6, mbc7::Mbc7, nombc::NoMbc, tama5::Tama5,
+    mbc6::Mbc6, mbc7::Mbc7, mmm01::Mmm01, nombc::NoMbc, tama5::Tama5,
     unlicensed::{
         Bbd, Ggb81, Hitek, LiCheng, M161, NtOld, Rocket, Sachen, Sintax, Vf001, WisdomTree,
     },
@@ -140,6 +141,7 @@ pub(super) enum Mapper {
     // APPEND-ONLY below this line: the variant index is the bincode savestate
     // discriminant, so inserting anywhere above re-numbers every board.
     Mbc6(Mbc6),
+    Mmm01(Mmm01),
 }
 
 impl Banking for Mapper {
@@ -346,6 +348,11 @@ impl Mapper {
             HUC3 => Mapper::HuC3(HuC3 { state: HuC3State::default() }),
             TAMA5 => Mapper::Tama5(Tama5 { state: Tama5State::default() }),
             MBC6 => Mapper::Mbc6(Mbc6 { state: Mbc6State::default() }),
+            MMM01 | MMM01_RAM | MMM01_RAM_BATTERY => Mapper::Mmm01(Mmm01 {
+                ram_enabled: false,
+                has_ram: cartridge_type != MMM01,
+                state: Mmm01State::default(),
+            }),
             POCKET_CAMERA => Mapper::Camera(Camera { ram_enabled: false, state: CameraState::default() }),
             ROM_RAM => Mapper::NoMbc(NoMbc { battery: false }),
             ROM_RAM_BATTERY => Mapper::NoMbc(NoMbc { battery: true }),
@@ -385,6 +392,7 @@ impl Mapper {
             Mapper::Hitek(m) => f(m),
             Mapper::Tama5(m) => f(m),
             Mapper::Mbc6(m) => f(m),
+            Mapper::Mmm01(m) => f(m),
         }
     }
 }
@@ -29,17 +29,15 @@ impl Banking for Mbc2 {
 // --- container-side board logic -----------------------------------------
 
 impl Cartridge {
-    /// Write a byte to MBC2 RAM and save file simultaneously (if battery-backed)
+    /// Write a byte to MBC2 RAM and stage it for the save file (if battery-backed)
     pub(super) fn write_mbc2_ram_byte(&mut self, offset: usize, value: u8) -> Result<(), io::Error> {
         if !self.mbc2_ram.is_empty() {
             // Write to MBC2 RAM buffer (offset is already wrapped by caller)
             self.mbc2_ram[offset] = value & 0x0F; // Only 4 bits valid
 
-            // Also write to save file if we have one open
-            if let Some(ref mut file) = self.save_file {
-                file.seek(SeekFrom::Start(offset as u64))?;
-                file.write_all(&[self.mbc2_ram[offset]])?;
-                file.flush()?; // Ensure immediate write
+            // Mark the page dirty; `flush_save` writes it out
+            if let Some(file) = self.save_file.as_mut() {
+                file.write(offset, &[self.mbc2_ram[offset]]);
             }
         }
         Ok(())
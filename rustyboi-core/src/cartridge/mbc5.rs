@@ -25,7 +25,10 @@ impl Banking for Mbc5 {
         0
     }
     fn ram_bank(&self, g: Geom) -> usize {
-        (self.regs.ram_bank & 0x0F) as usize % g.ram_banks.max(1)
+        // Rumble boards route bit 3 to the motor, so only 3 bits (64 KiB)
+        // address RAM; plain MBC5 decodes all 4 (16 x 8 KiB = 128 KiB).
+        let mask = if self.rumble { 0x07 } else { 0x0F };
+        (self.regs.ram_bank & mask) as usize % g.ram_banks.max(1)
     }
 }
 
@@ -35,7 +35,7 @@ impl Cartridge {
     }
     pub(super) fn mbc7_eeprom_set_word(&mut self, addr: usize, word: u16) {
         let i = (addr & 0x7F) * 2;
-        // write_ram_byte streams to the battery save file as well.
+        // write_ram_byte stages the battery save file as well.
         let _ = self.write_ram_byte(i, (word & 0xFF) as u8);
         let _ = self.write_ram_byte(i + 1, (word >> 8) as u8);
     }
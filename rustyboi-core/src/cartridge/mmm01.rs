@@ -0,0 +1,203 @@
+//! MMM01 board: register state + address->bank math, plus the container-side
+//! header location (the compilation menu lives in the LAST 32 KiB of ROM).
+//!
+//! Pan Docs "MMM01": the board powers up "unmapped", serving the final 32 KiB
+//! of the chip at $0000-$7FFF so the menu (and its header, which the boot ROM
+//! checks) runs first. The menu then programs the outer bank bits and the
+//! write masks that carve out one game's slice of ROM/RAM, and sets the MAP bit
+//! ($0000 bit 6). From then on the board behaves as an MBC1 confined to that
+//! slice: every outer bit and mask is locked until the next power cycle, so a
+//! sub-game can never bank its way back into the menu or a sibling game.
+
+use super::*;
+use super::mapper::{Banking, Geom};
+use serde::{Deserialize, Serialize};
+
+/// Size of the menu window the board maps while unmapped (two 16 KiB banks).
+const MENU_SIZE: usize = 0x8000;
+
+// --- board struct + banking ---------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct Mmm01 {
+    pub ram_enabled: bool,
+    pub has_ram: bool,
+    pub state: Mmm01State,
+}
+
+impl Mmm01 {
+    /// The bank bits RA19-RA20 as the current mode wires them: the $2000
+    /// bits 5-6 normally, or the $4000 RAM-bank bits in multiplex mode (which
+    /// is how a large-ROM MBC1 game sees BANK2 drive ROM bits 5-6).
+    fn rom_bank_mid(&self) -> usize {
+        let s = &self.state;
+        if s.multiplex { s.ram_bank_low as usize } else { s.rom_bank_mid as usize }
+    }
+
+    /// The $2000 bits 0-4 with MBC1's zero->one remap, applied only to the
+    /// bits the menu left writable (a locked bit is part of the game base).
+    fn rom_bank_low(&self) -> usize {
+        let s = &self.state;
+        let writable = !(s.rom_bank_mask << 1) & 0x1F;
+        if s.rom_bank_low & writable == 0 {
+            (s.rom_bank_low | 1) as usize
+        } else {
+            s.rom_bank_low as usize
+        }
+    }
+
+    /// A $0000-$7FFF register write. Everything the menu uses to pick a game
+    /// (outer bank bits, masks, mode bits) is frozen once MAP is set; the
+    /// MBC1-compatible fields stay live, minus any bits the masks protect (the
+    /// masks only bite once mapped, so the menu can still set the game base).
+    pub(super) fn write(&mut self, addr: u16, value: u8) {
+        let s = &mut self.state;
+        match addr {
+            0x0000..=0x1FFF => {
+                self.ram_enabled = (value & 0x0F) == 0x0A;
+                if !s.locked {
+                    s.ram_bank_mask = (value >> 4) & 0x03;
+                    s.locked = value & 0x40 != 0;
+                }
+            }
+            0x2000..=0x3FFF => {
+                if !s.locked {
+                    s.rom_bank_mid = (value >> 5) & 0x03;
+                }
+                let protected = if s.locked { (s.rom_bank_mask << 1) & 0x1E } else { 0 };
+                s.rom_bank_low = (s.rom_bank_low & protected) | (value & !protected & 0x1F);
+            }
+            0x4000..=0x5FFF => {
+                let protected = if s.locked { s.ram_bank_mask } else { 0 };
+                s.ram_bank_low = (s.ram_bank_low & protected) | (value & !protected & 0x03);
+                if !s.locked {
+                    s.ram_bank_high = (value >> 2) & 0x03;
+                    s.rom_bank_high = (value >> 4) & 0x03;
+                    s.mbc1_mode_locked = value & 0x40 != 0;
+                }
+            }
+            0x6000..=0x7FFF => {
+                if !s.mbc1_mode_locked {
+                    s.mbc1_mode = value & 0x01 != 0;
+                }
+                if !s.locked {
+                    s.rom_bank_mask = (value >> 2) & 0x0F;
+                    s.multiplex = value & 0x40 != 0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Banking for Mmm01 {
+    fn rom_bankn(&self, g: Geom) -> usize {
+        if !self.state.locked {
+            return 0x1FF % g.rom_banks;
+        }
+        let bank =
+            self.rom_bank_low() | (self.rom_bank_mid() << 5) | ((self.state.rom_bank_high as usize) << 7);
+        bank % g.rom_banks
+    }
+    fn rom_bank0(&self, g: Geom) -> usize {
+        let s = &self.state;
+        if !s.locked {
+            return 0x1FE % g.rom_banks;
+        }
+        // The masked (menu-owned) low bits stay; the game-writable ones read
+        // as 0, so $0000-$3FFF is the first bank of the game's slice. In
+        // multiplex mode the BANK2 bits only reach this window in MBC1 mode 1.
+        let low = (s.rom_bank_low & (s.rom_bank_mask << 1)) as usize;
+        let mid = if s.multiplex && !s.mbc1_mode { 0 } else { self.rom_bank_mid() };
+        (low | (mid << 5) | ((s.rom_bank_high as usize) << 7)) % g.rom_banks
+    }
+    fn ram_bank(&self, g: Geom) -> usize {
+        let s = &self.state;
+        if !s.locked {
+            return 0;
+        }
+        // As on MBC1, the game's own RAM-bank bits only apply in mode 1; the
+        // menu-owned (masked) bits always select the game's RAM slice.
+        let low = match (s.multiplex, s.mbc1_mode) {
+            (true, true) => s.rom_bank_mid,
+            (true, false) => 0,
+            (false, true) => s.ram_bank_low,
+            (false, false) => s.ram_bank_low & s.ram_bank_mask,
+        };
+        ((low as usize) | ((s.ram_bank_high as usize) << 2)) % g.ram_banks.max(1)
+    }
+}
+
+// --- state ---------------------------------------------------------------
+
+/// MMM01 registers (Pan Docs "MMM01"). Every field is 0 at power-on, which is
+/// the unmapped state: the menu window is served until `locked` is set.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub(super) struct Mmm01State {
+    /// MAP ($0000 bit 6): set once by the menu; freezes every outer/mask bit.
+    pub(super) locked: bool,
+    /// RA14-RA18 ($2000 bits 0-4), the MBC1-visible ROM bank.
+    pub(super) rom_bank_low: u8,
+    /// RA19-RA20 ($2000 bits 5-6, pre-lock only).
+    pub(super) rom_bank_mid: u8,
+    /// RA21-RA22 ($4000 bits 4-5, pre-lock only).
+    pub(super) rom_bank_high: u8,
+    /// RAM bank bits 0-1 ($4000 bits 0-1), the MBC1-visible RAM bank.
+    pub(super) ram_bank_low: u8,
+    /// RAM bank bits 2-3 ($4000 bits 2-3, pre-lock only).
+    pub(super) ram_bank_high: u8,
+    /// Write-protect for `ram_bank_low` ($0000 bits 4-5, pre-lock only).
+    pub(super) ram_bank_mask: u8,
+    /// Write-protect for `rom_bank_low` bits 1-4 ($6000 bits 2-5, pre-lock only).
+    pub(super) rom_bank_mask: u8,
+    /// MBC1 banking mode ($6000 bit 0).
+    pub(super) mbc1_mode: bool,
+    /// Freezes `mbc1_mode` ($4000 bit 6, pre-lock only), for games that were
+    /// never meant to switch modes.
+    pub(super) mbc1_mode_locked: bool,
+    /// Multiplex ($6000 bit 6, pre-lock only): swap the roles of RA19-RA20 and
+    /// the low RAM-bank bits, as a large-ROM MBC1 game expects.
+    pub(super) multiplex: bool,
+}
+
+// --- container-side board logic -----------------------------------------
+
+impl Cartridge {
+    /// Offset of the header that describes an MMM01 cart, or `None` when the
+    /// image is not one. The board boots the last 32 KiB, so that is where the
+    /// menu's header (type $0B-$0D, checked by the boot ROM) lives; the header
+    /// at $0100 of the file belongs to the first game. The checksum is
+    /// required too so a stray $0B-$0D byte in some other game's last bank
+    /// cannot trigger the board.
+    pub(super) fn mmm01_header_base(data: &[u8]) -> Option<usize> {
+        let base = data.len().checked_sub(MENU_SIZE)?;
+        Self::is_mmm01_menu_header(&data[base..]).then_some(base)
+    }
+
+    /// Whether the 32 KiB window starting at `menu` carries an MMM01 menu
+    /// header: type $0B-$0D and a header checksum the boot ROM would accept.
+    fn is_mmm01_menu_header(menu: &[u8]) -> bool {
+        let header = &menu[0x0100..0x0150];
+        let sum = header[0x34..0x4D].iter().fold(0u8, |a, &b| a.wrapping_sub(b).wrapping_sub(1));
+        (MMM01..=MMM01_RAM_BATTERY).contains(&menu[CARTRIDGE_TYPE_OFFSET]) && sum == header[0x4D]
+    }
+
+    /// Re-order a menu-first MMM01 dump into the physical layout, or `None`
+    /// when the data is not one. Some dumps move the menu's 32 KiB to the
+    /// front so the file "looks like" an ordinary cart; the board itself maps
+    /// the END of the chip at power-on, so rotate the menu back there.
+    pub(super) fn reorder_menu_first_mmm01(data: &[u8]) -> Option<Vec<u8>> {
+        if data.len() <= MENU_SIZE || !data.len().is_multiple_of(MENU_SIZE) {
+            return None;
+        }
+        if !Self::is_mmm01_menu_header(data) {
+            return None;
+        }
+        if Self::mmm01_header_base(data).is_some() {
+            return None; // already in hardware order
+        }
+        let mut out = data.to_vec();
+        out.rotate_left(MENU_SIZE);
+        Some(out)
+    }
+}
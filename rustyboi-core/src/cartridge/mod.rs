@@ -13,8 +13,8 @@ use std::cell::Cell;
 use std::fs;
 use std::fs::{File, OpenOptions};
 use std::io;
-use std::io::{Read, Seek, SeekFrom, Write};
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use zip::ZipArchive;
 
@@ -23,6 +23,7 @@ mod mapper;
 pub use self::header::{find_logo_in_boot_rom, CgbSupport, Destination};
 use self::header::*;
 mod rtc;
+mod save_file;
 mod mbc7;
 mod huc3;
 mod camera;
@@ -32,11 +33,26 @@ mod mbc2;
 mod mbc3;
 mod mbc5;
 mod mbc6;
+mod mmm01;
 mod huc1;
 mod nombc;
 mod tama5;
 use self::mapper::*;
 use self::rtc::{HuC3Rtc, Mbc3Rtc};
+use self::save_file::SaveFile;
+
+/// Member names an archive's ROM is preferred by (matched lowercase, as
+/// suffixes); with none present the largest member is taken.
+const ROM_EXTENSIONS: [&str; 3] = [".gb", ".gbc", ".sgb"];
+/// 7-Zip signature header.
+const SEVEN_ZIP_MAGIC: &[u8] = b"7z\xBC\xAF\x27\x1C";
+/// gzip ID1/ID2 plus the deflate method byte (the only one gzip defines).
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B, 0x08];
+/// Largest ROM an archive may unpack to. Licensed carts top out at 8 MiB;
+/// the headroom is for pirate multicart dumps. Anything bigger is a
+/// decompression bomb or not a Game Boy ROM.
+const MAX_ROM_SIZE: usize = 64 * 1024 * 1024;
+
 // ---------------------------------------------------------------------------
 // Unlicensed / bootleg mappers. These boards spoof the header type byte
 // ($00/$01, or use out-of-spec values like $97/$99/$EA), so they are detected
@@ -1307,6 +1323,8 @@ pub(crate) enum CartridgeType {
     /// chip (implies RAM+BATTERY). Only "Net de Get - Minigame @ 100" uses it.
     MBC6,
     MBC7,
+    /// MMM01 ($0B-$0D): the Momotarou Collection multicart board.
+    MMM01 { ram: bool, battery: bool },
     HuC1,
     HuC3,
     PocketCamera,
@@ -1322,6 +1340,46 @@ pub(crate) enum CartridgeType {
     M161,
 }
 
+/// How [`Cartridge::load_with`] picks the ROM out of its file and where it
+/// keeps the battery sidecars.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LoadOptions<'a> {
+    /// The member of a zip/7z bundle to load (see
+    /// [`Cartridge::archive_rom_entries`]); `None` takes the default pick.
+    pub archive_entry: Option<&'a str>,
+    /// Directory for the `.sav`/`.rtc` sidecars, one subfolder per ROM; `None`
+    /// keeps them next to the ROM.
+    pub save_dir: Option<&'a Path>,
+    /// Where an RTC cart persists its clock.
+    pub rtc_format: RtcFormat,
+}
+
+/// Where an RTC cart (MBC3 timer / HuC-3) keeps its clock on disk. Either
+/// place is read back on load when present, whichever this is; it picks
+/// what gets created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(any(feature = "cli", test), derive(clap::ValueEnum))]
+pub enum RtcFormat {
+    /// Appended to the `.sav` after the RAM image (the VBA-M/BGB layout). An
+    /// `.rtc` already there is still read, and kept in step.
+    Footer,
+    /// A separate `.rtc` file next to the `.sav`, which stays bare RAM.
+    Sidecar,
+    /// Both of the above.
+    #[default]
+    Both,
+}
+
+impl RtcFormat {
+    fn footer(self) -> bool {
+        matches!(self, RtcFormat::Footer | RtcFormat::Both)
+    }
+
+    fn sidecar(self) -> bool {
+        matches!(self, RtcFormat::Sidecar | RtcFormat::Both)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Cartridge {
     // ROM data - all banks. Read-only (never mutated after construction) and
@@ -1363,12 +1421,13 @@ pub struct Cartridge {
     // Number of ROM and RAM banks
     rom_banks: usize,
     ram_banks: usize,
-    // ROM file path (for determining .sav file location)
+    // Where the .sav/.rtc sidecars go, minus the extension (see `save_base_for`)
     #[serde(skip)]
-    rom_path: Option<String>,
-    // Open file handle for save file (for battery-backed cartridges)
+    save_base: Option<PathBuf>,
+    // Write-back cache over the attached .sav (battery-backed cartridges);
+    // flushed by `flush_save` and on drop
     #[serde(skip)]
-    save_file: Option<File>,
+    save_file: Option<SaveFile>,
 
     // The live mapper: each board's volatile registers, enum-dispatched (see
     // cartridge/mapper.rs). The battery/persistent domain (RAM, RTC) and the
@@ -1452,6 +1511,15 @@ pub struct Cartridge {
     // I/O and no host-clock reads ever happen without this handle.
     #[serde(skip)]
     rtc_file: Option<File>,
+    // Which of the `.sav` footer / `.rtc` sidecar the clock is written to.
+    #[serde(skip)]
+    rtc_format: RtcFormat,
+
+    // Host-clock RTC mode (`set_rtc_host_clock`): the UNIX second the live
+    // counters were last synced to. None => the default cycle-derived crystal
+    // above. Host state, like the sidecar handle: never serialized.
+    #[serde(skip, default)]
+    rtc_host_anchor: Option<u64>,
 
     // When true the cartridge will not open or write sidecar `.sav`/`.rtc`
     // files; the host (e.g. RetroArch) owns persistence of the in-memory RAM.
@@ -1505,7 +1573,7 @@ impl Clone for Cartridge {
             cartridge_type: self.cartridge_type,
             rom_banks: self.rom_banks,
             ram_banks: self.ram_banks,
-            rom_path: self.rom_path.clone(),
+            save_base: self.save_base.clone(),
             save_file: None, // Don't clone file handles
             mapper: self.mapper.clone(),
             mbc1_multicart: self.mbc1_multicart,
@@ -1524,6 +1592,8 @@ impl Clone for Cartridge {
             rtc_memory: self.rtc_memory.clone(),
             rtc_memory_synced: self.rtc_memory_synced.clone(),
             rtc_file: None, // Don't clone file handles
+            rtc_format: self.rtc_format,
+            rtc_host_anchor: self.rtc_host_anchor,
             host_managed_saves: self.host_managed_saves,
         }
     }
@@ -1634,22 +1704,41 @@ impl Cartridge {
     }
 
     pub fn load(path: &str) -> Result<Self, io::Error> {
-        let data = if path.to_lowercase().ends_with(".zip") {
-            Self::extract_rom_from_zip_bytes(&fs::read(path)?)?
-        } else {
-            fs::read(path)?
+        Self::load_with(path, LoadOptions::default())
+    }
+
+    /// [`load`](Self::load) with a choice of archive member and save location
+    /// (see [`LoadOptions`]).
+    pub fn load_with(path: &str, opts: LoadOptions<'_>) -> Result<Self, io::Error> {
+        let raw = fs::read(path)?;
+        let data = match opts.archive_entry {
+            Some(name) => Self::extract_archive_entry(&raw, name)?,
+            None if path.to_lowercase().ends_with(".zip") => Self::extract_rom_from_zip_bytes(&raw)?,
+            None => Self::extract_rom_bytes(&raw)?,
         };
+        // A `.bps`/`.ups`/`.ips` with the ROM's base name is applied in memory
+        // (romhacks, translations); the files on disk are never modified.
+        let data = crate::patch::apply_sidecar_patch(Path::new(path), data)?;
 
         let mut cartridge = Self::from_rom_image(data)?;
-        cartridge.rom_path = Some(path.to_string());
+        cartridge.attach_sidecars(Path::new(path), opts)?;
+        Ok(cartridge)
+    }
+
+    /// Give a cartridge loaded from memory the battery sidecars a
+    /// [`load_with`](Self::load_with) of the ROM at `path` would have: the
+    /// `.sav` (read back, or created from the current RAM) and the RTC,
+    /// placed per `opts.save_dir`. For hosts that read the ROM themselves,
+    /// such as a GUI load.
+    pub fn attach_sidecars(&mut self, path: &Path, opts: LoadOptions<'_>) -> Result<(), io::Error> {
+        self.save_base = Some(Self::save_base_for(path, opts));
+        self.rtc_format = opts.rtc_format;
 
         // Try to load existing save file or create new one (only for battery-backed RAM)
-        cartridge.load_or_create_save_file()?;
+        self.load_or_create_save_file()?;
         // Restore the persisted RTC (with wall-clock catch-up) and attach the
         // `.rtc` sidecar. Disk-load path only; in-memory loads skip this.
-        cartridge.attach_rtc_sidecar()?;
-
-        Ok(cartridge)
+        self.attach_rtc_sidecar()
     }
 
     /// Shared constructor core: derive everything from an already-unzipped ROM
@@ -1662,11 +1751,16 @@ impl Cartridge {
 
         // Re-expand trimmed MBC1 multicart dumps before any derived fields.
         let data = Self::reconstruct_trimmed_mbc1m(&data).unwrap_or(data);
+        // Put a menu-first MMM01 dump back in hardware order.
+        let data = Self::reorder_menu_first_mmm01(&data).unwrap_or(data);
 
-        // Read cartridge header information
-        let cartridge_type = data[CARTRIDGE_TYPE_OFFSET];
-        let rom_size_code = data[ROM_SIZE_OFFSET];
-        let ram_size_code = data[RAM_SIZE_OFFSET];
+        // Read cartridge header information. An MMM01 compilation is
+        // described by its menu's header in the last 32 KiB; the one at $0100
+        // belongs to the first game.
+        let header_base = Self::mmm01_header_base(&data).unwrap_or(0);
+        let cartridge_type = data[header_base + CARTRIDGE_TYPE_OFFSET];
+        let rom_size_code = data[header_base + ROM_SIZE_OFFSET];
+        let ram_size_code = data[header_base + RAM_SIZE_OFFSET];
 
         // Calculate number of ROM banks (header size, widened to the real file).
         let rom_banks = Self::compute_rom_banks(rom_size_code, data.len())?;
@@ -1817,7 +1911,7 @@ impl Cartridge {
             cartridge_type,
             rom_banks,
             ram_banks,
-            rom_path: None,
+            save_base: None,
             save_file: None,
             mapper: Mapper::from_header(&unl_mapper, cartridge_type, mbc1_multicart, rom_banks, ram_banks),
             mbc1_multicart,
@@ -1839,10 +1933,27 @@ impl Cartridge {
             rtc_memory: Vec::new(),
             rtc_memory_synced: Vec::new(),
             rtc_file: None,
+            rtc_format: RtcFormat::default(),
+            rtc_host_anchor: None,
             host_managed_saves: false,
         }
     }
 
+    /// Read an archive member, refusing one that inflates past
+    /// [`MAX_ROM_SIZE`]. `size_hint` is the size the archive claims, which is
+    /// only trusted as far as the cap.
+    fn read_rom_member(reader: impl Read, size_hint: u64) -> Result<Vec<u8>, io::Error> {
+        let mut rom_data = Vec::with_capacity((size_hint as usize).min(MAX_ROM_SIZE));
+        reader.take(MAX_ROM_SIZE as u64 + 1).read_to_end(&mut rom_data)?;
+        if rom_data.len() > MAX_ROM_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("archive member is larger than {} MiB", MAX_ROM_SIZE >> 20),
+            ));
+        }
+        Ok(rom_data)
+    }
+
     /// Extract the ROM image from an in-memory zip container: prefer a member
     /// with a Game Boy extension, else the largest non-directory member.
     /// `load` reads the file in and comes through here too, so the path and
@@ -1853,18 +1964,14 @@ impl Cartridge {
         let cursor = Cursor::new(data);
         let mut archive = ZipArchive::new(cursor)?;
 
-        // Common Game Boy ROM extensions
-        let rom_extensions = [".gb", ".gbc", ".sgb"];
-
         // First, try to find a file with a ROM extension
         for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
+            let file = archive.by_index(i)?;
             let name = file.name().to_lowercase();
 
-            if rom_extensions.iter().any(|ext| name.ends_with(ext)) {
-                let mut rom_data = Vec::with_capacity(file.size() as usize);
-                file.read_to_end(&mut rom_data)?;
-                return Ok(rom_data);
+            if ROM_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) {
+                let size = file.size();
+                return Self::read_rom_member(file, size);
             }
         }
 
@@ -1881,10 +1988,8 @@ impl Cartridge {
         }
 
         if largest_size > 0 {
-            let mut file = archive.by_index(largest_file_index)?;
-            let mut rom_data = Vec::with_capacity(file.size() as usize);
-            file.read_to_end(&mut rom_data)?;
-            return Ok(rom_data);
+            let file = archive.by_index(largest_file_index)?;
+            return Self::read_rom_member(file, largest_size);
         }
 
         Err(io::Error::new(
@@ -1893,13 +1998,125 @@ impl Cartridge {
         ))
     }
 
-    /// Decompress `data` to the raw ROM bytes: unzips a `PK\x03\x04` container
-    /// (the same extraction `from_bytes` does), else returns the bytes as-is.
-    /// Useful when a caller needs the actual ROM image — e.g. to hash it for a
-    /// No-Intro CRC32 lookup rather than hashing the zip container.
+    /// Extract the ROM image from an in-memory 7-Zip archive, by the same rule
+    /// as [`extract_rom_from_zip_bytes`](Self::extract_rom_from_zip_bytes). The
+    /// pick is made from the archive's file table up front: a solid archive
+    /// decodes its members as one stream, so every member before the pick is
+    /// still decoded (and discarded) on the way to it.
+    fn extract_rom_from_7z_bytes(data: &[u8]) -> Result<Vec<u8>, io::Error> {
+        use sevenz_rust::{Password, SevenZReader};
+        use std::io::Cursor;
+
+        let invalid = |e: sevenz_rust::Error| io::Error::new(io::ErrorKind::InvalidData, e.to_string());
+        let archive =
+            SevenZReader::new(Cursor::new(data), data.len() as u64, Password::empty()).map_err(invalid)?;
+
+        let files = &archive.archive().files;
+        let members = || files.iter().filter(|f| !f.is_directory() && f.has_stream() && f.size() > 0);
+        let pick = members()
+            .find(|f| ROM_EXTENSIONS.iter().any(|ext| f.name().to_lowercase().ends_with(ext)))
+            .or_else(|| members().fold(None, |best, f| match best {
+                Some(b) if f.size() <= b.size() => Some(b),
+                _ => Some(f),
+            }))
+            .map(|f| f.name().to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "No suitable ROM file found in 7z archive"))?;
+        Self::read_7z_member(archive, &pick)
+    }
+
+    /// Decode the member named `pick` out of an opened 7-Zip archive.
+    fn read_7z_member<R: io::Read + io::Seek>(
+        mut archive: sevenz_rust::SevenZReader<R>,
+        pick: &str,
+    ) -> Result<Vec<u8>, io::Error> {
+        let invalid = |e: sevenz_rust::Error| io::Error::new(io::ErrorKind::InvalidData, e.to_string());
+        let mut rom_data = None;
+        archive
+            .for_each_entries(|entry, reader| {
+                if rom_data.is_none() && entry.name() == pick {
+                    rom_data = Some(Self::read_rom_member(reader, entry.size()).map_err(sevenz_rust::Error::io)?);
+                    return Ok(false);
+                }
+                io::copy(reader, &mut io::sink()).map_err(sevenz_rust::Error::io)?;
+                Ok(true)
+            })
+            .map_err(invalid)?;
+        rom_data.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "7z archive member could not be read"))
+    }
+
+    /// Names of the Game Boy ROM members (by extension) of a zip or 7z
+    /// archive, in archive order; empty for a gzip stream or a bare ROM. More
+    /// than one means the archive is a bundle, and a caller should ask which
+    /// to load ([`extract_archive_entry`](Self::extract_archive_entry)) rather
+    /// than let the default rule take the first.
+    pub fn archive_rom_entries(data: &[u8]) -> Result<Vec<String>, io::Error> {
+        let is_rom = |name: &str| ROM_EXTENSIONS.iter().any(|ext| name.to_lowercase().ends_with(ext));
+        if data.starts_with(b"PK\x03\x04") {
+            let mut archive = ZipArchive::new(io::Cursor::new(data))?;
+            let mut names = Vec::new();
+            for i in 0..archive.len() {
+                let file = archive.by_index_raw(i)?;
+                if !file.is_dir() && is_rom(file.name()) {
+                    names.push(file.name().to_string());
+                }
+            }
+            Ok(names)
+        } else if data.starts_with(SEVEN_ZIP_MAGIC) {
+            use sevenz_rust::{Password, SevenZReader};
+            let archive = SevenZReader::new(io::Cursor::new(data), data.len() as u64, Password::empty())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            Ok(archive
+                .archive()
+                .files
+                .iter()
+                .filter(|f| !f.is_directory() && f.has_stream() && is_rom(f.name()))
+                .map(|f| f.name().to_string())
+                .collect())
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Extract the member `name` from a zip or 7z archive (the user's pick
+    /// from [`archive_rom_entries`](Self::archive_rom_entries)).
+    pub fn extract_archive_entry(data: &[u8], name: &str) -> Result<Vec<u8>, io::Error> {
+        let missing = || io::Error::new(io::ErrorKind::NotFound, format!("no member {name:?} in archive"));
+        if data.starts_with(b"PK\x03\x04") {
+            let mut archive = ZipArchive::new(io::Cursor::new(data))?;
+            let file = archive.by_name(name).map_err(|_| missing())?;
+            let size = file.size();
+            Self::read_rom_member(file, size)
+        } else if data.starts_with(SEVEN_ZIP_MAGIC) {
+            use sevenz_rust::{Password, SevenZReader};
+            let archive = SevenZReader::new(io::Cursor::new(data), data.len() as u64, Password::empty())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            if !archive.archive().files.iter().any(|f| f.name() == name) {
+                return Err(missing());
+            }
+            Self::read_7z_member(archive, name)
+        } else {
+            Err(io::Error::new(io::ErrorKind::InvalidInput, "not a zip or 7z archive"))
+        }
+    }
+
+    /// Inflate a gzip stream (`game.gb.gz`). gzip holds exactly one file, so
+    /// there is no member to choose.
+    fn extract_rom_from_gzip_bytes(data: &[u8]) -> Result<Vec<u8>, io::Error> {
+        Self::read_rom_member(flate2::read::MultiGzDecoder::new(data), 0)
+    }
+
+    /// Decompress `data` to the raw ROM bytes: unpacks a zip, 7z or gzip
+    /// container (recognised by magic, the same extraction `from_bytes` does),
+    /// else returns the bytes as-is. Useful when a caller needs the actual ROM
+    /// image — e.g. to hash it for a No-Intro CRC32 lookup rather than hashing
+    /// the container.
     pub fn extract_rom_bytes(data: &[u8]) -> Result<Vec<u8>, io::Error> {
-        if data.len() >= 4 && &data[0..4] == b"PK\x03\x04" {
+        if data.starts_with(b"PK\x03\x04") {
             Self::extract_rom_from_zip_bytes(data)
+        } else if data.starts_with(SEVEN_ZIP_MAGIC) {
+            Self::extract_rom_from_7z_bytes(data)
+        } else if data.starts_with(GZIP_MAGIC) {
+            Self::extract_rom_from_gzip_bytes(data)
         } else {
             Ok(data.to_vec())
         }
@@ -2110,6 +2327,9 @@ impl Cartridge {
             MBC5_RUMBLE_RAM_BATTERY => CartridgeType::MBC5 { ram: true, battery: true, rumble: true },
             MBC6 => CartridgeType::MBC6,
             MBC7_SENSOR_RUMBLE_RAM_BATTERY => CartridgeType::MBC7,
+            MMM01 => CartridgeType::MMM01 { ram: false, battery: false },
+            MMM01_RAM => CartridgeType::MMM01 { ram: true, battery: false },
+            MMM01_RAM_BATTERY => CartridgeType::MMM01 { ram: true, battery: true },
             HUC1_RAM_BATTERY => CartridgeType::HuC1,
             HUC3 => CartridgeType::HuC3,
             POCKET_CAMERA => CartridgeType::PocketCamera,
@@ -2160,6 +2380,7 @@ impl Cartridge {
             Mapper::Mbc5(m) => m.ram_enabled,
             Mapper::Mbc6(m) => m.state.ram_enabled,
             Mapper::Mbc7(m) => m.ram_enabled,
+            Mapper::Mmm01(m) => m.ram_enabled,
             Mapper::Camera(m) => m.ram_enabled,
             Mapper::NtOld(m) => m.ram_enabled,
             Mapper::Vf001(m) => m.ram_enabled,
@@ -2277,23 +2498,35 @@ impl Cartridge {
         Some((addr - EXTERNAL_RAM_START) as usize % self.ram_data.len())
     }
 
-    /// Get the save file path for this cartridge
-    fn get_save_file_path(&self) -> Option<String> {
-        self.rom_path.as_ref().map(|path| {
-            // Replace the extension with .sav
-            let mut save_path = path.clone();
-            if let Some(dot_pos) = save_path.rfind('.') {
-                save_path.truncate(dot_pos);
-            }
-            save_path.push_str(".sav");
-            save_path
+    /// Where the sidecars of the ROM at `path` go, minus the extension: next to
+    /// the ROM, or in a per-ROM folder `<save_dir>/<name>/` when a save
+    /// directory is set. `<name>` is the ROM's file stem — the picked member's
+    /// for an archive bundle, so its games keep separate saves.
+    fn save_base_for(path: &Path, opts: LoadOptions<'_>) -> PathBuf {
+        let named = opts.archive_entry.map_or(path, Path::new);
+        let name = named.file_stem().unwrap_or_default();
+        match opts.save_dir {
+            Some(dir) => dir.join(name).join(name),
+            None => path.with_file_name(name),
+        }
+    }
+
+    /// The `.{ext}` sidecar path (`sav`, `rtc`) for a disk-loaded cartridge.
+    pub(super) fn sidecar_path(&self, ext: &str) -> Option<PathBuf> {
+        self.save_base.as_ref().map(|base| {
+            // Appended rather than `with_extension`, which would eat a dotted
+            // name like "Game v1.1".
+            let mut path = base.clone().into_os_string();
+            path.push(".");
+            path.push(ext);
+            PathBuf::from(path)
         })
     }
 
     /// Load save file data into RAM if it exists, or create empty save file (only for battery-backed RAM)
     fn load_or_create_save_file(&mut self) -> Result<(), io::Error> {
-        if let Some(save_path) = self.get_save_file_path() {
-            self.attach_save_file_at(Path::new(&save_path))
+        if let Some(save_path) = self.sidecar_path("sav") {
+            self.attach_save_file_at(&save_path)
         } else {
             Ok(())
         }
@@ -2301,12 +2534,12 @@ impl Cartridge {
 
     /// Attach a battery-backed save file at an explicit path. Used by
     /// callers (e.g. the Android entry point) that loaded the ROM via
-    /// `from_bytes` and therefore have no `rom_path` from which to derive
+    /// `from_bytes` and therefore have no `save_base` from which to derive
     /// the default sidecar `.sav` location. Behaviour mirrors
     /// `load_or_create_save_file`: if the file exists its contents are
     /// copied into the cart's RAM, otherwise the current RAM contents
-    /// are written out. Either way the file is kept open for streaming
-    /// per-byte writes from `write_ram_byte` / `write_mbc2_ram_byte`.
+    /// are written out. Either way the file is kept open and later RAM
+    /// writes reach it on [`flush_save`](Self::flush_save).
     ///
     /// No-op for cartridges without battery-backed RAM.
     pub fn attach_save_file(&mut self, path: impl AsRef<Path>) -> Result<(), io::Error> {
@@ -2328,8 +2561,10 @@ impl Cartridge {
             return Ok(0);
         }
         let copied = self.load_save_image(bytes);
-        // If a save file is attached, flush the current RAM image so the
-        // internal sidecar mirrors the freshly-loaded state.
+        // If a save file is attached, flush the current RAM image (and any
+        // clock the image carried) so the internal sidecar mirrors the
+        // freshly-loaded state.
+        self.flush_rtc_file();
         self.flush_save_image()?;
         Ok(copied)
     }
@@ -2339,18 +2574,18 @@ impl Cartridge {
     /// The single load policy behind every save-attachment path:
     ///
     /// Only the RAM-sized prefix is taken. An oversized file is legitimate for
-    /// the de-facto RTC-carrying `.sav` (an appended footer, read separately by
-    /// `read_sav_rtc_footer`), and for the rest it is still the safer of the
-    /// options: `attach_save_file_at` opens the file for streaming writes
-    /// whether or not it loaded anything, so refusing to load never actually
-    /// protected the bytes — it only discarded the user's save as well. Callers
-    /// that want a mis-picked file rejected outright go through
-    /// `import_save_ram`, which bounds the size before delegating here.
+    /// the de-facto RTC-carrying `.sav` (an appended footer, which restores the
+    /// clock here when it matches `sav_rtc_footer`), and for the rest it is
+    /// still the safer of the options: `attach_save_file_at` opens the file
+    /// for write-back whether or not it loaded anything, so refusing to load
+    /// never actually protected the bytes — it only discarded the user's save
+    /// as well. Callers that want a mis-picked file rejected outright go
+    /// through `import_save_ram`, which bounds the size before delegating here.
     ///
     /// MBC2 nibble masking is not cosmetic. The built-in RAM is physically
     /// 512 x 4 bits: the upper nibble has no storage cell on the die, which is
     /// why the read path returns `0xF0 | nibble` for the undriven lines. Masking
-    /// on load keeps `save_ram()` exports and the streamed sidecar (whose
+    /// on load keeps `save_ram()` exports and the cached sidecar (whose
     /// `write_mbc2_ram_byte` already masks) from carrying bits the silicon
     /// cannot hold.
     fn load_save_image(&mut self, bytes: &[u8]) -> usize {
@@ -2363,6 +2598,11 @@ impl Cartridge {
                 *b &= 0x0F;
             }
         }
+        if let Some(footer) = self.sav_rtc_footer(bytes)
+            && self.rtc_restore_with_catch_up(footer)
+        {
+            println!("Loaded RTC footer from save file");
+        }
         n
     }
 
@@ -2370,16 +2610,30 @@ impl Cartridge {
     /// no save file is attached.
     fn flush_save_image(&mut self) -> Result<(), io::Error> {
         let is_mbc2 = matches!(self.get_cartridge_type(), CartridgeType::MBC2 { .. });
-        if let Some(ref mut file) = self.save_file {
-            file.seek(SeekFrom::Start(0))?;
+        if let Some(file) = self.save_file.as_mut() {
             // Disjoint field borrows: `save_ram()` would re-borrow all of self.
-            let buf: &[u8] = if is_mbc2 { &self.mbc2_ram } else { &self.ram_data };
-            file.write_all(buf)?;
+            file.replace(if is_mbc2 { &self.mbc2_ram } else { &self.ram_data });
             file.flush()?;
         }
         Ok(())
     }
 
+    /// Write battery RAM the game has touched since the last flush back to
+    /// the attached `.sav`. RAM writes only mark pages dirty; the host calls
+    /// this periodically and on demand, and dropping the cartridge flushes
+    /// too. No-op without an attached save file.
+    pub fn flush_save(&mut self) -> Result<(), io::Error> {
+        match self.save_file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether battery RAM writes are waiting for [`flush_save`](Self::flush_save).
+    pub fn save_dirty(&self) -> bool {
+        self.save_file.as_ref().is_some_and(SaveFile::is_dirty)
+    }
+
     fn attach_save_file_at(&mut self, save_path: &Path) -> Result<(), io::Error> {
         // Only process save files for cartridges with battery-backed RAM
         if !self.has_battery() || self.host_managed_saves || self.save_ram().is_empty() {
@@ -2401,22 +2655,23 @@ impl Cartridge {
             fs::write(save_path, self.save_ram())?;
         }
 
-        // Open file handle for efficient streaming writes
-        self.save_file = Some(OpenOptions::new().write(true).open(save_path)?);
-        Ok(())
+        // Keep the file open; RAM writes are cached and flushed in batches.
+        let file = OpenOptions::new().write(true).open(save_path)?;
+        self.save_file = Some(SaveFile::new(file, self.save_ram()));
+        // Stamp the RTC footer so the file is complete from the start.
+        self.flush_rtc_file();
+        self.flush_save()
     }
 
-    /// Write a byte to both RAM and save file simultaneously (if battery-backed)
+    /// Write a byte to RAM and stage it for the save file (if battery-backed)
     fn write_ram_byte(&mut self, offset: usize, value: u8) -> Result<(), io::Error> {
         if !self.ram_data.is_empty() {
             // Write to RAM buffer (offset is already wrapped by caller)
             self.ram_data[offset] = value;
 
-            // Also write to save file if we have one open
-            if let Some(ref mut file) = self.save_file {
-                file.seek(SeekFrom::Start(offset as u64))?;
-                file.write_all(&[value])?;
-                file.flush()?; // Ensure immediate write
+            // Mark the page dirty; `flush_save` writes it out
+            if let Some(file) = self.save_file.as_mut() {
+                file.write(offset, &[value]);
             }
         }
         Ok(())
@@ -2430,6 +2685,7 @@ impl Cartridge {
             CartridgeType::MBC2 { battery } => battery,
             CartridgeType::MBC3 { battery, .. } => battery,
             CartridgeType::MBC5 { battery, .. } => battery,
+            CartridgeType::MMM01 { battery, .. } => battery,
             // MBC7's EEPROM is inherently non-volatile; HuC-3 ($FE) implies
             // RAM+BATTERY+RTC, HuC-1 ($FF) implies RAM+BATTERY, and POCKET
             // CAMERA ($FC) implies RAM+BATTERY (the photo album).
@@ -2522,6 +2778,9 @@ impl Cartridge {
             },
             MBC6 => "MBC6+RAM+Battery+Flash",
             MBC7 => "MBC7+Sensor+Rumble+RAM+Battery",
+            MMM01 { ram: false, .. } => "MMM01",
+            MMM01 { ram: true, battery: false } => "MMM01+RAM",
+            MMM01 { ram: true, battery: true } => "MMM01+RAM+Battery",
             HuC1 => "HuC1+RAM+Battery",
             HuC3 => "HuC3+RTC+RAM+Battery",
             PocketCamera => "Pocket Camera",
@@ -2591,6 +2850,18 @@ impl Cartridge {
         sum
     }
 
+    /// Checksum bytes as stored in the header: the header checksum ($014D) and
+    /// the big-endian global checksum ($014E-$014F). `None` for a detached ROM.
+    pub fn stored_checksums(&self) -> Option<(u8, u16)> {
+        let hdr = self.rom_data.get(0x014D..=0x014F)?;
+        Some((hdr[0], u16::from_be_bytes([hdr[1], hdr[2]])))
+    }
+
+    /// Mask ROM version number ($014C), usually 0.
+    pub fn rom_version(&self) -> u8 {
+        self.rom_data.get(0x014C).copied().unwrap_or(0)
+    }
+
     /// Raw cartridge-type byte ($0147) as stored in the header.
     pub fn cartridge_type_byte(&self) -> u8 {
         self.cartridge_type
@@ -2611,6 +2882,12 @@ impl Cartridge {
         (!self.rom_data.is_empty()).then(|| crate::checksum::crc32(&self.rom_data))
     }
 
+    /// SHA-1 of the whole ROM, the digest DAT files and ROM sites quote
+    /// alongside the CRC32. `None` if the ROM is detached.
+    pub fn rom_sha1(&self) -> Option<[u8; 20]> {
+        (!self.rom_data.is_empty()).then(|| crate::checksum::sha1(&self.rom_data))
+    }
+
 
 
 
@@ -2635,8 +2912,9 @@ impl Cartridge {
     // little-endian. Common tools write this same block as a footer
     // appended to the `.sav`, and libretro cores expose it verbatim as
     // RETRO_MEMORY_RTC, so RetroArch `.rtc` files use it too. We store it in
-    // a `.rtc` sidecar next to the `.sav` (the RetroArch convention) and
-    // additionally READ it from a `.sav` footer for imported saves.
+    // a `.rtc` sidecar next to the `.sav` (the RetroArch convention) AND as
+    // the `.sav` footer, so the save file alone carries the clock between
+    // emulators; on load the sidecar wins when both exist.
     //
     //   offset size field
     //   0x00   4    seconds       (live counter)
@@ -2707,13 +2985,25 @@ impl Cartridge {
         }
     }
 
+    /// The battery save image as other emulators expect a `.sav`: the save RAM,
+    /// plus the de-facto RTC footer stamped with the current wall clock for
+    /// carts with a clock (File → Export Battery Save).
+    pub fn battery_image(&self) -> Vec<u8> {
+        let mut out = self.save_ram().to_vec();
+        if let Some(blob) = self.rtc_serialize(Self::unix_now()) {
+            out.extend_from_slice(&blob);
+        }
+        out
+    }
+
     /// Import a battery save image into the cart's RAM (File → Import Battery
     /// Save). Copies `min(src, dst)` bytes so a footer-carrying `.sav` (RTC
-    /// footer) or a short file loads its RAM-sized prefix; a wildly-oversized
-    /// file (more than double the RAM) is rejected so a mis-picked file can't be
-    /// silently accepted. If a sidecar `.sav` is attached (desktop) the freshly
-    /// loaded image is flushed straight through it, so the import survives a
-    /// reload with no extra host plumbing. No-op for non-battery carts.
+    /// footer, which also restores the clock) or a short file loads its
+    /// RAM-sized prefix; a wildly-oversized file (more than double the RAM) is
+    /// rejected so a mis-picked file can't be silently accepted. If a sidecar
+    /// `.sav` is attached (desktop) the freshly loaded image is flushed
+    /// straight through it, so the import survives a reload with no extra host
+    /// plumbing. No-op for non-battery carts.
     pub fn import_save_ram(&mut self, bytes: &[u8]) -> Result<usize, String> {
         if !self.has_battery() {
             return Err("cartridge has no battery-backed save RAM".into());
@@ -2731,6 +3021,17 @@ impl Cartridge {
         self.load_sram_bytes(bytes).map_err(|e| e.to_string())
     }
 
+    /// Blank the battery-backed RAM to its power-on fill (File → Battery
+    /// Saves → Erase) and write the blank image through any attached save
+    /// file. The RTC keeps running: only the SRAM is a "save".
+    pub fn erase_save_ram(&mut self) -> Result<(), String> {
+        if !self.has_battery() || self.save_ram().is_empty() {
+            return Err("cartridge has no battery-backed save RAM".into());
+        }
+        self.save_ram_mut().fill(0xFF);
+        self.flush_save_image().map_err(|e| e.to_string())
+    }
+
     /// Byte the cartridge RAM chip drives when the OAM-DMA controller asserts
     /// the external-RAM chip select (gb-ctr "OAM DMA address decoding": all
     /// A000-FFFF sources are external-RAM accesses). Bypasses the CPU read
@@ -2804,6 +3105,17 @@ impl Cartridge {
         matches!(&self.mapper, Mapper::Mbc5(m) if m.rumble_motor)
     }
 
+    /// HuC-1 IR transceiver: `Some(led)` while the $0000-$1FFF register maps
+    /// A000-BFFF to the IR port (`led` = the emitter latch), `None` in RAM mode
+    /// or on any other board. Lets the bus couple the cart's LED/receiver to
+    /// the same IR partner as the CGB RP port.
+    pub(crate) fn huc1_ir_led(&self) -> Option<bool> {
+        match &self.mapper {
+            Mapper::HuC1(m) if m.state.ir_mode => Some(m.state.ir_led),
+            _ => None,
+        }
+    }
+
     /// Patch a ROM byte (Game Genie). `addr` is a 0x0000-0x7FFF CPU address;
     /// the patch is applied to ROM bank 0 for 0x0000-0x3FFF and to the bank
     /// currently mapped at 0x4000-0x7FFF otherwise. When `compare` is given the
@@ -2836,7 +3148,7 @@ impl Cartridge {
     /// HuC-3 RTC memory, and their sub-second accumulators — just like
     /// pressing the console's reset/power button, which cuts mapper power but
     /// not the cart battery. Transient hardware inputs (accelerometer tilt,
-    /// camera image) and host plumbing (file handles, rom_path,
+    /// camera image) and host plumbing (file handles, save_base,
     /// host_managed_saves, sram_cs_lazy, libretro RTC views, boot-logo seed)
     /// persist too. Everything else — bank registers, enable gates, banking
     /// modes, boot locks, in-flight peripheral state — comes from `fresh`, so
@@ -2870,9 +3182,11 @@ impl Cartridge {
             mbc7_sensor_y: self.mbc7_sensor_y,
             cam_image: std::mem::take(&mut self.cam_image),
             // Host plumbing.
-            rom_path: self.rom_path.take(),
+            save_base: self.save_base.take(),
             save_file: self.save_file.take(),
             rtc_file: self.rtc_file.take(),
+            rtc_format: self.rtc_format,
+            rtc_host_anchor: self.rtc_host_anchor,
             rtc_memory: std::mem::take(&mut self.rtc_memory),
             rtc_memory_synced: std::mem::take(&mut self.rtc_memory_synced),
             rocket_boot_logo: self.rocket_boot_logo,
@@ -3167,6 +3481,16 @@ impl memory::Addressable for Cartridge {
                             0xFF
                         }
                     }
+                    // MMM01 games see plain MBC1 RAM (the slice is in the bank math).
+                    Mapper::Mmm01(m) if m.has_ram => {
+                        if m.ram_enabled
+                            && let Some(offset) = self.banked_ram_offset(addr)
+                        {
+                            self.ram_data[offset]
+                        } else {
+                            0xFF
+                        }
+                    }
                     // Vast Fame VF001 is electrically MBC5+RAM; its protection
                     // reads are served by the front-end above, so a fall-through
                     // read here is plain cart RAM.
@@ -3259,8 +3583,9 @@ impl memory::Addressable for Cartridge {
                     Mapper::HuC1(m) => {
                         if m.state.ir_mode {
                             // IR receiver: 0xC1 = light seen, 0xC0 = no light
-                            // (Pan Docs HuC1). No IR transport is modeled, so
-                            // this always reads the documented idle 0xC0.
+                            // (Pan Docs HuC1). The cart alone always reads the
+                            // documented idle 0xC0; Mmio ORs in bit 0 when an
+                            // attached IR partner is lit (`huc1_ir_led`).
                             0xC0
                         } else if let Some(offset) = self.banked_ram_offset(addr) {
                             // RAM is always enabled (no MBC1-style gate).
@@ -3462,6 +3787,14 @@ impl memory::Addressable for Cartridge {
             {
                 self.xploder_write(addr, value);
             }
+            // MMM01: every register block mixes MBC1 fields with the menu's
+            // pre-lock outer-bank/mask bits, so the board decodes the whole
+            // $0000-$7FFF window itself.
+            RAM_ENABLE_START..=BANKING_MODE_END if matches!(self.mapper, Mapper::Mmm01(_)) => {
+                if let Mapper::Mmm01(m) = &mut self.mapper {
+                    m.write(addr, value);
+                }
+            }
             // RAM Enable (0x0000-0x1FFF)
             RAM_ENABLE_START..=RAM_ENABLE_END => match &mut self.mapper {
                 Mapper::Mbc1(m) => m.ram_enabled = (value & 0x0F) == 0x0A,
@@ -3767,6 +4100,7 @@ impl memory::Addressable for Cartridge {
                     Mapper::Mbc3(m) if m.has_ram => Ext::Mbc3Ram(m.ram_enabled, m.ram_bank),
                     Mapper::Mbc3(m) => Ext::Mbc3Rtc(m.ram_enabled && m.timer, m.ram_bank),
                     Mapper::Mbc5(m) => Ext::Banked(m.has_ram && m.ram_enabled),
+                    Mapper::Mmm01(m) => Ext::Banked(m.has_ram && m.ram_enabled),
                     Mapper::Vf001(m) => Ext::Banked(m.ram_enabled),
                     Mapper::LiCheng(m) => Ext::Banked(m.ram_enabled),
                     Mapper::Bbd(m) => Ext::Banked(m.ram_enabled),
@@ -3840,7 +4174,8 @@ impl memory::Addressable for Cartridge {
                     },
                     Ext::HuC1(ir) => {
                         if ir {
-                            // IR transmitter: bit 0 drives the LED (latched, unobserved).
+                            // IR transmitter: bit 0 drives the LED (latched;
+                            // Mmio publishes it to any attached IR partner).
                             if let Mapper::HuC1(m) = &mut self.mapper {
                                 m.state.ir_led = value & 0x01 != 0;
                             }
@@ -5240,6 +5575,7 @@ mod tests {
             (HUC1_RAM_BATTERY, "HuC1+RAM+Battery"),
             (POCKET_CAMERA, "Pocket Camera"),
             (TAMA5, "Bandai TAMA5"),
+            (MMM01, "MMM01"),
         ];
         for &(ty, name) in cases {
             let cart = Cartridge::from_bytes(&make_rom(ty, 0x02)).unwrap();
@@ -5247,6 +5583,63 @@ mod tests {
         }
     }
 
+    /// 256 KiB MMM01 compilation: bank markers from `make_sized_rom`, and the
+    /// menu's header (MMM01+RAM+Battery, valid checksum) in banks 14-15.
+    fn make_mmm01_rom() -> Vec<u8> {
+        let mut rom = make_sized_rom(0x00, 0x03, 0x40000);
+        let menu = 0x40000 - 0x8000;
+        rom[menu + CARTRIDGE_TYPE_OFFSET] = MMM01_RAM_BATTERY;
+        rom[menu + ROM_SIZE_OFFSET] = 0x03;
+        rom[menu + RAM_SIZE_OFFSET] = 0x03;
+        rom[menu + 0x14D] =
+            rom[menu + 0x134..menu + 0x14D].iter().fold(0u8, |a, &b| a.wrapping_sub(b).wrapping_sub(1));
+        rom
+    }
+
+    /// MMM01 boots its menu from the last 32 KiB, then the menu confines the
+    /// board to one game's slice: the masked outer bits survive every game
+    /// write, and MAP freezes the masks themselves.
+    #[test]
+    fn mmm01_boots_the_menu_then_locks_to_a_game_slice() {
+        let mut cart = Cartridge::from_bytes(&make_mmm01_rom()).unwrap();
+        assert_eq!(cart.mapper_name(), "MMM01+RAM+Battery");
+        assert!(cart.has_battery());
+        assert_eq!(cart.read(0x1000), 14, "unmapped: menu bank 0 is the second-to-last bank");
+        assert_eq!(cart.read(0x5000), 15);
+
+        // Menu: base bank 4, protect ROM bank bits 2-4 (a 4-bank game), map.
+        cart.write(0x2000, 0x04);
+        cart.write(0x6000, 0x0E << 2);
+        cart.write(0x0000, 0x40);
+        assert_eq!(cart.read(0x1000), 4);
+        assert_eq!(cart.read(0x5000), 5, "MBC1 zero->one remap on the writable bits");
+
+        // The game banks within its slice and cannot reach outside it.
+        cart.write(0x2000, 0x03);
+        assert_eq!(cart.read(0x5000), 7);
+        cart.write(0x2000, 0x1F);
+        assert_eq!(cart.read(0x5000), 7);
+        cart.write(0x6000, 0x00);
+        cart.write(0x2000, 0x10);
+        assert_eq!(cart.read(0x5000), 5, "the mask is frozen once mapped");
+        cart.write(0x0000, 0x00);
+        assert_eq!(cart.read(0x1000), 4, "MAP stays set until power-off");
+    }
+
+    /// Menu-first dumps are rotated back to hardware order on load.
+    #[test]
+    fn mmm01_menu_first_dump_is_reordered() {
+        let mut rom = make_mmm01_rom();
+        rom.rotate_right(0x8000);
+        let cart = Cartridge::from_bytes(&rom).unwrap();
+        assert!(matches!(
+            cart.get_cartridge_type(),
+            CartridgeType::MMM01 { ram: true, battery: true }
+        ));
+        assert_eq!(cart.read(0x1000), 14);
+        assert_eq!(cart.read(0x5000), 15);
+    }
+
     /// TAMA5's save RAM and battery come from the TYPE byte, not the header
     /// RAM-size byte (which the real carts leave at $00) — the same shape as
     /// MBC7's EEPROM. Host-side identity only; the bus protocol is pinned by
@@ -5351,14 +5744,14 @@ mod tests {
         let cart = Cartridge::from_bytes(&make_rom(0x30, 0x00)).unwrap();
         assert!(matches!(cart.get_cartridge_type(), CartridgeType::NoMBC { .. }));
 
-        // Documented-but-unimplemented types are NOT inferred, however large:
-        // $0B names a real board (MMM01), so it is evidence about the cart
-        // rather than evidence the header is garbage.
-        let mut rom = make_sized_rom(0x0B, 0x00, 0x10000);
+        // Documented types are NOT inferred, however large: $0B names a real
+        // board (MMM01), so it is evidence about the cart rather than
+        // evidence the header is garbage.
+        let mut rom = make_sized_rom(MMM01, 0x00, 0x10000);
         rom[0x104..0x134].copy_from_slice(&LICENSED_LOGO);
         let cart = Cartridge::from_bytes(&rom).unwrap();
         assert!(
-            matches!(cart.get_cartridge_type(), CartridgeType::NoMBC { .. }),
+            matches!(cart.get_cartridge_type(), CartridgeType::MMM01 { .. }),
             "MMM01 ($0B) must not be inferred"
         );
 
@@ -5423,6 +5816,9 @@ mod tests {
             .filter(|&(i, _)| i != 0x014E && i != 0x014F)
             .fold(0u16, |a, (_, &b)| a.wrapping_add(b as u16));
         assert_eq!(cart.global_checksum(), expected);
+        assert_eq!(cart.stored_checksums(), Some((rom[0x014D], u16::from_be_bytes([rom[0x014E], rom[0x014F]]))));
+        assert_eq!(cart.rom_version(), rom[0x014C]);
+        assert_eq!(cart.rom_sha1(), Some(crate::checksum::sha1(&rom)));
 
         // Corrupt a header byte -> checksum no longer matches.
         let mut bad = rom.clone();
@@ -6356,6 +6752,53 @@ mod tests {
         assert_eq!(mbc3_rtc(&cart), (5, 6, 7, 8, 0x40));
     }
 
+    /// Host-clock mode: whole elapsed seconds drive the cascade (day carry
+    /// included), emulated cycles no longer do, HALT holds the counters
+    /// without banking the halted time, and a backwards host clock re-anchors.
+    #[test]
+    fn mbc3_host_clock_mode_follows_wall_time() {
+        let mut cart = mbc3_rtc_cart();
+        set_mbc3_rtc(&mut cart, (59, 59, 23, 0xFF, 0x01));
+        cart.set_rtc_host_clock(true);
+        assert!(cart.rtc_host_clock());
+        cart.rtc_host_anchor = Some(1_000);
+
+        cart.rtc_tick(10 * 4_194_304, RtcTickKind::Mbc3);
+        assert_eq!(mbc3_rtc(&cart), (59, 59, 23, 0xFF, 0x01), "cycles are ignored");
+
+        cart.rtc_host_clock_advance_to(1_001);
+        assert_eq!(mbc3_rtc(&cart), (0, 0, 0, 0x00, 0x80), "day counter overflow sets carry");
+
+        cart.rtc.days_high |= 0x40;
+        cart.rtc_host_clock_advance_to(1_100);
+        assert_eq!(mbc3_rtc(&cart), (0, 0, 0, 0x00, 0xC0));
+        cart.rtc.days_high &= !0x40;
+        cart.rtc_host_clock_advance_to(1_105);
+        assert_eq!(mbc3_rtc(&cart).0, 5, "halted time is not banked");
+
+        cart.rtc_host_clock_advance_to(900);
+        cart.rtc_host_clock_advance_to(902);
+        assert_eq!(mbc3_rtc(&cart).0, 7);
+
+        cart.set_rtc_host_clock(false);
+        cart.rtc_tick(4_194_304, RtcTickKind::Mbc3);
+        assert_eq!(mbc3_rtc(&cart).0, 8, "cycle-derived clock resumes");
+    }
+
+    /// HuC-3 counts minutes: the anchor only advances by whole minutes, so a
+    /// per-frame sync never drops the sub-minute remainder.
+    #[test]
+    fn huc3_host_clock_mode_keeps_sub_minute_remainder() {
+        let mut cart = huc3_cart();
+        cart.set_rtc_host_clock(true);
+        cart.rtc_host_anchor = Some(0);
+        for now in 1..=90 {
+            cart.rtc_host_clock_advance_to(now);
+        }
+        assert_eq!(cart.huc3_clock(), (1, 0));
+        assert_eq!(cart.rtc_host_anchor, Some(60));
+    }
+
     #[test]
     fn huc3_rtc_blob_round_trips_with_nibble_packing() {
         let mut cart = huc3_cart();
@@ -6408,6 +6851,36 @@ mod tests {
         }
     }
 
+    /// With a save directory the `.sav`/`.rtc` go to a per-ROM folder under
+    /// it and nothing is written beside the ROM; a bundle member is named
+    /// after itself rather than the archive.
+    #[test]
+    fn save_dir_holds_sidecars_in_per_rom_folders() {
+        let dir = std::env::temp_dir().join(format!(
+            "rustyboi-savedir-test-{}-{}",
+            std::process::id(),
+            unique_suffix()
+        ));
+        let (roms, saves) = (dir.join("roms"), dir.join("saves"));
+        fs::create_dir_all(&roms).unwrap();
+        let rom_path = roms.join("Game v1.1.gb");
+        fs::write(&rom_path, make_rom(MBC3_TIMER_RAM_BATTERY, 0x03)).unwrap();
+
+        let opts = LoadOptions { save_dir: Some(&saves), ..Default::default() };
+        Cartridge::load_with(rom_path.to_str().unwrap(), opts).unwrap();
+        assert!(saves.join("Game v1.1").join("Game v1.1.sav").exists());
+        assert!(saves.join("Game v1.1").join("Game v1.1.rtc").exists());
+        assert_eq!(fs::read_dir(&roms).unwrap().count(), 1, "nothing written beside the ROM");
+
+        let zip_path = roms.join("pack.zip");
+        fs::write(&zip_path, make_zip(&[("a.gb", make_rom(MBC1_RAM_BATTERY, 0x02)), ("b.gb", make_rom(MBC1, 0))]))
+            .unwrap();
+        let opts = LoadOptions { archive_entry: Some("a.gb"), ..Default::default() };
+        Cartridge::load_with(zip_path.to_str().unwrap(), opts).unwrap();
+        assert!(roms.join("a.sav").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     /// End-to-end sidecar flow on the disk-load path: a fresh load creates
     /// the `.rtc`; a reload after back-dating its timestamp catches the clock
     /// up by the elapsed wall time; a halted clock stays put.
@@ -6487,19 +6960,99 @@ mod tests {
         // Sidecar was created and now wins over the footer.
         assert!(dir.join("game.rtc").exists());
 
-        // The live RAM write path still streams to the .sav without
-        // clobbering the (read-only) footer.
+        // Flushed RAM writes land in the .sav without clobbering the
+        // footer, which now tracks the live clock.
         cart.write(0x0000, 0x0A);
         cart.write(0x4000, 0x00);
         cart.write(0xA000, 0x77);
+        cart.flush_save().unwrap();
         let sav_after = fs::read(dir.join("game.sav")).unwrap();
         assert_eq!(sav_after.len(), sav.len());
         assert_eq!(sav_after[0], 0x77);
-        assert_eq!(&sav_after[32 * 1024..], &sav[32 * 1024..]);
+        let mut reread = mbc3_rtc_cart();
+        reread.mbc3_rtc_deserialize(&sav_after[32 * 1024..]).unwrap();
+        assert_eq!(mbc3_rtc(&reread), mbc3_rtc(&cart));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// A fresh RTC cart writes the footer into its own `.sav`, so the save
+    /// file alone carries the clock (no `.rtc` needed) into the next session
+    /// or another emulator; exports carry it too. Clock updates reach the
+    /// footer on the save flush, not as they happen.
+    #[test]
+    fn sav_rtc_footer_is_written_and_round_trips() {
+        let dir = std::env::temp_dir().join(format!(
+            "rustyboi-footer-write-test-{}-{}",
+            std::process::id(),
+            unique_suffix()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let rom_path = dir.join("game.gb");
+        fs::write(&rom_path, make_rom(MBC3_TIMER_RAM_BATTERY, 0x03)).unwrap();
+
+        let mut cart = Cartridge::load(rom_path.to_str().unwrap()).unwrap();
+        set_mbc3_rtc(&mut cart, (5, 6, 7, 0x08, 0x40)); // halted: no catch-up
+        cart.flush_rtc_file();
+        let stale = fs::read(dir.join("game.sav")).unwrap();
+        assert!(cart.save_dirty());
+        cart.flush_save().unwrap();
+        let flushed = fs::read(dir.join("game.sav")).unwrap();
+        assert_ne!(stale, flushed, "footer reached disk before a flush");
+        assert_eq!(flushed.len(), 32 * 1024 + 48);
+        assert_eq!(cart.battery_image().len(), 32 * 1024 + 48);
+        drop(cart);
+
+        fs::remove_file(dir.join("game.rtc")).unwrap();
+        let cart = Cartridge::load(rom_path.to_str().unwrap()).unwrap();
+        assert_eq!(mbc3_rtc(&cart), (5, 6, 7, 0x08, 0x40));
 
         fs::remove_dir_all(&dir).unwrap();
     }
 
+    /// `RtcFormat` picks where the clock is written: sidecar-only leaves the
+    /// `.sav` as bare RAM (trimming an old footer), footer-only creates no
+    /// `.rtc` but still reads (and keeps current) one left by an earlier run,
+    /// and either one alone still restores the clock on reload.
+    #[test]
+    fn rtc_format_selects_footer_or_sidecar() {
+        let dir = save_test_dir("rtc-format");
+        let rom_path = dir.join("game.gb");
+        let rom = rom_path.to_str().unwrap();
+        fs::write(&rom_path, make_rom(MBC3_TIMER_RAM_BATTERY, 0x03)).unwrap();
+        fs::write(dir.join("game.sav"), vec![0u8; 32 * 1024 + 48]).unwrap();
+
+        let sidecar = LoadOptions { rtc_format: RtcFormat::Sidecar, ..Default::default() };
+        let mut cart = Cartridge::load_with(rom, sidecar).unwrap();
+        set_mbc3_rtc(&mut cart, (1, 2, 3, 0x04, 0x40)); // halted: no catch-up
+        cart.flush_rtc_file();
+        drop(cart);
+        assert_eq!(fs::read(dir.join("game.sav")).unwrap().len(), 32 * 1024);
+        let cart = Cartridge::load_with(rom, sidecar).unwrap();
+        assert_eq!(mbc3_rtc(&cart), (1, 2, 3, 0x04, 0x40));
+        drop(cart);
+
+        // Switching to footer-only: the `.rtc` is the only copy of the clock.
+        let footer = LoadOptions { rtc_format: RtcFormat::Footer, ..Default::default() };
+        let mut cart = Cartridge::load_with(rom, footer).unwrap();
+        assert_eq!(mbc3_rtc(&cart), (1, 2, 3, 0x04, 0x40));
+        set_mbc3_rtc(&mut cart, (5, 6, 7, 0x08, 0x40));
+        cart.flush_rtc_file();
+        drop(cart);
+        assert_eq!(fs::read(dir.join("game.sav")).unwrap().len(), 32 * 1024 + 48);
+        let cart = Cartridge::load_with(rom, footer).unwrap();
+        assert_eq!(mbc3_rtc(&cart), (5, 6, 7, 0x08, 0x40), "the kept .rtc is not stale");
+        drop(cart);
+
+        fs::remove_file(dir.join("game.rtc")).unwrap();
+        let cart = Cartridge::load_with(rom, footer).unwrap();
+        assert_eq!(mbc3_rtc(&cart), (5, 6, 7, 0x08, 0x40));
+        drop(cart);
+        assert!(!dir.join("game.rtc").exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     /// The libretro RETRO_MEMORY_RTC region: stable pointer, de-facto-format
     /// content, and external writes are adopted with catch-up on the next
     /// frame sync.
@@ -6979,6 +7532,47 @@ mod tests {
         assert_eq!(cart.ram_data[0], 0x5A);
     }
 
+    /// 128 KiB MBC5 RAM (header $04, 16 x 8 KiB): every bank is distinct
+    /// (nothing wraps at 32/64 KiB), the battery image is the full 128 KiB,
+    /// and a savestate carries all of it.
+    #[test]
+    fn mbc5_128k_ram_is_fully_banked_saved_and_serialized() {
+        let mut cart = Cartridge::from_bytes(&make_rom(MBC5_RAM_BATTERY, 0x04)).unwrap();
+        assert_eq!(cart.save_ram().len(), 128 * 1024);
+        cart.write(0x0000, 0x0A);
+        for bank in 0..16u8 {
+            cart.write(0x4000, bank);
+            cart.write(0xA000, 0xB0 | bank);
+            cart.write(0xBFFF, bank);
+        }
+        for bank in 0..16u8 {
+            cart.write(0x4000, bank);
+            assert_eq!(cart.read(0xA000), 0xB0 | bank, "bank {bank}");
+        }
+        assert_eq!(cart.save_ram()[15 * 0x2000], 0xBF);
+        assert_eq!(cart.save_ram()[16 * 0x2000 - 1], 15);
+
+        let bytes = bincode::serialize(&cart).unwrap();
+        let mut restored: Cartridge = bincode::deserialize(&bytes).unwrap();
+        restored.attach_rom(make_rom(MBC5_RAM_BATTERY, 0x04));
+        assert_eq!(restored.save_ram(), cart.save_ram());
+        restored.write(0x4000, 0x0C);
+        assert_eq!(restored.read(0xA000), 0xBC);
+    }
+
+    /// On rumble boards bit 3 of the RAM-bank write is the motor, not a bank
+    /// bit: turning the motor on must not move the RAM window.
+    #[test]
+    fn mbc5_rumble_bit_does_not_select_ram_bank() {
+        let mut cart = Cartridge::from_bytes(&make_rom(MBC5_RUMBLE_RAM_BATTERY, 0x05)).unwrap();
+        cart.write(0x0000, 0x0A);
+        cart.write(0x4000, 0x01);
+        cart.write(0xA000, 0x11);
+        cart.write(0x4000, 0x09); // bank 1 + motor on
+        assert!(cart.rumble_active());
+        assert_eq!(cart.read(0xA000), 0x11);
+    }
+
     /// MBC5 reset: bank registers re-home (ROMB0=1, ROMB1=0, RAMB=0) and the
     /// rumble motor line drops.
     #[test]
@@ -7209,6 +7803,51 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    /// SRAM writes are cached: the `.sav` only changes on `flush_save`, which
+    /// writes the touched pages, and dropping the cart flushes what is left.
+    #[test]
+    fn save_ram_writes_reach_disk_on_flush_and_drop() {
+        let dir = save_test_dir("batched");
+        let sav = dir.join("mbc1.sav");
+        let mut cart = Cartridge::from_bytes(&make_rom(MBC1_RAM_BATTERY, 0x02)).unwrap();
+        cart.attach_save_file(&sav).unwrap();
+        cart.write(0x0000, 0x0A);
+        cart.write(0xA010, 0x42);
+        cart.write(0xBFFF, 0x99);
+        assert!(cart.save_dirty());
+        assert_eq!(fs::read(&sav).unwrap()[0x10], 0xFF, "write reached disk before a flush");
+
+        cart.flush_save().unwrap();
+        assert!(!cart.save_dirty());
+        let bytes = fs::read(&sav).unwrap();
+        assert_eq!((bytes.len(), bytes[0x10], bytes[0x1FFF]), (0x2000, 0x42, 0x99));
+
+        cart.write(0xA020, 0x17);
+        drop(cart);
+        assert_eq!(fs::read(&sav).unwrap()[0x20], 0x17);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    /// Erasing blanks SRAM in memory and on disk at once, and refuses carts
+    /// without a battery.
+    #[test]
+    fn erase_save_ram_blanks_ram_and_sidecar() {
+        let dir = save_test_dir("erase");
+        let sav = dir.join("mbc1.sav");
+        fs::write(&sav, vec![0x5A; 0x2000]).unwrap();
+        let mut cart = Cartridge::from_bytes(&make_rom(MBC1_RAM_BATTERY, 0x02)).unwrap();
+        cart.attach_save_file(&sav).unwrap();
+        assert_eq!(cart.save_ram()[0], 0x5A);
+
+        cart.erase_save_ram().unwrap();
+        assert!(cart.save_ram().iter().all(|&b| b == 0xFF));
+        assert!(fs::read(&sav).unwrap().iter().all(|&b| b == 0xFF));
+
+        let mut plain = Cartridge::from_bytes(&make_rom(MBC1, 0x00)).unwrap();
+        assert!(plain.erase_save_ram().is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+
     /// The other end of the same one-policy load: a short save file fills its
     /// prefix and leaves the rest of the array at its power-on fill rather than
     /// being rejected or zero-extending the cart's RAM. Both the path and the
@@ -7304,4 +7943,130 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    /// Build an in-memory 7z. `solid` packs every member into one LZMA2
+    /// stream, so reaching a later member means decoding the earlier ones.
+    fn make_7z(entries: &[(&str, Vec<u8>)], solid: bool) -> Vec<u8> {
+        use sevenz_rust::{SeqReader, SevenZArchiveEntry, SevenZWriter, SourceReader};
+        let entry = |name: &str| {
+            let mut e = SevenZArchiveEntry::new();
+            e.name = name.to_string();
+            e.has_stream = true;
+            e
+        };
+        let mut w = SevenZWriter::new(std::io::Cursor::new(Vec::new())).unwrap();
+        if solid {
+            let names = entries.iter().map(|(n, _)| entry(n)).collect();
+            let readers = entries.iter().map(|(_, d)| SourceReader::new(&d[..])).collect();
+            w.push_archive_entries(names, SeqReader::new(readers)).unwrap();
+        } else {
+            for (name, data) in entries {
+                w.push_archive_entry(entry(name), Some(&data[..])).unwrap();
+            }
+        }
+        w.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn extracts_rom_from_7z_by_extension_then_size() {
+        let rom = make_rom(MBC1, 0x00);
+        let big = vec![0x5Au8; rom.len() * 2];
+        for solid in [false, true] {
+            // The .gb member wins over a larger decoy ahead of it.
+            let archive = make_7z(&[("big.bin", big.clone()), ("game.gb", rom.clone())], solid);
+            assert_eq!(Cartridge::extract_rom_bytes(&archive).unwrap(), rom, "solid={solid}");
+            // No ROM extension anywhere: the largest member is taken.
+            let archive = make_7z(&[("a.bin", rom.clone()), ("b.bin", big.clone())], solid);
+            assert_eq!(Cartridge::extract_rom_bytes(&archive).unwrap(), big, "solid={solid}");
+        }
+        let cart = Cartridge::from_bytes(&make_7z(&[("game.gb", rom.clone())], false)).unwrap();
+        assert_eq!(cart.rom_data[..rom.len()], rom[..]);
+    }
+
+    /// A bundle lists every ROM member (in archive order, non-ROMs skipped)
+    /// and any one of them can be taken by name, from zip and 7z alike.
+    #[test]
+    fn archive_bundles_list_and_extract_each_rom() {
+        let first = make_rom(MBC1, 0x00);
+        let mut second = make_rom(MBC1, 0x00);
+        second[0x0134] = b'B';
+        let members = [
+            ("readme.txt", vec![0x11; 16]),
+            ("One.gb", first.clone()),
+            ("two.GBC", second.clone()),
+        ];
+        for archive in [make_zip(&members), make_7z(&members, false), make_7z(&members, true)] {
+            assert_eq!(Cartridge::archive_rom_entries(&archive).unwrap(), ["One.gb", "two.GBC"]);
+            assert_eq!(Cartridge::extract_archive_entry(&archive, "two.GBC").unwrap(), second);
+            assert_eq!(Cartridge::extract_archive_entry(&archive, "One.gb").unwrap(), first);
+            assert!(Cartridge::extract_archive_entry(&archive, "three.gb").is_err());
+        }
+        assert!(Cartridge::archive_rom_entries(&first).unwrap().is_empty());
+        assert!(Cartridge::extract_archive_entry(&first, "One.gb").is_err());
+    }
+
+    #[test]
+    fn extracts_rom_from_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        let rom = make_rom(MBC1, 0x00);
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(&rom).unwrap();
+        let gz = enc.finish().unwrap();
+        assert_eq!(Cartridge::extract_rom_bytes(&gz).unwrap(), rom);
+        assert!(Cartridge::from_bytes(&gz).is_ok());
+        // A truncated stream is an error, not a garbage ROM.
+        assert!(Cartridge::extract_rom_bytes(&gz[..gz.len() / 2]).is_err());
+    }
+
+    // A few kilobytes of archive that inflate past the ROM cap are refused
+    // instead of being unpacked into memory.
+    #[test]
+    fn oversized_archive_members_are_refused() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        let zeros = vec![0u8; 1 << 20];
+        let mut enc = GzEncoder::new(Vec::new(), Compression::fast());
+        for _ in 0..=MAX_ROM_SIZE >> 20 {
+            enc.write_all(&zeros).unwrap();
+        }
+        let gz = enc.finish().unwrap();
+        assert!(gz.len() < MAX_ROM_SIZE / 64);
+        let err = Cartridge::extract_rom_bytes(&gz).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let zip = make_zip(&[("big.gb", vec![0; MAX_ROM_SIZE + 1])]);
+        assert!(Cartridge::extract_rom_bytes(&zip).is_err());
+        assert!(Cartridge::extract_archive_entry(&zip, "big.gb").is_err());
+    }
+
+    /// A same-named `.ips` next to the ROM is applied in memory at load time;
+    /// the ROM file itself is left untouched, and a broken patch is an error.
+    #[test]
+    fn load_soft_patches_from_sidecar_ips() {
+        let rom = make_rom(MBC1, 0x00);
+        let dir = std::env::temp_dir().join(format!(
+            "rustyboi-ips-test-{}-{}",
+            std::process::id(),
+            unique_suffix()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let rom_path = dir.join("game.gb");
+        fs::write(&rom_path, &rom).unwrap();
+
+        // One record: write "HI" at $0200.
+        let mut ips = b"PATCH".to_vec();
+        ips.extend_from_slice(&[0x00, 0x02, 0x00, 0x00, 0x02, b'H', b'I']);
+        ips.extend_from_slice(b"EOF");
+        fs::write(dir.join("game.ips"), &ips).unwrap();
+
+        let cart = Cartridge::load(rom_path.to_str().unwrap()).unwrap();
+        assert_eq!(&cart.rom_data[0x0200..0x0202], b"HI");
+        assert_eq!(fs::read(&rom_path).unwrap(), rom, "the ROM on disk is not modified");
+
+        fs::write(dir.join("game.ips"), b"PATCH\x00").unwrap();
+        assert!(Cartridge::load(rom_path.to_str().unwrap()).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -4,7 +4,6 @@
 use super::*;
 use std::fs::OpenOptions;
 use std::io::{Seek, SeekFrom, Write};
-use std::path::Path;
 
 impl Cartridge {
     pub(super) const MBC3_RTC_BLOB_LEN: usize = 48;
@@ -76,7 +75,8 @@ impl Cartridge {
     /// sub-second accumulator keeps running so the halt/resume boundary lands
     /// on an exact second, matching hardware.
     pub(crate) fn rtc_tick(&mut self, cycles: u64, kind: RtcTickKind) {
-        if cycles == 0 {
+        // In host-clock mode `sync_rtc_host_clock` owns the counters.
+        if cycles == 0 || self.rtc_host_anchor.is_some() {
             return;
         }
         match kind {
@@ -96,9 +96,9 @@ impl Cartridge {
                     advanced = true;
                 }
                 if advanced {
-                    // Stream the advanced clock to the `.rtc` sidecar (no-op
-                    // without one, keeping the test path I/O- and
-                    // wall-clock-free).
+                    // Stream the advanced clock to the `.rtc` sidecar and
+                    // `.sav` footer (no-op without them, keeping the test path
+                    // I/O- and wall-clock-free).
                     self.flush_rtc_file();
                 }
             }
@@ -371,6 +371,72 @@ impl Cartridge {
             _ => {}
         }
     }
+    /// Drive the clock from the host wall clock instead of emulated cycles, so
+    /// it keeps real time through pauses, fast-forward, and slow hosts (the
+    /// way a cart's own crystal ignores the console). Off by default: the
+    /// cycle-derived clock is what keeps replays and tests deterministic.
+    /// Enabling anchors the counters at "now"; the frontend then calls
+    /// [`sync_rtc_host_clock`](Self::sync_rtc_host_clock) regularly. No-op for
+    /// carts without an RTC.
+    pub fn set_rtc_host_clock(&mut self, enabled: bool) {
+        if !self.has_rtc() {
+            return;
+        }
+        match (enabled, self.rtc_host_anchor) {
+            (true, None) => {
+                self.rtc_host_anchor = Some(Self::unix_now());
+                self.rtc_cycle_accum = 0;
+                self.huc3_rtc.accum = 0;
+            }
+            (false, Some(_)) => self.rtc_host_anchor = None,
+            _ => {}
+        }
+    }
+    /// Whether the clock currently follows the host wall clock.
+    pub fn rtc_host_clock(&self) -> bool {
+        self.rtc_host_anchor.is_some()
+    }
+    /// Host-clock mode: advance the counters by the whole real seconds elapsed
+    /// since the last sync. No-op in the default cycle-derived mode.
+    pub fn sync_rtc_host_clock(&mut self) {
+        if self.rtc_host_anchor.is_some() {
+            self.rtc_host_clock_advance_to(Self::unix_now());
+        }
+    }
+    /// `sync_rtc_host_clock` against an explicit `now`. The anchor only moves
+    /// by the units the clock consumed (whole seconds for MBC3, whole minutes
+    /// for HuC-3), so frequent syncs never drop the remainder. MBC3 HALT
+    /// holds the counters but lets the anchor move (time spent halted is not
+    /// banked); the day counter's carry flag is set by the cascade as usual.
+    pub(super) fn rtc_host_clock_advance_to(&mut self, now: u64) {
+        let Some(anchor) = self.rtc_host_anchor else {
+            return;
+        };
+        // Host clock stepped backwards: re-anchor rather than run the clock
+        // backwards or freeze it until the host catches up.
+        if now < anchor {
+            self.rtc_host_anchor = Some(now);
+            return;
+        }
+        let elapsed = now - anchor;
+        let consumed = match self.rtc_kind() {
+            RtcTickKind::Mbc3 => {
+                if self.rtc.days_high & 0x40 == 0 {
+                    self.mbc3_rtc_advance_seconds(elapsed);
+                }
+                elapsed
+            }
+            RtcTickKind::HuC3 => {
+                self.huc3_rtc_advance_minutes(elapsed / 60);
+                elapsed - elapsed % 60
+            }
+            RtcTickKind::None => return,
+        };
+        if consumed > 0 {
+            self.rtc_host_anchor = Some(anchor + consumed);
+            self.flush_rtc_file();
+        }
+    }
     /// Restore RTC state from a blob and apply wall-clock catch-up. A zero
     /// timestamp (writer had no wall clock, e.g. an older rustyboi
     /// RETRO_MEMORY_RTC dump) or one from the future (host clock skew)
@@ -400,23 +466,11 @@ impl Cartridge {
             .map(|d| d.as_secs())
             .unwrap_or(0)
     }
-    /// `.rtc` sidecar path: the ROM path with its extension replaced (same
-    /// derivation as the `.sav`, so the two land side by side).
-    pub(super) fn get_rtc_file_path(&self) -> Option<String> {
-        self.rom_path.as_ref().map(|path| {
-            let mut rtc_path = path.clone();
-            if let Some(dot_pos) = rtc_path.rfind('.') {
-                rtc_path.truncate(dot_pos);
-            }
-            rtc_path.push_str(".rtc");
-            rtc_path
-        })
-    }
-    /// A de-facto RTC blob appended to the `.sav`, if the file is
-    /// exactly RAM+blob sized. Read-only interop: the `.rtc` sidecar is
-    /// canonical for us and the footer is never (re)written, but a save
-    /// imported from other tools restores its clock on first load.
-    pub(super) fn read_sav_rtc_footer(&self) -> Option<Vec<u8>> {
+    /// The de-facto RTC blob appended to a `.sav` image, if `data` is exactly
+    /// RAM+blob sized for this cart's clock. Other tools (and our own
+    /// `flush_rtc_file`) write it there, so a save moved between emulators
+    /// carries its clock along.
+    pub(super) fn sav_rtc_footer<'a>(&self, data: &'a [u8]) -> Option<&'a [u8]> {
         let expected: &[usize] = match self.get_cartridge_type() {
             CartridgeType::MBC3 { timer: true, .. } => {
                 &[Self::MBC3_RTC_BLOB_LEN, Self::MBC3_RTC_BLOB_LEN_LEGACY]
@@ -424,37 +478,46 @@ impl Cartridge {
             CartridgeType::HuC3 => &[Self::HUC3_RTC_BLOB_LEN],
             _ => return None,
         };
-        let sav_path = self.get_save_file_path()?;
-        let data = fs::read(Path::new(&sav_path)).ok()?;
-        let footer_len = data.len().checked_sub(self.ram_data.len())?;
-        if expected.contains(&footer_len) {
-            Some(data[self.ram_data.len()..].to_vec())
-        } else {
-            None
+        let ram_len = self.save_ram().len();
+        let footer_len = data.len().checked_sub(ram_len)?;
+        expected.contains(&footer_len).then(|| &data[ram_len..])
+    }
+    /// Stage the `.sav` RTC footer (right after the RAM image) as `blob`. The
+    /// next [`flush_save`](Self::flush_save) writes it and trims anything past
+    /// it, so the file stays exactly RAM+blob sized and other tools recognise
+    /// the footer; with the footer turned off the `.sav` is trimmed back to
+    /// the bare RAM image instead. No-op without an attached `.sav`.
+    fn write_sav_rtc_footer(&mut self, blob: &[u8]) {
+        let blob = if self.rtc_format.footer() { blob } else { &[] };
+        if let Some(file) = self.save_file.as_mut() {
+            file.write_footer(blob);
         }
     }
     /// Attach the `.rtc` sidecar (disk-load path only): restore persisted RTC
     /// state with wall-clock catch-up and keep the file open for streaming
-    /// rewrites as the clock advances. When no sidecar exists, fall back to a
-    /// `.sav` RTC footer, then create the sidecar. No-op without an RTC, for
-    /// host-managed carts, and for in-memory carts (no `rom_path`).
+    /// rewrites as the clock advances. An existing sidecar wins over the `.sav`
+    /// footer already restored by `attach_save_file_at`, whatever the
+    /// [`RtcFormat`]: footer-only just never creates one, and keeps one left
+    /// from an earlier run current so it can't later win with a stale clock.
+    /// Without one, the sidecar is created from the restored state. No-op
+    /// without an RTC, for host-managed carts, and for in-memory carts (no
+    /// `save_base`).
     pub(super) fn attach_rtc_sidecar(&mut self) -> Result<(), io::Error> {
         if !self.has_rtc() || self.host_managed_saves {
             return Ok(());
         }
-        let Some(rtc_path) = self.get_rtc_file_path() else {
+        // Same derivation as the `.sav`, so the two land side by side.
+        let Some(rtc_path) = self.sidecar_path("rtc") else {
             return Ok(());
         };
-        let rtc_path = Path::new(&rtc_path);
+        let rtc_path = rtc_path.as_path();
         if rtc_path.exists() {
             let data = fs::read(rtc_path)?;
             if self.rtc_restore_with_catch_up(&data) {
                 println!("Loaded RTC file: {}", rtc_path.display());
             }
-        } else if let Some(footer) = self.read_sav_rtc_footer()
-            && self.rtc_restore_with_catch_up(&footer)
-        {
-            println!("Loaded RTC footer from existing save file");
+        } else if !self.rtc_format.sidecar() {
+            return Ok(());
         }
         self.rtc_file = Some(
             OpenOptions::new()
@@ -467,12 +530,13 @@ impl Cartridge {
         self.flush_rtc_file();
         Ok(())
     }
-    /// Rewrite the `.rtc` sidecar with the current state stamped with the
-    /// current wall clock. No-op unless a sidecar is attached, so the
-    /// deterministic test path performs no I/O and never reads the host
-    /// clock. I/O errors are swallowed like the `.sav` streaming writes.
+    /// Rewrite the `.rtc` sidecar, and stage the `.sav` footer for the next
+    /// save flush, with the current state stamped with the current wall clock.
+    /// No-op unless one of the files is attached, so the deterministic test
+    /// path performs no I/O and never reads the host clock. I/O errors are
+    /// swallowed like the `.sav` streaming writes.
     pub(super) fn flush_rtc_file(&mut self) {
-        if self.rtc_file.is_none() {
+        if self.rtc_file.is_none() && self.save_file.is_none() {
             return;
         }
         let Some(blob) = self.rtc_serialize(Self::unix_now()) else {
@@ -483,6 +547,7 @@ impl Cartridge {
             let _ = file.write_all(&blob);
             let _ = file.flush();
         }
+        self.write_sav_rtc_footer(&blob);
     }
     /// True if this cartridge has a real-time clock (MBC3 timer or HuC-3).
     /// Gates the bus-driven `rtc_tick` path.
@@ -0,0 +1,102 @@
+//! The attached battery `.sav`: a write-back cache over the sidecar so SRAM
+//! traffic never touches the disk byte by byte.
+//!
+//! Games that stream to SRAM (Pokémon's box shuffles, the Camera's album)
+//! write thousands of bytes in a burst. The sidecar keeps its own copy of the
+//! image and marks the pages a write touched; [`SaveFile::flush`] writes just
+//! those pages back. The RTC footer past the image is staged the same way and
+//! rides along with the next flush. The session flushes on a timer and on
+//! demand, and the handle flushes itself when dropped, so a clean exit never
+//! loses a write.
+
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Granularity of dirty tracking: one flag per 256 bytes (an MBC2 array is
+/// two pages, a 128 KiB album 512).
+const PAGE_SIZE: usize = 0x100;
+
+pub(super) struct SaveFile {
+    file: File,
+    image: Vec<u8>,
+    dirty: Vec<bool>,
+    /// RTC footer waiting for the next flush, if the clock moved since the last.
+    footer: Option<Vec<u8>>,
+}
+
+impl SaveFile {
+    /// Wrap an open sidecar whose first `image.len()` bytes already hold
+    /// `image`.
+    pub(super) fn new(file: File, image: &[u8]) -> Self {
+        Self {
+            file,
+            image: image.to_vec(),
+            dirty: vec![false; image.len().div_ceil(PAGE_SIZE)],
+            footer: None,
+        }
+    }
+
+    /// Stage `bytes` at `offset` for the next flush.
+    pub(super) fn write(&mut self, offset: usize, bytes: &[u8]) {
+        let end = (offset + bytes.len()).min(self.image.len());
+        if offset >= end {
+            return;
+        }
+        self.image[offset..end].copy_from_slice(&bytes[..end - offset]);
+        for page in offset / PAGE_SIZE..end.div_ceil(PAGE_SIZE) {
+            self.dirty[page] = true;
+        }
+    }
+
+    /// Stage a whole new image (an import or a freshly loaded save).
+    pub(super) fn replace(&mut self, image: &[u8]) {
+        self.write(0, image);
+    }
+
+    /// Whether any staged write has not reached the disk yet.
+    pub(super) fn is_dirty(&self) -> bool {
+        self.footer.is_some() || self.dirty.iter().any(|&d| d)
+    }
+
+    /// Write every dirty page, then any staged footer, back to the sidecar.
+    /// Runs of adjacent dirty pages go out as one write.
+    pub(super) fn flush(&mut self) -> io::Result<()> {
+        let mut page = 0;
+        while page < self.dirty.len() {
+            if !self.dirty[page] {
+                page += 1;
+                continue;
+            }
+            let first = page;
+            while page < self.dirty.len() && self.dirty[page] {
+                page += 1;
+            }
+            let start = first * PAGE_SIZE;
+            let end = (page * PAGE_SIZE).min(self.image.len());
+            self.file.seek(SeekFrom::Start(start as u64))?;
+            self.file.write_all(&self.image[start..end])?;
+            self.dirty[first..page].fill(false);
+        }
+        if let Some(blob) = &self.footer {
+            let at = self.image.len() as u64;
+            self.file.seek(SeekFrom::Start(at))?;
+            self.file.write_all(blob)?;
+            self.file.set_len(at + blob.len() as u64)?;
+            self.footer = None;
+        }
+        self.file.flush()
+    }
+
+    /// Stage `blob` as everything past the RAM image (the RTC footer); the
+    /// flush writes it and trims the file to end there. Only the newest blob
+    /// is kept, so a clock ticking every second costs no disk traffic.
+    pub(super) fn write_footer(&mut self, blob: &[u8]) {
+        self.footer = Some(blob.to_vec());
+    }
+}
+
+impl Drop for SaveFile {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
@@ -1,6 +1,7 @@
 use crate::memory::Addressable;
 use crate::memory::mmio::Mmio;
 use crate::ppu::{self, Ppu};
+use super::scheduler::{Scheduler, Source};
 use std::ops::{Deref, DerefMut};
 
 /// One CPU M-cycle in master cc. Always 4, at either speed (a double-speed cc is
@@ -65,10 +66,10 @@ impl<'a> Bus<'a> {
     }
 
     /// Advance every peripheral by exactly one dot (one `master_cc`). This is the
-    /// per-cc resolution primitive shared by the per-dot crank and the event-loop
-    /// driver (`run_to`); it steps each peripheral in a fixed order so both paths
-    /// resolve identically. The per-instruction `dot`/`ticked` counters are NOT
-    /// touched here — callers own that bookkeeping.
+    /// per-cc resolution primitive behind the event-loop driver's world dots
+    /// (`run_to`); it steps each peripheral in a fixed order. The
+    /// per-instruction `dot`/`ticked` counters are NOT touched here — callers
+    /// own that bookkeeping.
     fn resolve_one_dot(&mut self) {
         self.mmio.step_timer();
         self.mmio.step_serial();
@@ -267,153 +268,167 @@ impl<'a> Bus<'a> {
     /// including) that cc. The per-instruction `dot`/`ticked` counters are
     /// advanced by the number of dots actually resolved so `tick_remaining` and
     /// the PPU's per-instruction `dot` semantics are preserved.
-    fn run_to(&mut self, target_cc: u64) {
-        self.run_to_min_event(target_cc);
-    }
-
-    /// Min-event-jump driver: advance `master_cc` toward `target_cc`, jumping over
-    /// idle spans in one step and resolving dot-by-dot otherwise.
     ///
-    /// The renderer / PPU / OAM-DMA / HDMA / powered-APU are per-dot stateful
-    /// machines (mode edges, duty/freq counters, period-edge detection), so while
-    /// any of them is live the loop resolves dot-by-dot (`resolve_one_dot`). Over
-    /// IDLE spans (`Mmio::idle_bulk_skippable`: LCD off, no DMA/HDMA, APU off,
-    /// serial idle) only the timer and serial advance, and both are closed-form
-    /// over the span, so the whole idle span jumps in one `bulk_advance_idle` to
-    /// the next scheduled event cc (the next timer-overflow delivery — the only
-    /// event that can fire while idle) or, failing that, clamped to `target_cc`
-    /// where the CPU's own access boundary re-evaluates the world.
-    fn run_to_min_event(&mut self, target_cc: u64) {
-        while self.mmio.master_cc() < target_cc {
-            // The PPU's LCD-off transition (set `disabled`, reset the pipeline,
-            // LY=0) is applied lazily inside `ppu.step()` on the first dot after
-            // the enable bit clears. `idle_bulk_skippable` keys on the LCDC
-            // register bit, so a disable followed immediately by an idle span
-            // would jump over that first dot and leave the PPU stuck in its
-            // pre-disable running state (stale LY, never re-initializing on the
-            // next enable). Run one real dot first so the disable is processed at
-            // the exact dot the per-dot path would have, then allow the skip.
-            if !self.ppu.is_lcd_disabled() && !self.mmio.lcd_display_enabled() {
-                self.resolve_one_dot();
-                self.ticked += 1;
-                continue;
+    /// Event-driven (`cpu::scheduler`): each source posts the cc of its next
+    /// event, the world jumps to the earliest, and only that dot is stepped.
+    /// A PPU event steps the PPU family alone (`ppu_dot`); any other steps the
+    /// whole world (`full_dot`) and re-posts the world sources.
+    fn run_to(&mut self, target_cc: u64) {
+        if self.mmio.master_cc() >= target_cc {
+            return;
+        }
+        #[cfg(test)]
+        if super::scheduler::per_dot_reference() {
+            while self.mmio.master_cc() < target_cc {
+                self.full_dot();
             }
-            if self.mmio.idle_bulk_skippable() {
-                // Jump straight to the next event the timer can raise (an overflow
-                // IRQ delivery) or, if none, to the target. The timer overflow is
-                // delivered at its absolute fire cc inside `bulk_advance_idle`, so
-                // landing exactly there fires it at the identical cc the per-dot
-                // path would have. Clamp to target so the CPU access boundary
-                // re-checks the world (e.g. a freshly written FF40 turning the LCD
-                // on) before we skip past it.
-                let next_event = self
-                    .mmio
-                    .next_timer_overflow_fire_cc()
-                    .filter(|&cc| cc > self.mmio.master_cc())
-                    .map(|cc| cc.min(target_cc))
-                    .unwrap_or(target_cc);
-                let span = next_event.wrapping_sub(self.mmio.master_cc());
-                self.mmio.bulk_advance_idle(next_event);
-                self.ticked += span as u32;
-            } else if self.try_quiet_span(target_cc) {
-                // Quiet-span fast loop consumed a chunk of the span; the
-                // while condition re-checks the world at its end.
-            } else {
-                let stall_before = self.mmio.peek_dma_stall();
-                self.resolve_one_dot();
-                self.ticked += 1;
-                // Event-interleaved HDMA transfer. A block that just fired in
-                // `step_hdma` queued its transfer cc as `pending_dma_stall` (the
-                // CPU pays it at a LATER step, so the PPU otherwise catches up only
-                // then and the resume read sees the un-advanced, mode-3-locked
-                // line). Hardware advances all peripherals through the transfer cc
-                // in lockstep at fire time. Tick the world through the just-queued
-                // transfer NOW (consuming the stall) so a same-instruction resume
-                // read after the block observes the extended line. Scoped to
-                // `hdma_resume_lockstep_window` — armed only at a Requested-context
-                // (multi-block) IME-off HALT-bug unhalt, so normal m0-edge blocks
-                // keep the deferred-stall path.
-                if self.mmio.hdma_resume_lockstep_window() {
-                    let stall_after = self.mmio.peek_dma_stall();
-                    let delta = stall_after.saturating_sub(stall_before);
-                    if delta > 0 {
-                        self.mmio.reduce_dma_stall(delta);
-                        self.mmio.set_hdma_lockstep_active(true);
-                        for _ in 0..delta {
-                            self.resolve_one_dot();
-                            self.ticked += 1;
-                        }
-                        self.mmio.set_hdma_lockstep_active(false);
-                    }
+            return;
+        }
+        let mut events = Scheduler::new(target_cc);
+        self.post_world_events(&mut events);
+        loop {
+            let parked = self.ppu_parked();
+            self.post_ppu_event(&mut events, parked);
+            let (source, due) = events.next();
+            self.jump_to(due - 1, parked);
+            match source {
+                Source::Target => return,
+                Source::Ppu => self.ppu_dot(),
+                _ => {
+                    self.full_dot();
+                    self.post_world_events(&mut events);
                 }
             }
         }
     }
 
-    /// Quiet-span fast loop: when the only live per-dot machines are the PPU
-    /// family (+CGB HDMA tracking, RTC, t-phase) and the timer is a pure
-    /// abs_cc bump until a proven event bound, the rest of
-    /// `resolve_one_dot`'s per-dot fan-out (serial/joypad/DMA checks, timer
-    /// delivery/FS-edge checks) is hoisted out. Byte-identical: every skipped
-    /// step is a proven no-op over the span, and the excluded states only
-    /// change at CPU access boundaries or at ccs beyond the bound. Returns
-    /// whether a span was consumed (false = fall back to a single fully
-    /// checked dot).
-    fn try_quiet_span(&mut self, target_cc: u64) -> bool {
-        let quiet_end = self.mmio.quiet_span_end(target_cc);
-        let cc_now = self.mmio.master_cc();
-        if quiet_end <= cc_now {
-            return false;
+    /// Post the timer, OAM-DMA, serial and JOYP-filter events. The last three
+    /// have no closed form while live, so they post every dot.
+    fn post_world_events(&self, events: &mut Scheduler) {
+        let next = self.mmio.master_cc() + 1;
+        let every_dot = |live: bool| if live { next } else { u64::MAX };
+        events.post(Source::Timer, self.mmio.timer_event_cc().max(next));
+        events.post(Source::Dma, every_dot(self.mmio.oam_dma_ticking()));
+        events.post(Source::Serial, every_dot(self.mmio.serial_ticking()));
+        events.post(Source::Joypad, every_dot(self.mmio.joypad_filter_ticking()));
+    }
+
+    /// Whether the PPU family has nothing to do until a CPU write: LCD off with
+    /// the off-transition already applied, no delayed LCDC commit, and no HDMA
+    /// armed or draining (with the LCD off an armed HBlank DMA fires at once).
+    fn ppu_parked(&self) -> bool {
+        self.ppu.is_lcd_disabled()
+            && !self.mmio.lcd_display_enabled()
+            && !self.ppu.has_pending_lcdc_events()
+            && !self.mmio.has_pending_hdma_deferred()
+            && !(self.mmio.is_cgb_features_enabled()
+                && (self.mmio.hdma_is_enabled() || self.mmio.hdma_req_pending()))
+    }
+
+    /// Post the PPU's next event: none while parked, else the dot after its
+    /// inert run. Inert runs are whole render dots from an even t-phase (the
+    /// render grid), so the odd-phase sub-dot dispatches they cover are the
+    /// same bounded no-ops the inert proof covers; deferred HDMA writes need
+    /// their per-dot drain.
+    fn post_ppu_event(&mut self, events: &mut Scheduler, parked: bool) {
+        if parked {
+            events.post(Source::Ppu, u64::MAX);
+            return;
         }
-        let n = (quiet_end - cc_now) as u32;
-        let double_speed = self.mmio.is_double_speed_mode();
-        let cgb = self.mmio.is_cgb_features_enabled();
-        let ds_shift = double_speed as u32;
-        // Track the t-phase parity locally and batch the phase counter and
-        // the RTC advance to the span end: both are linear in dots and only
-        // observed at CPU access boundaries (RTC/camera registers resolve at
-        // reads; the phase counter's only consumer is the parity gate here).
-        let base_parity = (self.mmio.cpu_t_phase() & 1) as u32;
+        let now = self.mmio.master_cc();
+        let ds = self.mmio.is_double_speed_mode() as u32;
+        let inert = if self.ppu.maybe_inert_state()
+            && (ds == 0 || self.mmio.cpu_t_phase().is_multiple_of(2))
+            && !self.mmio.has_pending_hdma_deferred()
+        {
+            self.ppu.inert_render_dots(self.mmio)
+        } else {
+            0
+        };
+        events.post(Source::Ppu, now + ((inert as u64) << ds) + 1);
+    }
+
+    /// Jump every clock to `end` (a cc before the next event). The PPU skips
+    /// the render dots inside the span; at double speed an odd span ends on a
+    /// render dot, stepped for real.
+    fn jump_to(&mut self, end: u64, parked: bool) {
+        let now = self.mmio.master_cc();
+        if end <= now {
+            return;
+        }
+        let span = end - now;
+        if parked {
+            self.mmio.advance_world_to(end);
+            self.ticked += span as u32;
+            return;
+        }
+        let ds = self.mmio.is_double_speed_mode() as u32;
+        let render_dots = span >> ds;
+        if render_dots > 0 {
+            self.ppu.skip_inert_dots(self.mmio, render_dots as u32);
+            self.mmio.advance_world_to(now + (render_dots << ds));
+            self.ticked += (render_dots << ds) as u32;
+        }
+        if self.mmio.master_cc() < end {
+            self.ppu_dot();
+        }
+    }
 
-        let mut i = 0u32;
-        while i < n {
-            // Inert-PPU fast-forward: whole render dots from an even t-phase
-            // (the render grid), so the skipped odd-phase sub-dot dispatches
-            // are the same bounded no-ops the skip proof covers. Deferred
-            // HDMA writes need their per-dot drain, so no skip while pending.
-            let even = (base_parity + i) & 1 == 0;
-            if self.ppu.maybe_inert_state()
-                && (!double_speed || even)
-                && !self.mmio.has_pending_hdma_deferred()
-            {
-                let budget = (n - i) >> ds_shift;
-                let r = self.ppu.skip_inert_dots(self.mmio, budget);
-                if r > 0 {
-                    let m = r << ds_shift;
-                    self.mmio.bump_master_cc_by(m as u64);
-                    i += m;
-                    continue;
+    /// Step the whole world one dot, including the HDMA resume lockstep.
+    fn full_dot(&mut self) {
+        let stall_before = self.mmio.peek_dma_stall();
+        self.resolve_one_dot();
+        self.ticked += 1;
+        // Event-interleaved HDMA transfer. A block that just fired in
+        // `step_hdma` queued its transfer cc as `pending_dma_stall` (the
+        // CPU pays it at a LATER step, so the PPU otherwise catches up only
+        // then and the resume read sees the un-advanced, mode-3-locked
+        // line). Hardware advances all peripherals through the transfer cc
+        // in lockstep at fire time. Tick the world through the just-queued
+        // transfer NOW (consuming the stall) so a same-instruction resume
+        // read after the block observes the extended line. Scoped to
+        // `hdma_resume_lockstep_window` — armed only at a Requested-context
+        // (multi-block) IME-off HALT-bug unhalt, so normal m0-edge blocks
+        // keep the deferred-stall path.
+        if self.mmio.hdma_resume_lockstep_window() {
+            let stall_after = self.mmio.peek_dma_stall();
+            let delta = stall_after.saturating_sub(stall_before);
+            if delta > 0 {
+                self.mmio.reduce_dma_stall(delta);
+                self.mmio.set_hdma_lockstep_active(true);
+                for _ in 0..delta {
+                    self.resolve_one_dot();
+                    self.ticked += 1;
                 }
+                self.mmio.set_hdma_lockstep_active(false);
             }
-            self.mmio.bump_master_cc_one();
-            if !double_speed || even {
-                self.ppu.step_scheduled_stat_events(self.mmio);
-                self.ppu.step(self.mmio);
-            } else {
-                self.ppu.step_subdot(self.mmio);
-            }
-            if cgb && self.mmio.master_cc() >= self.mmio.hdma_tracker_sleep_until() {
-                let period = self.ppu.hdma_period(double_speed);
-                self.mmio.step_hdma(period);
-            }
-            self.mmio.step_hdma_deferred();
-            self.ppu.step_lcdc_events(self.mmio);
-            i += 1;
         }
-        self.mmio.tick_rtc(n as u64);
-        self.mmio.advance_cpu_t_phase_by(n as u64);
-        self.ticked += n;
-        true
+    }
+
+    /// Step one dot on which only the PPU family has work: the timer is a
+    /// raw clock bump (no world event is due, so `step_timer` would do no
+    /// more), serial/JOYP/OAM-DMA are idle, and the rest is `resolve_one_dot`
+    /// minus the DMA fetcher-bus publish (consumed only by a live OAM-DMA).
+    fn ppu_dot(&mut self) {
+        self.mmio.bump_master_cc_one();
+        let double_speed = self.mmio.is_double_speed_mode();
+        if !double_speed || self.mmio.cpu_t_phase().is_multiple_of(2) {
+            self.ppu.step_scheduled_stat_events(self.mmio);
+            self.ppu.step(self.mmio);
+        } else {
+            self.ppu.step_subdot(self.mmio);
+        }
+        if self.mmio.is_cgb_features_enabled()
+            && self.mmio.master_cc() >= self.mmio.hdma_tracker_sleep_until()
+        {
+            let period = self.ppu.hdma_period(double_speed);
+            self.mmio.step_hdma(period);
+        }
+        self.mmio.step_hdma_deferred();
+        self.ppu.step_lcdc_events(self.mmio);
+        self.mmio.tick_rtc(1);
+        self.mmio.advance_cpu_t_phase();
+        self.ticked += 1;
     }
 
     /// Tick one internal (non-memory) M-cycle, for opcodes that need their
@@ -1,6 +1,7 @@
 pub mod bus;
 pub mod sm83;
 mod opcodes;
+mod scheduler;
 pub mod registers;
 
 pub(crate) use bus::Bus;
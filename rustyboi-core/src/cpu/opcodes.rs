@@ -270,7 +270,7 @@ pub(super) fn stop(cpu: &mut cpu::SM83, mmio: &mut crate::cpu::Bus) -> u32 {
     // 1-byte (the byte after $10 executes as the next instruction), else 2-byte.
     let irq_pending = (mmio.peek(registers::INTERRUPT_FLAG)
         & mmio.peek(registers::INTERRUPT_ENABLE)
-        & 0x1F)
+        & registers::INTERRUPT_BITS)
         != 0;
     if irq_pending {
         // Rewind the operand skip above: 1-byte form.
@@ -456,11 +456,11 @@ pub(super) fn halt(cpu: &mut cpu::SM83, mmio: &mut crate::cpu::Bus) -> u32 {
     // executes and re-reads its own bytes (the double-read); when IME=1 the next
     // step's interrupt service undoes the prefetch (pc -= 1) so the return address
     // is the HALT itself, and HALT re-runs after the ISR. The pending test is
-    // `IE & IF & 0x1F != 0`, independent of IME.
+    // `IE & IF & INTERRUPT_BITS != 0`, independent of IME.
     // Pan Docs: halt bug — https://gbdev.io/pandocs/halt.html
     let if_reg = mmio.peek(registers::INTERRUPT_FLAG);
     let ie_reg = mmio.peek(registers::INTERRUPT_ENABLE);
-    if (if_reg & ie_reg & 0x1F) != 0 {
+    if (if_reg & ie_reg & registers::INTERRUPT_BITS) != 0 {
         // pc already points at the byte after HALT (the 0x76 fetch advanced it).
         // Fetch that byte WITHOUT advancing pc and mark it prefetched; the +4
         // charge is deferred to consumption. The fetch is a REAL bus read (PPU
@@ -20,6 +20,10 @@ pub(crate) enum InterruptFlag {
 pub const INTERRUPT_FLAG: u16 = 0xFF0F;
 pub const INTERRUPT_ENABLE: u16 = 0xFFFF;
 
+/// The five interrupt sources' bits. IF holds only these (its top three bits
+/// read 1); IE stores all eight, but only these can arm an interrupt.
+pub const INTERRUPT_BITS: u8 = 0x1F;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Registers {
     pub a: u8,
@@ -0,0 +1,286 @@
+//! The bus event scheduler. Every per-dot machine posts the master cc of its
+//! next event — the first dot whose step is not a plain clock bump — and
+//! `Bus::run_to` jumps the whole world to the earliest one, steps that dot for
+//! real, and re-posts. Between events nothing but the clocks advances:
+//!
+//! - `Timer`: the next overflow delivery or APU frame-sequencer edge
+//!   (`Timer::quiet_until`); the dots before it are a raw `abs_cc` bump.
+//! - `Dma`, `Serial`, `Joypad`: OAM-DMA (and the HDMA resume lockstep), an
+//!   active serial transfer or link peer, and the JOYP IRQ filter step every
+//!   dot while live and post nothing while idle.
+//! - `Ppu`: the dot after its inert run (`Ppu::inert_render_dots`), every
+//!   dot while it does real work, nothing while the LCD is off and parked.
+//! - `Target`: one past the cc the CPU asked to reach.
+//!
+//! The APU has no entry: it raises no interrupts and catches up lazily from
+//! the master cc at its own register and sample boundaries. The RTC and the
+//! CPU t-phase counter are linear in dots and advance with each jump.
+//!
+//! World events can only move at a world dot or a CPU access boundary, so they
+//! are re-posted after world dots only; the PPU re-posts after each of its own.
+
+/// One event source. Declaration order breaks ties: the target stops the
+/// run before any event past it, and a world dot also steps the PPU, so the
+/// PPU goes last.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum Source {
+    Target,
+    Timer,
+    Dma,
+    Serial,
+    Joypad,
+    Ppu,
+}
+
+const SOURCES: [Source; 6] = [
+    Source::Target,
+    Source::Timer,
+    Source::Dma,
+    Source::Serial,
+    Source::Joypad,
+    Source::Ppu,
+];
+
+/// Next-event master cc per source; `u64::MAX` = nothing scheduled.
+pub(super) struct Scheduler {
+    due: [u64; SOURCES.len()],
+}
+
+impl Scheduler {
+    /// A schedule for a run ending at `target_cc`, with nothing else posted.
+    pub(super) fn new(target_cc: u64) -> Self {
+        let mut events = Scheduler { due: [u64::MAX; SOURCES.len()] };
+        events.post(Source::Target, target_cc + 1);
+        events
+    }
+
+    /// Replace `source`'s pending event with one at `cc`.
+    #[inline]
+    pub(super) fn post(&mut self, source: Source, cc: u64) {
+        self.due[source as usize] = cc;
+    }
+
+    /// The earliest pending event.
+    #[inline]
+    pub(super) fn next(&self) -> (Source, u64) {
+        let mut next = (Source::Target, self.due[0]);
+        for (&source, &cc) in SOURCES.iter().zip(&self.due).skip(1) {
+            if cc < next.1 {
+                next = (source, cc);
+            }
+        }
+        next
+    }
+}
+
+// Test-only switch that makes `Bus::run_to` step every dot in full, the
+// reference the scheduler is checked against.
+#[cfg(test)]
+thread_local! {
+    static PER_DOT_REFERENCE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+#[cfg(test)]
+pub(super) fn per_dot_reference() -> bool {
+    PER_DOT_REFERENCE.with(|r| r.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PER_DOT_REFERENCE;
+    use crate::cartridge::Cartridge;
+    use crate::gb::{GB, Hardware};
+    use crate::input::ButtonState;
+
+    /// APU setup shared by every program: all of NR52/NR50/NR51 on, channel
+    /// 1 with sweep, a length counter and a falling envelope, channel 4 with
+    /// an envelope, so the frame sequencer has work on every edge.
+    #[rustfmt::skip]
+    const SOUND: &[u8] = &[
+        0xF3,             // di
+        0x31, 0xF0, 0xDF, // ld sp,$DFF0
+        0x3E, 0x80, 0xE0, 0x26, // NR52 = $80
+        0x3E, 0x77, 0xE0, 0x24, // NR50 = $77
+        0x3E, 0xFF, 0xE0, 0x25, // NR51 = $FF
+        0x3E, 0x11, 0xE0, 0x10, // NR10 = $11: sweep up
+        0x3E, 0xA0, 0xE0, 0x11, // NR11 = $A0: length 32
+        0x3E, 0xF1, 0xE0, 0x12, // NR12 = $F1: envelope down
+        0x3E, 0xC7, 0xE0, 0x14, // NR14 = $C7: trigger, length on
+        0x3E, 0xF3, 0xE0, 0x21, // NR42 = $F3
+        0x3E, 0x55, 0xE0, 0x22, // NR43 = $55
+        0x3E, 0x80, 0xE0, 0x23, // NR44 = $80: trigger
+        0x3E, 0x05, 0xE0, 0x07, // TAC = $05: 262 kHz
+    ];
+
+    /// The timer alone: TAC = 262 kHz with the APU left unpowered.
+    #[rustfmt::skip]
+    const TIMER: &[u8] = &[
+        0xF3,             // di
+        0x31, 0xF0, 0xDF, // ld sp,$DFF0
+        0x3E, 0x05, 0xE0, 0x07, // TAC = $05: 262 kHz
+    ];
+
+    /// LCD on with BG and OBJ, LYC/mode-2/mode-0 STAT interrupts, then `ei`.
+    #[rustfmt::skip]
+    const RASTER: &[u8] = &[
+        0x3E, 0x93, 0xE0, 0x40, // LCDC = $93
+        0x3E, 0x44, 0xE0, 0x45, // LYC = $44
+        0x3E, 0x68, 0xE0, 0x41, // STAT = $68
+        0x3E, 0x1F, 0xE0, 0xFF, // IE = all
+        0xFB,                   // ei
+    ];
+
+    /// SCX/WRAM churn with a short delay loop: the CPU is never idle.
+    #[rustfmt::skip]
+    const BUSY: &[u8] = &[
+        0xF0, 0x43,       // loop: ldh a,(SCX)
+        0x3C,             // inc a
+        0xE0, 0x43,       // ldh (SCX),a
+        0x21, 0x00, 0xC0, // ld hl,$C000
+        0x77,             // ld (hl),a
+        0x34,             // inc (hl)
+        0x06, 0x20,       // ld b,$20
+        0x05,             // inner: dec b
+        0x20, 0xFD,       // jr nz,inner
+        0x18, 0xEF,       // jr loop
+    ];
+
+    const HALT: &[u8] = &[0x76, 0x00, 0x18, 0xFC]; // halt; nop; jr -4
+
+    /// Serial transfer on the internal clock and JOYP select toggles each
+    /// pass, sleeping in between.
+    #[rustfmt::skip]
+    const LINK: &[u8] = &[
+        0x3E, 0x55, 0xE0, 0x01, // loop: SB = $55
+        0x3E, 0x81, 0xE0, 0x02, // SC = $81: start, internal clock
+        0x3E, 0x10, 0xE0, 0x00, // P1 = $10
+        0x3E, 0x20, 0xE0, 0x00, // P1 = $20
+        0x76, 0x00,             // halt; nop
+        0x18, 0xEC,             // jr loop
+    ];
+
+    /// Copy the OAM DMA routine (`ldh (DMA),a`, a 40-pass wait, `ret`) into
+    /// HRAM at $FF80.
+    #[rustfmt::skip]
+    const DMA_ROUTINE: &[u8] = &[
+        0x3E, 0xE0, 0xE0, 0x80, 0x3E, 0x46, 0xE0, 0x81,
+        0x3E, 0x3E, 0xE0, 0x82, 0x3E, 0x28, 0xE0, 0x83,
+        0x3E, 0x3D, 0xE0, 0x84, 0x3E, 0x20, 0xE0, 0x85,
+        0x3E, 0xFD, 0xE0, 0x86, 0x3E, 0xC9, 0xE0, 0x87,
+    ];
+
+    /// CGB double speed: KEY1 = 1, STOP.
+    const DOUBLE_SPEED: &[u8] = &[0x3E, 0x01, 0xE0, 0x4D, 0x10, 0x00];
+
+    const LCD_OFF: &[u8] = &[0xAF, 0xE0, 0x40, 0x3E, 0x04, 0xE0, 0xFF, 0xFB]; // LCDC = 0, IE = timer, ei
+
+    /// Interrupt handlers: VBlank rotates BGP and (with `dma`) runs OAM DMA
+    /// from $C100 and an HBlank HDMA of 8 blocks, STAT bumps SCY, the rest
+    /// return.
+    fn rom(cgb: bool, dma: bool, program: &[&[u8]]) -> Vec<u8> {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // nop; jp $0150
+        rom[0x143] = if cgb { 0x80 } else { 0x00 };
+        rom[0x40..0x43].copy_from_slice(&[0xC3, 0x00, 0x02]); // jp $0200
+        #[rustfmt::skip]
+        rom[0x48..0x50].copy_from_slice(&[
+            0xF5, 0xF0, 0x42, 0x3C, 0xE0, 0x42, 0xF1, 0xD9, // SCY += 1
+        ]);
+        for vector in [0x50, 0x58, 0x60] {
+            rom[vector] = 0xD9; // reti
+        }
+        let mut vblank = vec![0xF5, 0xF0, 0x47, 0x0F, 0xE0, 0x47]; // BGP = rrca(BGP)
+        if dma {
+            vblank.extend_from_slice(&[0x3E, 0xC1, 0xCD, 0x80, 0xFF]); // ld a,$C1; call $FF80
+            #[rustfmt::skip]
+            vblank.extend_from_slice(&[
+                0x3E, 0xC0, 0xE0, 0x51, 0xAF, 0xE0, 0x52, // HDMA1/2 = $C000
+                0x3E, 0x88, 0xE0, 0x53, 0xAF, 0xE0, 0x54, // HDMA3/4 = $8800
+                0x3E, 0x87, 0xE0, 0x55,                   // HDMA5 = $87: HBlank, 8 blocks
+            ]);
+        }
+        vblank.extend_from_slice(&[0xF1, 0xD9]); // pop af; reti
+        rom[0x200..0x200 + vblank.len()].copy_from_slice(&vblank);
+        let code: Vec<u8> = program.concat();
+        rom[0x150..0x150 + code.len()].copy_from_slice(&code);
+        rom
+    }
+
+    fn machine(hardware: Hardware, rom: &[u8], scanline: bool) -> GB {
+        let mut gb = GB::new(hardware);
+        gb.insert(Cartridge::from_bytes(rom).unwrap());
+        gb.skip_bios();
+        gb.set_scanline_ppu(scanline);
+        // Seed WRAM (the DMA/HDMA source) and VRAM with a pattern.
+        for i in 0..0x200u16 {
+            gb.write_memory(0xC000 + i, (i as u8).wrapping_mul(37));
+        }
+        gb
+    }
+
+    /// Run the same program through the scheduler and through the per-dot
+    /// reference driver and require byte-identical savestates (and
+    /// presented frames) at every frame boundary.
+    fn assert_matches_per_dot(name: &str, hardware: Hardware, rom: &[u8], scanline: bool, frames: usize) {
+        let mut scheduled = machine(hardware, rom, scanline);
+        let mut reference = machine(hardware, rom, scanline);
+        for frame in 0..frames {
+            let input = ButtonState { a: frame % 3 == 0, down: frame % 5 == 0, ..Default::default() };
+            scheduled.set_input_state(input);
+            reference.set_input_state(input);
+            let (a, _) = scheduled.run_until_frame(true);
+            PER_DOT_REFERENCE.with(|r| r.set(true));
+            let (b, _) = reference.run_until_frame(true);
+            PER_DOT_REFERENCE.with(|r| r.set(false));
+            assert_eq!(scheduled.master_cc(), reference.master_cc(), "{name}: frame {frame}: cc");
+            assert!(a.rgb() == b.rgb(), "{name}: frame {frame}: picture differs");
+            assert!(
+                scheduled.to_state_bytes().unwrap() == reference.to_state_bytes().unwrap(),
+                "{name}: frame {frame}: state differs"
+            );
+        }
+    }
+
+    #[test]
+    fn raster_programs_match_per_dot_stepping() {
+        for hardware in [Hardware::DMG, Hardware::CGB] {
+            let cgb = hardware == Hardware::CGB;
+            for scanline in [false, true] {
+                let busy = rom(cgb, false, &[SOUND, RASTER, BUSY]);
+                assert_matches_per_dot("busy", hardware, &busy, scanline, 12);
+                let halt = rom(cgb, false, &[SOUND, RASTER, HALT]);
+                assert_matches_per_dot("halt", hardware, &halt, scanline, 12);
+            }
+        }
+    }
+
+    #[test]
+    fn lcd_off_programs_match_per_dot_stepping() {
+        for hardware in [Hardware::DMG, Hardware::CGB] {
+            let cgb = hardware == Hardware::CGB;
+            let halt = rom(cgb, false, &[SOUND, LCD_OFF, HALT]);
+            assert_matches_per_dot("lcd off", hardware, &halt, false, 8);
+            let silent = rom(cgb, false, &[TIMER, LCD_OFF, HALT]);
+            assert_matches_per_dot("lcd off, apu off", hardware, &silent, false, 8);
+        }
+    }
+
+    #[test]
+    fn dma_serial_and_joypad_match_per_dot_stepping() {
+        for hardware in [Hardware::DMG, Hardware::CGB] {
+            let cgb = hardware == Hardware::CGB;
+            let link = rom(cgb, true, &[SOUND, DMA_ROUTINE, RASTER, LINK]);
+            assert_matches_per_dot("link", hardware, &link, false, 12);
+        }
+    }
+
+    #[test]
+    fn double_speed_hdma_matches_per_dot_stepping() {
+        for scanline in [false, true] {
+            let busy = rom(true, true, &[DOUBLE_SPEED, SOUND, DMA_ROUTINE, RASTER, BUSY]);
+            assert_matches_per_dot("double speed", Hardware::CGB, &busy, scanline, 12);
+            let halt = rom(true, true, &[DOUBLE_SPEED, SOUND, DMA_ROUTINE, RASTER, HALT]);
+            assert_matches_per_dot("double speed halt", Hardware::CGB, &halt, scanline, 12);
+        }
+    }
+}
@@ -63,6 +63,11 @@ pub(crate) struct SM83 {
     /// ordering from test-ROM refs.
     #[serde(default)]
     pub(crate) hdma_dma_due_defer_service: bool,
+    /// Whether the last `step` executed an opcode (not a halted, stalled or
+    /// interrupt-dispatch step). Read by `GB` right after the step to count
+    /// instructions; never carried across steps.
+    #[serde(skip)]
+    pub(crate) retired: bool,
 }
 
 impl Default for SM83 {
@@ -85,6 +90,7 @@ impl SM83 {
             m2_halt_stall_charged: false,
             cgb_lcd_halt_stall_charged: false,
             hdma_dma_due_defer_service: false,
+            retired: false,
         }
     }
 
@@ -97,6 +103,7 @@ impl SM83 {
     }
 
     pub fn step(&mut self, mmio: &mut crate::cpu::Bus) -> u32 {
+        self.retired = false;
         // While stalled after a CGB STOP-speed-switch, advance peripherals in
         // small slices without fetching instructions. CPU fetch is suspended for
         // 0x20000 + 4 T-cycles after STOP completes; the per-cycle peripheral loop
@@ -695,6 +702,7 @@ impl SM83 {
             if std::mem::take(&mut self.halt_bug_prefetch) {
                 mmio.tick_opcode_fetch_mcycle();
             }
+            self.retired = true;
             cycles += self.execute(op, mmio);
             mmio.mmio.set_hdma_dma_due_write_cc_bias(0);
             self.apply_ime_delay();
@@ -727,6 +735,7 @@ impl SM83 {
         if std::mem::take(&mut self.halt_bug_prefetch) {
             mmio.tick_opcode_fetch_mcycle();
         }
+        self.retired = true;
         cycles += self.execute(op, mmio);
         self.apply_ime_delay();
         mmio.set_hdma_resume_lockstep_window(false);
@@ -893,7 +902,10 @@ impl SM83 {
     /// `Mmio::read` takes `&self`, so no read in the chain can observe a value a
     /// later one would not.
     fn pending_interrupt(&self, mmio: &memory::mmio::Mmio, exclude: u8) -> Option<registers::InterruptFlag> {
-        let armed = mmio.read(registers::INTERRUPT_ENABLE) & mmio.read(registers::INTERRUPT_FLAG) & !exclude;
+        let armed = mmio.read(registers::INTERRUPT_ENABLE)
+            & mmio.read(registers::INTERRUPT_FLAG)
+            & registers::INTERRUPT_BITS
+            & !exclude;
         INTERRUPT_PRIORITY.into_iter().find(|flag| armed & (*flag as u8) != 0)
     }
 
@@ -152,6 +152,17 @@ pub(crate) fn cartridge_compatibility(hardware: Hardware, cartridge: &cartridge:
     }
 }
 
+/// Machine time since power-on or the last reset, for scripts, movies and
+/// determinism checks that need an exact point on the emulated timeline
+/// rather than a frame number.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub(crate) struct Counters {
+    /// CPU T-cycles (4 per M-cycle, at the current CPU speed).
+    pub(crate) cycles: u64,
+    /// Instructions executed.
+    pub(crate) instructions: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct GB {
     cpu: cpu::SM83,
@@ -184,12 +195,34 @@ pub struct GB {
     // not survive a savestate (the state already carries the applied palette).
     #[serde(skip, default)]
     forced_compat_palette: Option<u8>,
+    // The APU's output resampler (its FIR history and phase). Host output,
+    // not machine state, so it is not serialized: a restore starts a fresh
+    // one, and a restore over a running machine carries the live one across
+    // (`carry_output_from`). Format-5 and older states still hold its bytes.
+    #[serde(skip_serializing, deserialize_with = "legacy_resampler")]
+    resampler: audio::Resampler,
+    // Elapsed machine time. Serialized last, so the savestate upgrader that
+    // introduced it only had to append its bytes; new serialized fields go
+    // after it.
+    counters: Counters,
     // `+ Send` so a cloned GB (whose audio_output is always None) can be moved
     // to a worker thread for off-thread savestate serialization with NO unsafe:
     // GB is `Send` iff every field is, and this was the only field that wasn't.
     // Every AudioOutput sink (platform Output, session CaptureSink) is Send.
     #[serde(skip)]
     audio_output: Option<Box<dyn audio::AudioOutput + Send>>,
+    // Reused between instructions for the samples each one emits (usually 0
+    // or 1): refilling one buffer instead of allocating per sample keeps the
+    // ~44k allocations a second off the emulation thread.
+    #[serde(skip)]
+    audio_scratch: Vec<(f32, f32)>,
+}
+
+// The output resampler in a payload older than version 6 (see
+// `savestate::dropped_in`); a current payload carries nothing here.
+fn legacy_resampler<'de, D: serde::Deserializer<'de>>(d: D) -> Result<audio::Resampler, D::Error> {
+    crate::savestate::dropped_in::<audio::Resampler, D>(6, d)?;
+    Ok(audio::Resampler::default())
 }
 
 impl Clone for GB {
@@ -205,7 +238,10 @@ impl Clone for GB {
             skip_bios: self.skip_bios,
             breakpoints: self.breakpoints.clone(),
             forced_compat_palette: self.forced_compat_palette,
+            resampler: self.resampler.clone(),
+            counters: self.counters,
             audio_output: None, // Don't clone audio output - it will be recreated if needed
+            audio_scratch: Vec::new(),
         }
     }
 }
@@ -236,10 +272,13 @@ pub enum DmgPaletteChoice {
     #[default]
     Green,
     Pocket,
+    /// A user-supplied palette: four colours, lightest first. Shown exactly
+    /// as given, whatever the colour correction.
+    Custom([[u8; 3]; 4]),
 }
 
 impl DmgPaletteChoice {
-    /// All choices, in Settings-menu display order.
+    /// The built-in choices, in Settings-menu display order.
     pub const ALL: [DmgPaletteChoice; 3] =
         [Self::Grayscale, Self::Green, Self::Pocket];
 
@@ -260,6 +299,7 @@ impl DmgPaletteChoice {
     pub fn shades(self, correction: ppu::ColorCorrection) -> [[u8; 3]; 4] {
         use ppu::ColorCorrection::{Lcd, Linear};
         match (self, correction) {
+            (Self::Custom(shades), _) => shades,
             (Self::Grayscale, _) => [[255, 255, 255], [170, 170, 170], [85, 85, 85], [0, 0, 0]],
             // Classic DMG green, raw.
             (Self::Green, Linear) => {
@@ -291,6 +331,7 @@ impl DmgPaletteChoice {
             Self::Grayscale => "Grayscale",
             Self::Green => "Green",
             Self::Pocket => "Game Boy Pocket",
+            Self::Custom(_) => "Custom",
         }
     }
 
@@ -300,6 +341,7 @@ impl DmgPaletteChoice {
             Self::Grayscale => "grayscale",
             Self::Green => "green",
             Self::Pocket => "pocket",
+            Self::Custom(_) => "custom",
         }
     }
 
@@ -480,7 +522,10 @@ impl GB {
             sgb_palette: SgbPaletteChoice::default(),
             breakpoints: HashSet::new(),
             forced_compat_palette: None,
+            resampler: audio::Resampler::default(),
+            counters: Counters::default(),
             audio_output: None, // Audio will be enabled when needed
+            audio_scratch: Vec::with_capacity(16),
         }
     }
 
@@ -1162,17 +1207,63 @@ impl GB {
         self.mmio.has_sgb_firmware()
     }
 
+    /// Set the host audio output rate in Hz (44100 by default; 48000 and 96000
+    /// are the other rates frontends offer). Like the region, a host setting
+    /// rather than machine state: it is not saved and a savestate load
+    /// resets it, so the frontend re-applies it.
+    pub fn set_audio_sample_rate(&mut self, rate: u32) {
+        if rate != self.mmio.audio_sample_rate() {
+            self.mmio.set_audio_sample_rate(rate);
+        }
+    }
+
+    /// The host audio output rate in Hz.
+    pub fn audio_sample_rate(&self) -> u32 {
+        self.mmio.audio_sample_rate()
+    }
+
+    /// Turn the APU's output high-pass filter on or off. On by default; off
+    /// hands out the raw DAC mix, DC offset and all. A host preference, not
+    /// machine state: it is not saved and a savestate load resets it.
+    pub fn set_audio_high_pass(&mut self, on: bool) {
+        self.mmio.set_audio_high_pass(on);
+    }
+
+    /// Choose which of CH1..CH4 reach the host output (`false` mutes). Like
+    /// the high-pass switch, a host preference a savestate load resets.
+    pub fn set_audible_channels(&mut self, audible: [bool; 4]) {
+        self.mmio.set_audible_channels(audible);
+    }
+
     /// Engage the per-sample channel tap ([ch1..4], nr50, nr51, enabled) —
     /// recording/measurement companion to `enable_audio`.
     pub fn set_channel_tap(&mut self, on: bool) {
         self.mmio.set_channel_tap(on);
     }
 
+    /// Each APU channel's state for a debugger readout, CH1 to CH4.
+    pub fn apu_channel_states(&self) -> [audio::ChannelState; 4] {
+        self.mmio.apu_channel_states()
+    }
+
     /// Take tapped channel samples accumulated since the last drain.
     pub fn drain_channel_tap(&mut self) -> Vec<audio::ChannelSample> {
         self.mmio.drain_channel_tap()
     }
 
+    /// Capture every byte the link port shifts out (whatever is plugged in,
+    /// or nothing), for echoing a test ROM's serial report. Off by default;
+    /// a loaded savestate comes back with capture off.
+    pub fn set_serial_capture(&mut self, on: bool) {
+        self.mmio.set_serial_capture(on);
+    }
+
+    /// Take the bytes shifted out of the link port since the last drain
+    /// (empty while capture is off).
+    pub fn drain_serial_output(&mut self) -> Vec<u8> {
+        self.mmio.drain_serial_output()
+    }
+
     /// Whether this machine mixes digitally (AGB), which a holder of tap data
     /// needs in order to reconstruct the stereo mix — NR51's unrouted case
     /// differs. See `audio::Audio::mix_tap_sample`.
@@ -1228,6 +1319,7 @@ impl GB {
                 // STOP-wake semantics are asserted against raw master_cc by
                 // hardware tests; never leave the wake advance carried.
                 bus.flush_all_lag();
+                self.counters.cycles += 8;
                 8
             } else {
                 4
@@ -1254,6 +1346,8 @@ impl GB {
             let mut bus = cpu::Bus::new(&mut self.mmio, &mut self.ppu);
             let cycles = self.cpu.step(&mut bus);
             bus.tick_remaining(cycles);
+            self.counters.cycles += u64::from(cycles);
+            self.counters.instructions += u64::from(self.cpu.retired);
             // STOP freezes master_cc at the exact stop cc; never park the
             // stopping instruction's tail across the frozen window.
             if self.cpu.stopped {
@@ -1275,14 +1369,16 @@ impl GB {
         if !collect_audio {
             return;
         }
+        profile_span!(fine, "apu_samples");
         // In double speed mode, audio runs at normal speed, so we need to adjust the cycle count
         let audio_cycles = if is_double_speed { cycles / 2 } else { cycles };
-        let audio_samples = self.mmio.generate_audio_samples(audio_cycles);
+        self.audio_scratch.clear();
+        self.mmio.generate_audio_samples(audio_cycles, &mut self.resampler, &mut self.audio_scratch);
 
         // Send audio samples directly to output as they're generated
-        if !audio_samples.is_empty()
+        if !self.audio_scratch.is_empty()
             && let Some(audio_output) = &mut self.audio_output {
-                audio_output.add_samples(&audio_samples);
+                audio_output.add_samples(&self.audio_scratch);
         }
     }
 
@@ -1290,12 +1386,14 @@ impl GB {
     /// presented always-RGB [`Frame`], applying the DMG base palette + colour
     /// correction to a monochrome frame (colour frames are already corrected).
     fn presented_frame(&mut self) -> Frame {
+        profile_span!("ppu_frame_out");
+        // Resolved up front: the shade indices below borrow the PPU.
+        let shades = self
+            .sgb_presentation_shades()
+            .unwrap_or_else(|| self.dmg_palette.shades(self.ppu.cgb_color_conversion()));
         match self.ppu.get_frame(&self.mmio) {
             ppu::RenderedFrame::Color(rgb) => Frame(rgb),
             ppu::RenderedFrame::Monochrome(idx) => {
-                let shades = self
-                    .sgb_presentation_shades()
-                    .unwrap_or_else(|| self.dmg_palette.shades(self.ppu.cgb_color_conversion()));
                 let mut rgb = vec![0u8; ppu::FRAMEBUFFER_SIZE * 3].into_boxed_slice();
                 for (i, &s) in idx.iter().enumerate() {
                     let c = shades[(s as usize) & 3];
@@ -1400,7 +1498,21 @@ impl GB {
         self.hardware.cpu_hz(self.region)
     }
 
+    /// Run until the PPU finishes a frame (or a breakpoint hits, returning
+    /// `true`), and return the presented frame.
+    ///
+    /// Deterministic: the same machine state and joypad input always produce
+    /// the same frame and the same next state. Nothing on this path reads the
+    /// wall clock (the RTC advances from emulated cycles; only the opt-in
+    /// [`Cartridge::set_rtc_host_clock`](crate::cartridge::Cartridge::set_rtc_host_clock)
+    /// mode and the save-file catch-up read it, outside this call), draws
+    /// random numbers, or depends on hash-map iteration order.
+    /// `collect_audio` only decides whether samples are handed to the sink.
+    /// Movies, rewind and the golden-hash regressions rely on this;
+    /// [`movie::verify_determinism`](crate::movie::verify_determinism) checks
+    /// it.
     pub fn run_until_frame(&mut self, collect_audio: bool) -> (Frame, bool) {
+        profile_span!("run_until_frame");
         let mut cpu_cycles_this_frame = 0u32;
         // Normal frame should be 70224 PPU dots (154 scanlines × 456 dots)
         // If we exceed this, we assume PPU is disabled or stuck
@@ -1437,6 +1549,49 @@ impl GB {
         }
     }
 
+    /// Run until at least `samples` stereo host samples have gone to the audio
+    /// sink, for frontends paced by their audio callback rather than by vsync.
+    /// Returns every frame completed on the way (none, one or several,
+    /// depending on how `samples` compares with a frame's ~738), and whether a
+    /// breakpoint stopped the run early. The last instruction can overshoot
+    /// by a sample or two; callers keep the surplus queued rather than asking
+    /// for exact counts.
+    ///
+    /// A frame with the LCD off is cut at the same cycle cap as
+    /// [`run_until_frame`](Self::run_until_frame), so a game that blanks the
+    /// screen still hands the frontend something to present.
+    pub fn run_until_audio_samples(&mut self, samples: usize) -> (Vec<Frame>, bool) {
+        let mut frames = Vec::new();
+        let mut produced = 0usize;
+        let mut cpu_cycles_this_frame = 0u32;
+        const MAX_NORMAL_SPEED_CPU_CYCLES_PER_FRAME: u32 = 70224;
+
+        while produced < samples {
+            let (breakpoint_hit, cycles) = self.step_instruction(true);
+            produced += self.audio_scratch.len();
+            cpu_cycles_this_frame += cycles;
+
+            if breakpoint_hit {
+                return (frames, true);
+            }
+
+            let max_cpu_cycles_per_frame = if self.mmio.is_double_speed_mode() {
+                MAX_NORMAL_SPEED_CPU_CYCLES_PER_FRAME * 2
+            } else {
+                MAX_NORMAL_SPEED_CPU_CYCLES_PER_FRAME
+            };
+            if self.ppu.frame_ready() {
+                self.mmio.service_sgb_vram_transfer(self.ppu.dmg_shade_frame());
+                frames.push(self.presented_frame());
+                cpu_cycles_this_frame = 0;
+            } else if cpu_cycles_this_frame >= max_cpu_cycles_per_frame {
+                frames.push(self.presented_frame());
+                cpu_cycles_this_frame = 0;
+            }
+        }
+        (frames, false)
+    }
+
     pub fn run_until_lcd_frame(
         &mut self,
         collect_audio: bool,
@@ -1508,6 +1663,30 @@ impl GB {
         self.ppu.set_cgb_color_conversion(conversion);
     }
 
+    /// Render whole scanlines at mode-3 end instead of per dot: much cheaper,
+    /// but mid-line raster effects are lost. Timing (STAT, IRQs, HDMA) is
+    /// unchanged. Not saved in savestates.
+    pub fn set_scanline_ppu(&mut self, enabled: bool) {
+        self.ppu.set_scanline_renderer(enabled);
+    }
+
+    pub fn scanline_ppu(&self) -> bool {
+        self.ppu.scanline_renderer()
+    }
+
+    /// Emulate the DMG OAM corruption bug (the default) or leave OAM intact
+    /// when a 16-bit inc/dec or a CPU OAM access lands in mode 2. Survives a
+    /// reset; not saved in savestates.
+    pub fn set_oam_bug(&mut self, on: bool) {
+        self.mmio.set_oam_bug(on);
+    }
+
+    /// Whether the DMG OAM corruption bug is emulated (see
+    /// [`set_oam_bug`](Self::set_oam_bug)).
+    pub fn oam_bug(&self) -> bool {
+        self.mmio.oam_bug()
+    }
+
     pub fn set_fetch_debug_events_enabled(&mut self, enabled: bool) {
         self.ppu.set_fetch_debug_events_enabled(enabled);
     }
@@ -1556,11 +1735,30 @@ impl GB {
         self.mmio.set_cart_sram_cs_lazy(lazy);
     }
 
+    /// CPU T-cycles run since power-on or the last reset: 4 per M-cycle, so a
+    /// double-speed frame counts twice as many. A STOP's frozen clock adds
+    /// none. Saved in savestates, so it carries across a load.
+    pub fn cycle_count(&self) -> u64 {
+        self.counters.cycles
+    }
+
+    /// Instructions executed since power-on or the last reset. Interrupt
+    /// dispatch, HALT and DMA stalls take cycles but execute none.
+    pub fn instruction_count(&self) -> u64 {
+        self.counters.instructions
+    }
+
     /// Master cycle counter (abs_cc) for timing trace reconciliation vs cctracer.
     pub fn master_cc(&self) -> u64 {
         self.mmio.master_cc()
     }
 
+    /// Whether the CPU runs at CGB double speed, where `master_cc` advances
+    /// two counts per LCD dot instead of one.
+    pub fn is_double_speed(&self) -> bool {
+        self.mmio.is_double_speed_mode()
+    }
+
     /// Write a byte through the memory bus. Used by the libretro frontend to
     /// apply per-frame GameShark RAM pokes.
     pub fn write_memory(&mut self, address: u16, value: u8) {
@@ -1586,6 +1784,14 @@ impl GB {
         b.mmio.attach_link(pb);
     }
 
+    /// Plug this instance into a link cable whose other end is a
+    /// [`crate::serial::RemoteLink`] (netplay: the partner runs elsewhere and
+    /// a transport carries the link's messages). Re-plugging a link that was
+    /// severed by a state load keeps the remote side's last reported state.
+    pub fn plug_remote_link(&mut self, link: &mut crate::serial::RemoteLink) {
+        self.mmio.attach_link(link.plug());
+    }
+
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
     // wiring, not rot. No in-tree caller, so `dead_code` fires; do not delete.
     /// Plug one end of a link cable into this instance (the other end goes to
@@ -1600,6 +1806,21 @@ impl GB {
         self.mmio.link_attached()
     }
 
+    /// Whether a link cable joins this instance to another live one. A clone
+    /// or a loaded savestate carries a severed end, so a frontend that swaps
+    /// the machine reconnects when this turns false.
+    pub fn link_connected(&self) -> bool {
+        self.mmio.link_connected()
+    }
+
+    /// Plug any [`crate::serial::SerialDevice`] into the link port, replacing
+    /// what was there. The caller keeps a clone of the handle to read the
+    /// device back. Clones and loaded savestates come back unplugged, so a
+    /// frontend that swaps the machine attaches the handle again.
+    pub fn attach_serial_device(&mut self, device: crate::serial::SharedSerialDevice) {
+        self.mmio.attach_serial_device(device);
+    }
+
     /// Unplug the link-port device (back to a disconnected cable).
     pub fn detach_serial_device(&mut self) {
         self.mmio.detach_serial_device();
@@ -1761,6 +1982,12 @@ impl GB {
         self.mmio.read_vram_bank(bank, address)
     }
 
+    /// A whole VRAM bank (0x8000-0x9FFF) for debug views; `None` where
+    /// `read_vram_bank` would read 0xFF (bank 1 without CGB features).
+    pub fn vram_bank(&self, bank: u8) -> Option<&[u8]> {
+        self.mmio.vram_bank_slice(bank)
+    }
+
     /// 16-bit internal timer/DIV counter (for state snapshots / diagnostics).
     pub fn timer_internal_counter(&self) -> u16 {
         self.mmio.timer_internal_counter()
@@ -1781,16 +2008,20 @@ impl GB {
         (high as u16) << 8 | (low as u16)
     }
 
+    /// Load a `.rustyboisave` file (the versioned [`crate::savestate`]
+    /// container). The ROM is not checked: the caller inserts it afterwards.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn from_state_file(path: &str) -> Result<Self, io::Error> {
         let saved_state = fs::read(path)?;
-        Self::from_state_bytes(&saved_state)
+        crate::savestate::read_state(&saved_state, None)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 
     /// Re-seed all the `#[serde(skip)]` derived/mirror state after a savestate
     /// deserialize: cartridge-flag cache, sub-module hardware-revision flags (which
-    /// otherwise revert to default-CGB), and the CPU-mirror flags. The ROM image
-    /// itself is re-attached separately by the frontend via `reattach_rom`.
+    /// otherwise revert to default-CGB), the CPU-mirror flags, and the PPU's
+    /// per-line sprite list. The ROM image itself is re-attached separately by
+    /// the frontend via `reattach_rom`.
     fn post_load_fixup(&mut self) {
         self.mmio.resync_cart_flags();
         // The `hardware` identity survives serialization; re-apply the setters
@@ -1800,6 +2031,18 @@ impl GB {
         // CPU-mirror flags (halt / STOP-window) re-derived from the serialized CPU.
         self.mmio
             .sync_cpu_mirror_flags(self.cpu.halted, self.cpu.stop_unhalt_cycles > 0);
+        // The PPU's per-line sprite list is not serialized; re-map it for the
+        // line the state was taken on.
+        self.ppu.rebuild_sprites_after_load(&mut self.mmio);
+    }
+
+    /// Continue `live`'s host output (the resampler's filter history and
+    /// phase) in this restored machine, so replacing a running machine with a
+    /// state does not restart the filter from silence and click at the seam.
+    /// What the host hears is continuous with what it last played, not with
+    /// the moment the state was saved.
+    pub fn carry_output_from(&mut self, live: &GB) {
+        self.resampler = live.resampler.clone();
     }
 
     /// Re-attach the ROM image to a savestate-restored machine. The runtime
@@ -1832,11 +2075,12 @@ impl GB {
     /// (bincode) — `serde_bytes` blobs (VRAM/WRAM/OAM/framebuffers) become
     /// length-prefixed byte runs, not JSON number-arrays, so a snapshot is
     /// ~its raw size instead of megabytes of text (inline web rewind was
-    /// stalling on the JSON encode).    ///
-    /// Deliberately unversioned while the project is pre-release: the layout
-    /// moves freely and old states are simply invalid. A stale buffer therefore
-    /// fails as an opaque bincode error, or — if it happens to decode — yields a
-    /// wrong machine. Add a magic/version header before the first release.
+    /// stalling on the JSON encode).
+    ///
+    /// Unversioned: a stale buffer fails as an opaque bincode error, or — if it
+    /// happens to decode — yields a wrong machine. That is fine for in-memory
+    /// snapshots (rewind, movie anchors); anything persisted goes through the
+    /// versioned [`crate::savestate`] container, which wraps these bytes.
     pub fn to_state_bytes(&mut self) -> Result<Vec<u8>, io::Error> {
         // Canonicalize: resolve any carried cross-instruction lag so the
         // serialized machine state is schedule-independent (the carry decision
@@ -1854,7 +2098,8 @@ impl GB {
     /// cartridge-flag cache exactly as `from_state_file` does. WASM-clean.
     ///
     /// Unversioned (see `to_state_bytes`): a buffer from a different layout is
-    /// rejected only insofar as bincode happens to notice.
+    /// rejected only insofar as bincode happens to notice. Persisted states
+    /// load through [`crate::savestate::read_state`] instead.
     pub fn from_state_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
         let mut gb: GB = bincode::deserialize(bytes)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
@@ -1884,6 +2129,7 @@ impl GB {
         // Also re-seeds `Registers::new()`, which the boot ROM path then runs
         // from and `skip_bios` overwrites wholesale.
         self.cpu.reset();
+        self.counters = Counters::default();
         if self.skip_bios {
             self.skip_bios();
         }
@@ -1910,6 +2156,16 @@ impl GB {
     pub fn get_breakpoints(&self) -> &HashSet<u16> {
         &self.breakpoints
     }
+
+    /// Byte offset in `to_state_bytes` where a pre-version-5 payload carried
+    /// the PPU's sprite list (see `Ppu::sprite_list_offset`).
+    #[cfg(test)]
+    pub(crate) fn sprite_list_offset(&self) -> usize {
+        let size = |r: Result<u64, bincode::Error>| r.unwrap() as usize;
+        size(bincode::serialized_size(&self.cpu))
+            + size(bincode::serialized_size(&self.mmio))
+            + self.ppu.sprite_list_offset()
+    }
 }
 
 #[cfg(test)]
@@ -1918,7 +2174,8 @@ mod stop_tests {
     //! behavior chart (Lior Halphon, "Reducing Power Consumption"): the
     //! STOP-mode / HALT-mode / NOP forks, the 1-vs-2-byte opcode length, the
     //! DIV reset, the whole-machine clock freeze, and the selected-line-only
-    //! joypad wake. The armed-KEY1 speed-switch path (owned by the age spsw /
+    //! joypad wake, plus the per-source HALT wake the button-held fork relies
+    //! on. The armed-KEY1 speed-switch path (owned by the age spsw /
     //! speedchange suites) gets a tripwire sanity check only.
     use super::*;
     use crate::input::ButtonState;
@@ -1997,6 +2254,41 @@ mod stop_tests {
         assert_eq!(a.read_memory(0xFF56) & 0x02, 0x02, "detached -> no partner");
     }
 
+    /// Two HuC-1 carts (Pokémon Card GB "Card Pop") over a shared IR channel:
+    /// in IR mode ($0E at $0000) A000 writes drive the LED and A000 reads see
+    /// the partner's LED as bit 0 (Pan Docs HuC1: $C1 = light, $C0 = dark).
+    #[test]
+    fn huc1_ir_couples_cart_led_to_peer_receiver() {
+        fn huc1_gb() -> GB {
+            let mut rom = rom_with(&[], 0x00);
+            rom[0x147] = 0xFF; // HuC1+RAM+BATTERY
+            rom[0x149] = 0x03;
+            let mut gb = GB::new(Hardware::DMG);
+            gb.insert(cartridge::Cartridge::from_bytes(&rom).unwrap());
+            gb.skip_bios();
+            gb
+        }
+        let mut a = huc1_gb();
+        let mut b = huc1_gb();
+        GB::connect_ir(&mut a, &mut b);
+        a.write_memory(0x0000, 0x0E);
+        b.write_memory(0x0000, 0x0E);
+        assert_eq!(b.read_memory(0xA000), 0xC0, "dark partner");
+
+        a.write_memory(0xA000, 0x01);
+        assert_eq!(b.read_memory(0xA000), 0xC1, "B must see A's cart LED");
+        assert_eq!(a.read_memory(0xA000), 0xC0, "A must not see its own");
+
+        a.write_memory(0xA000, 0x00);
+        assert_eq!(b.read_memory(0xA000), 0xC0);
+
+        // RAM mode on B: A000 is plain SRAM again, untouched by the LED.
+        a.write_memory(0xA000, 0x01);
+        b.write_memory(0x0000, 0x00);
+        b.write_memory(0xA000, 0x42);
+        assert_eq!(b.read_memory(0xA000), 0x42);
+    }
+
     /// A Game Boy with a Mobile Adapter must complete the START "NINTENDO"
     /// handshake over real internal-clock serial: driving the libmobile packet
     /// by hand (SB = byte, SC = internal clock + start), the adapter begins a
@@ -2293,6 +2585,63 @@ mod stop_tests {
         assert_eq!(gb.cpu.registers.a, 2);
     }
 
+    /// HALT (IME off, so no dispatch) wakes on whichever of the five sources
+    /// is enabled in IE going pending, and on nothing else.
+    #[test]
+    fn halt_wakes_on_each_enabled_source() {
+        for bit in 0..5 {
+            let flag = 1u8 << bit;
+            // 0100: di ; ld a,flag ; ldh (FF),a   IE = this source only
+            // 0105: halt
+            // 0106: nop
+            // 0107: jr self
+            let mut gb = gb_with(&[0xF3, 0x3E, flag, 0xE0, 0xFF, 0x76, 0x00, 0x18, 0xFE], Hardware::DMG, 0x00);
+            step_n(&mut gb, 3);
+            gb.mmio.write(0xFF0F, 0x00);
+            gb.step_instruction(false); // HALT
+            assert!(gb.cpu.halted, "source {bit}: HALT entered");
+
+            // Every other source pending: still halted.
+            gb.mmio.write(0xFF0F, 0x1F & !flag);
+            gb.step_instruction(false);
+            assert!(gb.cpu.halted, "source {bit}: a disabled source must not wake");
+
+            gb.mmio.write(0xFF0F, flag);
+            step_until(&mut gb, 4, "HALT exit", |gb| !gb.cpu.halted);
+            step_until(&mut gb, 4, "resume", |gb| gb.cpu.registers.pc >= 0x0107);
+        }
+    }
+
+    /// The IME=0 wake only resumes: no handler runs and IF keeps the bit, so
+    /// a polling loop after HALT can read it. With IME=1 the same wake
+    /// dispatches to the vector and clears the bit.
+    #[test]
+    fn halt_wake_with_ime_off_resumes_without_dispatch() {
+        for (ime_op, dispatched) in [(0xF3, false), (0xFB, true)] {
+            // 0100: di/ei ; ld a,$04 ; ldh (FF),a   IE = timer
+            // 0105: halt
+            // 0106: nop
+            // 0107: jr self
+            let mut gb = gb_with(&[ime_op, 0x3E, 0x04, 0xE0, 0xFF, 0x76, 0x00, 0x18, 0xFE], Hardware::DMG, 0x00);
+            step_n(&mut gb, 3);
+            gb.mmio.write(0xFF0F, 0x00);
+            gb.step_instruction(false); // HALT
+            assert!(gb.cpu.halted);
+            gb.mmio.write(0xFF0F, 0x04);
+            step_until(&mut gb, 4, "HALT exit", |gb| !gb.cpu.halted);
+            gb.step_instruction(false);
+            let pc = gb.cpu.registers.pc;
+            let if_timer = gb.mmio.read(0xFF0F) & 0x04 != 0;
+            if dispatched {
+                assert!((0x0050..0x0060).contains(&pc), "IME=1 runs the timer handler (pc {pc:#06X})");
+                assert!(!if_timer, "dispatch acknowledges IF");
+            } else {
+                assert!((0x0106..=0x0107).contains(&pc), "IME=0 resumes after HALT (pc {pc:#06X})");
+                assert!(if_timer, "IF stays set for the poll");
+            }
+        }
+    }
+
     /// Pan Docs panel behavior: a plain STOP with the LCD enabled turns a CGB
     /// panel black and a DMG panel blank/white (outside mode 3). The pre-STOP
     /// frame shows the boot logo, so the paint is observable.
@@ -2579,6 +2928,37 @@ mod savestate_roundtrip_tests {
         }
     }
 
+    /// The per-line sprite list is not in the state: a restore taken mid-mode-3
+    /// on a sprite line must re-map it, or the rest of that line (and every
+    /// frame compare after it) loses its sprites. ROM-free: a hand-assembled
+    /// cart fills tile 1 and lays 40 sprites down the screen, three per line.
+    #[test]
+    fn midline_restore_rebuilds_the_sprite_list() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // nop; jp 0150
+        rom[0x150..0x185].copy_from_slice(&[
+            0xAF, 0xE0, 0x40, // xor a; ldh (LCDC), a
+            0x21, 0x10, 0x80, 0x06, 0x10, 0x3E, 0xFF, // ld hl, 8010; ld b, 16; ld a, FF
+            0x22, 0x05, 0x20, 0xFC, // tile 1: ld (hl+), a; dec b; jr nz
+            0x21, 0x00, 0xFE, 0x0E, 0x28, 0x1E, 0x10, 0x16, 0x08, // ld hl, FE00; ld c, 40; ld e, 16; ld d, 8
+            0x7B, 0x22, 0x7A, 0x22, 0x3E, 0x01, 0x22, 0xAF, 0x22, // y, x, tile 1, attr 0
+            0x7B, 0xC6, 0x03, 0x5F, 0x7A, 0xC6, 0x04, 0x57, // e += 3; d += 4
+            0x0D, 0x20, 0xEC, // dec c; jr nz
+            0x3E, 0xE4, 0xE0, 0x48, 0x3E, 0x93, 0xE0, 0x40, // OBP0 = E4; LCDC = 93
+            0x18, 0xFE, // jr $
+        ]);
+        let mut gb = GB::new(Hardware::DMG);
+        gb.insert(cartridge::Cartridge::from_bytes(&rom).unwrap());
+        gb.skip_bios();
+        for _ in 0..3 {
+            gb.run_until_frame(false);
+        }
+        while !(gb.ppu.in_pixel_transfer() && (40..100).contains(&gb.read_memory(0xFF44))) {
+            gb.step_instruction(false);
+        }
+        assert_roundtrip(gb, 3, "mid-mode-3 sprite line");
+    }
+
     /// The APU half of the round-trip, asserted on the *audio* the restored
     /// machine goes on to produce rather than on its serialized bytes: with all
     /// four channels live (sweep, both envelope directions, length counters
@@ -2663,6 +3043,9 @@ mod savestate_roundtrip_tests {
 
         let state = gb.to_state_bytes().expect("serialize");
         let mut restored = restore_with_rom(&state, &gb);
+        // The resampler is host output, not machine state: carry it across as
+        // the session does, or the FIR would refill from silence.
+        restored.carry_output_from(&gb);
         // The sink is `#[serde(skip)]`, so the restored machine has none; the
         // live machine keeps the one it already has (`enable_audio` no-ops when
         // a sink is attached), so drop the settle-phase capture instead.
@@ -2756,6 +3139,7 @@ mod savestate_roundtrip_tests {
         // copy over the same span.
         let state = gb.to_state_bytes().expect("serialize");
         let mut restored = restore_with_rom(&state, &gb);
+        restored.carry_output_from(&gb);
         let redo_buf = Arc::new(Mutex::new(Vec::new()));
         restored.enable_audio(Box::new(Cap(redo_buf.clone()))).unwrap();
         buf.lock().unwrap().clear();
@@ -3312,6 +3696,21 @@ mod clock_tests {
         }
     }
 
+    /// Audio-paced running hands back whatever frames completed along the
+    /// way: two frames' worth of samples (738.4 a frame at 44.1 kHz) spans
+    /// two frames, give or take the one in progress when the run started.
+    #[test]
+    fn running_until_audio_samples_returns_the_frames_it_crossed() {
+        let mut gb = GB::new(Hardware::DMG);
+        gb.skip_bios();
+        let (frames, breakpoint) = gb.run_until_audio_samples(1_477);
+        assert!(!breakpoint);
+        assert!((1..=3).contains(&frames.len()), "{} frames", frames.len());
+
+        let (frames, _) = gb.run_until_audio_samples(100);
+        assert!(frames.len() <= 1, "100 samples is a fraction of a frame");
+    }
+
     /// The documented speedups, from the ratio rather than the raw figures.
     #[test]
     fn sgb1_runs_fast_by_the_documented_margins() {
@@ -3333,7 +3732,9 @@ mod clock_tests {
         let mut gb = GB::new(hw);
         gb.set_region(region);
         // One frame of dots, fed in one go: the dot count is model-independent.
-        gb.mmio.generate_audio_samples(70_224).len()
+        let mut samples = Vec::new();
+        gb.mmio.generate_audio_samples(70_224, &mut audio::Resampler::default(), &mut samples);
+        samples.len()
     }
 
     #[test]
@@ -3945,3 +4346,152 @@ mod dmg_compat_boot_duration_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod no_cartridge_tests {
+    //! An empty cartridge slot: ROM and external-RAM space float to $FF, so
+    //! the CPU runs `RST $38` forever as real hardware does. The machine must
+    //! keep running (and presenting frames) rather than fault, so a frontend
+    //! can sit on it until a ROM is loaded.
+    use super::*;
+
+    #[test]
+    fn cartridge_space_reads_open_bus() {
+        let gb = GB::new(Hardware::DMG);
+        assert!(!gb.has_rom());
+        for addr in [0x0000, 0x0100, 0x0104, 0x014D, 0x4000, 0x7FFF, 0xA000, 0xBFFF] {
+            assert_eq!(gb.read_memory(addr), 0xFF, "{addr:#06X}");
+        }
+    }
+
+    #[test]
+    fn runs_frames_without_a_cartridge() {
+        for hardware in [Hardware::DMG, Hardware::CGB] {
+            let mut gb = GB::new(hardware);
+            gb.skip_bios();
+            for _ in 0..60 {
+                gb.run_until_frame(false);
+            }
+            // Stuck on the RST $38 vector, pushing onto a wrapping stack.
+            let pc = gb.get_cpu_registers().pc;
+            assert!((0x0038..=0x0039).contains(&pc), "{hardware:?}: pc {pc:#06X}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod counter_tests {
+    use super::*;
+
+    fn gb_with(code: &[u8]) -> GB {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x100..0x100 + code.len()].copy_from_slice(code);
+        let mut gb = GB::new(Hardware::DMG);
+        gb.insert(cartridge::Cartridge::from_bytes(&rom).unwrap());
+        gb.skip_bios();
+        gb
+    }
+
+    #[test]
+    fn counts_t_cycles_and_executed_instructions() {
+        // 0100: nop ; ld a,$12 ; swap a ; di ; halt
+        let mut gb = gb_with(&[0x00, 0x3E, 0x12, 0xCB, 0x37, 0xF3, 0x76]);
+        assert_eq!((gb.cycle_count(), gb.instruction_count()), (0, 0));
+        for _ in 0..4 {
+            gb.step_instruction(false);
+        }
+        // 4 + 8 + 8 (CB-prefixed, one instruction) + 4.
+        assert_eq!((gb.cycle_count(), gb.instruction_count()), (24, 4));
+
+        gb.step_instruction(false); // HALT
+        let (cycles, instructions) = (gb.cycle_count(), gb.instruction_count());
+        for _ in 0..10 {
+            gb.step_instruction(false);
+        }
+        assert!(gb.cycle_count() > cycles, "halted time still counts");
+        assert_eq!(gb.instruction_count(), instructions, "but executes nothing");
+    }
+
+    #[test]
+    fn counters_survive_a_savestate_and_clear_on_reset() {
+        let mut gb = gb_with(&[0x18, 0xFE]); // jr self
+        gb.run_until_frame(false);
+        let counters = (gb.cycle_count(), gb.instruction_count());
+        assert!(counters.1 > 0);
+
+        let restored = GB::from_state_bytes(&gb.to_state_bytes().unwrap()).unwrap();
+        assert_eq!((restored.cycle_count(), restored.instruction_count()), counters);
+
+        gb.reset();
+        assert_eq!((gb.cycle_count(), gb.instruction_count()), (0, 0));
+    }
+}
+
+#[cfg(test)]
+mod scanline_ppu_tests {
+    use super::*;
+
+    /// A scrolled tile pattern, a window over its lower right and a sprite,
+    /// all static, on a `jr` loop. The registers go through the CPU so the
+    /// PPU's delayed copies see them.
+    fn still_scene(hardware: Hardware, scanline: bool) -> GB {
+        #[rustfmt::skip]
+        let code = [
+            0xAF,             // 0100: xor a
+            0xE0, 0x40,       // 0101: ldh (LCDC),a
+            0x3E, 5,          // 0103: ld a,5
+            0xE0, 0x42,       // 0105: ldh (SCY),a
+            0x3E, 3,          // 0107: ld a,3
+            0xE0, 0x43,       // 0109: ldh (SCX),a
+            0x3E, 70,         // 010B: ld a,70
+            0xE0, 0x4A,       // 010D: ldh (WY),a
+            0x3E, 60,         // 010F: ld a,60
+            0xE0, 0x4B,       // 0111: ldh (WX),a
+            0x3E, 0xE4,       // 0113: ld a,$E4
+            0xE0, 0x48,       // 0115: ldh (OBP0),a
+            0x3E, 0xB3,       // 0117: ld a,$B3 (LCD, window, OBJ, BG on)
+            0xE0, 0x40,       // 0119: ldh (LCDC),a
+            0x18, 0xFE,       // 011B: jr $011B
+        ];
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x100..0x100 + code.len()].copy_from_slice(&code);
+        let mut gb = GB::new(hardware);
+        gb.insert(cartridge::Cartridge::from_bytes(&rom).unwrap());
+        gb.skip_bios();
+        gb.set_scanline_ppu(scanline);
+        gb.step_instruction(false);
+        gb.step_instruction(false);
+        // LCD off: VRAM and OAM are open to direct writes.
+        for row in 0..8u16 {
+            let tile1 = [0xF0, 0x3C];
+            let tile2 = [0xAA ^ row as u8, 0x55];
+            for (i, b) in tile1.into_iter().chain(tile2).enumerate() {
+                gb.write_memory(0x8010 + (i as u16 / 2) * 16 + row * 2 + i as u16 % 2, b);
+            }
+        }
+        for i in 0..0x400u16 {
+            gb.write_memory(0x9800 + i, (i % 3) as u8);
+        }
+        for (i, b) in [60u8, 40, 0x02, 0x00].into_iter().enumerate() {
+            gb.write_memory(0xFE00 + i as u16, b);
+        }
+        gb
+    }
+
+    #[test]
+    fn scanline_frames_match_the_dot_renderer_on_a_still_scene() {
+        for hardware in [Hardware::DMG, Hardware::CGB] {
+            let mut dot = still_scene(hardware, false);
+            let mut line = still_scene(hardware, true);
+            assert!(line.scanline_ppu());
+            for _ in 0..3 {
+                let (a, _) = dot.run_until_frame(false);
+                let (b, _) = line.run_until_frame(false);
+                assert!(a.rgb() == b.rgb(), "{hardware:?}: scanline frame differs");
+            }
+            let frame = dot.presented_frame();
+            assert!(frame.rgb().chunks(3).any(|p| p != &frame.rgb()[..3]), "{hardware:?}: blank frame");
+            assert_eq!(dot.master_cc(), line.master_cc(), "{hardware:?}: timing drifted");
+        }
+    }
+}
@@ -1,3 +1,30 @@
+/// Open a profiler span named `$name` until the end of the enclosing block.
+/// Frame-level work (a whole frame, a scanline, the frame output) uses the
+/// plain form, a `debug` span; `fine` marks per-instruction work, a `trace`
+/// span a subscriber can leave out, as thousands land in every frame.
+/// Without the `profiling` feature it expands to nothing.
+#[cfg(feature = "profiling")]
+#[macro_export]
+macro_rules! profile_span {
+    ($name:literal) => {
+        let _span = $crate::__tracing::debug_span!($name).entered();
+    };
+    (fine, $name:literal) => {
+        let _span = $crate::__tracing::trace_span!($name).entered();
+    };
+}
+
+#[cfg(not(feature = "profiling"))]
+#[macro_export]
+macro_rules! profile_span {
+    ($name:literal) => {};
+    (fine, $name:literal) => {};
+}
+
+#[cfg(feature = "profiling")]
+#[doc(hidden)]
+pub use tracing as __tracing;
+
 pub mod audio;
 pub mod cgb_compat_palette;
 pub mod checksum;
@@ -11,8 +38,11 @@ pub mod ir;
 pub mod memory;
 pub mod mobile;
 pub mod movie;
+pub mod movie_import;
+pub mod patch;
 pub mod ppu;
 pub mod printer;
+pub mod savestate;
 pub mod serial;
 pub mod sgb;
 pub mod sgb_firmware;
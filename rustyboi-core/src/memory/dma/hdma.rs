@@ -89,7 +89,7 @@ impl Mmio {
 
     /// True while deferred HDMA block writes are still in their
     /// per-dot countdown (`step_hdma_deferred` must run each dot to commit them at
-    /// the right cc). Blocks the idle bulk-skip.
+    /// the right cc). Keeps the bus scheduler stepping the PPU family per dot.
     pub(crate) fn has_pending_hdma_deferred(&self) -> bool {
         !self.dma.hdma.pending_writes.is_empty()
     }
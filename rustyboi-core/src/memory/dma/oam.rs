@@ -672,3 +672,72 @@ impl Mmio {
         self.dma.oam.active || self.dma.hdma.oam_dma_stall_suppress != 0
     }
 }
+
+#[cfg(test)]
+mod oam_dma_tests {
+    //! The OAM-DMA transfer seen from the CPU: its arm delay and 160-M-cycle
+    //! length, the E000-FFFF source mapping, and what a conflicting access
+    //! reads instead of the addressed byte.
+    use crate::memory::mmio::{Mmio, OAM_START, REG_DMA};
+    use crate::memory::Addressable;
+
+    /// A DMG bus with WRAM C000-C09F holding `i ^ 0x5A` and HRAM FF80 = 0x33.
+    fn dmg() -> Mmio {
+        let mut m = Mmio::new();
+        for i in 0..0xA0u16 {
+            m.write(0xC000 + i, i as u8 ^ 0x5A);
+        }
+        m.write(0xFF80, 0x33);
+        m
+    }
+
+    /// Run `n` M-cycles of the DMA engine.
+    fn m_cycles(m: &mut Mmio, n: usize) {
+        for _ in 0..n * 4 {
+            m.step_dma();
+        }
+    }
+
+    #[test]
+    fn transfer_starts_after_the_arm_delay_and_takes_160_m_cycles() {
+        let mut m = dmg();
+        m.write(REG_DMA, 0xC0);
+        let mut first_byte_at = None;
+        let mut m_cycle = 0;
+        while m.dma_read_conflict_active() || first_byte_at.is_none() {
+            m_cycles(&mut m, 1);
+            m_cycle += 1;
+            if first_byte_at.is_none() && m.oam.read(OAM_START) == 0x5A {
+                first_byte_at = Some(m_cycle);
+            }
+            assert!(m_cycle < 400, "transfer never finished");
+        }
+        let first = first_byte_at.unwrap();
+        assert_eq!(first, 2, "byte 0 lands on the second M-cycle after the FF46 write");
+        assert_eq!(m_cycle - first, 160, "the bus is held 160 M-cycles from byte 0, one byte each");
+        assert!((0..0xA0u16).all(|i| m.oam.read(OAM_START + i) == i as u8 ^ 0x5A));
+    }
+
+    #[test]
+    fn dmg_e000_source_reads_the_wram_echo() {
+        let mut m = dmg();
+        m.write(REG_DMA, 0xE0);
+        m_cycles(&mut m, 170);
+        assert!((0..0xA0u16).all(|i| m.oam.read(OAM_START + i) == i as u8 ^ 0x5A));
+    }
+
+    #[test]
+    fn conflicting_reads_see_the_dma_byte_but_hram_is_free() {
+        let mut m = dmg();
+        m.write(REG_DMA, 0xC0);
+        m_cycles(&mut m, 12);
+        let in_flight = m.oam.read(OAM_START + m.dma.oam.pos as u16);
+        // A WRAM-source transfer holds the main bus: ROM and WRAM reads return
+        // the byte being copied, not the addressed one (nor a blanket 0xFF).
+        assert_eq!(m.read(0x0150), in_flight);
+        assert_eq!(m.read(0xC080), in_flight);
+        assert_ne!(in_flight, 0xFF);
+        // VRAM is on its own bus and HRAM is inside the CPU.
+        assert_eq!(m.read(0xFF80), 0x33);
+    }
+}
@@ -360,6 +360,11 @@ pub struct Mmio {
     // it is the only thing consumed, and `Mmio` is cloned on the hot rewind path.
     #[serde(skip, default)]
     sgb_firmware: Option<Box<crate::sgb_firmware::SgbBorder>>,
+    // Host accuracy knob: leave OAM alone where the DMG OAM corruption bug
+    // would trash it (`set_oam_bug`). A host preference, not machine state, so
+    // `serde(skip)` like `bios`; `false` — the bug emulated — is the default.
+    #[serde(skip, default)]
+    oam_bug_disabled: bool,
     // The cartridge's RUNTIME state (RAM, bank registers, RTC, ...) is serialized
     // so a state fully round-trips the MBC; the multi-MB read-only ROM image is
     // held out via `Cartridge::rom_data` being `#[serde(skip)]` and re-attached on
@@ -391,13 +396,18 @@ pub struct Mmio {
     // beside `serial` (not inside it) so the per-dot serial clone dance never
     // copies a device's buffers.
     #[serde(default)]
-    serial_device: serial::SerialDevice,
+    serial_device: serial::PortDevice,
     // Partner plugged into the CGB IR port (RP/$FF56). Disconnected by default
     // so a lone GBC's receiver always reads "no light", byte-identical to the
     // pre-IR behaviour. Skipped from savestates like the link cable: the
     // Arc-backed channel is a live connection, not persistable state.
     #[serde(skip, default)]
     ir_device: crate::ir::IrDevice,
+    // Bytes shifted out of the link port since the last drain, kept only while
+    // a frontend echoes serial output (`None` = not capturing). A debug tap,
+    // not machine state, so never serialized.
+    #[serde(skip, default)]
+    serial_out: Option<Vec<u8>>,
     // Passive-read page table: 4KB pages resolved to their backing region so
     // the Bus's passive fast path (plain ROM/WRAM/echo reads) skips the full
     // address dispatch and per-access bank derivation. Rebuilt lazily;
@@ -683,6 +693,7 @@ impl Mmio {
         Mmio {
             bios: None,
             sgb_firmware: None,
+            oam_bug_disabled: false,
             cartridge: None,
             input: input::Input::new(),
             vram: Box::new(memory::Memory::new()),
@@ -692,8 +703,9 @@ impl Mmio {
             oam_high: [0; 0x60],
             timer: timer::Timer::new(),
             serial: serial::Serial::new(),
-            serial_device: serial::SerialDevice::Disconnected,
+            serial_device: serial::PortDevice::Disconnected,
             ir_device: crate::ir::IrDevice::Disconnected,
+            serial_out: None,
             passive_pages: [PassivePage::Fallback; 16],
             passive_pages_valid: false,
             dma: Dma::default(),
@@ -762,6 +774,7 @@ impl Mmio {
         // performed by the GUI's "Load ROM" path).
         new.bios = self.bios.take();
         new.cartridge = self.cartridge.take();
+        new.oam_bug_disabled = self.oam_bug_disabled;
         // Reset = power cycle for the cart too: volatile MBC latches (bank
         // registers, RAMG, banking mode) re-home to power-on values while the
         // battery-fed domain (RAM, RTC time) survives inside the moved cart.
@@ -1287,62 +1300,21 @@ impl Mmio {
         self.io_registers.read(ppu::LCD_CONTROL) & (ppu::LCDCFlags::DisplayEnable as u8) != 0
     }
 
-    /// Idle fast path: true when the whole world is
-    /// idle except the timer+serial, so a span of dots can be bulk-skipped to the
-    /// next scheduled event without losing any per-dot peripheral side effect.
-    /// Requires: LCD off (no PPU renderer / mode edges / STAT-IRQ schedule),
-    /// no OAM-DMA in flight, no HDMA armed, APU powered off (its channels step per
-    /// dot), no deferred HDMA block writes, no OAM-DMA stall catch-up, no halt OAM
-    /// grace, and no queued delayed register writes. Under all of these only
-    /// `step_timer` and `step_serial` advance, and both are span-collapsible
-    /// (the timer via `Timer::step_to`, serial via its phase-based `step`). The CPU
-    /// T-phase parity only gates the PPU, which is off here, so collapsing it is a
-    /// no-op. This is purely an advance-mechanism optimization: the per-dot
-    /// fallback in `Bus::run_to` handles every cc the guard rejects, so behavior is
-    /// byte-identical to the per-dot crank.
-    pub(crate) fn idle_bulk_skippable(&self) -> bool {
-        let lcd_on = self.io_registers.read(ppu::LCD_CONTROL)
-            & (ppu::LCDCFlags::DisplayEnable as u8)
-            != 0;
-        !lcd_on
-            && !self.dma.oam.active
-            && self.dma.hdma.oam_dma_stall_suppress == 0
-            && self.halt.oam_grace == 0
-            && !self.dma.hdma.enabled
-            && !self.dma.hdma.req_pending
-            && !self.audio.is_powered()
-            && !self.serial.is_active()
-            && self.joypad_irq_delay == 0
-            && !self.has_pending_hdma_deferred()
-            // An external clock source (link peer or DMG-07 adapter) deposits on
-            // another timeline; per-dot polling keeps the external-clock
-            // completion cc tight, so never bulk-skip with one attached (the
-            // disconnected default stays byte-identical).
-            && !self.serial_device.drives_external_clock()
-    }
-
-    /// Bulk-advance the timer+serial to `target_cc` in one shot
-    /// (only call when `idle_bulk_skippable()` held for the entire span). Mirrors
-    /// the order `resolve_one_dot` uses (timer, then serial) so the net effect is
-    /// byte-identical to cranking each dot. `master_cc` is `timer.abs_cc`, so the
-    /// timer's `step_to` carries the master cc to the target and the serial step
-    /// observes the final phase.
-    pub(crate) fn bulk_advance_idle(&mut self, target_cc: u64) {
+    /// Jump the world's clocks to `target_cc` over a span the bus scheduler
+    /// proved event-free for every world source (`cpu::scheduler`): the timer
+    /// lands on the target in one `Timer::step_to` (no overflow delivery or
+    /// FS edge inside, so it is a pure clock move), the RTC and camera
+    /// crystals advance by the same dot count, and so does the t-phase
+    /// counter. Byte-identical to the per-dot crank over the span; the PPU is
+    /// the caller's to advance.
+    pub(crate) fn advance_world_to(&mut self, target_cc: u64) {
         let dots = target_cc.wrapping_sub(self.master_cc());
-        let mut timer = self.timer.clone();
-        timer.step_to(target_cc, self);
-        self.timer = timer;
-        // The RTC keeps ticking across the idle span (LCD off etc. does not stop
-        // the crystal). Advance it by the same dot count the per-dot crank would
-        // have, so the idle fast path is byte-identical for the RTC too.
+        let ds = self.is_double_speed_mode();
+        let cpu_halted = self.cpu_is_halted();
+        if self.timer.step_to(target_cc, ds, cpu_halted) {
+            self.request_interrupt(cpu::registers::InterruptFlag::Timer);
+        }
         self.tick_rtc(dots);
-        // Serial is phase-based: stepping once at the final phase shifts the same
-        // bits and (if it completed) fires the IRQ exactly as the per-dot path. The
-        // guard already requires it inactive, so this is a defensive no-op.
-        self.step_serial();
-        // The per-dot path advances `cpu_t_phase` once per dot; collapse the same
-        // count so the T-phase parity that gates the (currently off) PPU stays
-        // exactly where the per-dot crank would have left it.
         self.cpu_t_phase = self.cpu_t_phase.wrapping_add(dots);
     }
 
@@ -1351,11 +1323,8 @@ impl Mmio {
     /// at the timer's current `abs_cc`, which the CPU positions at the access
     /// start cc.
     pub(crate) fn write_timer(&mut self, addr: u16, value: u8) {
-        let mut timer = self.timer.clone();
-        timer.write(addr, value);
-        let irq = timer.take_pending_irq();
-        self.timer = timer;
-        if irq {
+        self.timer.write(addr, value);
+        if self.timer.take_pending_irq() {
             self.request_interrupt(cpu::registers::InterruptFlag::Timer);
         }
     }
@@ -1364,11 +1333,8 @@ impl Mmio {
     /// overflow events at the write cc and raise their IF BEFORE the store, so
     /// an exact-collision CPU write wins (see Timer::flush_overflow_for_ifreg_write).
     pub(crate) fn flush_timer_overflow_for_ifreg_write(&mut self) {
-        let mut timer = self.timer.clone();
-        timer.flush_overflow_for_ifreg_write();
-        let irq = timer.take_pending_irq();
-        self.timer = timer;
-        if irq {
+        self.timer.flush_overflow_for_ifreg_write();
+        if self.timer.take_pending_irq() {
             self.request_interrupt(cpu::registers::InterruptFlag::Timer);
         }
     }
@@ -1404,7 +1370,7 @@ impl Mmio {
         // clock. `abs_cc` is advanced at the start of the
         // timer step within this same dot's tick, so it is the live cc here.
         // Serial::step is a no-op while no transfer is active (the common case),
-        // so skip the per-dot clone entirely then. With a link peer attached the
+        // so there is nothing to do then. With a link peer attached the
         // idle path additionally polls the cable for a completed exchange from
         // the peer instance's window (the external-clock data path); anything
         // else keeps the exact pre-link fast path.
@@ -1438,9 +1404,10 @@ impl Mmio {
             }
         }
         let phase = self.timer.abs_cc();
-        let mut serial = self.serial.clone();
-        serial.step(phase, self);
-        self.serial = serial;
+        if let Some((tx, rx, cc)) = self.serial.step(phase) {
+            self.request_interrupt(cpu::registers::InterruptFlag::Serial);
+            self.serial_device_receive(tx, rx, cc);
+        }
     }
 
     /// Link-cable poll while our serial unit is idle: if we armed an
@@ -1461,7 +1428,9 @@ impl Mmio {
         let Some(byte) = self.serial_device.link_take_deposit() else {
             return;
         };
+        let tx = self.serial.read(serial::SB);
         self.serial.complete_external(byte);
+        self.serial_device.external_exchanged(tx, byte, self.timer.abs_cc());
         self.serial_device.link_disarm(byte);
         self.request_interrupt(cpu::registers::InterruptFlag::Serial);
     }
@@ -1496,12 +1465,27 @@ impl Mmio {
     /// byte our shift register received (a link peer records it as our new
     /// live shift-register contents).
     pub(crate) fn serial_device_receive(&mut self, tx: u8, rx: u8, cc: u64) {
+        if let Some(out) = self.serial_out.as_mut() {
+            out.push(tx);
+        }
         self.serial_device.receive_byte(tx, rx, cc);
     }
 
+    pub fn set_serial_capture(&mut self, on: bool) {
+        match (on, self.serial_out.is_some()) {
+            (true, false) => self.serial_out = Some(Vec::new()),
+            (false, true) => self.serial_out = None,
+            _ => {}
+        }
+    }
+
+    pub fn drain_serial_output(&mut self) -> Vec<u8> {
+        self.serial_out.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     /// Plug a Game Boy Printer into the link port.
     pub fn attach_printer(&mut self) {
-        self.serial_device = serial::SerialDevice::Printer(crate::printer::GbPrinter::new());
+        self.serial_device = serial::PortDevice::Printer(crate::printer::GbPrinter::new());
     }
 
     /// Plug one end of a link cable (the other end goes to a second GB
@@ -1510,7 +1494,7 @@ impl Mmio {
     /// contents.
     pub(crate) fn attach_link(&mut self, peer: serial::LinkPeer) {
         peer.seed_live_sb(self.serial.read(serial::SB));
-        self.serial_device = serial::SerialDevice::Link(peer);
+        self.serial_device = serial::PortDevice::Link(peer);
     }
 
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
@@ -1519,32 +1503,36 @@ impl Mmio {
         self.serial_device.is_link()
     }
 
+    pub(crate) fn link_connected(&self) -> bool {
+        self.serial_device.link_connected()
+    }
+
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
     // wiring, not rot. No in-tree caller, so `dead_code` fires; do not delete.
     /// Plug this Game Boy into a 4-Player Adapter (DMG-07) port.
     pub(crate) fn attach_four_player(&mut self, mut port: crate::dmg07::FourPlayerPort) {
         port.mirror_sb(self.serial.read(serial::SB));
-        self.serial_device = serial::SerialDevice::FourPlayer(port);
+        self.serial_device = serial::PortDevice::FourPlayer(port);
     }
 
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
     // wiring, not rot. No in-tree caller, so `dead_code` fires; do not delete.
     pub(crate) fn four_player_attached(&self) -> bool {
-        matches!(self.serial_device, serial::SerialDevice::FourPlayer(_))
+        matches!(self.serial_device, serial::PortDevice::FourPlayer(_))
     }
 
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
     // wiring, not rot. No in-tree caller, so `dead_code` fires; do not delete.
     /// Plug a Mobile Adapter GB into the link port.
     pub(crate) fn attach_mobile_adapter(&mut self, adapter: crate::mobile::MobileAdapter) {
-        self.serial_device = serial::SerialDevice::Mobile(adapter);
+        self.serial_device = serial::PortDevice::Mobile(adapter);
     }
 
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
     // wiring, not rot. No in-tree caller, so `dead_code` fires; do not delete.
     pub(crate) fn mobile_adapter(&self) -> Option<&crate::mobile::MobileAdapter> {
         match &self.serial_device {
-            serial::SerialDevice::Mobile(m) => Some(m),
+            serial::PortDevice::Mobile(m) => Some(m),
             _ => None,
         }
     }
@@ -1556,9 +1544,14 @@ impl Mmio {
         self.serial.transfer_complete_at()
     }
 
+    /// Plug a frontend-provided serial device into the link port.
+    pub fn attach_serial_device(&mut self, device: serial::SharedSerialDevice) {
+        self.serial_device = serial::PortDevice::Plugged(serial::PluggedDevice::new(device));
+    }
+
     /// Unplug whatever is on the link port (back to a disconnected cable).
     pub fn detach_serial_device(&mut self) {
-        self.serial_device = serial::SerialDevice::Disconnected;
+        self.serial_device = serial::PortDevice::Disconnected;
     }
 
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
@@ -1595,14 +1588,14 @@ impl Mmio {
 
     pub(crate) fn printer(&self) -> Option<&crate::printer::GbPrinter> {
         match &self.serial_device {
-            serial::SerialDevice::Printer(p) => Some(p),
+            serial::PortDevice::Printer(p) => Some(p),
             _ => None,
         }
     }
 
     pub(crate) fn printer_mut(&mut self) -> Option<&mut crate::printer::GbPrinter> {
         match &mut self.serial_device {
-            serial::SerialDevice::Printer(p) => Some(p),
+            serial::PortDevice::Printer(p) => Some(p),
             _ => None,
         }
     }
@@ -1623,6 +1616,16 @@ impl Mmio {
         self.serial.is_cgb()
     }
 
+    /// Emulate the DMG OAM corruption bug (the default) or skip it. Only the
+    /// corruption is skipped; mode 2/3 still lock the CPU out of OAM.
+    pub fn set_oam_bug(&mut self, on: bool) {
+        self.oam_bug_disabled = !on;
+    }
+
+    pub fn oam_bug(&self) -> bool {
+        !self.oam_bug_disabled
+    }
+
     /// Select the inserted board's SRAM chip-select decode (fixture-level; see
     /// `Cartridge::dma_sram_bus_read`). No-op without a cartridge.
     pub fn set_cart_sram_cs_lazy(&mut self, lazy: bool) {
@@ -1714,7 +1717,7 @@ impl Mmio {
     /// off the backing stores for the lag-carry gate.
     #[inline]
     pub(crate) fn pending_if_ie(&self) -> u8 {
-        self.io_registers.read(cpu::registers::INTERRUPT_FLAG) & self.ie_register & 0x1F
+        self.io_registers.read(cpu::registers::INTERRUPT_FLAG) & self.ie_register & cpu::registers::INTERRUPT_BITS
     }
 
     pub(crate) fn request_interrupt(&mut self, flag: cpu::registers::InterruptFlag) {
@@ -1967,7 +1970,7 @@ impl Mmio {
 
     /// The EXACT cc the next timer overflow's IF bit is raised
     /// at, with the same `fold` `step_to`/`update_irq_delivery` will apply. The
-    /// min-event idle fast path lands on this cc so the overflow fires identically.
+    /// halted batch and the lag carry bound their spans with it.
     pub(crate) fn next_timer_overflow_fire_cc(&self) -> Option<u64> {
         self.timer.next_overflow_fire_cc(self.cpu_is_halted())
     }
@@ -1982,13 +1985,8 @@ impl Mmio {
     /// a non-halt/non-stop EI loop so the serviced ISR runs on hardware's exact
     /// phase, ahead of the normal `CC_OFF`-late per-dot delivery.
     pub(crate) fn force_ei_timer_delivery(&mut self, boundary: u64) {
-        let mut timer = self.timer.clone();
-        let fired = timer.force_ei_delivery(boundary);
-        self.timer = timer;
-        if fired {
-            let mut t = self.timer.clone();
-            t.flush_pending_irq(self);
-            self.timer = t;
+        if self.timer.force_ei_delivery(boundary) && self.timer.take_pending_irq() {
+            self.request_interrupt(cpu::registers::InterruptFlag::Timer);
         }
     }
 
@@ -2037,10 +2035,30 @@ impl Mmio {
         self.timer.abs_cc()
     }
 
+    pub fn set_audio_sample_rate(&mut self, rate: u32) {
+        self.audio.set_sample_rate(rate);
+    }
+
+    pub fn audio_sample_rate(&self) -> u32 {
+        self.audio.sample_rate()
+    }
+
+    pub fn set_audio_high_pass(&mut self, on: bool) {
+        self.audio.set_high_pass(on);
+    }
+
+    pub fn set_audible_channels(&mut self, audible: [bool; 4]) {
+        self.audio.set_audible_channels(audible);
+    }
+
     pub fn set_channel_tap(&mut self, on: bool) {
         self.audio.set_channel_tap(on);
     }
 
+    pub fn apu_channel_states(&self) -> [audio::ChannelState; 4] {
+        self.audio.channel_states()
+    }
+
     pub fn drain_channel_tap(&mut self) -> Vec<audio::ChannelSample> {
         self.audio.drain_channel_tap()
     }
@@ -2049,12 +2067,17 @@ impl Mmio {
         self.audio.mixes_digitally()
     }
 
-    pub(crate) fn generate_audio_samples(&mut self, cpu_cycles: u32) -> Vec<(f32, f32)> {
+    pub(crate) fn generate_audio_samples(
+        &mut self,
+        cpu_cycles: u32,
+        resampler: &mut audio::Resampler,
+        samples: &mut Vec<(f32, f32)>,
+    ) {
         // Catch the lazy APU up to the current cc first so the mixer state the
         // down-sampler reads is the instruction-end state (the same state the
         // per-dot crank used to leave it in).
         self.sync_apu_cc();
-        self.audio.generate_samples(cpu_cycles)
+        self.audio.generate_samples(cpu_cycles, resampler, samples)
     }
 
     /// CPU has left HALT. Clears the halted mirror so the
@@ -2549,7 +2572,7 @@ impl Mmio {
     /// word0 = bitwise_glitch(this, preceding-word0, preceding-word2); words 1..3
     /// copied from the preceding row.
     pub(crate) fn oam_bug_write_corrupt(&mut self, row: usize) {
-        if row == 0 || row >= 20 {
+        if row == 0 || row >= 20 || self.oam_bug_disabled {
             return;
         }
         let base = (row * 8) as isize;
@@ -2565,7 +2588,7 @@ impl Mmio {
     /// model including the revision-specific secondary/tertiary cases. `row` is the
     /// PPU-scanned row index (0..19); only rows >= 1 corrupt.
     pub(crate) fn oam_bug_read_corrupt(&mut self, row: usize) {
-        if row == 0 || row >= 20 {
+        if row == 0 || row >= 20 || self.oam_bug_disabled {
             return;
         }
         let aor = row * 8; // accessed-OAM-row byte offset (8..0x98)
@@ -2956,58 +2979,43 @@ impl Mmio {
     /// by the caller (`Bus::halted_idle_dots`).
     #[inline]
     pub(crate) fn halt_batchable(&self) -> bool {
-        self.joypad_irq_delay == 0
-            && !self.serial.is_active()
-            && !self.serial_device.drives_external_clock()
-    }
-
-    /// Exclusive master-cc bound (clamped to `target`) up to which the per-dot
-    /// resolve loop needs ONLY the PPU family + CGB HDMA tracking + RTC +
-    /// t-phase per dot: the timer is a pure `abs_cc` bump (no overflow
-    /// delivery, no APU FS edge — `Timer::quiet_until`), serial and the JOYP
-    /// filter are idle, no OAM-DMA is in flight and no HDMA lockstep window is
-    /// armed. Every excluded condition can only change at a CPU access
-    /// boundary or at a bounded event cc, never silently inside the span
-    /// (an HDMA block firing mid-span touches `oam_dma_stall_suppress` only
-    /// when `dma.active`, which is excluded). Returns `master_cc()` when no
-    /// quiet span is available.
-    pub(crate) fn quiet_span_end(&self, target: u64) -> u64 {
-        if self.dma.oam.active
+        !self.joypad_filter_ticking() && !self.serial_ticking()
+    }
+
+    /// The timer's next scheduler event: the first cc whose `Timer::step`
+    /// is more than a clock bump (an overflow delivery or APU FS edge, see
+    /// `Timer::quiet_until`). `master_cc()` when one is already due.
+    pub(crate) fn timer_event_cc(&self) -> u64 {
+        self.timer.quiet_until(self.cpu_is_halted())
+    }
+
+    /// Whether OAM-DMA needs its per-dot step: a transfer in flight, its
+    /// stall catch-up, or the HDMA resume-lockstep window (stepped through
+    /// the same full dots). None of these can start inside a scheduler jump:
+    /// each is armed by a CPU write or at a full dot. An HDMA block firing at
+    /// a PPU dot touches `oam_dma_stall_suppress` only when `dma.active`.
+    pub(crate) fn oam_dma_ticking(&self) -> bool {
+        self.dma.oam.active
             || self.dma.hdma.oam_dma_stall_suppress != 0
-            || self.joypad_irq_delay != 0
-            || self.serial.is_active()
-            || self.serial_device.drives_external_clock()
             || self.dma.hdma.resume_lockstep_window
-        {
-            return self.timer.abs_cc();
-        }
-        // `quiet_until` is exclusive: the dot landing ON the bound (an
-        // overflow delivery or FS-edge cc) must be a real `Timer::step`, so
-        // the bumped span may reach at most bound-1. `target` itself is a CPU
-        // access boundary with no event at it, so landing on it is fine.
-        target.min(self.timer.quiet_until(self.cpu_is_halted()).saturating_sub(1))
     }
 
-    /// Raw one-dot master-clock bump for the quiet-span fast loop (see
-    /// `Timer::bump_cc_one`).
-    #[inline]
-    pub(crate) fn bump_master_cc_one(&mut self) {
-        self.timer.bump_cc_one();
+    /// Whether serial needs its per-dot step: a transfer in flight, or a link
+    /// peer driving the external clock on another timeline.
+    pub(crate) fn serial_ticking(&self) -> bool {
+        self.serial.is_active() || self.serial_device.drives_external_clock()
     }
 
-    /// Raw n-dot master-clock bump for the inert-PPU fast-forward. Same
-    /// soundness argument as `bump_master_cc_one`: every bumped dot lies
-    /// strictly below the quiet bound.
-    #[inline]
-    pub(crate) fn bump_master_cc_by(&mut self, n: u64) {
-        self.timer.bump_cc_by(n);
+    /// Whether the JOYP select-write IRQ filter is counting down.
+    pub(crate) fn joypad_filter_ticking(&self) -> bool {
+        self.joypad_irq_delay != 0
     }
 
-    /// Batch t-phase advance for the inert-PPU fast-forward (the per-dot
-    /// counter is a plain increment).
+    /// Raw one-dot master-clock bump for a scheduler PPU dot (see
+    /// `Timer::bump_cc_one`).
     #[inline]
-    pub(crate) fn advance_cpu_t_phase_by(&mut self, n: u64) {
-        self.cpu_t_phase = self.cpu_t_phase.wrapping_add(n);
+    pub(crate) fn bump_master_cc_one(&mut self) {
+        self.timer.bump_cc_one();
     }
 
     /// PPU-side update of FF44 (LY). Bypasses the CPU-write reset semantics so
@@ -3101,6 +3109,16 @@ impl Mmio {
         self.hram.as_mut_slice()
     }
 
+    /// Video RAM bank `bank` (0x8000-0x9FFF) as a slice, on the same terms as
+    /// `read_vram_bank`: bank 1 only with CGB features on.
+    pub fn vram_bank_slice(&self, bank: u8) -> Option<&[u8]> {
+        match bank {
+            0 => Some(self.vram.as_slice()),
+            1 if self.cgb_features_enabled => Some(self.vram_bank1.as_slice()),
+            _ => None,
+        }
+    }
+
     /// Video RAM bank 0 (0x8000-0x9FFF) as a mutable slice.
     pub(crate) fn vram_slice_mut(&mut self) -> &mut [u8] {
         self.vram.as_mut_slice()
@@ -3322,7 +3340,12 @@ impl memory::Addressable for Mmio {
                 },
                 EXTERNAL_RAM_START..=EXTERNAL_RAM_END => {
                     match &self.cartridge {
-                        Some(cart) => cart.read(addr),
+                        // HuC-1 IR mode: the cart's receiver sees the same IR
+                        // partner as the CGB RP port (bit 0 = light seen).
+                        Some(cart) => match cart.huc1_ir_led() {
+                            Some(led) => cart.read(addr) | self.ir_device.receiving(led) as u8,
+                            None => cart.read(addr),
+                        },
                         None => EMPTY_BYTE,
                     }
                 },
@@ -3634,7 +3657,13 @@ impl memory::Addressable for Mmio {
                     }
                 },
                 EXTERNAL_RAM_START..=EXTERNAL_RAM_END => {
-                    if let Some(cart) = self.cartridge.as_mut() { cart.write(addr, value) }
+                    if let Some(cart) = self.cartridge.as_mut() {
+                        cart.write(addr, value);
+                        // HuC-1 IR mode: drive the cart's LED onto the partner.
+                        if let Some(led) = cart.huc1_ir_led() {
+                            self.ir_device.set_emitter(led);
+                        }
+                    }
                 },
                 WRAM_START..=WRAM_END => self.wram.write(addr, value),
                 WRAM_BANK_START..=WRAM_BANK_END => self.banked_wram_mut().write(addr, value),
@@ -3815,6 +3844,12 @@ impl memory::Addressable for Mmio {
                             self.ir_device.set_emitter((value & 0x01) != 0);
                         }
 
+                        // IF stores only the five source bits; the rest are
+                        // `or_mask::IF` on read, so nothing can latch there.
+                        cpu::registers::INTERRUPT_FLAG => {
+                            self.io_registers.write(addr, value & cpu::registers::INTERRUPT_BITS)
+                        }
+
                         _ => self.io_registers.write(addr, value),
                     }
                 }
@@ -4026,4 +4061,102 @@ mod bios_crc_tests {
         // AGB's masked crc at the wrong (256) length is rejected.
         assert!(!bios_crc_is_known(BIOS_SIZE, AGB_BIOS_CRC32, 0));
     }
+
+    /// The overlay is chosen by image size: 256 bytes maps only 0x000-0x0FF;
+    /// 2304 bytes also maps 0x200-0x8FF and leaves the 0x100-0x1FF header
+    /// window to the cartridge. Writing FF50 unmaps either.
+    #[test]
+    fn overlay_layout_follows_image_size() {
+        let mut m = Mmio::new();
+        m.bios = Some(vec![0xD0; BIOS_SIZE]);
+        assert_eq!(m.bios_overlay_read(0x00FF), Some(0xD0));
+        assert_eq!(m.bios_overlay_read(0x0100), None);
+        assert_eq!(m.bios_overlay_read(0x0200), None);
+
+        let mut cgb = vec![0xC0; CGB_BIOS_SIZE];
+        cgb[0x0200] = 0x22;
+        cgb[0x08FF] = 0x88;
+        m.bios = Some(cgb);
+        assert_eq!(m.bios_overlay_read(0x0000), Some(0xC0));
+        assert_eq!(m.bios_overlay_read(0x0100), None, "header window");
+        assert_eq!(m.bios_overlay_read(0x01FF), None, "header window");
+        assert_eq!(m.bios_overlay_read(0x0200), Some(0x22));
+        assert_eq!(m.bios_overlay_read(0x08FF), Some(0x88));
+        assert_eq!(m.bios_overlay_read(0x0900), None);
+
+        m.io_registers.write(REG_BOOT_OFF, 1);
+        assert_eq!(m.bios_overlay_read(0x0000), None);
+    }
+}
+
+#[cfg(test)]
+mod unused_io_tests {
+    //! Unused IO: holes with no register read 0xFF and unused bits of real
+    //! registers read 1 whatever was written (the `or_mask` table), as the
+    //! mooneye unused_hwio ROMs check.
+    use super::*;
+
+    /// Write `value` everywhere in `addrs` on a DMG bus, then read each back.
+    fn write_then_read(m: &mut Mmio, addrs: impl IntoIterator<Item = u16>, value: u8) -> Vec<(u16, u8)> {
+        addrs
+            .into_iter()
+            .map(|addr| {
+                m.write(addr, value);
+                (addr, m.read(addr))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn holes_read_ff_on_dmg() {
+        let mut m = Mmio::new();
+        let holes = [0xFF03, 0xFF15, 0xFF1F, 0xFF4E]
+            .into_iter()
+            .chain(0xFF08..=0xFF0E)
+            .chain(0xFF27..=0xFF2F)
+            .chain(0xFF78..=0xFF7F);
+        for (addr, read) in write_then_read(&mut m, holes, 0x00) {
+            assert_eq!(read, 0xFF, "{addr:#06X}");
+        }
+    }
+
+    #[test]
+    fn cgb_registers_read_ff_on_dmg() {
+        let mut m = Mmio::new();
+        let cgb_only = [REG_KEY1, REG_VBK, REG_HDMA5, REG_BCPS, REG_BCPD, REG_OCPS, REG_OCPD, REG_SVBK, 0xFF6C, 0xFF75];
+        for (addr, read) in write_then_read(&mut m, cgb_only, 0x00) {
+            assert_eq!(read, 0xFF, "{addr:#06X}");
+        }
+    }
+
+    #[test]
+    fn unused_bits_read_one() {
+        let mut m = Mmio::new();
+        let masked = [
+            (timer::TAC, or_mask::TAC),
+            (cpu::registers::INTERRUPT_FLAG, or_mask::IF),
+            (ppu::LCD_STATUS, or_mask::STAT),
+        ];
+        for (addr, mask) in masked {
+            m.write(addr, 0x00);
+            assert_eq!(m.read(addr) & mask, mask, "{addr:#06X} unused bits");
+        }
+        m.write(cpu::registers::INTERRUPT_FLAG, 0x1F);
+        assert_eq!(m.read(cpu::registers::INTERRUPT_FLAG), 0xFF, "the five flags are real bits");
+    }
+
+    /// IF never stores its unused bits, so the raw pending test can't see a
+    /// stale one; IE keeps all eight bits but only the low five arm anything.
+    #[test]
+    fn if_and_ie_upper_bits() {
+        let mut m = Mmio::new();
+        m.write(cpu::registers::INTERRUPT_FLAG, 0xE4);
+        assert_eq!(m.io_registers.read(cpu::registers::INTERRUPT_FLAG), 0x04);
+        assert_eq!(m.read(cpu::registers::INTERRUPT_FLAG), 0xE4);
+        m.write(IE_REGISTER, 0xE0);
+        assert_eq!(m.read(IE_REGISTER), 0xE0, "IE reads back every bit written");
+        assert_eq!(m.pending_if_ie(), 0, "upper IE bits arm nothing");
+        m.write(IE_REGISTER, 0xFF);
+        assert_eq!(m.pending_if_ie(), 0x04);
+    }
 }
@@ -19,7 +19,8 @@ use crate::input::ButtonState;
 use serde::{Deserialize, Serialize};
 
 /// How a movie begins: cold power-on (then `skip_bios`), or resumed from a
-/// serialized `GB` savestate (the exact bytes `GB::to_state_bytes` yields).
+/// serialized `GB` savestate (the exact bytes `GB::to_state_bytes` yields),
+/// either embedded in the movie or referenced by its SHA-256.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum MovieStart {
     /// Power on, insert the ROM, `skip_bios()`, then feed inputs from frame 0.
@@ -27,6 +28,23 @@ pub enum MovieStart {
     /// Resume from a savestate blob (`GB::to_state_bytes` bincode); inputs
     /// continue from the savestate's current frame.
     SaveState(Vec<u8>),
+    /// Resume from a savestate the player already has, named by the SHA-256
+    /// of its `GB::to_state_bytes` blob instead of carrying it. Keeps the
+    /// movie small; playback must find a state with exactly this hash and
+    /// refuse to start otherwise.
+    SaveStateRef([u8; 32]),
+}
+
+impl MovieStart {
+    /// SHA-256 of the anchor savestate blob, embedded or referenced; `None`
+    /// for a power-on movie.
+    pub fn anchor_sha256(&self) -> Option<[u8; 32]> {
+        match self {
+            MovieStart::PowerOn => None,
+            MovieStart::SaveState(blob) => Some(sha256(blob)),
+            MovieStart::SaveStateRef(hash) => Some(*hash),
+        }
+    }
 }
 
 /// Descriptive, non-load-bearing movie metadata. None of these affect replay.
@@ -50,6 +68,9 @@ pub struct Movie {
     /// applied to the cartridge it was authored for.
     pub rom_sha256: [u8; 32],
     pub hardware: Hardware,
+    /// Whether the DMG OAM corruption bug was emulated ([`GB::set_oam_bug`]).
+    /// It changes what the CPU reads back from OAM, so replay must match it.
+    pub oam_bug: bool,
     pub start: MovieStart,
     pub inputs: Vec<ButtonState>,
     pub meta: MovieMeta,
@@ -66,21 +87,30 @@ pub struct Movie {
 /// replayed (`play_movie` surfaces whatever `GB::from_state_bytes` returns);
 /// `PowerOn` movies are unaffected, and bumping here would wrongly reject them
 /// too.
+///
+/// Version 2 added the machine-flags byte; version 1 movies still load, as
+/// recorded with the OAM bug on (the only behaviour before it was a setting).
 const MOVIE_MAGIC: &[u8; 4] = b"RBMV";
-const MOVIE_VERSION: u8 = 1;
+const MOVIE_VERSION: u8 = 2;
+
+/// Machine-flags byte bits.
+const FLAG_NO_OAM_BUG: u8 = 1;
 
 impl Movie {
     /// Serialize to a compact, deterministic byte buffer.
     ///
-    /// Layout: `RBMV` magic, version, ROM hash, hardware id, start kind (+ blob
-    /// for `SaveState`), the input frames each packed to one byte, then the
-    /// metadata as UTF-8 length-prefixed fields. Fully self-contained, no deps.
+    /// Layout: `RBMV` magic, version, ROM hash, hardware id, machine flags
+    /// (bit 0: OAM bug off), start kind (+ blob for `SaveState`, + hash for
+    /// `SaveStateRef`), the input frames each
+    /// packed to one byte, then the metadata as UTF-8 length-prefixed fields.
+    /// Fully self-contained, no deps.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut out = Vec::with_capacity(64 + self.inputs.len());
         out.extend_from_slice(MOVIE_MAGIC);
         out.push(MOVIE_VERSION);
         out.extend_from_slice(&self.rom_sha256);
         out.push(hardware_to_id(self.hardware));
+        out.push(if self.oam_bug { 0 } else { FLAG_NO_OAM_BUG });
         match &self.start {
             MovieStart::PowerOn => out.push(0),
             MovieStart::SaveState(blob) => {
@@ -88,6 +118,10 @@ impl Movie {
                 out.extend_from_slice(&(blob.len() as u32).to_le_bytes());
                 out.extend_from_slice(blob);
             }
+            MovieStart::SaveStateRef(hash) => {
+                out.push(2);
+                out.extend_from_slice(hash);
+            }
         }
         out.extend_from_slice(&(self.inputs.len() as u32).to_le_bytes());
         for input in &self.inputs {
@@ -108,18 +142,24 @@ impl Movie {
             return Err(MovieError::BadMagic);
         }
         let version = r.u8()?;
-        if version != MOVIE_VERSION {
+        if !(1..=MOVIE_VERSION).contains(&version) {
             return Err(MovieError::Version(version));
         }
         let mut rom_sha256 = [0u8; 32];
         rom_sha256.copy_from_slice(r.take(32)?);
         let hardware = hardware_from_id(r.u8()?)?;
+        let flags = if version >= 2 { r.u8()? } else { 0 };
         let start = match r.u8()? {
             0 => MovieStart::PowerOn,
             1 => {
                 let len = r.u32()? as usize;
                 MovieStart::SaveState(r.take(len)?.to_vec())
             }
+            2 => {
+                let mut hash = [0u8; 32];
+                hash.copy_from_slice(r.take(32)?);
+                MovieStart::SaveStateRef(hash)
+            }
             k => return Err(MovieError::StartKind(k)),
         };
         let n = r.u32()? as usize;
@@ -134,11 +174,26 @@ impl Movie {
         Ok(Movie {
             rom_sha256,
             hardware,
+            oam_bug: flags & FLAG_NO_OAM_BUG == 0,
             start,
             inputs,
             meta: MovieMeta { author, rom_name, frame_count, note },
         })
     }
+
+    /// Swap an embedded savestate start for a reference to it by hash,
+    /// returning the blob so the caller can keep it where playback will look.
+    /// `None` (and no change) unless the movie embeds its anchor.
+    pub fn reference_anchor(&mut self) -> Option<Vec<u8>> {
+        let MovieStart::SaveState(blob) = &self.start else {
+            return None;
+        };
+        let hash = sha256(blob);
+        match core::mem::replace(&mut self.start, MovieStart::SaveStateRef(hash)) {
+            MovieStart::SaveState(blob) => Some(blob),
+            _ => unreachable!(),
+        }
+    }
 }
 
 /// Errors from decoding a movie byte buffer.
@@ -239,6 +294,7 @@ impl<'a> Recorder<'a> {
         Movie {
             rom_sha256: self.rom_sha256,
             hardware: self.hardware,
+            oam_bug: self.gb.oam_bug(),
             start: self.start,
             inputs: self.inputs,
             meta: self.meta,
@@ -262,8 +318,8 @@ pub struct ReplayResult {
 /// Replay a movie's inputs frame-by-frame against `gb`, which the caller must
 /// have already brought to the movie's start condition (ROM inserted; for a
 /// `PowerOn` movie, `skip_bios()` called; for a `SaveState` movie, deserialized
-/// from `movie.start`'s blob). Feeds `inputs[i]`, then steps one frame, for
-/// every recorded frame.
+/// from `movie.start`'s blob). Switches the OAM bug to match the recording,
+/// then feeds `inputs[i]` and steps one frame, for every recorded frame.
 ///
 /// `collect_frame_hashes` controls whether the per-frame trace is populated
 /// (off keeps replay allocation-free beyond the single final frame).
@@ -275,6 +331,7 @@ pub fn replay(movie: &Movie, gb: &mut GB, collect_frame_hashes: bool) -> ReplayR
     };
     let mut final_frame_hash = 0u64;
     let mut boot_ok = false;
+    gb.set_oam_bug(movie.oam_bug);
     for input in &movie.inputs {
         gb.set_input_state(*input);
         let (frame, _breakpoint) = gb.run_until_frame(false);
@@ -292,6 +349,43 @@ pub fn replay(movie: &Movie, gb: &mut GB, collect_frame_hashes: bool) -> ReplayR
     }
 }
 
+/// The first frame two replays of the same movie disagreed on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// Zero-based input frame index.
+    pub frame: usize,
+    /// That frame's hash in the first and the second run.
+    pub first: u64,
+    pub second: u64,
+}
+
+impl core::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "replays diverged at frame {}: {:016x} vs {:016x}",
+            self.frame, self.first, self.second
+        )
+    }
+}
+
+/// Replay `movie` twice, each time on a fresh machine from `boot` (which must
+/// bring it to the movie's start condition, as for [`replay`]), and compare
+/// the two runs frame by frame. Returns the first run's result when every
+/// frame hash matches, else the first frame that differed.
+pub fn verify_determinism(movie: &Movie, mut boot: impl FnMut() -> GB) -> Result<ReplayResult, Divergence> {
+    let first = replay(movie, &mut boot(), true);
+    let second = replay(movie, &mut boot(), true);
+    match first.frame_hashes.iter().zip(&second.frame_hashes).position(|(a, b)| a != b) {
+        Some(frame) => Err(Divergence {
+            frame,
+            first: first.frame_hashes[frame],
+            second: second.frame_hashes[frame],
+        }),
+        None => Ok(first),
+    }
+}
+
 /// Deterministic 64-bit hash of a frame's raw bytes (FNV-1a). Stable across
 /// runs and machines; the golden-regression and per-frame trace key. Includes a
 /// 1-byte tag so a monochrome and a color frame of coincidentally-equal bytes
@@ -453,7 +547,7 @@ fn pack_buttons(s: &ButtonState) -> u8 {
         | (s.down as u8) << 7
 }
 
-fn unpack_buttons(b: u8) -> ButtonState {
+pub(crate) fn unpack_buttons(b: u8) -> ButtonState {
     ButtonState {
         a: b & 1 != 0,
         b: b & 2 != 0,
@@ -690,6 +784,7 @@ mod tests {
         let movie = Movie {
             rom_sha256: sha256(b"rom-bytes"),
             hardware: Hardware::CGB,
+            oam_bug: false,
             start: MovieStart::SaveState(vec![1, 2, 3, 4, 5]),
             inputs: vec![
                 ButtonState { a: true, ..Default::default() },
@@ -710,6 +805,47 @@ mod tests {
         assert_eq!(bytes, decoded.to_bytes());
     }
 
+    #[test]
+    fn referenced_anchor_round_trips_by_hash() {
+        let blob = vec![9, 8, 7, 6];
+        let mut movie = Movie {
+            rom_sha256: sha256(b"rom-bytes"),
+            hardware: Hardware::DMG,
+            oam_bug: true,
+            start: MovieStart::SaveState(blob.clone()),
+            inputs: vec![ButtonState::default(); 2],
+            meta: MovieMeta::default(),
+        };
+        let embedded = movie.start.anchor_sha256();
+        assert_eq!(movie.reference_anchor(), Some(blob.clone()));
+        assert_eq!(movie.start, MovieStart::SaveStateRef(sha256(&blob)));
+        assert_eq!(movie.start.anchor_sha256(), embedded);
+        assert_eq!(movie.reference_anchor(), None, "already a reference");
+
+        let decoded = Movie::from_bytes(&movie.to_bytes()).expect("decode");
+        assert_eq!(decoded, movie);
+    }
+
+    /// A version 1 movie (no flags byte) still loads, with the OAM bug on.
+    #[test]
+    fn version_1_movies_load_with_the_oam_bug_on() {
+        let movie = Movie {
+            rom_sha256: sha256(b"rom-bytes"),
+            hardware: Hardware::DMG,
+            oam_bug: true,
+            start: MovieStart::PowerOn,
+            inputs: vec![ButtonState { a: true, ..Default::default() }],
+            meta: MovieMeta::default(),
+        };
+        let mut v1 = movie.to_bytes();
+        v1[4] = 1;
+        v1.remove(4 + 1 + 32 + 1);
+        assert_eq!(Movie::from_bytes(&v1).unwrap(), movie);
+        let mut future = movie.to_bytes();
+        future[4] = MOVIE_VERSION + 1;
+        assert_eq!(Movie::from_bytes(&future).unwrap_err(), MovieError::Version(MOVIE_VERSION + 1));
+    }
+
     #[test]
     fn from_bytes_rejects_garbage() {
         assert_eq!(Movie::from_bytes(b"nope").unwrap_err(), MovieError::BadMagic);
@@ -770,6 +906,36 @@ mod tests {
         assert_eq!(r1, r3, "serialized movie must replay identically");
     }
 
+    /// `verify_determinism` passes on identical machines and names the first
+    /// frame that differs when the two boots do not match.
+    #[test]
+    fn verify_determinism_reports_the_first_diverging_frame() {
+        let rom = test_rom();
+        let movie = Movie {
+            rom_sha256: sha256(&rom),
+            hardware: Hardware::DMG,
+            oam_bug: true,
+            start: MovieStart::PowerOn,
+            inputs: vec![ButtonState::default(); 8],
+            meta: MovieMeta::default(),
+        };
+        let result = verify_determinism(&movie, || fresh_gb(&rom)).expect("deterministic");
+        assert_eq!(result.frames, 8);
+
+        // A second boot on other hardware stands in for a nondeterministic core.
+        let mut boots = 0;
+        let divergence = verify_determinism(&movie, || {
+            boots += 1;
+            let mut gb = GB::new(if boots == 1 { Hardware::DMG } else { Hardware::CGB });
+            gb.insert(Cartridge::from_bytes(&rom).unwrap());
+            gb.skip_bios();
+            gb
+        })
+        .unwrap_err();
+        assert_eq!(divergence.frame, 0);
+        assert_ne!(divergence.first, divergence.second);
+    }
+
     /// Concrete proof of the user's example: a DMG game (no CGB flag) booted on
     /// CGB hardware is colorized by the CGB boot ROM's compatibility palette,
     /// and a d-pad direction held at `skip_bios()` time overrides the automatic
@@ -0,0 +1,235 @@
+//! Import other emulators' movies into the native [`Movie`] format, so an
+//! existing TAS can be replayed here and its frames compared against the
+//! emulator that recorded it.
+//!
+//! Pure byte ops, like [`crate::patch`]. The entry point is [`import_movie`],
+//! which sniffs the format: a native `.rbmovie`, a VBA / VBA-M `.vbm`, or a
+//! BGB input log. Only power-on movies convert; a movie that starts from
+//! another emulator's savestate or SRAM cannot be reproduced and is refused.
+//!
+//! BGB input logs are plain text with one line per frame naming the buttons
+//! held (`A`, `B`, `Select`, `Start`, `Right`, `Left`, `Up`, `Down`, any case,
+//! separated by spaces, commas or `+`). A blank line or `-` holds nothing;
+//! lines starting with `#` or `;` are comments.
+
+use crate::gb::Hardware;
+use crate::input::ButtonState;
+use crate::movie::{unpack_buttons, Movie, MovieMeta, MovieStart};
+
+const VBM_MAGIC: &[u8; 4] = b"VBM\x1a";
+/// Size of the fixed `.vbm` header; the offsets below index into it.
+const VBM_HEADER_LEN: usize = 0x100;
+/// Start flags: the movie begins from an embedded savestate, or from SRAM.
+const VBM_FROM_SNAPSHOT: u8 = 1;
+const VBM_FROM_SRAM: u8 = 2;
+/// System flags: which machine VBA emulated (none set: a plain Game Boy).
+const VBM_SYSTEM_GBA: u8 = 1;
+const VBM_SYSTEM_GBC: u8 = 2;
+const VBM_SYSTEM_SGB: u8 = 4;
+/// Controller-word bit VBA sets on a frame that resets the machine.
+const VBM_RESET: u16 = 0x0800;
+
+/// Decode a movie in any supported format. `rom` is the loaded ROM image:
+/// an imported movie is stamped with `rom_sha256` (the formats carry no
+/// SHA-256), after checking the header checksums a `.vbm` does carry. A
+/// BGB log names no machine, so it is stamped with `hardware`.
+pub fn import_movie(bytes: &[u8], rom: Option<&[u8]>, rom_sha256: [u8; 32], hardware: Hardware) -> Result<Movie, String> {
+    if bytes.starts_with(b"RBMV") {
+        Movie::from_bytes(bytes).map_err(|e| e.to_string())
+    } else if bytes.starts_with(VBM_MAGIC) {
+        import_vbm(bytes, rom, rom_sha256)
+    } else if let Ok(text) = std::str::from_utf8(bytes) {
+        import_bgb_log(text, rom_sha256, hardware)
+    } else {
+        Err("unrecognized movie format (expected .rbmovie, .vbm, or a BGB input log)".into())
+    }
+}
+
+/// Convert a VBA / VBA-M `.vbm`, keeping controller 1's input.
+pub fn import_vbm(bytes: &[u8], rom: Option<&[u8]>, rom_sha256: [u8; 32]) -> Result<Movie, String> {
+    if bytes.len() < VBM_HEADER_LEN || !bytes.starts_with(VBM_MAGIC) {
+        return Err("VBM header truncated".into());
+    }
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+    let frames = u32_at(0x0c) as usize;
+    let start = bytes[0x14];
+    let controllers = bytes[0x15] & 0x0f;
+    let system = bytes[0x16];
+    if start & VBM_FROM_SNAPSHOT != 0 {
+        return Err("VBM starts from a VBA savestate, which cannot be converted".into());
+    }
+    if start & VBM_FROM_SRAM != 0 {
+        return Err("VBM starts from saved SRAM, which cannot be converted".into());
+    }
+    if system & VBM_SYSTEM_GBA != 0 {
+        return Err("VBM was recorded on the Game Boy Advance core".into());
+    }
+    if controllers & 1 == 0 {
+        return Err("VBM has no input for controller 1".into());
+    }
+    if let Some(rom) = rom
+        && rom.len() >= 0x150
+    {
+        let header_checksum = rom[0x14d];
+        let global_checksum = u16::from_be_bytes([rom[0x14e], rom[0x14f]]);
+        let vbm_global = u16::from_le_bytes([bytes[0x32], bytes[0x33]]);
+        if bytes[0x31] != header_checksum || vbm_global != global_checksum {
+            return Err("VBM was recorded against a different ROM".into());
+        }
+    }
+
+    // Each frame holds one 16-bit word per recorded controller, in order.
+    let stride = 2 * controllers.count_ones() as usize;
+    let data = u32_at(0x3c) as usize;
+    let end = frames.checked_mul(stride).and_then(|n| n.checked_add(data));
+    let input = end.and_then(|end| bytes.get(data..end)).ok_or("VBM input data truncated")?;
+    let mut inputs = Vec::with_capacity(frames);
+    for (frame, word) in input.chunks_exact(stride).enumerate() {
+        let word = u16::from_le_bytes([word[0], word[1]]);
+        if word & VBM_RESET != 0 {
+            return Err(format!("VBM resets the machine on frame {frame}, which cannot be converted"));
+        }
+        inputs.push(unpack_buttons(word as u8));
+    }
+
+    let hardware = if system & VBM_SYSTEM_GBC != 0 {
+        Hardware::CGB
+    } else if system & VBM_SYSTEM_SGB != 0 {
+        Hardware::SGB
+    } else {
+        Hardware::DMG
+    };
+    Ok(Movie {
+        rom_sha256,
+        hardware,
+        // Both emulators always emulate the corruption.
+        oam_bug: true,
+        start: MovieStart::PowerOn,
+        meta: MovieMeta {
+            author: c_string(&bytes[0x40..0x80]),
+            rom_name: c_string(&bytes[0x24..0x30]),
+            frame_count: inputs.len() as u32,
+            note: format!("Imported from VBM ({} rerecords)", u32_at(0x10)),
+        },
+        inputs,
+    })
+}
+
+/// Convert a BGB input log (see the module docs for the layout).
+pub fn import_bgb_log(text: &str, rom_sha256: [u8; 32], hardware: Hardware) -> Result<Movie, String> {
+    let mut inputs = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        let mut input = ButtonState::default();
+        for name in line.split([' ', '\t', ',', '+']).filter(|s| !s.is_empty() && *s != "-") {
+            let held = match name.to_ascii_lowercase().as_str() {
+                "a" => &mut input.a,
+                "b" => &mut input.b,
+                "select" => &mut input.select,
+                "start" => &mut input.start,
+                "right" => &mut input.right,
+                "left" => &mut input.left,
+                "up" => &mut input.up,
+                "down" => &mut input.down,
+                _ => return Err(format!("BGB log line {}: unknown button `{name}`", n + 1)),
+            };
+            *held = true;
+        }
+        inputs.push(input);
+    }
+    if inputs.is_empty() {
+        return Err("BGB log holds no frames".into());
+    }
+    Ok(Movie {
+        rom_sha256,
+        hardware,
+        oam_bug: true,
+        start: MovieStart::PowerOn,
+        meta: MovieMeta {
+            frame_count: inputs.len() as u32,
+            note: "Imported from a BGB input log".into(),
+            ..MovieMeta::default()
+        },
+        inputs,
+    })
+}
+
+/// A NUL-terminated (or field-filling) string from a fixed-size header field.
+fn c_string(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vbm(start: u8, words: &[u16]) -> Vec<u8> {
+        let mut out = vec![0u8; VBM_HEADER_LEN];
+        out[..4].copy_from_slice(VBM_MAGIC);
+        out[0x04] = 1;
+        out[0x0c..0x10].copy_from_slice(&(words.len() as u32).to_le_bytes());
+        out[0x10] = 7;
+        out[0x14] = start;
+        out[0x15] = 1;
+        out[0x24..0x28].copy_from_slice(b"TEST");
+        out[0x3c..0x40].copy_from_slice(&(VBM_HEADER_LEN as u32).to_le_bytes());
+        out[0x40..0x43].copy_from_slice(b"tas");
+        for w in words {
+            out.extend_from_slice(&w.to_le_bytes());
+        }
+        out
+    }
+
+    #[test]
+    fn vbm_power_on_movie_converts_controller_one() {
+        let movie = import_movie(&vbm(0, &[0x0001, 0x0090, 0]), None, [3; 32], Hardware::CGB).unwrap();
+        assert_eq!(movie.start, MovieStart::PowerOn);
+        assert_eq!(movie.hardware, Hardware::DMG);
+        assert_eq!(movie.rom_sha256, [3; 32]);
+        assert_eq!(movie.inputs[0], ButtonState { a: true, ..Default::default() });
+        assert_eq!(movie.inputs[1], ButtonState { right: true, down: true, ..Default::default() });
+        assert_eq!(movie.inputs[2], ButtonState::default());
+        assert_eq!((movie.meta.author.as_str(), movie.meta.rom_name.as_str()), ("tas", "TEST"));
+        assert_eq!(movie.meta.frame_count, 3);
+    }
+
+    #[test]
+    fn vbm_system_flags_pick_the_machine() {
+        for (system, hardware) in [(VBM_SYSTEM_SGB, Hardware::SGB), (VBM_SYSTEM_GBC, Hardware::CGB), (0, Hardware::DMG)] {
+            let mut bytes = vbm(0, &[0]);
+            bytes[0x16] = system;
+            assert_eq!(import_vbm(&bytes, None, [0; 32]).unwrap().hardware, hardware, "system {system}");
+        }
+        let mut gba = vbm(0, &[0]);
+        gba[0x16] = VBM_SYSTEM_GBA;
+        assert!(import_vbm(&gba, None, [0; 32]).is_err());
+    }
+
+    #[test]
+    fn vbm_movies_that_cannot_be_reproduced_are_refused() {
+        assert!(import_vbm(&vbm(VBM_FROM_SNAPSHOT, &[0]), None, [0; 32]).is_err());
+        assert!(import_vbm(&vbm(VBM_FROM_SRAM, &[0]), None, [0; 32]).is_err());
+        assert!(import_vbm(&vbm(0, &[0, VBM_RESET]), None, [0; 32]).is_err());
+        assert!(import_vbm(&vbm(0, &[0])[..0x20], None, [0; 32]).is_err());
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x14d] = 0x42;
+        assert_eq!(import_vbm(&vbm(0, &[0]), Some(&rom), [0; 32]).unwrap_err(), "VBM was recorded against a different ROM");
+    }
+
+    #[test]
+    fn bgb_log_lines_become_frames() {
+        let log = "# recorded in BGB\nA\n\nright, down\n-\nStart+Select\n";
+        let movie = import_movie(log.as_bytes(), None, [0; 32], Hardware::MGB).unwrap();
+        assert_eq!(movie.hardware, Hardware::MGB);
+        assert_eq!(movie.inputs.len(), 5);
+        assert!(movie.inputs[0].a);
+        assert_eq!(movie.inputs[1], ButtonState::default());
+        assert_eq!(movie.inputs[2], ButtonState { right: true, down: true, ..Default::default() });
+        assert_eq!(movie.inputs[4], ButtonState { start: true, select: true, ..Default::default() });
+        assert!(import_bgb_log("A\nturbo\n", [0; 32], Hardware::DMG).unwrap_err().contains("line 2"));
+    }
+}
@@ -1,9 +1,15 @@
 //! Native ROM patching: apply IPS / UPS / BPS patches to a loaded ROM so users
 //! can run romhacks and translations without external tools.
 //!
-//! Pure byte ops — no I/O, no deps — so it builds identically on desktop, web
-//! (wasm32), and Android. The entry point is [`apply_patch`], which auto-detects
-//! the format by magic and returns the patched ROM bytes.
+//! Pure byte ops — no deps — so it builds identically on desktop, web (wasm32),
+//! and Android. The entry point is [`apply_patch`], which auto-detects the
+//! format by magic and returns the patched ROM bytes. The only I/O is the
+//! sidecar lookup [`Cartridge::load`](crate::cartridge::Cartridge::load) uses to
+//! soft-patch a ROM from a `.bps`/`.ups`/`.ips` sitting next to it.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 
 /// Apply an IPS / UPS / BPS `patch` to `rom`, auto-detecting the format by its
 /// magic bytes. Returns the patched output on success, or a human-readable error.
@@ -19,9 +25,41 @@ pub fn apply_patch(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, String> {
     }
 }
 
+// --- sidecar soft-patches --------------------------------------------------
+
+/// Patch extensions probed next to a ROM, most robust format first (BPS and UPS
+/// verify their source and output CRCs; IPS cannot).
+const SIDECAR_EXTENSIONS: [&str; 3] = ["bps", "ups", "ips"];
+
+/// Read the soft-patch stored next to `rom_path` under the same base name
+/// (`Game.gb` -> `Game.bps` / `Game.ups` / `Game.ips`), if there is one.
+pub fn read_sidecar_patch(rom_path: &Path) -> io::Result<Option<(PathBuf, Vec<u8>)>> {
+    for ext in SIDECAR_EXTENSIONS {
+        let candidate = rom_path.with_extension(ext);
+        match fs::read(&candidate) {
+            Ok(bytes) => return Ok(Some((candidate, bytes))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(None)
+}
+
+/// Soft-patch `rom` in memory with the sidecar patch for `rom_path` (see
+/// [`read_sidecar_patch`]); neither file on disk is touched. `rom` comes back
+/// unchanged when there is no sidecar. A patch that does not apply is an error
+/// naming the patch file, never a silently unpatched game.
+pub fn apply_sidecar_patch(rom_path: &Path, rom: Vec<u8>) -> io::Result<Vec<u8>> {
+    let Some((patch_path, patch)) = read_sidecar_patch(rom_path)? else {
+        return Ok(rom);
+    };
+    apply_patch(&rom, &patch)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", patch_path.display())))
+}
+
 // --- CRC32 (IEEE, reflected) -----------------------------------------------
 
-pub(crate) use rustyboi_core_lib::checksum::crc32;
+use crate::checksum::crc32;
 
 // --- IPS --------------------------------------------------------------------
 
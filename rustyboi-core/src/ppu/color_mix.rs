@@ -4,7 +4,7 @@ use super::controller::{
     lcdc_has, ColorCorrection, LCDCFlags, Ppu, Sprite, SpriteAttributes, SpriteFetchPhase,
     SpriteFetchRec, LY, MAX_SPRITES_PER_LINE, OAMDMA_CHANGE_CC_OFFSET, OAM_BYTES_PER_SPRITE,
     OAM_SPRITE_COUNT, OBJ_READ_HIGH_BACK, OBJ_READ_HIGH_BACK_CGB, OBJ_READ_LOW_BACK,
-    OBJ_READ_LOW_BACK_CGB, SCX,
+    OBJ_READ_LOW_BACK_CGB, SCX, State,
 };
 
 /// Game Boy Advance LCD colour curve as a 15-bit-colour -> RGB888 table, built
@@ -359,9 +359,24 @@ impl Ppu {
         mmio.peek_oam_pos(&mut pos);
         self.objs.oam_reader.update(cc, &lc, &pos);
 
+        self.map_sprites_from_snapshot(mmio, mmio.read(LY));
+        // Ghost propagation stop: any sprite fetched on THIS line while the DMA
+        // window is still open rewrites the Y bus with a mid-DMA tile byte
+        // (on hardware a mid-DMA sprite fetch clobbers the Y bus), so the retained scan pair does not survive
+        // into the NEXT line's walk (strikethrough: the ghost bar renders on
+        // line 68 only; line 69's scan — still inside the ~1.4-line window —
+        // sees the clobbered bus and stays clean).
+        if self.objs.oam_reader.src_disabled && !self.objs.sprites_on_line.is_empty() {
+            self.objs.oam_reader.ghost = (0xFF, 0xFF);
+        }
+    }
+
+    /// Re-map `sprites_on_line` from the posbuf for line `ly`: the sprite
+    /// mapping half of `build_sprites_from_snapshot`, without the flush.
+    fn map_sprites_from_snapshot(&mut self, mmio: &mmio::Mmio, ly: u8) {
         self.objs.sprites_on_line.clear();
-        let ly = mmio.read(LY);
         for i in 0..OAM_SPRITE_COUNT {
+
             if self.objs.sprites_on_line.len() >= MAX_SPRITES_PER_LINE {
                 break;
             }
@@ -409,14 +424,45 @@ impl Ppu {
                 });
             }
         }
-        // Ghost propagation stop: any sprite fetched on THIS line while the DMA
-        // window is still open rewrites the Y bus with a mid-DMA tile byte
-        // (on hardware a mid-DMA sprite fetch clobbers the Y bus), so the retained scan pair does not survive
-        // into the NEXT line's walk (strikethrough: the ghost bar renders on
-        // line 68 only; line 69's scan — still inside the ~1.4-line window —
-        // sees the clobbered bus and stays clean).
-        if self.objs.oam_reader.src_disabled && !self.objs.sprites_on_line.is_empty() {
-            self.objs.oam_reader.ghost = (0xFF, 0xFF);
+    }
+
+    /// Put the line's sprites in fetch order: OAM index on CGB, X then OAM
+    /// index on DMG.
+    pub(in crate::ppu) fn sort_sprites_for_fetch(&mut self, is_cgb: bool) {
+        if is_cgb {
+            self.objs.sprites_on_line.sort_by_key(|sprite| sprite.oam_index);
+        } else {
+            self.objs.sprites_on_line.sort_by(|a, b| {
+                a.x.cmp(&b.x).then(a.oam_index.cmp(&b.oam_index))
+            });
+        }
+    }
+
+    /// Rebuild `sprites_on_line`, which a save state does not carry, on a
+    /// restored machine. Mid-mode-2 the incremental scan re-reads the slots it
+    /// already visited; from the mode-2-end mapping until the next line's scan
+    /// (mode 3, HBlank, and line 143's list held through VBlank) the list is
+    /// re-mapped from the restored OAM snapshot. Exact unless an OAM DMA ran
+    /// between the mapping and the save.
+    pub(crate) fn rebuild_sprites_after_load(&mut self, mmio: &mut mmio::Mmio) {
+        self.objs.sprites_on_line.clear();
+        if self.disabled || self.clk.first_line_after_enable {
+            return;
+        }
+        match self.state {
+            State::OAMSearch => {
+                let latch = self.objs.scan_obj_size_large;
+                for i in 0..self.objs.current_oam_sprite_index.min(OAM_SPRITE_COUNT) {
+                    self.objs.scan_obj_size_large = self.objs.scan_slot_large[i];
+                    self.check_single_sprite_for_scanline(mmio, i);
+                }
+                self.objs.scan_obj_size_large = latch;
+            }
+            State::PixelTransfer | State::HBlank | State::VBlank => {
+                let ly = if self.state == State::VBlank { 143 } else { mmio.read(LY) };
+                self.map_sprites_from_snapshot(mmio, ly);
+                self.sort_sprites_for_fetch(mmio.is_cgb_features_enabled());
+            }
         }
     }
 
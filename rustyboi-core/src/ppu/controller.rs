@@ -709,7 +709,7 @@ pub(in crate::ppu) const OBJ_READ_HIGH_BACK_CGB: u128 = 3;
 pub(in crate::ppu) const LINE_153_LY_ZERO_DOT: u128 = 6;
 
 // Sprite attribute flags (from byte 3 of sprite data)
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
 pub(crate) struct SpriteAttributes {
     pub priority: bool,    // 0 = above BG, 1 = behind BG colors 1-3
     pub y_flip: bool,      // 0 = normal, 1 = vertically mirrored
@@ -731,7 +731,7 @@ impl SpriteAttributes {
 }
 
 // Sprite data structure
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
 pub struct Sprite {
     pub y: u8,
     pub x: u8,
@@ -740,6 +740,58 @@ pub struct Sprite {
     pub(crate) oam_index: u8, // For priority resolution
 }
 
+// The mode-2 scan's per-line sprite list: at most MAX_SPRITES_PER_LINE
+// entries, held inline so the scan and every machine clone (rewind, run-ahead)
+// allocate nothing. Reads as a slice. Not part of the save state: a restore
+// re-maps it from the serialized OAM snapshot (`rebuild_sprites_after_load`).
+#[derive(Clone, Copy, Default)]
+pub(in crate::ppu) struct SpriteList {
+    sprites: [Sprite; MAX_SPRITES_PER_LINE],
+    len: u8,
+}
+
+impl SpriteList {
+    // Append `sprite`; a full list keeps its first ten, as the scan does.
+    pub(in crate::ppu) fn push(&mut self, sprite: Sprite) {
+        if let Some(slot) = self.sprites.get_mut(self.len as usize) {
+            *slot = sprite;
+            self.len += 1;
+        }
+    }
+
+    pub(in crate::ppu) fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl std::ops::Deref for SpriteList {
+    type Target = [Sprite];
+    fn deref(&self) -> &[Sprite] {
+        &self.sprites[..self.len as usize]
+    }
+}
+
+impl std::ops::DerefMut for SpriteList {
+    fn deref_mut(&mut self) -> &mut [Sprite] {
+        &mut self.sprites[..self.len as usize]
+    }
+}
+
+impl<'a> IntoIterator for &'a SpriteList {
+    type Item = &'a Sprite;
+    type IntoIter = std::slice::Iter<'a, Sprite>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// `sprites_on_line` in a payload older than version 5 (see
+// `savestate::dropped_in`); a current payload carries nothing here.
+fn legacy_sprite_list<'de, D: serde::Deserializer<'de>>(d: D) -> Result<SpriteList, D::Error> {
+    crate::savestate::dropped_in::<Vec<Sprite>, D>(5, d)?;
+    Ok(SpriteList::default())
+}
+
 // Live mode-3 per-sprite fetch record (parallel to `sprites_on_line`, same
 // index space as `next_sprite_fetch_index`). Tracks whether the live walk
 // actually fetched a sprite this line and at which dot its stall armed, so the
@@ -1173,11 +1225,24 @@ pub enum ColorCorrection {
 /// converts it to the unified always-RGB [`crate::gb::Frame`] using the DMG
 /// palette + colour correction, while the shade indices remain available
 /// (correction-independent) via [`Ppu::dmg_shade_frame`] for the test suite.
-pub(crate) enum RenderedFrame {
-    Monochrome(Box<[u8; FRAMEBUFFER_SIZE]>),
+/// The shade indices are borrowed (the GB expands them straight into the
+/// presented frame, no intermediate copy); a colour frame is already the
+/// presented frame's buffer and is handed over owned.
+pub(crate) enum RenderedFrame<'a> {
+    Monochrome(&'a [u8; FRAMEBUFFER_SIZE]),
     Color(Box<[u8; FRAMEBUFFER_SIZE * 3]>),
 }
 
+/// Blank shade frames for the panel-off and SGB mask cases, so a blank
+/// monochrome frame borrows like any other.
+static BLANK_SHADES: [[u8; FRAMEBUFFER_SIZE]; 4] =
+    [[0; FRAMEBUFFER_SIZE], [1; FRAMEBUFFER_SIZE], [2; FRAMEBUFFER_SIZE], [3; FRAMEBUFFER_SIZE]];
+
+/// The blank frame filled with DMG `shade` (0..=3).
+pub(in crate::ppu) fn blank_shades(shade: u8) -> &'static [u8; FRAMEBUFFER_SIZE] {
+    &BLANK_SHADES[usize::from(shade & 3)]
+}
+
 /// Speed-switch / STOP-bridge sub-dot corrections: the residual phase the
 /// whole-dot DS<->SS bridge cannot express, accumulated per switch.
 #[derive(Serialize, Deserialize, Clone, Default)]
@@ -1877,8 +1942,10 @@ impl Default for PlotHistory {
 /// stall pacing (plus the exact-cc OBJ-size latch each scan slot samples).
 #[derive(Serialize, Deserialize, Clone)]
 pub(in crate::ppu) struct ObjState {
-    // Sprite data for current scanline
-    pub(in crate::ppu) sprites_on_line: Vec<Sprite>,
+    // Sprite data for current scanline. Dropped from the state in version 5
+    // and rebuilt on restore; older payloads still carry it as a `Vec`.
+    #[serde(skip_serializing, deserialize_with = "legacy_sprite_list")]
+    pub(in crate::ppu) sprites_on_line: SpriteList,
     pub(in crate::ppu) current_oam_sprite_index: usize, // Current sprite being checked during OAM search
     // Lazy OAM Y/X snapshot. Drives sprite
     // visibility so an OAM-DMA overlapping mode-2 retroactively zeroes positions
@@ -1965,7 +2032,7 @@ pub(in crate::ppu) struct ObjState {
 impl Default for ObjState {
     fn default() -> Self {
         ObjState {
-            sprites_on_line: Vec::new(),
+            sprites_on_line: SpriteList::default(),
             current_oam_sprite_index: 0,
             oam_reader: OamReader::default(),
             prev_dma_writing: false,
@@ -2357,6 +2424,10 @@ pub struct Ppu {
     pub(in crate::ppu) wg: BusGlitch,
     #[serde(default)]
     pub(in crate::ppu) cgb_color_conversion: ColorCorrection,
+    // Draw whole lines at mode-3 end instead of per dot (see `scanline`). A
+    // host preference, re-applied after every restore.
+    #[serde(skip)]
+    pub(in crate::ppu) scanline_renderer: bool,
 }
 
 impl Default for Ppu {
@@ -2385,6 +2456,7 @@ impl Ppu {
             lcdc: LcdcState::default(),
             wg: BusGlitch::default(),
             cgb_color_conversion: ColorCorrection::Lcd,
+            scanline_renderer: false,
         }
     }
 
@@ -2400,6 +2472,14 @@ impl Ppu {
         self.cgb_color_conversion
     }
 
+    pub fn set_scanline_renderer(&mut self, enabled: bool) {
+        self.scanline_renderer = enabled;
+    }
+
+    pub fn scanline_renderer(&self) -> bool {
+        self.scanline_renderer
+    }
+
     pub(crate) fn sync_lcdc_from_mmio(&mut self, mmio: &mmio::Mmio) {
         self.set_lcdc_visible(mmio.read(LCD_CONTROL), mmio.is_cgb_features_enabled(), mmio.is_double_speed_mode());
         self.lcdc.pending_lcdc_events.clear();
@@ -2505,6 +2585,18 @@ impl Ppu {
         self.clk.sched_oneshot_statirq = stat_irq::DISABLED_TIME;
     }
 
+    /// Byte offset of `objs.sprites_on_line` in this PPU's serialization, where
+    /// a pre-version-5 payload carried it; lets tests hand-build such payloads.
+    #[cfg(test)]
+    pub(crate) fn sprite_list_offset(&self) -> usize {
+        let size = |r: Result<u64, bincode::Error>| r.unwrap() as usize;
+        size(bincode::serialized_size(&self.fetcher))
+            + size(bincode::serialized_size(&self.disabled))
+            + size(bincode::serialized_size(&self.state))
+            + size(bincode::serialized_size(&self.ticks))
+            + size(bincode::serialized_size(&self.x))
+    }
+
     /// True while the renderer is in pixel transfer (mode 3) — consumer: the
     /// bus's sticky mid-m3 LCDC-writer marker (CGB halt-exit stall scoping).
     pub(crate) fn in_pixel_transfer(&self) -> bool {
@@ -2583,7 +2675,7 @@ impl Ppu {
         // Mode-3 preamble fast path: while the budget holds (see
         // `fast_dots_left`), every piece gated on `!fast` below is a proven
         // no-op for this dot.
-        let fast = if matches!(self.state, State::PixelTransfer) {
+        let fast = if matches!(self.state, State::PixelTransfer) && !self.scanline_renderer {
             if self.clk.fast_hold > 0 {
                 self.clk.fast_hold -= 1;
                 self.clk.fast_dots_left = 0;
@@ -2665,6 +2757,7 @@ impl Ppu {
 
         match self.state {
             State::OAMSearch => self.step_mode2(mmio),
+            State::PixelTransfer if self.scanline_renderer => self.step_mode3_scanline(mmio),
             State::PixelTransfer => self.step_mode3_dot(mmio, fast),
             State::HBlank => {
                 if self.step_hblank(mmio) {
@@ -2784,6 +2877,21 @@ mod tests {
         assert!(!ppu.lcdc.cgb_tile_index_is_tile_data);
     }
 
+    // The inline per-line sprite list caps at ten like the scan it records,
+    // and reads back in push order.
+    #[test]
+    fn sprite_list_keeps_its_first_ten() {
+        let sprite = |i: u8| Sprite { y: 16 + i, x: 8 * i, tile_index: i, attributes: SpriteAttributes::from_byte(0x90), oam_index: i };
+        let mut list = SpriteList::default();
+        for i in 0..12 {
+            list.push(sprite(i));
+        }
+        assert_eq!(list.len(), MAX_SPRITES_PER_LINE);
+        assert_eq!(list.iter().map(|s| s.oam_index).collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        list.clear();
+        assert!(list.is_empty());
+    }
+
     // The DMG "line 154" STAT-write VBlank-IF glitch is a PPU-line phenomenon:
     // line 154 only exists while the LCD is actively scanning, so the glitch
     // cannot occur with the LCD disabled. When a game turns the LCD off while the
@@ -2830,7 +2938,7 @@ mod tests {
             // Park the master clock well past the window so the anchor never
             // underflows, then anchor the last drive `diff` cc in the past.
             let now = 4 * CGB_WINDOW;
-            mmio.bulk_advance_idle(now);
+            mmio.advance_world_to(now);
 
             let mut ppu = Ppu::new();
             ppu.out.color_fb_b.fill(0x12); // a distinctive, non-white retained image
@@ -1,7 +1,7 @@
 use crate::memory::{boxed_filled, mmio, Addressable};
 use crate::ppu::fetcher;
 use super::controller::{
-    rgb555_to_rgb888, FetchDebugEvent, FetchDebugEventKind, LCDCFlags, PixelDebugEvent, Ppu,
+    blank_shades, rgb555_to_rgb888, FetchDebugEvent, FetchDebugEventKind, LCDCFlags, PixelDebugEvent, Ppu,
     RenderedFrame, SgbBorderLayers, State, FRAMEBUFFER_SIZE, LY, SGB_FRAME_HEIGHT, SGB_FRAME_SIZE,
     SGB_FRAME_WIDTH, SGB_WINDOW_X, SGB_WINDOW_Y,
 };
@@ -155,7 +155,11 @@ impl Ppu {
     pub(crate) fn presented_dmg_shades(&self, mmio: &mmio::Mmio) -> Box<[u8; FRAMEBUFFER_SIZE]> {
         if let Some(sgb) = mmio.sgb() {
             return match self.sgb_frame(sgb) {
-                RenderedFrame::Monochrome(m) => m,
+                RenderedFrame::Monochrome(m) => {
+                    let mut shades: Box<[u8; FRAMEBUFFER_SIZE]> = boxed_filled(0);
+                    shades.copy_from_slice(m);
+                    shades
+                }
                 RenderedFrame::Color(_) => self.out.fb_b.clone(),
             };
         }
@@ -212,7 +216,7 @@ impl Ppu {
             && mmio.master_cc().wrapping_sub(self.out.last_drive_cc) <= window
     }
 
-    pub(crate) fn get_frame(&mut self, mmio: &mmio::Mmio) -> RenderedFrame {
+    pub(crate) fn get_frame(&mut self, mmio: &mmio::Mmio) -> RenderedFrame<'_> {
         self.out.have_frame = false;
         // Hardware panel blank: the LCD off state and the first frame after an
         // enable both show "whiter than white" (blank), not the framebuffer. The
@@ -254,9 +258,9 @@ impl Ppu {
         } else {
             if blank_panel {
                 // DMG white == shade index 0.
-                return RenderedFrame::Monochrome(boxed_filled(0));
+                return RenderedFrame::Monochrome(blank_shades(0));
             }
-            RenderedFrame::Monochrome(self.out.fb_b.clone())
+            RenderedFrame::Monochrome(&self.out.fb_b)
         }
     }
 
@@ -266,26 +270,25 @@ impl Ppu {
     /// attribute cell (producing RGB888). When no palette command has run the
     /// frame stays monochrome, matching plain-GB (grayscale) behavior — which is
     /// what the `sgb-ext-test` grayscale reference expects.
-    fn sgb_frame(&self, sgb: &crate::sgb::Sgb) -> RenderedFrame {
+    fn sgb_frame(&self, sgb: &crate::sgb::Sgb) -> RenderedFrame<'_> {
         use crate::sgb::MaskMode;
         // MASK_EN: Freeze shows the latched pre-freeze frame; Black shows pure
         // black (the SNES blanks to color 0x0000); Color0 blanks to the shared
         // backdrop color (color 0).
         let blank = matches!(sgb.mask, MaskMode::Black | MaskMode::Color0);
-        let src: &[u8] = match self.out.sgb_freeze_fb.as_deref() {
-            Some(f) if f.len() == FRAMEBUFFER_SIZE => f,
-            _ => &self.out.fb_b[..],
-        };
+        let src: &[u8; FRAMEBUFFER_SIZE] =
+            match self.out.sgb_freeze_fb.as_deref().and_then(|f| f.try_into().ok()) {
+                Some(f) => f,
+                None => &self.out.fb_b,
+            };
 
         if !sgb.colorized {
             if blank {
                 // Blank to shade 0 (Color0) / darkest for Black.
                 let fill = if matches!(sgb.mask, MaskMode::Black) { 3 } else { 0 };
-                return RenderedFrame::Monochrome(boxed_filled(fill));
+                return RenderedFrame::Monochrome(blank_shades(fill));
             }
-            let mut out: Box<[u8; FRAMEBUFFER_SIZE]> = boxed_filled(0);
-            out.copy_from_slice(src);
-            return RenderedFrame::Monochrome(out);
+            return RenderedFrame::Monochrome(src);
         }
 
         // Colorized: build an RGB888 frame from the SGB palettes.
@@ -467,8 +470,8 @@ impl Ppu {
     }
 
     /// Whether the PPU has processed its LCD-off transition. False means the PPU
-    /// still holds its running state (used to force the disable dot before an
-    /// idle bulk-skip so the transition is never jumped over).
+    /// still holds its running state (the bus scheduler keeps stepping it until
+    /// the transition lands, so it is never jumped over).
     pub(crate) fn is_lcd_disabled(&self) -> bool { self.disabled }
 
     /// DMG OAM-bug support: the OAM row (0..19) the PPU is scanning when a CPU
@@ -321,6 +321,11 @@ impl Ppu {
         }
     }
 
+    /// Whether a delayed LCDC commit is still counting down.
+    pub(crate) fn has_pending_lcdc_events(&self) -> bool {
+        !self.lcdc.pending_lcdc_events.is_empty()
+    }
+
     /// Per-dot LCDC delayed-commit pump. The queue is empty except for a few
     /// dots after a CPU FF40 write, so the hot path is the empty check alone;
     /// the drain loop lives out of line.
@@ -11,6 +11,7 @@ mod mode3;
 mod modes;
 mod reads;
 mod reg_writes;
+mod scanline;
 mod stat_engine;
 mod window_glitch;
 mod stat_irq;
@@ -383,15 +383,7 @@ impl Ppu {
                 self.build_sprites_from_snapshot(mmio);
             }
             // Sort sprites by priority after OAM search is complete
-            if is_cgb {
-                // CGB mode: Sort by OAM index only (already in order, but ensure it)
-                self.objs.sprites_on_line.sort_by_key(|sprite| sprite.oam_index);
-            } else {
-                // DMG mode: Sort by X coordinate first, then OAM index
-                self.objs.sprites_on_line.sort_by(|a, b| {
-                    a.x.cmp(&b.x).then(a.oam_index.cmp(&b.oam_index))
-                });
-            }
+            self.sort_sprites_for_fetch(is_cgb);
 
             self.x = 0;
             self.fetcher.reset();
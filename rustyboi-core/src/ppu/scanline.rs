@@ -0,0 +1,144 @@
+//! The opt-in scanline renderer: draws a whole line in one pass when mode 3
+//! ends instead of running the per-dot fetcher/FIFO pipeline. Meant for weak
+//! hosts (and wasm) where the dot renderer can't hold full speed.
+//!
+//! Only the picture takes the shortcut. Mode 2 still scans OAM per slot, and
+//! mode 3 still ends at the closed-form mode-0 time, so FF41, the STAT IRQ
+//! edges, HDMA and the LY/LYC machinery run exactly as under the dot
+//! renderer. What is lost is every mid-line effect: SCX/WX/palette/LCDC
+//! writes during mode 3 land on the whole line (the values as of mode-3 end),
+//! and the window glitches and fetcher-bus quirks are not modelled. On lines
+//! with no closed-form mode-0 time (the first line after LCD enable) mode 3
+//! lasts the nominal length from `compute_m3_length`.
+
+use crate::memory::mmio;
+use super::controller::{wy2_disabled, LCDCFlags, Ppu, State, LY, SCX, SCY, WX};
+
+/// One BG/window tile row, decoded: per pixel the colour index (bits 0-1).
+type TileRow = [u8; 8];
+
+impl Ppu {
+    /// Mode 3 under the scanline renderer: wait out the line's mode-3 length,
+    /// then draw it and enter HBlank.
+    pub(in crate::ppu) fn step_mode3_scanline(&mut self, mmio: &mut mmio::Mmio) {
+        let done = match self.m0.m0_time_master {
+            Some(m0t) => mmio.master_cc() >= m0t,
+            None => {
+                let is_cgb = mmio.is_cgb_features_enabled();
+                self.ticks >= self.m3.m3_arm_dot + self.compute_m3_length(mmio, is_cgb)
+            }
+        };
+        if !done {
+            return;
+        }
+        self.render_scanline(mmio);
+        self.m0.scheduled_mode0_dot = None;
+        if !self.m0.mode0_reported_this_line {
+            self.m0.mode0_reported_this_line = true;
+            Self::set_lcd_status_mode(mmio, 0);
+        }
+        self.clk.fast_dots_left = 0;
+        self.x = 160;
+        self.state = State::HBlank;
+    }
+
+    /// Exclusive end tick of this line's mode-3 interior: the dots before the
+    /// mode-0 time where `step_mode3_scanline` only compares the clock, which
+    /// the bus scheduler may jump (see `inert_render_dots`). Stops 8 cc short
+    /// of mode 0, the lead the HDMA tracker and the STAT dispatch use, and on
+    /// CGB never runs past the tracker's wake. None when no dot qualifies.
+    pub(in crate::ppu) fn scanline_mode3_interior_end(&self, mmio: &mmio::Mmio) -> Option<u32> {
+        let m0t = self.m0.m0_time_master?;
+        if self.clk.first_line_after_enable
+            || self.objs.objsize_apply_cc != wy2_disabled()
+            || !self.objs.oam_reader_seeded
+            || self.objs.prev_dma_writing
+            || mmio.oam_snoop_event_possible()
+        {
+            return None;
+        }
+        let mut limit = m0t.saturating_sub(8);
+        if mmio.is_cgb_features_enabled() {
+            limit = limit.min(mmio.hdma_tracker_sleep_until());
+        }
+        let ds = mmio.is_double_speed_mode() as u32;
+        let dots = limit.saturating_sub(mmio.master_cc() + 1) >> ds;
+        (dots > 0).then(|| self.ticks as u32 + dots as u32)
+    }
+
+    /// Draw line LY into the back framebuffer from the registers as they stand
+    /// now, through the same BG/OBJ mixers the dot renderer plots with.
+    fn render_scanline(&mut self, mmio: &mmio::Mmio) {
+        let ly = mmio.ppu_io_reg(LY);
+        if ly >= 144 {
+            return;
+        }
+        profile_span!("ppu_scanline");
+        let lcdc = self.lcdc.reg;
+        let cgb = mmio.is_cgb_features_enabled();
+        let compat = self.is_cgb_compat_dmg(mmio);
+        let bg_enabled = self.lcdc_has(LCDCFlags::BGDisplay);
+        let scx = mmio.ppu_io_reg(SCX);
+        let scy = mmio.ppu_io_reg(SCY);
+        let wx = mmio.ppu_io_reg(WX);
+
+        let window = self.win.window_y_triggered && self.lcdc_has(LCDCFlags::WindowDisplayEnable) && wx <= 166;
+        if window {
+            self.win.win_y_pos = self.win.win_y_pos.wrapping_add(1);
+        }
+        let win_y = self.win.win_y_pos;
+
+        // Consecutive columns mostly share a tile row; decode each once.
+        let mut cached: Option<((u16, u8), TileRow, u8)> = None;
+        for x in 0..160u8 {
+            let in_window = window && x as u16 + 7 >= wx as u16;
+            let (map_base, map_x, map_y) = if in_window {
+                let base = if lcdc & 0x40 != 0 { 0x9C00 } else { 0x9800 };
+                (base, (x as u16 + 7 - wx as u16) as u8, win_y)
+            } else {
+                let base = if lcdc & 0x08 != 0 { 0x9C00 } else { 0x9800 };
+                (base, x.wrapping_add(scx), ly.wrapping_add(scy))
+            };
+            let map_addr = map_base + (map_y as u16 / 8) * 32 + map_x as u16 / 8;
+            let key = (map_addr, map_y & 7);
+            let (row, attrs) = match cached {
+                Some((k, row, attrs)) if k == key => (row, attrs),
+                _ => {
+                    let (row, attrs) = self.decode_tile_row(mmio, map_addr, map_y & 7, lcdc, cgb);
+                    cached = Some((key, row, attrs));
+                    (row, attrs)
+                }
+            };
+            let idx = row[(map_x & 7) as usize];
+            let fb = ly as usize * 160 + x as usize;
+            if cgb || compat {
+                let rgb = if cgb {
+                    self.mix_background_and_sprites_color(mmio, idx, attrs, x, ly, bg_enabled)
+                } else {
+                    self.mix_background_and_sprites_compat(mmio, idx, x, ly, bg_enabled)
+                };
+                self.out.color_fb_a[fb * 3..fb * 3 + 3].copy_from_slice(&[rgb.0, rgb.1, rgb.2]);
+            } else {
+                self.out.fb_a[fb] = self.mix_background_and_sprites(mmio, idx, x, ly, bg_enabled);
+            }
+        }
+    }
+
+    /// The colour indices of one tile row (already X-flipped) and, on CGB,
+    /// the map entry's attribute byte.
+    fn decode_tile_row(&self, mmio: &mmio::Mmio, map_addr: u16, line: u8, lcdc: u8, cgb: bool) -> (TileRow, u8) {
+        let tile = mmio.read_vram_bank(0, map_addr);
+        let attrs = if cgb { mmio.read_vram_bank(1, map_addr) } else { 0 };
+        let line = if attrs & 0x40 != 0 { 7 - line } else { line };
+        let bank = (attrs >> 3) & 1;
+        let addr = self.fetcher.get_tile_data_address(tile, line, lcdc);
+        let lo = mmio.read_vram_bank(bank, addr);
+        let hi = mmio.read_vram_bank(bank, addr + 1);
+        let mut row = [0u8; 8];
+        for (i, px) in row.iter_mut().enumerate() {
+            let bit = if attrs & 0x20 != 0 { i } else { 7 - i };
+            *px = ((hi >> bit) & 1) << 1 | ((lo >> bit) & 1);
+        }
+        (row, attrs)
+    }
+}
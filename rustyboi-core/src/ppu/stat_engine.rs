@@ -435,17 +435,20 @@ impl Ppu {
         }
         bound.saturating_sub(8)
     }
-    /// One-compare pre-gate for `skip_inert_dots`: only mode 0/1/2 interiors
-    /// can be inert, so mode-3 dots skip the full-call attempt entirely.
+    /// One-compare pre-gate for `inert_render_dots`: only mode 0/1/2
+    /// interiors (and mode 3 under the scanline renderer) can be inert, so
+    /// dot-renderer mode-3 dots skip the full-call attempt entirely.
     #[inline]
     pub(crate) fn maybe_inert_state(&self) -> bool {
-        matches!(self.state, State::HBlank | State::VBlank | State::OAMSearch)
+        self.scanline_renderer || matches!(self.state, State::HBlank | State::VBlank | State::OAMSearch)
     }
-    /// Fast-forward through inert HBlank/VBlank interior dots, where the whole
-    /// per-dot `step` body is provably bookkeeping: `ticks`/`line_cycle`
+    /// How many render dots from here are inert: HBlank/VBlank interior dots
+    /// (and, under the scanline renderer, mode-3 interior dots) where the
+    /// whole per-dot `step` body is provably bookkeeping: `ticks`/`line_cycle`
     /// advance, the LYC compare rewrites an unchanged flag, the palette latch
     /// re-reads unchanged registers, and the state arm does real work only at
-    /// the line edges. Returns RENDER dots consumed (0 = not skippable now).
+    /// the line edges. This is the PPU's entry in the bus scheduler: the dot
+    /// after the last inert one is its next event. 0 = the next dot is real.
     ///
     /// Soundness constraints (each maps to per-dot work that would otherwise
     /// run):
@@ -453,29 +456,30 @@ impl Ppu {
     ///   actions live at ticks 455 (line advance / frame swap) and ticks 6 of
     ///   line 153; the FF41 mode-2 anticipation at 453 and the window-Y latch
     ///   checkpoints (1/450/454) and LYC next-line anticipation (454+) are
-    ///   outside the interior.
+    ///   outside the interior. Mode 2 and scanline mode 3 have their own
+    ///   interiors (below and `scanline_mode3_interior_end`).
     /// - internal LY in [2, 152]: excludes the line-153 LY-0 transient and
     ///   the l154 glitch-window disarm checks on lines 0/1.
     /// - no scheduled dispatch event can come due inside the span
     ///   (`sched_min` bound with the same margin the dispatch bail uses), so
     ///   skipping the per-dot dispatch calls skips only no-ops.
     /// - LYC/STAT/palette registers cannot change inside the span (no CPU
-    ///   access boundary inside a quiet span) and `bgp_defer_countdown == 0`,
-    ///   so the per-dot rewrites are idempotent; the final state equals the
-    ///   per-dot outcome.
-    /// - the caller (the quiet-span loop) already excludes OAM-DMA, serial,
-    ///   the JOYP filter and the HDMA lockstep window, and guarantees no
-    ///   pending deferred HDMA writes; within an HBlank interior the HDMA
-    ///   period tracker sees no edge and no LY change, so skipped
-    ///   `step_hdma` calls are state-identical no-ops (a block fired at the
-    ///   mode-0 edge before the interior began).
+    ///   access boundary inside a scheduler jump) and `bgp_defer_countdown ==
+    ///   0`, so the per-dot rewrites are idempotent; the final state equals
+    ///   the per-dot outcome.
+    /// - the caller (the bus scheduler) only asks while OAM-DMA and the HDMA
+    ///   lockstep window are idle and no deferred HDMA writes are pending,
+    ///   and never jumps past a serial, JOYP-filter or timer event; within an
+    ///   HBlank interior the HDMA period tracker sees no edge and no LY
+    ///   change, so skipped `step_hdma` calls are state-identical no-ops (a
+    ///   block fired at the mode-0 edge before the interior began).
     /// - `abs_cc` is advanced with the skip: the CPU register-write hooks
     ///   (`write_cc`) and the exact-cc override compares read it at the very
     ///   next access boundary, before any real step would re-derive it.
-    pub(crate) fn skip_inert_dots(&mut self, mmio: &mut mmio::Mmio, max_render_dots: u32) -> u32 {
+    pub(crate) fn inert_render_dots(&self, mmio: &mmio::Mmio) -> u32 {
         const INTERIOR_START: u32 = 8;
         const INTERIOR_END: u32 = 448;
-        if self.disabled || self.plot.bgp_defer_countdown > 0 || max_render_dots == 0 {
+        if self.disabled || self.plot.bgp_defer_countdown > 0 {
             return 0;
         }
         // A pending delayed LCDC commit must land at its exact dot.
@@ -487,14 +491,14 @@ impl Ppu {
             State::VBlank => {}
             State::OAMSearch => {
                 // Mode-2 interior: the per-dot body is the every-2nd-dot OAM
-                // scan slot (batched below with identical per-slot work — the
-                // pushes ARE observable at a mid-mode-2 DMA-start boundary,
-                // so they must run) plus the same idempotent preamble. The
-                // tick-0/1 init and ly0 window checkpoint sit below the
-                // interior start; the pixel-transfer arm dot (80/82) and its
-                // snapshot rebuild sit past its end. A pending exact-cc
-                // OBJ-size override needs its per-dot/per-slot abs_cc
-                // resolution, so no batching then.
+                // scan slot (batched by `skip_inert_dots` with identical
+                // per-slot work — the pushes ARE observable at a mid-mode-2
+                // DMA-start boundary, so they must run) plus the same
+                // idempotent preamble. The tick-0/1 init and ly0 window
+                // checkpoint sit below the interior start; the pixel-transfer
+                // arm dot (80/82) and its snapshot rebuild sit past its end. A
+                // pending exact-cc OBJ-size override needs its per-dot/per-slot
+                // abs_cc resolution, so no batching then.
                 if self.clk.first_line_after_enable || self.objs.objsize_apply_cc != wy2_disabled() {
                     return 0;
                 }
@@ -514,6 +518,12 @@ impl Ppu {
                 };
                 interior = (4, arm - 2);
             }
+            State::PixelTransfer if self.scanline_renderer => {
+                let Some(end) = self.scanline_mode3_interior_end(mmio) else {
+                    return 0;
+                };
+                interior = (0, end);
+            }
             State::HBlank => {
                 // With CGB HBlank DMA armed, a block can fire a dot or two
                 // INTO HBlank via the per-dot STAT-mode-edge fallback (window
@@ -549,13 +559,15 @@ impl Ppu {
         let abs_now = mmio.master_cc().wrapping_sub(self.clk.p_now);
         let event_slack = self.clk.sched_min.saturating_sub(abs_now.saturating_add(8));
         let to_event = event_slack >> ds;
-        let n = ((interior.1 - t) as u64)
-            .min(to_event)
-            .min(max_render_dots as u64);
-        if n == 0 {
-            return 0;
-        }
-        let n = n as u32;
+        ((interior.1 - t) as u64).min(to_event) as u32
+    }
+
+    /// Advance `n` inert render dots in one step (`n` at most what
+    /// `inert_render_dots` just reported), leaving the PPU exactly where `n`
+    /// per-dot steps would have.
+    pub(crate) fn skip_inert_dots(&mut self, mmio: &mut mmio::Mmio, n: u32) {
+        let ds = mmio.is_double_speed_mode() as u32;
+        let t = self.ticks as u32;
         // Mode-2: run the scan slots the skipped dots would have run, with
         // the identical per-slot sequence (slot-size latch from the constant
         // LCDC, visibility check + push, next-slot re-latch). One slot per
@@ -584,7 +596,6 @@ impl Ppu {
         self.plot.bgp_delayed = mmio.ppu_io_reg(BGP);
         self.plot.obp0_delayed = mmio.ppu_io_reg(OBP0);
         self.plot.obp1_delayed = mmio.ppu_io_reg(OBP1);
-        n
     }
     /// Conservative count of MASTER-cc dots until the PPU's frame wrap (the
     /// ly153->0 frame swap), minus an 8-dot safety margin so the caller's
@@ -29,6 +29,7 @@
 //! in the background); no game observes the distinction (both bits are outside
 //! every error mask), so like other emulators we keep bit3 until PRINT.
 
+use crate::serial::SerialDevice;
 use serde::{Deserialize, Serialize};
 
 /// Print head width in pixels; every band is 20 tiles wide.
@@ -97,7 +98,7 @@ enum PacketState {
 }
 
 #[derive(Serialize, Deserialize, Clone, Default)]
-pub(crate) struct GbPrinter {
+pub struct GbPrinter {
     state: PacketState,
     command: u8,
     compression: bool,
@@ -121,6 +122,18 @@ pub(crate) struct GbPrinter {
     completed: Vec<PrintSheet>,
 }
 
+/// The printer can also go in as a plain [`SerialDevice`] (a frontend that
+/// drains prints from its own handle).
+impl SerialDevice for GbPrinter {
+    fn start_exchange(&mut self, _tx: u8) -> u8 {
+        self.preloaded_response()
+    }
+
+    fn on_byte_exchanged(&mut self, tx: u8, _rx: u8, cc: u64) {
+        self.receive_byte(tx, cc);
+    }
+}
+
 impl GbPrinter {
     pub fn new() -> Self {
         Self::default()
@@ -314,7 +327,7 @@ impl GbPrinter {
     }
 
     /// Drain completed prints (oldest first).
-    pub(crate) fn take_completed(&mut self) -> Vec<PrintSheet> {
+    pub fn take_completed(&mut self) -> Vec<PrintSheet> {
         std::mem::take(&mut self.completed)
     }
 
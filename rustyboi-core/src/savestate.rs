@@ -0,0 +1,438 @@
+//! The `.rustyboisave` container: a small header in front of the machine state.
+//!
+//! [`GB::to_state_bytes`] is the raw, unversioned bincode of the machine, which
+//! is fine for in-memory snapshots (rewind, movie anchors) that never outlive
+//! the build that wrote them. Anything written to disk or long-lived storage
+//! (exported states, numbered slots) goes through this container instead, so a
+//! state from another build is recognised up front: older formats are walked
+//! forward through [`UPGRADERS`], newer ones are refused with a message naming
+//! the core that wrote them, and a blob without the magic is rejected rather
+//! than deserialized into whatever struct layout happens to match.
+//!
+//! Layout: `RBST` magic, format version (u16 LE), the writing core's version
+//! (u8 length + UTF-8), the SHA-256 of the ROM it was saved against (all zero
+//! when unknown), a screenshot thumbnail (u32 LE length + RGB888, version 2
+//! on), then the `to_state_bytes` payload. Everything up to the core version
+//! is frozen, so any build can name the build that wrote a state it refuses.
+//! For debugging, File → Export → Save State as JSON and `harness state-json`
+//! render a container's header and machine in the versioned JSON schema of
+//! `rustyboi_session::state_json`, so two states diff as text.
+
+use std::cell::Cell;
+use std::fmt;
+use std::io;
+
+use crate::gb::GB;
+
+const STATE_MAGIC: &[u8; 4] = b"RBST";
+
+/// Current container format version. Bump it whenever the machine's serialized
+/// layout changes, and append the upgrader that rewrites the previous
+/// version's payload to [`UPGRADERS`].
+pub const STATE_VERSION: u16 = 6;
+
+/// Thumbnail size: the 160x144 screen box-filtered down by 4.
+pub const THUMBNAIL_WIDTH: usize = 40;
+pub const THUMBNAIL_HEIGHT: usize = 36;
+const THUMBNAIL_SCALE: usize = 4;
+
+/// Rewrites a payload of version `n` into version `n + 1`.
+type Upgrader = fn(Vec<u8>) -> Result<Vec<u8>, StateError>;
+
+/// `UPGRADERS[n - 1]` upgrades version `n` to `n + 1`, so it always holds
+/// `STATE_VERSION - 1` entries. A layout change that cannot be migrated still
+/// gets an entry: one that returns [`StateError::NoUpgrade`] for its version.
+const UPGRADERS: &[Upgrader] = &[
+    // 1 -> 2 added the header thumbnail; the payload is unchanged.
+    Ok,
+    // 2 -> 3 added the APU output resampler as the machine's last field.
+    append_resampler,
+    // 3 -> 4 added the cycle / instruction counters after it.
+    append_counters,
+    // 4 -> 5 stopped serializing the PPU's per-line sprite list, and 5 -> 6
+    // the output resampler. Neither rewrites the payload: the dropped bytes
+    // are read and discarded as it decodes (see `dropped_in`).
+    Ok,
+    Ok,
+];
+const _: () = assert!(UPGRADERS.len() == STATE_VERSION as usize - 1);
+
+/// Append a fresh (silent) output resampler to a version-2 payload. The
+/// resampler is the last serialized field of [`GB`], so its bytes go at the
+/// very end; the old state then plays from silence for the filter's length
+/// (well under a millisecond) like a machine that just powered on.
+fn append_resampler(mut payload: Vec<u8>) -> Result<Vec<u8>, StateError> {
+    let fresh = bincode::serialize(&crate::audio::Resampler::default())
+        .map_err(|e| StateError::Decode(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    payload.extend_from_slice(&fresh);
+    Ok(payload)
+}
+
+/// Append zeroed cycle / instruction counters to a version-3 payload: an old
+/// state counts machine time from the moment it is loaded.
+fn append_counters(mut payload: Vec<u8>) -> Result<Vec<u8>, StateError> {
+    let fresh = bincode::serialize(&crate::gb::Counters::default())
+        .map_err(|e| StateError::Decode(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    payload.extend_from_slice(&fresh);
+    Ok(payload)
+}
+
+thread_local! {
+    /// Format version of the payload being decoded: [`STATE_VERSION`] except
+    /// while [`decode`] reads an upgraded older one.
+    static DECODING: Cell<u16> = const { Cell::new(STATE_VERSION) };
+}
+
+/// Deserialize a payload that the upgraders have walked forward from
+/// `version`. They only ever append, so a field dropped since `version` is
+/// still in its bytes and is read past (see [`dropped_in`]).
+fn decode(version: u16, payload: &[u8]) -> Result<GB, StateError> {
+    DECODING.set(version);
+    let gb = GB::from_state_bytes(payload);
+    DECODING.set(STATE_VERSION);
+    gb.map_err(StateError::Decode)
+}
+
+/// Read (and discard) a field's old encoding, of type `L`, when the payload
+/// being decoded predates `version`, the format that stopped serializing it.
+/// A current payload carries nothing there, so this reads nothing. Backs the
+/// `deserialize_with` of a `skip_serializing` field; the field itself is
+/// rebuilt after load.
+pub(crate) fn dropped_in<'de, L, D>(version: u16, d: D) -> Result<(), D::Error>
+where
+    L: serde::Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    if DECODING.get() < version {
+        L::deserialize(d)?;
+    }
+    Ok(())
+}
+
+/// The parsed container header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateHeader {
+    pub version: u16,
+    /// `CARGO_PKG_VERSION` of the core that wrote the state.
+    pub core_version: String,
+    /// SHA-256 of the ROM the state was saved against, all zero when unknown.
+    pub rom_sha256: [u8; 32],
+    /// The screen at save time, [`THUMBNAIL_WIDTH`]x[`THUMBNAIL_HEIGHT`]
+    /// RGB888; `None` for a version-1 state.
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+#[derive(Debug)]
+pub enum StateError {
+    /// No `RBST` magic: not a save state, or one from before the header existed.
+    Unversioned,
+    /// The header itself is cut short.
+    Truncated,
+    /// Written by a newer core whose format this build does not know.
+    TooNew { version: u16, core_version: String },
+    /// An older format with no migration path to the current one.
+    NoUpgrade(u16),
+    /// Saved against a different ROM than the one it is being loaded onto.
+    WrongRom,
+    /// The (possibly upgraded) payload failed to deserialize.
+    Decode(io::Error),
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::Unversioned => {
+                write!(f, "not a RustyBoi save state, or one saved before states were versioned")
+            }
+            StateError::Truncated => write!(f, "save state header is truncated"),
+            StateError::TooNew { version, core_version } => write!(
+                f,
+                "save state format {version} (from RustyBoi {core_version}) is newer than this build supports ({STATE_VERSION})"
+            ),
+            StateError::NoUpgrade(version) => {
+                write!(f, "save state format {version} is too old to upgrade to {STATE_VERSION}")
+            }
+            StateError::WrongRom => write!(f, "save state belongs to a different ROM"),
+            StateError::Decode(e) => write!(f, "save state is corrupt: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+/// Serialize `gb` into the container, stamped with `rom_sha256` (all zero
+/// when the caller has no ROM identity).
+pub fn write_state(gb: &mut GB, rom_sha256: [u8; 32]) -> Result<Vec<u8>, io::Error> {
+    let thumbnail = thumbnail(gb.get_current_frame().rgb());
+    let payload = gb.to_state_bytes()?;
+    let core_version = env!("CARGO_PKG_VERSION").as_bytes();
+    let mut out = Vec::with_capacity(43 + core_version.len() + thumbnail.len() + payload.len());
+    out.extend_from_slice(STATE_MAGIC);
+    out.extend_from_slice(&STATE_VERSION.to_le_bytes());
+    out.push(core_version.len() as u8);
+    out.extend_from_slice(core_version);
+    out.extend_from_slice(&rom_sha256);
+    out.extend_from_slice(&(thumbnail.len() as u32).to_le_bytes());
+    out.extend_from_slice(&thumbnail);
+    out.extend_from_slice(&payload);
+    Ok(out)
+}
+
+/// Box-filter a 160x144 RGB888 screen down to the thumbnail size.
+fn thumbnail(rgb: &[u8]) -> Vec<u8> {
+    let width = THUMBNAIL_WIDTH * THUMBNAIL_SCALE;
+    let mut out = Vec::with_capacity(THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT * 3);
+    for ty in 0..THUMBNAIL_HEIGHT {
+        for tx in 0..THUMBNAIL_WIDTH {
+            let mut sum = [0u32; 3];
+            for y in ty * THUMBNAIL_SCALE..(ty + 1) * THUMBNAIL_SCALE {
+                for x in tx * THUMBNAIL_SCALE..(tx + 1) * THUMBNAIL_SCALE {
+                    let px = &rgb[(y * width + x) * 3..][..3];
+                    for (s, &c) in sum.iter_mut().zip(px) {
+                        *s += u32::from(c);
+                    }
+                }
+            }
+            out.extend(sum.map(|s| (s / (THUMBNAIL_SCALE * THUMBNAIL_SCALE) as u32) as u8));
+        }
+    }
+    out
+}
+
+/// Parse the header, returning it with the payload that follows.
+pub fn read_header(bytes: &[u8]) -> Result<(StateHeader, &[u8]), StateError> {
+    if bytes.get(..4) != Some(STATE_MAGIC) {
+        return Err(StateError::Unversioned);
+    }
+    let version = u16::from_le_bytes(bytes.get(4..6).ok_or(StateError::Truncated)?.try_into().unwrap());
+    let len = *bytes.get(6).ok_or(StateError::Truncated)? as usize;
+    let core_version = bytes.get(7..7 + len).ok_or(StateError::Truncated)?;
+    let core_version = String::from_utf8_lossy(core_version).into_owned();
+    if version > STATE_VERSION {
+        return Err(StateError::TooNew { version, core_version });
+    }
+    let mut at = 7 + len;
+    let rom_sha256 = bytes.get(at..at + 32).ok_or(StateError::Truncated)?.try_into().unwrap();
+    at += 32;
+    let mut thumbnail = None;
+    if version >= 2 {
+        let len = u32::from_le_bytes(bytes.get(at..at + 4).ok_or(StateError::Truncated)?.try_into().unwrap());
+        at += 4;
+        let rgb = bytes.get(at..at + len as usize).ok_or(StateError::Truncated)?;
+        at += rgb.len();
+        thumbnail = (rgb.len() == THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT * 3).then(|| rgb.to_vec());
+    }
+    let header = StateHeader { version, core_version, rom_sha256, thumbnail };
+    Ok((header, &bytes[at..]))
+}
+
+/// Decode a container into a machine, upgrading older formats first. When
+/// `rom_sha256` is given and the header carries a ROM hash, the two must
+/// match. The ROM image itself is not in the state (see `GB::reattach_rom`).
+pub fn read_state(bytes: &[u8], rom_sha256: Option<[u8; 32]>) -> Result<GB, StateError> {
+    let (header, payload) = read_header(bytes)?;
+    if let Some(expected) = rom_sha256
+        && header.rom_sha256 != [0; 32]
+        && header.rom_sha256 != expected
+    {
+        return Err(StateError::WrongRom);
+    }
+    let payload = upgrade(&header, payload.to_vec(), UPGRADERS)?;
+    decode(header.version, &payload)
+}
+
+/// Walk a version-`header.version` payload forward to [`STATE_VERSION`].
+fn upgrade(header: &StateHeader, mut payload: Vec<u8>, upgraders: &[Upgrader]) -> Result<Vec<u8>, StateError> {
+    let current = upgraders.len() as u16 + 1;
+    if header.version > current {
+        return Err(StateError::TooNew {
+            version: header.version,
+            core_version: header.core_version.clone(),
+        });
+    }
+    if header.version == 0 {
+        return Err(StateError::NoUpgrade(0));
+    }
+    for step in &upgraders[header.version as usize - 1..] {
+        payload = step(payload)?;
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gb::Hardware;
+
+    #[test]
+    fn round_trips_and_checks_the_rom() {
+        let mut gb = GB::new(Hardware::DMG);
+        let bytes = write_state(&mut gb, [7; 32]).unwrap();
+        let (header, payload) = read_header(&bytes).unwrap();
+        assert_eq!(header.version, STATE_VERSION);
+        assert_eq!(header.core_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(header.rom_sha256, [7; 32]);
+        assert_eq!(header.thumbnail.map(|t| t.len()), Some(THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT * 3));
+        assert_eq!(payload, gb.to_state_bytes().unwrap());
+
+        assert!(read_state(&bytes, Some([7; 32])).is_ok());
+        assert!(read_state(&bytes, None).is_ok());
+        assert!(matches!(read_state(&bytes, Some([8; 32])), Err(StateError::WrongRom)));
+    }
+
+    #[test]
+    fn rejects_headerless_truncated_and_newer_states() {
+        let mut gb = GB::new(Hardware::DMG);
+        let raw = gb.to_state_bytes().unwrap();
+        assert!(matches!(read_state(&raw, None), Err(StateError::Unversioned)));
+
+        let mut bytes = write_state(&mut gb, [0; 32]).unwrap();
+        assert!(matches!(read_state(&bytes[..12], None), Err(StateError::Truncated)));
+
+        bytes[4..6].copy_from_slice(&(STATE_VERSION + 1).to_le_bytes());
+        let err = read_state(&bytes, None).err().expect("a newer format is refused");
+        assert!(matches!(err, StateError::TooNew { .. }));
+        assert!(err.to_string().contains(env!("CARGO_PKG_VERSION")));
+    }
+
+    /// A machine's payload as a version-5 core wrote it: a (fresh) output
+    /// resampler between the PPU side of the machine and the trailing counters.
+    fn version_five_payload(gb: &mut GB) -> Vec<u8> {
+        let mut payload = gb.to_state_bytes().unwrap();
+        let counters = bincode::serialize(&crate::gb::Counters::default()).unwrap();
+        let at = payload.len() - counters.len();
+        payload.splice(at..at, bincode::serialize(&crate::audio::Resampler::default()).unwrap());
+        payload
+    }
+
+    /// Version 4: the (empty, on a fresh machine) sprite list still in the PPU
+    /// as a `Vec`.
+    fn version_four_payload(gb: &mut GB) -> Vec<u8> {
+        let mut payload = version_five_payload(gb);
+        let at = gb.sprite_list_offset();
+        payload.splice(at..at, bincode::serialize(&Vec::<u8>::new()).unwrap());
+        payload
+    }
+
+    /// Version 3: everything but the trailing counters.
+    fn version_three_payload(gb: &mut GB) -> Vec<u8> {
+        let mut payload = version_four_payload(gb);
+        let counters = bincode::serialize(&crate::gb::Counters::default()).unwrap();
+        payload.truncate(payload.len() - counters.len());
+        payload
+    }
+
+    /// Versions 1/2: nor the resampler before them.
+    fn version_two_payload(gb: &mut GB) -> Vec<u8> {
+        let mut payload = version_three_payload(gb);
+        let resampler = bincode::serialize(&crate::audio::Resampler::default()).unwrap();
+        payload.truncate(payload.len() - resampler.len());
+        payload
+    }
+
+    /// Wrap `payload` in a container of format `version` (2 on, so with an
+    /// empty thumbnail).
+    fn container(version: u16, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = STATE_MAGIC.to_vec();
+        bytes.extend_from_slice(&version.to_le_bytes());
+        bytes.push(5);
+        bytes.extend_from_slice(b"0.0.1");
+        bytes.extend_from_slice(&[0; 32]);
+        if version >= 2 {
+            bytes.extend_from_slice(&0u32.to_le_bytes());
+        }
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    /// Load a version-`version` container and check it decodes to `gb`.
+    fn assert_upgrades(version: u16, payload: &[u8], gb: &mut GB) {
+        let mut loaded = read_state(&container(version, payload), None).unwrap();
+        assert_eq!(loaded.to_state_bytes().unwrap(), gb.to_state_bytes().unwrap());
+    }
+
+    #[test]
+    fn version_one_states_load_without_a_thumbnail() {
+        let mut gb = GB::new(Hardware::DMG);
+        let mut bytes = STATE_MAGIC.to_vec();
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.push(5);
+        bytes.extend_from_slice(b"0.0.1");
+        bytes.extend_from_slice(&[7; 32]);
+        bytes.extend_from_slice(&version_two_payload(&mut gb));
+
+        let (header, _) = read_header(&bytes).unwrap();
+        assert_eq!(header.version, 1);
+        assert_eq!(header.thumbnail, None);
+        assert!(read_state(&bytes, Some([7; 32])).is_ok());
+    }
+
+    #[test]
+    fn version_two_states_gain_a_fresh_resampler() {
+        let mut gb = GB::new(Hardware::DMG);
+        let v2 = version_two_payload(&mut gb);
+        assert_upgrades(2, &v2, &mut gb);
+    }
+
+    #[test]
+    fn version_three_states_gain_zeroed_counters() {
+        let mut gb = GB::new(Hardware::DMG);
+        let v3 = version_three_payload(&mut gb);
+        assert_upgrades(3, &v3, &mut gb);
+    }
+
+    #[test]
+    fn version_four_states_drop_the_sprite_list() {
+        let mut gb = GB::new(Hardware::DMG);
+        let v4 = version_four_payload(&mut gb);
+        assert_upgrades(4, &v4, &mut gb);
+    }
+
+    #[test]
+    fn version_five_states_drop_the_resampler() {
+        let mut gb = GB::new(Hardware::DMG);
+        let v5 = version_five_payload(&mut gb);
+        assert_upgrades(5, &v5, &mut gb);
+    }
+
+    #[test]
+    fn thumbnail_box_filters_the_screen() {
+        let mut rgb = vec![0u8; 160 * 144 * 3];
+        // Light the top-left pixel of the first 4x4 block only.
+        rgb[..3].copy_from_slice(&[160, 80, 16]);
+        let thumb = thumbnail(&rgb);
+        assert_eq!(thumb.len(), THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT * 3);
+        assert_eq!(&thumb[..3], &[10, 5, 1]);
+        assert!(thumb[3..].iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn older_payloads_walk_the_upgrader_chain() {
+        fn one_to_two(mut p: Vec<u8>) -> Result<Vec<u8>, StateError> {
+            p.push(2);
+            Ok(p)
+        }
+        fn two_to_three(mut p: Vec<u8>) -> Result<Vec<u8>, StateError> {
+            p.push(3);
+            Ok(p)
+        }
+        fn dead_end(_: Vec<u8>) -> Result<Vec<u8>, StateError> {
+            Err(StateError::NoUpgrade(2))
+        }
+        let header = |version| StateHeader {
+            version,
+            core_version: "0.0.1".into(),
+            rom_sha256: [0; 32],
+            thumbnail: None,
+        };
+
+        let chain: &[Upgrader] = &[one_to_two, two_to_three];
+        assert_eq!(upgrade(&header(1), vec![1], chain).unwrap(), [1, 2, 3]);
+        assert_eq!(upgrade(&header(2), vec![1], chain).unwrap(), [1, 3]);
+        assert_eq!(upgrade(&header(3), vec![1], chain).unwrap(), [1]);
+        assert!(matches!(upgrade(&header(4), vec![1], chain), Err(StateError::TooNew { version: 4, .. })));
+
+        let blocked: &[Upgrader] = &[one_to_two, dead_end];
+        assert!(matches!(upgrade(&header(1), vec![1], blocked), Err(StateError::NoUpgrade(2))));
+    }
+}
@@ -1,6 +1,4 @@
-use crate::cpu;
 use crate::memory::Addressable;
-use crate::memory::mmio;
 use crate::printer;
 
 use serde::{Deserialize, Serialize};
@@ -60,6 +58,9 @@ struct LinkSideState {
     /// arrival order (the peer master's shifted-out bytes).
     deposits: [u8; DEPOSIT_FIFO],
     deposit_len: u8,
+    /// A [`RemoteLink`] stands in for this side: nothing local disarms it when
+    /// it takes a deposit, so the depositing window does.
+    remote: bool,
 }
 
 impl Default for LinkSideState {
@@ -70,6 +71,7 @@ impl Default for LinkSideState {
             armed_internal: false,
             deposits: [0; DEPOSIT_FIFO],
             deposit_len: 0,
+            remote: false,
         }
     }
 }
@@ -125,7 +127,7 @@ impl LinkCable {
 }
 
 /// One end of a link cable, owned by a GB's serial unit as
-/// `SerialDevice::Link`. Savestates and clones sever the cable (the handle is
+/// `PortDevice::Link`. Savestates and clones sever the cable (the handle is
 /// not serializable and a cloned instance must not ghost-drive its twin's
 /// cable): a severed end behaves like a cable with no partner plugged in.
 #[derive(Serialize, Deserialize)]
@@ -153,6 +155,13 @@ impl LinkPeer {
         (self.side & 1) as usize ^ 1
     }
 
+    /// Whether the other end is still held by someone. A severed end (from a
+    /// clone or savestate) owns its cable alone, as does an end whose partner
+    /// instance was dropped.
+    fn connected(&self) -> bool {
+        Arc::strong_count(&self.cable) > 1
+    }
+
     // POISON SURVIVOR — the `unwrap()`s below are deliberate; do NOT convert
     // them to the `into_inner()` recovery used for plain data (see
     // `crate::ir::IrLink`). A side's `live_sb` / `armed` / `armed_internal` /
@@ -227,6 +236,9 @@ impl LinkPeer {
         let peer = &mut cable.sides[self.peer()];
         if peer.armed && !peer.armed_internal {
             peer.push_deposit(tx);
+            // A remote slave completes on this byte and disarms out of our
+            // sight; don't let our next window latch the byte it already sent.
+            peer.armed = !peer.remote;
         }
     }
 
@@ -246,6 +258,214 @@ impl LinkPeer {
     }
 }
 
+/// What one side of a [`RemoteLink`] tells the other: its shift register and
+/// SC arming, or a byte its internal-clock window shifted out to an armed
+/// external-clock partner. Carrying these is all a transport has to do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkMessage {
+    /// The sender's live SB and SC.7 / SC.0 as of its last change.
+    State { sb: u8, armed: bool, internal: bool },
+    /// A completed master window's outgoing byte, for the receiver's
+    /// external-clock side.
+    Deposit(u8),
+}
+
+/// The far end of a link cable whose partner Game Boy runs somewhere else (a
+/// second process or machine). Plug it in with
+/// [`crate::gb::GB::plug_remote_link`], then each pump hand
+/// [`outgoing`](Self::outgoing) to the transport and feed what arrives to
+/// [`deliver`](Self::deliver).
+///
+/// Latency hiding: the remote side's last reported state stands in for it
+/// locally, so a master starting a transfer against a slave that armed ahead
+/// of time latches its byte at once instead of waiting a round trip. Once our
+/// master's window completes, the remote side counts as disarmed until it says
+/// otherwise, so the next transfer never reuses a stale byte. A remote that
+/// never answers degrades through the usual [`LINK_STALL_TIMEOUT_CC`] hold.
+pub struct RemoteLink {
+    peer: LinkPeer,
+    /// The remote side as last reported (re-seeded whenever we re-plug).
+    remote: LinkSideState,
+    /// Our side as last sent, so only changes go out.
+    sent: Option<(u8, bool, bool)>,
+}
+
+impl Default for RemoteLink {
+    fn default() -> Self {
+        RemoteLink {
+            peer: LinkPeer { cable: Arc::default(), side: 1 },
+            remote: LinkSideState::default(),
+            sent: None,
+        }
+    }
+}
+
+impl RemoteLink {
+    /// A fresh cable for `gb`'s end; ours holds the remote's side of it.
+    pub(crate) fn plug(&mut self) -> LinkPeer {
+        let (local, far) = LinkCable::pair();
+        {
+            let mut cable = far.cable.lock().unwrap();
+            let side = &mut cable.sides[far.me()];
+            side.live_sb = self.remote.live_sb;
+            side.armed = self.remote.armed;
+            side.armed_internal = self.remote.armed_internal;
+            side.remote = true;
+        }
+        self.peer = far;
+        self.sent = None;
+        local
+    }
+
+    /// Whether the Game Boy end is still plugged in (a savestate load or
+    /// restart swaps in a machine with a severed end; plug it in again).
+    pub fn connected(&self) -> bool {
+        self.peer.connected()
+    }
+
+    /// What the remote side needs to hear since the last call: our side's
+    /// state when it changed, then any bytes our master shifted out to it.
+    pub fn outgoing(&mut self) -> Vec<LinkMessage> {
+        let mut out = Vec::new();
+        let mut cable = self.peer.cable.lock().unwrap();
+        let local = cable.sides[self.peer.peer()];
+        let state = (local.live_sb, local.armed, local.armed_internal);
+        if self.sent != Some(state) {
+            self.sent = Some(state);
+            out.push(LinkMessage::State { sb: state.0, armed: state.1, internal: state.2 });
+        }
+        let far = &mut cable.sides[self.peer.me()];
+        while let Some(byte) = far.pop_deposit() {
+            out.push(LinkMessage::Deposit(byte));
+            self.remote.armed = false;
+        }
+        out
+    }
+
+    /// Apply a message from the remote side.
+    pub fn deliver(&mut self, message: LinkMessage) {
+        let mut cable = self.peer.cable.lock().unwrap();
+        match message {
+            LinkMessage::State { sb, armed, internal } => {
+                let far = &mut cable.sides[self.peer.me()];
+                far.live_sb = sb;
+                far.armed = armed;
+                far.armed_internal = internal;
+                self.remote.live_sb = sb;
+                self.remote.armed = armed;
+                self.remote.armed_internal = internal;
+            }
+            LinkMessage::Deposit(byte) => cable.sides[self.peer.peer()].push_deposit(byte),
+        }
+    }
+}
+
+/// Which side drives the shift clock of an exchange with a [`SerialDevice`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SerialClock {
+    /// The Game Boy clocks each byte (SC.0 set) and the device answers from
+    /// [`SerialDevice::start_exchange`], like the printer or Mobile Adapter.
+    #[default]
+    GameBoy,
+    /// The device clocks: while the game holds an external-clock transfer
+    /// armed (SC = 0x80) it hands bytes over through
+    /// [`SerialDevice::clock_in`], like the DMG-07 4-Player Adapter.
+    Device,
+}
+
+/// A peripheral a frontend plugs into the link port with
+/// [`crate::gb::GB::attach_serial_device`]. The bus is a simultaneous
+/// exchange: the device's reply is fixed when a transfer starts, so its
+/// answer to byte N can only depend on bytes before N.
+///
+/// A second Game Boy is not a `SerialDevice`: local partners share a
+/// [`LinkCable`] ([`crate::gb::GB::connect_link`]) and network partners go
+/// through a [`RemoteLink`], which need the cable's armed/idle state that a
+/// plain byte exchange does not carry.
+pub trait SerialDevice: Send {
+    fn clock_source(&self) -> SerialClock {
+        SerialClock::GameBoy
+    }
+
+    /// A Game Boy-clocked transfer is starting with `tx` in SB: the byte the
+    /// device shifts back. Nothing driving the line reads as ones.
+    fn start_exchange(&mut self, _tx: u8) -> u8 {
+        0xFF
+    }
+
+    /// Device-clocked only: the byte to clock into the armed Game Boy now, or
+    /// `None` to keep it waiting.
+    fn clock_in(&mut self) -> Option<u8> {
+        None
+    }
+
+    /// A byte exchange completed at master clock `cc` (deterministic, never
+    /// wall-clock): `tx` was shifted out, `rx` shifted in.
+    fn on_byte_exchanged(&mut self, tx: u8, rx: u8, cc: u64);
+}
+
+/// A frontend's handle on a plugged-in [`SerialDevice`]; it keeps a clone to
+/// read the device's results back.
+pub type SharedSerialDevice = Arc<Mutex<dyn SerialDevice>>;
+
+/// Nothing plugged in, as a device: ones shift in and sent bytes vanish.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct NullDevice;
+
+impl SerialDevice for NullDevice {
+    fn on_byte_exchanged(&mut self, _tx: u8, _rx: u8, _cc: u64) {}
+}
+
+/// A loopback plug (SOUT wired to SIN): every byte the Game Boy sends comes
+/// straight back, the usual link-port self test.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct Loopback;
+
+impl SerialDevice for Loopback {
+    fn start_exchange(&mut self, tx: u8) -> u8 {
+        tx
+    }
+
+    fn on_byte_exchanged(&mut self, _tx: u8, _rx: u8, _cc: u64) {}
+}
+
+/// A frontend device as `PortDevice::Plugged`. Savestates and clones unplug it,
+/// as they sever a link cable: the handle is not serializable, and a clone
+/// (rewind snapshot, run-ahead) must not feed bytes to the live device. The
+/// frontend re-attaches its handle after swapping machines.
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct PluggedDevice {
+    #[serde(skip)]
+    device: Option<SharedSerialDevice>,
+    /// The device's clock source, read once at attach: the serial fast path
+    /// asks every dot and must not take the lock.
+    #[serde(skip)]
+    clock: SerialClock,
+}
+
+impl Clone for PluggedDevice {
+    fn clone(&self) -> Self {
+        PluggedDevice::default()
+    }
+}
+
+impl PluggedDevice {
+    pub(crate) fn new(device: SharedSerialDevice) -> Self {
+        let clock = device.lock().unwrap_or_else(|e| e.into_inner()).clock_source();
+        PluggedDevice { device: Some(device), clock }
+    }
+
+    /// Run `f` on the device, if still plugged. The device is plain data to
+    /// the core, so a poisoned lock is recovered rather than propagated.
+    fn with<R>(&self, f: impl FnOnce(&mut dyn SerialDevice) -> R) -> Option<R> {
+        self.device.as_ref().map(|d| f(&mut *d.lock().unwrap_or_else(|e| e.into_inner())))
+    }
+
+    fn device_clocked(&self) -> bool {
+        self.clock == SerialClock::Device
+    }
+}
+
 /// A device plugged into the link port. The serial unit latches the device's
 /// preloaded response byte at transfer start (the peer shift register's
 /// contents) and hands the completed outgoing byte back at transfer end, so a
@@ -257,7 +477,7 @@ impl LinkPeer {
 /// are only loosely coupled), and completes external-clock transfers when the
 /// peer's window deposits its byte.
 #[derive(Serialize, Deserialize, Clone, Default)]
-pub(crate) enum SerialDevice {
+pub(crate) enum PortDevice {
     #[default]
     Disconnected,
     Printer(printer::GbPrinter),
@@ -269,20 +489,31 @@ pub(crate) enum SerialDevice {
     /// Mobile Adapter GB: an internal-clock serial slave (like the printer) that
     /// answers the libmobile packet protocol byte by byte.
     Mobile(crate::mobile::MobileAdapter),
+    /// Any other [`SerialDevice`] a frontend plugged in.
+    Plugged(PluggedDevice),
 }
 
-impl SerialDevice {
+impl PortDevice {
     #[allow(dead_code)] // KEEP (owner decision 2026-07-20): implemented peripheral awaiting frontend
     // wiring, not rot. No in-tree caller, so `dead_code` fires; do not delete.
     pub(crate) fn is_link(&self) -> bool {
-        matches!(self, SerialDevice::Link(_))
+        matches!(self, PortDevice::Link(_))
+    }
+
+    /// True for a link cable whose other end is still plugged in.
+    pub(crate) fn link_connected(&self) -> bool {
+        matches!(self, PortDevice::Link(l) if l.connected())
     }
 
     /// True for devices that drive the clock externally and complete transfers
     /// via the idle deposit poll (a link peer or the DMG-07 adapter) rather than
     /// this Game Boy's own internal-clock window.
     pub(crate) fn drives_external_clock(&self) -> bool {
-        matches!(self, SerialDevice::Link(_) | SerialDevice::FourPlayer(_))
+        match self {
+            PortDevice::Link(_) | PortDevice::FourPlayer(_) => true,
+            PortDevice::Plugged(p) => p.device_clocked(),
+            _ => false,
+        }
     }
 
     /// Observe an SC write and answer what an internal-clock transfer start
@@ -290,17 +521,23 @@ impl SerialDevice {
     /// must be called for every SC write.
     pub(crate) fn sc_write(&mut self, value: u8, sb: u8) -> LinkStart {
         match self {
-            SerialDevice::Disconnected => LinkStart::Disconnected,
-            SerialDevice::Printer(p) => LinkStart::Ready(p.preloaded_response()),
-            SerialDevice::Link(l) => l.sc_write(value, sb),
+            PortDevice::Disconnected => LinkStart::Disconnected,
+            PortDevice::Printer(p) => LinkStart::Ready(p.preloaded_response()),
+            PortDevice::Link(l) => l.sc_write(value, sb),
             // The adapter is the clock master; driving an internal-clock
             // transfer against it yields garbage. External-clock arming
             // schedules no internal window, so this is only consumed on that
             // misuse.
-            SerialDevice::FourPlayer(_) => LinkStart::Ready(0xFF),
+            PortDevice::FourPlayer(_) => LinkStart::Ready(0xFF),
             // The Mobile Adapter is an internal-clock slave: the Game Boy clocks
             // and receives the adapter's preloaded byte, like the printer.
-            SerialDevice::Mobile(m) => LinkStart::Ready(m.preloaded_response()),
+            PortDevice::Mobile(m) => LinkStart::Ready(m.preloaded_response()),
+            // Only a transfer start asks the device for its reply; other SC
+            // writes schedule nothing.
+            PortDevice::Plugged(p) if value & 0x81 == 0x81 => {
+                p.with(|d| LinkStart::Ready(d.start_exchange(sb))).unwrap_or(LinkStart::Disconnected)
+            }
+            PortDevice::Plugged(_) => LinkStart::Disconnected,
         }
     }
 
@@ -310,23 +547,26 @@ impl SerialDevice {
     /// wall-clock).
     pub(crate) fn receive_byte(&mut self, tx: u8, rx: u8, cc: u64) {
         match self {
-            SerialDevice::Disconnected => {}
-            SerialDevice::Printer(p) => p.receive_byte(tx, cc),
-            SerialDevice::Link(l) => l.complete_master(tx, rx),
+            PortDevice::Disconnected => {}
+            PortDevice::Printer(p) => p.receive_byte(tx, cc),
+            PortDevice::Link(l) => l.complete_master(tx, rx),
             // The adapter clocks externally; the Game Boy's reply is captured
             // via `link_mirror_sb` before each deposit, so nothing to do here.
-            SerialDevice::FourPlayer(_) => {}
+            PortDevice::FourPlayer(_) => {}
             // Feed the Game Boy's shifted-out byte into the packet FSM, which
             // preloads the response for the next transfer.
-            SerialDevice::Mobile(m) => m.receive_byte(tx),
+            PortDevice::Mobile(m) => m.receive_byte(tx),
+            PortDevice::Plugged(p) => {
+                p.with(|d| d.on_byte_exchanged(tx, rx, cc));
+            }
         }
     }
 
     /// Keep the peer's / adapter's view of our outgoing SB in sync.
     pub(crate) fn link_mirror_sb(&mut self, sb: u8) {
         match self {
-            SerialDevice::Link(l) => l.mirror_sb(sb),
-            SerialDevice::FourPlayer(p) => p.mirror_sb(sb),
+            PortDevice::Link(l) => l.mirror_sb(sb),
+            PortDevice::FourPlayer(p) => p.mirror_sb(sb),
             _ => {}
         }
     }
@@ -334,7 +574,7 @@ impl SerialDevice {
     /// Stalled internal-clock transfer: the peer's byte once its side arms.
     pub(crate) fn link_poll_peer(&mut self) -> Option<u8> {
         match self {
-            SerialDevice::Link(l) => l.poll_peer(),
+            PortDevice::Link(l) => l.poll_peer(),
             _ => None,
         }
     }
@@ -342,7 +582,7 @@ impl SerialDevice {
     /// Stall-timeout fallback byte (peer's live shift register / 0xFF).
     pub(crate) fn link_peer_live_sb(&self) -> u8 {
         match self {
-            SerialDevice::Link(l) => l.peer_live_sb(),
+            PortDevice::Link(l) => l.peer_live_sb(),
             _ => 0xFF,
         }
     }
@@ -352,15 +592,25 @@ impl SerialDevice {
     /// ready, clocking one exchange per pull (deposit-on-arm cadence).
     pub(crate) fn link_take_deposit(&mut self) -> Option<u8> {
         match self {
-            SerialDevice::Link(l) => l.take_deposit(),
-            SerialDevice::FourPlayer(p) => Some(p.clock()),
+            PortDevice::Link(l) => l.take_deposit(),
+            PortDevice::FourPlayer(p) => Some(p.clock()),
+            PortDevice::Plugged(p) if p.device_clocked() => p.with(|d| d.clock_in()).flatten(),
             _ => None,
         }
     }
 
+    /// A device-clocked exchange completed: `tx` left our SB, `rx` replaced
+    /// it. Only a plugged [`SerialDevice`] hears about it; the link peer and
+    /// adapter track our side through their SB mirrors instead.
+    pub(crate) fn external_exchanged(&mut self, tx: u8, rx: u8, cc: u64) {
+        if let PortDevice::Plugged(p) = self {
+            p.with(|d| d.on_byte_exchanged(tx, rx, cc));
+        }
+    }
+
     /// External-clock completion applied: drop our armed mirror.
     pub(crate) fn link_disarm(&mut self, sb: u8) {
-        if let SerialDevice::Link(l) = self {
+        if let PortDevice::Link(l) = self {
             l.disarm(sb);
         }
     }
@@ -399,7 +649,7 @@ pub struct Serial {
     // Internal-clock transfer holding for a link peer that hasn't armed yet:
     // the shift clock is frozen (no bits move, SB unchanged — hardware-true
     // for "no exchange happened yet") until the peer posts or the stall times
-    // out. Only ever set with a `SerialDevice::Link` attached, so every other
+    // out. Only ever set with a `PortDevice::Link` attached, so every other
     // configuration keeps its exact timing.
     #[serde(default)]
     link_wait: bool,
@@ -448,8 +698,8 @@ impl Serial {
     }
 
     /// True while a serial transfer is in flight (its `complete_at` event is
-    /// pending). Blocks the idle bulk-skip so the bit-shift and completion IRQ
-    /// land at the exact cc.
+    /// pending). Keeps the bus scheduler stepping serial every dot so the
+    /// bit-shift and completion IRQ land at the exact cc.
     pub(crate) fn is_active(&self) -> bool {
         self.active
     }
@@ -692,11 +942,13 @@ impl Serial {
     }
 
     /// Advance bookkeeping at master cc `phase` (the timer's `abs_cc`, sampled
-    /// within this dot's tick). Shifts SB as bits clock out and raises the serial
-    /// IRQ exactly when `complete_at` is reached.
-    pub fn step(&mut self, phase: u64, mmio: &mut mmio::Mmio) {
+    /// within this dot's tick). Shifts SB as bits clock out and, exactly when
+    /// `complete_at` is reached, returns the finished exchange as
+    /// `(tx, rx, completion cc)`; the caller (`Mmio::step_serial`) raises the
+    /// serial IRQ and hands the byte to the link device.
+    pub fn step(&mut self, phase: u64) -> Option<(u8, u8, u64)> {
         if !self.active || self.link_wait {
-            return;
+            return None;
         }
         // Number of bits whose clock edge has passed by `phase`.
         let target = if phase >= self.complete_at {
@@ -721,12 +973,12 @@ impl Serial {
             // INT $58 — https://gbdev.io/pandocs/Interrupt_Sources.html
             self.active = false;
             self.sc &= !SC_TRANSFER_START;
-            mmio.request_interrupt(cpu::registers::InterruptFlag::Serial);
             // The device sees the byte at the transfer's true completion cc
             // (not the possibly-later observation phase after a bulk skip).
             // `self.sb` is the received byte (rx) after all 8 shifts.
-            mmio.serial_device_receive(self.tx_acc, self.sb, self.complete_at);
+            return Some((self.tx_acc, self.sb, self.complete_at));
         }
+        None
     }
 }
 
@@ -755,6 +1007,7 @@ impl Addressable for Serial {
 mod tests {
     use crate::cartridge::Cartridge;
     use crate::gb::{GB, Hardware};
+    use std::sync::{Arc, Mutex};
 
     /// Hand-assembled ROM: sends the 10 bytes at 0x0200 over the link port
     /// (SB write, SC=0x81, poll SC bit 7, read SB) and stores each response to
@@ -805,6 +1058,56 @@ mod tests {
         assert_eq!(run_probe(false), vec![0xFF; 10]);
     }
 
+    /// Serial capture records every byte shifted out, in order, and drains
+    /// empty; with capture off nothing accumulates.
+    #[test]
+    fn serial_capture_records_bytes_shifted_out() {
+        let mut gb = GB::new(Hardware::DMG);
+        gb.insert(Cartridge::from_bytes(&link_probe_rom()).unwrap());
+        gb.skip_bios();
+        gb.set_serial_capture(true);
+        for _ in 0..20 {
+            gb.run_until_frame(false);
+        }
+        assert_eq!(gb.drain_serial_output(), &link_probe_rom()[0x200..0x20A]);
+        assert!(gb.drain_serial_output().is_empty());
+        gb.set_serial_capture(false);
+        assert!(gb.drain_serial_output().is_empty());
+    }
+
+    /// An internal-clock start spans eight bit periods of the selected clock:
+    /// 512 cc per bit at 8192 Hz, 16 cc per bit on the CGB fast clock. An
+    /// external-clock start schedules nothing; the peer drives it.
+    #[test]
+    fn internal_clock_transfer_spans_eight_bit_periods() {
+        use super::{LinkStart, Serial};
+        let mut serial = Serial::new();
+        serial.schedule_sc(0x81, 0, 1000, LinkStart::Disconnected);
+        assert_eq!(serial.transfer_complete_at(), Some(1000 + 8 * 512));
+        serial.set_cgb(true);
+        serial.schedule_sc(0x83, 0, 1000, LinkStart::Disconnected);
+        assert_eq!(serial.transfer_complete_at(), Some(1000 + 8 * 16));
+        serial.schedule_sc(0x80, 0, 1000, LinkStart::Disconnected);
+        assert_eq!(serial.transfer_complete_at(), None);
+    }
+
+    /// Stepping hands the finished exchange back rather than touching mmio:
+    /// nothing before the last bit period, then (sent, received, completion
+    /// cc) once, with SC.7 cleared.
+    #[test]
+    fn step_returns_the_finished_exchange() {
+        use super::{LinkStart, SC, SB, Serial};
+        use crate::memory::Addressable;
+        let mut serial = Serial::new();
+        serial.write(SB, 0x5A);
+        serial.schedule_sc(0x81, 0, 1000, LinkStart::Disconnected);
+        let done = 1000 + 8 * 512;
+        assert_eq!(serial.step(done - 1), None);
+        assert_eq!(serial.step(done), Some((0x5A, 0xFF, done)));
+        assert_eq!(serial.read(SC) & 0x80, 0);
+        assert_eq!(serial.step(done + 1), None);
+    }
+
     /// With a printer attached the INIT packet gets 0x00 during the body and
     /// the 0x81 alive + 0x00 status pair in the trailing slots, through the
     /// real serial timing path (schedule/shift/IRQ), not a shortcut.
@@ -862,6 +1165,61 @@ mod tests {
         rom
     }
 
+    /// A loopback plug hands every byte straight back on a Game Boy-clocked
+    /// transfer.
+    #[test]
+    fn loopback_device_echoes_sent_bytes() {
+        let payload = [0x10, 0x20, 0x30, 0x40, 0x55, 0xAA, 0x00, 0xFF];
+        let mut gb = gb_with(link_xfer_rom(0x81, &payload, false), Hardware::DMG);
+        gb.attach_serial_device(Arc::new(Mutex::new(super::Loopback)));
+        for _ in 0..10 {
+            gb.run_until_frame(false);
+        }
+        let got: Vec<u8> = (0..8).map(|i| gb.read_memory(0xC000 + i)).collect();
+        assert_eq!(got, payload);
+    }
+
+    /// A device-clocked plug completes each armed external-clock transfer with
+    /// its next byte and hears what the Game Boy sent back; a clone of the
+    /// machine comes back unplugged.
+    #[test]
+    fn device_clocked_plug_drives_external_transfers() {
+        use super::{SerialClock, SerialDevice};
+        #[derive(Default)]
+        struct Feeder {
+            next: u8,
+            heard: Vec<(u8, u8)>,
+        }
+        impl SerialDevice for Feeder {
+            fn clock_source(&self) -> SerialClock {
+                SerialClock::Device
+            }
+            fn clock_in(&mut self) -> Option<u8> {
+                self.next += 1;
+                Some(self.next)
+            }
+            fn on_byte_exchanged(&mut self, tx: u8, rx: u8, _cc: u64) {
+                self.heard.push((tx, rx));
+            }
+        }
+
+        let payload = [0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7, 0xA8];
+        let mut gb = gb_with(link_xfer_rom(0x80, &payload, false), Hardware::DMG);
+        let feeder = Arc::new(Mutex::new(Feeder::default()));
+        gb.attach_serial_device(feeder.clone());
+        for _ in 0..3 {
+            gb.run_until_frame(false);
+        }
+        let got: Vec<u8> = (0..8).map(|i| gb.read_memory(0xC000 + i)).collect();
+        assert_eq!(got, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let heard = feeder.lock().unwrap().heard.clone();
+        assert_eq!(heard, payload.iter().zip(1..).map(|(&tx, rx)| (tx, rx)).collect::<Vec<_>>());
+
+        let mut twin = gb.clone();
+        twin.run_until_frame(false);
+        assert_eq!(feeder.lock().unwrap().heard.len(), 8);
+    }
+
     /// A ROM that never touches the serial registers (idle link partner).
     fn spin_rom() -> Vec<u8> {
         let mut rom = vec![0u8; 0x8000];
@@ -953,6 +1311,42 @@ mod tests {
         }
     }
 
+    /// Netplay shape: each instance talks to a [`super::RemoteLink`] and the
+    /// messages only cross between whole frames, as a transport delivers
+    /// them. The slave's early arm reaches the master ahead of its start, so
+    /// all eight bytes still arrive in order each way.
+    #[test]
+    fn remote_links_exchange_bytes_between_frames() {
+        use super::RemoteLink;
+        let tx_a: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+        let tx_b: [u8; 8] = [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7];
+        let mut a = gb_with(link_xfer_rom(0x81, &tx_a, true), Hardware::DMG);
+        let mut b = gb_with(link_xfer_rom(0x80, &tx_b, false), Hardware::DMG);
+        let (mut la, mut lb) = (RemoteLink::default(), RemoteLink::default());
+        a.plug_remote_link(&mut la);
+        b.plug_remote_link(&mut lb);
+        assert!(la.connected() && lb.connected());
+        for _ in 0..120 {
+            a.run_until_frame(false);
+            b.run_until_frame(false);
+            for m in la.outgoing() {
+                lb.deliver(m);
+            }
+            for m in lb.outgoing() {
+                la.deliver(m);
+            }
+        }
+        assert_eq!(wram(&a, 8), tx_b);
+        assert_eq!(wram(&b, 8), tx_a);
+
+        // A clone severs its end; re-plugging keeps the remote's last state.
+        let mut c = a.clone();
+        drop(a);
+        assert!(!la.connected());
+        c.plug_remote_link(&mut la);
+        assert!(la.connected());
+    }
+
     /// The headless two-instance proof: instance A (internal clock) sends
     /// 0x01..=0x08, instance B (external clock) sends 0xA0..=0xA7, pumped in
     /// instruction-alternating lockstep. Each side must receive the other's
@@ -1149,6 +1543,20 @@ mod tests {
         assert_eq!(a.read_memory(0xC000), 0xFF);
     }
 
+    /// Clones and restored states get a severed end; dropping one instance
+    /// leaves the other holding a cable to nobody.
+    #[test]
+    fn link_connection_ends_with_a_clone_or_a_dropped_peer() {
+        let mut a = GB::new(Hardware::DMG);
+        let mut b = GB::new(Hardware::DMG);
+        assert!(!a.link_connected());
+        GB::connect_link(&mut a, &mut b);
+        assert!(a.link_connected() && b.link_connected());
+        assert!(!a.clone().link_connected());
+        drop(b);
+        assert!(!a.link_connected());
+    }
+
     /// Clock conflict: both sides start internal-clock transfers. On
     /// hardware both drive the clock line and both shift registers exchange;
     /// here each side completes its own window against the other's live
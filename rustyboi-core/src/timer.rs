@@ -1,6 +1,4 @@
-use crate::cpu;
 use crate::memory::Addressable;
-use crate::memory::mmio;
 
 use serde::{Deserialize, Serialize};
 
@@ -237,9 +235,9 @@ impl Timer {
     /// The exact cc at which the next scheduled overflow's IF bit will be raised
     /// inside `update_irq_delivery` / `step_to`, accounting for the same `fold`
     /// that path applies (`IF_OFF` on the non-halt early-ISR grid, else `CC_OFF`).
-    /// The min-event idle fast path lands precisely on this cc so the overflow
-    /// fires at the identical cc the per-dot crank would have. `None` when the
-    /// timer is disabled / no overflow scheduled.
+    /// The bus scheduler's timer event (`quiet_until`) lands precisely on this
+    /// cc so the overflow fires at the identical cc the per-dot crank would
+    /// have. `None` when the timer is disabled / no overflow scheduled.
     pub(crate) fn next_overflow_fire_cc(&self, cpu_halted: bool) -> Option<u64> {
         if self.tac & TAC_ENABLE == 0 || self.next_irq_event_time == DISABLED_TIME {
             return None;
@@ -810,15 +808,9 @@ impl Timer {
         }
     }
 
-    /// Raise the pending TIMA IRQ (if any) into `mmio`. Called from `step` and
-    /// immediately after a write that may have flagged a glitch IRQ.
-    pub(crate) fn flush_pending_irq(&mut self, mmio: &mut mmio::Mmio) {
-        if self.pending_irq {
-            self.pending_irq = false;
-            mmio.request_interrupt(cpu::registers::InterruptFlag::Timer);
-        }
-    }
-
+    /// Take the pending TIMA IRQ (if any) for the caller to raise in mmio.
+    /// Read after `step_to`, the EI fast delivery, and immediately after a
+    /// write that may have flagged a glitch IRQ.
     pub(crate) fn take_pending_irq(&mut self) -> bool {
         let p = self.pending_irq;
         self.pending_irq = false;
@@ -856,13 +848,13 @@ impl Timer {
             false
         };
 
-        // Recorded for `quiet_until`, which bounds the idle fast path at the
+        // Recorded for `quiet_until`, which bounds the scheduler's jumps at the
         // next DIV-bit-12 (bit-13 in double speed) falling edge.
         self.last_double_speed = ds;
         timer_irq
     }
 
-    /// Raw one-dot master-clock bump for the quiet-span fast loop: byte-
+    /// Raw one-dot master-clock bump for a bus scheduler PPU dot: byte-
     /// identical to `step` for any dot proven to cross no scheduled overflow
     /// delivery and no APU FS edge (see `quiet_until`) — `update_irq_delivery`
     /// is then a no-op (its while-loop condition is keyed on absolute ccs and
@@ -872,9 +864,8 @@ impl Timer {
         self.abs_cc = self.abs_cc.wrapping_add(1);
     }
 
-    /// n-dot variant of `bump_cc_one`; every bumped dot must lie strictly
-    /// below `quiet_until`.
-    #[inline]
+    /// n-dot variant of `bump_cc_one`, for tests that park the clock.
+    #[cfg(test)]
     pub(crate) fn bump_cc_by(&mut self, n: u64) {
         self.abs_cc = self.abs_cc.wrapping_add(n);
     }
@@ -882,7 +873,7 @@ impl Timer {
     /// Exclusive upper bound up to which per-dot `step` is a pure `abs_cc`
     /// increment: the earlier of the next scheduled overflow delivery cc and
     /// the next APU frame-sequencer edge cc. A pending undelivered IRQ or a
-    /// due event yields `abs_cc` (no quiet span).
+    /// due event yields `abs_cc` (no jump).
     pub(crate) fn quiet_until(&self, cpu_halted: bool) -> u64 {
         if self.pending_irq {
             return self.abs_cc;
@@ -906,20 +897,19 @@ impl Timer {
     /// all overflows due <= abs_cc, so a single call at the final cc fires the same
     /// set as the per-dot calls). The only per-dot bookkeeping is the
     /// `abs_cc += 1`, which is collapsed to a single assignment here. This is the
-    /// timer half of the min-event-jump idle fast path (`Bus::run_to`); it is only
-    /// invoked when the world is provably idle except for the timer (LCD off, no
-    /// DMA/HDMA, audio off, serial idle), so no other peripheral's per-dot state is
-    /// skipped.
-    pub(crate) fn step_to(&mut self, target_abs_cc: u64, mmio: &mut mmio::Mmio) {
+    /// timer half of a bus scheduler jump (`Mmio::advance_world_to`). Like
+    /// `step`, takes the mmio flags by value and returns whether a TIMA
+    /// overflow IRQ should be raised.
+    pub(crate) fn step_to(&mut self, target_abs_cc: u64, double_speed: bool, cpu_halted: bool) -> bool {
         if target_abs_cc <= self.abs_cc {
-            return;
+            return false;
         }
         self.abs_cc = target_abs_cc;
         if self.tac & TAC_ENABLE != 0 {
-            self.update_irq_delivery(self.abs_cc, mmio.cpu_is_halted());
+            self.update_irq_delivery(self.abs_cc, cpu_halted);
         }
-        self.flush_pending_irq(mmio);
-        self.last_double_speed = mmio.is_double_speed_mode();
+        self.last_double_speed = double_speed;
+        self.take_pending_irq()
     }
 }
 
@@ -1159,3 +1149,87 @@ mod enabled_tac_write_glitch_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod obscure_behaviour_tests {
+    //! The Pan Docs "Timer obscure behaviour" cases the mooneye timer ROMs
+    //! (tima_reload, tima_write_reloading, tma_write_reloading, div_write,
+    //! rapid_toggle) exercise, pinned at the register level.
+    use super::*;
+
+    /// TAC enabled at 16 T-cycles per tick (DIV bit 3).
+    const E16: u8 = 0b101;
+
+    /// A running E16 timer one tick from overflow with TMA = 0x42.
+    fn about_to_overflow() -> Timer {
+        let mut t = Timer::new();
+        t.bump_cc_by(0x1000);
+        t.write(TMA, 0x42);
+        t.write(TAC, E16);
+        t.write(DIV, 0);
+        t.write(TIMA, 0xFF);
+        t
+    }
+
+    /// Step until TIMA reads 0: the overflow's reload window has opened.
+    fn to_overflow(t: &mut Timer) {
+        for _ in 0..64 {
+            if t.read(TIMA) == 0 {
+                return;
+            }
+            t.bump_cc_one();
+        }
+        panic!("TIMA never overflowed");
+    }
+
+    #[test]
+    fn tima_reads_zero_for_one_m_cycle_before_the_reload() {
+        let mut t = about_to_overflow();
+        to_overflow(&mut t);
+        // The CPU samples once per M-cycle: exactly one read sees the 0.
+        let mut zero_reads = 0;
+        while t.read(TIMA) == 0 {
+            zero_reads += 1;
+            t.bump_cc_by(4);
+        }
+        assert_eq!(zero_reads, 1, "the reload lags the overflow by one M-cycle");
+        assert_eq!(t.read(TIMA), 0x42);
+    }
+
+    #[test]
+    fn a_tima_write_in_the_delay_cancels_the_reload() {
+        let mut t = about_to_overflow();
+        to_overflow(&mut t);
+        t.write(TIMA, 0x10);
+        t.bump_cc_by(8);
+        assert_eq!(t.read(TIMA), 0x10, "the written value survives; TMA is never loaded");
+    }
+
+    #[test]
+    fn a_tma_write_in_the_delay_is_what_gets_reloaded() {
+        let mut t = about_to_overflow();
+        to_overflow(&mut t);
+        t.write(TMA, 0x77);
+        t.bump_cc_by(8);
+        assert_eq!(t.read(TIMA), 0x77);
+    }
+
+    /// Resetting DIV while the selected bit is high is a falling edge on the
+    /// TIMA clock: one extra tick. While it is low, nothing happens.
+    #[test]
+    fn a_div_write_with_the_selected_bit_high_ticks_tima() {
+        let probe = |sys: u64| {
+            let mut t = Timer::new();
+            t.bump_cc_by(0x1000);
+            t.write(TAC, E16);
+            t.write(DIV, 0);
+            t.write(TIMA, 0x10);
+            t.bump_cc_by(sys);
+            let before = t.read(TIMA);
+            t.write(DIV, 0);
+            t.read(TIMA).wrapping_sub(before)
+        };
+        assert_eq!(probe(10), 1, "DIV bit 3 high: the reset glitch-ticks TIMA");
+        assert_eq!(probe(2), 0, "DIV bit 3 low: no edge, no tick");
+    }
+}
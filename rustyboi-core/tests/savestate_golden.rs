@@ -9,11 +9,17 @@
 //! pair; no CGB banks) and cgb_acid2 (high-entropy RGB → palette-indexed color
 //! buffers + Solid all-zero mono pair; vram_bank1 + wram_banks populated).
 //!
+//! cgb_acid2.v4.rustyboisave is the cgb_acid2 state as a version-4 container
+//! (mid-line, sprites on the line), pinning the upgrades that dropped the PPU's
+//! sprite list and the output resampler from the payload: it must load and
+//! re-serialize as the current fixture.
+//!
 //! Regenerating a fixture is an explicit, reviewed act:
 //!   cargo test -p rustyboi-core --test savestate_golden -- --ignored write_golden_fixtures
 
 use rustyboi_core_lib::cartridge::Cartridge;
 use rustyboi_core_lib::gb::{Hardware, GB};
+use rustyboi_core_lib::savestate;
 use std::fs;
 use std::path::PathBuf;
 
@@ -86,6 +92,19 @@ fn golden_fixtures_reserialize_byte_identically() {
     }
 }
 
+/// Older formats walk the upgrader chain onto the current layout: the
+/// version-4 container decodes to exactly the current fixture.
+#[test]
+fn version_four_container_upgrades_to_the_current_fixture() {
+    let v4 = fs::read(fixture_path("cgb_acid2.v4.rustyboisave")).expect("v4 fixture");
+    let mut upgraded = savestate::read_state(&v4, None).expect("v4 state loads");
+    assert_eq!(
+        upgraded.to_state_bytes().expect("serialize"),
+        fs::read(fixture_path("cgb_acid2.state")).expect("fixture"),
+        "upgraded v4 state differs from the current fixture"
+    );
+}
+
 /// Determinism gate: regenerating the state from the ROM must reproduce the
 /// committed fixture exactly (runs wherever gb-test-roms is present, e.g. CI
 /// after `make setup`; skips gracefully without it).
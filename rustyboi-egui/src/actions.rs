@@ -8,9 +8,11 @@
 //! behavior — `Session::apply` does.
 
 pub use rustyboi_session::action::{
-    ActionKind, CommandDescriptor, FileData, GbcDmgPalette, HardwareChoice, HardwareFamily,
-    KeyBind, LcdEffect, MenuCategory, DmgPaletteChoice, ScalingMode, SessionUiState,
-    SgbPaletteChoice, TextureFilter, UiAction, COMMANDS, FAST_FORWARD_SPEEDS, PRINTER_SCALES,
+    AccuracyPreset, ActionKind, AspectRatio, ChannelMix, CommandDescriptor, FileData, FrameSkip, GbcDmgPalette, HardwareChoice,
+    HardwareFamily, KeyBind, LcdEffect, MenuCategory, DmgPaletteChoice, ScalingMode, SessionUiState,
+    SgbPaletteChoice, TextureFilter, UiAction, COMMANDS, EMULATION_SPEEDS, FAST_FORWARD_SPEEDS, FRAME_SKIPS,
+    PRINTER_SCALES, AUDIO_BUFFER_SIZES, AUDIO_LATENCY_FRAMES, GIF_LENGTHS, GIF_SCALES, SAMPLE_RATES, WINDOW_SCALES,
+    step_emulation_speed,
 };
 pub use rustyboi_session::ColorCorrection;
 
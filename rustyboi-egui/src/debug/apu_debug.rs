@@ -0,0 +1,85 @@
+use egui::{Color32, Context, RichText};
+use rustyboi_session::DebugSnapshot;
+use crate::ui::Gui;
+
+const CHANNEL_NAMES: [&str; 4] = ["CH1 Pulse", "CH2 Pulse", "CH3 Wave", "CH4 Noise"];
+const DUTY_LABELS: [&str; 4] = ["12.5%", "25%", "50%", "75%"];
+const WAVE_LEVELS: [&str; 4] = ["mute", "100%", "50%", "25%"];
+
+const SCOPE_WIDTH: f32 = 230.0;
+const SCOPE_HEIGHT: f32 = 36.0;
+
+impl Gui {
+    pub(in crate) fn render_apu_debug_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>) {
+        let Some(apu) = debug.and_then(|s| s.apu.as_ref()) else { return };
+        self.debug_window("APU", [1160.0, 50.0])
+            .default_size([250.0, 560.0])
+            .collapsible(true)
+            .resizable(false)
+            .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(crate::ui::PANEL_BACKGROUND))
+            .show(ctx, |ui| {
+                ui.set_width(SCOPE_WIDTH);
+
+                let powered = apu.nr52 & 0x80 != 0;
+                ui.monospace(RichText::new(format!("Power: {}", if powered { "ON" } else { "OFF" }))
+                    .color(if powered { Color32::LIGHT_GREEN } else { Color32::LIGHT_RED }));
+                ui.monospace(RichText::new(format!("NR50: {:02X}  NR51: {:02X}  NR52: {:02X}", apu.nr50, apu.nr51, apu.nr52))
+                    .color(Color32::WHITE));
+
+                for (i, ch) in apu.channels.iter().enumerate() {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(CHANNEL_NAMES[i]).color(Color32::LIGHT_GRAY).strong());
+                        let (text, color) = match (ch.enabled, ch.dac_on) {
+                            (true, _) => ("playing", Color32::LIGHT_GREEN),
+                            (false, true) => ("stopped, DAC on", Color32::YELLOW),
+                            (false, false) => ("off", Color32::GRAY),
+                        };
+                        ui.small(RichText::new(text).color(color));
+                    });
+
+                    scope(ui, apu.scope.iter().map(|levels| levels[i]), ch.enabled);
+
+                    let volume = if i == 2 {
+                        WAVE_LEVELS[ch.volume as usize & 3].to_string()
+                    } else {
+                        format!("{:>2}/15", ch.volume)
+                    };
+                    ui.monospace(RichText::new(format!("Freq: {:>9.1} Hz  Vol: {volume}", ch.frequency_hz))
+                        .color(Color32::WHITE));
+                    let duty = ch.duty.map_or("—", |d| DUTY_LABELS[d as usize & 3]);
+                    let length = if ch.length_enabled {
+                        format!("{}", ch.length_remaining)
+                    } else {
+                        "off".to_string()
+                    };
+                    ui.monospace(RichText::new(format!("Duty: {duty:<5}  Length: {length}")).color(Color32::WHITE));
+                }
+            });
+    }
+}
+
+/// Draw one channel's last frame of levels as a trace. A level is the DAC's
+/// analog output, where digital 15 is -1, so it is drawn flipped to put louder
+/// samples at the top.
+fn scope(ui: &mut egui::Ui, levels: impl ExactSizeIterator<Item = f32>, playing: bool) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(SCOPE_WIDTH, SCOPE_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, Color32::from_gray(16));
+    painter.hline(rect.x_range(), rect.center().y, egui::Stroke::new(1.0, Color32::from_gray(48)));
+
+    let count = levels.len();
+    if count < 2 {
+        return;
+    }
+    let half = rect.height() / 2.0 - 2.0;
+    let points: Vec<egui::Pos2> = levels
+        .enumerate()
+        .map(|(n, level)| {
+            let x = rect.left() + rect.width() * n as f32 / (count - 1) as f32;
+            egui::pos2(x, rect.center().y + level * half)
+        })
+        .collect();
+    let color = if playing { Color32::LIGHT_GREEN } else { Color32::GRAY };
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+}
@@ -1,6 +1,6 @@
 use crate::ui::Gui;
 use egui::{Color32, Context, RichText};
-use rustyboi_session::DebugSnapshot;
+use rustyboi_session::{DebugSnapshot, DumpStatus};
 
 /// Human-readable ROM/RAM size (GB sizes are powers of two).
 fn human(n: usize) -> String {
@@ -16,8 +16,7 @@ fn human(n: usize) -> String {
 impl Gui {
     pub(crate) fn render_cartridge_info_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>) {
         let info = debug.and_then(|s| s.cartridge.as_ref());
-        egui::Window::new("Cartridge Info")
-            .default_pos([270.0, 50.0])
+        self.debug_window("ROM Info", [270.0, 50.0])
             .default_size([320.0, 480.0])
             .collapsible(true)
             .resizable(true)
@@ -55,6 +54,7 @@ impl Gui {
                     row(ui, "Type byte", format!("{:#04X}", c.type_byte));
                     row(ui, "Licensee", c.licensee.clone().unwrap_or_else(|| "unknown".into()));
                     row(ui, "Region", c.destination.clone().unwrap_or_else(|| "—".into()));
+                    row(ui, "Version", format!("{}", c.version));
                 });
 
                 head(ui, "Size");
@@ -82,14 +82,37 @@ impl Gui {
                 head(ui, "Integrity");
                 egui::Grid::new("cart_int").num_columns(2).spacing([12.0, 2.0]).show(ui, |ui| {
                     row(ui, "CRC32", c.crc32.map_or("—".into(), |v| format!("{v:08X}")));
-                    ui.label(RichText::new("Header checksum").color(Color32::GRAY));
-                    ui.label(if c.header_checksum_ok {
-                        RichText::new("valid").color(Color32::LIGHT_GREEN)
-                    } else {
-                        RichText::new("BAD").color(Color32::LIGHT_RED)
-                    });
-                    ui.end_row();
-                    row(ui, "Global checksum", format!("{:04X}", c.global_checksum));
+                    row(ui, "SHA-1", c.sha1.map_or("—".into(), |d| d.iter().map(|b| format!("{b:02x}")).collect()));
+                    // Only with a verification DAT configured.
+                    if let Some(dump) = &c.dump {
+                        ui.label(RichText::new("Dump").color(Color32::GRAY));
+                        ui.label(match dump {
+                            DumpStatus::Verified(name) => {
+                                RichText::new(format!("verified good dump ({name})")).color(Color32::LIGHT_GREEN)
+                            }
+                            DumpStatus::Unknown => RichText::new("unknown").color(Color32::YELLOW),
+                        });
+                        ui.end_row();
+                    }
+                    // A stored checksum next to its verdict; green when it matches.
+                    let check = |ui: &mut egui::Ui, k: &str, stored: String, ok: bool| {
+                        ui.label(RichText::new(k).color(Color32::GRAY));
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(stored).color(Color32::WHITE).monospace());
+                            ui.label(if ok {
+                                RichText::new("valid").color(Color32::LIGHT_GREEN)
+                            } else {
+                                RichText::new("BAD").color(Color32::LIGHT_RED)
+                            });
+                        });
+                        ui.end_row();
+                    };
+                    check(ui, "Header checksum", format!("{:02X}", c.header_checksum), c.header_checksum_ok);
+                    let global_ok = c.stored_global_checksum == c.global_checksum;
+                    check(ui, "Global checksum", format!("{:04X}", c.stored_global_checksum), global_ok);
+                    if !global_ok {
+                        row(ui, "Computed", format!("{:04X}", c.global_checksum));
+                    }
                 });
             });
     }
@@ -48,8 +48,7 @@ impl Gui {
     pub(in crate) fn render_cpu_registers_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>, action: &mut Option<GuiAction>, paused: bool) {
         if let Some(snap) = debug {
                 let regs = &snap.cpu;
-                egui::Window::new("CPU Registers")
-                    .default_pos([10.0, 50.0])
+                self.debug_window("CPU Registers", [10.0, 50.0])
                     .default_size([250.0, 400.0])
                     .collapsible(true)
                     .resizable(false)
@@ -86,6 +85,9 @@ impl Gui {
                         ui.separator();
                         ui.monospace(egui::RichText::new(format!("IME: {}", if regs.ime { "ON" } else { "OFF" })).color(egui::Color32::WHITE));
                         ui.separator();
+                        ui.monospace(egui::RichText::new(format!("Cycles: {}", regs.cycles)).color(egui::Color32::WHITE));
+                        ui.monospace(egui::RichText::new(format!("Instrs: {}", regs.instructions)).color(egui::Color32::WHITE));
+                        ui.separator();
 
                         // Instruction viewer around PC
                         ui.small(egui::RichText::new("Instructions:").color(egui::Color32::LIGHT_GRAY));
@@ -0,0 +1,137 @@
+//! The debug window arrangement: which debug windows are open and where,
+//! saved to the session config ([`DebugLayout`]) and restored on the next
+//! run, plus Debug → Tile Windows, which packs the open ones into a grid.
+//!
+//! Every debug window is built through [`Gui::debug_window`] so its id is
+//! its title and its position can be restored or tiled. The GUI reports the
+//! arrangement back with [`GuiAction::SetDebugLayout`] whenever it changes,
+//! but only once the pointer is released so a drag persists once, not per
+//! frame.
+
+use egui::{Context, Pos2};
+use rustyboi_session::{DebugLayout, DebugWindowLayout, SessionUiState};
+
+use crate::actions::GuiAction;
+use crate::ui::Gui;
+
+/// Titles of the windows the layout covers, in tiling order.
+const DEBUG_WINDOWS: [&str; 10] = [
+    "CPU Registers",
+    "Stack Explorer",
+    "Memory Explorer",
+    "PPU Debug",
+    "APU",
+    "Sprite Debug",
+    "Palette Explorer",
+    "Tile Explorer",
+    "ROM Info",
+    "Breakpoint Manager",
+];
+
+/// Where tiling starts: clear of the menu bar.
+const TILE_ORIGIN: [f32; 2] = [10.0, 50.0];
+/// Space left between tiled windows.
+const TILE_GAP: f32 = 8.0;
+/// Size assumed for a window egui has not laid out yet.
+const FALLBACK_SIZE: [f32; 2] = [250.0, 400.0];
+
+impl Gui {
+    /// A debug window titled `title`, opening at its saved position (or
+    /// `default_pos` when it has none), and moved this frame when a restore
+    /// or tile placed it.
+    pub(crate) fn debug_window(&self, title: &'static str, default_pos: [f32; 2]) -> egui::Window<'static> {
+        let saved = self.debug_layout.windows.iter().find(|w| w.title == title).map(|w| w.pos);
+        let window = egui::Window::new(title).id(egui::Id::new(title)).default_pos(saved.unwrap_or(default_pos));
+        match self.debug_layout_moves.iter().find(|(t, _)| *t == title) {
+            Some(&(_, pos)) => window.current_pos(pos),
+            None => window,
+        }
+    }
+
+    fn debug_window_open(&mut self, title: &str) -> Option<&mut bool> {
+        Some(match title {
+            "CPU Registers" => &mut self.show_cpu_registers,
+            "Stack Explorer" => &mut self.show_stack_explorer,
+            "Memory Explorer" => &mut self.show_memory_explorer,
+            "PPU Debug" => &mut self.show_ppu_debug,
+            "APU" => &mut self.show_apu_debug,
+            "Sprite Debug" => &mut self.show_sprite_debug,
+            "Palette Explorer" => &mut self.show_palette_explorer,
+            "Tile Explorer" => &mut self.show_tile_explorer,
+            "ROM Info" => &mut self.show_cartridge_info,
+            "Breakpoint Manager" => &mut self.show_breakpoint_panel,
+            _ => return None,
+        })
+    }
+
+    /// Arrange the open debug windows left to right in rows across the
+    /// screen, each row as tall as its tallest window. Applied next frame.
+    pub(crate) fn tile_debug_windows(&mut self, ctx: &Context) {
+        let right = ctx.content_rect().right();
+        let [mut x, mut y] = TILE_ORIGIN;
+        let mut row_height: f32 = 0.0;
+        self.debug_layout_moves.clear();
+        for title in DEBUG_WINDOWS {
+            if !self.debug_window_open(title).is_some_and(|open| *open) {
+                continue;
+            }
+            let size = ctx
+                .memory(|m| m.area_rect(egui::Id::new(title)))
+                .map_or(FALLBACK_SIZE.into(), |r| r.size());
+            if x > TILE_ORIGIN[0] && x + size.x > right {
+                x = TILE_ORIGIN[0];
+                y += row_height + TILE_GAP;
+                row_height = 0.0;
+            }
+            self.debug_layout_moves.push((title, Pos2::new(x, y)));
+            x += size.x + TILE_GAP;
+            row_height = row_height.max(size.y);
+        }
+    }
+
+    /// The arrangement as it stands: every debug window that is open or has
+    /// been placed, with its top-left corner.
+    fn current_debug_layout(&mut self, ctx: &Context) -> DebugLayout {
+        let windows = DEBUG_WINDOWS
+            .into_iter()
+            .filter_map(|title| {
+                let open = self.debug_window_open(title).is_some_and(|open| *open);
+                let pos = ctx
+                    .memory(|m| m.area_rect(egui::Id::new(title)))
+                    .map(|r| [r.min.x, r.min.y])
+                    .or_else(|| self.debug_layout.windows.iter().find(|w| w.title == title).map(|w| w.pos))?;
+                Some(DebugWindowLayout { title: title.to_owned(), open, pos })
+            })
+            .collect();
+        DebugLayout { windows }
+    }
+
+    /// Called once per frame after the debug windows are drawn. The first
+    /// saved layout the session reports is restored (it can arrive a few
+    /// frames late on web); after that, changes are reported back.
+    pub(crate) fn sync_debug_layout(&mut self, ctx: &Context, session: &SessionUiState, action: &mut Option<GuiAction>) {
+        self.debug_layout_moves.clear();
+        if !self.debug_layout_restored && session.debug_layout != DebugLayout::default() {
+            self.debug_layout_restored = true;
+            self.debug_layout = session.debug_layout.clone();
+            for saved in &session.debug_layout.windows {
+                if let Some(title) = DEBUG_WINDOWS.into_iter().find(|t| *t == saved.title) {
+                    if let Some(open) = self.debug_window_open(title) {
+                        *open = saved.open;
+                    }
+                    self.debug_layout_moves.push((title, Pos2::from(saved.pos)));
+                }
+            }
+            return;
+        }
+        if action.is_some() || ctx.input(|i| i.pointer.any_down()) {
+            return;
+        }
+        let layout = self.current_debug_layout(ctx);
+        if layout != self.debug_layout {
+            self.debug_layout_restored = true;
+            self.debug_layout = layout.clone();
+            *action = Some(GuiAction::SetDebugLayout(layout));
+        }
+    }
+}
@@ -5,8 +5,7 @@ use crate::ui::Gui;
 impl Gui {
     pub(in crate) fn render_memory_explorer_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>) {
         if let Some(snap) = debug {
-            egui::Window::new("Memory Explorer")
-                .default_pos([410.0, 50.0])
+            self.debug_window("Memory Explorer", [410.0, 50.0])
                 .default_size([220.0, 400.0])
                 .collapsible(true)
                 .resizable(false)
@@ -1,5 +1,7 @@
+mod apu_debug;
 mod cartridge_info;
 mod cpu_registers;
+mod layout;
 mod memory_explorer;
 pub(crate) mod pixels;
 mod sprite_debug;
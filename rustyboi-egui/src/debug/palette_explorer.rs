@@ -5,8 +5,7 @@ use crate::ui::Gui;
 impl Gui {
     pub(in crate) fn render_palette_explorer_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>) {
         if let Some(snap) = debug {
-            egui::Window::new("Palette Explorer")
-                .default_pos([900.0, 50.0])
+            self.debug_window("Palette Explorer", [900.0, 50.0])
                 .default_size([250.0, 500.0])
                 .collapsible(true)
                 .resizable(true)
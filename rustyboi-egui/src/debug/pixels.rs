@@ -12,6 +12,8 @@
 //! then draws one (or a few) scaled `Image` widgets — two triangles each,
 //! nearest-filtered so the Game Boy pixels stay crisp. The per-frame texture
 //! upload (tens to ~100 KB) is trivial next to the tessellation it replaces.
+//! Panels whose input rarely changes (the tile atlas) go further with
+//! [`PixelTexture::update_from`] and skip the bake and upload while it holds.
 
 use egui::{Color32, ColorImage, Context, TextureHandle, TextureId, TextureOptions};
 
@@ -21,6 +23,8 @@ use egui::{Color32, ColorImage, Context, TextureHandle, TextureId, TextureOption
 #[derive(Default)]
 pub(crate) struct PixelTexture {
     handle: Option<TextureHandle>,
+    /// The input the texture was last baked from, for `update_from`.
+    source: Vec<u8>,
 }
 
 impl PixelTexture {
@@ -43,4 +47,25 @@ impl PixelTexture {
         }
         self.handle.as_ref().expect("just set").id()
     }
+
+    /// As [`update`](Self::update), but only bakes (and uploads) when `source`
+    /// (everything the pixels are computed from) differs from last time.
+    pub(crate) fn update_from(
+        &mut self,
+        ctx: &Context,
+        name: &str,
+        w: usize,
+        h: usize,
+        source: &[u8],
+        bake: impl FnOnce() -> Vec<Color32>,
+    ) -> TextureId {
+        if let Some(handle) = &self.handle
+            && self.source == source
+        {
+            return handle.id();
+        }
+        self.source.clear();
+        self.source.extend_from_slice(source);
+        self.update(ctx, name, w, h, bake())
+    }
 }
@@ -9,8 +9,7 @@ impl Gui {
             let ppu = &snap.ppu;
             let mmio = &snap.mmio;
             let pixel_buffer = ppu.fetcher_pixels;
-            egui::Window::new("PPU Debug")
-                .default_pos([640.0, 50.0])
+            self.debug_window("PPU Debug", [640.0, 50.0])
                 .default_size([250.0, 500.0])
                 .collapsible(true)
                 .resizable(false)
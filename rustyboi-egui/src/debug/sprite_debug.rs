@@ -11,8 +11,7 @@ const PREVIEW_DISPLAY: f32 = 16.0;
 impl Gui {
     pub(in crate) fn render_sprite_debug_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>) {
         if let Some(snap) = debug {
-            egui::Window::new("Sprite Debug")
-                .default_pos([900.0, 50.0])
+            self.debug_window("Sprite Debug", [900.0, 50.0])
                 .default_size([400.0, 600.0])
                 .collapsible(true)
                 .resizable(true)
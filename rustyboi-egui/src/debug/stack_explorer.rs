@@ -6,8 +6,7 @@ impl Gui {
     pub(in crate) fn render_stack_explorer_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>) {
         if let Some(snap) = debug {
                 let sp = snap.cpu.sp;
-                egui::Window::new("Stack Explorer")
-                    .default_pos([220.0, 50.0])
+                self.debug_window("Stack Explorer", [220.0, 50.0])
                     .default_size([180.0, 400.0])
                     .collapsible(true)
                     .resizable(false)
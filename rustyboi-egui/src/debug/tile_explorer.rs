@@ -14,8 +14,7 @@ const TILE_DISPLAY: f32 = 20.0;
 impl Gui {
     pub(in crate) fn render_tile_explorer_panel(&mut self, ctx: &Context, debug: Option<&DebugSnapshot>) {
         if let Some(snap) = debug {
-            egui::Window::new("Tile Explorer")
-                .default_pos([1120.0, 50.0])
+            self.debug_window("Tile Explorer", [1120.0, 50.0])
                 .default_size([350.0, 500.0])
                 .collapsible(true)
                 .resizable(true)
@@ -53,11 +52,20 @@ impl Gui {
 
                     // Bake all 384 tiles into one atlas texture and draw it as a
                     // single scaled image, rather than emitting 384*64 rects.
+                    // The bake reruns only when the tile data or the shades
+                    // change, which most frames they don't.
                     let bank = if snap.cgb { self.tile_explorer_vram_bank } else { 0 };
-                    let pixels = build_tile_atlas(self, snap, bank);
-                    let tex = self
-                        .tile_atlas_tex
-                        .update(ctx, "tile_atlas", ATLAS_W, ATLAS_H, pixels);
+                    let shades = tile_shades(self, snap);
+                    let tiles = snap
+                        .vram
+                        .as_ref()
+                        .and_then(|banks| banks[bank as usize & 1].get(..TOTAL_TILES * 16))
+                        .unwrap_or(&[]);
+                    let mut source = tiles.to_vec();
+                    source.extend(shades.iter().flat_map(|c| c.to_array()));
+                    let tex = self.tile_atlas_tex.update_from(ctx, "tile_atlas", ATLAS_W, ATLAS_H, &source, || {
+                        build_tile_atlas(tiles, &shades)
+                    });
 
                     egui::ScrollArea::vertical().show(ui, |ui| {
                         let size = egui::vec2(
@@ -129,40 +137,44 @@ impl Gui {
     }
 }
 
-/// Decode all 384 VRAM tiles into a `ATLAS_W`×`ATLAS_H` row-major pixel buffer,
-/// 16 tiles per row. Same palette mapping the panel used per-pixel, done once.
-fn build_tile_atlas(gui: &Gui, snap: &DebugSnapshot, bank: u8) -> Vec<egui::Color32> {
+/// The colour each of the four colour indices shows in: the chosen CGB BG
+/// palette, or BGP's grey on DMG.
+fn tile_shades(gui: &Gui, snap: &DebugSnapshot) -> [egui::Color32; 4] {
+    std::array::from_fn(|pixel_value| {
+        let pixel_value = pixel_value as u8;
+        if snap.cgb {
+            let (r, g, b) = snap.cgb_bg_rgb(gui.tile_explorer_palette, pixel_value).unwrap_or((0, 0, 0));
+            egui::Color32::from_rgb(r, g, b)
+        } else {
+            match (snap.mmio.bgp >> (pixel_value * 2)) & 0x03 {
+                0 => egui::Color32::from_rgb(255, 255, 255),
+                1 => egui::Color32::from_rgb(170, 170, 170),
+                2 => egui::Color32::from_rgb(85, 85, 85),
+                _ => egui::Color32::from_rgb(0, 0, 0),
+            }
+        }
+    })
+}
+
+/// Decode all 384 VRAM tiles (`tiles`, 16 bytes each from 0x8000; missing
+/// bytes read as 0) into a `ATLAS_W`×`ATLAS_H` row-major pixel buffer, 16
+/// tiles per row.
+fn build_tile_atlas(tiles: &[u8], shades: &[egui::Color32; 4]) -> Vec<egui::Color32> {
     let mut pixels = vec![egui::Color32::BLACK; ATLAS_W * ATLAS_H];
-    let bgp = snap.mmio.bgp;
+    let byte = |i: usize| tiles.get(i).copied().unwrap_or(0);
     for tile_index in 0..TOTAL_TILES {
         let tile_col = tile_index % TILES_PER_ROW;
         let tile_row = tile_index / TILES_PER_ROW;
-        let tile_addr = 0x8000u16 + (tile_index as u16 * 16);
-        for y in 0..8u16 {
-            let low_byte = snap.vram_byte(bank, tile_addr + (y * 2));
-            let high_byte = snap.vram_byte(bank, tile_addr + (y * 2) + 1);
-            let px_y = tile_row * 8 + y as usize;
+        for y in 0..8usize {
+            let low_byte = byte(tile_index * 16 + y * 2);
+            let high_byte = byte(tile_index * 16 + y * 2 + 1);
+            let px_y = tile_row * 8 + y;
             for x in 0..8usize {
                 let bit = 7 - x; // Pixels are stored MSB first
                 let low_bit = (low_byte >> bit) & 1;
                 let high_bit = (high_byte >> bit) & 1;
                 let pixel_value = (high_bit << 1) | low_bit;
-
-                let color = if snap.cgb {
-                    let (r, g, b) = snap
-                        .cgb_bg_rgb(gui.tile_explorer_palette, pixel_value)
-                        .unwrap_or((0, 0, 0));
-                    egui::Color32::from_rgb(r, g, b)
-                } else {
-                    let palette_bits = (bgp >> (pixel_value * 2)) & 0x03;
-                    match palette_bits {
-                        0 => egui::Color32::from_rgb(255, 255, 255),
-                        1 => egui::Color32::from_rgb(170, 170, 170),
-                        2 => egui::Color32::from_rgb(85, 85, 85),
-                        _ => egui::Color32::from_rgb(0, 0, 0),
-                    }
-                };
-                pixels[px_y * ATLAS_W + tile_col * 8 + x] = color;
+                pixels[px_y * ATLAS_W + tile_col * 8 + x] = shades[pixel_value as usize];
             }
         }
     }
@@ -35,6 +35,20 @@ pub(crate) fn new() -> impl FileDialogBuilder {
     FileDialogBuilderImpl::new()
 }
 
+/// Ask for a folder (Settings → Save Folder). Desktop only: the other hosts
+/// keep their saves in app storage and have nothing to choose.
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub(crate) fn pick_folder() -> Option<PathBuf> {
+    rfd::FileDialog::new().pick_folder()
+}
+
+/// Ask where to save a file (Debug → Profiler's export, which writes it
+/// itself). Desktop only, like [`pick_folder`].
+#[cfg(all(feature = "profiler", not(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))))]
+pub(crate) fn pick_save_path(file_name: &str, filter: &str, extensions: &[&str]) -> Option<PathBuf> {
+    rfd::FileDialog::new().set_file_name(file_name).add_filter(filter, extensions).save_file()
+}
+
 #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
 mod sync_impl {
     use super::*;
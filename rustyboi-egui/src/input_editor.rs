@@ -0,0 +1,86 @@
+//! The TAS input editor window (Emulation → TAS Input Editor): a piano roll
+//! of the session's input timeline around the frame the machine is on. Rows
+//! are frames, columns the eight Game Boy buttons; clicking a cell emits
+//! [`GuiAction::SetTasInput`], clicking a frame number seeks to it, and
+//! Commit runs the next frame as a frame advance. The session owns the
+//! timeline and its greenzone; this only draws [`InputEditorView`].
+
+use egui::{Color32, Context, RichText};
+use rustyboi_session::input_config::gb_label;
+use rustyboi_session::{GbButton, InputEditorView};
+
+use crate::actions::GuiAction;
+use crate::ui::Gui;
+
+/// Rows already run are drawn dimmer than the ones still to come.
+const RUN_ROW_TEXT: Color32 = Color32::from_gray(150);
+
+impl Gui {
+    pub(crate) fn render_input_editor(&mut self, ctx: &Context, action: &mut Option<GuiAction>, view: &InputEditorView) {
+        let mut open = true;
+        egui::Window::new("TAS Input Editor")
+            .open(&mut open)
+            .default_pos([640.0, 50.0])
+            .default_width(340.0)
+            .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(crate::ui::PANEL_BACKGROUND))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Frame {} of {}", view.frame, view.len));
+                    if ui.small_button("⏮").on_hover_text("First frame").clicked() {
+                        *action = Some(GuiAction::SeekTasFrame(0));
+                    }
+                    if ui.small_button("◀").on_hover_text("Back one frame").clicked() {
+                        *action = Some(GuiAction::SeekTasFrame(view.frame.saturating_sub(1)));
+                    }
+                    if ui.button("Commit ▶").on_hover_text("Run the next frame with its inputs").clicked() {
+                        *action = Some(GuiAction::FrameAdvance);
+                    }
+                    if ui.small_button("⏭").on_hover_text("Last frame").clicked() {
+                        *action = Some(GuiAction::SeekTasFrame(view.len));
+                    }
+                });
+                ui.label(
+                    RichText::new(
+                        "Click a cell to hold or release a button. Changing a frame that already ran \
+                         re-plays from the nearest snapshot (●).",
+                    )
+                    .weak(),
+                );
+                ui.separator();
+
+                egui::Grid::new("tas_piano_roll").striped(true).spacing([4.0, 2.0]).show(ui, |ui| {
+                    ui.strong("Frame");
+                    for b in GbButton::ALL {
+                        ui.strong(gb_label(b));
+                    }
+                    ui.end_row();
+
+                    for (frame, row) in (view.first..).zip(&view.rows) {
+                        let snapshot = if view.snapshots.contains(&frame) { "●" } else { " " };
+                        let mut label = RichText::new(format!("{snapshot}{frame:>6}")).monospace();
+                        if frame < view.frame {
+                            label = label.color(RUN_ROW_TEXT);
+                        }
+                        if ui.selectable_label(frame == view.frame, label).on_hover_text("Go to this frame").clicked() {
+                            *action = Some(GuiAction::SeekTasFrame(frame));
+                        }
+                        for (b, &held) in GbButton::ALL.into_iter().zip(row) {
+                            let text = if held { gb_label(b) } else { "·" };
+                            if ui.selectable_label(held, text).clicked() {
+                                *action = Some(GuiAction::SetTasInput(frame, b, !held));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                ui.separator();
+                if ui.button("Save Movie…").clicked() {
+                    *action = Some(GuiAction::ExportTasMovie);
+                }
+            });
+        if !open {
+            *action = Some(GuiAction::ToggleInputEditor);
+        }
+    }
+}
@@ -3,13 +3,16 @@
 //! working [`InputConfig`] seeded from the persisted `SessionUiState.input` and
 //! emits [`GuiAction::SetInputConfig`] on every change, which `Session::apply`
 //! persists. Gamepad triggers are added via dropdowns (egui can't poll pads).
+//! A connected controller can get its own button bindings ([`PadProfile`],
+//! stored by GUID), which replace the shared pad bindings for that controller.
 
 use egui::Context;
 
-use crate::actions::{GuiAction, SessionUiState};
-use crate::ui::Gui;
+use crate::actions::{ActionKind, GuiAction, SessionUiState};
+use crate::file_dialog::{self, FileDialogBuilder};
+use crate::ui::{command_label, Gui};
 use rustyboi_session::input_config::{gb_label, HotkeyAction, InputTrigger, KeyName, PadButton};
-use rustyboi_session::{GbButton, Hotkey, InputConfig};
+use rustyboi_session::{ConnectedPad, GbButton, Hotkey, InputConfig, PadProfile};
 
 /// Map an egui key to the host-agnostic [`KeyName`] vocabulary, if representable.
 fn key_from_egui(key: egui::Key) -> Option<KeyName> {
@@ -50,9 +53,12 @@ impl Gui {
         session: &SessionUiState,
         held_pad: &std::collections::HashSet<PadButton>,
     ) {
-        // Seed the working copy from persisted state when the panel first opens.
-        if self.input_config.is_none() {
+        // Seed the working copy from persisted state when the panel first
+        // opens, and again whenever that state changes under it (an imported
+        // profile). The echo of our own edits matches the copy already.
+        if self.input_config_seen.as_ref() != Some(&session.input) {
             self.input_config = Some(session.input.clone());
+            self.input_config_seen = Some(session.input.clone());
         }
 
         // Capture keyboard input for whichever rebind/record mode is active.
@@ -80,19 +86,31 @@ impl Gui {
             .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(crate::ui::PANEL_BACKGROUND))
             .show(ctx, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
-                    changed |= self.gb_bindings_section(ui, pressed_key, pressed_pad);
+                    self.controller_picker(ui, &session.connected_pads);
+                    if self.pad_profile_guid.is_some() {
+                        changed |= self.pad_profile_section(ui, &session.connected_pads, pressed_key, pressed_pad);
+                    } else {
+                        changed |= self.gb_bindings_section(ui, pressed_key, pressed_pad);
+                    }
+                    ui.add_space(12.0);
+                    ui.separator();
+                    changed |= self.stick_section(ui);
                     ui.add_space(12.0);
                     ui.separator();
                     changed |= self.hotkeys_section(ui, &keys_down, held_pad);
                     ui.add_space(12.0);
                     ui.separator();
-                    if ui.button("Reset to Defaults").clicked() {
-                        self.input_config = Some(InputConfig::default());
-                        self.rebinding_gb = None;
-                        self.recording_chord = None;
-                        self.recorded_chord.clear();
-                        changed = true;
-                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Reset to Defaults").clicked() {
+                            self.input_config = Some(InputConfig::default());
+                            self.rebinding_gb = None;
+                            self.pad_profile_guid = None;
+                            self.recording_chord = None;
+                            self.recorded_chord.clear();
+                            changed = true;
+                        }
+                        self.profile_buttons(ui, action);
+                    });
                 });
             });
 
@@ -102,6 +120,27 @@ impl Gui {
             }
     }
 
+    /// Export the map as a portable profile file, or import one picked from
+    /// disk (it replaces the map; the working copy reseeds once it lands).
+    fn profile_buttons(&mut self, ui: &mut egui::Ui, action: &mut Option<GuiAction>) {
+        if ui.button(command_label(ActionKind::ExportInputProfile)).clicked() {
+            *action = Some(GuiAction::ExportInputProfile);
+        }
+        if ui.button(command_label(ActionKind::ImportInputProfile)).clicked() {
+            let dialog = file_dialog::new()
+                .add_filter("Controls Profile", &["json"])
+                .add_filter("All Files", &["*"]);
+            let holder = std::sync::Arc::clone(&self.pending_dialog_result);
+            dialog.pick_file(move |file_data| {
+                if let Some(file_data) = file_data
+                    && let Ok(mut pending) = holder.lock()
+                {
+                    *pending = Some(GuiAction::ImportInputProfile(file_data));
+                }
+            });
+        }
+    }
+
     fn gb_bindings_section(
         &mut self,
         ui: &mut egui::Ui,
@@ -133,11 +172,7 @@ impl Gui {
                 (None, None) => None,
             };
             if let Some(t) = trigger {
-                if let Some((_, triggers)) = cfg.gb_bindings.iter_mut().find(|(b, _)| *b == btn)
-                    && !triggers.contains(&t) {
-                        triggers.push(t);
-                        changed = true;
-                    }
+                changed |= cfg.add_binding(btn, t);
                 self.rebinding_gb = None;
             }
         }
@@ -190,18 +225,20 @@ impl Gui {
                     ui.end_row();
                 }
             });
+        if ui
+            .button("Also use WASD for the d-pad")
+            .on_hover_text("Bind W, A, S and D next to the arrow keys")
+            .clicked()
+        {
+            changed |= cfg.add_wasd_dpad();
+        }
 
         if let Some(gb) = start_capture {
             self.rebinding_gb = Some(gb);
         }
-        if let Some((gb, p)) = add_pad
-            && let Some((_, tr)) = cfg.gb_bindings.iter_mut().find(|(b, _)| *b == gb) {
-                let t = InputTrigger::Pad(p);
-                if !tr.contains(&t) {
-                    tr.push(t);
-                    changed = true;
-                }
-            }
+        if let Some((gb, p)) = add_pad {
+            changed |= cfg.add_binding(gb, InputTrigger::Pad(p));
+        }
         if let Some((gb, i)) = remove
             && let Some((_, tr)) = cfg.gb_bindings.iter_mut().find(|(b, _)| *b == gb)
                 && i < tr.len() {
@@ -211,6 +248,195 @@ impl Gui {
         changed
     }
 
+    /// Left stick deadzone and diagonal bias, for the `L-Stick` bindings.
+    fn stick_section(&mut self, ui: &mut egui::Ui) -> bool {
+        let Some(cfg) = self.input_config.as_mut() else { return false };
+        let stick = &mut cfg.left_stick;
+        ui.heading("Left Stick");
+        ui.label(
+            egui::RichText::new("How far the stick must move, and how easily a push counts as a diagonal.")
+                .weak(),
+        );
+        let mut changed = false;
+        egui::Grid::new("left_stick").num_columns(2).spacing([12.0, 8.0]).show(ui, |ui| {
+            ui.label("Deadzone");
+            changed |= ui
+                .add(egui::Slider::new(&mut stick.deadzone, 0.05..=0.95).fixed_decimals(2))
+                .changed();
+            ui.end_row();
+            ui.label("Diagonals");
+            changed |= ui
+                .add(egui::Slider::new(&mut stick.diagonal_bias, 0.0..=1.0).fixed_decimals(2))
+                .on_hover_text("0: straight directions only, 0.5: even eight-way, 1: any off-axis push")
+                .changed();
+            ui.end_row();
+        });
+        changed
+    }
+
+    /// Pick whose button bindings to edit: the shared set, or one connected
+    /// (or previously customized) controller. Hidden when there are none.
+    fn controller_picker(&mut self, ui: &mut egui::Ui, connected: &[ConnectedPad]) {
+        let Some(cfg) = self.input_config.as_ref() else { return };
+        let mut choices: Vec<ConnectedPad> = connected.to_vec();
+        for profile in &cfg.pad_profiles {
+            if !choices.iter().any(|p| p.guid == profile.guid) {
+                choices.push(ConnectedPad { guid: profile.guid.clone(), name: format!("{} (not connected)", profile.name) });
+            }
+        }
+        if choices.is_empty() {
+            self.pad_profile_guid = None;
+            return;
+        }
+        let selected = self
+            .pad_profile_guid
+            .as_ref()
+            .and_then(|guid| choices.iter().find(|p| &p.guid == guid))
+            .map_or("All controllers", |p| p.name.as_str())
+            .to_owned();
+        let before = self.pad_profile_guid.clone();
+        ui.horizontal(|ui| {
+            ui.label("Bindings for");
+            egui::ComboBox::from_id_salt("pad_profile")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.pad_profile_guid, None, "All controllers");
+                    for pad in &choices {
+                        let label = if cfg.pad_profile(&pad.guid).is_some() {
+                            format!("{} ✎", pad.name)
+                        } else {
+                            pad.name.clone()
+                        };
+                        ui.selectable_value(&mut self.pad_profile_guid, Some(pad.guid.clone()), label);
+                    }
+                });
+        });
+        if self.pad_profile_guid != before {
+            self.rebinding_gb = None;
+        }
+        ui.add_space(6.0);
+    }
+
+    /// The selected controller's own bindings: offer to customize it, or
+    /// edit its profile with the same press-to-bind capture as the shared set.
+    fn pad_profile_section(
+        &mut self,
+        ui: &mut egui::Ui,
+        connected: &[ConnectedPad],
+        pressed_key: Option<KeyName>,
+        pressed_pad: Option<PadButton>,
+    ) -> bool {
+        let Some(guid) = self.pad_profile_guid.clone() else { return false };
+        let Some(cfg) = self.input_config.as_mut() else { return false };
+        let mut changed = false;
+        ui.heading("Buttons");
+        let Some(index) = cfg.pad_profiles.iter().position(|p| p.guid == guid) else {
+            ui.label(egui::RichText::new("This controller uses the shared bindings.").weak());
+            if let Some(pad) = connected.iter().find(|p| p.guid == guid)
+                && ui.button("Customize for this controller").clicked() {
+                    let profile = PadProfile::from_shared(pad, cfg);
+                    cfg.pad_profiles.push(profile);
+                    changed = true;
+                }
+            return changed;
+        };
+        ui.label(
+            egui::RichText::new(
+                "These replace the shared controller bindings for this controller only. \
+                 Click a binding to remove it.",
+            )
+            .weak(),
+        );
+        ui.add_space(6.0);
+
+        if let Some(btn) = self.rebinding_gb {
+            if pressed_key == Some(KeyName::Escape) {
+                self.rebinding_gb = None;
+            } else if let Some(p) = pressed_pad {
+                if let Some((_, pads)) = cfg.pad_profiles[index].bindings.iter_mut().find(|(b, _)| *b == btn)
+                    && !pads.contains(&p) {
+                        pads.push(p);
+                        changed = true;
+                    }
+                self.rebinding_gb = None;
+            }
+        }
+
+        let rebinding = self.rebinding_gb;
+        let mut add: Option<(GbButton, PadButton)> = None;
+        let mut remove: Option<(GbButton, usize)> = None;
+        let mut start_capture: Option<GbButton> = None;
+        let profile = &cfg.pad_profiles[index];
+        egui::Grid::new("pad_profile_binds")
+            .num_columns(2)
+            .spacing([12.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for gb in GbButton::ALL {
+                    ui.strong(gb_label(gb));
+                    ui.horizontal_wrapped(|ui| {
+                        let pads = profile.bindings.iter().find(|(b, _)| *b == gb).map(|(_, p)| p.as_slice()).unwrap_or_default();
+                        for (i, p) in pads.iter().enumerate() {
+                            if ui.small_button(p.label()).on_hover_text("Remove").clicked() {
+                                remove = Some((gb, i));
+                            }
+                        }
+                        if rebinding == Some(gb) {
+                            ui.label(
+                                egui::RichText::new("press a button…")
+                                    .italics()
+                                    .color(egui::Color32::LIGHT_BLUE),
+                            );
+                        } else {
+                            ui.menu_button("Add…", |ui| {
+                                if ui.button("Press a button").clicked() {
+                                    start_capture = Some(gb);
+                                    ui.close();
+                                }
+                                ui.separator();
+                                if let Some(p) = pad_menu(ui) {
+                                    add = Some((gb, p));
+                                    ui.close();
+                                }
+                            });
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        if let Some(gb) = start_capture {
+            self.rebinding_gb = Some(gb);
+        }
+        let bindings = &mut cfg.pad_profiles[index].bindings;
+        if let Some((gb, p)) = add {
+            match bindings.iter_mut().find(|(b, _)| *b == gb) {
+                Some((_, pads)) if pads.contains(&p) => {}
+                Some((_, pads)) => {
+                    pads.push(p);
+                    changed = true;
+                }
+                None => {
+                    bindings.push((gb, vec![p]));
+                    changed = true;
+                }
+            }
+        }
+        if let Some((gb, i)) = remove
+            && let Some((_, pads)) = bindings.iter_mut().find(|(b, _)| *b == gb)
+                && i < pads.len() {
+                    pads.remove(i);
+                    changed = true;
+                }
+        ui.add_space(6.0);
+        if ui.button("Use the shared bindings").clicked() {
+            cfg.pad_profiles.remove(index);
+            self.rebinding_gb = None;
+            changed = true;
+        }
+        changed
+    }
+
     fn hotkeys_section(
         &mut self,
         ui: &mut egui::Ui,
@@ -359,8 +585,9 @@ fn chord_label(chord: &[InputTrigger]) -> String {
         .join(" + ")
 }
 
-/// Action dropdown, including Turbo(button) variants. `id` disambiguates the
-/// egui widget id (usize::MAX = the "new hotkey" staging row).
+/// Action dropdown, including Turbo(button), state-slot and window-scale
+/// variants. `id` disambiguates the egui widget id (usize::MAX = the "new
+/// hotkey" staging row).
 fn action_combo(ui: &mut egui::Ui, id: usize, action: &mut HotkeyAction) -> bool {
     let before = *action;
     egui::ComboBox::from_id_salt(("hotkey_action", id))
@@ -373,6 +600,15 @@ fn action_combo(ui: &mut egui::Ui, id: usize, action: &mut HotkeyAction) -> bool
                 let a = HotkeyAction::Turbo(b);
                 ui.selectable_value(action, a, a.label());
             }
+            for n in 0..10 {
+                for a in [HotkeyAction::SaveSlot(n), HotkeyAction::LoadSlot(n)] {
+                    ui.selectable_value(action, a, a.label());
+                }
+            }
+            for n in crate::actions::WINDOW_SCALES {
+                let a = HotkeyAction::WindowScale(n);
+                ui.selectable_value(action, a, a.label());
+            }
         });
     *action != before
 }
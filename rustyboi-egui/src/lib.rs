@@ -5,10 +5,13 @@ pub mod android_bridge;
 pub mod ios_bridge;
 mod debug;
 mod file_dialog;
+mod input_editor;
 mod keybind_settings;
 #[cfg(any(target_os = "android", test))]
 pub mod library;
+#[cfg(feature = "profiler")]
+pub mod profiler;
 mod touch_controls;
 mod ui;
 
-pub use ui::{CentralRect, Gui, UiOutput};
+pub use ui::{AudioStats, CentralRect, Gui, UiOutput};
@@ -0,0 +1,321 @@
+//! Debug → Profiler (the `profiler` feature, native desktop): a puffin view
+//! of the spans the emulator opens with `rustyboi_core_lib::profile_span!`,
+//! for "it's slow on my machine" reports.
+//!
+//! The emulation thread shows the session's frame (`run_frame`, `run_ahead`)
+//! around `run_until_frame`, under which sit the PPU's frame output and, with
+//! the scanline renderer, each `ppu_scanline`. The dot renderer and the APU
+//! are clocked inline with every memory access, so they have no span of their
+//! own; their time is the rest of `run_until_frame`, except the APU's
+//! per-instruction `apu_samples`, recorded on request because thousands land
+//! in every frame. The event-loop thread shows `ui_layout` and `render`.
+//!
+//! [`install`] routes those `tracing` spans into puffin scopes; the platform
+//! calls it once at startup and [`new_frame`] once per tick. Scopes are only
+//! recorded while the window is open and not paused. The window tabulates
+//! the recent frames per scope and exports what it holds as a `.puffin` file,
+//! which `puffin_viewer` opens as a flamegraph.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use egui::Context;
+use puffin::{FrameView, GlobalFrameView, Reader, ScopeCollection, ScopeDetails, ScopeId, Stream, ThreadProfiler};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use crate::ui::{Gui, PANEL_BACKGROUND};
+
+/// Frames the window holds (and exports): about five seconds.
+const MAX_FRAMES: usize = 300;
+/// Frames the table averages over.
+const TABLE_FRAMES: usize = 60;
+/// New frames between table rebuilds, so a heavy capture isn't re-read
+/// every repaint.
+const TABLE_EVERY: u64 = 30;
+
+/// Whether `fine` (per-instruction) spans are recorded too.
+static FINE: AtomicBool = AtomicBool::new(false);
+
+/// Route the emulator's profiler spans into puffin. Call once, at startup;
+/// it does nothing if a global `tracing` subscriber is already set.
+pub fn install() {
+    let _ = tracing::subscriber::set_global_default(PuffinBridge::default());
+}
+
+/// Close the current profiler frame: the scopes finished since the last call
+/// become one frame in the window. Call once per tick, from the event loop.
+pub fn new_frame() {
+    puffin::GlobalProfiler::lock().new_frame();
+}
+
+/// A `tracing` subscriber that opens and closes a puffin scope as each span
+/// is entered and exited, on the thread's own profiler. Every span from one
+/// callsite shares a puffin scope, so a span's id is its scope id.
+#[derive(Default)]
+struct PuffinBridge {
+    scopes: Mutex<HashMap<tracing::callsite::Identifier, ScopeId>>,
+}
+
+thread_local! {
+    /// Stream offsets of the scopes open on this thread, innermost last;
+    /// `None` for a span entered while recording was off.
+    static OPEN: RefCell<Vec<Option<usize>>> = const { RefCell::new(Vec::new()) };
+}
+
+impl Subscriber for PuffinBridge {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        // Recording comes and goes with the window, so ask `enabled` each time.
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_span()
+            && puffin::are_scopes_on()
+            && (*metadata.level() != Level::TRACE || FINE.load(Ordering::Relaxed))
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let metadata = span.metadata();
+        let mut scopes = self.scopes.lock().unwrap_or_else(|e| e.into_inner());
+        let scope = *scopes.entry(metadata.callsite()).or_insert_with(|| {
+            let details = ScopeDetails::from_scope_name(metadata.name())
+                .with_file(metadata.file().unwrap_or_default())
+                .with_line_nr(metadata.line().unwrap_or_default());
+            puffin::GlobalProfiler::lock().register_user_scopes(&[details])[0]
+        });
+        Id::from_non_zero_u64(scope.0.into())
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        let offset = u32::try_from(span.into_u64())
+            .ok()
+            .and_then(NonZeroU32::new)
+            .filter(|_| puffin::are_scopes_on())
+            .map(|id| ThreadProfiler::call(|tp| tp.begin_scope(ScopeId(id), "")));
+        OPEN.with_borrow_mut(|open| open.push(offset));
+    }
+
+    fn exit(&self, _: &Id) {
+        if let Some(Some(offset)) = OPEN.with_borrow_mut(Vec::pop) {
+            ThreadProfiler::call(|tp| tp.end_scope(offset));
+        }
+    }
+}
+
+/// The open window's capture and the table drawn from it.
+pub(crate) struct Profiler {
+    view: GlobalFrameView,
+    paused: bool,
+    rows: Vec<Row>,
+    /// Frames the table covers.
+    table_frames: usize,
+    /// The newest frame the table has seen.
+    table_index: Option<u64>,
+    /// How the last export went.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    export_status: Option<String>,
+}
+
+/// One scope, by its path from the thread down, over the tabulated frames.
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    depth: usize,
+    name: String,
+    calls: u64,
+    total_ns: i64,
+    max_ns: i64,
+}
+
+impl Row {
+    /// An empty row for the last scope of `path`.
+    fn new(path: &[String]) -> Self {
+        Self { depth: path.len() - 1, name: path[path.len() - 1].clone(), calls: 0, total_ns: 0, max_ns: 0 }
+    }
+}
+
+impl Profiler {
+    fn new() -> Self {
+        let view = GlobalFrameView::default();
+        view.lock().set_max_recent(MAX_FRAMES);
+        Self {
+            view,
+            paused: false,
+            rows: Vec::new(),
+            table_frames: 0,
+            table_index: None,
+            #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+            export_status: None,
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.paused, "Pause");
+            let mut fine = FINE.load(Ordering::Relaxed);
+            if ui
+                .checkbox(&mut fine, "Per-instruction APU")
+                .on_hover_text("Record apu_samples too: thousands of scopes a frame, which slows emulation")
+                .changed()
+            {
+                FINE.store(fine, Ordering::Relaxed);
+            }
+            #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+            if ui.button("Save .puffin…").on_hover_text("Open in puffin_viewer for a flamegraph").clicked() {
+                self.export_status = self.export();
+            }
+        });
+        #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+        if let Some(status) = &self.export_status {
+            ui.small(status);
+        }
+        ui.separator();
+
+        let latest = self.view.lock().latest_frame().map(|f| f.frame_index());
+        if !self.paused && latest.is_some_and(|i| self.table_index.is_none_or(|t| i >= t + TABLE_EVERY)) {
+            let view = self.view.lock();
+            (self.table_frames, self.rows) = tabulate(&view, TABLE_FRAMES);
+            self.table_index = latest;
+        }
+        if self.rows.is_empty() {
+            ui.label("No frames yet.");
+            return;
+        }
+        ui.small(format!("Per frame, over the last {} frames", self.table_frames));
+        let frames = self.table_frames.max(1) as f64;
+        egui::Grid::new("profiler_scopes").striped(true).num_columns(4).show(ui, |ui| {
+            ui.strong("Scope");
+            ui.strong("Calls");
+            ui.strong("ms");
+            ui.strong("Max ms");
+            ui.end_row();
+            for row in &self.rows {
+                if row.depth == 0 {
+                    ui.label(egui::RichText::new(format!("{} thread", row.name)).italics());
+                    ui.end_row();
+                    continue;
+                }
+                ui.monospace(format!("{}{}", "  ".repeat(row.depth - 1), row.name));
+                ui.monospace(format!("{:.1}", row.calls as f64 / frames));
+                ui.monospace(format!("{:.3}", row.total_ns as f64 / frames / 1e6));
+                ui.monospace(format!("{:.3}", row.max_ns as f64 / 1e6));
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Ask where to save the capture, then write it. `None` when cancelled.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    fn export(&self) -> Option<String> {
+        let path = crate::file_dialog::pick_save_path("rustyboi.puffin", "Puffin profile", &["puffin"])?;
+        let written = std::fs::File::create(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|mut file| self.view.lock().write(&mut file).map_err(|e| e.to_string()));
+        Some(match written {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(e) => format!("Could not save {}: {e}", path.display()),
+        })
+    }
+}
+
+/// Sum every scope in the newest `frames` frames by its path (thread name,
+/// then each enclosing scope), in tree order, each thread heading its own
+/// scopes. Returns the frames read too.
+fn tabulate(view: &FrameView, frames: usize) -> (usize, Vec<Row>) {
+    let mut stats: BTreeMap<Vec<String>, Row> = BTreeMap::new();
+    let mut read = 0;
+    for frame in view.latest_frames(frames) {
+        let Ok(frame) = frame.unpacked() else { continue };
+        read += 1;
+        for (thread, stream) in &frame.thread_streams {
+            let mut path = vec![thread.name.clone()];
+            stats.entry(path.clone()).or_insert_with(|| Row::new(&path));
+            let _ = tally(view.scope_collection(), &stream.stream, Reader::from_start(&stream.stream), &mut path, &mut stats);
+        }
+    }
+    (read, stats.into_values().collect())
+}
+
+/// Add the scopes `reader` yields, and their children, to `stats` under `path`.
+fn tally(
+    names: &ScopeCollection,
+    stream: &Stream,
+    reader: Reader<'_>,
+    path: &mut Vec<String>,
+    stats: &mut BTreeMap<Vec<String>, Row>,
+) -> puffin::Result<()> {
+    for scope in reader {
+        let scope = scope?;
+        let name = names.fetch_by_id(&scope.id).map_or_else(|| "?".to_owned(), |d| d.name().to_string());
+        path.push(name);
+        let row = stats.entry(path.clone()).or_insert_with(|| Row::new(path));
+        row.calls += 1;
+        row.total_ns += scope.record.duration_ns;
+        row.max_ns = row.max_ns.max(scope.record.duration_ns);
+        if scope.child_begin_position < scope.child_end_position {
+            tally(names, stream, Reader::with_offset(stream, scope.child_begin_position)?, path, stats)?;
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+impl Gui {
+    /// Debug → Profiler. The capture starts when the window opens and is
+    /// dropped when it closes, so a closed profiler records nothing.
+    pub(crate) fn render_profiler_panel(&mut self, ctx: &Context) {
+        if !self.show_profiler {
+            if self.profiler.take().is_some() {
+                puffin::set_scopes_on(false);
+            }
+            return;
+        }
+        let window = self
+            .debug_window("Profiler", [560.0, 50.0])
+            .default_width(360.0)
+            .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(PANEL_BACKGROUND));
+        let profiler = self.profiler.get_or_insert_with(Profiler::new);
+        puffin::set_scopes_on(!profiler.paused);
+        window.show(ctx, |ui| profiler.ui(ui));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_become_scopes_tabulated_by_path() {
+        let view = GlobalFrameView::default();
+        puffin::set_scopes_on(true);
+        tracing::subscriber::with_default(PuffinBridge::default(), || {
+            for _ in 0..2 {
+                let _frame = tracing::debug_span!("frame").entered();
+                for _ in 0..3 {
+                    let _line = tracing::debug_span!("line").entered();
+                }
+                // `fine` spans stay out until asked for.
+                let _sample = tracing::trace_span!("sample").entered();
+            }
+        });
+        new_frame();
+        puffin::set_scopes_on(false);
+
+        let (frames, rows) = tabulate(&view.lock(), TABLE_FRAMES);
+        assert_eq!(frames, 1);
+        let thread = std::thread::current().name().unwrap_or_default().to_owned();
+        let calls: Vec<_> = rows.iter().map(|r| (r.depth, r.name.as_str(), r.calls)).collect();
+        assert_eq!(calls, [(0, thread.as_str(), 0), (1, "frame", 2), (2, "line", 6)]);
+        assert!(rows[1].total_ns >= rows[2].total_ns && rows[1].max_ns > 0);
+    }
+}
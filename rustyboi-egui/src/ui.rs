@@ -6,12 +6,14 @@ use std::env;
 use std::sync::{Arc, Mutex};
 use egui::Context;
 use crate::actions::{
-    ActionKind, ColorCorrection, GuiAction, LcdEffect, ScalingMode, SessionUiState,
-    TextureFilter, COMMANDS,
+    ActionKind, AspectRatio, ChannelMix, ColorCorrection, GuiAction, LcdEffect, ScalingMode,
+    SessionUiState, TextureFilter, COMMANDS,
 };
 // Hardware / palette pickers live only in the desktop Settings menu bar.
 #[cfg(not(mobile))]
 use crate::actions::{GbcDmgPalette, HardwareChoice, DmgPaletteChoice, SgbPaletteChoice};
+#[cfg(not(mobile))]
+use rustyboi_session::HotkeyAction;
 use crate::file_dialog::{self, FileDialogBuilder};
 #[cfg(target_os = "android")]
 use crate::library::LibraryPanel;
@@ -23,7 +25,7 @@ pub(crate) const PANEL_BACKGROUND: egui::Color32 = egui::Color32::from_rgba_prem
 /// single edit there re-labels every frontend. Falls back to the debug name if a
 /// kind is somehow absent (it never is — `menu_labels_cover_every_command`
 /// pins that).
-fn command_label(kind: ActionKind) -> &'static str {
+pub(crate) fn command_label(kind: ActionKind) -> &'static str {
     COMMANDS
         .iter()
         .find(|c| c.action_kind == kind)
@@ -31,6 +33,23 @@ fn command_label(kind: ActionKind) -> &'static str {
         .unwrap_or("?")
 }
 
+/// `label` followed by the key chord currently bound to `hotkey`, if there is
+/// one ("Quicksave (F5)"), so the menus follow the user's bindings.
+#[cfg(not(mobile))]
+fn with_shortcut(label: &str, session: &SessionUiState, hotkey: HotkeyAction) -> String {
+    match session.input.shortcut(hotkey) {
+        Some(keys) => format!("{label} ({keys})"),
+        None => label.to_string(),
+    }
+}
+
+/// When `slot` was last written, formatted for its menu entry, or `None` for an
+/// empty slot or one saved without a timestamp.
+fn slot_time(times: &[(u32, u64)], slot: u32) -> Option<String> {
+    let &(_, at) = times.iter().find(|&&(s, _)| s == slot)?;
+    rustyboi_session::Session::slot_time_label(at)
+}
+
 /// A File → Import submenu button: opens a file picker filtered to
 /// `filter_name`/`ext`, and stores the picked file wrapped by `make_action`
 /// (e.g. `GuiAction::ImportBatterySave`) into `pending` for the host to apply.
@@ -43,12 +62,12 @@ fn import_menu_button(
     pending: &Arc<Mutex<Option<GuiAction>>>,
     label: &str,
     filter_name: &str,
-    ext: &str,
+    exts: &[&str],
     make_action: fn(crate::actions::FileData) -> GuiAction,
 ) {
     if ui.button(label).clicked() {
         let dialog = file_dialog::new()
-            .add_filter(filter_name, &[ext])
+            .add_filter(filter_name, exts)
             .add_filter("All Files", &["*"]);
         let holder = Arc::clone(pending);
         dialog.pick_file(move |file_data| {
@@ -62,6 +81,89 @@ fn import_menu_button(
     }
 }
 
+/// The auto-save resume prompt shown when a ROM with an exit snapshot loads:
+/// Resume loads it ([`GuiAction::ResumeAutoSave`]); Start Fresh or closing the
+/// window keeps the fresh boot ([`GuiAction::DismissResume`]).
+fn render_resume_offer(ctx: &Context, action: &mut Option<GuiAction>, saved_at: u64) {
+    let mut open = true;
+    egui::Window::new("Resume?")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(PANEL_BACKGROUND))
+        .show(ctx, |ui| {
+            match rustyboi_session::Session::slot_time_label(saved_at) {
+                Some(when) => ui.label(format!("Resume where you left off ({when} UTC)?")),
+                None => ui.label("Resume where you left off?"),
+            };
+            ui.horizontal(|ui| {
+                if ui.button("Resume").clicked() {
+                    *action = Some(GuiAction::ResumeAutoSave);
+                }
+                if ui.button("Start Fresh").clicked() {
+                    *action = Some(GuiAction::DismissResume);
+                }
+            });
+        });
+    if !open {
+        *action = Some(GuiAction::DismissResume);
+    }
+}
+
+/// A radio label for an audio output rate ("44.1 kHz").
+fn sample_rate_label(rate: u32) -> String {
+    format!("{:.1} kHz", f64::from(rate) / 1000.0)
+}
+
+/// A radio label for an audio device buffer size (`0` = the host's choice).
+fn audio_buffer_label(pairs: u32) -> String {
+    match pairs {
+        0 => "Auto (host default)".into(),
+        n => format!("{n} samples"),
+    }
+}
+
+/// The Settings → Audio Output Device choices: "System Default" plus every
+/// device the platform enumerated, and the preferred one marked unplugged when
+/// it is missing. Shared by the desktop menu bar and the mobile overlay;
+/// returns the new choice when a different one was picked.
+fn audio_device_radios(ui: &mut egui::Ui, session: &SessionUiState) -> Option<Option<String>> {
+    let mut picked = None;
+    if ui.radio(session.audio_device.is_none(), "System Default").clicked() && session.audio_device.is_some() {
+        picked = Some(None);
+    }
+    let missing = session.audio_device.as_ref().filter(|name| !session.audio_devices.contains(name));
+    for name in session.audio_devices.iter().chain(missing) {
+        let selected = session.audio_device.as_ref() == Some(name);
+        let label = if missing == Some(name) { format!("{name} (unplugged)") } else { name.clone() };
+        if ui.radio(selected, label).clicked() && !selected {
+            picked = Some(Some(name.clone()));
+        }
+    }
+    picked
+}
+
+/// The Settings → Audio Channels table: a Mute and a Solo checkbox per APU
+/// channel. Shared by the desktop menu bar and the mobile overlay; returns the
+/// edited mix when a box was toggled.
+fn channel_mix_grid(ui: &mut egui::Ui, mut mix: ChannelMix) -> Option<ChannelMix> {
+    let mut changed = false;
+    egui::Grid::new("channel_mix").num_columns(3).show(ui, |ui| {
+        ui.label("");
+        ui.label("Mute");
+        ui.label("Solo");
+        ui.end_row();
+        for (i, name) in ChannelMix::NAMES.iter().enumerate() {
+            ui.label(*name);
+            changed |= ui.checkbox(&mut mix.muted[i], "").clicked();
+            changed |= ui.checkbox(&mut mix.soloed[i], "").clicked();
+            ui.end_row();
+        }
+    });
+    changed.then_some(mix)
+}
+
 /// Render a single toggle row in the mobile menu overlay. Behaves like
 /// `ui.checkbox(...)` but lays out as a full-width row with a check
 /// glyph on the right so it matches the rest of the touch-sized rows.
@@ -87,11 +189,11 @@ fn mobile_import_row(
     pending: &Arc<Mutex<Option<GuiAction>>>,
     label: &str,
     filter_name: &str,
-    ext: &str,
+    exts: &[&str],
     make_action: fn(crate::actions::FileData) -> GuiAction,
 ) -> bool {
     if ui.add(egui::Button::new(label).min_size(size)).clicked() {
-        let dialog = file_dialog::new().add_filter(filter_name, &[ext]);
+        let dialog = file_dialog::new().add_filter(filter_name, exts);
         let holder = Arc::clone(pending);
         dialog.pick_file(move |file_data| {
             if let Some(file_data) = file_data
@@ -117,6 +219,17 @@ pub struct CentralRect {
     pub height: f32,
 }
 
+/// The audio device's health, measured by the platform that owns it and shown
+/// under the FPS overlay.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioStats {
+    /// Output latency: the queued ring plus the device buffer, in milliseconds.
+    pub latency_ms: f32,
+    /// Device callbacks that found the ring empty and played silence, since
+    /// the device opened.
+    pub underruns: u64,
+}
+
 /// Result of laying out one egui frame.
 pub struct UiOutput {
     pub action: Option<GuiAction>,
@@ -124,22 +237,37 @@ pub struct UiOutput {
     pub central_rect: CentralRect,
 }
 
+/// How long an on-screen notice stays up, in seconds.
+const OSD_SECONDS: f32 = 2.0;
+/// The closing stretch of [`OSD_SECONDS`] over which it fades out.
+const OSD_FADE_SECONDS: f32 = 0.75;
+
 pub struct Gui {
     error_message: Option<String>,
     #[cfg(not(target_os = "android"))]
     status_message: Option<String>,
-    show_cpu_registers: bool,
-    show_stack_explorer: bool,
-    show_memory_explorer: bool,
-    show_ppu_debug: bool,
-    show_sprite_debug: bool,
-    show_palette_explorer: bool,
-    show_tile_explorer: bool,
-    show_cartridge_info: bool,
+    pub(super) show_cpu_registers: bool,
+    pub(super) show_stack_explorer: bool,
+    pub(super) show_memory_explorer: bool,
+    pub(super) show_ppu_debug: bool,
+    pub(super) show_apu_debug: bool,
+    pub(super) show_sprite_debug: bool,
+    pub(super) show_palette_explorer: bool,
+    pub(super) show_tile_explorer: bool,
+    pub(super) show_cartridge_info: bool,
     show_keybind_settings: bool,
-    show_breakpoint_panel: bool,
+    pub(super) show_breakpoint_panel: bool,
+    #[cfg(feature = "profiler")]
+    pub(super) show_profiler: bool,
+    #[cfg(feature = "profiler")]
+    pub(super) profiler: Option<crate::profiler::Profiler>,
     show_cheats_panel: bool,
+    show_battery_manager: bool,
+    /// The battery manager's Erase was clicked once and awaits confirmation.
+    battery_erase_armed: bool,
     cheat_code_input: String,
+    /// Hex colours typed into the DMG Palette menu's add-palette field.
+    palette_hex_input: String,
     /// Which fetched-cheat rows (indices into `SessionUiState.fetched_cheats`) the
     /// user has ticked in the cheat-DB picker, awaiting confirmation.
     fetched_cheat_selected: std::collections::HashSet<usize>,
@@ -162,16 +290,34 @@ pub struct Gui {
     // of thousands of per-pixel rects (see `debug::pixels`).
     pub(super) tile_atlas_tex: crate::debug::pixels::PixelTexture,
     pub(super) sprite_atlas_tex: crate::debug::pixels::PixelTexture,
+    // Per-slot save-state thumbnails for the Emulation menu's slot lists.
+    pub(super) slot_thumb_tex: std::collections::HashMap<u32, crate::debug::pixels::PixelTexture>,
+    // The second Game Boy on the link cable: its latest frame as handed in by
+    // the frontend (`None` while unplugged) and the texture it is drawn from.
+    link_partner_pixels: Option<Vec<egui::Color32>>,
+    link_partner_tex: crate::debug::pixels::PixelTexture,
+    // Debug window arrangement (see `debug::layout`): the layout last restored
+    // or reported, whether the saved one has been restored yet, and windows to
+    // move on the next frame they are drawn.
+    pub(super) debug_layout: rustyboi_session::DebugLayout,
+    pub(super) debug_layout_restored: bool,
+    pub(super) debug_layout_moves: Vec<(&'static str, egui::Pos2)>,
     // Keybind editor working state. `input_config` is the live edited copy
     // (seeded from the persisted `SessionUiState.input` when the panel opens,
-    // `None` while closed); the rest track in-progress rebind/record UI.
+    // `None` while closed) and `input_config_seen` the persisted map it was
+    // last seeded from, so an imported profile reseeds it; the rest track
+    // in-progress rebind/record UI.
     pub(super) input_config: Option<rustyboi_session::InputConfig>,
+    pub(super) input_config_seen: Option<rustyboi_session::InputConfig>,
     pub(super) rebinding_gb: Option<rustyboi_session::GbButton>,
+    /// The controller whose own bindings are being edited (by GUID); `None`
+    /// edits the shared bindings.
+    pub(super) pad_profile_guid: Option<String>,
     pub(super) recording_chord: Option<usize>,
     pub(super) recorded_chord: Vec<rustyboi_session::InputTrigger>,
     pub(super) new_hotkey_action: rustyboi_session::HotkeyAction,
     // File dialog result tracking
-    pending_dialog_result: Arc<Mutex<Option<GuiAction>>>,
+    pub(super) pending_dialog_result: Arc<Mutex<Option<GuiAction>>>,
     // On-screen Game Boy controls state (mirrors winit `key_held` on desktop).
     // Mutated by the touch panel each frame; latest snapshot read by the
     // platform loop and OR'd with keyboard input.
@@ -193,6 +339,9 @@ pub struct Gui {
     /// vanish mid-interaction.
     #[cfg(not(mobile))]
     menu_open_last_frame: bool,
+    /// The on-screen notice being shown: its id and when it appeared (egui
+    /// time, seconds), so a new notice restarts the fade.
+    osd_shown: Option<(u32, f64)>,
 }
 
 impl Default for Gui {
@@ -222,14 +371,22 @@ impl Gui {
             show_stack_explorer: false,
             show_memory_explorer: false,
             show_ppu_debug: false,
+            show_apu_debug: false,
             show_sprite_debug: false,
             show_palette_explorer: false,
             show_tile_explorer: false,
             show_cartridge_info: false,
             show_keybind_settings: false,
             show_breakpoint_panel: false,
+            #[cfg(feature = "profiler")]
+            show_profiler: false,
+            #[cfg(feature = "profiler")]
+            profiler: None,
             show_cheats_panel: false,
+            show_battery_manager: false,
+            battery_erase_armed: false,
             cheat_code_input: String::new(),
+            palette_hex_input: String::new(),
             fetched_cheat_selected: std::collections::HashSet::new(),
             breakpoint_address_input: String::from("0000"),
             stack_scroll_offset: 0,
@@ -244,8 +401,16 @@ impl Gui {
             tile_explorer_palette: 0,
             tile_atlas_tex: crate::debug::pixels::PixelTexture::default(),
             sprite_atlas_tex: crate::debug::pixels::PixelTexture::default(),
+            slot_thumb_tex: std::collections::HashMap::new(),
+            link_partner_pixels: None,
+            link_partner_tex: crate::debug::pixels::PixelTexture::default(),
+            debug_layout: rustyboi_session::DebugLayout::default(),
+            debug_layout_restored: false,
+            debug_layout_moves: Vec::new(),
             input_config: None,
+            input_config_seen: None,
             rebinding_gb: None,
+            pad_profile_guid: None,
             recording_chord: None,
             recorded_chord: Vec::new(),
             new_hotkey_action: rustyboi_session::HotkeyAction::FastForward,
@@ -262,6 +427,7 @@ impl Gui {
             show_mobile_menu: false,
             #[cfg(not(mobile))]
             menu_open_last_frame: false,
+            osd_shown: None,
         }
     }
 
@@ -293,7 +459,7 @@ impl Gui {
     // The per-frame inputs are bundled as `UiRunInputs` on the frontend side; the
     // widget entry point still takes them positionally (one call site).
     #[allow(clippy::too_many_arguments)]
-    pub fn ui(&mut self, ui: &mut egui::Ui, paused: bool, debug: Option<&DebugSnapshot>, fullscreen: bool, session: &SessionUiState, held_pad: &std::collections::HashSet<rustyboi_session::input_config::PadButton>, fps: f32) -> UiOutput {
+    pub fn ui(&mut self, ui: &mut egui::Ui, paused: bool, debug: Option<&DebugSnapshot>, fullscreen: bool, session: &SessionUiState, held_pad: &std::collections::HashSet<rustyboi_session::input_config::PadButton>, fps: f32, speed: f32, audio: Option<AudioStats>) -> UiOutput {
         // egui 0.35 made panels `Ui`-scoped (`Context::run_ui` hands us a root
         // `Ui`; panels carve space from it). Floating Areas/Windows still take a
         // `&Context`, so keep a cheap Arc clone for those. Reserved panels (the
@@ -336,9 +502,25 @@ impl Gui {
         #[cfg(mobile)]
         let _ = fullscreen;
         self.render_debug_panels(ctx, debug, &mut action, paused, session, held_pad);
+        self.sync_debug_layout(ctx, session, &mut action);
+        if let Some(view) = &session.input_editor {
+            self.render_input_editor(ctx, &mut action, view);
+        }
         if self.show_cheats_panel {
             self.render_cheats_panel(ctx, &mut action, session);
         }
+        if self.show_battery_manager {
+            self.render_battery_manager(ctx, &mut action, session);
+        }
+        if !session.archive_choices.is_empty() {
+            self.render_archive_chooser(ctx, &mut action, session);
+        }
+        if session.link_partner {
+            self.render_link_partner(ctx, &mut action, session);
+        }
+        if let Some(saved_at) = session.resume_offer {
+            render_resume_offer(ctx, &mut action, saved_at);
+        }
         #[cfg(target_os = "android")]
         if let Some(lib_action) = self.library.show(ctx) {
             action = Some(lib_action);
@@ -363,12 +545,14 @@ impl Gui {
         // FPS overlay: a floating, non-interactive label pinned to the top-right
         // of the game region. Opt-in (session-owned toggle) so it costs nothing
         // when off. This is the only way to read the frame rate on web / Android /
-        // iOS (which have no window title).
+        // iOS (which have no window title). Platforms that own an audio device
+        // add its latency and underrun count underneath.
         if session.show_fps {
-            Self::render_fps_overlay(ctx, central, fps);
+            Self::render_fps_overlay(ctx, central, fps, speed, audio);
         }
+        self.render_osd(ctx, central, session);
 
-        self.render_error_panel(ui, &mut action);
+        self.render_error_panel(ui, &mut action, session);
 
         // Android mobile menu: floating soft button + full-screen
         // overlay. Rendered after the debug panels / error overlay so
@@ -436,6 +620,26 @@ impl Gui {
             });
     }
 
+    /// A slot menu entry, led by the screenshot saved with the slot when it
+    /// has one so slots can be told apart at a glance.
+    #[cfg(not(mobile))]
+    fn slot_button(&mut self, ui: &mut egui::Ui, session: &SessionUiState, slot: u32, label: String) -> egui::Response {
+        use rustyboi_core_lib::savestate::{THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH};
+        let Some((_, rgb)) = session.slot_thumbnails.iter().find(|(s, _)| *s == slot) else {
+            return ui.button(label);
+        };
+        let pixels = rgb.chunks_exact(3).map(|p| egui::Color32::from_rgb(p[0], p[1], p[2])).collect();
+        let tex = self.slot_thumb_tex.entry(slot).or_default().update(
+            ui.ctx(),
+            &format!("slot_thumbnail_{slot}"),
+            THUMBNAIL_WIDTH,
+            THUMBNAIL_HEIGHT,
+            pixels,
+        );
+        let image = egui::Image::new((tex, egui::vec2(THUMBNAIL_WIDTH as f32, THUMBNAIL_HEIGHT as f32)));
+        ui.add(egui::Button::image_and_text(image, label))
+    }
+
     #[cfg(not(mobile))]
     fn menu_bar_contents(&mut self, ui: &mut egui::Ui, action: &mut Option<GuiAction>, any_menu_open: &mut bool, paused: bool, session: &SessionUiState) {
         {
@@ -452,7 +656,7 @@ impl Gui {
                     }
                     if ui.button(command_label(ActionKind::LoadRom)).clicked() {
                         let mut dialog = file_dialog::new()
-                            .add_filter("Game Boy ROM", &["gb", "gbc", "zip"])
+                            .add_filter("Game Boy ROM", &["gb", "gbc", "sgb", "zip", "7z", "gz"])
                             .add_filter("All Files", &["*"]);
                         if env::current_dir().is_ok() {
                             dialog = dialog.set_directory(env::current_dir().unwrap());
@@ -466,6 +670,31 @@ impl Gui {
                         });
                         ui.close();
                     }
+                    // Only desktop paths can be reopened; the list stays empty
+                    // elsewhere.
+                    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+                    ui.menu_button("Recent", |ui| {
+                        if session.recent_roms.is_empty() {
+                            ui.weak("No recent ROMs");
+                        }
+                        for path in &session.recent_roms {
+                            let name = std::path::Path::new(path)
+                                .file_name()
+                                .map_or_else(|| path.clone(), |n| n.to_string_lossy().into_owned());
+                            if ui.button(name).on_hover_text(path).clicked() {
+                                *action = Some(GuiAction::LoadRom(crate::actions::FileData::Path(path.into())));
+                                ui.close();
+                            }
+                        }
+                        ui.separator();
+                        if ui
+                            .add_enabled(!session.recent_roms.is_empty(), egui::Button::new(command_label(ActionKind::ClearRecentRoms)))
+                            .clicked()
+                        {
+                            *action = Some(GuiAction::ClearRecentRoms);
+                            ui.close();
+                        }
+                    });
                     ui.separator();
                     // Cross-platform save-data import/export. Import picks a file
                     // (bytes flow through the session's finish_import_* path);
@@ -475,16 +704,16 @@ impl Gui {
                     ui.menu_button("Import", |ui| {
                         import_menu_button(ui, &self.pending_dialog_result,
                             command_label(ActionKind::ImportState),
-                            "RustyBoi Save State", "rustyboisave", GuiAction::ImportState);
+                            "RustyBoi Save State", &["rustyboisave"], GuiAction::ImportState);
                         if session.has_battery {
                             import_menu_button(ui, &self.pending_dialog_result,
                                 command_label(ActionKind::ImportBatterySave),
-                                "Battery Save", "sav", GuiAction::ImportBatterySave);
+                                "Battery Save", &["sav"], GuiAction::ImportBatterySave);
                         }
                         if session.has_rtc {
                             import_menu_button(ui, &self.pending_dialog_result,
                                 command_label(ActionKind::ImportRtc),
-                                "RTC", "rtc", GuiAction::ImportRtc);
+                                "RTC", &["rtc"], GuiAction::ImportRtc);
                         }
                     });
                     ui.menu_button("Export", |ui| {
@@ -492,17 +721,30 @@ impl Gui {
                             *action = Some(GuiAction::ExportState);
                             ui.close();
                         }
+                        if ui.button(command_label(ActionKind::ExportStateJson)).clicked() {
+                            *action = Some(GuiAction::ExportStateJson);
+                            ui.close();
+                        }
                         if session.has_battery
                             && ui.button(command_label(ActionKind::ExportBatterySave)).clicked() {
                             *action = Some(GuiAction::ExportBatterySave);
                             ui.close();
                         }
+                        if session.has_battery
+                            && ui.button(command_label(ActionKind::FlushSaves)).clicked() {
+                            *action = Some(GuiAction::FlushSaves);
+                            ui.close();
+                        }
                         if session.has_rtc
                             && ui.button(command_label(ActionKind::ExportRtc)).clicked() {
                             *action = Some(GuiAction::ExportRtc);
                             ui.close();
                         }
                     });
+                    if session.has_battery && ui.button("Battery Saves…").clicked() {
+                        self.show_battery_manager = true;
+                        ui.close();
+                    }
                     // Apply an IPS/UPS/BPS ROM patch (romhack/translation) to the
                     // loaded ROM; the picked bytes flow through apply_rom_patch.
                     ui.add_enabled_ui(session.has_rom, |ui| {
@@ -519,44 +761,39 @@ impl Gui {
                             });
                             ui.close();
                         }
+                        // The parsed cartridge header + ROM digests; the same
+                        // window as Debug → ROM Info.
+                        if ui.button("ROM Info…").clicked() {
+                            self.show_cartridge_info = true;
+                            ui.close();
+                        }
+                        if ui.button(command_label(ActionKind::CopyScreenshot)).clicked() {
+                            *action = Some(GuiAction::CopyScreenshot);
+                            ui.close();
+                        }
                     });
                     ui.separator();
-                    // Quick + numbered savestate slots (via the session). The
-                    // quick slot has dedicated hotkeys (F5/F8); the numbered
-                    // slots (0-9) are keyed by ROM id under the save dir.
-                    if ui.button(format!("{} (F5)", command_label(ActionKind::Quicksave))).clicked() {
+                    // The quick slot has dedicated hotkeys (F5/F8 by default);
+                    // the numbered slots live under Emulation.
+                    let quicksave = with_shortcut(command_label(ActionKind::Quicksave), session, HotkeyAction::Quicksave);
+                    if ui.button(quicksave).clicked() {
                         *action = Some(GuiAction::Quicksave);
                         ui.close();
                     }
-                    if ui.button(format!("{} (F8)", command_label(ActionKind::Quickload))).clicked() {
+                    let quickload = with_shortcut(command_label(ActionKind::Quickload), session, HotkeyAction::Quickload);
+                    if ui.button(quickload).clicked() {
                         *action = Some(GuiAction::Quickload);
                         ui.close();
                     }
-                    ui.menu_button(command_label(ActionKind::SaveSlot), |ui| {
-                        for slot in 0u32..10 {
-                            let filled = session.slots.contains(&slot);
-                            let label = if filled {
-                                format!("Slot {slot} (overwrite)")
-                            } else {
-                                format!("Slot {slot}")
-                            };
-                            if ui.button(label).clicked() {
-                                *action = Some(GuiAction::SaveSlot(slot));
-                                ui.close();
-                            }
-                        }
-                    });
-                    ui.menu_button(command_label(ActionKind::LoadSlot), |ui| {
-                        if session.slots.is_empty() {
-                            ui.label("No saved slots");
-                        }
-                        for &slot in &session.slots {
-                            if ui.button(format!("Slot {slot}")).clicked() {
-                                *action = Some(GuiAction::LoadSlot(slot));
-                                ui.close();
-                            }
-                        }
-                    });
+                    let undo = egui::Button::new(with_shortcut(
+                        command_label(ActionKind::UndoLoadState),
+                        session,
+                        HotkeyAction::UndoLoadState,
+                    ));
+                    if ui.add_enabled(session.can_undo_load, undo).clicked() {
+                        *action = Some(GuiAction::UndoLoadState);
+                        ui.close();
+                    }
                     ui.separator();
                     if ui.button(command_label(ActionKind::Exit)).clicked() {
                         *action = Some(GuiAction::Exit);
@@ -576,20 +813,79 @@ impl Gui {
                         *action = Some(GuiAction::TogglePause);
                         ui.close();
                     }
-                    let ff_text = if session.fast_forward {
-                        "Fast-Forward: On (Tab)"
-                    } else {
-                        "Fast-Forward: Off (Tab)"
-                    };
-                    if ui.button(ff_text).clicked() {
+                    let ff_text = if session.fast_forward { "Fast-Forward: On" } else { "Fast-Forward: Off" };
+                    if ui.button(with_shortcut(ff_text, session, HotkeyAction::FastForward)).clicked() {
                         *action = Some(GuiAction::ToggleFastForward);
                         ui.close();
                     }
-                    if ui.button("Frame Advance (Backslash)").clicked() {
+                    let speed = session.emulation_speed;
+                    let speed_text = format!("{}: {speed}%", command_label(ActionKind::SetEmulationSpeed));
+                    ui.menu_button(speed_text, |ui| {
+                        for (label, target, hotkey) in [
+                            ("Faster", crate::actions::step_emulation_speed(speed, true), HotkeyAction::SpeedUp),
+                            ("Slower", crate::actions::step_emulation_speed(speed, false), HotkeyAction::SlowDown),
+                            ("Normal", 100, HotkeyAction::NormalSpeed),
+                        ] {
+                            let button = egui::Button::new(with_shortcut(label, session, hotkey));
+                            if ui.add_enabled(target != speed, button).clicked() {
+                                *action = Some(GuiAction::SetEmulationSpeed(target));
+                            }
+                        }
+                        ui.separator();
+                        for percent in crate::actions::EMULATION_SPEEDS {
+                            let selected = speed == percent;
+                            if ui.radio(selected, format!("{percent}%")).clicked() && !selected {
+                                *action = Some(GuiAction::SetEmulationSpeed(percent));
+                            }
+                        }
+                    });
+                    let mut run_ahead = session.run_ahead;
+                    if ui
+                        .checkbox(&mut run_ahead, command_label(ActionKind::SetRunAhead))
+                        .on_hover_text("Shows the next frame early to cut a frame of input lag; emulates twice as much")
+                        .clicked()
+                    {
+                        *action = Some(GuiAction::SetRunAhead(run_ahead));
+                    }
+                    if ui.button(with_shortcut("Frame Advance", session, HotkeyAction::FrameAdvance)).clicked() {
                         *action = Some(GuiAction::FrameAdvance);
                         ui.close();
                     }
                     ui.separator();
+                    // Numbered savestate slots (0-9), keyed by ROM id under
+                    // the save dir. By default Shift+F1..F10 saves and
+                    // Ctrl+F1..F10 loads (F10 is slot 0); each entry shows
+                    // when it was last written.
+                    ui.menu_button(command_label(ActionKind::SaveSlot), |ui| {
+                        for slot in 0u32..10 {
+                            let label = match slot_time(&session.slot_times, slot) {
+                                Some(when) => format!("Slot {slot} — {when}"),
+                                None => format!("Slot {slot}"),
+                            };
+                            let label = with_shortcut(&label, session, HotkeyAction::SaveSlot(slot as u8));
+                            if self.slot_button(ui, session, slot, label).clicked() {
+                                *action = Some(GuiAction::SaveSlot(slot));
+                                ui.close();
+                            }
+                        }
+                    });
+                    ui.menu_button(command_label(ActionKind::LoadSlot), |ui| {
+                        if session.slots.is_empty() {
+                            ui.label("No saved slots");
+                        }
+                        for &slot in &session.slots {
+                            let label = match slot_time(&session.slot_times, slot) {
+                                Some(when) => format!("Slot {slot} — {when}"),
+                                None => format!("Slot {slot}"),
+                            };
+                            let label = with_shortcut(&label, session, HotkeyAction::LoadSlot(slot as u8));
+                            if self.slot_button(ui, session, slot, label).clicked() {
+                                *action = Some(GuiAction::LoadSlot(slot));
+                                ui.close();
+                            }
+                        }
+                    });
+                    ui.separator();
                     let mut sgb_border = session.sgb_border;
                     if ui.checkbox(&mut sgb_border, "SGB border").clicked() {
                         *action = Some(GuiAction::ToggleSgbBorder);
@@ -605,6 +901,20 @@ impl Gui {
                         *action = Some(GuiAction::TogglePrinter);
                         ui.close();
                     }
+                    // The second Game Boy needs its frames drawn by the host
+                    // that runs it; the web worker hands back only one screen.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let mut linked = session.link_partner;
+                        if ui.checkbox(&mut linked, command_label(ActionKind::ToggleLinkPartner)).clicked() {
+                            *action = Some(GuiAction::ToggleLinkPartner);
+                            ui.close();
+                        }
+                    }
+                    if session.netplay && ui.button(command_label(ActionKind::StopNetplay)).clicked() {
+                        *action = Some(GuiAction::StopNetplay);
+                        ui.close();
+                    }
                     ui.separator();
                     // TAS record/replay: record from the current state into a
                     // `.rbmovie` (exported like a save), or load one back and
@@ -618,13 +928,89 @@ impl Gui {
                         *action = Some(GuiAction::ToggleRecording);
                         ui.close();
                     }
+                    let mut by_hash = session.movie_anchor_by_hash;
+                    let by_hash_box = ui.checkbox(&mut by_hash, command_label(ActionKind::SetMovieAnchorByHash));
+                    if by_hash_box
+                        .on_hover_text("Keep a movie's starting state here and save only its hash in the movie")
+                        .clicked()
+                    {
+                        *action = Some(GuiAction::SetMovieAnchorByHash(by_hash));
+                    }
+                    let mut editing = session.input_editor.is_some();
+                    if ui.checkbox(&mut editing, command_label(ActionKind::ToggleInputEditor)).clicked() {
+                        *action = Some(GuiAction::ToggleInputEditor);
+                        ui.close();
+                    }
                     import_menu_button(ui, &self.pending_dialog_result,
                         command_label(ActionKind::LoadMovie),
-                        "RustyBoi Movie", "rbmovie", GuiAction::LoadMovie);
+                        "Movie", &["rbmovie", "vbm", "txt"], GuiAction::LoadMovie);
                     if session.replaying && ui.button(command_label(ActionKind::StopReplay)).clicked() {
                         *action = Some(GuiAction::StopReplay);
                         ui.close();
                     }
+                    ui.separator();
+                    // Audio capture: the mix as a WAV, plus a stem per channel
+                    // when asked (the stems option locks while recording).
+                    let audio_text = if session.recording_audio {
+                        "⏹ Stop Audio Recording"
+                    } else {
+                        "⏺ Record Audio"
+                    };
+                    if ui.button(audio_text).clicked() {
+                        *action = Some(GuiAction::ToggleAudioRecording);
+                        ui.close();
+                    }
+                    let mut stems = session.record_stems;
+                    let stems_box = ui.add_enabled(
+                        !session.recording_audio,
+                        egui::Checkbox::new(&mut stems, command_label(ActionKind::SetRecordStems)),
+                    );
+                    if stems_box.on_hover_text("Also save pulse 1, pulse 2, wave and noise as separate WAVs").clicked() {
+                        *action = Some(GuiAction::SetRecordStems(stems));
+                    }
+                    // GIF capture of the presented frames; scale and length
+                    // lock while recording, like the stems option.
+                    let gif_text = if session.recording_gif {
+                        "⏹ Stop GIF Recording"
+                    } else {
+                        "⏺ Record GIF"
+                    };
+                    if ui.button(gif_text).clicked() {
+                        *action = Some(GuiAction::ToggleGifRecording);
+                        ui.close();
+                    }
+                    ui.add_enabled_ui(!session.recording_gif, |ui| {
+                        ui.menu_button("GIF Options", |ui| {
+                            ui.label(command_label(ActionKind::SetGifScale));
+                            for scale in crate::actions::GIF_SCALES {
+                                let selected = session.gif_scale == scale;
+                                if ui.radio(selected, format!("{scale}×")).clicked() && !selected {
+                                    *action = Some(GuiAction::SetGifScale(scale));
+                                }
+                            }
+                            ui.separator();
+                            ui.label(command_label(ActionKind::SetGifMaxSeconds));
+                            for seconds in crate::actions::GIF_LENGTHS {
+                                let selected = session.gif_max_seconds == seconds;
+                                if ui.radio(selected, format!("{seconds} s")).clicked() && !selected {
+                                    *action = Some(GuiAction::SetGifMaxSeconds(seconds));
+                                }
+                            }
+                        });
+                    });
+                    let video_text = if session.recording_video {
+                        "⏹ Stop Video Recording"
+                    } else {
+                        "⏺ Record Video"
+                    };
+                    if ui
+                        .button(video_text)
+                        .on_hover_text("PNG frames plus a WAV, synced on emulated time, saved as a zip")
+                        .clicked()
+                    {
+                        *action = Some(GuiAction::ToggleVideoRecording);
+                        ui.close();
+                    }
                 });
 
                 ui.menu_button("Debug", |ui| {
@@ -633,12 +1019,20 @@ impl Gui {
                     ui.checkbox(&mut self.show_stack_explorer, "Stack Explorer");
                     ui.checkbox(&mut self.show_memory_explorer, "Memory Explorer");
                     ui.checkbox(&mut self.show_ppu_debug, "PPU");
+                    ui.checkbox(&mut self.show_apu_debug, "APU");
                     ui.checkbox(&mut self.show_sprite_debug, "Sprite Debug");
                     ui.checkbox(&mut self.show_palette_explorer, "Palette Explorer");
                     ui.checkbox(&mut self.show_tile_explorer, "Tile Explorer");
-                    ui.checkbox(&mut self.show_cartridge_info, "Cartridge Info");
+                    ui.checkbox(&mut self.show_cartridge_info, "ROM Info");
                     ui.separator();
                     ui.checkbox(&mut self.show_breakpoint_panel, "Breakpoint Manager");
+                    #[cfg(feature = "profiler")]
+                    ui.checkbox(&mut self.show_profiler, "Profiler");
+                    ui.separator();
+                    if ui.button("Tile Windows").clicked() {
+                        self.tile_debug_windows(ui.ctx());
+                        ui.close();
+                    }
                 });
 
                 ui.menu_button("Settings", |ui| {
@@ -674,6 +1068,37 @@ impl Gui {
                                     ui.close();
                                 }
                             }
+                            // User palettes: pick, or ✖ to forget one.
+                            for (i, custom) in session.custom_palettes.iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    let choice = custom.choice();
+                                    let selected = session.palette == choice;
+                                    let radio = ui.radio(selected, &custom.name).on_hover_text(custom.to_hex());
+                                    if radio.clicked() && !selected {
+                                        *action = Some(GuiAction::SetPalette(choice));
+                                        ui.close();
+                                    }
+                                    if ui.small_button("✖").on_hover_text("Remove this palette").clicked() {
+                                        *action = Some(GuiAction::RemovePalette(i));
+                                    }
+                                });
+                            }
+                            import_menu_button(ui, &self.pending_dialog_result,
+                                command_label(ActionKind::LoadPaletteFile),
+                                "Palette", &["pal"], GuiAction::LoadPaletteFile);
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut self.palette_hex_input)
+                                        .desired_width(220.0)
+                                        .hint_text("#9BBC0F #8BAC0F #306230 #0F380F")
+                                        .font(egui::TextStyle::Monospace),
+                                );
+                                if ui.button("Add").clicked() && !self.palette_hex_input.trim().is_empty() {
+                                    let text = std::mem::take(&mut self.palette_hex_input);
+                                    *action = Some(GuiAction::AddPalette(text));
+                                    ui.close();
+                                }
+                            });
                             ui.separator();
                             ui.label("GBC colorization (DMG games on CGB)");
                             for (choice, label) in GbcDmgPalette::choices() {
@@ -738,6 +1163,7 @@ impl Gui {
                         for (filter, label) in [
                             (TextureFilter::Nearest, "Nearest (sharp)"),
                             (TextureFilter::Linear, "Linear (smooth)"),
+                            (TextureFilter::SharpBilinear, "Sharp bilinear"),
                         ] {
                             let selected = session.texture_filter == filter;
                             if ui.radio(selected, label).clicked() && !selected {
@@ -753,6 +1179,7 @@ impl Gui {
                             (LcdEffect::Off, "Off"),
                             (LcdEffect::Grid, "LCD grid"),
                             (LcdEffect::Scanlines, "Scanlines"),
+                            (LcdEffect::Crt, "CRT"),
                         ] {
                             let selected = session.lcd_effect == effect;
                             if ui.radio(selected, label).clicked() && !selected {
@@ -761,6 +1188,11 @@ impl Gui {
                             }
                         }
                     });
+                    let mut dot_matrix = session.dot_matrix;
+                    if ui.checkbox(&mut dot_matrix, command_label(ActionKind::ToggleDotMatrix)).clicked() {
+                        *action = Some(GuiAction::ToggleDotMatrix);
+                        ui.close();
+                    }
 
                     ui.menu_button("Printer Scale", |ui| {
                         ui.label("Saved Game Boy Printer image size");
@@ -838,6 +1270,85 @@ impl Gui {
                         }
                     });
 
+                    let mut rtc_host_clock = session.rtc_host_clock;
+                    if ui
+                        .checkbox(&mut rtc_host_clock, command_label(ActionKind::SetRtcHostClock))
+                        .on_hover_text("Off: the clock advances with emulated time (deterministic)")
+                        .clicked()
+                    {
+                        *action = Some(GuiAction::SetRtcHostClock(rtc_host_clock));
+                    }
+
+                    ui.menu_button(command_label(ActionKind::SetAccuracyPreset), |ui| {
+                        for preset in crate::actions::AccuracyPreset::ALL {
+                            let selected = session.accuracy_preset == Some(preset);
+                            if ui.radio(selected, preset.label()).clicked() && !selected {
+                                *action = Some(GuiAction::SetAccuracyPreset(preset));
+                            }
+                        }
+                        if session.accuracy_preset.is_none() {
+                            ui.add_enabled(false, egui::RadioButton::new(true, "Custom"));
+                        }
+                    });
+
+                    // A preset that puts every ROM on the scanline renderer
+                    // overrides the per-ROM choice.
+                    let scanline_all = session.accuracy_preset.is_some_and(|p| p.scanline_ppu());
+                    let mut scanline_ppu = session.scanline_ppu || scanline_all;
+                    if ui
+                        .add_enabled(
+                            session.has_rom && !scanline_all,
+                            egui::Checkbox::new(&mut scanline_ppu, command_label(ActionKind::ToggleScanlinePpu)),
+                        )
+                        .on_hover_text("Draws whole lines at once: faster on slow machines, but mid-line effects are lost")
+                        .clicked()
+                    {
+                        *action = Some(GuiAction::ToggleScanlinePpu);
+                    }
+
+                    let mut auto_save_state = session.auto_save_state;
+                    if ui
+                        .checkbox(&mut auto_save_state, command_label(ActionKind::SetAutoSaveState))
+                        .on_hover_text("Offers to resume where you left off the next time the game loads")
+                        .clicked()
+                    {
+                        *action = Some(GuiAction::SetAutoSaveState(auto_save_state));
+                    }
+
+                    // Only desktop keeps battery saves as files it can place.
+                    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+                    ui.menu_button(command_label(ActionKind::SetSaveDir), |ui| {
+                        let next_to_rom = session.save_dir.is_none();
+                        if ui.radio(next_to_rom, "Next to each ROM").clicked() && !next_to_rom {
+                            *action = Some(GuiAction::SetSaveDir(None));
+                            ui.close();
+                        }
+                        if let Some(dir) = &session.save_dir {
+                            let _ = ui.radio(true, dir.as_str());
+                        }
+                        if ui.button("Choose Folder…").clicked() {
+                            if let Some(dir) = file_dialog::pick_folder() {
+                                *action = Some(GuiAction::SetSaveDir(Some(dir.to_string_lossy().into_owned())));
+                            }
+                            ui.close();
+                        }
+                        ui.separator();
+                        ui.weak("Applies from the next ROM loaded");
+                    });
+
+                    // The browser tab has no window focus to follow.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let mut pause_on_focus_loss = session.pause_on_focus_loss;
+                        if ui
+                            .checkbox(&mut pause_on_focus_loss, command_label(ActionKind::SetPauseOnFocusLoss))
+                            .on_hover_text("Pauses and silences the game while another window is focused")
+                            .clicked()
+                        {
+                            *action = Some(GuiAction::SetPauseOnFocusLoss(pause_on_focus_loss));
+                        }
+                    }
+
                     ui.menu_button("Fast-forward speed", |ui| {
                         for (factor, label) in crate::actions::FAST_FORWARD_SPEEDS {
                             let selected = session.fast_forward_factor == factor;
@@ -845,6 +1356,35 @@ impl Gui {
                                 *action = Some(GuiAction::SetFastForwardFactor(factor));
                             }
                         }
+                        ui.separator();
+                        let mut muted = session.fast_forward_muted;
+                        if ui.checkbox(&mut muted, command_label(ActionKind::SetFastForwardMuted)).clicked() {
+                            *action = Some(GuiAction::SetFastForwardMuted(muted));
+                        }
+                        // The browser always presents at vsync.
+                        #[cfg(not(target_arch = "wasm32"))]
+                        {
+                            let mut vsync_off = session.fast_forward_vsync_off;
+                            if ui
+                                .checkbox(&mut vsync_off, command_label(ActionKind::SetFastForwardVsyncOff))
+                                .on_hover_text("Fast-forwards as fast as the computer allows; the picture may tear")
+                                .clicked()
+                            {
+                                *action = Some(GuiAction::SetFastForwardVsyncOff(vsync_off));
+                            }
+                        }
+                    });
+
+                    // The web build presents from its worker's shared frame
+                    // buffer and doesn't skip.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.menu_button(command_label(ActionKind::SetFrameSkip), |ui| {
+                        for (skip, label) in crate::actions::FRAME_SKIPS {
+                            let selected = session.frame_skip == skip;
+                            if ui.radio(selected, label).clicked() && !selected {
+                                *action = Some(GuiAction::SetFrameSkip(skip));
+                            }
+                        }
                     });
 
                     ui.menu_button("Scaling", |ui| {
@@ -860,6 +1400,19 @@ impl Gui {
                         }
                     });
 
+                    ui.menu_button("Aspect Ratio", |ui| {
+                        for (aspect, label) in [
+                            (AspectRatio::Square, "Square pixels"),
+                            (AspectRatio::TenByNine, "10:9 (handheld)"),
+                            (AspectRatio::FourByThree, "4:3 (TV)"),
+                        ] {
+                            let selected = session.aspect_ratio == aspect;
+                            if ui.radio(selected, label).clicked() && !selected {
+                                *action = Some(GuiAction::SetAspectRatio(aspect));
+                            }
+                        }
+                    });
+
                     ui.menu_button("Renderer", |ui| {
                         for (backend, label) in rustyboi_session::GraphicsBackend::choices().iter().copied() {
                             let selected = session.graphics_backend == backend;
@@ -872,11 +1425,68 @@ impl Gui {
                     });
 
                     ui.separator();
-                    ui.label("Volume");
-                    let mut vol = session.volume;
-                    if ui.add(egui::Slider::new(&mut vol, 0..=100)).changed() {
-                        *action = Some(GuiAction::SetVolume(vol));
-                    }
+                    // Everything audio in one submenu: level, mute, the mix, and the device.
+                    ui.menu_button("Audio", |ui| {
+                        ui.label("Volume");
+                        let mut vol = session.volume;
+                        if ui.add(egui::Slider::new(&mut vol, 0..=100)).changed() {
+                            *action = Some(GuiAction::SetVolume(vol));
+                        }
+                        let mut muted = session.muted;
+                        if ui.checkbox(&mut muted, command_label(ActionKind::ToggleMute)).clicked() {
+                            *action = Some(GuiAction::ToggleMute);
+                        }
+                        let mut high_pass = session.audio_high_pass;
+                        if ui
+                            .checkbox(&mut high_pass, command_label(ActionKind::SetAudioHighPass))
+                            .on_hover_text("Off: the raw mix, DC offset and all")
+                            .clicked()
+                        {
+                            *action = Some(GuiAction::SetAudioHighPass(high_pass));
+                        }
+                        ui.menu_button(command_label(ActionKind::SetChannelMix), |ui| {
+                            if let Some(mix) = channel_mix_grid(ui, session.channel_mix) {
+                                *action = Some(GuiAction::SetChannelMix(mix));
+                            }
+                        });
+                        ui.menu_button(command_label(ActionKind::SetSampleRate), |ui| {
+                            for rate in crate::actions::SAMPLE_RATES {
+                                let selected = session.sample_rate == rate;
+                                if ui.radio(selected, sample_rate_label(rate)).clicked() && !selected {
+                                    *action = Some(GuiAction::SetSampleRate(rate));
+                                }
+                            }
+                            ui.separator();
+                            ui.weak("Applies at next launch");
+                        });
+                        ui.menu_button(command_label(ActionKind::SetAudioBufferSize), |ui| {
+                            for size in crate::actions::AUDIO_BUFFER_SIZES {
+                                let selected = session.audio_buffer_size == size;
+                                if ui.radio(selected, audio_buffer_label(size)).clicked() && !selected {
+                                    *action = Some(GuiAction::SetAudioBufferSize(size));
+                                }
+                            }
+                            ui.separator();
+                            ui.weak("Applies at next launch");
+                        });
+                        // Only platforms that can choose a device enumerate any.
+                        if !session.audio_devices.is_empty() {
+                            ui.menu_button(command_label(ActionKind::SetAudioDevice), |ui| {
+                                if let Some(device) = audio_device_radios(ui, session) {
+                                    *action = Some(GuiAction::SetAudioDevice(device));
+                                }
+                            });
+                        }
+                        ui.label(command_label(ActionKind::SetAudioLatencyFrames));
+                        let mut latency = session.audio_latency_frames;
+                        if ui
+                            .add(egui::Slider::new(&mut latency, crate::actions::AUDIO_LATENCY_FRAMES).suffix(" frames"))
+                            .on_hover_text("More rides out crackle on a busy system; fewer responds faster")
+                            .changed()
+                        {
+                            *action = Some(GuiAction::SetAudioLatencyFrames(latency));
+                        }
+                    });
                 });
 
                 ui.menu_button("View", |ui| {
@@ -904,7 +1514,27 @@ impl Gui {
                             }
                         });
                     }
-                    if ui.button("Toggle Fullscreen").clicked() {
+                    // Only a desktop window can be resized; the web canvas
+                    // follows the page.
+                    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+                    ui.menu_button(command_label(ActionKind::SetWindowScale), |ui| {
+                        for scale in crate::actions::WINDOW_SCALES {
+                            let selected = session.window_scale == scale;
+                            let mut item = egui::Button::selectable(selected, format!("{scale}×"));
+                            if let Some(keys) = session.input.shortcut(HotkeyAction::WindowScale(scale)) {
+                                item = item.shortcut_text(keys);
+                            }
+                            if ui.add(item).clicked() {
+                                *action = Some(GuiAction::SetWindowScale(scale));
+                                ui.close();
+                            }
+                        }
+                    });
+                    let mut toggle = egui::Button::new("Toggle Fullscreen");
+                    if let Some(keys) = session.input.shortcut(HotkeyAction::ToggleFullscreen) {
+                        toggle = toggle.shortcut_text(keys);
+                    }
+                    if ui.add(toggle).clicked() {
                         *action = Some(GuiAction::ToggleFullscreen);
                         ui.close();
                     }
@@ -930,6 +1560,10 @@ impl Gui {
             self.render_ppu_debug_panel(ctx, debug);
         }
 
+        if self.show_apu_debug {
+            self.render_apu_debug_panel(ctx, debug);
+        }
+
         if self.show_sprite_debug {
             self.render_sprite_debug_panel(ctx, debug);
         }
@@ -952,11 +1586,15 @@ impl Gui {
             // Panel closed: drop the working copy so it re-seeds from persisted
             // state next time it opens.
             self.input_config = None;
+            self.input_config_seen = None;
         }
 
         if self.show_breakpoint_panel {
             self.render_breakpoint_panel(ctx, action, debug);
         }
+
+        #[cfg(feature = "profiler")]
+        self.render_profiler_panel(ctx);
     }
 
     /// Which heavy [`DebugSnapshot`] sections the currently-open panels need.
@@ -977,6 +1615,7 @@ impl Gui {
                 || self.show_sprite_debug,
             stack: self.show_stack_explorer,
             cartridge: self.show_cartridge_info,
+            apu: self.show_apu_debug,
         }
     }
 
@@ -989,6 +1628,7 @@ impl Gui {
             || self.show_stack_explorer
             || self.show_memory_explorer
             || self.show_ppu_debug
+            || self.show_apu_debug
             || self.show_sprite_debug
             || self.show_palette_explorer
             || self.show_tile_explorer
@@ -997,9 +1637,11 @@ impl Gui {
     }
 
     /// Draw the FPS overlay: a small themed label in the top-right of the game
-    /// region (`central`, in egui points). Non-interactive and drawn on the
-    /// foreground so it floats over the framebuffer without claiming layout space.
-    fn render_fps_overlay(ctx: &Context, central: egui::Rect, fps: f32) {
+    /// region (`central`, in egui points) giving the rate and `speed` (percent
+    /// of the machine's own rate), with the audio readout under it when
+    /// there is one. Non-interactive and drawn on the foreground so it floats
+    /// over the framebuffer without claiming layout space.
+    fn render_fps_overlay(ctx: &Context, central: egui::Rect, fps: f32, speed: f32, audio: Option<AudioStats>) {
         let pos = egui::pos2(central.right() - 8.0, central.top() + 8.0);
         egui::Area::new(egui::Id::new("fps_overlay"))
             .order(egui::Order::Foreground)
@@ -1010,14 +1652,56 @@ impl Gui {
                 egui::Frame::popup(ui.style()).show(ui, |ui| {
                     ui.add(
                         egui::Label::new(
-                            egui::RichText::new(format!("{:.1} FPS", fps.max(0.0)))
+                            egui::RichText::new(format!("{:.1} FPS · {:.0}%", fps.max(0.0), speed.max(0.0)))
                                 .monospace()
                                 .strong(),
                         )
                         .wrap_mode(egui::TextWrapMode::Extend),
                     );
+                    if let Some(audio) = audio {
+                        let text = format!("{:.0} ms · {} underruns", audio.latency_ms, audio.underruns);
+                        ui.add(
+                            egui::Label::new(egui::RichText::new(text).monospace().small())
+                                .wrap_mode(egui::TextWrapMode::Extend),
+                        );
+                    }
+                });
+            });
+    }
+
+    /// Draw the latest on-screen notice at the top centre of the game region,
+    /// fading out over its last [`OSD_FADE_SECONDS`]. Foreground and
+    /// non-interactive like the FPS overlay, so it shows over the game even
+    /// in fullscreen with the menu bar hidden.
+    fn render_osd(&mut self, ctx: &Context, central: egui::Rect, session: &SessionUiState) {
+        let Some(osd) = &session.osd else { return };
+        let now = ctx.input(|i| i.time);
+        let shown_at = match self.osd_shown {
+            Some((id, at)) if id == osd.id => at,
+            _ => {
+                self.osd_shown = Some((osd.id, now));
+                now
+            }
+        };
+        let left = OSD_SECONDS - (now - shown_at) as f32;
+        if left <= 0.0 {
+            return;
+        }
+        egui::Area::new(egui::Id::new("osd_overlay"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(egui::pos2(central.center().x, central.top() + 8.0))
+            .pivot(egui::Align2::CENTER_TOP)
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.set_opacity((left / OSD_FADE_SECONDS).min(1.0));
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(&osd.text).strong())
+                            .wrap_mode(egui::TextWrapMode::Extend),
+                    );
                 });
             });
+        ctx.request_repaint();
     }
 
     #[cfg(not(target_os = "android"))]
@@ -1049,7 +1733,7 @@ impl Gui {
         }
     }
 
-    fn render_error_panel(&mut self, ui: &mut egui::Ui, action: &mut Option<GuiAction>) {
+    fn render_error_panel(&mut self, ui: &mut egui::Ui, action: &mut Option<GuiAction>, session: &SessionUiState) {
         if let Some(error_msg) = &self.error_message.clone() {
             egui::CentralPanel::default().show(ui, |ui| {
                 ui.heading("🚨 Emulator Crashed");
@@ -1071,6 +1755,15 @@ impl Gui {
                 ui.add_space(10.0);
 
                 ui.horizontal(|ui| {
+                    if let Some(frame) = session.recovery_snapshot
+                        && ui
+                            .button("⏪ Restore Last Good Snapshot")
+                            .on_hover_text(format!("Taken at frame {frame}; restore again to go further back"))
+                            .clicked()
+                    {
+                        *action = Some(GuiAction::RestoreRecoverySnapshot);
+                    }
+
                     if ui.button("🔄 Restart Emulation").clicked() {
                         *action = Some(GuiAction::Restart);
                     }
@@ -1091,6 +1784,13 @@ impl Gui {
         self.error_message = None;
     }
 
+    /// Hand in the second Game Boy's latest frame (RGB888, 160×144) for its
+    /// window, or `None` while no partner runs on the link cable.
+    pub fn set_link_partner_frame(&mut self, rgb: Option<&[u8]>) {
+        self.link_partner_pixels =
+            rgb.map(|rgb| rgb.chunks_exact(3).map(|p| egui::Color32::from_rgb(p[0], p[1], p[2])).collect());
+    }
+
     pub fn set_status(&mut self, status_message: String) {
         #[cfg(target_os = "android")]
         {
@@ -1274,7 +1974,7 @@ impl Gui {
                             .clicked()
                         {
                             let dialog = file_dialog::new()
-                                .add_filter("Game Boy ROM", &["gb", "gbc", "zip"])
+                                .add_filter("Game Boy ROM", &["gb", "gbc", "sgb", "zip", "7z", "gz"])
                                 .add_filter("All Files", &["*"]);
                             let result_holder = Arc::clone(&self.pending_dialog_result);
                             dialog.pick_file(move |file_data| {
@@ -1291,10 +1991,10 @@ impl Gui {
                         // exports emit the payload-free action → SaveBytes → SAF
                         // create-document, never rfd `save_file`.
                         if mobile_import_row(ui, row_size, &self.pending_dialog_result,
-                            "Import Battery Save…", "Battery Save", "sav",
+                            "Import Battery Save…", "Battery Save", &["sav"],
                             GuiAction::ImportBatterySave) { close_after_action = true; }
                         if mobile_import_row(ui, row_size, &self.pending_dialog_result,
-                            "Import RTC…", "RTC", "rtc", GuiAction::ImportRtc) {
+                            "Import RTC…", "RTC", &["rtc"], GuiAction::ImportRtc) {
                             close_after_action = true;
                         }
                         // Apply an IPS/UPS/BPS ROM patch to the loaded ROM.
@@ -1313,6 +2013,12 @@ impl Gui {
                             });
                             close_after_action = true;
                         }
+                        if session.has_rom
+                            && ui.add(egui::Button::new("ROM Info…").min_size(row_size)).clicked()
+                        {
+                            self.show_cartridge_info = true;
+                            close_after_action = true;
+                        }
                         if ui
                             .add(egui::Button::new("Export Battery Save…").min_size(row_size))
                             .clicked()
@@ -1320,6 +2026,22 @@ impl Gui {
                             *action = Some(GuiAction::ExportBatterySave);
                             close_after_action = true;
                         }
+                        if session.has_battery
+                            && ui
+                                .add(egui::Button::new("Flush Saves Now").min_size(row_size))
+                                .clicked()
+                        {
+                            *action = Some(GuiAction::FlushSaves);
+                            close_after_action = true;
+                        }
+                        if session.has_battery
+                            && ui
+                                .add(egui::Button::new("Battery Saves…").min_size(row_size))
+                                .clicked()
+                        {
+                            self.show_battery_manager = true;
+                            close_after_action = true;
+                        }
                         if ui
                             .add(egui::Button::new("Export RTC…").min_size(row_size))
                             .clicked()
@@ -1368,6 +2090,16 @@ impl Gui {
                             *action = Some(GuiAction::Quickload);
                             close_after_action = true;
                         }
+                        if ui
+                            .add_enabled(
+                                session.can_undo_load,
+                                egui::Button::new("Undo Load State").min_size(row_size),
+                            )
+                            .clicked()
+                        {
+                            *action = Some(GuiAction::UndoLoadState);
+                            close_after_action = true;
+                        }
                         let ff_text = if session.fast_forward {
                             "Fast-Forward: On"
                         } else {
@@ -1380,6 +2112,18 @@ impl Gui {
                             *action = Some(GuiAction::ToggleFastForward);
                             close_after_action = true;
                         }
+                        ui.label(command_label(ActionKind::SetEmulationSpeed));
+                        for percent in crate::actions::EMULATION_SPEEDS {
+                            let selected = session.emulation_speed == percent;
+                            if ui.radio(selected, format!("{percent}%")).clicked() && !selected {
+                                *action = Some(GuiAction::SetEmulationSpeed(percent));
+                            }
+                        }
+                        let mut run_ahead = session.run_ahead;
+                        mobile_toggle_row(ui, row_size, command_label(ActionKind::SetRunAhead), &mut run_ahead);
+                        if run_ahead != session.run_ahead {
+                            *action = Some(GuiAction::SetRunAhead(run_ahead));
+                        }
                         if ui
                             .add(egui::Button::new("Frame Advance").min_size(row_size))
                             .clicked()
@@ -1407,9 +2151,57 @@ impl Gui {
                             *action = Some(GuiAction::ToggleRecording);
                             close_after_action = true;
                         }
+                        let mut by_hash = session.movie_anchor_by_hash;
+                        mobile_toggle_row(ui, row_size, command_label(ActionKind::SetMovieAnchorByHash), &mut by_hash);
+                        if by_hash != session.movie_anchor_by_hash {
+                            *action = Some(GuiAction::SetMovieAnchorByHash(by_hash));
+                        }
                         if mobile_import_row(ui, row_size, &self.pending_dialog_result,
                             command_label(ActionKind::LoadMovie),
-                            "RustyBoi Movie", "rbmovie", GuiAction::LoadMovie) {
+                            "Movie", &["rbmovie", "vbm", "txt"], GuiAction::LoadMovie) {
+                            close_after_action = true;
+                        }
+                        let audio_text = if session.recording_audio {
+                            "Stop Audio Recording"
+                        } else {
+                            "Record Audio"
+                        };
+                        if ui
+                            .add(egui::Button::new(audio_text).min_size(row_size))
+                            .clicked()
+                        {
+                            *action = Some(GuiAction::ToggleAudioRecording);
+                            close_after_action = true;
+                        }
+                        if !session.recording_audio {
+                            let mut stems = session.record_stems;
+                            mobile_toggle_row(ui, row_size, command_label(ActionKind::SetRecordStems), &mut stems);
+                            if stems != session.record_stems {
+                                *action = Some(GuiAction::SetRecordStems(stems));
+                            }
+                        }
+                        let gif_text = if session.recording_gif {
+                            "Stop GIF Recording"
+                        } else {
+                            "Record GIF"
+                        };
+                        if ui
+                            .add(egui::Button::new(gif_text).min_size(row_size))
+                            .clicked()
+                        {
+                            *action = Some(GuiAction::ToggleGifRecording);
+                            close_after_action = true;
+                        }
+                        let video_text = if session.recording_video {
+                            "Stop Video Recording"
+                        } else {
+                            "Record Video"
+                        };
+                        if ui
+                            .add(egui::Button::new(video_text).min_size(row_size))
+                            .clicked()
+                        {
+                            *action = Some(GuiAction::ToggleVideoRecording);
                             close_after_action = true;
                         }
                         if session.replaying
@@ -1442,6 +2234,7 @@ impl Gui {
                             &mut self.show_memory_explorer,
                         );
                         mobile_toggle_row(ui, row_size, "PPU", &mut self.show_ppu_debug);
+                        mobile_toggle_row(ui, row_size, "APU", &mut self.show_apu_debug);
                         mobile_toggle_row(
                             ui,
                             row_size,
@@ -1463,7 +2256,7 @@ impl Gui {
                         mobile_toggle_row(
                             ui,
                             row_size,
-                            "Cartridge Info",
+                            "ROM Info",
                             &mut self.show_cartridge_info,
                         );
                         mobile_toggle_row(
@@ -1506,6 +2299,27 @@ impl Gui {
                                 *action = Some(GuiAction::ToggleShowFps);
                             }
                         }
+                        {
+                            let mut on = session.auto_save_state;
+                            mobile_toggle_row(ui, row_size, command_label(ActionKind::SetAutoSaveState), &mut on);
+                            if on != session.auto_save_state {
+                                *action = Some(GuiAction::SetAutoSaveState(on));
+                            }
+                        }
+                        ui.label(command_label(ActionKind::SetAccuracyPreset));
+                        for preset in crate::actions::AccuracyPreset::ALL {
+                            let selected = session.accuracy_preset == Some(preset);
+                            if ui.radio(selected, preset.label()).clicked() && !selected {
+                                *action = Some(GuiAction::SetAccuracyPreset(preset));
+                            }
+                        }
+                        if session.has_rom && !session.accuracy_preset.is_some_and(|p| p.scanline_ppu()) {
+                            let mut on = session.scanline_ppu;
+                            mobile_toggle_row(ui, row_size, command_label(ActionKind::ToggleScanlinePpu), &mut on);
+                            if on != session.scanline_ppu {
+                                *action = Some(GuiAction::ToggleScanlinePpu);
+                            }
+                        }
                         if session.touch_controls {
                             ui.label("On-screen control opacity");
                             let mut op = session.touch_opacity;
@@ -1526,6 +2340,18 @@ impl Gui {
                             }
                         }
 
+                        ui.label("Aspect Ratio");
+                        for (aspect, label) in [
+                            (AspectRatio::Square, "Square pixels"),
+                            (AspectRatio::TenByNine, "10:9 (handheld)"),
+                            (AspectRatio::FourByThree, "4:3 (TV)"),
+                        ] {
+                            let selected = session.aspect_ratio == aspect;
+                            if ui.radio(selected, label).clicked() && !selected {
+                                *action = Some(GuiAction::SetAspectRatio(aspect));
+                            }
+                        }
+
                         ui.label("Renderer (applies at next launch)");
                         for (backend, label) in rustyboi_session::GraphicsBackend::choices().iter().copied() {
                             let selected = session.graphics_backend == backend;
@@ -1549,6 +2375,7 @@ impl Gui {
                         for (filter, label) in [
                             (TextureFilter::Nearest, "Nearest (sharp)"),
                             (TextureFilter::Linear, "Linear (smooth)"),
+                            (TextureFilter::SharpBilinear, "Sharp bilinear"),
                         ] {
                             let selected = session.texture_filter == filter;
                             if ui.radio(selected, label).clicked() && !selected {
@@ -1562,12 +2389,17 @@ impl Gui {
                             (LcdEffect::Off, "Off"),
                             (LcdEffect::Grid, "LCD grid"),
                             (LcdEffect::Scanlines, "Scanlines"),
+                            (LcdEffect::Crt, "CRT"),
                         ] {
                             let selected = session.lcd_effect == effect;
                             if ui.radio(selected, label).clicked() && !selected {
                                 *action = Some(GuiAction::SetLcdEffect(effect));
                             }
                         }
+                        let mut dot_matrix = session.dot_matrix;
+                        if ui.checkbox(&mut dot_matrix, command_label(ActionKind::ToggleDotMatrix)).clicked() {
+                            *action = Some(GuiAction::ToggleDotMatrix);
+                        }
 
                         ui.label("Printer Scale");
                         for scale in crate::actions::PRINTER_SCALES {
@@ -1577,11 +2409,69 @@ impl Gui {
                             }
                         }
 
+                        ui.label(command_label(ActionKind::SetGifScale));
+                        for scale in crate::actions::GIF_SCALES {
+                            let selected = session.gif_scale == scale;
+                            if ui.radio(selected, format!("{scale}×")).clicked() && !selected {
+                                *action = Some(GuiAction::SetGifScale(scale));
+                            }
+                        }
+                        ui.label(command_label(ActionKind::SetGifMaxSeconds));
+                        for seconds in crate::actions::GIF_LENGTHS {
+                            let selected = session.gif_max_seconds == seconds;
+                            if ui.radio(selected, format!("{seconds} s")).clicked() && !selected {
+                                *action = Some(GuiAction::SetGifMaxSeconds(seconds));
+                            }
+                        }
+
                         ui.label("Volume");
                         let mut vol = session.volume;
                         if ui.add(egui::Slider::new(&mut vol, 0..=100)).changed() {
                             *action = Some(GuiAction::SetVolume(vol));
                         }
+                        let mut muted = session.muted;
+                        mobile_toggle_row(ui, row_size, command_label(ActionKind::ToggleMute), &mut muted);
+                        if muted != session.muted {
+                            *action = Some(GuiAction::ToggleMute);
+                        }
+                        let mut high_pass = session.audio_high_pass;
+                        if ui.checkbox(&mut high_pass, command_label(ActionKind::SetAudioHighPass)).clicked() {
+                            *action = Some(GuiAction::SetAudioHighPass(high_pass));
+                        }
+                        ui.label(command_label(ActionKind::SetChannelMix));
+                        if let Some(mix) = channel_mix_grid(ui, session.channel_mix) {
+                            *action = Some(GuiAction::SetChannelMix(mix));
+                        }
+
+                        ui.label(format!("{} (applies at next launch)", command_label(ActionKind::SetSampleRate)));
+                        for rate in crate::actions::SAMPLE_RATES {
+                            let selected = session.sample_rate == rate;
+                            if ui.radio(selected, sample_rate_label(rate)).clicked() && !selected {
+                                *action = Some(GuiAction::SetSampleRate(rate));
+                            }
+                        }
+
+                        ui.label(format!("{} (applies at next launch)", command_label(ActionKind::SetAudioBufferSize)));
+                        for size in crate::actions::AUDIO_BUFFER_SIZES {
+                            let selected = session.audio_buffer_size == size;
+                            if ui.radio(selected, audio_buffer_label(size)).clicked() && !selected {
+                                *action = Some(GuiAction::SetAudioBufferSize(size));
+                            }
+                        }
+
+                        if !session.audio_devices.is_empty() {
+                            ui.label(command_label(ActionKind::SetAudioDevice));
+                            if let Some(device) = audio_device_radios(ui, session) {
+                                *action = Some(GuiAction::SetAudioDevice(device));
+                            }
+                        }
+
+                        ui.label(command_label(ActionKind::SetAudioLatencyFrames));
+                        let mut latency = session.audio_latency_frames;
+                        let slider = egui::Slider::new(&mut latency, crate::actions::AUDIO_LATENCY_FRAMES).suffix(" frames");
+                        if ui.add(slider).changed() {
+                            *action = Some(GuiAction::SetAudioLatencyFrames(latency));
+                        }
 
                         ui.label("Fast-forward speed");
                         for (factor, label) in crate::actions::FAST_FORWARD_SPEEDS {
@@ -1590,6 +2480,22 @@ impl Gui {
                                 *action = Some(GuiAction::SetFastForwardFactor(factor));
                             }
                         }
+                        let mut muted = session.fast_forward_muted;
+                        mobile_toggle_row(ui, row_size, command_label(ActionKind::SetFastForwardMuted), &mut muted);
+                        if muted != session.fast_forward_muted {
+                            *action = Some(GuiAction::SetFastForwardMuted(muted));
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        {
+                            ui.label(command_label(ActionKind::SetFrameSkip));
+                            for (skip, label) in crate::actions::FRAME_SKIPS {
+                                let selected = session.frame_skip == skip;
+                                if ui.radio(selected, label).clicked() && !selected {
+                                    *action = Some(GuiAction::SetFrameSkip(skip));
+                                }
+                            }
+                        }
 
                         if close_after_action {
                             close_requested = true;
@@ -1734,8 +2640,140 @@ impl Gui {
         });
     }
 
+    /// File → Battery Saves…: the cart's SRAM size plus export, import (the
+    /// core rejects a file far larger than the RAM), flush and a two-step
+    /// erase, for moving a save between emulators or to a flashcart.
+    fn render_battery_manager(
+        &mut self,
+        ctx: &Context,
+        action: &mut Option<GuiAction>,
+        session: &SessionUiState,
+    ) {
+        let mut open = self.show_battery_manager;
+        egui::Window::new("Battery Saves")
+            .open(&mut open)
+            .default_width(280.0)
+            .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(PANEL_BACKGROUND))
+            .show(ctx, |ui| {
+                if !session.has_battery {
+                    ui.label("This cartridge has no battery save.");
+                    return;
+                }
+                let size = session.battery_size;
+                if size >= 1024 {
+                    ui.label(format!("Save RAM: {} KiB", size / 1024));
+                } else {
+                    ui.label(format!("Save RAM: {size} bytes"));
+                }
+                if session.has_rtc {
+                    ui.small("Exports carry the clock as a footer after the RAM.");
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Export…").clicked() {
+                        *action = Some(GuiAction::ExportBatterySave);
+                    }
+                    if ui.button("Import…").clicked() {
+                        let dialog = file_dialog::new()
+                            .add_filter("Battery Save", &["sav", "srm"])
+                            .add_filter("All Files", &["*"]);
+                        let holder = Arc::clone(&self.pending_dialog_result);
+                        dialog.pick_file(move |file_data| {
+                            if let Some(file_data) = file_data
+                                && let Ok(mut pending) = holder.lock()
+                            {
+                                *pending = Some(GuiAction::ImportBatterySave(file_data));
+                            }
+                        });
+                    }
+                    if ui.button("Flush Now").clicked() {
+                        *action = Some(GuiAction::FlushSaves);
+                    }
+                });
+                ui.separator();
+                if self.battery_erase_armed {
+                    ui.label("Erase this game's save? This cannot be undone.");
+                    ui.horizontal(|ui| {
+                        if ui.button("Erase").clicked() {
+                            *action = Some(GuiAction::EraseBatterySave);
+                            self.battery_erase_armed = false;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.battery_erase_armed = false;
+                        }
+                    });
+                } else if ui.button("Erase…").clicked() {
+                    self.battery_erase_armed = true;
+                }
+            });
+        if !open {
+            self.battery_erase_armed = false;
+        }
+        self.show_battery_manager = open;
+    }
+
+    /// The second Game Boy on the link cable, at twice native size, with the
+    /// switch that hands it the keyboard and pad. Closing the window unplugs
+    /// it ([`GuiAction::ToggleLinkPartner`]).
+    fn render_link_partner(
+        &mut self,
+        ctx: &Context,
+        action: &mut Option<GuiAction>,
+        session: &SessionUiState,
+    ) {
+        const WIDTH: usize = 160;
+        const HEIGHT: usize = 144;
+        let Some(pixels) = self.link_partner_pixels.take() else { return };
+        let tex = self.link_partner_tex.update(ctx, "link_partner_frame", WIDTH, HEIGHT, pixels);
+        let mut open = true;
+        egui::Window::new("Second Game Boy")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.image((tex, egui::vec2(2.0 * WIDTH as f32, 2.0 * HEIGHT as f32)));
+                let mut controlled = session.link_input_partner;
+                if ui.checkbox(&mut controlled, command_label(ActionKind::SetLinkInputPartner)).changed() {
+                    *action = Some(GuiAction::SetLinkInputPartner(controlled));
+                }
+            });
+        if !open {
+            *action = Some(GuiAction::ToggleLinkPartner);
+        }
+    }
+
+    /// The ROM members of a multi-ROM archive the user just opened: clicking
+    /// one loads it ([`GuiAction::ChooseArchiveEntry`]); closing the window
+    /// drops the archive ([`GuiAction::DismissArchiveChoice`]).
+    fn render_archive_chooser(
+        &mut self,
+        ctx: &Context,
+        action: &mut Option<GuiAction>,
+        session: &SessionUiState,
+    ) {
+        let mut open = true;
+        egui::Window::new("Choose a ROM")
+            .open(&mut open)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(PANEL_BACKGROUND))
+            .show(ctx, |ui| {
+                ui.label("This archive holds several ROMs:");
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        for name in &session.archive_choices {
+                            if ui.button(name).clicked() {
+                                *action = Some(GuiAction::ChooseArchiveEntry(name.clone()));
+                            }
+                        }
+                    });
+            });
+        if !open {
+            *action = Some(GuiAction::DismissArchiveChoice);
+        }
+    }
+
     fn render_breakpoint_panel(&mut self, ctx: &Context, action: &mut Option<GuiAction>, debug: Option<&DebugSnapshot>) {
-        egui::Window::new("Breakpoint Manager")
+        self.debug_window("Breakpoint Manager", [10.0, 460.0])
             .default_width(300.0)
             .frame(egui::Frame::window(&ctx.style_of(ctx.theme())).fill(PANEL_BACKGROUND))
             .show(ctx, |ui| {
@@ -9,9 +9,13 @@ struct Locals {
     transform: mat4x4<f32>,
     // Active source texture size in texels (for the LCD-effect texel math).
     source_size: vec2<f32>,
-    // 0 = off, 1 = LCD grid, 2 = scanlines.
+    // 0 = off, 1 = LCD grid, 2 = scanlines, 3 = CRT.
     effect: u32,
-    _pad: u32,
+    // 1 = sharp bilinear (the bound sampler is linear; the coordinate is
+    // remapped so only the seam between texels blends), 0 = sample as bound.
+    sharp: u32,
+    // 1 = DMG dot-matrix pixel gaps (drawn over the effect), 0 = off.
+    dot_matrix: u32,
 }
 @group(0) @binding(2) var<uniform> r_locals: Locals;
 
@@ -31,14 +35,60 @@ fn vs_main(
 @group(0) @binding(1) var r_tex_sampler: sampler;
 
 @fragment
-fn fs_main(@location(0) tex_coord: vec2<f32>) -> @location(0) vec4<f32> {
-    let color = textureSample(r_tex_color, r_tex_sampler, tex_coord);
+fn fs_main(
+    @location(0) tex_coord: vec2<f32>,
+    @builtin(position) frag_pos: vec4<f32>,
+) -> @location(0) vec4<f32> {
+    // Source texel coordinate and its per-pixel step (screen-space
+    // derivatives, taken up front so they stay in uniform control flow).
+    let t = tex_coord * r_locals.source_size;
+    let stepv = vec2<f32>(dpdx(t.x), dpdy(t.y));
+
+    var uv = tex_coord;
+    if (r_locals.sharp == 1u) {
+        // Sharp bilinear: hold each texel's colour across the largest
+        // whole-factor block of pixels and ramp linearly only over the
+        // remaining seam, i.e. stretch the bilinear weight by that factor.
+        let scale = max(floor(1.0 / abs(stepv)), vec2<f32>(1.0));
+        let region = 0.5 - 0.5 / scale;
+        let d = fract(t) - 0.5;
+        let f = (d - clamp(d, -region, region)) * scale + 0.5;
+        uv = (floor(t) + f) / r_locals.source_size;
+    }
+    let color = textureSample(r_tex_color, r_tex_sampler, uv);
+    let shaded = apply_effect(color, t, stepv, frag_pos);
+    if (r_locals.dot_matrix == 0u) {
+        return shaded;
+    }
+
+    // DMG dot-matrix: the real panel shows its pale substrate between the
+    // pixel cells. Only at whole-number scales of at least 2, where every
+    // cell spans the same pixels (fractional scales would leave an uneven
+    // grid); the boundary pixel is picked as for the LCD grid.
+    let k = 1.0 / abs(stepv);
+    let whole = all(abs(k - round(k)) < vec2<f32>(0.01)) && all(k >= vec2<f32>(1.5));
+    let gap = floor(t.x + stepv.x) != floor(t.x) || floor(t.y + stepv.y) != floor(t.y);
+    if (!(whole && gap)) {
+        return shaded;
+    }
+    let substrate = vec3<f32>(0.776, 0.871, 0.549);
+    return vec4<f32>(mix(shaded.rgb, substrate, 0.35), shaded.a);
+}
+
+// The selected LCD effect applied to the sampled colour `color` at source
+// texel coordinate `t` (per-pixel step `stepv`).
+fn apply_effect(
+    color: vec4<f32>,
+    t: vec2<f32>,
+    stepv: vec2<f32>,
+    frag_pos: vec4<f32>,
+) -> vec4<f32> {
     if (r_locals.effect == 0u) {
         return color;
     }
 
     // Position within the current source texel (0..1 on each axis).
-    let f = fract(tex_coord * r_locals.source_size);
+    let f = fract(t);
 
     if (r_locals.effect == 1u) {
         // LCD grid: darken the boundary PIXEL of each source texel so each
@@ -49,13 +99,22 @@ fn fs_main(@location(0) tex_coord: vec2<f32>) -> @location(0) vec4<f32> {
         // grid invisible exactly where it is most used. A pixel is a boundary
         // pixel when its right/lower neighbour falls in a different texel
         // (screen-space derivatives give the per-pixel texel step).
-        let t = tex_coord * r_locals.source_size;
-        let stepv = vec2<f32>(dpdx(t.x), dpdy(t.y));
         let edge = floor(t.x + stepv.x) != floor(t.x) || floor(t.y + stepv.y) != floor(t.y);
         let grid = select(1.0, 0.80, edge);
         return vec4<f32>(color.rgb * grid, color.a);
     }
 
+    if (r_locals.effect == 3u) {
+        // CRT: deeper scanlines with a squared falloff, under an RGB
+        // aperture grille of one screen pixel per phosphor stripe (the other
+        // two channels dim to 80%, as the LCD grid's gap does).
+        let d = abs(f.y - 0.5) * 2.0;
+        let s = 1.0 - 0.50 * d * d;
+        var mask = vec3<f32>(0.80);
+        mask[u32(frag_pos.x) % 3u] = 1.0;
+        return vec4<f32>(color.rgb * mask * s, color.a);
+    }
+
     // effect == 2u: scanlines — brightness peaks mid-row and dims toward the
     // top/bottom edge of each source row.
     let s = 1.0 - 0.40 * abs(f.y - 0.5) * 2.0;
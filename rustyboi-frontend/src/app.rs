@@ -15,7 +15,7 @@ use std::time::Instant;
 use rustyboi_core_lib::{gb, input, ppu};
 use rustyboi_session::action::{FileData, LoadPurpose};
 use rustyboi_session::apply::FetchPurpose;
-use rustyboi_session::{AbstractInput, GbButton, RunMode, Session, SessionUiState};
+use rustyboi_session::{AbstractInput, DebugDetail, DebugSnapshot, GbButton, RunMode, Session, SessionUiState};
 #[cfg(target_os = "android")]
 use rustyboi_session::UiAction;
 
@@ -23,8 +23,8 @@ use rustyboi_egui_lib::actions::GuiAction;
 
 use crate::contract::{drive_action, Frontend, PauseHint};
 use rustyboi_session::{frame_to_pixels, rgb_to_pixels, DmgPaletteChoice, PixelOrder, SgbPaletteChoice};
-use crate::renderer::{GameFrame, Present, SourceSize};
-use crate::ui_host::{ExtraEvents, UiHost};
+use crate::renderer::{EguiPaint, GameFrame, Present, SourceSize};
+use crate::ui_host::{ExtraEvents, UiFrame, UiHost};
 
 /// Something only the platform (OS/window/fs) can do, surfaced by the app for
 /// the platform to perform after a `draw`.
@@ -66,6 +66,98 @@ pub enum PlatformRequest {
     AndroidLibrary(GuiAction),
 }
 
+/// What the UI is laid out and rendered from, read off the session by
+/// [`App::snapshot_ui`]. It owns everything it carries, so a platform whose
+/// app lives on an emulation thread can send it to the UI thread.
+pub struct UiPrep {
+    paused: bool,
+    session: SessionUiState,
+    debug: Option<DebugSnapshot>,
+    held_pad: std::collections::HashSet<rustyboi_session::input_config::PadButton>,
+    fps: f32,
+    speed: f32,
+    audio: Option<rustyboi_egui_lib::AudioStats>,
+    error: Option<String>,
+    /// The link partner's screen (RGB), for the link-cable panel.
+    link_partner: Option<Vec<u8>>,
+    presentation: Presentation,
+}
+
+impl UiPrep {
+    /// The presentation policy to render the game under.
+    pub fn presentation(&self) -> &Presentation {
+        &self.presentation
+    }
+}
+
+/// The session's presentation policy, pushed into the renderer before each
+/// composite: letterboxing, texture filter, and LCD post-process effect.
+#[derive(Clone, Copy)]
+pub struct Presentation {
+    scaling_mode: rustyboi_session::ScalingMode,
+    aspect_ratio: rustyboi_session::AspectRatio,
+    texture_filter: rustyboi_session::TextureFilter,
+    lcd_effect: rustyboi_session::LcdEffect,
+    dot_matrix: bool,
+}
+
+impl Presentation {
+    fn apply(&self, renderer: &mut dyn Present) {
+        renderer.set_scaling_mode(self.scaling_mode);
+        renderer.set_aspect_ratio(self.aspect_ratio);
+        renderer.set_texture_filter(self.texture_filter);
+        renderer.set_lcd_effect(self.lcd_effect);
+        renderer.set_dot_matrix(self.dot_matrix);
+    }
+}
+
+/// One laid-out UI frame, waiting for [`App::finish_draw`] (or, split across
+/// threads, for [`App::apply_ui`] and [`App::render_laid_out`]).
+pub struct LaidOutUi {
+    paint: EguiPaint,
+    frame: UiFrame,
+}
+
+impl LaidOutUi {
+    /// The action the user took this frame, for [`App::apply_ui`].
+    pub fn take_action(&mut self) -> Option<GuiAction> {
+        self.frame.action.take()
+    }
+
+    /// Whether a menu is open (emulation auto-pauses under it).
+    pub fn menu_open(&self) -> bool {
+        self.frame.menu_open
+    }
+}
+
+/// A presented frame packed to RGBA by [`App::picture`], owned so it can
+/// cross to the thread that renders it.
+pub struct Picture {
+    size: SourceSize,
+    rgba: Vec<u8>,
+}
+
+impl Picture {
+    pub fn game_frame(&self) -> GameFrame<'_> {
+        GameFrame { size: self.size, rgba: &self.rgba }
+    }
+
+    /// The pixel buffer, to hand back to [`App::picture`] for reuse.
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.rgba
+    }
+}
+
+// Native platforms give the app to an emulation thread for its lifetime.
+#[cfg(not(target_arch = "wasm32"))]
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<App>();
+    assert_send::<UiPrep>();
+    assert_send::<Picture>();
+    assert_send::<PlatformRequest>();
+};
+
 /// The portable app.
 ///
 /// It deliberately does NOT own the [`UiHost`] or [`Renderer`]: those are
@@ -88,6 +180,9 @@ pub struct App {
 
     current_rom_path: Option<String>,
     current_bios_path: Option<String>,
+    /// Path of a multi-ROM archive held in the session for the chooser; it
+    /// becomes `current_rom_path` once a member is picked.
+    archive_path: Option<String>,
 
     input: AbstractInput,
 
@@ -107,12 +202,23 @@ pub struct App {
     manually_paused: bool,
     auto_paused_no_content: bool,
     breakpoint_hit: bool,
+    /// Held paused because the window lost focus with Settings → Pause When
+    /// Unfocused on; like an open menu, it never touches `user_paused`.
+    focus_paused: bool,
 
     // Perf readout. The app holds NO pacing logic and reads no pacing clock:
     // the platform's tick loop owns the shared `rustyboi_session::pacing`
     // Regulator and feeds this meter via [`App::note_frames`].
     meter: rustyboi_session::pacing::RateMeter,
+    /// Applies Settings → Frame Skip to each tick's newest frame; see
+    /// [`App::note_grant`].
+    frame_skipper: rustyboi_session::pacing::FrameSkipper,
+    /// This tick's frame was skipped: the renderer re-presents the last one.
+    skip_present: bool,
     last_title_update: Instant,
+    /// The platform's latest audio-device readout for the FPS overlay (see
+    /// [`App::set_audio_stats`]); `None` without a device.
+    audio_stats: Option<rustyboi_egui_lib::AudioStats>,
 
     /// The most recent chrome inset in *logical points*: how much wider/taller
     /// the window is than the egui central region (menu bar + any status
@@ -129,6 +235,11 @@ pub struct App {
     /// Reused RGBA upload scratch for `present`, so the per-frame frame-to-RGBA
     /// conversion (up to SGB 256×224×4) doesn't heap-allocate every frame.
     rgba_scratch: Vec<u8>,
+
+    /// The system clipboard, opened on the first Copy Screenshot and kept:
+    /// on X11 the copied image is served from it, so it must outlive the copy.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    clipboard: Option<arboard::Clipboard>,
 }
 
 impl App {
@@ -149,6 +260,11 @@ impl App {
         // at startup — only a user SetPalette writes config).
         session.init_palette_choice(palette);
         session.init_sgb_palette(sgb_palette);
+        // An empty slot runs as on hardware (open bus, the boot ROM stuck at
+        // its logo check); say why the screen stays blank.
+        if !session.gb().has_rom() {
+            session.flash_osd("No cartridge — load a ROM");
+        }
         let now = Instant::now();
         App {
             session,
@@ -159,6 +275,7 @@ impl App {
             step_single_cycle: false,
             current_rom_path: rom_path,
             current_bios_path: bios_path,
+            archive_path: None,
             input: AbstractInput::none(),
             held_pad: std::collections::HashSet::new(),
             pending_requests: Vec::new(),
@@ -166,11 +283,17 @@ impl App {
             manually_paused: should_pause,
             auto_paused_no_content: should_pause,
             breakpoint_hit: false,
+            focus_paused: false,
             meter: rustyboi_session::pacing::RateMeter::new(),
+            frame_skipper: rustyboi_session::pacing::FrameSkipper::new(),
+            skip_present: false,
             last_title_update: now,
+            audio_stats: None,
             content_inset: (0.0, 0.0),
             safe_insets: [0.0; 4],
             rgba_scratch: Vec::new(),
+            #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+            clipboard: None,
         }
     }
 
@@ -208,6 +331,11 @@ impl App {
         self.meter.fps()
     }
 
+    /// Measured game speed as a percentage of the running machine's rate.
+    pub fn speed_percent(&self) -> f64 {
+        self.meter.speed_percent()
+    }
+
     /// Record one tick into the shared rate meter: `emulated` frames advanced
     /// (the FPS readout is game speed). The platform's tick loop calls this
     /// every tick, including idle ones.
@@ -219,6 +347,24 @@ impl App {
         self.meter.record(now_seconds, emulated);
     }
 
+    /// Decide whether this tick's newest frame reaches the display, from the
+    /// frames it `emulated` and whether the platform's regulator is `behind`
+    /// ([`Regulator::is_behind`](rustyboi_session::pacing::Regulator::is_behind)).
+    /// Call after emulating and before [`finish_draw`](Self::finish_draw) or
+    /// [`picture`](Self::picture).
+    /// Ticks that emulated nothing always re-present.
+    pub fn note_grant(&mut self, emulated: u32, behind: bool) {
+        self.skip_present =
+            emulated > 0 && !self.frame_skipper.present(self.session.frame_skip(), behind);
+    }
+
+    /// Hand over the audio device's latency and underrun count for the FPS
+    /// overlay. The platform owns the device, so its tick loop measures and
+    /// calls this; the app only displays it.
+    pub fn set_audio_stats(&mut self, stats: Option<rustyboi_egui_lib::AudioStats>) {
+        self.audio_stats = stats;
+    }
+
     /// Cumulative drift versus a perfect 59.7275fps timeline (diagnostics).
     pub fn drift_frames(&self, now_seconds: f64) -> f64 {
         self.meter.drift_frames(now_seconds)
@@ -237,6 +383,12 @@ impl App {
         self.session.content_size()
     }
 
+    /// `content_size` reshaped to the chosen aspect ratio: the shape the
+    /// window should fit so the frame fills it without bars.
+    pub fn display_size(&self) -> (u32, u32) {
+        self.session.display_size()
+    }
+
     /// The chrome inset (menu bar + status panel) in logical points measured on
     /// the last `draw`, so the platform can size the window to
     /// `content*scale + inset`. `(0, 0)` before the first frame.
@@ -287,10 +439,31 @@ impl App {
         self.session.quickload().map(|_| ()).map_err(|e| e.to_string())
     }
 
+    pub fn undo_load_state(&mut self) -> Result<(), String> {
+        self.session.undo_load_state().map_err(|e| e.to_string())
+    }
+
+    pub fn save_slot(&mut self, slot: u32, timestamp: u64) -> Result<(), String> {
+        self.session.save_slot(slot, timestamp).map_err(|e| e.to_string())
+    }
+
+    pub fn load_slot(&mut self, slot: u32) -> Result<(), String> {
+        self.session.load_slot(slot).map(|_| ()).map_err(|e| e.to_string())
+    }
+
     pub fn toggle_fast_forward(&mut self) {
         self.session.toggle_fast_forward();
     }
 
+    /// Report whether the hold-to-fast-forward chord is down this tick.
+    pub fn hold_fast_forward(&mut self, held: bool) {
+        self.session.hold_fast_forward(held);
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.session.toggle_mute();
+    }
+
     pub fn is_fast_forward(&self) -> bool {
         matches!(self.session.mode(), RunMode::FastForward(_))
     }
@@ -320,6 +493,27 @@ impl App {
         self.is_paused = self.manually_paused;
     }
 
+    /// Pause or resume outright, for remote control (the control socket and
+    /// debug server). Unlike [`App::toggle_pause`] this doesn't depend on the
+    /// last choice, so resuming also leaves a breakpoint stop.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.user_paused = paused;
+        self.manually_paused = paused || self.error_state.is_some();
+        self.is_paused = self.manually_paused;
+    }
+
+    /// The window gained or lost focus. With pause-on-focus-loss on, losing
+    /// focus pauses (no frames run, so the audio goes quiet too); regaining
+    /// it resumes unless the user paused in the meantime.
+    pub fn set_window_focused(&mut self, focused: bool) {
+        self.focus_paused = !focused && self.session.pause_on_focus_loss();
+        if self.focus_paused {
+            self.is_paused = true;
+        } else if !self.manually_paused && self.error_state.is_none() {
+            self.is_paused = false;
+        }
+    }
+
     /// Request a debug single-frame step (honored while paused).
     pub fn request_step_frame(&mut self) {
         self.step_single_frame = true;
@@ -339,9 +533,26 @@ impl App {
 
     /// Load a ROM from raw bytes (platform resolves `FileData::Path` → bytes on
     /// desktop; web/Android pass bytes directly). `path` is the display/name for
-    /// title + printer output (`None` for content-only sources).
-    pub fn load_rom_bytes(&mut self, bytes: Vec<u8>, path: Option<String>) -> Result<(), String> {
-        self.session.finish_load_rom(&bytes).map_err(|e| e.to_string())?;
+    /// title + printer output (`None` for content-only sources). `patch` is a
+    /// soft-patch the platform found next to the ROM, applied in memory.
+    pub fn load_rom_bytes(
+        &mut self,
+        bytes: Vec<u8>,
+        path: Option<String>,
+        patch: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        match patch {
+            Some(patch) => self.session.finish_load_soft_patched_rom(&bytes, &patch),
+            None => self.session.finish_load_rom(&bytes),
+        }
+        .map_err(|e| e.to_string())?;
+        // Only desktop paths can be reopened (File → Recent) or hold a `.sav`
+        // beside them; elsewhere the path is just a display name.
+        #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+        if let Some(path) = &path {
+            self.attach_save_files(path);
+            self.note_recent_rom(path);
+        }
         self.current_rom_path = path;
         self.error_state = None;
         self.frame = None;
@@ -354,6 +565,37 @@ impl App {
         Ok(())
     }
 
+    /// List the ROM at `path` under File → Recent. The path is stored
+    /// absolute, so an entry typed relative on the command line still opens
+    /// once the app runs from another directory.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    pub fn note_recent_rom(&mut self, path: &str) {
+        match std::fs::canonicalize(path) {
+            Ok(absolute) => self.session.note_recent_rom(&absolute.to_string_lossy()),
+            Err(_) => self.session.note_recent_rom(path),
+        }
+    }
+
+    /// Give a ROM loaded from `path` the same `.sav`/`.rtc` files a command
+    /// line load gets, in the configured save folder. When they can't be
+    /// opened the game still runs, its save kept by the storage port alone,
+    /// and the status line says so.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    fn attach_save_files(&mut self, path: &str) {
+        let save_dir = self.session.save_dir().map(std::path::PathBuf::from);
+        let opts = rustyboi_core_lib::cartridge::LoadOptions {
+            save_dir: save_dir.as_deref(),
+            rtc_format: self.session.rtc_format(),
+            ..Default::default()
+        };
+        if let Some(cart) = self.session.gb_mut().cartridge_mut()
+            && let Err(e) = cart.attach_sidecars(std::path::Path::new(path), opts)
+        {
+            let message = format!("Save files unavailable, keeping the save in app data: {e}");
+            self.pending_requests.push(PlatformRequest::Status(message));
+        }
+    }
+
     /// The BIOS path currently attached (so the platform can re-read it on a
     /// state load, mirroring the old World behavior).
     pub fn current_bios_path(&self) -> Option<&str> {
@@ -403,7 +645,7 @@ impl App {
     /// Serialize the current machine state to bytes (for File → Save State; the
     /// platform writes them).
     pub fn state_bytes(&mut self) -> Result<Vec<u8>, String> {
-        self.session.gb_mut().to_state_bytes().map_err(|e| e.to_string())
+        self.session.state_file_bytes().map_err(|e| e.to_string())
     }
 
     // --- UI state snapshot --------------------------------------------------
@@ -420,23 +662,41 @@ impl App {
     fn present(&mut self) -> Option<GameFrame<'_>> {
         // All conversions fill the reused `rgba_scratch` so the desktop present
         // path never heap-allocates the (up to 256×224×4) RGBA buffer per frame.
-        let scratch = &mut self.rgba_scratch;
+        let mut scratch = std::mem::take(&mut self.rgba_scratch);
+        let size = self.pack_frame(&mut scratch);
+        self.rgba_scratch = scratch;
+        Some(GameFrame { size: size?, rgba: &self.rgba_scratch })
+    }
+
+    /// Pack the presented frame into `out` as RGBA, returning its source size
+    /// (`None` before the first frame). The packers write every byte, so `out`
+    /// is only resized, never cleared first.
+    fn pack_frame(&self, out: &mut Vec<u8>) -> Option<SourceSize> {
         if self.session.sgb_border()
             && let Some(rgb) = self.session.gb().sgb_composited_frame()
         {
-            scratch.clear();
-            scratch.resize((rgb.len() / 3) * 4, 0);
-            rgb_to_pixels(&rgb[..], PixelOrder::Rgba, scratch);
-            return Some(GameFrame { size: SourceSize::Sgb, rgba: scratch });
+            out.resize((rgb.len() / 3) * 4, 0);
+            rgb_to_pixels(&rgb[..], PixelOrder::Rgba, out);
+            return Some(SourceSize::Sgb);
         }
 
         // The core presents an always-RGB frame (DMG palette + correction already
         // applied in-core); the shared packer just expands it to RGBA.
         let gb_frame = self.frame.as_ref()?;
-        scratch.clear();
-        scratch.resize(ppu::FRAMEBUFFER_SIZE * 4, 0);
-        frame_to_pixels(gb_frame, PixelOrder::Rgba, scratch);
-        Some(GameFrame { size: SourceSize::Gb, rgba: scratch })
+        out.resize(ppu::FRAMEBUFFER_SIZE * 4, 0);
+        frame_to_pixels(gb_frame, PixelOrder::Rgba, out);
+        Some(SourceSize::Gb)
+    }
+
+    /// The presented frame packed into `buffer` (a spent [`Picture`]'s, so
+    /// steady state allocates nothing), or `None` when the last grant's frame
+    /// is skipped (Settings → Frame Skip) or there is none yet.
+    pub fn picture(&mut self, mut buffer: Vec<u8>) -> Option<Picture> {
+        if self.skip_present {
+            return None;
+        }
+        let size = self.pack_frame(&mut buffer)?;
+        Some(Picture { size, rgba: buffer })
     }
 
     // --- run one emulation frame -------------------------------------------
@@ -525,9 +785,20 @@ impl App {
         // pacing clock, so game speed is identical on every platform and
         // host-timer quirks (macOS sleep coalescing) cannot slow it.
         if self.session.gb().get_breakpoints().is_empty() {
-            let output = self.session.run_frame(self.input);
-            self.frame = Some(output.frame);
-            FrameStep { audio: output.audio, pump_workers: true, advanced: output.advanced }
+            // A core panic lands on the crash panel (with its recovery
+            // snapshot offer) instead of taking the whole app down.
+            let (session, input) = (&mut self.session, self.input);
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| session.run_frame(input))) {
+                Ok(output) => {
+                    self.frame = Some(output.frame);
+                    FrameStep { audio: output.audio, pump_workers: true, advanced: output.advanced }
+                }
+                Err(p) => {
+                    self.error_state = Some(panic_message(p, "during frame"));
+                    self.frame = None;
+                    FrameStep::default()
+                }
+            }
         } else {
             match self.run_frame_on_core() {
                 Some((frame, bp)) => {
@@ -586,7 +857,7 @@ impl App {
         } else if paused {
             format!("{app} - PAUSED | {:.1} FPS", self.fps())
         } else {
-            format!("{app} | {:.1} FPS", self.fps())
+            format!("{app} | {:.1} FPS ({:.0}%)", self.fps(), self.speed_percent())
         };
         Some(title)
     }
@@ -609,6 +880,13 @@ impl App {
     /// bytes when the OS is required (reading a picked ROM/state file). It
     /// returns `None` for actions it doesn't handle (all the pure ones), which
     /// the app then applies itself.
+    ///
+    /// The phases are public for a platform whose app lives on an emulation
+    /// thread: there the app takes a [`snapshot_ui`](Self::snapshot_ui) and
+    /// [`apply_ui`](Self::apply_ui)s the result, while the UI thread runs
+    /// [`lay_out_ui`](Self::lay_out_ui) and
+    /// [`render_laid_out`](Self::render_laid_out), neither of which needs the
+    /// `App`.
     pub fn draw(
         &mut self,
         window: &winit::window::Window,
@@ -616,45 +894,91 @@ impl App {
         renderer: &mut dyn Present,
         extra_events: ExtraEvents,
         fullscreen: bool,
-        mut resolve_gui_action: impl FnMut(&GuiAction) -> Option<ResolvedAction>,
+        resolve_gui_action: impl FnMut(&GuiAction) -> Option<ResolvedAction>,
     ) -> Vec<PlatformRequest> {
-        let mut requests = Vec::new();
-
-        let paused_for_ui = self.manually_paused || self.error_state.is_some();
-        let ui_state = self.ui_state();
-
-        // Build the debug read-model only when a debug panel is open (the common
-        // case builds nothing). Detail comes from the Gui's open-panel state,
-        // read before we borrow the session so the borrows don't overlap.
-        let debug_snapshot = if ui.any_debug_panel_open() {
-            Some(self.session.debug_snapshot(ui.wanted_debug_detail()))
-        } else {
-            None
-        };
+        let prep = self.prepare_ui(ui);
+        let laid_out = Self::lay_out_ui(window, ui, &prep, extra_events, fullscreen);
+        self.finish_draw(ui, renderer, laid_out, resolve_gui_action)
+    }
+
+    /// [`snapshot_ui`](Self::snapshot_ui) for the debug panels `ui` has open.
+    pub fn prepare_ui(&mut self, ui: &UiHost) -> UiPrep {
+        self.snapshot_ui(ui.any_debug_panel_open().then(|| ui.wanted_debug_detail()))
+    }
+
+    /// Snapshot what the UI renders from: the menus' [`SessionUiState`], and
+    /// the debug read-model at `debug` detail while a debug panel is open
+    /// (the common case, `None`, builds nothing).
+    pub fn snapshot_ui(&mut self, debug: Option<DebugDetail>) -> UiPrep {
+        self.session.set_apu_scope(debug.is_some_and(|detail| detail.apu));
+        UiPrep {
+            paused: self.manually_paused || self.error_state.is_some(),
+            session: self.ui_state(),
+            debug: debug.map(|detail| self.session.debug_snapshot(detail)),
+            held_pad: self.held_pad.clone(),
+            fps: self.fps() as f32,
+            speed: self.speed_percent() as f32,
+            audio: self.audio_stats,
+            error: self.error_state.clone(),
+            link_partner: self.session.link_partner_frame().map(|f| f.rgb().to_vec()),
+            presentation: self.presentation(),
+        }
+    }
 
-        // Run the UI first, collecting its output, then drop the borrow.
-        let (paint, ui_frame) = {
-            // Desktop renders every frame (force_repaint: true); repaint-gating is
-            // a web concern (its main thread also composites the worker's frames).
-            ui.run(
-                window,
-                crate::ui_host::UiRunInputs {
-                    paused: paused_for_ui,
-                    debug: debug_snapshot.as_ref(),
-                    fullscreen,
-                    session: &ui_state,
-                    extra_events,
-                    held_pad: &self.held_pad,
-                    force_repaint: true,
-                    fps: self.fps() as f32,
-                },
-            )
-        };
+    fn presentation(&self) -> Presentation {
+        Presentation {
+            scaling_mode: self.session.scaling_mode(),
+            aspect_ratio: self.session.aspect_ratio(),
+            texture_filter: self.session.texture_filter(),
+            lcd_effect: self.session.lcd_effect().resolve(self.session.hardware()),
+            dot_matrix: self.session.dot_matrix(),
+        }
+    }
 
-        // Dispatch the action.
-        if let Some(action) = ui_frame.action {
-            self.dispatch_action(action, &mut requests, &mut resolve_gui_action);
+    /// Lay out one egui frame from `prep`.
+    pub fn lay_out_ui(
+        window: &winit::window::Window,
+        ui: &mut UiHost,
+        prep: &UiPrep,
+        extra_events: ExtraEvents,
+        fullscreen: bool,
+    ) -> LaidOutUi {
+        rustyboi_core_lib::profile_span!("ui_layout");
+        ui.set_link_partner_frame(prep.link_partner.as_deref());
+        if let Some(err) = &prep.error {
+            ui.set_error(err.clone());
         }
+        // Desktop renders every frame (force_repaint: true); repaint-gating is
+        // a web concern (its main thread also composites the worker's frames).
+        let (paint, frame) = ui.run(
+            window,
+            crate::ui_host::UiRunInputs {
+                paused: prep.paused,
+                debug: prep.debug.as_ref(),
+                fullscreen,
+                session: &prep.session,
+                extra_events,
+                held_pad: &prep.held_pad,
+                force_repaint: true,
+                fps: prep.fps,
+                speed: prep.speed,
+                audio: prep.audio,
+            },
+        );
+        LaidOutUi { paint, frame }
+    }
+
+    /// Dispatch a laid-out frame's action, then render the game with the UI
+    /// on top. Returns the platform requests produced, as [`draw`](Self::draw).
+    pub fn finish_draw(
+        &mut self,
+        ui: &mut UiHost,
+        renderer: &mut dyn Present,
+        mut laid_out: LaidOutUi,
+        resolve_gui_action: impl FnMut(&GuiAction) -> Option<ResolvedAction>,
+    ) -> Vec<PlatformRequest> {
+        let action = laid_out.take_action();
+        let mut requests = self.apply_ui(action, laid_out.menu_open(), resolve_gui_action);
 
         // Apply any UI-error-overlay clears the shared driver requested.
         requests.retain(|r| {
@@ -666,8 +990,42 @@ impl App {
             }
         });
 
-        // Auto-pause when a menu is open, respecting manual pause.
-        let should_be_paused = self.manually_paused || ui_frame.menu_open;
+        // Surface any error to the UI.
+        if let Some(err) = self.error_state.clone() {
+            ui.set_error(err);
+        }
+
+        let presentation = self.presentation();
+        let safe_insets = self.safe_insets;
+        // A skipped frame is never packed or uploaded; `None` re-presents the
+        // previous one under this tick's UI.
+        let game = if self.skip_present { None } else { self.present() };
+        let inset = Self::render_laid_out(renderer, laid_out, &presentation, game.as_ref(), safe_insets);
+        self.content_inset = inset;
+
+        requests
+    }
+
+    /// Dispatch a laid-out frame's `action` and follow the UI's menu state
+    /// into the auto-pause. Returns the platform requests produced, as
+    /// [`draw`](Self::draw); a `ClearError` among them is for the platform to
+    /// apply to its `UiHost`.
+    pub fn apply_ui(
+        &mut self,
+        action: Option<GuiAction>,
+        menu_open: bool,
+        mut resolve_gui_action: impl FnMut(&GuiAction) -> Option<ResolvedAction>,
+    ) -> Vec<PlatformRequest> {
+        let mut requests = Vec::new();
+
+        // Dispatch the action.
+        if let Some(action) = action {
+            self.dispatch_action(action, &mut requests, &mut resolve_gui_action);
+        }
+
+        // Auto-pause when a menu is open or the window is unfocused,
+        // respecting manual pause.
+        let should_be_paused = self.manually_paused || menu_open || self.focus_paused;
         if should_be_paused != self.is_paused {
             if should_be_paused {
                 self.is_paused = true;
@@ -676,50 +1034,58 @@ impl App {
             }
         }
 
-        // Surface any error to the UI.
-        if let Some(err) = self.error_state.clone() {
-            ui.set_error(err);
+        if self.error_state.is_some() {
             self.manually_paused = self.user_paused || self.error_state.is_some();
         }
 
-        // Measure the chrome inset (menu bar + status panel) in logical points
-        // from this frame's surface vs. central region, so the platform can grow
-        // the window to make the central rect exactly content*scale. Dynamic:
-        // recomputed every frame, so an upstream egui size change is absorbed.
+        requests
+    }
+
+    /// Render `game` (`None` re-presents the last one uploaded) letterboxed
+    /// into the laid-out frame's central region, shrunk by the platform
+    /// `safe_insets` (see [`set_safe_insets`](Self::set_safe_insets)), with
+    /// egui on top. Returns the chrome inset (menu bar + status panel) in
+    /// logical points, so the platform can size the window to
+    /// `content*scale + inset`.
+    pub fn render_laid_out(
+        renderer: &mut dyn Present,
+        laid_out: LaidOutUi,
+        presentation: &Presentation,
+        game: Option<&GameFrame>,
+        safe_insets: [f32; 4],
+    ) -> (f32, f32) {
+        let LaidOutUi { paint, frame: ui_frame } = laid_out;
+
+        // Measure the chrome inset from this frame's surface vs. central
+        // region. Dynamic: recomputed every frame, so an upstream egui size
+        // change is absorbed.
         let ppp = paint.pixels_per_point.max(0.01);
         let (surf_w, surf_h) = renderer.surface_size();
         let inset_w = ((surf_w as f32 - ui_frame.region.width).max(0.0)) / ppp;
         let inset_h = ((surf_h as f32 - ui_frame.region.height).max(0.0)) / ppp;
-        self.content_inset = (inset_w, inset_h);
-
-        // Render: game letterboxed into the central region, egui on top. Push the
-        // current presentation policy from the session config first (one shared
-        // site): letterboxing, texture filter, and LCD post-process effect.
-        renderer.set_scaling_mode(self.session.scaling_mode());
-        renderer.set_texture_filter(self.session.texture_filter());
-        renderer.set_lcd_effect(self.session.lcd_effect().resolve(self.session.hardware()));
+
+        // Render: game letterboxed into the central region, egui on top,
+        // under the current presentation policy.
+        presentation.apply(renderer);
         // Shrink the game region by the platform safe-area insets so it is not
         // drawn behind system bars / a display cutout (Android). No-op elsewhere.
-        // Computed before `present` borrows self.
-        let [si_l, si_t, si_r, si_b] = self.safe_insets;
+        let [si_l, si_t, si_r, si_b] = safe_insets;
         let mut region = ui_frame.region;
         region.x += si_l;
         region.y += si_t;
         region.width = (region.width - si_l - si_r).max(0.0);
         region.height = (region.height - si_t - si_b).max(0.0);
-        let game = self.present();
+        rustyboi_core_lib::profile_span!("render");
         // Reconfigure + retry next frame (the platform loop syncs the surface to
         // the window size before the next render). Validation errors surface
         // through the device error scope, so any other status just skips this
         // frame (Timeout/Occluded never reach here — render() maps them to Ok).
-        if let Err(wgpu::SurfaceStatus::Lost | wgpu::SurfaceStatus::Outdated) =
-            renderer.render(game.as_ref(), region, paint)
-        {
+        if let Err(wgpu::SurfaceStatus::Lost | wgpu::SurfaceStatus::Outdated) = renderer.render(game, region, paint) {
             let (w, h) = renderer.surface_size();
             renderer.resize(w, h);
         }
 
-        requests
+        (inset_w, inset_h)
     }
 
     /// Apply a UI action. ROM/state loads need the platform's file resolver, so
@@ -742,18 +1108,18 @@ impl App {
             | GuiAction::ImportRtc(_)
             | GuiAction::ApplyPatch(_)
             | GuiAction::LoadMovie(_)
-            | GuiAction::LoadSgbFirmware(_)) => {
+            | GuiAction::LoadSgbFirmware(_)
+            | GuiAction::LoadPaletteFile(_)
+            | GuiAction::ImportInputProfile(_)) => {
                 match resolve(&action) {
-                    Some(ResolvedAction::LoadRom { bytes, path }) => {
-                        match self.load_rom_bytes(bytes, path) {
-                            Ok(()) => {
-                                self.manually_paused = self.user_paused;
-                                requests.push(PlatformRequest::ClearError);
-                                let (w, h) = self.content_size();
-                                requests.push(PlatformRequest::ResizeContent { width: w, height: h });
-                                requests.push(PlatformRequest::Status("ROM loaded".into()));
+                    Some(ResolvedAction::LoadRom { bytes, path, patch }) => {
+                        match self.session.offer_archive_choice(&bytes, patch.as_deref()) {
+                            Some(outcome) => {
+                                self.archive_path = path;
+                                crate::contract::route_outcome(self, outcome);
+                                requests.append(&mut self.pending_requests);
                             }
-                            Err(e) => requests.push(PlatformRequest::Error(format!("Failed to load ROM: {e}"))),
+                            None => self.finish_rom_load(bytes, path, patch, requests),
                         }
                     }
                     Some(ResolvedAction::LoadState { state, reload_rom }) => {
@@ -790,10 +1156,26 @@ impl App {
                             app.manually_paused = app.user_paused;
                         });
                     }
+                    Some(ResolvedAction::LoadPalette { bytes }) => {
+                        self.finish_file(LoadPurpose::Palette, &bytes, requests, |_| {});
+                    }
+                    Some(ResolvedAction::ImportInputProfile { bytes }) => {
+                        self.finish_file(LoadPurpose::InputProfile, &bytes, requests, |_| {});
+                    }
                     None => {}
                 }
             }
 
+            // The archive chooser's pick is a ROM load, so it needs the same
+            // path / pause bookkeeping as one.
+            GuiAction::ChooseArchiveEntry(name) => match self.session.take_archive_entry(&name) {
+                Ok((bytes, patch)) => {
+                    let path = self.archive_path.take();
+                    self.finish_rom_load(bytes, path, patch, requests);
+                }
+                Err(e) => requests.push(PlatformRequest::Error(format!("Failed to load ROM: {e}"))),
+            },
+
             // Everything else: one shared behavior path via the contract driver.
             other => {
                 drive_action(self, other, now_epoch_secs());
@@ -802,6 +1184,30 @@ impl App {
         }
     }
 
+    /// Load a resolved ROM through [`load_rom_bytes`](Self::load_rom_bytes) and
+    /// report it the way `Session::finish_file` does for hosts without the
+    /// app-side bookkeeping.
+    fn finish_rom_load(
+        &mut self,
+        bytes: Vec<u8>,
+        path: Option<String>,
+        patch: Option<Vec<u8>>,
+        requests: &mut Vec<PlatformRequest>,
+    ) {
+        match self.load_rom_bytes(bytes, path, patch) {
+            Ok(()) => {
+                self.manually_paused = self.user_paused;
+                requests.push(PlatformRequest::ClearError);
+                let (w, h) = self.content_size();
+                requests.push(PlatformRequest::ResizeContent { width: w, height: h });
+                requests.push(PlatformRequest::Status(self.session.load_status("ROM loaded")));
+                // A save-file warning from the load outranks the plain status.
+                requests.append(&mut self.pending_requests);
+            }
+            Err(e) => requests.push(PlatformRequest::Error(format!("Failed to load ROM: {e}"))),
+        }
+    }
+
     /// Hand resolved file `bytes` to [`Session::finish_file`], run `on_success`
     /// for the app-side bookkeeping the session can't know about, then route the
     /// outcome through the same `Frontend` capability methods `drive_action`
@@ -821,17 +1227,42 @@ impl App {
         crate::contract::route_outcome(self, outcome);
         requests.append(&mut self.pending_requests);
     }
+
+    /// Put a `width x height` RGBA8 image on the system clipboard.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    fn put_image_on_clipboard(&mut self, width: u32, height: u32, rgba: Vec<u8>) -> Result<(), String> {
+        let clipboard = match &mut self.clipboard {
+            Some(clipboard) => clipboard,
+            None => self.clipboard.insert(arboard::Clipboard::new().map_err(|e| e.to_string())?),
+        };
+        let image = arboard::ImageData {
+            width: width as usize,
+            height: height as usize,
+            bytes: std::borrow::Cow::Owned(rgba),
+        };
+        clipboard.set_image(image).map_err(|e| e.to_string())
+    }
+
+    /// Mobile has no system clipboard for images (and the menus leave the
+    /// action out); the web copies on the page, not through the app.
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    fn put_image_on_clipboard(&mut self, _width: u32, _height: u32, _rgba: Vec<u8>) -> Result<(), String> {
+        Err("no clipboard on this platform".into())
+    }
 }
 
 /// A `GuiAction` the platform resolved into bytes the app can apply.
 pub enum ResolvedAction {
-    LoadRom { bytes: Vec<u8>, path: Option<String> },
+    /// `patch`: a same-named `.bps`/`.ups`/`.ips` found next to the ROM.
+    LoadRom { bytes: Vec<u8>, path: Option<String>, patch: Option<Vec<u8>> },
     LoadState { state: Vec<u8>, reload_rom: Option<(String, Vec<u8>)> },
     ImportBattery { bytes: Vec<u8> },
     ImportRtc { bytes: Vec<u8> },
     ApplyPatch { bytes: Vec<u8> },
     LoadMovie { bytes: Vec<u8> },
     LoadSgbFirmware { bytes: Vec<u8> },
+    LoadPalette { bytes: Vec<u8> },
+    ImportInputProfile { bytes: Vec<u8> },
 }
 
 /// Human label for an identified SGB firmware image, used in the status line.
@@ -896,6 +1327,13 @@ impl Frontend for App {
             .push(PlatformRequest::FetchUrl { urls, purpose });
     }
 
+    fn copy_image(&mut self, width: u32, height: u32, rgba: Vec<u8>) {
+        match self.put_image_on_clipboard(width, height, rgba) {
+            Ok(()) => self.set_status("Screenshot copied".into()),
+            Err(e) => self.set_error(format!("Failed to copy screenshot: {e}")),
+        }
+    }
+
     fn on_pause_changed(&mut self, hint: PauseHint) {
         match hint {
             PauseHint::TogglePause => {
@@ -926,6 +1364,12 @@ impl Frontend for App {
                 self.error_state = None;
                 self.frame = None;
             }
+            PauseHint::Recover => {
+                self.error_state = None;
+                self.frame = None;
+                self.manually_paused = self.user_paused;
+                self.is_paused = self.manually_paused;
+            }
             PauseHint::Load => {}
         }
     }
@@ -1138,6 +1582,20 @@ mod pause_and_load_tests {
     }
 
     // Restart clears error/frame and every pause flag (fresh, running machine).
+    #[test]
+    fn set_paused_resumes_from_a_breakpoint_stop() {
+        let mut a = paused_app();
+        a.set_paused(false);
+        // A breakpoint stop pauses without touching the user's choice.
+        a.is_paused = true;
+        a.breakpoint_hit = true;
+        a.set_paused(false);
+        assert!(!a.is_paused, "one resume leaves the stop");
+        a.set_paused(true);
+        a.set_paused(true);
+        assert!(a.user_paused && a.manually_paused && a.is_paused, "idempotent");
+    }
+
     #[test]
     fn on_pause_changed_restart_clears_everything() {
         let mut a = paused_app();
@@ -1170,6 +1628,27 @@ mod pause_and_load_tests {
         assert!(a.user_paused && a.manually_paused);
     }
 
+    // Losing focus pauses only with the setting on, and regaining it never
+    // overrides a pause the user made while away.
+    #[test]
+    fn focus_loss_pauses_without_fighting_user_pause() {
+        let mut a = paused_app();
+        a.toggle_pause();
+        a.set_window_focused(false);
+        assert!(!a.is_paused, "off by default");
+
+        a.session.apply(rustyboi_session::UiAction::SetPauseOnFocusLoss(true), 0);
+        a.set_window_focused(false);
+        assert!(a.is_paused && !a.user_paused);
+        a.set_window_focused(true);
+        assert!(!a.is_paused, "resumes on focus");
+
+        a.set_window_focused(false);
+        a.toggle_pause();
+        a.set_window_focused(true);
+        assert!(a.is_paused && a.user_paused, "user pause kept");
+    }
+
     // SetHardware rebuilds the machine (clear error + frame) but leaves the pause
     // state exactly as the user had it.
     #[test]
@@ -1185,6 +1664,23 @@ mod pause_and_load_tests {
         assert!(!a.is_paused && !a.user_paused && !a.manually_paused, "pause untouched");
     }
 
+    // Recovering from a crash clears the error and runs again, unless the
+    // user had paused before it.
+    #[test]
+    fn on_pause_changed_recover_resumes_unless_user_paused() {
+        let mut a = paused_app();
+        a.user_paused = false;
+        a.error_state = Some("boom".into());
+        a.on_pause_changed(PauseHint::Recover);
+        assert!(a.error_state.is_none());
+        assert!(!a.is_paused && !a.manually_paused);
+
+        let mut a = paused_app();
+        a.error_state = Some("boom".into());
+        a.on_pause_changed(PauseHint::Recover);
+        assert!(a.is_paused && a.manually_paused, "user pause kept");
+    }
+
     // Load is a no-op in the pause state machine (loads do their own bookkeeping).
     #[test]
     fn on_pause_changed_load_is_a_noop() {
@@ -1193,16 +1689,68 @@ mod pause_and_load_tests {
         assert!(a.user_paused && a.manually_paused && a.is_paused, "unchanged");
     }
 
+    // Starting with an empty slot says so on screen, and the cart-less machine
+    // keeps producing frames instead of faulting.
+    #[test]
+    fn empty_slot_flashes_a_notice_and_keeps_running() {
+        let a = paused_app();
+        assert_eq!(a.session.osd().map(|m| m.text.as_str()), Some("No cartridge — load a ROM"));
+        let mut a = App::new(
+            Session::new(Config::default(), ports(), [0u8; 32]),
+            DmgPaletteChoice::Green,
+            SgbPaletteChoice::Auto,
+            None,
+            None,
+            false,
+        );
+        for _ in 0..10 {
+            assert!(a.run_frame_on_core().is_some());
+        }
+    }
+
     // A successful ROM load auto-unpauses (the no-content latch releases) and
     // clears any error/frame.
     #[test]
     fn load_rom_bytes_auto_unpauses() {
         let mut a = paused_app();
         a.error_state = Some("stale".into());
-        a.load_rom_bytes(tiny_rom(), Some("game.gb".into())).expect("valid ROM loads");
+        a.load_rom_bytes(tiny_rom(), Some("game.gb".into()), None).expect("valid ROM loads");
         assert!(!a.is_paused && !a.user_paused && !a.manually_paused);
         assert!(!a.auto_paused_no_content, "no-content latch released");
         assert!(a.error_state.is_none() && a.frame.is_none());
+        assert_eq!(a.session.recent_roms(), ["game.gb"], "listed under File → Recent");
+    }
+
+    // A relative path (as typed on the command line) is listed absolute, so
+    // File → Recent still finds it from another working directory.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    #[test]
+    fn recent_roms_are_stored_absolute() {
+        let mut a = paused_app();
+        a.note_recent_rom("Cargo.toml");
+        let absolute = std::fs::canonicalize("Cargo.toml").unwrap();
+        assert_eq!(a.session.recent_roms(), [absolute.to_string_lossy()]);
+    }
+
+    // A ROM opened from the GUI gets its `.sav` in Settings → Save Folder,
+    // the same per-ROM folder a `--save-dir` load uses.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    #[test]
+    fn load_rom_bytes_keeps_the_battery_save_in_the_save_folder() {
+        let dir = std::env::temp_dir().join(format!("rustyboi-gui-savedir-{}", std::process::id()));
+        let mut a = paused_app();
+        a.session.apply(
+            rustyboi_session::UiAction::SetSaveDir(Some(dir.join("saves").to_string_lossy().into_owned())),
+            0,
+        );
+        let mut rom = tiny_rom();
+        rom[0x147] = 0x03; // MBC1+RAM+BATTERY
+        rom[0x149] = 0x02; // 8KB
+        let path = dir.join("roms").join("game.gb");
+        a.load_rom_bytes(rom, Some(path.to_string_lossy().into_owned()), None).unwrap();
+        assert!(dir.join("saves").join("game").join("game.sav").exists());
+        assert!(!dir.join("roms").exists(), "nothing written beside the ROM");
+        let _ = std::fs::remove_dir_all(&dir);
     }
 
     // A failed ROM load surfaces the error to the caller and preserves the
@@ -1210,7 +1758,7 @@ mod pause_and_load_tests {
     #[test]
     fn load_rom_bytes_failure_preserves_state() {
         let mut a = paused_app();
-        let err = a.load_rom_bytes(vec![0u8; 4], None); // too small to be a cartridge
+        let err = a.load_rom_bytes(vec![0u8; 4], None, None); // too small to be a cartridge
         assert!(err.is_err(), "an invalid ROM must fail");
         assert!(a.is_paused && a.auto_paused_no_content, "pause state preserved");
     }
@@ -1221,7 +1769,7 @@ mod pause_and_load_tests {
     fn load_state_bytes_auto_unpauses_with_content() {
         // Produce a valid savestate from a running machine.
         let mut src = paused_app();
-        src.load_rom_bytes(tiny_rom(), Some("game.gb".into())).unwrap();
+        src.load_rom_bytes(tiny_rom(), Some("game.gb".into()), None).unwrap();
         let state = src.state_bytes().expect("serialize state");
 
         let mut a = paused_app();
@@ -64,6 +64,11 @@ pub(crate) trait Frontend {
     /// background thread; web hands it to the JS `fetch()` bridge.
     fn fetch_url(&mut self, urls: Vec<String>, purpose: FetchPurpose);
 
+    /// Put a `width x height` RGBA8 image on the system clipboard (File →
+    /// Copy Screenshot), and report whether it worked: the session leaves
+    /// the success status to the frontend.
+    fn copy_image(&mut self, width: u32, height: u32, rgba: Vec<u8>);
+
     /// The session run/pause state changed in a way the frontend's pause model
     /// must observe (toggle pause, restart, frame advance, error clear, load).
     fn on_pause_changed(&mut self, action_hint: PauseHint);
@@ -85,6 +90,9 @@ pub(crate) enum PauseHint {
     /// Hardware change rebuilt the machine: clear error/frame but keep the
     /// user's pause state (matches the pre-refactor behavior).
     SetHardware,
+    /// A crash-recovery snapshot replaced the crashed machine: clear the error
+    /// and run again unless the user had paused.
+    Recover,
     Load,
 }
 
@@ -125,6 +133,9 @@ pub(crate) fn route_outcome<F: Frontend>(frontend: &mut F, outcome: ActionOutcom
             }
             PlatformRequest::LoadFile { file, purpose } => frontend.load_file(file, purpose),
             PlatformRequest::FetchUrl { urls, purpose } => frontend.fetch_url(urls, purpose),
+            PlatformRequest::CopyImage { width, height, rgba } => {
+                frontend.copy_image(width, height, rgba)
+            }
             PlatformRequest::Status(s) => frontend.set_status(s),
             PlatformRequest::Error(e) => frontend.set_error(e),
             PlatformRequest::ClearError => frontend.clear_error(),
@@ -139,11 +150,17 @@ fn pause_hint_for(action: &UiAction) -> Option<PauseHint> {
         UiAction::TogglePause => Some(PauseHint::TogglePause),
         UiAction::Restart => Some(PauseHint::Restart),
         UiAction::ClearError => Some(PauseHint::ClearError),
-        UiAction::FrameAdvance => Some(PauseHint::FrameAdvance),
+        // Opening the input editor or stepping its timeline pauses like a
+        // frame advance.
+        UiAction::FrameAdvance
+        | UiAction::ToggleInputEditor
+        | UiAction::SetTasInput(..)
+        | UiAction::SeekTasFrame(_) => Some(PauseHint::FrameAdvance),
         UiAction::LoadRom(_) | UiAction::LoadState(_) | UiAction::ImportState(_) => {
             Some(PauseHint::Load)
         }
         UiAction::SetHardware(_) => Some(PauseHint::SetHardware),
+        UiAction::RestoreRecoverySnapshot => Some(PauseHint::Recover),
         _ => None,
     }
 }
@@ -170,6 +187,7 @@ mod tests {
             pause_hint_for(&UiAction::SetHardware(HardwareChoice::Cgb)),
             Some(PauseHint::SetHardware)
         );
+        assert_eq!(pause_hint_for(&UiAction::RestoreRecoverySnapshot), Some(PauseHint::Recover));
     }
 
     #[test]
@@ -238,6 +256,9 @@ mod tests {
         fn fetch_url(&mut self, _urls: Vec<String>, _purpose: FetchPurpose) {
             self.calls.push("fetch_url".into());
         }
+        fn copy_image(&mut self, _width: u32, _height: u32, _rgba: Vec<u8>) {
+            self.calls.push("copy_image".into());
+        }
         fn on_pause_changed(&mut self, hint: PauseHint) {
             self.calls.push(format!("on_pause_changed({hint:?})"));
         }
@@ -309,6 +330,13 @@ mod tests {
         );
     }
 
+    // Copy Screenshot hands the frame to copy_image(), which reports the
+    // outcome itself once the clipboard has answered.
+    #[test]
+    fn copy_screenshot_routes_to_copy_image() {
+        assert_eq!(drive(UiAction::CopyScreenshot), vec!["copy_image"]);
+    }
+
     // An action whose outcome is an Error request routes to set_error().
     #[test]
     fn error_outcome_routes_to_set_error() {
@@ -30,6 +30,8 @@ pub fn key_code(k: KeyName) -> KeyCode {
         N::Tab => KeyCode::Tab, N::Backspace => KeyCode::Backspace,
         N::Escape => KeyCode::Escape, N::Backslash => KeyCode::Backslash,
         N::ShiftLeft => KeyCode::ShiftLeft, N::ShiftRight => KeyCode::ShiftRight,
+        N::ControlLeft => KeyCode::ControlLeft, N::ControlRight => KeyCode::ControlRight,
+        N::AltLeft => KeyCode::AltLeft, N::AltRight => KeyCode::AltRight,
         N::F1 => KeyCode::F1, N::F2 => KeyCode::F2, N::F3 => KeyCode::F3,
         N::F4 => KeyCode::F4, N::F5 => KeyCode::F5, N::F6 => KeyCode::F6,
         N::F7 => KeyCode::F7, N::F8 => KeyCode::F8, N::F9 => KeyCode::F9,
@@ -29,7 +29,9 @@ pub mod renderer;
 pub mod soft;
 pub mod ui_host;
 
-pub use app::{sgb_firmware_label, App, FrameStep, PlatformRequest, ResolvedAction};
+pub use app::{
+    sgb_firmware_label, App, FrameStep, LaidOutUi, Picture, PlatformRequest, Presentation, ResolvedAction, UiPrep,
+};
 pub use renderer::{GameFrame, PhysicalRect, Present, Renderer, SourceSize};
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use soft::SoftRenderer;
@@ -45,6 +47,11 @@ pub mod egui_events {
 // Re-export the egui action + UI-state types the platform must name to build
 // the `SessionUiState` snapshot and match `GuiAction`s it resolves (file loads).
 pub use rustyboi_egui_lib::actions::{self, FileData, GuiAction, HardwareChoice, DmgPaletteChoice, SessionUiState};
+// ...and the audio-device readout the platform measures for the FPS overlay.
+pub use rustyboi_egui_lib::AudioStats;
+// ...and, with the `profiler` feature, the span bridge it installs at startup.
+#[cfg(feature = "profiler")]
+pub use rustyboi_egui_lib::profiler;
 
 // The Android JNI glue (in `rustyboi-platform`) installs handlers and drives the
 // ROM-library panel through these; re-export them so the platform depends only
@@ -12,17 +12,20 @@
 
 use egui::{ClippedPrimitive, TexturesDelta};
 use egui_wgpu::ScreenDescriptor;
-use rustyboi_session::{LcdEffect, ScalingMode, TextureFilter};
+use rustyboi_session::{AspectRatio, LcdEffect, ScalingMode, TextureFilter};
 use wgpu::util::DeviceExt;
 
 /// Size in bytes of the fragment/vertex uniform block: a 4x4 transform (64) +
-/// source size `vec2<f32>` (8) + effect `u32` (4) + padding (4) = 80, a
-/// multiple of 16 as WGSL requires.
-const UNIFORM_BYTES: usize = 80;
-
-/// Build the 80-byte uniform block from the NDC transform, the active source
-/// dimensions (for LCD-effect texel math), and the effect selector.
-fn uniform_bytes(transform: &[f32; 16], source: (f32, f32), effect: u32) -> Vec<u8> {
+/// source size `vec2<f32>` (8) + effect `u32` (4) + sharp-bilinear flag `u32`
+/// (4) + dot-matrix flag `u32` (4), padded to 96, a multiple of 16 as WGSL
+/// requires.
+const UNIFORM_BYTES: usize = 96;
+
+/// Build the 96-byte uniform block from the NDC transform, the active source
+/// dimensions (for LCD-effect texel math), the effect selector, whether the
+/// linear sampler's coordinate is remapped for sharp bilinear, and whether
+/// the dot-matrix filter draws its pixel gaps.
+fn uniform_bytes(transform: &[f32; 16], source: (f32, f32), effect: u32, sharp: bool, dot_matrix: bool) -> Vec<u8> {
     let mut bytes = Vec::with_capacity(UNIFORM_BYTES);
     for v in transform {
         bytes.extend_from_slice(&v.to_ne_bytes());
@@ -30,7 +33,9 @@ fn uniform_bytes(transform: &[f32; 16], source: (f32, f32), effect: u32) -> Vec<
     bytes.extend_from_slice(&source.0.to_ne_bytes());
     bytes.extend_from_slice(&source.1.to_ne_bytes());
     bytes.extend_from_slice(&effect.to_ne_bytes());
-    bytes.extend_from_slice(&0u32.to_ne_bytes()); // padding to 16-byte alignment
+    bytes.extend_from_slice(&u32::from(sharp).to_ne_bytes());
+    bytes.extend_from_slice(&u32::from(dot_matrix).to_ne_bytes());
+    bytes.resize(UNIFORM_BYTES, 0);
     bytes
 }
 
@@ -42,6 +47,7 @@ fn effect_code(effect: LcdEffect) -> u32 {
         LcdEffect::Auto | LcdEffect::Off => 0,
         LcdEffect::Grid => 1,
         LcdEffect::Scanlines => 2,
+        LcdEffect::Crt => 3,
     }
 }
 
@@ -211,8 +217,10 @@ pub trait Present {
     fn surface_size(&self) -> (u32, u32);
     fn resize(&mut self, width: u32, height: u32);
     fn set_scaling_mode(&mut self, mode: ScalingMode);
+    fn set_aspect_ratio(&mut self, aspect: AspectRatio);
     fn set_texture_filter(&mut self, filter: TextureFilter);
     fn set_lcd_effect(&mut self, effect: LcdEffect);
+    fn set_dot_matrix(&mut self, on: bool);
     /// Upload a game frame, retaining it as the active source for subsequent
     /// `render(game: None, ..)` calls. The web driver uploads directly from
     /// its worker-shared buffer and then renders with `game: None` to avoid a
@@ -231,6 +239,9 @@ pub trait Present {
     /// software blit, or a skipped/occluded frame) the platform throttles the
     /// tick itself. Part of the pacing scheme — see `rustyboi_session::pacing`.
     fn vsync_paced(&self) -> bool;
+    /// Switch the swapchain's present mode (fast-forward with vsync off).
+    /// Cheap when the mode is unchanged, so it can ride every tick.
+    fn set_present_mode(&mut self, mode: wgpu::PresentMode);
 }
 
 /// Everything egui produced this frame, handed from the `App` to the renderer.
@@ -266,6 +277,9 @@ pub struct Renderer {
     /// Current sampling filter + LCD effect, pushed each frame from the session.
     texture_filter: TextureFilter,
     lcd_effect: LcdEffect,
+    /// DMG dot-matrix filter: pixel gaps at integer scales, on top of the
+    /// LCD effect.
+    dot_matrix: bool,
     clear_color: wgpu::Color,
     /// Set once any game frame has been uploaded. Lets a render tick with no
     /// fresh frame redraw the last texture instead of clearing to black — the
@@ -281,6 +295,9 @@ pub struct Renderer {
     /// Frame letterboxing policy the frontend pushes each frame from the session
     /// config. `FitAspect` (default) reproduces the historical layout exactly.
     scaling_mode: ScalingMode,
+    /// The shape the aspect-keeping scaling modes show the frame at, pushed
+    /// alongside `scaling_mode`. `Square` reproduces the historical layout.
+    aspect_ratio: AspectRatio,
 
     egui: EguiCompositor,
 }
@@ -549,7 +566,7 @@ impl Renderer {
         ];
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("rustyboi_game_matrix_uniform_buffer"),
-            contents: &uniform_bytes(&identity, (GB_WIDTH as f32, GB_HEIGHT as f32), 0),
+            contents: &uniform_bytes(&identity, (GB_WIDTH as f32, GB_HEIGHT as f32), 0, false, false),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
@@ -678,10 +695,12 @@ impl Renderer {
             bind_group_layout,
             texture_filter: TextureFilter::Nearest,
             lcd_effect: LcdEffect::Off,
+            dot_matrix: false,
             clear_color: wgpu::Color::BLACK,
             has_game: false,
             last_presented: false,
             scaling_mode: ScalingMode::FitAspect,
+            aspect_ratio: AspectRatio::Square,
             egui,
         }
     }
@@ -693,8 +712,16 @@ impl Renderer {
         self.scaling_mode = mode;
     }
 
+    /// Set the shape the frame is shown at by the next `render`. Pushed each
+    /// frame alongside the scaling mode.
+    pub fn set_aspect_ratio(&mut self, aspect: AspectRatio) {
+        self.aspect_ratio = aspect;
+    }
+
     /// Set the upscale texture filter. On a change, rebuilds both source bind
     /// groups against the selected sampler (cheap — the textures are reused).
+    /// Sharp bilinear binds the linear sampler; the shader remaps its
+    /// coordinate (see `scale.wgsl`).
     pub fn set_texture_filter(&mut self, filter: TextureFilter) {
         if filter == self.texture_filter {
             return;
@@ -702,7 +729,7 @@ impl Renderer {
         self.texture_filter = filter;
         let sampler = match filter {
             TextureFilter::Nearest => &self.nearest_sampler,
-            TextureFilter::Linear => &self.linear_sampler,
+            TextureFilter::Linear | TextureFilter::SharpBilinear => &self.linear_sampler,
         };
         self.gb_source
             .rebuild_bind_group(&self.device, &self.bind_group_layout, sampler, &self.uniform_buffer);
@@ -716,6 +743,12 @@ impl Renderer {
         self.lcd_effect = effect;
     }
 
+    /// Turn the DMG dot-matrix pixel gaps on/off for the next `render`
+    /// (uniform-driven; drawn only at integer scales, see `scale.wgsl`).
+    pub fn set_dot_matrix(&mut self, on: bool) {
+        self.dot_matrix = on;
+    }
+
     /// A `Device` clone the platform can use to build companion GPU state if
     /// needed. Cheap (wgpu handles are `Arc`-backed).
     pub fn device(&self) -> &wgpu::Device {
@@ -742,6 +775,19 @@ impl Renderer {
         }
     }
 
+    /// Reconfigure the surface for `mode` if it isn't already presenting with
+    /// it. The caller picks a mode the surface supports.
+    pub fn set_present_mode(&mut self, mode: wgpu::PresentMode) {
+        if self.config.present_mode != mode {
+            self.config.present_mode = mode;
+            match &self.target {
+                FrameTarget::Surface(surface) => surface.configure(&self.device, &self.config),
+                #[cfg(test)]
+                FrameTarget::Offscreen { .. } => {}
+            }
+        }
+    }
+
     /// Current surface size in physical pixels.
     pub fn surface_size(&self) -> (u32, u32) {
         (self.config.width, self.config.height)
@@ -779,6 +825,7 @@ impl Renderer {
             surface,
             region,
             self.scaling_mode,
+            self.aspect_ratio,
         )
     }
 
@@ -860,7 +907,13 @@ impl Renderer {
         self.queue.write_buffer(
             &self.uniform_buffer,
             0,
-            &uniform_bytes(&transform, source_dims, effect_code(self.lcd_effect)),
+            &uniform_bytes(
+                &transform,
+                source_dims,
+                effect_code(self.lcd_effect),
+                self.texture_filter == TextureFilter::SharpBilinear,
+                self.dot_matrix,
+            ),
         );
         {
             let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -1033,12 +1086,18 @@ impl Present for Renderer {
     fn set_scaling_mode(&mut self, mode: ScalingMode) {
         Renderer::set_scaling_mode(self, mode)
     }
+    fn set_aspect_ratio(&mut self, aspect: AspectRatio) {
+        Renderer::set_aspect_ratio(self, aspect)
+    }
     fn set_texture_filter(&mut self, filter: TextureFilter) {
         Renderer::set_texture_filter(self, filter)
     }
     fn set_lcd_effect(&mut self, effect: LcdEffect) {
         Renderer::set_lcd_effect(self, effect)
     }
+    fn set_dot_matrix(&mut self, on: bool) {
+        Renderer::set_dot_matrix(self, on)
+    }
     fn upload_game(&mut self, frame: &GameFrame) {
         Renderer::upload_game(self, frame)
     }
@@ -1056,6 +1115,9 @@ impl Present for Renderer {
         // surface error) paces nothing regardless of mode.
         self.last_presented && self.config.present_mode == wgpu::PresentMode::Fifo
     }
+    fn set_present_mode(&mut self, mode: wgpu::PresentMode) {
+        Renderer::set_present_mode(self, mode)
+    }
 }
 
 /// Pure letterbox math: integer-scaled, aspect-preserving placement of a
@@ -1069,9 +1131,13 @@ pub(crate) fn compute_layout(
     surface: (f32, f32),
     region: PhysicalRect,
     mode: ScalingMode,
+    aspect: AspectRatio,
 ) -> ([f32; 16], (u32, u32, u32, u32)) {
     let (surface_w, surface_h) = surface;
     let (texture_width, texture_height) = texture;
+    // The aspect-keeping modes place the frame at its display shape; with
+    // `Square` this is the texture width, so the historical math is unchanged.
+    let texture_width = aspect.display_width(texture_width, texture_height);
 
     // Clamp the region to the surface so scissor stays in bounds.
     let rx = region.x.max(0.0);
@@ -1133,15 +1199,16 @@ mod tests {
 
     // --- pure helper fns (no GPU) ------------------------------------------
 
-    // uniform_bytes lays out the 80-byte block exactly: 64B transform, then the
-    // 8B source vec2, then the 4B effect u32, then 4B zero pad — all native-endian.
+    // uniform_bytes lays out the 96-byte block exactly: 64B transform, then the
+    // 8B source vec2, then the 4B effect u32, the 4B sharp flag and the 4B
+    // dot-matrix flag — all native-endian — then zero padding.
     #[test]
-    fn uniform_bytes_is_the_exact_80_byte_layout() {
-        assert_eq!(UNIFORM_BYTES, 80);
+    fn uniform_bytes_is_the_exact_96_byte_layout() {
+        assert_eq!(UNIFORM_BYTES, 96);
         let transform: [f32; 16] = [
             1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
         ];
-        let bytes = uniform_bytes(&transform, (160.0, 144.0), 2);
+        let bytes = uniform_bytes(&transform, (160.0, 144.0), 2, false, false);
         assert_eq!(bytes.len(), UNIFORM_BYTES);
 
         let mut expect = Vec::new();
@@ -1152,13 +1219,19 @@ mod tests {
         expect.extend_from_slice(&144.0f32.to_ne_bytes());
         expect.extend_from_slice(&2u32.to_ne_bytes());
         expect.extend_from_slice(&0u32.to_ne_bytes());
+        expect.resize(UNIFORM_BYTES, 0);
         assert_eq!(bytes, expect);
 
         // The four segments land at their documented offsets.
         assert_eq!(&bytes[0..64], &expect[0..64]); // transform
         assert_eq!(&bytes[64..72], &expect[64..72]); // source vec2
         assert_eq!(&bytes[72..76], &2u32.to_ne_bytes()); // effect
-        assert_eq!(&bytes[76..80], &[0u8; 4]); // pad
+        assert_eq!(&bytes[76..80], &[0u8; 4]); // sharp off
+        let sharp = uniform_bytes(&transform, (160.0, 144.0), 2, true, false);
+        assert_eq!(&sharp[76..80], &1u32.to_ne_bytes());
+        let dot = uniform_bytes(&transform, (160.0, 144.0), 2, false, true);
+        assert_eq!(&dot[80..84], &1u32.to_ne_bytes());
+        assert_eq!(&dot[84..96], &[0u8; 12]);
     }
 
     #[test]
@@ -1166,6 +1239,7 @@ mod tests {
         assert_eq!(effect_code(LcdEffect::Off), 0);
         assert_eq!(effect_code(LcdEffect::Grid), 1);
         assert_eq!(effect_code(LcdEffect::Scanlines), 2);
+        assert_eq!(effect_code(LcdEffect::Crt), 3);
     }
 
     #[test]
@@ -1202,6 +1276,7 @@ mod tests {
             (800.0, 720.0),
             rect(0.0, 0.0, 800.0, 720.0),
             ScalingMode::FitAspect,
+            AspectRatio::Square,
         );
         #[rustfmt::skip]
         let want = [
@@ -1223,6 +1298,7 @@ mod tests {
             (800.0, 720.0),
             rect(0.0, 20.0, 800.0, 700.0),
             ScalingMode::FitAspect,
+            AspectRatio::Square,
         );
         let s = (700.0f32 / 144.0) / 5.0; // scale/surface on both axes
         assert!((t[0] - s).abs() < 1e-4, "sw = {}", t[0]);
@@ -1241,6 +1317,7 @@ mod tests {
             (800.0, 720.0),
             rect(0.0, 20.0, 800.0, 700.0),
             ScalingMode::Stretch,
+            AspectRatio::Square,
         );
         assert!((t[0] - 1.0).abs() < 1e-4, "sw = {}", t[0]);
         assert!((t[5] - (700.0 / 720.0)).abs() < 1e-4, "sh = {}", t[5]);
@@ -1252,7 +1329,7 @@ mod tests {
     // A 160x144 game in a full 800x720 surface region scales 5x and fills it.
     #[test]
     fn exact_integer_fit_centers_and_fills() {
-        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 800.0, 720.0), ScalingMode::FitAspect);
+        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 800.0, 720.0), ScalingMode::FitAspect, AspectRatio::Square);
         assert_eq!(scissor, (0, 0, 800, 720));
     }
 
@@ -1264,7 +1341,7 @@ mod tests {
     // fill; this covers the transient mid-resize case.)
     #[test]
     fn fractional_fit_fills_limiting_axis() {
-        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 20.0, 800.0, 700.0), ScalingMode::FitAspect);
+        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 20.0, 800.0, 700.0), ScalingMode::FitAspect, AspectRatio::Square);
         assert_eq!(scissor.3, 700); // height fills exactly
         assert_eq!(scissor.2, 777); // 160 * (700/144) = 777.7 -> 777
         assert_eq!(scissor.1, 20); // no vertical bar on the limiting axis
@@ -1274,7 +1351,7 @@ mod tests {
     // 5x and fills, proving the source-size drives the fit (the sizing-bug fix).
     #[test]
     fn sgb_source_uses_its_own_aspect() {
-        let (_t, scissor) = compute_layout((256.0, 224.0), (1280.0, 1120.0), rect(0.0, 0.0, 1280.0, 1120.0), ScalingMode::FitAspect);
+        let (_t, scissor) = compute_layout((256.0, 224.0), (1280.0, 1120.0), rect(0.0, 0.0, 1280.0, 1120.0), ScalingMode::FitAspect, AspectRatio::Square);
         assert_eq!(scissor, (0, 0, 1280, 1120));
     }
 
@@ -1282,7 +1359,7 @@ mod tests {
     // so `render` skips the draw rather than panicking.
     #[test]
     fn collapsed_region_is_safe() {
-        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 0.0, 0.0), ScalingMode::FitAspect);
+        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 0.0, 0.0), ScalingMode::FitAspect, AspectRatio::Square);
         // A zero-size region yields scale 0 and a collapsed scissor.
         assert_eq!(scissor.2, 0);
         assert_eq!(scissor.3, 0);
@@ -1293,7 +1370,7 @@ mod tests {
     // centered with bars on both axes.
     #[test]
     fn integer_aspect_floors_to_whole_scale() {
-        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 800.0, 700.0), ScalingMode::IntegerAspect);
+        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 800.0, 700.0), ScalingMode::IntegerAspect, AspectRatio::Square);
         assert_eq!(scissor.2, 640); // 160 * 4
         assert_eq!(scissor.3, 576); // 144 * 4
         assert_eq!(scissor.0, 80); // (800 - 640) / 2
@@ -1303,17 +1380,25 @@ mod tests {
     // An exact-integer region still fills under IntegerAspect (floor of 5.0 = 5).
     #[test]
     fn integer_aspect_exact_fit_still_fills() {
-        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 800.0, 720.0), ScalingMode::IntegerAspect);
+        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 800.0, 720.0), ScalingMode::IntegerAspect, AspectRatio::Square);
         assert_eq!(scissor, (0, 0, 800, 720));
     }
 
     // Stretch fills the whole region on both axes, ignoring aspect (distorts).
     #[test]
     fn stretch_fills_region_ignoring_aspect() {
-        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 20.0, 800.0, 700.0), ScalingMode::Stretch);
+        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 20.0, 800.0, 700.0), ScalingMode::Stretch, AspectRatio::Square);
         assert_eq!(scissor, (0, 20, 800, 700));
     }
 
+    // A 4:3 aspect widens the 160x144 frame to 192x144 before scaling:
+    // IntegerAspect floors 720/144 = 5 vs 800/192 = 4.17 to 4x = 768x576.
+    #[test]
+    fn four_by_three_widens_before_integer_scaling() {
+        let (_t, scissor) = compute_layout((160.0, 144.0), (800.0, 720.0), rect(0.0, 0.0, 800.0, 720.0), ScalingMode::IntegerAspect, AspectRatio::FourByThree);
+        assert_eq!(scissor, (16, 72, 768, 576));
+    }
+
     // --- Headless GPU tests: the egui texture-ordering invariant -----------
     //
     // These reproduce the macOS startup failure *deterministically and without a
@@ -12,9 +12,10 @@
 //! Correctness notes mirrored from the wgpu path:
 //! - Game placement reuses [`renderer::compute_layout`]'s scissor rect, so both
 //!   backends letterbox identically for every [`ScalingMode`].
-//! - The LCD grid/scanline effects reproduce `scale.wgsl`'s math (per-texel
+//! - The LCD grid/scanline/CRT effects reproduce `scale.wgsl`'s math (per-texel
 //!   fraction → smoothstep gap / mid-row peak), precomputed as per-row and
-//!   per-column tables so the blit stays a fetch + multiply per pixel.
+//!   per-column tables so the blit stays a fetch + multiply per pixel. Sharp
+//!   bilinear is the Linear path with the shader's stretched blend weight.
 //! - egui vertices/textures are premultiplied sRGBA ([`egui::Color32`]); the
 //!   wgpu path renders onto a non-sRGB surface with standard
 //!   `ONE, ONE_MINUS_SRC_ALPHA` blending, i.e. blending happens in gamma
@@ -28,7 +29,7 @@ use crate::renderer::{
     compute_layout, EguiPaint, GameFrame, PhysicalRect, Present, SourceSize,
 };
 use egui::ClippedPrimitive;
-use rustyboi_session::{LcdEffect, ScalingMode, TextureFilter};
+use rustyboi_session::{AspectRatio, LcdEffect, ScalingMode, TextureFilter};
 use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::sync::Arc;
@@ -57,8 +58,10 @@ pub(crate) struct SoftCompositor {
     game_rgba: Vec<u8>,
     game_size: Option<SourceSize>,
     pub scaling_mode: ScalingMode,
+    pub aspect_ratio: AspectRatio,
     pub texture_filter: TextureFilter,
     pub lcd_effect: LcdEffect,
+    pub dot_matrix: bool,
 }
 
 impl SoftCompositor {
@@ -70,8 +73,10 @@ impl SoftCompositor {
             game_rgba: Vec::new(),
             game_size: None,
             scaling_mode: ScalingMode::FitAspect,
+            aspect_ratio: AspectRatio::Square,
             texture_filter: TextureFilter::Nearest,
             lcd_effect: LcdEffect::Off,
+            dot_matrix: false,
         }
     }
 
@@ -150,6 +155,7 @@ impl SoftCompositor {
                 (w as f32, h as f32),
                 region,
                 self.scaling_mode,
+                self.aspect_ratio,
             );
             self.blit_game(fb, w, h, (tw, th), scissor);
         }
@@ -190,6 +196,10 @@ impl SoftCompositor {
     /// a 10% edge band and a fraction-based grid silently vanishes — and the
     /// window auto-resize snaps the game to integer scale, so that was the
     /// common case (`grid_is_visible_at_exact_integer_scale` pins this).
+    ///
+    /// The dot-matrix filter's gaps sit on the same boundary pixels, blended
+    /// toward the DMG substrate over whatever the effect produced, and only
+    /// at whole-number scales of 2 or more (as in `scale.wgsl`).
     fn blit_game(
         &self,
         fb: &mut [u32],
@@ -222,19 +232,24 @@ impl SoftCompositor {
             .collect();
         let grid = self.lcd_effect == LcdEffect::Grid;
         let scan = self.lcd_effect == LcdEffect::Scanlines;
-        let col_edge: Vec<bool> = if grid {
+        let crt = self.lcd_effect == LcdEffect::Crt;
+        let dot = self.dot_matrix && dw % tw == 0 && dh % th == 0 && dw >= 2 * tw && dh >= 2 * th;
+        let col_edge: Vec<bool> = if grid || dot {
             (0..dwc)
                 .map(|c| c + 1 == dwc || col_nx[c] != col_nx[c + 1])
                 .collect()
         } else {
             Vec::new()
         };
-        // Scanline row factor (0..=256), from the shader: 1 - 0.4*|f-0.5|*2.
-        let row_scan: Vec<u32> = if scan {
+        // Scanline row factor (0..=256), from the shader: 1 - 0.4*|f-0.5|*2,
+        // or the CRT's squared 1 - 0.5*(|f-0.5|*2)^2.
+        let row_scan: Vec<u32> = if scan || crt {
             (0..dhc)
                 .map(|y| {
                     let f = ((((y as u64) * step_y + step_y / 2) & 0xFFFF) as f32) / 65536.0;
-                    ((1.0 - 0.40 * (f - 0.5).abs() * 2.0) * 256.0) as u32
+                    let d = (f - 0.5).abs() * 2.0;
+                    let s = if crt { 1.0 - 0.50 * d * d } else { 1.0 - 0.40 * d };
+                    (s * 256.0) as u32
                 })
                 .collect()
         } else {
@@ -251,9 +266,18 @@ impl SoftCompositor {
             dx,
             dwc,
             dhc,
-            bilinear: self.texture_filter == TextureFilter::Linear,
+            bilinear: self.texture_filter != TextureFilter::Nearest,
+            // The shader's whole-factor scale per axis, from the full (not
+            // fb-clamped) blit size.
+            sharp: if self.texture_filter == TextureFilter::SharpBilinear {
+                ((dw / tw).max(1), (dh / th).max(1))
+            } else {
+                (1, 1)
+            },
             grid,
             scan,
+            crt,
+            dot,
             col_nx: &col_nx,
             col_edge: &col_edge,
             row_scan: &row_scan,
@@ -692,8 +716,13 @@ struct BlitParams<'a> {
     dwc: usize,
     dhc: u32,
     bilinear: bool,
+    /// Sharp-bilinear weight stretch per axis; `(1, 1)` is plain bilinear.
+    sharp: (u32, u32),
     grid: bool,
     scan: bool,
+    crt: bool,
+    /// Dot-matrix gaps (already limited to whole-number scales).
+    dot: bool,
     col_nx: &'a [u32],
     col_edge: &'a [bool],
     row_scan: &'a [u32],
@@ -719,6 +748,26 @@ fn swar_mul(v: u32, m: u32) -> u32 {
     rb | g
 }
 
+/// Sharp-bilinear blend weight (0..=256): the bilinear weight stretched by the
+/// whole-factor `scale` about the seam midpoint, so pixels inside a texel's
+/// block hold its colour and only the seam ramps. `scale == 1` is identity.
+#[inline(always)]
+fn sharpen(w: u32, scale: u32) -> u32 {
+    ((w as i32 - 128) * scale as i32 + 128).clamp(0, 256) as u32
+}
+
+/// The CRT aperture grille at fb column `x`: keep one channel (R, G, B in
+/// turn) and dim the other two to 80%, as `scale.wgsl` does.
+#[inline(always)]
+fn crt_mask(v: u32, x: usize) -> u32 {
+    let keep = 0xFF0000 >> (8 * (x % 3));
+    (v & keep) | (swar_mul(v, 205) & !keep)
+}
+
+/// The DMG panel substrate the dot-matrix gaps blend toward (packed; the
+/// lightest dot-matrix shade, as in `scale.wgsl`).
+const DOT_SUBSTRATE: u32 = 0xC6DE8C;
+
 #[inline(always)]
 fn pack(px: &[u8]) -> u32 {
     ((px[0] as u32) << 16) | ((px[1] as u32) << 8) | (px[2] as u32)
@@ -738,8 +787,11 @@ fn blit_rows(chunk: &mut [u32], r0: u32, r1: u32, p: &BlitParams) {
         dx,
         dwc,
         bilinear,
+        sharp,
         grid,
         scan,
+        crt,
+        dot,
         col_nx,
         col_edge,
         row_scan,
@@ -751,7 +803,7 @@ fn blit_rows(chunk: &mut [u32], r0: u32, r1: u32, p: &BlitParams) {
     // (y-blend + row effect folded in), expanded below.
     let mut trow: Vec<u32> = vec![0; tw as usize];
 
-    let mut prev_key: Option<(u32, u32, usize)> = None; // (sy, row_mul, chunk row start)
+    let mut prev_key: Option<(u32, u32, bool, usize)> = None; // (sy, row_mul, row_gap, chunk row start)
     for row in r0..r1 {
         let fy = row as u64 * step_y + step_y / 2;
         let sy0 = ((fy >> 16) as u32).min(th - 1);
@@ -759,8 +811,10 @@ fn blit_rows(chunk: &mut [u32], r0: u32, r1: u32, p: &BlitParams) {
 
         // Row multiplier: scanline factor, or the grid row-edge dim; 256 =
         // identity. (Grid column edges are applied during expansion.)
-        let row_edge = grid && (row + 1 == dhc || row_sy(row + 1) != sy0);
-        let rm: u32 = if scan {
+        let row_boundary = row + 1 == dhc || row_sy(row + 1) != sy0;
+        let row_edge = grid && row_boundary;
+        let row_gap = dot && row_boundary;
+        let rm: u32 = if scan || crt {
             row_scan[row as usize]
         } else if row_edge {
             205
@@ -770,12 +824,13 @@ fn blit_rows(chunk: &mut [u32], r0: u32, r1: u32, p: &BlitParams) {
 
         // Rows with identical inputs are pure repeats — one memcpy.
         if !bilinear
-            && let Some((psy, prm, pstart)) = prev_key
+            && let Some((psy, prm, pgap, pstart)) = prev_key
             && psy == sy0
             && prm == rm
+            && pgap == row_gap
         {
             chunk.copy_within(pstart..pstart + dwc, out_start);
-            prev_key = Some((sy0, rm, out_start));
+            prev_key = Some((sy0, rm, row_gap, out_start));
             continue;
         }
 
@@ -785,7 +840,7 @@ fn blit_rows(chunk: &mut [u32], r0: u32, r1: u32, p: &BlitParams) {
             let cy = fy.saturating_sub(1 << 15);
             let y0 = ((cy >> 16) as u32).min(th - 1);
             let y1 = (y0 + 1).min(th - 1);
-            let wy = ((cy & 0xFFFF) >> 8) as u32;
+            let wy = sharpen(((cy & 0xFFFF) >> 8) as u32, sharp.1);
             let row0 = &src[(y0 * tw * 4) as usize..][..(tw * 4) as usize];
             let row1 = &src[(y1 * tw * 4) as usize..][..(tw * 4) as usize];
             for (t, out) in trow.iter_mut().enumerate() {
@@ -837,7 +892,7 @@ fn blit_rows(chunk: &mut [u32], r0: u32, r1: u32, p: &BlitParams) {
                     let w0 = (coord & 0xFFFF) as u32;
                     let sx = step_x as u32;
                     for (i, out) in out_row[px..p_next].iter_mut().enumerate() {
-                        *out = swar_lerp(c0, c1, (w0 + i as u32 * sx) >> 8);
+                        *out = swar_lerp(c0, c1, sharpen((w0 + i as u32 * sx) >> 8, sharp.0));
                     }
                 }
                 coord += step_x as i64 * (p_next - px) as i64;
@@ -865,8 +920,21 @@ fn blit_rows(chunk: &mut [u32], r0: u32, r1: u32, p: &BlitParams) {
                 }
             }
         }
+        if crt {
+            for (c, out) in out_row.iter_mut().enumerate() {
+                *out = crt_mask(*out, dx as usize + c);
+            }
+        }
+        // Dot-matrix gaps: the substrate shows through at 35% (90/256).
+        if dot {
+            for (c, out) in out_row.iter_mut().enumerate() {
+                if row_gap || col_edge[c] {
+                    *out = swar_lerp(*out, DOT_SUBSTRATE, 90);
+                }
+            }
+        }
 
-        prev_key = Some((sy0, rm, out_start));
+        prev_key = Some((sy0, rm, row_gap, out_start));
     }
 }
 
@@ -943,6 +1011,10 @@ impl Present for SoftRenderer {
         self.compositor.scaling_mode = mode;
     }
 
+    fn set_aspect_ratio(&mut self, aspect: AspectRatio) {
+        self.compositor.aspect_ratio = aspect;
+    }
+
     fn set_texture_filter(&mut self, filter: TextureFilter) {
         self.compositor.texture_filter = filter;
     }
@@ -951,6 +1023,10 @@ impl Present for SoftRenderer {
         self.compositor.lcd_effect = effect;
     }
 
+    fn set_dot_matrix(&mut self, on: bool) {
+        self.compositor.dot_matrix = on;
+    }
+
     fn upload_game(&mut self, frame: &GameFrame) {
         self.compositor.upload_game(frame);
     }
@@ -961,6 +1037,10 @@ impl Present for SoftRenderer {
         false
     }
 
+    fn set_present_mode(&mut self, _mode: wgpu::PresentMode) {
+        // No swapchain: a blit is never synced to vblank in the first place.
+    }
+
     fn render(
         &mut self,
         game: Option<&GameFrame>,
@@ -1114,6 +1194,34 @@ mod tests {
         assert!(n_white >= 60 && n_dim >= 30, "white={n_white} dim={n_dim}");
     }
 
+    // The dot-matrix gaps land on the texel boundary pixels at whole-number
+    // scales, stack on the LCD effect, and stay off at fractional scales
+    // where they would make an uneven grid.
+    #[test]
+    fn dot_matrix_gaps_only_at_whole_number_scales() {
+        let mut c = SoftCompositor::new();
+        c.dot_matrix = true;
+        c.game_rgba = vec![0u8; 2 * 2 * 4];
+        c.game_size = Some(SourceSize::Gb);
+        let mut fb = vec![0u32; 10 * 10];
+        c.blit_game(&mut fb, 10, 10, (2, 2), (0, 0, 10, 10));
+        let gap = swar_lerp(0, DOT_SUBSTRATE, 90);
+        assert_eq!(fb[0], 0, "interior pixel untouched");
+        assert_eq!(fb[4], gap, "column boundary shows the substrate");
+        assert_eq!(fb[4 * 10 + 1], gap, "row boundary shows the substrate");
+        assert_eq!(fb.iter().filter(|&&v| v == gap).count(), 36);
+
+        c.lcd_effect = LcdEffect::Scanlines;
+        let mut stacked = vec![0u32; 10 * 10];
+        c.blit_game(&mut stacked, 10, 10, (2, 2), (0, 0, 10, 10));
+        assert_eq!(stacked, fb, "black scanlined is still black under the gaps");
+
+        c.lcd_effect = LcdEffect::Off;
+        let mut fractional = vec![0u32; 9 * 9];
+        c.blit_game(&mut fractional, 9, 9, (2, 2), (0, 0, 9, 9));
+        assert!(fractional.iter().all(|&v| v == 0), "no gaps at 4.5x");
+    }
+
     // The scanline effect must vary WITHIN each source texel row (that is what
     // distinguishes it from a uniform dim), at exact integer scale.
     #[test]
@@ -1132,6 +1240,49 @@ mod tests {
         );
     }
 
+    // CRT: the scanline factor varies within a texel row and the aperture
+    // grille cycles R, G, B one fb column at a time.
+    #[test]
+    fn crt_masks_columns_and_shades_rows() {
+        let mut c = SoftCompositor::new();
+        c.lcd_effect = LcdEffect::Crt;
+        c.game_rgba = vec![255u8; 2 * 2 * 4];
+        c.game_size = Some(SourceSize::Gb);
+        let mut fb = vec![0u32; 12 * 10];
+        c.blit_game(&mut fb, 12, 10, (2, 2), (0, 0, 12, 10));
+        // Row 2 sits mid-texel (f = 0.5), so the row factor is full.
+        let mid = &fb[2 * 12..3 * 12];
+        assert_eq!(mid[0] >> 16, 0xFF, "column 0 keeps red");
+        assert!(mid[0] & 0xFF < 0xFF, "column 0 dims blue");
+        assert_eq!((mid[1] >> 8) & 0xFF, 0xFF, "column 1 keeps green");
+        assert_eq!(mid[2] & 0xFF, 0xFF, "column 2 keeps blue");
+        assert_eq!(mid[3], mid[0], "the grille repeats every three columns");
+        assert!(fb[0] >> 16 < mid[0] >> 16, "texel-row edge is darker than its middle");
+    }
+
+    // Sharp bilinear at 2.5x: pixels inside a texel's 2x block hold its
+    // colour (where plain bilinear would already blend), and only the seam
+    // pixel between texels mixes.
+    #[test]
+    fn sharp_bilinear_blends_only_the_seam() {
+        let mut c = SoftCompositor::new();
+        c.game_rgba = [[0u8, 0, 0, 255], [255, 255, 255, 255]].concat();
+        c.game_size = Some(SourceSize::Gb);
+        let blit = |c: &SoftCompositor| {
+            let mut fb = vec![0u32; 5];
+            c.blit_game(&mut fb, 5, 1, (2, 1), (0, 0, 5, 1));
+            fb
+        };
+        c.texture_filter = TextureFilter::Linear;
+        let linear = blit(&c);
+        c.texture_filter = TextureFilter::SharpBilinear;
+        let sharp = blit(&c);
+        assert!(linear[1] != 0 && linear[3] != 0xFFFFFF, "bilinear blends off-centre: {linear:x?}");
+        assert_eq!(sharp[..2], [0, 0], "{sharp:x?}");
+        assert_eq!(sharp[3..], [0xFFFFFF, 0xFFFFFF], "{sharp:x?}");
+        assert!(sharp[2] != 0 && sharp[2] != 0xFFFFFF, "the seam pixel blends: {sharp:x?}");
+    }
+
     // The restructured Linear path (per-texel y-blend + cached-pair x-lerp)
     // must produce exactly the classic per-pixel bilinear result.
     #[test]
@@ -1474,8 +1625,8 @@ mod perf_probe {
         for (label, w, h) in [("1000x900 (5x)", 1000u32, 900u32), ("2560x1380 (max)", 2560, 1380)] {
             let mut fb = vec![0u32; (w * h) as usize];
             let dst = (0, 0, (w / 160) * 160, (h / 144) * 144); // integer-ish full blit
-            for filter in [TextureFilter::Nearest, TextureFilter::Linear] {
-                for effect in [LcdEffect::Off, LcdEffect::Grid, LcdEffect::Scanlines] {
+            for filter in [TextureFilter::Nearest, TextureFilter::Linear, TextureFilter::SharpBilinear] {
+                for effect in [LcdEffect::Off, LcdEffect::Grid, LcdEffect::Scanlines, LcdEffect::Crt] {
                     c.texture_filter = filter;
                     c.lcd_effect = effect;
                     let t = Instant::now();
@@ -44,6 +44,12 @@ pub struct UiRunInputs<'a> {
     /// Current presented frames-per-second, drawn by the optional FPS overlay
     /// (gated on `session.show_fps`). Measured per-platform by the caller.
     pub fps: f32,
+    /// The same reading as a percentage of the machine's nominal rate, shown
+    /// beside it.
+    pub speed: f32,
+    /// The audio device's latency and underruns, shown under the FPS overlay;
+    /// `None` when the caller has no device to measure.
+    pub audio: Option<rustyboi_egui_lib::AudioStats>,
 }
 
 /// The egui host: context + winit input bridge + the UI.
@@ -179,6 +185,12 @@ impl UiHost {
         self.gui.set_status(message);
     }
 
+    /// Forward the second Game Boy's latest frame (see
+    /// [`Session::link_partner_frame`](rustyboi_session::Session::link_partner_frame)).
+    pub fn set_link_partner_frame(&mut self, rgb: Option<&[u8]>) {
+        self.gui.set_link_partner_frame(rgb);
+    }
+
     /// Mutable access to the Android ROM library panel (JNI callbacks push
     /// tree-URI / scan-results / status text into it).
     #[cfg(target_os = "android")]
@@ -199,6 +211,8 @@ impl UiHost {
             held_pad,
             force_repaint,
             fps,
+            speed,
+            audio,
         } = inputs;
         let mut raw_input = self.egui_state.take_egui_input(window);
         raw_input.events.extend(extra_events);
@@ -237,7 +251,7 @@ impl UiHost {
         // `Ui`-scoped); `Gui::ui` shows its panels inside it.
         let mut ui_result = None;
         let full_output = self.egui_ctx.run_ui(raw_input, |ui| {
-            ui_result = Some(self.gui.ui(ui, paused, debug, fullscreen, session, held_pad, fps));
+            ui_result = Some(self.gui.ui(ui, paused, debug, fullscreen, session, held_pad, fps, speed, audio));
         });
 
         self.egui_state
@@ -2,9 +2,9 @@
 //!
 //! Builds a `cdylib` that RetroArch (and other libretro frontends) can load.
 //! Video is emitted as XRGB8888, audio as interleaved stereo i16 at 44.1 kHz,
-//! input from the libretro joypad. Save states use the core's own
-//! `GB::to_state_bytes` / `from_state_bytes` (length-prefixed) so RetroArch's
-//! rewind, netplay and manual states all round-trip the full machine.
+//! input from the libretro joypad. Save states use the session's versioned
+//! `.rustyboisave` container (length-prefixed) so RetroArch's rewind, netplay
+//! and manual states all round-trip the full machine.
 //!
 //! This is a thin, `unsafe`-free adapter: all of the libretro C ABI lives in
 //! [`rustyboi_libretro_sys`] (our own hand-written bindings + a safe `Core`
@@ -17,9 +17,9 @@
 
 mod core_options;
 
-use std::cell::Cell;
 use std::ffi::CStr;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use rustyboi_libretro_sys::{
     libretro_core, AvInfo, Core, Environment, Frame, Game, Geometry, LogLevel, MemoryDescriptor,
@@ -60,11 +60,11 @@ const SERIALIZE_HEADER_LEN: usize = 8;
 /// the adapter only records the state into a shared cell; `run` reads it
 /// afterwards and drives the motors.
 struct LibretroRumble {
-    state: Rc<Cell<bool>>,
+    state: Arc<AtomicBool>,
 }
 impl Rumble for LibretroRumble {
     fn set(&mut self, on: bool) {
-        self.state.set(on);
+        self.state.store(on, Ordering::Relaxed);
     }
 }
 
@@ -83,7 +83,7 @@ struct RustyboiCore {
     color_correction: ColorCorrection,
     framebuffer: Vec<u8>,
     /// Shared with the [`LibretroRumble`] port; `run` reads and forwards it.
-    rumble_state: Rc<Cell<bool>>,
+    rumble_state: Arc<AtomicBool>,
     rumble_enabled: bool,
     use_real_boot_rom: bool,
     sgb_border_enabled: bool,
@@ -308,7 +308,7 @@ impl Core for RustyboiCore {
         SystemInfo {
             library_name: c"rustyboi",
             library_version: VERSION,
-            valid_extensions: c"gb|gbc|zip",
+            valid_extensions: c"gb|gbc|zip|7z|gz",
             need_fullpath: false,
             block_extract: false,
         }
@@ -325,7 +325,7 @@ impl Core for RustyboiCore {
             // Sized for the largest possible frame (SGB 256x224) so the same
             // buffer serves both the plain 160x144 and the composited SGB paths.
             framebuffer: vec![0u8; SGB_FRAME_SIZE * 4],
-            rumble_state: Rc::new(Cell::new(false)),
+            rumble_state: Arc::new(AtomicBool::new(false)),
             rumble_enabled: false,
             use_real_boot_rom: false,
             sgb_border_enabled: false,
@@ -448,7 +448,7 @@ impl Core for RustyboiCore {
             },
             ..Default::default()
         };
-        self.rumble_state.set(false);
+        self.rumble_state.store(false, Ordering::Relaxed);
         let ports = Ports {
             storage: Box::new(MemStorage::new()),
             rumble: Box::new(LibretroRumble { state: self.rumble_state.clone() }),
@@ -487,7 +487,7 @@ impl Core for RustyboiCore {
             gb.reset();
         }
         self.publish_memory_maps(env);
-        self.rumble_state.set(false);
+        self.rumble_state.store(false, Ordering::Relaxed);
         if self.rumble_enabled {
             env.set_rumble(0, 0);
         }
@@ -581,7 +581,7 @@ impl Core for RustyboiCore {
         // Drive the rumble motor for MBC5 rumble carts (state recorded by the
         // Rumble port during the frame step).
         if self.rumble_enabled {
-            let strength = if self.rumble_state.get() { u16::MAX } else { 0 };
+            let strength = if self.rumble_state.load(Ordering::Relaxed) { u16::MAX } else { 0 };
             frame.set_rumble(strength, strength);
         }
 
@@ -617,8 +617,8 @@ impl Core for RustyboiCore {
         // bare bincode (ROM held out); only the RLE-coded framebuffer portion
         // drifts with content. A 1/64 + 64 KiB pad plus our own 8-byte length
         // prefix covers that; serialize also guards the write.
-        match self.gb_mut() {
-            Some(gb) => match gb.to_state_bytes() {
+        match self.session.as_mut() {
+            Some(session) => match session.state_file_bytes() {
                 Ok(bytes) => SERIALIZE_HEADER_LEN + bytes.len() + bytes.len() / 64 + 64 * 1024,
                 Err(_) => 0,
             },
@@ -627,10 +627,10 @@ impl Core for RustyboiCore {
     }
 
     fn serialize(&mut self, into: &mut [u8]) -> bool {
-        let Some(gb) = self.gb_mut() else {
+        let Some(session) = self.session.as_mut() else {
             return false;
         };
-        let Ok(bytes) = gb.to_state_bytes() else {
+        let Ok(bytes) = session.state_file_bytes() else {
             return false;
         };
         if SERIALIZE_HEADER_LEN + bytes.len() > into.len() {
@@ -653,8 +653,7 @@ impl Core for RustyboiCore {
         let Some(session) = self.session.as_mut() else {
             return false;
         };
-        let rom_id = session.rom_id();
-        if session.finish_load_state(&payload, None, rom_id).is_err() {
+        if session.restore_host_state(&payload).is_err() {
             return false;
         }
         // Re-assert the libretro-only cart flag (RetroArch owns SRAM/RTC I/O).
@@ -1,7 +1,7 @@
 //! Platform audio output.
 //!
 //! Two backends behind one `Output` API (`start_device` / `push_samples` /
-//! `queued_pairs` / `consumed_pairs`), both built on the same lock-free SPSC
+//! `queued_pairs` / `consumed_pairs` / `stats`), both built on the same lock-free SPSC
 //! ring so the backlog signal is sample-accurate — it is the DAC-trim input to
 //! the shared pacing regulator (`rustyboi_session::pacing`):
 //!
@@ -9,8 +9,20 @@
 //!   never asks for, pinning it to the higher-latency Legacy path); its
 //!   real-time callback drains the ring.
 //! - **Everything else** (desktop native + iOS) → cpal directly, stream opened
-//!   at the core's 44100Hz (the host resamples to the device rate); its
+//!   at the core's output rate (the host resamples to the device rate); its
 //!   callback drains the ring.
+//!
+//! Both open at the session's `audio_sample_rate` (44100Hz unless Settings →
+//! Audio Sample Rate picked another), which the core emits at, with the
+//! device buffer from Settings → Audio Buffer Size (`0` = the host's default).
+//! Both count the callbacks that found the ring empty, and together with the
+//! ring depth that is the latency/underrun readout under the FPS overlay.
+//!
+//! The cpal backend also follows Settings → Audio Output Device: a watcher
+//! thread re-enumerates the host's outputs once a second, and when the open
+//! device disappears (or its stream reports it gone) the stream is rebuilt on
+//! the default, moving back when the chosen device is plugged in again. Oboe
+//! leaves routing to AAudio, which already follows the active output.
 
 #[cfg(not(target_os = "android"))]
 pub(crate) use cpal_backend::Output;
@@ -18,25 +30,42 @@ pub(crate) use cpal_backend::Output;
 #[cfg(target_os = "android")]
 pub use oboe_backend::Output;
 
-/// Stereo sample rate the core emits at (see `audio/controller.rs`).
-const SAMPLE_RATE: u32 = 44100;
+/// Stereo sample *pairs* per emulated frame at `sample_rate`, rounded (738 at
+/// 44100Hz). Only sizes the ring and the startup pre-fill — the pacing
+/// regulator does its frame math with the exact fractional rate (`pacing.rs`).
+fn pairs_per_frame(sample_rate: u32) -> usize {
+    (f64::from(sample_rate) / rustyboi_session::pacing::NOMINAL_FPS).round() as usize
+}
+
+/// The overlay readout for a device at `sample_rate` with `buffer_pairs` in
+/// its own buffer (`0` = unknown, so only the ring counts) and `queued_pairs`
+/// in the ring.
+fn stats(
+    sample_rate: u32,
+    buffer_pairs: u32,
+    queued_pairs: usize,
+    underruns: u64,
+) -> rustyboi_frontend_lib::AudioStats {
+    let pairs = queued_pairs as f64 + f64::from(buffer_pairs);
+    rustyboi_frontend_lib::AudioStats {
+        latency_ms: (pairs * 1000.0 / f64::from(sample_rate)) as f32,
+        underruns,
+    }
+}
 
 #[cfg(not(target_os = "android"))]
 mod cpal_backend {
-    use super::SAMPLE_RATE;
+    use super::{pairs_per_frame, stats};
     use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
     use ringbuf::{
         traits::{Consumer, Observer, Producer, Split},
         HeapProd, HeapRb,
     };
     use rustyboi_core_lib::audio::AudioOutput;
-    use std::sync::atomic::{AtomicU64, Ordering};
-    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
-    /// Stereo sample *pairs* per emulated frame (44100 / 59.7275 fps), rounded.
-    /// Only sizes the ring and the startup pre-fill — the pacing regulator does
-    /// its frame math with the exact fractional constant (`pacing.rs`).
-    const SAMPLES_PER_FRAME: usize = 738;
     /// Playback gain, applied on push (same as the Android backend's VOLUME).
     const VOLUME: f32 = 0.3;
     /// Ring capacity in frames. Generous — the pacing regulator holds the fill
@@ -49,38 +78,132 @@ mod cpal_backend {
     /// demanding emulated frames, and whatever the pipeline doesn't eat plays
     /// out inaudibly while the regulator's backlog trim settles the ring.
     const PRIME_SILENCE_FRAMES: usize = 12;
+    /// How often the watcher thread re-enumerates output devices.
+    const DEVICE_POLL: Duration = Duration::from_secs(1);
+
+    /// A device's display name, the key Settings → Audio Output Device stores.
+    fn device_name(device: &cpal::Device) -> Option<String> {
+        device.description().ok().map(|d| d.name().to_owned())
+    }
 
-    /// Desktop/iOS output: a cpal stream opened AT THE CORE'S 44100Hz whose
+    /// Every output device the host offers, by name.
+    fn device_names(host: &cpal::Host) -> Vec<String> {
+        host.output_devices()
+            .map(|devices| devices.filter_map(|d| device_name(&d)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Start the thread that publishes the device list every [`DEVICE_POLL`].
+    /// Enumeration can take tens of milliseconds on some hosts (ALSA probes
+    /// every card), so it stays off the render thread. The thread exits once
+    /// the returned slot is dropped.
+    fn spawn_device_watcher() -> Arc<Mutex<Option<Vec<String>>>> {
+        let slot = Arc::new(Mutex::new(None));
+        let weak = Arc::downgrade(&slot);
+        let spawned = std::thread::Builder::new().name("audio-devices".into()).spawn(move || {
+            let host = cpal::default_host();
+            loop {
+                let names = device_names(&host);
+                let Some(slot) = weak.upgrade() else { break };
+                if let Ok(mut slot) = slot.lock() {
+                    *slot = Some(names);
+                }
+                drop(slot);
+                std::thread::sleep(DEVICE_POLL);
+            }
+        });
+        if let Err(e) = spawned {
+            log::warn!("audio device watcher failed to start: {e}");
+        }
+        slot
+    }
+
+    /// Desktop/iOS output: a cpal stream opened AT THE CORE'S RATE whose
     /// callback drains the SPSC ring 1:1, zero-filling on underrun — exactly
     /// the Android oboe model. The host does any device-rate conversion
     /// (PipeWire/CoreAudio natively, WASAPI via AUTOCONVERTPCM), so ring
-    /// consumption is precisely 44100 pairs/s by the host audio clock: the
+    /// consumption is precisely the core's pairs/s by the host audio clock: the
     /// honest, sample-accurate signal the pacing regulator trims against.
     /// (The previous rodio sink's mixer/converter stack consumed the ring at
     /// the device rate when its span bootstrap mis-captured the source rate,
     /// and leaked samples on every span re-bootstrap — 10-20%% zero-fill.)
     pub(crate) struct Output {
+        sample_rate: u32,
+        /// Requested device buffer in pairs per callback (`0` = host default).
+        buffer_pairs: u32,
         stream: Option<cpal::Stream>,
         prod: Option<HeapProd<f32>>,
         scratch: Vec<f32>,
         /// Raw f32 samples the callback zero-filled because the ring was empty.
         underrun_samples: Arc<AtomicU64>,
+        /// Callbacks that zero-filled any of their buffer.
+        underruns: Arc<AtomicU64>,
+        /// Settings → Audio Output Device (`None` = the host default).
+        preferred: Option<String>,
+        /// Name of the device the stream is open on.
+        opened: Option<String>,
+        /// Set by the stream's error callback when the device went away.
+        lost: Arc<AtomicBool>,
+        /// The watcher thread's latest device list, taken by
+        /// [`follow_device`](Self::follow_device).
+        devices: Option<Arc<Mutex<Option<Vec<String>>>>>,
     }
 
     impl Output {
-        pub(crate) fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        pub(crate) fn new(
+            sample_rate: u32,
+            buffer_pairs: u32,
+            device: Option<String>,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
             Ok(Output {
+                sample_rate,
+                buffer_pairs,
                 stream: None,
                 prod: None,
                 scratch: Vec::new(),
                 underrun_samples: Arc::new(AtomicU64::new(0)),
+                underruns: Arc::new(AtomicU64::new(0)),
+                preferred: device,
+                opened: None,
+                lost: Arc::new(AtomicBool::new(false)),
+                devices: None,
             })
         }
 
         pub(crate) fn start_device(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+            self.devices.get_or_insert_with(spawn_device_watcher);
             <Self as AudioOutput>::start(self)
         }
 
+        /// Track Settings → Audio Output Device and the devices actually
+        /// present; call once per tick. Reopens the stream right away when the
+        /// preference changed, and on the watcher's next poll when the open
+        /// device is gone (onto the default) or the preferred one is back.
+        /// Returns the device list whenever the watcher published a new one.
+        pub(crate) fn follow_device(&mut self, preferred: Option<&str>) -> Option<Vec<String>> {
+            let names = self.devices.as_ref().and_then(|slot| slot.lock().ok()?.take());
+            let changed = preferred != self.preferred.as_deref();
+            let reopen = changed
+                || names.as_ref().is_some_and(|names| {
+                    let present = |name: &str| names.iter().any(|n| n == name);
+                    let gone = self.stream.is_none()
+                        || self.lost.load(Ordering::Relaxed)
+                        || self.opened.as_deref().is_some_and(|name| !present(name));
+                    let back = preferred.is_some_and(|want| self.opened.as_deref() != Some(want) && present(want));
+                    gone || back
+                });
+            if reopen {
+                self.preferred = preferred.map(str::to_owned);
+                self.stream = None;
+                if let Err(e) = <Self as AudioOutput>::start(self) {
+                    // Retried on the next poll; nothing plays meanwhile.
+                    log::warn!("audio device reopen failed: {e}");
+                    self.prod = None;
+                }
+            }
+            names
+        }
+
         pub(crate) fn push_samples(&mut self, samples: &[(f32, f32)]) {
             <Self as AudioOutput>::add_samples(self, samples)
         }
@@ -90,26 +213,52 @@ mod cpal_backend {
         pub(crate) fn queued_pairs(&self) -> usize {
             self.prod.as_ref().map_or(0, |p| p.occupied_len() / 2)
         }
+
+        /// Latency and underrun count for the FPS overlay.
+        pub(crate) fn stats(&self) -> rustyboi_frontend_lib::AudioStats {
+            let underruns = self.underruns.load(Ordering::Relaxed);
+            stats(self.sample_rate, self.buffer_pairs, self.queued_pairs(), underruns)
+        }
     }
 
     impl AudioOutput for Output {
         fn start(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-            let (mut prod, mut cons) = HeapRb::<f32>::new(RING_FRAMES * SAMPLES_PER_FRAME * 2).split();
+            let per_frame = pairs_per_frame(self.sample_rate);
+            let (mut prod, mut cons) = HeapRb::<f32>::new(RING_FRAMES * per_frame * 2).split();
             // Absorb the host pipeline's one-time startup fill with silence
             // (see PRIME_SILENCE_FRAMES) so it can't fast-forward the game.
-            let silence = vec![0.0f32; PRIME_SILENCE_FRAMES * SAMPLES_PER_FRAME * 2];
+            let silence = vec![0.0f32; PRIME_SILENCE_FRAMES * per_frame * 2];
             prod.push_slice(&silence);
 
             let host = cpal::default_host();
-            let device = host
-                .default_output_device()
-                .ok_or("no default audio output device")?;
-            let config = cpal::StreamConfig {
-                channels: 2,
-                sample_rate: SAMPLE_RATE,
-                buffer_size: cpal::BufferSize::Default,
+            let named = self.preferred.as_deref().and_then(|want| {
+                host.output_devices().ok()?.find(|d| device_name(d).as_deref() == Some(want))
+            });
+            if let (Some(want), None) = (self.preferred.as_deref(), named.as_ref()) {
+                log::warn!("audio device {want:?} not found; using the default until it returns");
+            }
+            let device = match named {
+                Some(device) => device,
+                None => host.default_output_device().ok_or("no default audio output device")?,
+            };
+            // A requested size outside what the device reports it supports is
+            // clamped into range rather than failing the open: a latency
+            // preference is not worth losing sound over.
+            let supported = device.default_output_config().map(|c| *c.buffer_size());
+            self.buffer_pairs = match (self.buffer_pairs, supported) {
+                (0, _) => 0,
+                (n, Ok(cpal::SupportedBufferSize::Range { min, max })) => n.clamp(min, max),
+                (n, _) => n,
             };
+            let buffer_size = match self.buffer_pairs {
+                0 => cpal::BufferSize::Default,
+                n => cpal::BufferSize::Fixed(n),
+            };
+            let config = cpal::StreamConfig { channels: 2, sample_rate: self.sample_rate, buffer_size };
             let underrun_samples = self.underrun_samples.clone();
+            let underruns = self.underruns.clone();
+            let lost = self.lost.clone();
+            lost.store(false, Ordering::Relaxed);
             let stream = device.build_output_stream(
                 config,
                 move |data: &mut [f32], _| {
@@ -118,13 +267,26 @@ mod cpal_backend {
                     if got < data.len() {
                         data[got..].fill(0.0);
                         underrun_samples.fetch_add((data.len() - got) as u64, Ordering::Relaxed);
+                        underruns.fetch_add(1, Ordering::Relaxed);
                     }
                 },
-                |e| log::warn!("audio stream error: {e}"),
+                move |e: cpal::Error| {
+                    // Unplugged or invalidated: `follow_device` rebuilds the
+                    // stream on the next poll.
+                    if matches!(e.kind(), cpal::ErrorKind::DeviceNotAvailable | cpal::ErrorKind::StreamInvalidated) {
+                        lost.store(true, Ordering::Relaxed);
+                    }
+                    log::warn!("audio stream error: {e}");
+                },
                 None,
             )?;
             stream.play()?;
-            log::info!("cpal stream opened at {SAMPLE_RATE}Hz stereo (host converts to device rate)");
+            self.opened = device_name(&device);
+            log::info!(
+                "cpal stream opened on {:?} at {}Hz stereo (host converts to device rate)",
+                self.opened.as_deref().unwrap_or("?"),
+                self.sample_rate
+            );
 
             self.stream = Some(stream);
             self.prod = Some(prod);
@@ -160,7 +322,7 @@ mod cpal_backend {
         /// real cpal device). Returns the consumer end so tests can inspect what
         /// the callback would drain.
         fn wire_ring(out: &mut Output) -> HeapCons<f32> {
-            let (prod, cons) = HeapRb::<f32>::new(RING_FRAMES * SAMPLES_PER_FRAME * 2).split();
+            let (prod, cons) = HeapRb::<f32>::new(RING_FRAMES * pairs_per_frame(44_100) * 2).split();
             out.prod = Some(prod);
             cons
         }
@@ -170,7 +332,7 @@ mod cpal_backend {
         /// empty and the callback zero-filled forever (silent desktop audio).
         #[test]
         fn add_samples_feeds_the_ring() {
-            let mut out = Output::new().unwrap();
+            let mut out = Output::new(44_100, 0, None).unwrap();
             let mut cons = wire_ring(&mut out);
 
             out.push_samples(&[(1.0, -1.0), (0.5, -0.5)]);
@@ -186,14 +348,14 @@ mod cpal_backend {
         #[test]
         fn add_samples_is_a_noop_before_the_device_starts() {
             // No ring wired yet: must not panic, must enqueue nothing.
-            let mut out = Output::new().unwrap();
+            let mut out = Output::new(44_100, 0, None).unwrap();
             out.push_samples(&[(1.0, -1.0)]);
             assert_eq!(out.queued_pairs(), 0);
         }
 
         #[test]
         fn empty_push_is_a_noop() {
-            let mut out = Output::new().unwrap();
+            let mut out = Output::new(44_100, 0, None).unwrap();
             let _cons = wire_ring(&mut out);
             out.push_samples(&[]);
             assert_eq!(out.queued_pairs(), 0);
@@ -203,7 +365,7 @@ mod cpal_backend {
 
 #[cfg(target_os = "android")]
 mod oboe_backend {
-    use super::SAMPLE_RATE;
+    use super::{pairs_per_frame, stats};
     use oboe::{
         AudioOutputCallback, AudioOutputStreamSafe, AudioStream, AudioStreamAsync,
         AudioStreamBase, AudioStreamBuilder, AudioStreamSafe, DataCallbackResult,
@@ -218,10 +380,6 @@ mod oboe_backend {
     use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
     use std::sync::Arc;
 
-    /// Stereo sample *pairs* per emulated frame (44100 / 59.7275 fps), rounded.
-    /// Only sizes the ring and the startup pre-fill — the pacing regulator does
-    /// its frame math with the exact fractional constant (`pacing.rs`).
-    const SAMPLES_PER_FRAME: usize = 738;
     /// Playback gain, matching the desktop sink's `set_volume(0.3)`.
     const VOLUME: f32 = 0.3;
     /// Ring capacity in frames. Generous — the pacing regulator holds the fill
@@ -239,6 +397,7 @@ mod oboe_backend {
     struct Callback {
         cons: HeapCons<f32>,
         underruns: Arc<AtomicU32>,
+        underrun_total: Arc<AtomicU64>,
         underrun_samples: Arc<AtomicU64>,
     }
 
@@ -262,6 +421,7 @@ mod oboe_backend {
             }
             if zero_filled > 0 {
                 self.underruns.fetch_add(1, Ordering::Relaxed);
+                self.underrun_total.fetch_add(1, Ordering::Relaxed);
                 self.underrun_samples.fetch_add(zero_filled, Ordering::Relaxed);
             }
             DataCallbackResult::Continue
@@ -269,9 +429,15 @@ mod oboe_backend {
     }
 
     pub struct Output {
+        sample_rate: u32,
+        /// Requested frames per callback (`0` = the stream's own burst size).
+        buffer_pairs: u32,
         stream: Option<AudioStreamAsync<OboeOutput, Callback>>,
         prod: Option<HeapProd<f32>>,
+        /// Underrun callbacks since the last log report (swapped to zero).
         underruns: Arc<AtomicU32>,
+        /// Underrun callbacks since the stream opened (the overlay's count).
+        underrun_total: Arc<AtomicU64>,
         underrun_samples: Arc<AtomicU64>,
         scratch: Vec<f32>,
         /// Counts appended frames to throttle the underrun check to ~once/second.
@@ -282,11 +448,20 @@ mod oboe_backend {
     }
 
     impl Output {
-        pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        /// `_device` is the desktop backend's Settings → Audio Output Device;
+        /// AAudio routes the stream to the active output itself.
+        pub fn new(
+            sample_rate: u32,
+            buffer_pairs: u32,
+            _device: Option<String>,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
             Ok(Output {
+                sample_rate,
+                buffer_pairs,
                 stream: None,
                 prod: None,
                 underruns: Arc::new(AtomicU32::new(0)),
+                underrun_total: Arc::new(AtomicU64::new(0)),
                 underrun_samples: Arc::new(AtomicU64::new(0)),
                 scratch: Vec::new(),
                 report_frames: 0,
@@ -298,6 +473,12 @@ mod oboe_backend {
             <Self as AudioOutput>::start(self)
         }
 
+        /// Same contract as the desktop backend, but AAudio follows the active
+        /// output on its own: there is no device list to offer.
+        pub fn follow_device(&mut self, _preferred: Option<&str>) -> Option<Vec<String>> {
+            None
+        }
+
         pub fn push_samples(&mut self, samples: &[(f32, f32)]) {
             <Self as AudioOutput>::add_samples(self, samples)
         }
@@ -319,33 +500,49 @@ mod oboe_backend {
         pub fn underrun_samples(&self) -> u64 {
             self.underrun_samples.load(Ordering::Relaxed)
         }
+
+        /// Latency and underrun count for the FPS overlay — same contract as
+        /// the desktop backend.
+        pub fn stats(&self) -> rustyboi_frontend_lib::AudioStats {
+            let underruns = self.underrun_total.load(Ordering::Relaxed);
+            stats(self.sample_rate, self.buffer_pairs, self.queued_pairs(), underruns)
+        }
     }
 
     impl AudioOutput for Output {
         fn start(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-            let (mut prod, cons) = HeapRb::<f32>::new(RING_FRAMES * SAMPLES_PER_FRAME * 2).split();
+            let per_frame = pairs_per_frame(self.sample_rate);
+            let (mut prod, cons) = HeapRb::<f32>::new(RING_FRAMES * per_frame * 2).split();
             let underruns = Arc::new(AtomicU32::new(0));
             // Absorb the host pipeline's one-time startup fill with silence
             // (see PRIME_SILENCE_FRAMES). The callback zero-fills on an empty
             // ring, so the stream can start immediately — no deferred-start
             // priming latch needed (same model as the desktop backend).
-            let silence = vec![0.0f32; PRIME_SILENCE_FRAMES * SAMPLES_PER_FRAME * 2];
+            let silence = vec![0.0f32; PRIME_SILENCE_FRAMES * per_frame * 2];
             prod.push_slice(&silence);
             self.pushed_pairs = (silence.len() / 2) as u64;
 
-            // Request the AAudio low-latency (MMAP) path. We feed 44.1kHz and let
-            // oboe resample to the device rate so the fast path is preserved.
-            let mut stream = AudioStreamBuilder::default()
+            // Request the AAudio low-latency (MMAP) path. We feed the core's rate
+            // and let oboe resample to the device rate so the fast path is preserved.
+            let builder = AudioStreamBuilder::default()
                 .set_performance_mode(PerformanceMode::LowLatency)
                 .set_sharing_mode(SharingMode::Shared)
                 .set_usage(Usage::Game)
                 .set_format::<f32>()
                 .set_channel_count::<Stereo>()
-                .set_sample_rate(SAMPLE_RATE as i32)
-                .set_sample_rate_conversion_quality(SampleRateConversionQuality::Medium)
+                .set_sample_rate(self.sample_rate as i32)
+                .set_sample_rate_conversion_quality(SampleRateConversionQuality::Medium);
+            // Settings → Audio Buffer Size; left unset, AAudio calls back once
+            // per burst, its lowest-latency choice.
+            let builder = match self.buffer_pairs {
+                0 => builder,
+                n => builder.set_frames_per_callback(n as i32),
+            };
+            let mut stream = builder
                 .set_callback(Callback {
                     cons,
                     underruns: underruns.clone(),
+                    underrun_total: self.underrun_total.clone(),
                     underrun_samples: self.underrun_samples.clone(),
                 })
                 .open_stream()
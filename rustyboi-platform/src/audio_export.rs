@@ -0,0 +1,151 @@
+//! Streams Emulation → Record Audio captures to disk as they run (native
+//! desktop).
+//!
+//! The destination is asked for when recording starts rather than when it
+//! stops, so each frame's PCM goes straight into a buffered file and nothing
+//! accumulates in memory however long the capture runs. With stems on, the
+//! four channels land beside the mix as `{name}-pulse1.wav` and so on. Each
+//! WAV starts with a placeholder header that is rewritten with the real
+//! length when the capture ends.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use rustyboi_session::{wav_header, AudioFile, AudioSink, STEM_NAMES};
+
+/// One WAV being written.
+struct WavFile {
+    path: PathBuf,
+    out: BufWriter<File>,
+    len: u32,
+}
+
+impl WavFile {
+    fn create(path: PathBuf, sample_rate: u32) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(&path)?);
+        out.write_all(&wav_header(sample_rate, 0))?;
+        Ok(WavFile { path, out, len: 0 })
+    }
+
+    fn write(&mut self, pcm: &[u8]) -> Result<(), String> {
+        self.out.write_all(pcm).map_err(|e| format!("{}: {e}", self.path.display()))?;
+        self.len = self.len.saturating_add(pcm.len() as u32);
+        Ok(())
+    }
+
+    fn close(mut self, sample_rate: u32) -> Result<(), String> {
+        let header = wav_header(sample_rate, self.len);
+        let result = self.out.seek(SeekFrom::Start(0)).and_then(|_| self.out.write_all(&header));
+        result.and_then(|()| self.out.flush()).map_err(|e| format!("{}: {e}", self.path.display()))
+    }
+}
+
+/// The session's sink for a capture written to disk.
+pub(crate) struct FileSink {
+    sample_rate: u32,
+    mix: WavFile,
+    stems: Option<[WavFile; 4]>,
+    /// The first write that failed; later writes are skipped and the error
+    /// is reported when the capture ends.
+    failed: Option<String>,
+}
+
+impl FileSink {
+    /// Create the mix at `path`, and the stems beside it when `stems` is set.
+    pub(crate) fn create(path: &Path, sample_rate: u32, stems: bool) -> io::Result<Self> {
+        let mix = WavFile::create(path.to_path_buf(), sample_rate)?;
+        let stems = if stems {
+            let mut files = Vec::with_capacity(STEM_NAMES.len());
+            for name in STEM_NAMES {
+                files.push(WavFile::create(stem_path(path, name), sample_rate)?);
+            }
+            files.try_into().ok()
+        } else {
+            None
+        };
+        Ok(FileSink { sample_rate, mix, stems, failed: None })
+    }
+}
+
+impl AudioSink for FileSink {
+    fn mix(&mut self, pcm: &[u8]) {
+        if self.failed.is_none()
+            && let Err(e) = self.mix.write(pcm)
+        {
+            self.failed = Some(e);
+        }
+    }
+
+    fn stems(&mut self, pcm: [&[u8]; 4]) {
+        let Some(stems) = self.stems.as_mut() else { return };
+        if self.failed.is_some() {
+            return;
+        }
+        for (stem, pcm) in stems.iter_mut().zip(pcm) {
+            if let Err(e) = stem.write(pcm) {
+                self.failed = Some(e);
+                return;
+            }
+        }
+    }
+
+    fn finish(self: Box<Self>) -> Option<AudioFile> {
+        let FileSink { sample_rate, mix, stems, mut failed } = *self;
+        let path = mix.path.clone();
+        for file in std::iter::once(mix).chain(stems.into_iter().flatten()) {
+            if let Err(e) = file.close(sample_rate) {
+                failed.get_or_insert(e);
+            }
+        }
+        match failed {
+            Some(e) => log::error!("Audio recording incomplete: {e}"),
+            None => log::info!("Audio recording written to {}", path.display()),
+        }
+        None
+    }
+}
+
+/// `recording.wav` → `recording-pulse1.wav`.
+fn stem_path(mix: &Path, stem: &str) -> PathBuf {
+    let base = mix.file_stem().map_or_else(|| "recording".into(), |s| s.to_string_lossy());
+    mix.with_file_name(format!("{base}-{stem}.wav"))
+}
+
+/// The session's [`AudioSinkOpener`](rustyboi_session::AudioSinkOpener):
+/// ask where the mix goes, then stream into it.
+pub(crate) fn open(sample_rate: u32, stems: bool) -> Result<Option<Box<dyn AudioSink>>, String> {
+    let Some(path) = rfd::FileDialog::new()
+        .set_file_name("recording.wav")
+        .add_filter("WAV audio", &["wav"])
+        .save_file()
+    else {
+        return Ok(None);
+    };
+    let sink = FileSink::create(&path, sample_rate, stems).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(Some(Box::new(sink)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_mix_and_stems_stream_into_finished_wavs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("take.wav");
+        let mut sink: Box<dyn AudioSink> = Box::new(FileSink::create(&path, 48_000, true).unwrap());
+        sink.mix(&[1, 0, 2, 0]);
+        sink.stems([&[1, 0, 0, 0], &[0; 4], &[0; 4], &[0, 0, 2, 0]]);
+        sink.mix(&[3, 0, 4, 0]);
+        assert!(sink.finish().is_none());
+
+        let mix = std::fs::read(&path).unwrap();
+        assert_eq!(&mix[..44], &wav_header(48_000, 8));
+        assert_eq!(&mix[44..], &[1, 0, 2, 0, 3, 0, 4, 0]);
+        for name in STEM_NAMES {
+            let stem = std::fs::read(dir.path().join(format!("take-{name}.wav"))).unwrap();
+            assert_eq!(&stem[..44], &wav_header(48_000, 4), "{name}");
+        }
+    }
+}
@@ -5,13 +5,15 @@
 //! table.
 
 use clap::Parser;
-use rustyboi_core_lib::gb;
+use rustyboi_core_lib::{cartridge, gb};
 
 pub(crate) use rustyboi_frontend_lib::DmgPaletteChoice;
 pub(crate) use rustyboi_session::SgbPaletteChoice;
+use rustyboi_session::palette::parse_palette;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[command(group(clap::ArgGroup::new("headless").args(["screenshot", "exit_on_break"]).multiple(true)))]
 pub(crate) struct RawConfig {
     /// BIOS file path, optional
     #[arg(short, long)]
@@ -32,15 +34,34 @@ pub(crate) struct RawConfig {
     #[arg(short, long)]
     rom: Option<String>,
 
+    /// Member of a zip/7z ROM archive to load, when it holds several ROMs
+    /// (defaults to the first)
+    #[arg(long)]
+    archive_entry: Option<String>,
+
     /// Save state file path to load on startup, optional
     #[arg(long)]
     state: Option<String>,
 
-    /// Scale factor for GUI
-    #[arg(short, long, default_value_t = 5)]
-    scale: u8,
+    /// Directory for battery saves (`.sav`/`.rtc`), one subfolder per ROM,
+    /// for this run. Defaults to Settings → Save Folder, else next to the ROM
+    /// (or the app data directory when the ROM's folder is read-only)
+    #[arg(long)]
+    save_dir: Option<String>,
+
+    /// Where RTC carts keep their clock for this run: footer (appended to the
+    /// `.sav`), sidecar (a separate `.rtc`), or both. Defaults to the saved
+    /// setting, else both
+    #[arg(long)]
+    rtc_format: Option<cartridge::RtcFormat>,
+
+    /// Window size as a multiple of the native picture for this run
+    /// (default: the saved View → Window Scale)
+    #[arg(short, long)]
+    scale: Option<u8>,
 
-    /// Color palette (greenlcd, grayscale, green, pocket, ...)
+    /// Color palette (greenlcd, grayscale, green, pocket, ...), or four hex
+    /// colours lightest first ("#9BBC0F,#8BAC0F,#306230,#0F380F")
     #[arg(short, long, default_value = "greenlcd")]
     palette: String,
 
@@ -53,17 +74,133 @@ pub(crate) struct RawConfig {
     #[arg(long, default_value_t = false)]
     skip_bios: bool,
 
+    /// No-Intro style DAT (clrmamepro text) to verify loaded ROMs against;
+    /// ROM Info and the status bar then report "verified good dump" or
+    /// "unknown dump"
+    #[arg(long)]
+    dat: Option<String>,
+
+    /// Refuse to boot a ROM whose header checksum is wrong (as the DMG boot
+    /// ROM would) instead of only warning about it
+    #[arg(long, default_value_t = false)]
+    strict_header: bool,
+
     /// Attach a Game Boy Printer to the link port; captured prints are
     /// written as PNGs next to the ROM
     #[arg(long, default_value_t = false)]
     printer: bool,
 
+    /// Print every byte the game sends over the link port to stdout as it is
+    /// sent, so serial-reporting test ROMs (blargg's) show their results in
+    /// the terminal
+    #[arg(long, default_value_t = false)]
+    serial_stdout: bool,
+
+    /// Feed a still picture to the Game Boy Camera sensor instead of its
+    /// built-in test pattern: a PGM (P5/P2) of any size, or a raw 128x112
+    /// grayscale dump
+    #[arg(long)]
+    camera_image: Option<String>,
+
+    /// Feed a live host webcam to the Game Boy Camera sensor, captured
+    /// through ffmpeg (which must be on the PATH): a device path on Linux
+    /// (/dev/video0), an index on macOS (0), or video=NAME on Windows
+    #[arg(long, value_name = "DEVICE", conflicts_with = "camera_image")]
+    camera_device: Option<String>,
+
     /// Rendering backend for this run: auto, vulkan, metal, opengl, or
     /// software. Overrides (without persisting) the saved Settings choice;
     /// auto probes the platform's native API first (Vulkan, or Metal on
     /// Apple), then OpenGL, then the CPU software renderer.
     #[arg(long)]
     graphics: Option<String>,
+
+    /// Mute APU channels, as a comma-separated list of 1-4 or pulse1, pulse2,
+    /// wave, noise. Replaces the saved Settings → Audio Channels mutes
+    #[arg(long)]
+    mute: Option<String>,
+
+    /// Solo APU channels (same list format as --mute): only these play.
+    /// Replaces the saved Settings → Audio Channels solos
+    #[arg(long)]
+    solo: Option<String>,
+
+    /// Record video from launch until exit, synchronized on emulated time. A
+    /// `.zip` path gets PNG frames plus a WAV; any other extension (`.mp4`,
+    /// `.mkv`, ...) is muxed by ffmpeg when it is on the PATH, else the zip
+    /// is written alongside
+    #[arg(long)]
+    record_video: Option<String>,
+
+    /// Host a netplay link cable: wait on this TCP port for a partner to
+    /// `--netplay-join`, then run with the link port joined to theirs
+    #[arg(long, conflicts_with = "netplay_join")]
+    netplay_host: Option<u16>,
+
+    /// Join a netplay link cable hosted at HOST:PORT
+    #[arg(long)]
+    netplay_join: Option<String>,
+
+    /// Let browsers spectate: serve a viewer page and stream the screen over
+    /// WebSocket on this TCP port (open http://HOST:PORT/)
+    #[arg(long)]
+    spectate: Option<u16>,
+
+    /// Stream the game's audio to spectators too
+    #[arg(long, default_value_t = false, requires = "spectate")]
+    spectate_audio: bool,
+
+    /// Accept JSON-RPC automation commands on this address: HOST:PORT for
+    /// TCP (loopback clients only, with the auth token printed at startup),
+    /// or unix:PATH for a Unix-domain socket
+    #[arg(long, value_name = "ADDR")]
+    control: Option<String>,
+
+    /// Serve the debugger (registers, memory, breakpoints, stepping) as
+    /// JSON-RPC over WebSocket on this localhost port, for external tools
+    #[arg(long, value_name = "PORT")]
+    debug_server: Option<u16>,
+
+    /// Run the ROM headless (no window or audio) as fast as possible for this
+    /// many seconds, print frames/s and the MHz-equivalent, and exit
+    #[arg(long, value_name = "SECONDS", conflicts_with = "screenshot")]
+    bench: Option<f64>,
+
+    /// Frames to run headless (with --exit-on-break, the most to run before
+    /// giving up on the breakpoint)
+    #[arg(long, value_name = "N", requires = "headless")]
+    frames: Option<u32>,
+
+    /// Run --frames frames headless (no window or audio) from power-on or
+    /// --state, write the final frame to this PNG, and exit
+    #[arg(long, value_name = "PATH", requires = "frames")]
+    screenshot: Option<String>,
+
+    /// Start with emulation paused, so the debugger can be set up before
+    /// the first instruction runs
+    #[arg(long, default_value_t = false)]
+    paused: bool,
+
+    /// Set a breakpoint at this hex address (0x0150, $0150 or 0150);
+    /// repeatable or comma-separated
+    #[arg(long = "break", value_name = "ADDR[,ADDR...]", value_delimiter = ',', value_parser = parse_address)]
+    breakpoints: Vec<u16>,
+
+    /// Run headless until a --break address is reached, then exit with
+    /// status 3 (0 if --frames ran out first)
+    #[arg(long, default_value_t = false, requires = "breakpoints", conflicts_with = "bench")]
+    exit_on_break: bool,
+
+    /// Write the registers and the 64 KiB memory map as JSON to this file
+    /// when an --exit-on-break run stops
+    #[arg(long, value_name = "PATH", requires = "exit_on_break")]
+    break_dump: Option<String>,
+}
+
+/// A `--break` address: hex, with or without a `0x`/`$` prefix.
+fn parse_address(s: &str) -> Result<u16, String> {
+    let digits = s.trim().trim_start_matches("0x").trim_start_matches("0X").trim_start_matches('$');
+    u16::from_str_radix(digits, 16).map_err(|_| format!("'{s}' is not a 16-bit hex address"))
 }
 
 pub(crate) struct CleanConfig {
@@ -73,14 +210,22 @@ pub(crate) struct CleanConfig {
     pub sgb_firmware: Option<String>,
     // path to ROM file
     pub rom: Option<String>,
+    // member of a multi-ROM archive to load (None = the first ROM)
+    pub archive_entry: Option<String>,
     // Hardware type (DMG, CGB, SGB, etc.)
     pub hardware: gb::Hardware,
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     // path to save state to load on startup
     pub state: Option<String>,
-    // GUI scale factor
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // battery-save directory (None = next to the ROM)
+    pub save_dir: Option<String>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // where RTC carts write their clock (None = the saved setting)
+    pub rtc_format: Option<cartridge::RtcFormat>,
+    // window scale override (None = the saved setting)
     #[cfg(not(target_os = "android"))]
-    pub scale: u8,
+    pub scale: Option<u8>,
     // Color palette
     pub palette: DmgPaletteChoice,
     // SGB colorization for DMG games (only applies on SGB hardware)
@@ -88,11 +233,78 @@ pub(crate) struct CleanConfig {
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     // skip BIOS on startup
     pub skip_bios: bool,
+    // DAT file to verify loaded ROMs against (None = no verification)
+    pub dat: Option<String>,
+    // refuse ROMs with a bad header checksum instead of warning
+    pub strict_header: bool,
     // attach a Game Boy Printer to the link port at startup
     pub printer: bool,
+    // echo bytes sent over the link port to stdout
+    pub serial_stdout: bool,
+    // still image fed to the Game Boy Camera sensor (None = test pattern)
+    pub camera_image: Option<String>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // host webcam captured for the Game Boy Camera sensor (None = no capture)
+    pub camera_device: Option<String>,
     // rendering backend override for this run (None = use the saved Settings
     // choice); never persisted
     pub graphics: Option<rustyboi_session::GraphicsBackend>,
+    // channels to mute / solo (None = keep the saved Settings choice)
+    pub mute: Option<[bool; 4]>,
+    pub solo: Option<[bool; 4]>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // where to write a capture recorded from launch to exit (None = no capture)
+    pub record_video: Option<String>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // TCP port to host netplay on (None = not hosting)
+    pub netplay_host: Option<u16>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // HOST:PORT of a netplay host to join (None = not joining)
+    pub netplay_join: Option<String>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // TCP port spectators watch on (None = no stream)
+    pub spectate: Option<u16>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // include audio in the spectator stream
+    pub spectate_audio: bool,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // address of the JSON-RPC control socket (None = no socket)
+    pub control: Option<String>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // localhost port of the WebSocket debug server (None = not serving)
+    pub debug_server: Option<u16>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // wall-clock seconds to benchmark for headless (None = normal run)
+    pub bench: Option<f64>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // frames to run headless (None = until a breakpoint)
+    pub frames: Option<u32>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // PNG to write the last headless frame to (None = no screenshot)
+    pub screenshot: Option<String>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // start with emulation paused
+    pub paused: bool,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // breakpoint addresses set before the first frame
+    pub breakpoints: Vec<u16>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // run headless until a breakpoint, then exit with a distinct status
+    pub exit_on_break: bool,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    // JSON file for the registers/memory when an --exit-on-break run stops
+    pub break_dump: Option<String>,
+}
+
+/// Parse a `--mute`/`--solo` channel list, warning about (and ignoring) a bad
+/// one so the saved Settings choice stays in effect.
+fn channel_list(flag: &str, list: Option<String>) -> Option<[bool; 4]> {
+    let list = list?;
+    let parsed = rustyboi_session::ChannelMix::parse_channels(&list);
+    if parsed.is_none() {
+        eprintln!("unknown --{flag} channel list '{list}' (expected 1-4 or pulse1|pulse2|wave|noise); using saved setting");
+    }
+    parsed
 }
 
 impl RawConfig {
@@ -109,17 +321,30 @@ impl RawConfig {
             bios: self.bios,
             sgb_firmware: self.sgb_firmware,
             rom: self.rom,
+            archive_entry: self.archive_entry,
             hardware: self.hardware,
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             state: self.state,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            save_dir: self.save_dir,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            rtc_format: self.rtc_format,
             #[cfg(not(target_os = "android"))]
-            scale: self.scale,
-            palette: DmgPaletteChoice::from_option_id(&self.palette).unwrap_or(DmgPaletteChoice::Green),
+            scale: self.scale.filter(|&s| s > 0),
+            palette: DmgPaletteChoice::from_option_id(&self.palette)
+                .or_else(|| parse_palette(&self.palette).ok().map(DmgPaletteChoice::Custom))
+                .unwrap_or(DmgPaletteChoice::Green),
             sgb_palette: SgbPaletteChoice::from_option_id(&self.sgb_palette)
                 .unwrap_or(SgbPaletteChoice::Auto),
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             skip_bios: _skip_bios,
+            dat: self.dat,
+            strict_header: self.strict_header,
             printer: self.printer,
+            serial_stdout: self.serial_stdout,
+            camera_image: self.camera_image,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            camera_device: self.camera_device,
             graphics: self.graphics.as_deref().and_then(|s| {
                 let parsed = rustyboi_session::GraphicsBackend::from_option_id(s);
                 if parsed.is_none() {
@@ -127,6 +352,35 @@ impl RawConfig {
                 }
                 parsed
             }),
+            mute: channel_list("mute", self.mute),
+            solo: channel_list("solo", self.solo),
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            record_video: self.record_video,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            netplay_host: self.netplay_host,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            netplay_join: self.netplay_join,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            spectate: self.spectate,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            spectate_audio: self.spectate_audio,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            control: self.control,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            debug_server: self.debug_server,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            bench: self.bench,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            frames: self.frames,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            screenshot: self.screenshot,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            paused: self.paused,            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            breakpoints: self.breakpoints,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            exit_on_break: self.exit_on_break,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            break_dump: self.break_dump,
         }
     }
 }
@@ -146,7 +400,7 @@ mod tests {
         assert_eq!(c.hardware, gb::Hardware::CGB);
         assert_eq!(c.palette, DmgPaletteChoice::Green);
         #[cfg(not(target_os = "android"))]
-        assert_eq!(c.scale, 5);
+        assert_eq!(c.scale, None);
     }
 
     #[test]
@@ -162,6 +416,15 @@ mod tests {
         assert_eq!(c.palette, DmgPaletteChoice::Green);
     }
 
+    #[test]
+    fn hex_palette_is_a_custom_choice() {
+        let c = parse(&["rustyboi", "--palette", "#FFFFFF,#AAAAAA,#555555,#000000"]);
+        assert_eq!(
+            c.palette,
+            DmgPaletteChoice::Custom([[255, 255, 255], [170, 170, 170], [85, 85, 85], [0, 0, 0]])
+        );
+    }
+
     #[test]
     fn sgb_palette_defaults_to_auto() {
         assert_eq!(parse(&["rustyboi"]).sgb_palette, SgbPaletteChoice::Auto);
@@ -185,6 +448,133 @@ mod tests {
         assert_eq!(c.sgb_palette, SgbPaletteChoice::Auto);
     }
 
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn spectate_audio_needs_a_spectate_port() {
+        let c = parse(&["rustyboi", "--spectate", "8080", "--spectate-audio"]);
+        assert_eq!((c.spectate, c.spectate_audio), (Some(8080), true));
+        assert!(RawConfig::try_parse_from(["rustyboi", "--spectate-audio"]).is_err());
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn control_takes_a_tcp_or_unix_address() {
+        assert_eq!(parse(&["rustyboi", "--control", "127.0.0.1:9000"]).control.as_deref(), Some("127.0.0.1:9000"));
+        assert_eq!(parse(&["rustyboi", "--control", "unix:/tmp/gb.sock"]).control.as_deref(), Some("unix:/tmp/gb.sock"));
+        assert_eq!(parse(&["rustyboi"]).control, None);
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn debug_server_takes_a_port() {
+        assert_eq!(parse(&["rustyboi", "--debug-server", "4711"]).debug_server, Some(4711));
+        assert!(RawConfig::try_parse_from(["rustyboi", "--debug-server", "99999"]).is_err());
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn bench_takes_seconds() {
+        assert_eq!(parse(&["rustyboi", "--bench", "2.5"]).bench, Some(2.5));
+        assert_eq!(parse(&["rustyboi"]).bench, None);
+        assert!(RawConfig::try_parse_from(["rustyboi", "--bench", "soon"]).is_err());
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn screenshot_needs_a_frame_count() {
+        let c = parse(&["rustyboi", "--frames", "120", "--screenshot", "out.png"]);
+        assert_eq!((c.frames, c.screenshot.as_deref()), (Some(120), Some("out.png")));
+        assert!(RawConfig::try_parse_from(["rustyboi", "--screenshot", "out.png"]).is_err());
+        assert!(RawConfig::try_parse_from(["rustyboi", "--frames", "120"]).is_err());
+        assert!(RawConfig::try_parse_from(["rustyboi", "--bench", "1", "--frames", "1", "--screenshot", "a.png"]).is_err());
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn exit_on_break_takes_a_hex_address_list() {
+        let c = parse(&["rustyboi", "--break", "0x0150,$C000,38", "--exit-on-break", "--break-dump", "regs.json"]);
+        assert_eq!(c.breakpoints, [0x0150, 0xC000, 0x0038]);
+        assert!(c.exit_on_break);
+        assert_eq!((c.frames, c.break_dump.as_deref()), (None, Some("regs.json")));
+        assert_eq!(parse(&["rustyboi", "--break", "150", "--exit-on-break", "--frames", "60"]).frames, Some(60));
+        assert!(RawConfig::try_parse_from(["rustyboi", "--break", "0x10000", "--exit-on-break"]).is_err());
+        assert!(RawConfig::try_parse_from(["rustyboi", "--exit-on-break"]).is_err());
+        assert!(RawConfig::try_parse_from(["rustyboi", "--break", "150", "--break-dump", "regs.json"]).is_err());
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn paused_and_repeated_breaks_preset_a_debugging_session() {
+        let c = parse(&["rustyboi", "--paused", "--break", "0x0150", "--break", "$4000,0x0200"]);
+        assert!(c.paused && !c.exit_on_break);
+        assert_eq!(c.breakpoints, [0x0150, 0x4000, 0x0200]);
+        let c = parse(&["rustyboi"]);
+        assert!(!c.paused && c.breakpoints.is_empty());
+    }
+
+    #[test]
+    fn netplay_hosts_or_joins_but_not_both() {
+        assert_eq!(parse(&["rustyboi", "--netplay-host", "5738"]).netplay_host, Some(5738));
+        let c = parse(&["rustyboi", "--netplay-join", "10.0.0.2:5738"]);
+        assert_eq!(c.netplay_join.as_deref(), Some("10.0.0.2:5738"));
+        assert!(RawConfig::try_parse_from(["rustyboi", "--netplay-host", "1", "--netplay-join", "x:1"]).is_err());
+    }
+
+    #[test]
+    fn camera_image_is_optional() {
+        assert!(parse(&["rustyboi"]).camera_image.is_none());
+        let c = parse(&["rustyboi", "--camera-image", "me.pgm"]);
+        assert_eq!(c.camera_image.as_deref(), Some("me.pgm"));
+    }
+
+    #[test]
+    fn camera_device_excludes_a_still_image() {
+        let c = parse(&["rustyboi", "--camera-device", "/dev/video0"]);
+        assert_eq!(c.camera_device.as_deref(), Some("/dev/video0"));
+        assert!(RawConfig::try_parse_from(["rustyboi", "--camera-device", "0", "--camera-image", "me.pgm"]).is_err());
+    }
+
+    #[test]
+    fn archive_entry_is_optional() {
+        assert!(parse(&["rustyboi"]).archive_entry.is_none());
+        let c = parse(&["rustyboi", "-r", "pack.zip", "--archive-entry", "Game (USA).gb"]);
+        assert_eq!(c.archive_entry.as_deref(), Some("Game (USA).gb"));
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn rtc_format_defaults_to_the_saved_setting() {
+        assert_eq!(parse(&["rustyboi"]).rtc_format, None);
+        let c = parse(&["rustyboi", "--rtc-format", "sidecar"]);
+        assert_eq!(c.rtc_format, Some(cartridge::RtcFormat::Sidecar));
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn save_dir_defaults_next_to_the_rom() {
+        assert!(parse(&["rustyboi"]).save_dir.is_none());
+        assert_eq!(parse(&["rustyboi", "--save-dir", "/saves"]).save_dir.as_deref(), Some("/saves"));
+    }
+
+    #[test]
+    fn dat_is_optional() {
+        assert!(parse(&["rustyboi"]).dat.is_none());
+        let c = parse(&["rustyboi", "--dat", "Nintendo - Game Boy.dat"]);
+        assert_eq!(c.dat.as_deref(), Some("Nintendo - Game Boy.dat"));
+    }
+
+    #[test]
+    fn strict_header_defaults_off() {
+        assert!(!parse(&["rustyboi"]).strict_header);
+        assert!(parse(&["rustyboi", "--strict-header"]).strict_header);
+    }
+
+    #[test]
+    fn serial_stdout_defaults_off() {
+        assert!(!parse(&["rustyboi"]).serial_stdout);
+        assert!(parse(&["rustyboi", "--serial-stdout"]).serial_stdout);
+    }
+
     #[test]
     fn unknown_graphics_value_is_none() {
         let c = parse(&["rustyboi", "--graphics", "banana"]);
@@ -197,6 +587,24 @@ mod tests {
         assert_eq!(c.graphics, Some(rustyboi_session::GraphicsBackend::Software));
     }
 
+    #[test]
+    fn mute_and_solo_take_channel_lists() {
+        let c = parse(&["rustyboi"]);
+        assert!(c.mute.is_none() && c.solo.is_none());
+        let c = parse(&["rustyboi", "--mute", "1,noise", "--solo", "wave"]);
+        assert_eq!(c.mute, Some([true, false, false, true]));
+        assert_eq!(c.solo, Some([false, false, true, false]));
+        assert!(parse(&["rustyboi", "--mute", "bass"]).mute.is_none());
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn record_video_is_optional() {
+        assert!(parse(&["rustyboi"]).record_video.is_none());
+        let c = parse(&["rustyboi", "--record-video", "run.mp4"]);
+        assert_eq!(c.record_video.as_deref(), Some("run.mp4"));
+    }
+
     #[test]
     fn no_graphics_flag_is_none() {
         assert!(parse(&["rustyboi"]).graphics.is_none());
@@ -0,0 +1,577 @@
+//! The automation control socket (`--control ADDR`): JSON-RPC 2.0 over a
+//! TCP port or, on Unix, a Unix-domain socket, one request per line and one
+//! response line per request. External test harnesses and bots drive the
+//! running emulator through it.
+//!
+//! Methods (params by name):
+//! - `auth {token}`: the first request on a TCP connection (see below).
+//! - `load_rom {path}`: load a ROM file (and any soft-patch beside it).
+//! - `pause`, `resume`.
+//! - `status`: `{paused, held_frames}`.
+//! - `save_state {slot}`, `load_state {slot}`: the numbered savestate slots.
+//! - `press {buttons, frames}`: hold buttons (`["a", "start"]`) on top of
+//!   the keyboard for `frames` emulated frames. The response is sent once
+//!   the frames have run (or a later `press` replaces them), so a client can
+//!   wait on it before looking; other requests are still served meanwhile.
+//! - `read_memory {address, length?}`: bytes from the bus, as numbers.
+//! - `screenshot`: the screen as presented, `{width, height, png}` with the
+//!   PNG in base64.
+//!
+//! Requests are served from the frame loop between frames, never blocking it.
+//!
+//! This socket can load any file and overwrite save slots, so a TCP one only
+//! takes connections from loopback addresses, and each must open with
+//! `auth` carrying the token printed at startup, fresh every run. A browser
+//! can still make a page POST to a localhost port, so a connection whose
+//! first bytes look like HTTP is dropped unanswered. A Unix socket is guarded
+//! by its file permissions and needs no token.
+
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::TcpListener;
+
+use rustyboi_frontend_lib::App;
+use rustyboi_session::input::GbButton;
+use rustyboi_session::ButtonState;
+use serde_json::{Value, json};
+
+use crate::websocket::base64;
+
+/// JSON-RPC 2.0 error codes.
+pub(crate) const PARSE_ERROR: i64 = -32700;
+pub(crate) const INVALID_REQUEST: i64 = -32600;
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+pub(crate) const INVALID_PARAMS: i64 = -32602;
+/// The method ran and failed (no ROM, missing file, empty slot, ...).
+pub(crate) const FAILED: i64 = -32000;
+
+/// A client's unanswered request line is cut off at this length.
+const MAX_LINE: usize = 64 * 1024;
+
+/// Either kind of connected client stream. `Send`, as the server is served
+/// from the emulation thread.
+trait Stream: Read + Write + Send {}
+impl<T: Read + Write + Send> Stream for T {}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixListener, std::path::PathBuf),
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Listener::Unix(_, path) = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+struct Client {
+    stream: Box<dyn Stream>,
+    /// Bytes received short of a whole line.
+    inbox: Vec<u8>,
+    /// Response bytes the socket hasn't taken yet.
+    outbox: Vec<u8>,
+    /// A `press` response held until its frames have run.
+    after_hold: Option<Value>,
+    /// Whether requests are served yet: a TCP client must `auth` first.
+    authorized: bool,
+    gone: bool,
+}
+
+impl Client {
+    fn new(stream: Box<dyn Stream>, authorized: bool) -> Self {
+        Client { stream, inbox: Vec::new(), outbox: Vec::new(), after_hold: None, authorized, gone: false }
+    }
+
+    fn receive(&mut self) {
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    self.gone = true;
+                    return;
+                }
+                Ok(n) => self.inbox.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => {
+                    self.gone = true;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn reply(&mut self, response: &Value) {
+        self.outbox.extend_from_slice(response.to_string().as_bytes());
+        self.outbox.push(b'\n');
+    }
+
+    fn flush(&mut self) {
+        while !self.gone && !self.outbox.is_empty() {
+            match self.stream.write(&self.outbox) {
+                Ok(0) => self.gone = true,
+                Ok(n) => {
+                    self.outbox.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.gone = true,
+            }
+        }
+    }
+}
+
+pub(crate) struct ControlServer {
+    listener: Listener,
+    /// What a TCP client's `auth` must carry; `None` for a Unix socket.
+    token: Option<String>,
+    clients: Vec<Client>,
+}
+
+/// A fresh 128-bit token in hex. `RandomState` is seeded from the OS on each
+/// construction, which is unguessable enough for a per-run secret.
+fn new_token() -> String {
+    let half = || std::collections::hash_map::RandomState::new().build_hasher().finish();
+    format!("{:016x}{:016x}", half(), half())
+}
+
+/// Whether what a client sent is really an HTTP request line or headers,
+/// i.e. a browser talking to the port.
+fn looks_like_http(text: &str) -> bool {
+    const METHODS: [&str; 7] = ["GET ", "POST ", "PUT ", "HEAD ", "OPTIONS ", "DELETE ", "PATCH "];
+    text.lines().any(|line| {
+        let line = line.trim_start();
+        METHODS.iter().any(|m| line.starts_with(m))
+            || line.contains(" HTTP/1.")
+            || line.get(..7).is_some_and(|head| head.eq_ignore_ascii_case("origin:"))
+    })
+}
+
+impl ControlServer {
+    /// Listen on `addr`: `unix:PATH` for a Unix-domain socket (replacing a
+    /// stale socket file), anything else as a TCP `HOST:PORT` that wants a
+    /// [`token`](Self::token).
+    pub(crate) fn bind(addr: &str) -> io::Result<Self> {
+        let listener = match addr.strip_prefix("unix:") {
+            #[cfg(unix)]
+            Some(path) => {
+                let _ = std::fs::remove_file(path);
+                let listener = std::os::unix::net::UnixListener::bind(path)?;
+                listener.set_nonblocking(true)?;
+                Listener::Unix(listener, path.into())
+            }
+            #[cfg(not(unix))]
+            Some(_) => return Err(io::Error::new(ErrorKind::Unsupported, "Unix sockets need a Unix host")),
+            None => {
+                let listener = TcpListener::bind(addr)?;
+                listener.set_nonblocking(true)?;
+                Listener::Tcp(listener)
+            }
+        };
+        let token = matches!(listener, Listener::Tcp(_)).then(new_token);
+        Ok(ControlServer { listener, token, clients: Vec::new() })
+    }
+
+    /// The token TCP clients must `auth` with, `None` on a Unix socket.
+    pub(crate) fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    fn accept(&mut self) {
+        loop {
+            let stream: Box<dyn Stream> = match &self.listener {
+                Listener::Tcp(l) => match l.accept() {
+                    Ok((_, peer)) if !peer.ip().is_loopback() => continue,
+                    Ok((s, _)) if s.set_nonblocking(true).is_ok() => {
+                        let _ = s.set_nodelay(true);
+                        Box::new(s)
+                    }
+                    Ok(_) => continue,
+                    Err(_) => return,
+                },
+                #[cfg(unix)]
+                Listener::Unix(l, _) => match l.accept() {
+                    Ok((s, _)) if s.set_nonblocking(true).is_ok() => Box::new(s),
+                    Ok(_) => continue,
+                    Err(_) => return,
+                },
+            };
+            self.clients.push(Client::new(stream, self.token.is_none()));
+        }
+    }
+
+    /// Accept clients and serve every complete request. Called once per tick
+    /// of the frame loop.
+    pub(crate) fn poll(&mut self, app: &mut App) {
+        self.accept();
+        for client in &mut self.clients {
+            client.receive();
+            if !client.authorized && looks_like_http(&String::from_utf8_lossy(&client.inbox)) {
+                client.gone = true;
+                continue;
+            }
+            while !client.gone
+                && let Some(end) = client.inbox.iter().position(|&b| b == b'\n')
+            {
+                let line: Vec<u8> = client.inbox.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if !client.authorized {
+                    match authenticate(&line, self.token.as_deref()) {
+                        Ok(response) => {
+                            client.authorized = true;
+                            client.reply(&response);
+                        }
+                        Err(response) => {
+                            client.reply(&response);
+                            client.flush();
+                            client.gone = true;
+                        }
+                    }
+                    continue;
+                }
+                match serve(app, &line) {
+                    Served::Now(response) => client.reply(&response),
+                    Served::AfterHold(response) => {
+                        // A new press replaces the old hold, which is over.
+                        if let Some(earlier) = client.after_hold.replace(response) {
+                            client.reply(&earlier);
+                        }
+                    }
+                }
+            }
+            if client.after_hold.is_some() && app.session().held_frames_left() == 0 {
+                let response = client.after_hold.take().unwrap_or_default();
+                client.reply(&response);
+            }
+            if client.inbox.len() > MAX_LINE {
+                client.reply(&error(Value::Null, INVALID_REQUEST, "request line too long"));
+                client.inbox.clear();
+            }
+            client.flush();
+        }
+        self.clients.retain(|c| !c.gone);
+    }
+}
+
+/// Check a client's first request, which must be `auth` with `token`: the
+/// response to send, as `Err` when the client is to be dropped after it.
+fn authenticate(line: &str, token: Option<&str>) -> Result<Value, Value> {
+    let request = parse_request(line)?;
+    if request.method != "auth" {
+        return Err(error(request.id, INVALID_REQUEST, "authenticate with `auth` first"));
+    }
+    match request.params.get("token").and_then(Value::as_str) {
+        Some(given) if Some(given) == token => Ok(response(request.id, Ok(Value::Null))),
+        _ => Err(error(request.id, INVALID_PARAMS, "wrong token")),
+    }
+}
+
+enum Served {
+    Now(Value),
+    /// Send once the held buttons are released.
+    AfterHold(Value),
+}
+
+pub(crate) fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// A parsed request: its id, method and params.
+pub(crate) struct Request {
+    pub id: Value,
+    pub method: String,
+    pub params: Value,
+}
+
+/// Parse one request, or the error response to send instead.
+pub(crate) fn parse_request(text: &str) -> Result<Request, Value> {
+    let request: Value = serde_json::from_str(text).map_err(|e| error(Value::Null, PARSE_ERROR, &e.to_string()))?;
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return Err(error(id, INVALID_REQUEST, "missing method"));
+    };
+    let method = method.to_owned();
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    Ok(Request { id, method, params })
+}
+
+/// The response to a request that ran to `result`.
+pub(crate) fn response(id: Value, result: CallResult) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error(id, code, &message),
+    }
+}
+
+/// Answer one request line.
+fn serve(app: &mut App, line: &str) -> Served {
+    let request = match parse_request(line) {
+        Ok(request) => request,
+        Err(response) => return Served::Now(response),
+    };
+    let result = call(app, &request.method, &request.params);
+    let deferred = request.method == "press" && result.is_ok();
+    let response = response(request.id, result);
+    if deferred { Served::AfterHold(response) } else { Served::Now(response) }
+}
+
+pub(crate) type CallResult = Result<Value, (i64, String)>;
+
+pub(crate) fn failed(e: impl ToString) -> (i64, String) {
+    (FAILED, e.to_string())
+}
+
+pub(crate) fn param_u64(params: &Value, name: &str) -> Result<u64, (i64, String)> {
+    params.get(name).and_then(Value::as_u64).ok_or_else(|| (INVALID_PARAMS, format!("`{name}` must be a number")))
+}
+
+/// A numbered savestate slot: anything that fits a `u32` short of the
+/// quick and auto slots reserved at the top.
+fn param_slot(params: &Value) -> Result<u32, (i64, String)> {
+    u32::try_from(param_u64(params, "slot")?)
+        .ok()
+        .filter(|slot| !matches!(*slot, rustyboi_session::QUICK_SLOT | rustyboi_session::AUTO_SLOT))
+        .ok_or_else(|| (INVALID_PARAMS, "`slot` is out of range".to_owned()))
+}
+
+/// `read_memory {address, length?}`: bytes from the bus, as a list.
+pub(crate) fn read_memory(app: &App, params: &Value) -> CallResult {
+    let address = param_u64(params, "address")?;
+    let length = params.get("length").map_or(Ok(1), |_| param_u64(params, "length"))?;
+    if address.saturating_add(length) > 0x1_0000 {
+        return Err((INVALID_PARAMS, "range runs past $FFFF".to_owned()));
+    }
+    let gb = app.session().gb();
+    let bytes: Vec<u8> = (address..address + length).map(|a| gb.read_memory(a as u16)).collect();
+    Ok(json!(bytes))
+}
+
+fn call(app: &mut App, method: &str, params: &Value) -> CallResult {
+    match method {
+        "load_rom" => {
+            let path = params
+                .get("path")
+                .and_then(Value::as_str)
+                .ok_or_else(|| (INVALID_PARAMS, "`path` must be a string".to_owned()))?;
+            let bytes = std::fs::read(path).map_err(|e| failed(format!("{path}: {e}")))?;
+            let patch = rustyboi_core_lib::patch::read_sidecar_patch(std::path::Path::new(path))
+                .ok()
+                .flatten()
+                .map(|(_, patch)| patch);
+            app.load_rom_bytes(bytes, Some(path.to_owned()), patch).map_err(failed)?;
+            Ok(Value::Null)
+        }
+        "pause" | "resume" => {
+            app.set_paused(method == "pause");
+            Ok(Value::Null)
+        }
+        "status" => Ok(json!({ "paused": app.is_effectively_paused(), "held_frames": app.session().held_frames_left() })),
+        // Already let in (or on a Unix socket, which needs no token).
+        "auth" => Ok(Value::Null),
+        "save_state" => {
+            let slot = param_slot(params)?;
+            app.save_slot(slot, crate::display::now_epoch_secs()).map_err(failed)?;
+            Ok(Value::Null)
+        }
+        "load_state" => {
+            let slot = param_slot(params)?;
+            app.load_slot(slot).map_err(failed)?;
+            Ok(Value::Null)
+        }
+        "press" => {
+            let frames = param_u64(params, "frames")?.min(u64::from(u32::MAX)) as u32;
+            let buttons = parse_buttons(params.get("buttons"))?;
+            if app.is_effectively_paused() && frames > 0 {
+                return Err(failed("emulation is paused; resume it first"));
+            }
+            app.session_mut().hold_buttons(buttons, frames);
+            Ok(json!({ "frames": frames }))
+        }
+        "read_memory" => read_memory(app, params),
+        "screenshot" => {
+            let png = app.session_mut().screenshot_png();
+            let (width, height) = png_size(&png);
+            Ok(json!({ "width": width, "height": height, "png": base64(&png) }))
+        }
+        _ => Err((METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+    }
+}
+
+/// Button names, any case: `a`, `b`, `start`, `select`, `up`, `down`,
+/// `left`, `right`.
+fn parse_buttons(names: Option<&Value>) -> Result<ButtonState, (i64, String)> {
+    let names = names
+        .and_then(Value::as_array)
+        .ok_or_else(|| (INVALID_PARAMS, "`buttons` must be a list of names".to_owned()))?;
+    let mut state = ButtonState::default();
+    for name in names {
+        let name = name.as_str().unwrap_or_default();
+        let button = GbButton::ALL
+            .into_iter()
+            .find(|b| format!("{b:?}").eq_ignore_ascii_case(name))
+            .ok_or_else(|| (INVALID_PARAMS, format!("unknown button `{name}`")))?;
+        button.set(&mut state, true);
+    }
+    Ok(state)
+}
+
+/// Width and height from a PNG's IHDR.
+fn png_size(png: &[u8]) -> (u32, u32) {
+    let at = |i: usize| png.get(i..i + 4).map_or(0, |b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    (at(16), at(20))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyboi_session::action::{DmgPaletteChoice, SgbPaletteChoice};
+    use rustyboi_session::config::Config;
+    use rustyboi_session::ports::{MemRumble, MemStorage, MemWebcam};
+    use rustyboi_session::{Ports, Session};
+
+    fn app() -> App {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        let mut session = Session::new(Config::default(), ports, [0u8; 32]);
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x101] = 0xC3; // JP 0x0100
+        rom[0x103] = 0x01;
+        session.finish_load_rom(&rom).unwrap();
+        App::new(session, DmgPaletteChoice::Green, SgbPaletteChoice::Auto, None, None, false)
+    }
+
+    fn now(served: Served) -> Value {
+        match served {
+            Served::Now(v) => v,
+            Served::AfterHold(_) => panic!("response deferred"),
+        }
+    }
+
+    #[test]
+    fn requests_get_results_and_errors_by_the_spec() {
+        let mut app = app();
+        let r = now(serve(&mut app, r#"{"jsonrpc":"2.0","id":7,"method":"read_memory","params":{"address":257,"length":3}}"#));
+        assert_eq!(r["id"], 7);
+        assert_eq!(r["result"], json!([0xC3, 0x00, 0x01]));
+
+        let r = now(serve(&mut app, r#"{"jsonrpc":"2.0","id":1,"method":"pause"}"#));
+        assert!(r["result"].is_null());
+        assert!(app.is_paused());
+        now(serve(&mut app, r#"{"jsonrpc":"2.0","id":2,"method":"resume"}"#));
+        assert!(!app.is_paused());
+
+        assert_eq!(now(serve(&mut app, "{nope"))["error"]["code"], PARSE_ERROR);
+        assert_eq!(now(serve(&mut app, r#"{"id":3,"method":"fly"}"#))["error"]["code"], METHOD_NOT_FOUND);
+        let r = now(serve(&mut app, r#"{"id":4,"method":"press","params":{"buttons":["jump"],"frames":1}}"#));
+        assert_eq!(r["error"]["code"], INVALID_PARAMS);
+        let r = now(serve(&mut app, r#"{"id":5,"method":"load_state","params":{"slot":3}}"#));
+        assert_eq!(r["error"]["code"], FAILED);
+    }
+
+    #[test]
+    fn a_press_answers_after_its_frames_and_screenshots_are_png() {
+        let mut app = app();
+        let served = serve(&mut app, r#"{"id":1,"method":"press","params":{"buttons":["A","start"],"frames":3}}"#);
+        assert!(matches!(served, Served::AfterHold(ref v) if v["result"]["frames"] == 3));
+        assert_eq!(app.session().held_frames_left(), 3);
+
+        let r = now(serve(&mut app, r#"{"id":2,"method":"screenshot"}"#));
+        assert_eq!((r["result"]["width"].as_u64(), r["result"]["height"].as_u64()), (Some(160), Some(144)));
+        assert!(r["result"]["png"].as_str().unwrap().starts_with("iVBORw0KGgo"));
+    }
+
+    #[test]
+    fn slots_must_fit_and_avoid_the_reserved_ones() {
+        let mut app = app();
+        for slot in ["4294967296", "4294967295", "4294967294"] {
+            let r = now(serve(&mut app, &format!(r#"{{"id":1,"method":"save_state","params":{{"slot":{slot}}}}}"#)));
+            assert_eq!(r["error"]["code"], INVALID_PARAMS, "{slot}");
+        }
+        let r = now(serve(&mut app, r#"{"id":2,"method":"save_state","params":{"slot":3}}"#));
+        assert!(r["result"].is_null());
+    }
+
+    /// A TCP server with one client connected, for driving through `poll`.
+    fn connect(app: &mut App) -> (ControlServer, std::net::TcpStream) {
+        let mut server = ControlServer::bind("127.0.0.1:0").unwrap();
+        let Listener::Tcp(listener) = &server.listener else { unreachable!() };
+        let client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        client.set_read_timeout(Some(std::time::Duration::from_millis(20))).unwrap();
+        server.poll(app);
+        (server, client)
+    }
+
+    /// Poll until `count` response lines arrive (or the server hangs up).
+    fn read_lines(server: &mut ControlServer, app: &mut App, client: &mut std::net::TcpStream, count: usize) -> Vec<Value> {
+        let mut got = Vec::new();
+        let mut buf = [0u8; 4096];
+        for _ in 0..100 {
+            server.poll(app);
+            match client.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => got.extend_from_slice(&buf[..n]),
+                Err(_) => {}
+            }
+            if got.iter().filter(|&&b| b == b'\n').count() >= count {
+                break;
+            }
+        }
+        String::from_utf8_lossy(&got).lines().map(|l| serde_json::from_str(l).unwrap()).collect()
+    }
+
+    #[test]
+    fn tcp_clients_need_the_token_and_browsers_are_dropped() {
+        let mut app = app();
+        let (mut server, mut client) = connect(&mut app);
+        client.write_all(b"{\"id\":1,\"method\":\"status\"}\n").unwrap();
+        let r = read_lines(&mut server, &mut app, &mut client, 1);
+        assert_eq!(r[0]["error"]["code"], INVALID_REQUEST);
+        assert!(server.clients.is_empty(), "dropped after a request without auth");
+
+        let (mut server, mut client) = connect(&mut app);
+        client.write_all(b"POST / HTTP/1.1\r\nOrigin: https://evil.example\r\n\r\n").unwrap();
+        assert!(read_lines(&mut server, &mut app, &mut client, 1).is_empty());
+        assert!(server.clients.is_empty());
+
+        let (mut server, mut client) = connect(&mut app);
+        let token = server.token().unwrap().to_owned();
+        assert_eq!(token.len(), 32);
+        writeln!(client, r#"{{"id":1,"method":"auth","params":{{"token":"{token}"}}}}"#).unwrap();
+        client.write_all(b"{\"id\":2,\"method\":\"status\"}\n").unwrap();
+        let r = read_lines(&mut server, &mut app, &mut client, 2);
+        assert!(r[0]["result"].is_null());
+        assert_eq!(r[1]["result"]["paused"], false);
+    }
+
+    #[test]
+    fn a_pause_during_a_press_is_still_served() {
+        let mut app = app();
+        let (mut server, mut client) = connect(&mut app);
+        let token = server.token().unwrap().to_owned();
+        writeln!(client, r#"{{"id":1,"method":"auth","params":{{"token":"{token}"}}}}"#).unwrap();
+        client.write_all(b"{\"id\":2,\"method\":\"press\",\"params\":{\"buttons\":[\"a\"],\"frames\":2}}\n").unwrap();
+        client.write_all(b"{\"id\":3,\"method\":\"pause\"}\n{\"id\":4,\"method\":\"status\"}\n").unwrap();
+        let r = read_lines(&mut server, &mut app, &mut client, 3);
+        let ids: Vec<_> = r.iter().map(|v| v["id"].clone()).collect();
+        assert_eq!(ids, [json!(1), json!(3), json!(4)], "the press is still pending");
+        assert_eq!(r[2]["result"], json!({ "paused": true, "held_frames": 2 }));
+
+        client.write_all(b"{\"id\":5,\"method\":\"resume\"}\n").unwrap();
+        assert_eq!(read_lines(&mut server, &mut app, &mut client, 1)[0]["id"], 5);
+        app.run_frame();
+        app.run_frame();
+        let r = read_lines(&mut server, &mut app, &mut client, 1);
+        assert_eq!(r[0]["id"], 2);
+        assert_eq!(r[0]["result"]["frames"], 2);
+    }
+}
@@ -0,0 +1,463 @@
+//! The remote debugger (`--debug-server PORT`): the same read-model and
+//! controls as the egui debug windows, served over WebSocket on localhost so
+//! an editor extension or web UI can attach.
+//!
+//! The protocol is JSON-RPC 2.0 in text messages, the same shape as the
+//! control socket. Methods (params by name):
+//! - `snapshot {memory?, vram?, oam?, palettes?, stack?, cartridge?, apu?}`:
+//!   the full debug snapshot, with the heavy sections asked for.
+//! - `registers`: the CPU registers, with the cycle and instruction counts.
+//! - `read_memory {address, length?}`, `write_memory {address, bytes}`.
+//! - `breakpoints`, `set_breakpoint {address}`, `remove_breakpoint {address}`,
+//!   `clear_breakpoints`.
+//! - `pause`, `continue`.
+//! - `step {count?}` (instructions), `step_frame {count?}`: only while
+//!   paused; the response carries the registers after the step.
+//!
+//! The server also notifies every client with `stopped {reason, pc}` (reason
+//! `breakpoint` or `pause`) when emulation halts and `continued` when it runs.
+//!
+//! Binding to localhost keeps other machines out, but not other web pages:
+//! a browser lets any site open a WebSocket to `localhost`, and this one can
+//! write memory. So the handshake is refused (403) unless its `Origin` is
+//! missing (a native client) or is itself on localhost.
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+use rustyboi_frontend_lib::App;
+use rustyboi_session::{DebugDetail, UiAction};
+use serde_json::{Value, json};
+
+use crate::control::{self, CallResult, INVALID_PARAMS, METHOD_NOT_FOUND, failed, param_u64};
+use crate::websocket::{self, upgrade_response, websocket_key};
+
+/// Longest HTTP request head read before the connection is dropped.
+const MAX_REQUEST: usize = 8 * 1024;
+/// Longest message a client may send.
+const MAX_MESSAGE: usize = 64 * 1024;
+
+/// Whether a handshake may proceed: no `Origin` (not a browser), or one whose
+/// host is localhost, whatever its scheme and port.
+fn origin_is_local(head: &str) -> bool {
+    let Some(origin) = websocket::header(head, "origin") else { return true };
+    let authority = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    let authority = authority.split('/').next().unwrap_or_default();
+    let host = match authority.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or_default(),
+        None => authority.rsplit_once(':').map_or(authority, |(host, _)| host),
+    };
+    host.eq_ignore_ascii_case("localhost") || host.parse::<std::net::IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+struct Client {
+    stream: TcpStream,
+    /// The HTTP request read so far; `None` once upgraded to a WebSocket.
+    request: Option<Vec<u8>>,
+    /// WebSocket bytes short of a whole frame.
+    inbox: Vec<u8>,
+    /// A fragmented text message being reassembled.
+    message: Vec<u8>,
+    /// Bytes the socket hasn't taken yet.
+    outbox: Vec<u8>,
+    /// The id of a step request, answered on the next poll (the step runs in
+    /// the frame loop in between); no further requests are served until then.
+    after_step: Option<Value>,
+    gone: bool,
+}
+
+impl Client {
+    fn receive(&mut self) {
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    self.gone = true;
+                    return;
+                }
+                Ok(n) => match self.request.as_mut() {
+                    Some(request) => request.extend_from_slice(&buf[..n]),
+                    None => self.inbox.extend_from_slice(&buf[..n]),
+                },
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => {
+                    self.gone = true;
+                    return;
+                }
+            }
+        }
+        if self.request.as_ref().is_some_and(|r| r.len() > MAX_REQUEST) || self.inbox.len() > MAX_MESSAGE * 2 {
+            self.gone = true;
+        }
+    }
+
+    /// Answer a complete request head: upgrade a WebSocket request, refuse
+    /// anything else.
+    fn upgrade(&mut self) {
+        let Some(request) = self.request.as_ref() else { return };
+        let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else { return };
+        let head = String::from_utf8_lossy(&request[..end]).into_owned();
+        let rest = request[end + 4..].to_vec();
+        self.request = None;
+        if !origin_is_local(&head) {
+            self.outbox = b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec();
+            self.flush();
+            self.gone = true;
+            return;
+        }
+        match websocket_key(&head) {
+            Some(key) => {
+                self.outbox = upgrade_response(key);
+                self.inbox = rest;
+                if let Ok(addr) = self.stream.peer_addr() {
+                    println!("Debugger attached from {addr}");
+                }
+            }
+            None => {
+                self.outbox = b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec();
+                self.flush();
+                self.gone = true;
+            }
+        }
+    }
+
+    /// The next complete text message, answering control frames on the way.
+    fn next_message(&mut self) -> Option<String> {
+        while let Some(frame) = websocket::take_frame(&mut self.inbox) {
+            match frame.opcode {
+                websocket::TEXT | websocket::BINARY | websocket::CONTINUATION => {
+                    self.message.extend_from_slice(&frame.payload);
+                    if self.message.len() > MAX_MESSAGE {
+                        self.gone = true;
+                        return None;
+                    }
+                    if frame.fin {
+                        let message = std::mem::take(&mut self.message);
+                        return Some(String::from_utf8_lossy(&message).into_owned());
+                    }
+                }
+                websocket::PING => self.outbox.extend(websocket::frame(websocket::PONG, &frame.payload)),
+                websocket::CLOSE => {
+                    self.outbox.extend(websocket::frame(websocket::CLOSE, &[]));
+                    self.flush();
+                    self.gone = true;
+                    return None;
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn send(&mut self, message: &Value) {
+        self.outbox.extend(websocket::frame(websocket::TEXT, message.to_string().as_bytes()));
+    }
+
+    fn flush(&mut self) {
+        while !self.outbox.is_empty() {
+            match self.stream.write(&self.outbox) {
+                Ok(0) => {
+                    self.gone = true;
+                    return;
+                }
+                Ok(n) => {
+                    self.outbox.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => {
+                    self.gone = true;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+pub(crate) struct DebugServer {
+    listener: TcpListener,
+    clients: Vec<Client>,
+    /// Whether emulation was halted at the last poll, to notice it stop.
+    was_halted: Option<bool>,
+}
+
+impl DebugServer {
+    /// Listen for debuggers on `port`, on localhost only: a client can
+    /// rewrite memory.
+    pub(crate) fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(DebugServer { listener, clients: Vec::new(), was_halted: None })
+    }
+
+    fn accept(&mut self) {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    if stream.set_nonblocking(true).is_err() {
+                        continue;
+                    }
+                    let _ = stream.set_nodelay(true);
+                    self.clients.push(Client {
+                        stream,
+                        request: Some(Vec::new()),
+                        inbox: Vec::new(),
+                        message: Vec::new(),
+                        outbox: Vec::new(),
+                        after_step: None,
+                        gone: false,
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// Accept debuggers, serve their requests and tell them when emulation
+    /// stops or runs. Called once per tick of the frame loop.
+    pub(crate) fn poll(&mut self, app: &mut App) {
+        self.accept();
+        let halted = app.is_effectively_paused();
+        let event = match (self.was_halted, halted) {
+            (Some(false), true) => {
+                let pc = app.gb().get_cpu_registers().pc;
+                let reason = if app.gb().get_breakpoints().contains(&pc) { "breakpoint" } else { "pause" };
+                Some(json!({ "jsonrpc": "2.0", "method": "stopped", "params": { "reason": reason, "pc": pc } }))
+            }
+            (Some(true), false) => Some(json!({ "jsonrpc": "2.0", "method": "continued" })),
+            _ => None,
+        };
+        self.was_halted = Some(halted);
+
+        for client in &mut self.clients {
+            client.receive();
+            if client.request.is_some() {
+                client.upgrade();
+            }
+            if client.request.is_none() && !client.gone {
+                if let Some(id) = client.after_step.take() {
+                    client.send(&control::response(id, Ok(registers(app))));
+                }
+                if let Some(event) = &event {
+                    client.send(event);
+                }
+                while client.after_step.is_none()
+                    && let Some(message) = client.next_message()
+                {
+                    let request = match control::parse_request(&message) {
+                        Ok(request) => request,
+                        Err(response) => {
+                            client.send(&response);
+                            continue;
+                        }
+                    };
+                    let result = call(app, &request.method, &request.params);
+                    if result.is_ok() && request.method.starts_with("step") {
+                        client.after_step = Some(request.id);
+                    } else {
+                        client.send(&control::response(request.id, result));
+                    }
+                }
+            }
+            client.flush();
+        }
+        self.clients.retain(|c| !c.gone);
+    }
+}
+
+fn registers(app: &App) -> Value {
+    json!(app.session().debug_snapshot(DebugDetail::default()).cpu)
+}
+
+fn param_address(params: &Value) -> Result<u16, (i64, String)> {
+    u16::try_from(param_u64(params, "address")?).map_err(|_| (INVALID_PARAMS, "`address` is past $FFFF".to_owned()))
+}
+
+/// A step count: `count`, default 1.
+fn param_count(params: &Value) -> Result<u32, (i64, String)> {
+    match params.get("count") {
+        None => Ok(1),
+        Some(_) => Ok(param_u64(params, "count")?.clamp(1, u64::from(u32::MAX)) as u32),
+    }
+}
+
+fn call(app: &mut App, method: &str, params: &Value) -> CallResult {
+    match method {
+        "snapshot" => {
+            let mut detail = json!(DebugDetail::default());
+            if let (Some(detail), Some(asked)) = (detail.as_object_mut(), params.as_object()) {
+                for (section, wanted) in asked {
+                    if let Some(flag) = detail.get_mut(section) {
+                        *flag = wanted.clone();
+                    }
+                }
+            }
+            let detail: DebugDetail =
+                serde_json::from_value(detail).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            Ok(json!(app.session().debug_snapshot(detail)))
+        }
+        "registers" => Ok(registers(app)),
+        "read_memory" => control::read_memory(app, params),
+        "write_memory" => {
+            let address = param_address(params)?;
+            let bytes: Vec<u8> = params
+                .get("bytes")
+                .and_then(|b| serde_json::from_value(b.clone()).ok())
+                .ok_or_else(|| (INVALID_PARAMS, "`bytes` must be a list of bytes".to_owned()))?;
+            if usize::from(address) + bytes.len() > 0x1_0000 {
+                return Err((INVALID_PARAMS, "range runs past $FFFF".to_owned()));
+            }
+            for (i, byte) in bytes.into_iter().enumerate() {
+                app.gb_mut().write_memory(address + i as u16, byte);
+            }
+            Ok(Value::Null)
+        }
+        "breakpoints" => {
+            let mut breakpoints: Vec<u16> = app.gb().get_breakpoints().iter().copied().collect();
+            breakpoints.sort_unstable();
+            Ok(json!(breakpoints))
+        }
+        "set_breakpoint" => {
+            app.session_mut().apply(UiAction::SetBreakpoint(param_address(params)?), 0);
+            Ok(Value::Null)
+        }
+        "remove_breakpoint" => {
+            app.session_mut().apply(UiAction::RemoveBreakpoint(param_address(params)?), 0);
+            Ok(Value::Null)
+        }
+        "clear_breakpoints" => {
+            app.session_mut().apply(UiAction::ClearBreakpoints, 0);
+            Ok(Value::Null)
+        }
+        "pause" | "continue" => {
+            app.set_paused(method == "pause");
+            Ok(Value::Null)
+        }
+        "step" | "step_frame" => {
+            if !app.is_effectively_paused() {
+                return Err(failed("emulation is running; pause it first"));
+            }
+            let count = param_count(params)?;
+            let action = if method == "step" { UiAction::StepCycles(count) } else { UiAction::StepFrames(count) };
+            app.session_mut().apply(action, 0);
+            Ok(Value::Null)
+        }
+        _ => Err((METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyboi_session::action::{DmgPaletteChoice, SgbPaletteChoice};
+    use rustyboi_session::config::Config;
+    use rustyboi_session::ports::{MemRumble, MemStorage, MemWebcam};
+    use rustyboi_session::{Ports, Session};
+
+    fn app() -> App {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        let mut session = Session::new(Config::default(), ports, [0u8; 32]);
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x101] = 0xC3; // JP 0x0100
+        rom[0x103] = 0x01;
+        session.finish_load_rom(&rom).unwrap();
+        App::new(session, DmgPaletteChoice::Green, SgbPaletteChoice::Auto, None, None, false)
+    }
+
+    #[test]
+    fn breakpoints_memory_and_snapshots() {
+        let mut app = app();
+        call(&mut app, "set_breakpoint", &json!({ "address": 0x150 })).unwrap();
+        call(&mut app, "set_breakpoint", &json!({ "address": 0x101 })).unwrap();
+        assert_eq!(call(&mut app, "breakpoints", &Value::Null).unwrap(), json!([0x101, 0x150]));
+        call(&mut app, "remove_breakpoint", &json!({ "address": 0x150 })).unwrap();
+        assert_eq!(call(&mut app, "breakpoints", &Value::Null).unwrap(), json!([0x101]));
+
+        call(&mut app, "write_memory", &json!({ "address": 0xC000, "bytes": [1, 2, 3] })).unwrap();
+        let read = call(&mut app, "read_memory", &json!({ "address": 0xC000, "length": 3 })).unwrap();
+        assert_eq!(read, json!([1, 2, 3]));
+        assert!(call(&mut app, "write_memory", &json!({ "address": 0xFFFF, "bytes": [1, 2] })).is_err());
+
+        let snapshot = call(&mut app, "snapshot", &json!({ "oam": true })).unwrap();
+        assert_eq!(snapshot["oam"].as_array().map(Vec::len), Some(0xA0));
+        assert!(snapshot["memory"].is_null());
+        assert_eq!(snapshot["breakpoints"], json!([0x101]));
+    }
+
+    #[test]
+    fn steps_only_while_paused() {
+        let mut app = app();
+        assert_eq!(call(&mut app, "step", &Value::Null).unwrap_err().0, control::FAILED);
+        call(&mut app, "pause", &Value::Null).unwrap();
+        call(&mut app, "step", &json!({ "count": 2 })).unwrap();
+        assert_eq!(app.session_mut().take_step_cycles(), Some(2));
+        assert_eq!(call(&mut app, "fly", &Value::Null).unwrap_err().0, METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn only_local_or_native_origins_may_attach() {
+        let head = |origin: &str| format!("GET / HTTP/1.1\r\nOrigin: {origin}\r\nSec-WebSocket-Key: k");
+        assert!(origin_is_local("GET / HTTP/1.1\r\nSec-WebSocket-Key: k"), "no Origin: not a browser");
+        for local in ["http://localhost:5173", "http://127.0.0.1", "https://[::1]:8080", "vscode-webview://LOCALHOST"] {
+            assert!(origin_is_local(&head(local)), "{local}");
+        }
+        for remote in ["https://evil.example", "http://localhost.evil.example", "null", "http://10.0.0.1:80"] {
+            assert!(!origin_is_local(&head(remote)), "{remote}");
+        }
+
+        let mut app = app();
+        let mut server = DebugServer::bind(0).unwrap();
+        let mut client = TcpStream::connect(server.listener.local_addr().unwrap()).unwrap();
+        client
+            .write_all(b"GET / HTTP/1.1\r\nOrigin: https://evil.example\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n")
+            .unwrap();
+        client.set_read_timeout(Some(std::time::Duration::from_millis(50))).unwrap();
+        let mut got = Vec::new();
+        let mut buf = [0u8; 256];
+        while got.is_empty() {
+            server.poll(&mut app);
+            if let Ok(n) = client.read(&mut buf) {
+                got.extend_from_slice(&buf[..n]);
+            }
+        }
+        assert!(String::from_utf8_lossy(&got).starts_with("HTTP/1.1 403"));
+    }
+
+    #[test]
+    fn a_websocket_client_is_told_when_emulation_stops() {
+        let mut app = app();
+        let mut server = DebugServer::bind(0).unwrap();
+        let addr = server.listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.write_all(b"GET / HTTP/1.1\r\nUpgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n").unwrap();
+        // A masked `registers` request (mask 0).
+        let request = br#"{"jsonrpc":"2.0","id":1,"method":"registers"}"#;
+        let mut frame = vec![0x81, 0x80 | request.len() as u8, 0, 0, 0, 0];
+        frame.extend_from_slice(request);
+        client.write_all(&frame).unwrap();
+
+        client.set_read_timeout(Some(std::time::Duration::from_millis(50))).unwrap();
+        let mut got = Vec::new();
+        let mut buf = [0u8; 1024];
+        let mut paused = false;
+        while !String::from_utf8_lossy(&got).contains("\"stopped\"") {
+            server.poll(&mut app);
+            if !paused && String::from_utf8_lossy(&got).contains("\"pc\"") {
+                app.set_paused(true);
+                paused = true;
+            }
+            if let Ok(n) = client.read(&mut buf) {
+                got.extend_from_slice(&buf[..n]);
+            }
+        }
+        let text = String::from_utf8_lossy(&got);
+        assert!(text.starts_with("HTTP/1.1 101"));
+        assert!(text.contains(r#""reason":"pause""#));
+    }
+}
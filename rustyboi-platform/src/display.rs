@@ -1,19 +1,24 @@
 //! The thin platform event loop. Creates the winit window + wgpu surface/device,
-//! pumps winit events into abstract input + UI events, and drives the portable
-//! `rustyboi_frontend::App` (which owns all UI/render/logic). Audio, file
-//! dialogs, worker threads, and the Android JNI entry stay here; everything
-//! window-agnostic lives in the frontend.
+//! pumps winit events into abstract input + UI events, and draws the portable
+//! `rustyboi_frontend::App` (which owns all UI/render/logic) from the snapshots
+//! its emulation thread sends (see `emu_thread`, which also owns audio and the
+//! worker threads). File dialogs and the Android JNI entry stay here;
+//! everything window-agnostic lives in the frontend.
 
 use crate::config;
 use crate::error::PlatformError;
 use rustyboi_core_lib::gb;
 use rustyboi_frontend_lib::actions::{FileData, GuiAction};
-use rustyboi_frontend_lib::{App, PlatformRequest, Renderer, ResolvedAction, UiHost};
-use rustyboi_session::input_config::{FiredHotkey, HeldInputs, HotkeyAction, KeyName};
+use rustyboi_frontend_lib::{App, Picture, PlatformRequest, Renderer, ResolvedAction, UiHost, UiPrep};
+use rustyboi_session::input_config::{HeldInputs, KeyName};
+#[cfg(not(target_os = "android"))]
+use rustyboi_session::{input_config::StickConfig, ConnectedPad};
 // Desktop (gilrs) + Android (native key events) both map physical pads to this.
 use rustyboi_session::input_config::PadButton;
 use rustyboi_session::Session;
 
+use crate::emu_thread::{Command, Drawn, EmuThread, Inputs, Services, View};
+
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 #[cfg(not(target_os = "android"))]
@@ -25,7 +30,7 @@ use winit::keyboard::KeyCode;
 use winit::window::{Window, WindowId};
 // Fullscreen is only toggled on desktop (the Android window is already fullscreen).
 #[cfg(not(target_os = "android"))]
-use winit::window::Fullscreen;
+use crate::fullscreen::FullscreenState;
 use winit_input_helper::WinitInputHelper;
 
 // Used by the desktop window sizing; the Android entry sizes to the
@@ -41,6 +46,10 @@ const HEIGHT: u32 = 144;
 struct RenderState {
     renderer: Box<dyn rustyboi_frontend_lib::Present>,
     ui: UiHost,
+    /// `(vsync, unsynced)` present modes: the one chosen below, and the
+    /// tearing one fast-forward switches to with vsync off. `None` on the
+    /// software backend or a surface that offers no other mode.
+    present_modes: Option<(wgpu::PresentMode, wgpu::PresentMode)>,
 }
 
 /// Create the wgpu surface + device + queue from `window`, then build the
@@ -107,7 +116,7 @@ fn create_render_state(
         // Shadows are per-frame gradient fills — meaningful CPU work on this
         // backend only, so the flat look is software-exclusive.
         ui.disable_window_shadows();
-        Ok(RenderState { renderer: Box::new(renderer), ui })
+        Ok(RenderState { renderer: Box::new(renderer), ui, present_modes: None })
     };
     #[cfg(not(target_os = "android"))]
     if backend == GraphicsBackend::Software {
@@ -182,6 +191,11 @@ fn create_render_state(
         wgpu::PresentMode::Mailbox
     };
     log::info!("surface present modes: {:?}; using {:?}", caps.present_modes, present_mode);
+    // Fast-forward with vsync off: Immediate tears but never waits; Mailbox
+    // at least doesn't block where Fifo is the default.
+    let unsynced = [wgpu::PresentMode::Immediate, wgpu::PresentMode::Mailbox]
+        .into_iter()
+        .find(|mode| *mode != present_mode && caps.present_modes.contains(mode));
     let surface_format = caps
         .formats
         .iter()
@@ -193,7 +207,7 @@ fn create_render_state(
     let renderer = Renderer::new(surface, device, queue, surface_format, width, height, present_mode);
     let ui = UiHost::new(&window, scale_factor, max_texture_size, pending_dialog_result);
 
-    Ok(RenderState { renderer: Box::new(renderer), ui })
+    Ok(RenderState { renderer: Box::new(renderer), ui, present_modes: unsynced.map(|mode| (present_mode, mode)) })
 }
 
 #[cfg(not(target_os = "android"))]
@@ -235,7 +249,7 @@ pub fn run_with_gui_android(
 }
 
 /// Base directory the session ports read/write savestates + config under.
-fn save_base() -> std::path::PathBuf {
+pub(crate) fn save_base() -> std::path::PathBuf {
     #[cfg(target_os = "android")]
     {
         crate::android::save_dir()
@@ -251,7 +265,7 @@ fn save_base() -> std::path::PathBuf {
 }
 
 /// Current epoch seconds, for savestate-slot timestamps.
-fn now_epoch_secs() -> u64 {
+pub(crate) fn now_epoch_secs() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_secs())
@@ -366,15 +380,36 @@ pub(crate) fn safe_insets_from_rect(
     (left, top, right, bottom)
 }
 
-/// Fold all connected gamepads' held buttons into the abstract [`PadButton`] set:
-/// standard face/shoulder/trigger buttons + D-pad via the hat OR the left stick.
-/// Draining `next_event` refreshes the cached state `is_pressed`/`value` read.
+/// Collect each connected gamepad's held buttons as abstract [`PadButton`]s,
+/// keyed by its GUID so per-controller bindings apply: standard
+/// face/shoulder/trigger buttons + D-pad via the hat OR the left stick.
+/// Draining `next_event` refreshes the cached state `is_pressed`/`value` read;
+/// controllers plugged in or pulled out along the way are announced through
+/// `notices` (a pad is picked up or dropped by `gamepads()` without any other
+/// wiring). Returns the connected list, for the keybind editor.
 #[cfg(not(target_os = "android"))]
-fn collect_gamepad_held(gilrs: &mut gilrs::Gilrs, pad: &mut std::collections::HashSet<PadButton>) {
-    use gilrs::{Axis, Button};
-    while gilrs.next_event().is_some() {}
+fn collect_gamepad_held(
+    gilrs: &mut gilrs::Gilrs,
+    held: &mut HeldInputs,
+    stick: StickConfig,
+    notices: &mut Vec<String>,
+) -> Vec<ConnectedPad> {
+    use gilrs::{Axis, Button, EventType};
+    while let Some(ev) = gilrs.next_event() {
+        match ev.event {
+            EventType::Connected => notices.push(format!("Controller connected: {}", gilrs.gamepad(ev.id).name())),
+            EventType::Disconnected => {
+                notices.push(format!("Controller disconnected: {}", gilrs.gamepad(ev.id).name()))
+            }
+            _ => {}
+        }
+    }
     const DZ: f32 = 0.5;
+    let mut connected = Vec::new();
     for (_id, gp) in gilrs.gamepads() {
+        let guid: String = gp.uuid().iter().map(|b| format!("{b:02x}")).collect();
+        connected.push(ConnectedPad { guid: guid.clone(), name: gp.name().to_owned() });
+        let mut pad = std::collections::HashSet::new();
         let mut hold = |cond: bool, b: PadButton| {
             if cond {
                 pad.insert(b);
@@ -396,75 +431,18 @@ fn collect_gamepad_held(gilrs: &mut gilrs::Gilrs, pad: &mut std::collections::Ha
         hold(gp.is_pressed(Button::DPadRight), PadButton::DpadRight);
         // Analog sticks as discrete directions past a deadzone (gilrs: +Y up,
         // +X right). Bound alongside the d-pad by default, but separately
-        // mappable — so the sticks and d-pad are interchangeable.
-        hold(gp.value(Axis::LeftStickY) > DZ, PadButton::LStickUp);
-        hold(gp.value(Axis::LeftStickY) < -DZ, PadButton::LStickDown);
-        hold(gp.value(Axis::LeftStickX) < -DZ, PadButton::LStickLeft);
-        hold(gp.value(Axis::LeftStickX) > DZ, PadButton::LStickRight);
+        // mappable — so the sticks and d-pad are interchangeable. The left
+        // stick's deadzone and diagonals follow the input settings.
+        for b in stick.directions(gp.value(Axis::LeftStickX), gp.value(Axis::LeftStickY)) {
+            hold(true, b);
+        }
         hold(gp.value(Axis::RightStickY) > DZ, PadButton::RStickUp);
         hold(gp.value(Axis::RightStickY) < -DZ, PadButton::RStickDown);
         hold(gp.value(Axis::RightStickX) < -DZ, PadButton::RStickLeft);
         hold(gp.value(Axis::RightStickX) > DZ, PadButton::RStickRight);
+        held.pads.push((guid, pad));
     }
-}
-
-/// Perform a fired hotkey on the desktop app. Returns `true` if the event loop
-/// should exit (Exit action). Turbo is handled inside the resolver (it drives
-/// the button state), so no dispatch is needed here for it. Rewind is a hold
-/// action (fires every active frame); the rest, including FastForward, are
-/// edge-triggered toggles that fire on the rising edge.
-#[cfg_attr(target_os = "android", allow(unused_variables))]
-fn dispatch_hotkey(
-    app: &mut App,
-    fired: FiredHotkey,
-    window: &Window,
-    event_loop: &ActiveEventLoop,
-    is_fullscreen: &mut bool,
-) -> bool {
-    match fired.action {
-        HotkeyAction::FastForward if fired.rising => {
-            app.toggle_fast_forward();
-            window.request_redraw();
-        }
-        HotkeyAction::Rewind => {
-            if app.rewind_enabled() {
-                app.rewind();
-                window.request_redraw();
-            }
-        }
-        HotkeyAction::Quicksave if fired.rising => {
-            match app.quicksave(now_epoch_secs()) {
-                Ok(()) => println!("Quicksaved"),
-                Err(e) => println!("Quicksave failed: {e}"),
-            }
-            window.request_redraw();
-        }
-        HotkeyAction::Quickload if fired.rising => match app.quickload() {
-            Ok(()) => window.request_redraw(),
-            Err(e) => println!("Quickload failed: {e}"),
-        },
-        HotkeyAction::FrameAdvance if fired.rising => {
-            app.frame_advance();
-            window.request_redraw();
-        }
-        HotkeyAction::TogglePause if fired.rising => {
-            app.toggle_pause();
-            window.request_redraw();
-        }
-        HotkeyAction::ToggleFullscreen if fired.rising => {
-            #[cfg(not(target_os = "android"))]
-            {
-                *is_fullscreen = !*is_fullscreen;
-                window.set_fullscreen(is_fullscreen.then(|| Fullscreen::Borderless(None)));
-            }
-        }
-        HotkeyAction::Exit if fired.rising => {
-            event_loop.exit();
-            return true;
-        }
-        _ => {}
-    }
-    false
+    connected
 }
 
 fn run_gui_loop(
@@ -472,17 +450,64 @@ fn run_gui_loop(
     gb: Box<gb::GB>,
     config: &config::CleanConfig,
 ) -> Result<(), PlatformError> {
+    #[cfg(feature = "profiler")]
+    rustyboi_frontend_lib::profiler::install();
     let input = WinitInputHelper::new();
 
-    let ports = crate::ports::build_ports(save_base());
+    let mut ports = crate::ports::build_ports(save_base());
+    if let Some(path) = &config.camera_image {
+        let still = std::fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| rustyboi_session::StillImage::from_bytes(&bytes));
+        match still {
+            Ok(still) => ports.webcam = Box::new(still),
+            Err(e) => eprintln!("--camera-image {path}: {e}; using the sensor test pattern"),
+        }
+    }
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    if let Some(device) = &config.camera_device {
+        match crate::webcam::FfmpegWebcam::open(device) {
+            Ok(webcam) => ports.webcam = Box::new(webcam),
+            Err(e) => eprintln!("--camera-device {device}: {e}; using the sensor test pattern"),
+        }
+    }
     let mut session_config = rustyboi_session::Config::load(ports.storage.as_ref());
     session_config.hardware = config.hardware;
+    if let Some(muted) = config.mute {
+        session_config.channel_mix.muted = muted;
+    }
+    if let Some(soloed) = config.solo {
+        session_config.channel_mix.soloed = soloed;
+    }
+    if let Some(dir) = &config.save_dir {
+        session_config.save_dir = Some(dir.clone());
+    }
 
-    // `mut` only used on native, where offloaded rewind is enabled below.
-    #[cfg_attr(target_os = "android", allow(unused_mut))]
     let mut session = {
-        session_from_gb(gb, config.rom.as_ref().and_then(|p| std::fs::read(p).ok()).as_deref(), session_config, ports)
+        // Identify the archive member actually inserted, not the default pick.
+        let rom = config.rom.as_ref().and_then(|p| std::fs::read(p).ok()).map(|bytes| {
+            match config.archive_entry.as_deref() {
+                Some(entry) => rustyboi_core_lib::cartridge::Cartridge::extract_archive_entry(&bytes, entry)
+                    .unwrap_or(bytes),
+                None => bytes,
+            }
+        });
+        session_from_gb(gb, rom.as_deref(), session_config, ports)
     };
+    session.set_strict_header(config.strict_header);
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    session.set_rtc_format_override(config.rtc_format);
+    session.set_serial_capture(config.serial_stdout);
+    if let Some(dat) = config.dat.as_deref() {
+        match std::fs::read_to_string(dat) {
+            Ok(text) if session.load_verification_dat(&text) > 0 => {}
+            Ok(_) => eprintln!("{dat}: no SHA-1 entries, ROMs will not be verified"),
+            Err(e) => eprintln!("{dat}: {e}"),
+        }
+    }
+    if let Some(warning) = session.header_warning() {
+        eprintln!("ROM header: {warning}");
+    }
 
     // The SGB's own power-on border, sourced from the user's firmware dump.
     #[cfg(not(target_arch = "wasm32"))]
@@ -501,10 +526,6 @@ fn run_gui_loop(
         session.set_rewind_offloaded(true);
         Some(crate::rewind_worker::RewindWorker::new())
     };
-    #[cfg(not(target_os = "android"))]
-    let png_worker: Option<crate::png_worker::PngWorker> = None;
-    #[cfg(not(target_os = "android"))]
-    let next_print_index: Option<(String, u32)> = None;
 
     // Native desktop: physical gamepad support (gilrs). `None` if no backend is
     // available; buttons are OR'd into the keyboard/touch input each frame.
@@ -520,11 +541,15 @@ fn run_gui_loop(
     // pays nothing.
     let mut fetch_worker: Option<crate::fetch_worker::FetchWorker> = None;
 
-    // Per-frame edge/phase state for the shared input resolver (hotkey rising
-    // edges + the turbo autofire square wave). Persists across frames.
-    let resolve_state = rustyboi_session::ResolveState::new();
+    // `--break` addresses, set before the first frame can run past them.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    for &address in &config.breakpoints {
+        session.gb_mut().add_breakpoint(address);
+    }
 
     let should_start_paused = !session.gb().has_rom() && !session.gb().has_bios();
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let should_start_paused = should_start_paused || config.paused;
 
     let mut app = App::new(
         session,
@@ -534,11 +559,74 @@ fn run_gui_loop(
         config.bios.clone(),
         should_start_paused,
     );
+    // A ROM opened from the command line goes on File → Recent too.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    if let Some(path) = config.rom.as_deref().filter(|_| app.gb().has_rom()) {
+        app.note_recent_rom(path);
+    }
 
     if config.printer {
         app.gb_mut().attach_printer();
         println!("Game Boy Printer attached to the link port");
     }
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    if let Some(transport) = crate::netplay::connect(config) {
+        app.session_mut().start_netplay(Box::new(transport));
+    }
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    if let Some(port) = config.spectate {
+        match crate::spectate::SpectatorServer::bind(port) {
+            Ok(server) => {
+                app.session_mut().start_spectating(Box::new(server), config.spectate_audio);
+                println!("Spectators can watch at http://localhost:{port}/");
+            }
+            Err(e) => eprintln!("Spectating on port {port} failed: {e}"),
+        }
+    }
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let control = config.control.as_deref().and_then(|addr| match crate::control::ControlServer::bind(addr) {
+        Ok(server) => {
+            match server.token() {
+                Some(token) => println!("Accepting control commands on {addr} (auth token {token})"),
+                None => println!("Accepting control commands on {addr}"),
+            }
+            Some(server)
+        }
+        Err(e) => {
+            eprintln!("Control socket on {addr} failed: {e}");
+            None
+        }
+    });
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let debug_server = config.debug_server.and_then(|port| match crate::debug_server::DebugServer::bind(port) {
+        Ok(server) => {
+            println!("Debugger can attach at ws://localhost:{port}/");
+            Some(server)
+        }
+        Err(e) => {
+            eprintln!("Debug server on port {port} failed: {e}");
+            None
+        }
+    });
+    // Record Audio streams into files picked when it starts; mobile keeps
+    // the session's in-memory capture and its SaveBytes export.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    app.session_mut().set_audio_sink_opener(Box::new(crate::audio_export::open));
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let video_writer = config.record_video.as_ref().and_then(|path| {
+        let rate = app.session().audio_sample_rate();
+        match crate::video_export::VideoWriter::start(path, rate) {
+            Ok((writer, sink)) => {
+                app.session_mut().start_video_recording_to(Box::new(sink));
+                println!("Recording video to {path} until exit");
+                Some(writer)
+            }
+            Err(e) => {
+                eprintln!("--record-video: {e}");
+                None
+            }
+        }
+    });
 
     // No-Intro game-name index: load cached DATs immediately, download any that
     // are missing. The data is CC-BY-SA-4.0 libretro-database material that is
@@ -556,19 +644,6 @@ fn run_gui_loop(
         }
     }
 
-    // Audio output device (cpal/rodio). The session returns samples from
-    // run_frame; we push them into this pure sink.
-    let audio = match crate::audio::Output::new().and_then(|mut o| {
-        o.start_device()?;
-        Ok(o)
-    }) {
-        Ok(o) => Some(o),
-        Err(e) => {
-            println!("Failed to initialize audio: {e}; continuing without audio");
-            None
-        }
-    };
-
     // Persist the pending-dialog-result Arc across UiHost suspend/resume cycles
     // (Android's SAF picker destroys the surface and drops the UiHost).
     let pending_dialog_result: std::sync::Arc<
@@ -588,6 +663,13 @@ fn run_gui_loop(
     // loop). `None` until the first fit.
     #[cfg(not(target_os = "android"))]
     let last_fit_logical: Option<(u32, u32)> = None;
+    // The window scale: `--scale` for this run, else the saved View → Window
+    // Scale. A new pick in the menu or on a hotkey replaces it and refits.
+    let view = View::of(&app);
+    #[cfg(not(target_os = "android"))]
+    let saved_window_scale = view.window_scale;
+    #[cfg(not(target_os = "android"))]
+    let window_scale = config.scale.unwrap_or(saved_window_scale);
     // Debounced aspect-snap state. During an interactive resize the window must
     // follow the cursor freely (requesting a size every `Resized` fights the
     // compositor — the rapid back-and-forth). So we only record the desired
@@ -600,48 +682,63 @@ fn run_gui_loop(
     let last_resize_at: Option<Instant> = None;
     #[cfg(not(target_os = "android"))]
     let resize_burst_start: Option<winit::dpi::PhysicalSize<u32>> = None;
+    // From here on the app, and everything that needs it every frame, belongs
+    // to the emulation thread (see `emu_thread`).
+    let emu_thread = EmuThread::spawn(
+        app,
+        Services {
+            #[cfg(not(target_os = "android"))]
+            rewind_worker,
+            fetch_worker,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            control,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            debug_server,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            video_writer,
+        },
+    );
     let mut gui = GuiApp {
         config,
         window: None,
         render_state,
         input,
-        app,
-        audio,
-        resolve_state,
+        emu_thread,
+        view,
+        prep: None,
+        picture: None,
+        requests: Vec::new(),
+        content_inset: (0.0, 0.0),
         pending_dialog_result,
         f_key_press_time: None,
         n_key_press_time: None,
         f_last_repeat_time: None,
         n_last_repeat_time: None,
         #[cfg(not(target_os = "android"))]
-        rewind_worker,
-        #[cfg(not(target_os = "android"))]
-        png_worker,
-        #[cfg(not(target_os = "android"))]
-        next_print_index,
-        #[cfg(not(target_os = "android"))]
         gilrs,
-        fetch_worker,
         #[cfg(target_os = "android")]
         android_pad,
         #[cfg(not(target_os = "android"))]
-        is_fullscreen: false,
+        fullscreen: FullscreenState::default(),
         #[cfg(not(target_os = "android"))]
         last_content_size,
         #[cfg(not(target_os = "android"))]
         last_fit_logical,
         #[cfg(not(target_os = "android"))]
+        window_scale,
+        #[cfg(not(target_os = "android"))]
+        saved_window_scale,
+        #[cfg(not(target_os = "android"))]
         pending_snap,
         #[cfg(not(target_os = "android"))]
         last_resize_at,
         #[cfg(not(target_os = "android"))]
         resize_burst_start,
-        regulator: rustyboi_session::pacing::Regulator::new(),
-        stretcher: rustyboi_session::pacing::Stretcher::new(),
-        pacing_epoch: Instant::now(),
         last_tick: Instant::now(),
         tick_interval_ema: 1.0 / 60.0,
+        display_period: FRAME_PERIOD,
         occluded: false,
+        keep_awake: crate::keep_awake::KeepAwake::default(),
     };
     event_loop.run_app(&mut gui).map_err(PlatformError::from_display)
 }
@@ -653,53 +750,76 @@ const REPEAT_INTERVAL: Duration = Duration::from_millis(67);
 #[cfg(not(target_os = "android"))]
 const SNAP_DEBOUNCE: Duration = Duration::from_millis(140);
 
+/// One emulated frame period at exactly
+/// [`NOMINAL_FPS`](rustyboi_session::pacing::NOMINAL_FPS) (70224 dots at
+/// 4.194304 MHz, ~16.7427 ms), the tick period when the display's refresh is
+/// unknown or nothing is presented. Game speed never depends on it (the
+/// emulation thread paces itself), but an exact period keeps the occluded and
+/// no-vsync paths ticking once per emulated frame instead of beating against
+/// it.
+const FRAME_PERIOD: Duration = Duration::from_nanos((1e9 / rustyboi_session::pacing::NOMINAL_FPS) as u64);
+
+/// The throttled tick period on a display refreshing at `refresh_millihertz`:
+/// one tick per refresh, so a 120/144 Hz panel redraws the UI at its own rate
+/// (ticks between emulated frames re-present the last finished one) instead
+/// of juddering at 59.7 Hz. Never longer than an
+/// emulated frame, so slow or unknown displays tick as before.
+fn tick_period(refresh_millihertz: Option<u32>) -> Duration {
+    match refresh_millihertz {
+        Some(mhz) if mhz > 0 => (Duration::from_secs(1000) / mhz.min(500_000)).min(FRAME_PERIOD),
+        _ => FRAME_PERIOD,
+    }
+}
+
 /// The winit 0.30 `ApplicationHandler`. It owns every piece of state the old
 /// `event_loop.run` closure captured. The window + GPU `RenderState` are created
-/// lazily in `resumed` and dropped in `suspended`; the emulation `App` persists.
+/// lazily in `resumed` and dropped in `suspended`; the emulation `App` persists
+/// on its own thread.
 struct GuiApp<'c> {
     #[cfg_attr(target_os = "android", allow(unused))]
     config: &'c config::CleanConfig,
     window: Option<Arc<Window>>,
     render_state: Option<RenderState>,
     input: WinitInputHelper,
-    app: App,
-    audio: Option<crate::audio::Output>,
-    resolve_state: rustyboi_session::ResolveState,
+    /// The emulation thread, which owns the `App` (see `emu_thread`).
+    emu_thread: EmuThread,
+    /// What the window needs to know about the app, and the newest UI
+    /// snapshot, frame and requests the emulation thread sent.
+    view: View,
+    prep: Option<UiPrep>,
+    picture: Option<Picture>,
+    requests: Vec<PlatformRequest>,
+    /// The chrome inset (menu bar + status panel) the last render measured.
+    content_inset: (f32, f32),
     pending_dialog_result: Arc<std::sync::Mutex<Option<GuiAction>>>,
     f_key_press_time: Option<Instant>,
     n_key_press_time: Option<Instant>,
     f_last_repeat_time: Option<Instant>,
     n_last_repeat_time: Option<Instant>,
     #[cfg(not(target_os = "android"))]
-    rewind_worker: Option<crate::rewind_worker::RewindWorker>,
-    #[cfg(not(target_os = "android"))]
-    png_worker: Option<crate::png_worker::PngWorker>,
-    #[cfg(not(target_os = "android"))]
-    next_print_index: Option<(String, u32)>,
-    #[cfg(not(target_os = "android"))]
     gilrs: Option<gilrs::Gilrs>,
-    fetch_worker: Option<crate::fetch_worker::FetchWorker>,
     #[cfg(target_os = "android")]
     android_pad: std::collections::HashSet<PadButton>,
+    /// Borderless fullscreen, the windowed size to return to, and the idle
+    /// cursor auto-hide.
     #[cfg(not(target_os = "android"))]
-    is_fullscreen: bool,
+    fullscreen: FullscreenState,
     #[cfg(not(target_os = "android"))]
     last_content_size: (u32, u32),
     #[cfg(not(target_os = "android"))]
     last_fit_logical: Option<(u32, u32)>,
+    /// The multiple of the content size the window is fitted to, and the
+    /// saved setting last seen (a change to it means the user picked a scale).
+    #[cfg(not(target_os = "android"))]
+    window_scale: u8,
+    #[cfg(not(target_os = "android"))]
+    saved_window_scale: u8,
     #[cfg(not(target_os = "android"))]
     pending_snap: Option<winit::dpi::PhysicalSize<u32>>,
     #[cfg(not(target_os = "android"))]
     last_resize_at: Option<Instant>,
     #[cfg(not(target_os = "android"))]
     resize_burst_start: Option<winit::dpi::PhysicalSize<u32>>,
-    /// The shared frame-pacing regulator (see `rustyboi_session::pacing`) and
-    /// the epoch its `now` timestamps are measured from.
-    regulator: rustyboi_session::pacing::Regulator,
-    /// Micro-resampler bridging the audio to the device clock (the game rate
-    /// is pure wall clock; the DAC has zero authority over the timeline).
-    stretcher: rustyboi_session::pacing::Stretcher,
-    pacing_epoch: Instant,
     /// When the previous tick's throttle completed — the reference the tick
     /// throttle at the end of `frame_tick` paces against.
     last_tick: Instant,
@@ -708,11 +828,16 @@ struct GuiApp<'c> {
     /// free-run — if ticks average implausibly fast, the throttle engages
     /// anyway (CPU bound only; the regulator already guarantees game speed).
     tick_interval_ema: f64,
+    /// The throttle's tick period: one refresh of the window's monitor (see
+    /// `tick_period`), re-read when the window moves or changes scale.
+    display_period: Duration,
     /// The window reported itself occluded (`WindowEvent::Occluded(true)`):
     /// skip rendering entirely — never touch the swapchain while hidden (the
     /// Wayland Fifo hidden-block hazard) — while emulation + audio keep
-    /// running on the throttle clock.
+    /// running on their own thread.
     occluded: bool,
+    /// Keeps the screensaver and sleep away while the game runs unpaused.
+    keep_awake: crate::keep_awake::KeepAwake,
 }
 
 impl ApplicationHandler for GuiApp<'_> {
@@ -722,8 +847,8 @@ impl ApplicationHandler for GuiApp<'_> {
             #[cfg(not(mobile))]
             let attrs = {
                 let size = LogicalSize::new(
-                    (WIDTH * (self.config.scale as u32)) as f64,
-                    (HEIGHT * (self.config.scale as u32)) as f64,
+                    (WIDTH * (self.window_scale as u32)) as f64,
+                    (HEIGHT * (self.window_scale as u32)) as f64,
                 );
                 Window::default_attributes()
                     .with_title("RustyBoi")
@@ -734,7 +859,10 @@ impl ApplicationHandler for GuiApp<'_> {
             #[cfg(mobile)]
             let attrs = Window::default_attributes().with_title("RustyBoi");
             match event_loop.create_window(attrs) {
-                Ok(w) => self.window = Some(Arc::new(w)),
+                Ok(w) => {
+                    self.window = Some(Arc::new(w));
+                    self.refresh_display_period();
+                }
                 Err(e) => {
                     println!("Failed to create window on Resumed: {e}");
                     event_loop.exit();
@@ -749,7 +877,7 @@ impl ApplicationHandler for GuiApp<'_> {
             let backend = self
                 .config
                 .graphics
-                .unwrap_or_else(|| self.app.session().graphics_backend());
+                .unwrap_or(self.view.graphics_backend);
             match create_render_state(window.clone(), Some(self.pending_dialog_result.clone()), backend) {
                 Ok(rs) => {
                     self.render_state = Some(rs);
@@ -778,11 +906,7 @@ impl ApplicationHandler for GuiApp<'_> {
     }
 
     fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
-        // On mobile, backgrounding is the last reliable chance to flush SRAM
-        // before the OS may reclaim the app. Persists through the storage port
-        // (no-op for non-battery carts). Desktop keeps its own sidecar `.sav`.
-        #[cfg(mobile)]
-        self.app.session_mut().persist_battery();
+        self.emu_thread.send(Command::Suspend);
         self.render_state = None;
     }
 
@@ -821,11 +945,21 @@ impl ApplicationHandler for GuiApp<'_> {
             WindowEvent::Resized(size) => self.handle_resize(size),
             // Minimized/hidden: stop rendering (the swapchain must not be
             // touched while hidden — Wayland Fifo can block indefinitely) but
-            // keep the tick loop, emulation, and audio running via the
-            // throttle clock. `frame_tick` checks the flag.
+            // keep the tick loop feeding input to the emulation thread.
+            // `frame_tick` checks the flag.
             WindowEvent::Occluded(occluded) => self.occluded = occluded,
             WindowEvent::RedrawRequested => self.frame_tick(event_loop),
             other => {
+                #[cfg(not(target_os = "android"))]
+                if let (WindowEvent::CursorMoved { .. }, Some(window)) = (&other, self.window.as_ref()) {
+                    self.fullscreen.cursor_moved(window);
+                }
+                if matches!(other, WindowEvent::Moved(_) | WindowEvent::ScaleFactorChanged { .. }) {
+                    self.refresh_display_period();
+                }
+                if let WindowEvent::Focused(focused) = other {
+                    self.emu_thread.send(Command::Focused(focused));
+                }
                 if let (Some(rs), Some(window)) =
                     (self.render_state.as_mut(), self.window.as_ref())
                 {
@@ -854,31 +988,37 @@ impl ApplicationHandler for GuiApp<'_> {
     // worker (`wl_proxy_destroy` on primary-selection objects whose connection
     // was already gone). Dropping it now joins the worker cleanly against a live
     // connection. Order matters: the wgpu surface (in `render_state`) borrows the
-    // window, so it must drop before the window; audio + the background workers
-    // are stopped deterministically too.
+    // window, so it must drop before the window; the emulation thread, with
+    // audio + the background workers, is stopped deterministically too.
     fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        // Every exit path (menu, hotkey, window close) lands here. The
+        // emulation thread snapshots the machine for the next launch's resume
+        // offer and finishes any recording before it returns.
+        self.emu_thread.stop();
         self.render_state = None;
-        self.audio = None;
-        // The background-worker fields are target-gated (see the struct), so the
-        // drops must carry the matching cfgs or non-desktop builds fail to
-        // compile with "no field" errors.
-        #[cfg(not(target_os = "android"))]
-        {
-            self.rewind_worker = None;
-            self.png_worker = None;
-        }
-        {
-            self.fetch_worker = None;
-        }
         self.window = None;
     }
 }
 
 impl GuiApp<'_> {
-    /// One presented frame (runs on each `RedrawRequested`): resolve input,
-    /// advance emulation + audio, then draw egui + the game. Merges the old
-    /// input-update block and the RedrawRequested render arm.
+    /// Re-read the refresh rate of the monitor the window is on.
+    fn refresh_display_period(&mut self) {
+        let refresh = self
+            .window
+            .as_ref()
+            .and_then(|w| w.current_monitor())
+            .and_then(|m| m.refresh_rate_millihertz());
+        self.display_period = tick_period(refresh);
+    }
+
+    /// One presented frame (runs on each `RedrawRequested`): hand input to the
+    /// emulation thread, take in what it finished, then lay out egui and draw
+    /// it over the newest game frame. Merges the old input-update block and the
+    /// RedrawRequested render arm.
     fn frame_tick(&mut self, event_loop: &ActiveEventLoop) {
+        // One profiler frame per tick: the UI layout and its render.
+        #[cfg(feature = "profiler")]
+        rustyboi_frontend_lib::profiler::new_frame();
         let Some(window) = self.window.clone() else { return };
 
         if self.input.key_pressed(KeyCode::Escape) || self.input.close_requested() {
@@ -886,22 +1026,19 @@ impl GuiApp<'_> {
             return;
         }
 
-        // F: frame stepping with debounce (paused/errored only).
+        // F: frame stepping with debounce (honoured while paused/errored).
         if self.input.key_pressed(KeyCode::KeyF) {
-            if self.app.stepping_allowed() {
-                self.app.request_step_frame();
-                let now = Instant::now();
-                self.f_key_press_time = Some(now);
-                self.f_last_repeat_time = Some(now);
-            }
+            self.emu_thread.send(Command::StepFrame);
+            let now = Instant::now();
+            self.f_key_press_time = Some(now);
+            self.f_last_repeat_time = Some(now);
         } else if self.input.key_held(KeyCode::KeyF) {
-            if self.app.stepping_allowed()
-                && let Some(press_time) = self.f_key_press_time
+            if let Some(press_time) = self.f_key_press_time
                 && press_time.elapsed() >= DEBOUNCE_DURATION
                 && let Some(last_repeat) = self.f_last_repeat_time
                 && last_repeat.elapsed() >= REPEAT_INTERVAL
             {
-                self.app.request_step_frame();
+                self.emu_thread.send(Command::StepFrame);
                 self.f_last_repeat_time = Some(Instant::now());
             }
         } else {
@@ -909,22 +1046,19 @@ impl GuiApp<'_> {
             self.f_last_repeat_time = None;
         }
 
-        // N: cycle stepping with debounce (paused/errored only).
+        // N: cycle stepping with debounce (honoured while paused/errored).
         if self.input.key_pressed(KeyCode::KeyN) {
-            if self.app.stepping_allowed() {
-                self.app.request_step_cycle();
-                let now = Instant::now();
-                self.n_key_press_time = Some(now);
-                self.n_last_repeat_time = Some(now);
-            }
+            self.emu_thread.send(Command::StepCycle);
+            let now = Instant::now();
+            self.n_key_press_time = Some(now);
+            self.n_last_repeat_time = Some(now);
         } else if self.input.key_held(KeyCode::KeyN) {
-            if self.app.stepping_allowed()
-                && let Some(press_time) = self.n_key_press_time
+            if let Some(press_time) = self.n_key_press_time
                 && press_time.elapsed() >= DEBOUNCE_DURATION
                 && let Some(last_repeat) = self.n_last_repeat_time
                 && last_repeat.elapsed() >= REPEAT_INTERVAL
             {
-                self.app.request_step_cycle();
+                self.emu_thread.send(Command::StepCycle);
                 self.n_last_repeat_time = Some(Instant::now());
             }
         } else {
@@ -938,18 +1072,18 @@ impl GuiApp<'_> {
             rs.ui.set_pixels_per_point(scale_factor as f32);
         }
 
-        // Build the raw held-input set (keyboard + gamepad) and resolve it through
-        // the shared config: GB-button bindings drive the button state, chord
-        // hotkeys drive features. Then OR the egui touch overlay on top and
-        // dispatch any fired hotkeys.
-        #[cfg_attr(target_os = "android", allow(unused_mut))]
-        let mut held = held_inputs_from_keyboard(&self.input);
+        // Build the raw held-input set (keyboard + gamepad) plus the egui touch
+        // overlay; the emulation thread resolves it through the shared config
+        // and dispatches any fired hotkeys.
+        let mut inputs = Inputs { held: held_inputs_from_keyboard(&self.input), ..Inputs::default() };
         #[cfg(not(target_os = "android"))]
         if let Some(g) = self.gilrs.as_mut() {
-            collect_gamepad_held(g, &mut held.pad);
+            let pads = collect_gamepad_held(g, &mut inputs.held, self.view.left_stick, &mut inputs.notices);
+            inputs.pads = Some(pads);
         }
         #[cfg(target_os = "android")]
         {
+            let held = &mut inputs.held;
             held.pad.extend(self.android_pad.iter().copied());
             // Analog sticks + hat arrive via Java (onGenericMotionEvent → JNI).
             // Android axes: +X right, +Y down. Hat covers controllers that report
@@ -961,10 +1095,9 @@ impl GuiApp<'_> {
                     held.pad.insert(b);
                 }
             };
-            on(ly < -dz, PadButton::LStickUp);
-            on(ly > dz, PadButton::LStickDown);
-            on(lx < -dz, PadButton::LStickLeft);
-            on(lx > dz, PadButton::LStickRight);
+            for b in self.view.left_stick.directions(lx, -ly) {
+                on(true, b);
+            }
             on(ry < -dz, PadButton::RStickUp);
             on(ry > dz, PadButton::RStickDown);
             on(rx < -dz, PadButton::RStickLeft);
@@ -977,138 +1110,133 @@ impl GuiApp<'_> {
             on(lt > dz, PadButton::LeftTrigger);
             on(rt > dz, PadButton::RightTrigger);
         }
-        let (mut button_state, fired) =
-            self.app.session().config().input.resolve(&held, &mut self.resolve_state);
         if let Some(rs) = self.render_state.as_ref() {
-            let touch = rs.ui.touch_button_state();
-            button_state.a |= touch.a;
-            button_state.b |= touch.b;
-            button_state.start |= touch.start;
-            button_state.select |= touch.select;
-            button_state.up |= touch.up;
-            button_state.down |= touch.down;
-            button_state.left |= touch.left;
-            button_state.right |= touch.right;
+            inputs.touch = rs.ui.touch_button_state();
         }
-        self.app.set_button_state(button_state);
-        // Forward the held pad set so the keybind editor can capture gamepad
-        // presses (egui never sees pad input).
-        self.app.set_held_pad(held.pad.clone());
+        self.emu_thread.send(Command::Input(inputs));
 
-        for f in fired {
-            #[cfg(not(target_os = "android"))]
-            let exit = dispatch_hotkey(&mut self.app, f, &window, event_loop, &mut self.is_fullscreen);
-            #[cfg(target_os = "android")]
-            let exit = {
-                let mut dummy = false;
-                dispatch_hotkey(&mut self.app, f, &window, event_loop, &mut dummy)
-            };
-            if exit {
-                return;
+        // Take in what the emulation thread finished since the last tick. A
+        // tick with nothing new re-presents the last frame under a fresh UI.
+        if let Some(update) = self.emu_thread.poll() {
+            if let Some(title) = update.title {
+                window.set_title(&title);
             }
+            if let Some(picture) = update.picture
+                && let Some(stale) = self.picture.replace(picture)
+            {
+                self.emu_thread.send(Command::Recycle(stale.into_buffer()));
+            }
+            self.requests.extend(update.requests);
+            self.prep = Some(update.prep);
+            self.view = update.view;
+        }
+        self.serve_requests(&window, event_loop);
+        if event_loop.exiting() {
+            return;
         }
 
-        // Android: keep the game region inside the safe area (system bars /
-        // display cutout) so it isn't clipped behind them. No-op elsewhere.
-        #[cfg(target_os = "android")]
-        if let Some(rs) = self.render_state.as_ref() {
-            let (w, h) = rs.renderer.surface_size();
-            let (l, t, r, b) = crate::android::safe_area_insets(w, h);
-            self.app.set_safe_insets(l, t, r, b);
+        // Only a running game holds the display awake: an empty slot (or the
+        // boot ROM on its own) lets the host sleep as usual.
+        self.keep_awake.set(self.view.running);
+        // Settings → Vsync Off While Fast-Forwarding: present without waiting
+        // for vblank and skip the tick throttle below, so the UI keeps up with
+        // the fast-forwarded frames.
+        let unsynced = self.view.unsynced;
+        if let Some(rs) = self.render_state.as_mut()
+            && let Some((vsync, tearing)) = rs.present_modes
+        {
+            rs.renderer.set_present_mode(if unsynced { tearing } else { vsync });
         }
 
-        // Emulate exactly as many frames as the shared regulator grants this
-        // tick: a wall-clock token bucket with a bounded DAC trim (see
-        // `rustyboi_session::pacing`). The app never sleeps and never reads a
-        // pacing clock; game speed is therefore exact 59.7275 regardless of
-        // tick cadence, host timer quality, or audio-pipeline misbehavior —
-        // the same regulator paces every platform, including the web worker.
-        //
-        // When paused, the regulator grants 0 but `run_frame` must still be
-        // called once: it services the debug-step requests and frame-advance
-        // mode (all of which early-return cheaply when idle).
-        let now = self.pacing_epoch.elapsed().as_secs_f64();
-        let paused = self.app.is_effectively_paused();
-        // Retune to the running machine: an SGB1 is clocked by the host SNES
-        // (÷5) and genuinely runs ~61.17 fps, not 59.73. Idempotent, so it can
-        // ride the tick and pick up a hardware/region change immediately.
-        self.regulator.set_cpu_hz(self.app.session().cpu_hz());
-        let granted = self.regulator.frames_to_run(
-            now,
-            self.audio.as_ref().map(|a| a.queued_pairs()),
-            self.app.is_fast_forward(),
-            paused,
-        );
-        let runs = if paused { 1 } else { granted };
-        let mut emulated = 0u32;
-        let mut pump = false;
-        for _ in 0..runs {
-            let step = self.app.run_frame();
-            pump |= step.pump_workers;
-            emulated += u32::from(step.advanced);
-            if let Some(a) = self.audio.as_mut() {
-                // Bridge to the device clock: micro-resample by the
-                // regulator's stretch ratio (1.0 on healthy hosts).
-                a.push_samples(self.stretcher.process(&step.audio, self.regulator.audio_stretch()));
-            }
-            // A breakpoint can pause mid-grant; don't burn the rest of the
-            // grant on no-op calls.
-            if self.app.is_effectively_paused() {
-                break;
+        // Lay out the UI from the newest snapshot and draw it over the newest
+        // frame. While occluded, never touch the swapchain.
+        let mut drawn = None;
+        if !self.occluded
+            && let (Some(rs), Some(prep)) = (self.render_state.as_mut(), self.prep.as_ref())
+        {
+            // Keep the render surface locked to the live window size *before*
+            // laying out egui (egui lays out using `window.inner_size()`; the
+            // `Resized` event is async, so the surface can lag after a
+            // programmatic resize). Syncing here (a cheap no-op when unchanged)
+            // keeps layout and target size in step.
+            let phys = window.inner_size();
+            let (pw, ph) = (phys.width.max(1), phys.height.max(1));
+            if (pw, ph) != rs.renderer.surface_size() {
+                rs.renderer.resize(pw, ph);
             }
-        }
-        if pump {
+            // Android IME: synthesize egui events winit drops.
+            let extra_events = collect_extra_egui_events();
+            // The menu-bar auto-hide flag is a desktop concern (Android is
+            // always fullscreen and uses the mobile menu; `fullscreen` only
+            // exists there).
             #[cfg(not(target_os = "android"))]
-            pump_workers(
-                &mut self.app,
-                self.rewind_worker.as_mut(),
-                &mut self.png_worker,
-                &mut self.next_print_index,
-            );
+            let fullscreen = self.fullscreen.is_on();
             #[cfg(target_os = "android")]
-            drain_printer_sheets_unsupported(&mut self.app);
-        }
-
-        // While occluded, never touch the swapchain (render skipped entirely);
-        // otherwise composite as usual.
-        if !self.occluded {
-            self.draw_frame(&window, event_loop);
+            let fullscreen = false;
+            let mut laid_out = App::lay_out_ui(&window, &mut rs.ui, prep, extra_events, fullscreen);
+            drawn = Some(Drawn {
+                action: laid_out.take_action(),
+                menu_open: laid_out.menu_open(),
+                debug: rs.ui.any_debug_panel_open().then(|| rs.ui.wanted_debug_detail()),
+            });
+            // Android: keep the game region inside the safe area (system bars /
+            // display cutout) so it isn't clipped behind them. No-op elsewhere.
+            #[cfg(target_os = "android")]
+            let safe_insets = {
+                let (w, h) = rs.renderer.surface_size();
+                let (l, t, r, b) = crate::android::safe_area_insets(w, h);
+                [l, t, r, b]
+            };
+            #[cfg(not(target_os = "android"))]
+            let safe_insets = [0.0; 4];
+            // A frame is uploaded once; later ticks re-present it.
+            let picture = self.picture.take();
+            let game = picture.as_ref().map(Picture::game_frame);
+            self.content_inset =
+                App::render_laid_out(rs.renderer.as_mut(), laid_out, prep.presentation(), game.as_ref(), safe_insets);
+            if let Some(picture) = picture {
+                self.emu_thread.send(Command::Recycle(picture.into_buffer()));
+            }
         }
+        // Hand the UI's action back, which also asks for the next update.
+        self.emu_thread.send(Command::Tick(drawn));
+        self.finish_frame(&window);
 
-        self.app.note_frames(now, emulated);
-
-        // Tick throttle: emulation itself never sleeps — the tick cadence must
+        // Tick throttle: the UI never waits on emulation, so its cadence must
         // come from somewhere. A blocking Fifo present (macOS/iOS Metal)
         // supplies it at true vsync; when the present can't pace us (Mailbox
         // never blocks — Linux/Android —, softbuffer blits return immediately,
         // the window is occluded) sleep out the remainder of the tick period
-        // here instead. Sleep overshoot (macOS coalescing) is harmless in this
-        // position: it delays only the next *tick*; the regulator banks the
-        // elapsed time and game speed is unaffected.
+        // here instead, once per display refresh so a high-refresh panel still
+        // gets a fresh present every refresh. Sleep overshoot (macOS
+        // coalescing) is harmless in this position: it delays only the next
+        // *tick*; the emulation thread keeps its own pace and game speed is
+        // unaffected. Fast-forward with vsync off skips it while visible.
         {
-            // One tick per emulated frame period. Sub-frame precision is
-            // pointless here (the regulator absorbs tick jitter), so the
-            // rounded period is fine.
-            const TICK_PERIOD: Duration = Duration::from_micros(16_743);
+            // Nothing is presented while occluded: one tick per emulated
+            // frame is all input needs.
+            let period = if self.occluded { FRAME_PERIOD } else { self.display_period };
             let tick_elapsed = self.last_tick.elapsed().as_secs_f64();
             self.tick_interval_ema += 0.125 * (tick_elapsed - self.tick_interval_ema);
             // VRR guard: a VRR display's Fifo present may never block, letting
             // ticks free-run while `vsync_paced()` still reads true. Ticks
-            // averaging < ~6ms are not a plausible fixed-refresh vsync — engage
-            // the throttle (bounds CPU/GPU; speed is already token-guaranteed).
-            let free_running = self.tick_interval_ema < 0.006;
+            // averaging well under the display's own period are not a
+            // plausible fixed-refresh vsync — engage the throttle (bounds
+            // CPU/GPU; speed is already token-guaranteed).
+            let free_running = self.tick_interval_ema < period.as_secs_f64() * 0.375;
             let vsync_paced = !self.occluded
                 && !free_running
                 && self
                     .render_state
                     .as_ref()
                     .is_some_and(|rs| rs.renderer.vsync_paced());
-            if !vsync_paced {
+            let unthrottled = unsynced && !self.occluded;
+            if !vsync_paced && !unthrottled {
                 // Windows defaults to 15.6ms timer granularity — request 1ms
                 // once, the first time the throttle actually engages (Fifo is
                 // preferred there, so many runs never need it at all).
-                // Sole unsafe in this crate: a WinMM FFI call with no memory
-                // arguments (takes a u32, returns a status we don't need).
+                // A WinMM FFI call with no memory arguments (takes a u32,
+                // returns a status we don't need).
                 #[cfg(target_os = "windows")]
                 #[allow(unsafe_code)]
                 {
@@ -1126,12 +1254,12 @@ impl GuiApp<'_> {
                 #[cfg(not(target_os = "windows"))]
                 const SLEEP_MARGIN: Duration = Duration::from_micros(50);
                 let elapsed = self.last_tick.elapsed();
-                if elapsed < TICK_PERIOD {
-                    let remaining = TICK_PERIOD - elapsed;
+                if elapsed < period {
+                    let remaining = period - elapsed;
                     if remaining > SLEEP_MARGIN * 2 {
                         std::thread::sleep(remaining - SLEEP_MARGIN);
                     }
-                    while self.last_tick.elapsed() < TICK_PERIOD {
+                    while self.last_tick.elapsed() < period {
                         std::hint::spin_loop();
                     }
                 }
@@ -1140,81 +1268,12 @@ impl GuiApp<'_> {
         }
     }
 
-    /// Composite egui + the game onto the surface and service the `App`'s
-    /// platform requests. (The old RedrawRequested arm.)
-    fn draw_frame(&mut self, window: &Arc<Window>, event_loop: &ActiveEventLoop) {
+    /// Service the platform requests the emulation thread passed on: the ones
+    /// only the window (or its dialogs and UI) can perform.
+    #[cfg_attr(target_os = "android", allow(unused_variables))]
+    fn serve_requests(&mut self, window: &Arc<Window>, event_loop: &ActiveEventLoop) {
         let Some(rs) = self.render_state.as_mut() else { return };
-
-        // Deliver any completed cheat-DB fetches into the session so the cheat
-        // picker shows them; report the outcome in the status bar.
-        if let Some(worker) = self.fetch_worker.as_mut() {
-            for done in worker.drain_finished() {
-                use rustyboi_session::FetchPurpose;
-                match (done.purpose, done.result) {
-                    (FetchPurpose::Cheats, Ok(body)) => {
-                        let n = self.app.session_mut().finish_fetched_cheats(&body);
-                        if n == 0 {
-                            rs.ui.set_status("No cheats found for this game".into());
-                        } else {
-                            rs.ui.set_status(format!("Fetched {n} cheats"));
-                        }
-                    }
-                    (FetchPurpose::NoIntro, Ok(body)) => {
-                        // Cache the downloaded DAT so we don't re-fetch it next
-                        // launch, then feed it into the index and re-resolve the
-                        // current ROM's display name.
-                        if let Some(url) = done.url.as_deref() {
-                            crate::no_intro_cache::store(&save_base(), url, &body);
-                        }
-                        self.app
-                            .session_mut()
-                            .finish_no_intro_dats(std::slice::from_ref(&body));
-                        if let Some(title) = self.app.title_if_due() {
-                            window.set_title(&title);
-                        }
-                    }
-                    (FetchPurpose::Cheats, Err(e)) => {
-                        // A failed cheat fetch is not fatal — surface it in the
-                        // status bar, never the crash screen.
-                        rs.ui.set_status(format!("Cheat fetch failed: {e}"));
-                    }
-                    (FetchPurpose::NoIntro, Err(e)) => {
-                        // No-Intro identification is best-effort; a failed DAT
-                        // download just leaves games on their header titles.
-                        log::warn!("No-Intro DAT fetch failed: {e}");
-                    }
-                }
-            }
-        }
-
-        if let Some(title) = self.app.title_if_due() {
-            window.set_title(&title);
-        }
-
-        // Keep the render surface locked to the live window size *before* laying
-        // out egui (egui lays out using `window.inner_size()`; the `Resized` event
-        // is async, so the surface can lag after a programmatic resize). Syncing
-        // here (a cheap no-op when unchanged) keeps layout and target size in step.
-        {
-            let phys = window.inner_size();
-            let (pw, ph) = (phys.width.max(1), phys.height.max(1));
-            if (pw, ph) != rs.renderer.surface_size() {
-                rs.renderer.resize(pw, ph);
-            }
-        }
-
-        // Android IME: synthesize egui events winit drops.
-        let extra_events = collect_extra_egui_events();
-
-        // The menu-bar auto-hide flag is a desktop concern (Android is always
-        // fullscreen and uses the mobile menu; `is_fullscreen` only exists there).
-        #[cfg(not(target_os = "android"))]
-        let fullscreen = self.is_fullscreen;
-        #[cfg(target_os = "android")]
-        let fullscreen = false;
-        let requests = self.app.draw(window, &mut rs.ui, rs.renderer.as_mut(), extra_events, fullscreen, resolve_gui_action);
-
-        for req in requests {
+        for req in std::mem::take(&mut self.requests) {
             match req {
                 PlatformRequest::Exit => {
                     event_loop.exit();
@@ -1222,12 +1281,7 @@ impl GuiApp<'_> {
                 }
                 PlatformRequest::ToggleFullscreen => {
                     #[cfg(not(target_os = "android"))]
-                    {
-                        self.is_fullscreen = !self.is_fullscreen;
-                        window.set_fullscreen(
-                            self.is_fullscreen.then(|| Fullscreen::Borderless(None)),
-                        );
-                    }
+                    self.fullscreen.toggle(window);
                 }
                 PlatformRequest::ResizeContent { width, height } => {
                     // Just record the new content size; the continuous fit below
@@ -1258,17 +1312,16 @@ impl GuiApp<'_> {
                 PlatformRequest::Status(s) => rs.ui.set_status(s),
                 PlatformRequest::Error(e) => rs.ui.set_error(e),
                 PlatformRequest::ClearError => rs.ui.clear_error(),
-                // ROM/state loads + battery/RTC imports are resolved inside
-                // `App::draw` (they need the file resolver), so this arm is
-                // unreachable on desktop/Android; kept for the shared contract
-                // (the web worker services it). Log if it fires.
+                // ROM/state loads + battery/RTC imports are resolved by
+                // `App::apply_ui` (they need the file resolver) and fetches are
+                // served by the emulation thread, so these arms are unreachable
+                // on desktop/Android; kept for the shared contract (the web
+                // worker services them). Log if one fires.
                 PlatformRequest::LoadFile { .. } => {
                     log::warn!("LoadFile request reached the platform loop unexpectedly");
                 }
-                PlatformRequest::FetchUrl { urls, purpose } => {
-                    self.fetch_worker
-                        .get_or_insert_with(crate::fetch_worker::FetchWorker::new)
-                        .submit(urls, purpose);
+                PlatformRequest::FetchUrl { .. } => {
+                    log::warn!("FetchUrl request reached the platform loop unexpectedly");
                 }
                 #[cfg(target_os = "android")]
                 PlatformRequest::AndroidLibrary(action) => {
@@ -1276,26 +1329,38 @@ impl GuiApp<'_> {
                 }
             }
         }
+    }
 
-        // Breakpoint-hit notification (surface the PC in the status bar).
-        if self.app.take_breakpoint_hit() {
-            let pc = self.app.gb().get_cpu_registers().pc;
-            rs.ui.set_status(format!("Breakpoint hit at PC: ${pc:04X}"));
-        }
-
+    /// Fit the window to the content and apply a settled aspect-snap.
+    #[cfg_attr(target_os = "android", allow(unused_variables))]
+    fn finish_frame(&mut self, window: &Arc<Window>) {
         // Programmatic fit: size the window so the egui central rect is exactly
         // content*scale (game fills it, no bars). Target = content*scale + the
         // measured chrome inset. Fires ONLY on the first frame (inset now known)
-        // and when the content size changes (SGB border appearing/disappearing) —
-        // never continuously, so it does not fight a user resize.
+        // and when the content size changes (SGB border appearing/disappearing,
+        // or a new aspect ratio reshaping it) and when a window scale is picked
+        // — never continuously, so it does not fight a user resize. Skipped
+        // while fullscreen; a change made there is fitted on leaving, after
+        // the windowed size is restored.
         #[cfg(not(target_os = "android"))]
         {
-            let content = self.app.content_size();
+            let saved = self.view.window_scale;
+            if saved != self.saved_window_scale {
+                self.saved_window_scale = saved;
+                self.window_scale = saved;
+                self.last_fit_logical = None;
+            }
+        }
+        #[cfg(not(target_os = "android"))]
+        if self.fullscreen.is_on() {
+            self.fullscreen.hide_idle_cursor(window);
+        } else {
+            let content = self.view.display_size;
             let content_changed = content != self.last_content_size;
             self.last_content_size = content;
             if content_changed || self.last_fit_logical.is_none() {
-                let scale = self.config.scale.max(1) as u32;
-                let (inset_w, inset_h) = self.app.content_inset();
+                let scale = self.window_scale.max(1) as u32;
+                let (inset_w, inset_h) = self.content_inset;
                 let target = (
                     (content.0 * scale + inset_w.round() as u32).max(1),
                     (content.1 * scale + inset_h.round() as u32).max(1),
@@ -1335,8 +1400,11 @@ impl GuiApp<'_> {
         // compositor. Record it as `pending_snap`; `draw_frame` applies it once the
         // resize settles. The window follows the cursor freely during the drag
         // (the game renders aspect-fit with a transient bar), then snaps on release.
+        // Fullscreen sizes are the monitor's, never snapped.
         #[cfg(not(target_os = "android"))]
-        {
+        if self.fullscreen.is_on() {
+            self.pending_snap = None;
+        } else {
             let now = Instant::now();
             // A gap since the last resize means a new drag burst began; baseline
             // the driving-axis detection to this size.
@@ -1349,10 +1417,10 @@ impl GuiApp<'_> {
             }
             self.last_resize_at = Some(now);
 
-            let (cw, ch) = self.app.content_size();
+            let (cw, ch) = self.view.display_size;
             let aspect = cw as f32 / ch as f32;
             let sf = self.window.as_ref().map_or(1.0, |w| w.scale_factor()) as f32;
-            let (iw, ih) = self.app.content_inset();
+            let (iw, ih) = self.content_inset;
             let (iw_p, ih_p) = (iw * sf, ih * sf);
             let (new_w, new_h) = (size.width as f32, size.height as f32);
             let base = self.resize_burst_start.unwrap_or(size);
@@ -1380,11 +1448,11 @@ impl GuiApp<'_> {
 /// Turn an OS-requiring UI action into bytes the app can apply. Handles the file
 /// reads (`LoadRom`/`LoadState` with a `Path`, or content bytes on web/Android);
 /// returns `None` for actions the app handles itself.
-fn resolve_gui_action(action: &GuiAction) -> Option<ResolvedAction> {
+pub(crate) fn resolve_gui_action(action: &GuiAction) -> Option<ResolvedAction> {
     match action {
         GuiAction::LoadRom(file_data) => {
             let (bytes, path) = read_file_data(file_data)?;
-            Some(ResolvedAction::LoadRom { bytes, path })
+            Some(ResolvedAction::LoadRom { bytes, path, patch: read_sidecar_patch(file_data) })
         }
         GuiAction::LoadState(file_data) | GuiAction::ImportState(file_data) => {
             let (state, _path) = read_file_data(file_data)?;
@@ -1415,6 +1483,14 @@ fn resolve_gui_action(action: &GuiAction) -> Option<ResolvedAction> {
             let (bytes, _path) = read_file_data(file_data)?;
             Some(ResolvedAction::LoadSgbFirmware { bytes })
         }
+        GuiAction::LoadPaletteFile(file_data) => {
+            let (bytes, _path) = read_file_data(file_data)?;
+            Some(ResolvedAction::LoadPalette { bytes })
+        }
+        GuiAction::ImportInputProfile(file_data) => {
+            let (bytes, _path) = read_file_data(file_data)?;
+            Some(ResolvedAction::ImportInputProfile { bytes })
+        }
         _ => None,
     }
 }
@@ -1466,6 +1542,26 @@ fn read_file_data(file_data: &FileData) -> Option<(Vec<u8>, Option<String>)> {
     }
 }
 
+/// The soft-patch stored next to a picked ROM file (`Game.gb` -> `Game.ips`
+/// etc.), if any. Only path-backed picks have neighbours to look at.
+fn read_sidecar_patch(file_data: &FileData) -> Option<Vec<u8>> {
+    match file_data {
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        FileData::Path(path) => match rustyboi_core_lib::patch::read_sidecar_patch(path) {
+            Ok(found) => found.map(|(patch_path, bytes)| {
+                println!("Soft-patching with {}", patch_path.display());
+                bytes
+            }),
+            Err(e) => {
+                eprintln!("Failed to read soft-patch next to {}: {e}", path.display());
+                None
+            }
+        },
+        #[cfg(any(target_os = "android", target_os = "ios"))]
+        FileData::Contents { .. } => None,
+    }
+}
+
 /// Collect any extra egui events to inject before the UI runs. On Android this
 /// diffs the GameTextInput buffer (winit 0.29 drops `TextEvent`) into egui
 /// Text/Backspace events. Empty everywhere else.
@@ -1480,55 +1576,6 @@ fn collect_extra_egui_events() -> Vec<rustyboi_frontend_lib::egui_events::Event>
     }
 }
 
-/// Drain a rewind snapshot to the background serializer, push back finished
-/// blobs, and drain any finished printer sheets to the PNG worker. Called once
-/// per emulated frame (native desktop only; Android keeps inline capture).
-#[cfg(not(target_os = "android"))]
-fn pump_workers(
-    app: &mut App,
-    rewind_worker: Option<&mut crate::rewind_worker::RewindWorker>,
-    png_worker: &mut Option<crate::png_worker::PngWorker>,
-    next_print_index: &mut Option<(String, u32)>,
-) {
-    // Rewind: hand the cheap clone off-thread; push back completed serializes.
-    if let Some(worker) = rewind_worker {
-        if let Some((frame, gb)) = app.session_mut().take_pending_snapshot() {
-            worker.submit(frame, gb);
-        }
-        for done in worker.drain_finished() {
-            app.session_mut().push_rewind_bytes(done.frame, done.bytes);
-        }
-    }
-
-    // Printer: drain finished photos (strips already stitched into one long
-    // sheet by the session), encode + write off-thread.
-    let sheets = app.session_mut().take_prints();
-    if sheets.is_empty() {
-        return;
-    }
-    let stem = app
-        .current_rom_path()
-        .map(|p| std::path::Path::new(p).with_extension("").to_string_lossy().into_owned())
-        .unwrap_or_else(|| "rustyboi".to_string());
-    let mut n = match next_print_index.as_ref() {
-        Some((s, i)) if *s == stem => *i,
-        _ => {
-            let mut i = 1u32;
-            while std::path::Path::new(&format!("{stem}-print-{i}.png")).exists() {
-                i += 1;
-            }
-            i
-        }
-    };
-    let worker = png_worker.get_or_insert_with(crate::png_worker::PngWorker::new);
-    for sheet in sheets {
-        let path = format!("{stem}-print-{n}.png");
-        n += 1;
-        worker.write_sheet(std::path::PathBuf::from(path), sheet);
-    }
-    *next_print_index = Some((stem, n));
-}
-
 /// Service an Android ROM-library / SAF action the app handed back: it needs the
 /// JNI bridge (`android_bridge`) + the library panel + persisted `LibraryState`,
 /// all platform-owned. Ported from the old display event-loop Android arms.
@@ -1623,16 +1670,6 @@ fn handle_android_library(
     }
 }
 
-/// On Android there is no off-thread PNG sink; drain and warn (rewind stays
-/// on the session's inline capture path there, so nothing else to pump).
-#[cfg(target_os = "android")]
-fn drain_printer_sheets_unsupported(app: &mut App) {
-    let sheets = app.session_mut().take_prints();
-    if !sheets.is_empty() {
-        log::warn!("{} print(s) captured but this platform has no print sink", sheets.len());
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1668,6 +1705,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ticks_follow_the_display_refresh() {
+        // 144 Hz and 120 Hz panels tick once per refresh.
+        assert_eq!(tick_period(Some(144_000)), Duration::from_nanos(6_944_444));
+        assert_eq!(tick_period(Some(120_000)), Duration::from_nanos(8_333_333));
+        // 60 Hz is a touch faster than the Game Boy's 59.73.
+        assert_eq!(tick_period(Some(60_000)), Duration::from_nanos(16_666_666));
+        // Slower or unknown displays keep one tick per emulated frame, at the
+        // exact 70224-dot period rather than a rounded one.
+        assert_eq!(FRAME_PERIOD, Duration::from_nanos(16_742_706));
+        assert_eq!(tick_period(Some(50_000)), FRAME_PERIOD);
+        assert_eq!(tick_period(Some(0)), FRAME_PERIOD);
+        assert_eq!(tick_period(None), FRAME_PERIOD);
+        // An absurd report is capped rather than spinning the loop.
+        assert_eq!(tick_period(Some(u32::MAX)), Duration::from_millis(2));
+    }
+
     #[test]
     fn safe_insets_from_rect_computes_the_gap() {
         // 1000x600 surface, content rect inset 10 left / 20 top and ending at
@@ -0,0 +1,699 @@
+//! The `emulation` thread: sole owner of the [`App`] — and with it the
+//! `Session` — from window-loop startup until exit.
+//!
+//! `GB::run_until_frame` used to run on the winit event-loop thread, so egui
+//! layout with debug windows open came straight out of the time left to
+//! emulate, and a heavy panel starved the audio ring. Now the event loop only
+//! draws. This thread paces itself with its own [`Regulator`], opens the
+//! audio device and feeds its ring directly, and serves everything else that
+//! needs the session (rewind/printer workers, fetches, the control socket and
+//! debug server, spectators). The two sides talk over channels:
+//!
+//! - [`Command`]s in: each tick's held inputs, the laid-out UI's `GuiAction`
+//!   and menu state, focus changes, debug steps.
+//! - [`Update`]s out, one per UI tick: the packed frame, the [`UiPrep`] the
+//!   next layout reads, and the [`PlatformRequest`]s only the window can
+//!   perform.
+//!
+//! Neither side waits for the other. A fast-forward batch only delays the
+//! next update, so the menus stay live; a slow layout only means fewer
+//! updates, so emulation and audio keep their pace.
+
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use rustyboi_core_lib::input::ButtonState;
+use rustyboi_frontend_lib::actions::GuiAction;
+use rustyboi_frontend_lib::{App, Picture, PlatformRequest, UiPrep};
+use rustyboi_session::input_config::{FiredHotkey, HeldInputs, HotkeyAction, StickConfig};
+use rustyboi_session::pacing::{Regulator, Stretcher};
+use rustyboi_session::{step_emulation_speed, ConnectedPad, DebugDetail, GraphicsBackend, ResolveState};
+
+use crate::display::{now_epoch_secs, resolve_gui_action, save_base};
+
+/// One UI tick's raw input, resolved through the session's bindings on the
+/// emulation thread.
+#[derive(Default)]
+pub(crate) struct Inputs {
+    pub held: HeldInputs,
+    /// The egui touch overlay's buttons, OR'd over the bindings.
+    pub touch: ButtonState,
+    /// Controller (dis)connect notices for the OSD.
+    pub notices: Vec<String>,
+    /// The controllers present, where the host enumerates them.
+    pub pads: Option<Vec<ConnectedPad>>,
+}
+
+/// What the laid-out UI did this tick.
+pub(crate) struct Drawn {
+    pub action: Option<GuiAction>,
+    pub menu_open: bool,
+    /// The detail the open debug panels want; `None` with none open.
+    pub debug: Option<DebugDetail>,
+}
+
+/// What the event loop sends the emulation thread.
+pub(crate) enum Command {
+    Input(Inputs),
+    /// A UI tick ended, with what its UI did (`None` when nothing was laid
+    /// out): apply it and answer with an [`Update`].
+    Tick(Option<Drawn>),
+    /// Debug single-frame / single-instruction steps (honoured while paused).
+    StepFrame,
+    StepCycle,
+    Focused(bool),
+    /// The window lost its surface: flush the save (mobile may never return).
+    Suspend,
+    /// A rendered [`Picture`]'s buffer, for the next one.
+    Recycle(Vec<u8>),
+}
+
+/// Window-side facts about the app, refreshed with each [`Update`].
+#[derive(Clone)]
+pub(crate) struct View {
+    pub display_size: (u32, u32),
+    pub window_scale: u8,
+    pub graphics_backend: GraphicsBackend,
+    /// Fast-forwarding under Settings → Vsync Off While Fast-Forwarding.
+    pub unsynced: bool,
+    /// A game is running unpaused (holds the display awake).
+    pub running: bool,
+    /// The left stick's deadzone and diagonals, for reading pads.
+    pub left_stick: StickConfig,
+}
+
+impl View {
+    pub(crate) fn of(app: &App) -> Self {
+        let session = app.session();
+        View {
+            display_size: app.display_size(),
+            window_scale: session.window_scale(),
+            graphics_backend: session.graphics_backend(),
+            unsynced: session.fast_forward_vsync_off(),
+            running: !app.is_effectively_paused() && app.gb().has_rom(),
+            left_stick: session.input_config().left_stick,
+        }
+    }
+}
+
+/// What the emulation thread sends back for each UI tick.
+pub(crate) struct Update {
+    pub prep: UiPrep,
+    /// The newest frame; `None` keeps showing the last one.
+    pub picture: Option<Picture>,
+    pub requests: Vec<PlatformRequest>,
+    pub title: Option<String>,
+    pub view: View,
+}
+
+/// What moves to the emulation thread with the app, built by the window
+/// loop's setup.
+pub(crate) struct Services {
+    #[cfg(not(target_os = "android"))]
+    pub rewind_worker: Option<crate::rewind_worker::RewindWorker>,
+    pub fetch_worker: Option<crate::fetch_worker::FetchWorker>,
+    /// The `--control` automation socket, served every tick.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub control: Option<crate::control::ControlServer>,
+    /// The `--debug-server` WebSocket debugger, served every tick.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub debug_server: Option<crate::debug_server::DebugServer>,
+    /// The `--record-video` export, fed by the session until exit.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub video_writer: Option<crate::video_export::VideoWriter>,
+}
+
+/// The event loop's handle on the emulation thread.
+pub(crate) struct EmuThread {
+    commands: Option<mpsc::Sender<Command>>,
+    updates: mpsc::Receiver<Update>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl EmuThread {
+    pub(crate) fn spawn(app: App, services: Services) -> Self {
+        let (commands, command_rx) = mpsc::channel();
+        let (update_tx, updates) = mpsc::channel();
+        let worker = std::thread::Builder::new()
+            .name("emulation".into())
+            .spawn(move || Emulator::new(app, services).run(&command_rx, &update_tx))
+            .expect("spawn emulation thread");
+        Self { commands: Some(commands), updates, worker: Some(worker) }
+    }
+
+    pub(crate) fn send(&mut self, command: Command) {
+        let sent = self.commands.as_ref().is_some_and(|commands| commands.send(command).is_ok());
+        if !sent {
+            self.rethrow();
+        }
+    }
+
+    /// The updates that arrived since the last call, merged into one: the
+    /// newest prep, view and frame, with every request and title in order.
+    /// `None` when nothing arrived.
+    pub(crate) fn poll(&mut self) -> Option<Update> {
+        let mut merged: Option<Update> = None;
+        loop {
+            match self.updates.try_recv() {
+                Ok(mut update) => {
+                    if let Some(older) = merged.take() {
+                        let mut requests = older.requests;
+                        requests.append(&mut update.requests);
+                        update.requests = requests;
+                        update.title = update.title.or(older.title);
+                        match (&update.picture, older.picture) {
+                            (None, picture) => update.picture = picture,
+                            (Some(_), Some(stale)) => self.send(Command::Recycle(stale.into_buffer())),
+                            (Some(_), None) => {}
+                        }
+                    }
+                    merged = Some(update);
+                }
+                Err(mpsc::TryRecvError::Empty) => return merged,
+                Err(mpsc::TryRecvError::Disconnected) => self.rethrow(),
+            }
+        }
+    }
+
+    /// Close the command channel and wait for the thread to save and wind
+    /// down.
+    pub(crate) fn stop(&mut self) {
+        drop(self.commands.take());
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+
+    /// The emulation thread hung up, which it only does by panicking:
+    /// re-raise its panic here.
+    fn rethrow(&mut self) -> ! {
+        drop(self.commands.take());
+        match self.worker.take().map(JoinHandle::join) {
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            _ => panic!("emulation thread exited"),
+        }
+    }
+}
+
+impl Drop for EmuThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// The emulation thread's state.
+struct Emulator {
+    app: App,
+    services: Services,
+    /// `None` when no device could be opened.
+    audio: Option<crate::audio::Output>,
+    /// The shared frame-pacing regulator (see `rustyboi_session::pacing`) and
+    /// the epoch its `now` timestamps are measured from.
+    regulator: Regulator,
+    pacing_epoch: Instant,
+    /// The micro-resampler bridging each frame's audio to the device clock.
+    stretcher: Stretcher,
+    /// Per-frame edge/phase state for the shared input resolver (hotkey
+    /// rising edges + the turbo autofire square wave).
+    resolve_state: ResolveState,
+    /// The detail the UI's open debug panels want (`None`: none open).
+    debug: Option<DebugDetail>,
+    /// Requests for the event loop, sent with the next update.
+    outbox: Vec<PlatformRequest>,
+    /// Rendered pictures' buffers, handed back by the event loop.
+    spare: Vec<Vec<u8>>,
+    #[cfg(not(target_os = "android"))]
+    png_worker: Option<crate::png_worker::PngWorker>,
+    #[cfg(not(target_os = "android"))]
+    next_print_index: Option<(String, u32)>,
+}
+
+impl Emulator {
+    fn new(app: App, services: Services) -> Self {
+        // The device is opened here, where it is fed: the ring its callback
+        // drains never waits on the event loop.
+        let session = app.session();
+        let (rate, buffer) = (session.audio_sample_rate(), session.audio_buffer_size());
+        let device = session.audio_device().map(str::to_owned);
+        let audio = match crate::audio::Output::new(rate, buffer, device).and_then(|mut o| {
+            o.start_device()?;
+            Ok(o)
+        }) {
+            Ok(o) => Some(o),
+            Err(e) => {
+                println!("Failed to initialize audio: {e}; continuing without audio");
+                None
+            }
+        };
+        let mut outbox = Vec::new();
+        // A ROM passed on the command line never went through the GUI load
+        // path, so surface its header warning and dump verdict here.
+        #[cfg(not(mobile))]
+        if session.header_warning().is_some() || session.dump_status().is_some() {
+            outbox.push(PlatformRequest::Status(session.load_status("ROM loaded")));
+        }
+        Emulator {
+            app,
+            services,
+            audio,
+            regulator: Regulator::new(),
+            pacing_epoch: Instant::now(),
+            stretcher: Stretcher::new(),
+            resolve_state: ResolveState::new(),
+            debug: None,
+            outbox,
+            spare: Vec::new(),
+            #[cfg(not(target_os = "android"))]
+            png_worker: None,
+            #[cfg(not(target_os = "android"))]
+            next_print_index: None,
+        }
+    }
+
+    /// Emulate whenever the regulator has a frame due, and apply commands as
+    /// they arrive in between, until the event loop hangs up.
+    fn run(mut self, commands: &mpsc::Receiver<Command>, updates: &mpsc::Sender<Update>) {
+        let mut due = Instant::now();
+        loop {
+            let mut answer = false;
+            match commands.recv_timeout(due.saturating_duration_since(Instant::now())) {
+                Ok(command) => answer |= self.handle(command),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+            // Apply the whole backlog before emulating or answering, so a
+            // stalled stretch is caught up with one update, not one per tick
+            // missed.
+            let mut open = true;
+            while open {
+                match commands.try_recv() {
+                    Ok(command) => answer |= self.handle(command),
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => open = false,
+                }
+            }
+            if !open {
+                break;
+            }
+            let now = Instant::now();
+            if now >= due {
+                due = now + self.emulate();
+            }
+            if answer && updates.send(self.update()).is_err() {
+                break;
+            }
+        }
+        self.shut_down();
+    }
+
+    /// Apply `command`; `true` when it asks for an update.
+    fn handle(&mut self, command: Command) -> bool {
+        let app = &mut self.app;
+        match command {
+            Command::Input(inputs) => self.apply_inputs(inputs),
+            Command::Tick(drawn) => {
+                if let Some(Drawn { action, menu_open, debug }) = drawn {
+                    self.debug = debug;
+                    let requests = app.apply_ui(action, menu_open, resolve_gui_action);
+                    self.forward(requests);
+                }
+                return true;
+            }
+            Command::StepFrame => {
+                if app.stepping_allowed() {
+                    app.request_step_frame();
+                }
+            }
+            Command::StepCycle => {
+                if app.stepping_allowed() {
+                    app.request_step_cycle();
+                }
+            }
+            Command::Focused(focused) => app.set_window_focused(focused),
+            Command::Suspend => {
+                // On mobile, backgrounding is the last reliable chance to
+                // flush SRAM before the OS may reclaim the app. Persists
+                // through the storage port (no-op for non-battery carts), then
+                // writes back whatever the attached sidecar `.sav` still has
+                // cached.
+                #[cfg(mobile)]
+                {
+                    app.session_mut().persist_battery();
+                    // The OS may never deliver `exiting` after a background,
+                    // so the auto-save snapshot is taken here too.
+                    if let Err(e) = app.session_mut().auto_save(now_epoch_secs()) {
+                        log::warn!("auto-save failed: {e}");
+                    }
+                }
+                if let Some(cart) = app.session_mut().gb_mut().cartridge_mut() {
+                    let _ = cart.flush_save();
+                }
+            }
+            Command::Recycle(buffer) => self.spare.push(buffer),
+        }
+        false
+    }
+
+    /// Resolve the held inputs through the shared config: GB-button bindings
+    /// drive the button state, chord hotkeys drive features.
+    fn apply_inputs(&mut self, inputs: Inputs) {
+        let app = &mut self.app;
+        for notice in inputs.notices {
+            app.session_mut().flash_osd(notice);
+        }
+        if let Some(pads) = inputs.pads
+            && app.session().connected_pads() != pads.as_slice()
+        {
+            app.session_mut().set_connected_pads(pads);
+        }
+        let (mut button_state, fired) = app.session().config().input.resolve(&inputs.held, &mut self.resolve_state);
+        let touch = inputs.touch;
+        button_state.a |= touch.a;
+        button_state.b |= touch.b;
+        button_state.start |= touch.start;
+        button_state.select |= touch.select;
+        button_state.up |= touch.up;
+        button_state.down |= touch.down;
+        button_state.left |= touch.left;
+        button_state.right |= touch.right;
+        app.set_button_state(button_state);
+        // Forward the held pad set so the keybind editor can capture gamepad
+        // presses (egui never sees pad input).
+        app.set_held_pad(inputs.held.all_pad());
+        app.hold_fast_forward(fired.iter().any(|f| f.action == HotkeyAction::HoldFastForward));
+        for f in fired {
+            dispatch_hotkey(app, f, &mut self.outbox);
+        }
+    }
+
+    /// Pass the app's requests on to the event loop, keeping the ones this
+    /// thread serves.
+    fn forward(&mut self, requests: Vec<PlatformRequest>) {
+        for request in requests {
+            match request {
+                PlatformRequest::FetchUrl { urls, purpose } => {
+                    self.services
+                        .fetch_worker
+                        .get_or_insert_with(crate::fetch_worker::FetchWorker::new)
+                        .submit(urls, purpose);
+                }
+                other => self.outbox.push(other),
+            }
+        }
+    }
+
+    /// Emulate exactly as many frames as the shared regulator grants: a
+    /// wall-clock token bucket with a bounded DAC trim (see
+    /// `rustyboi_session::pacing`). The app never sleeps and never reads a
+    /// pacing clock; game speed is therefore exact 59.7275 regardless of
+    /// host timer quality or audio-pipeline misbehavior — the same regulator
+    /// paces every platform, including the web worker. Returns how long to
+    /// wait before the next grant.
+    ///
+    /// When paused, the regulator grants 0 but `run_frame` must still be
+    /// called once: it services the debug-step requests and frame-advance
+    /// mode (all of which early-return cheaply when idle).
+    fn emulate(&mut self) -> Duration {
+        let app = &mut self.app;
+        let now = self.pacing_epoch.elapsed().as_secs_f64();
+        let paused = app.is_effectively_paused();
+        // Retune to the running machine: an SGB1 is clocked by the host SNES
+        // (÷5) and genuinely runs ~61.17 fps, not 59.73. Idempotent, so it can
+        // ride the tick and pick up a hardware/region change immediately.
+        let session = app.session();
+        self.regulator.set_cpu_hz(session.cpu_hz());
+        self.regulator.set_speed(session.emulation_speed());
+        self.regulator.set_sample_rate(session.audio_sample_rate());
+        self.regulator.set_target_backlog(session.audio_latency_frames());
+        let fast_forward = app.is_fast_forward();
+        let granted = self.regulator.frames_to_run(
+            now,
+            self.audio.as_ref().map(|a| a.queued_pairs()),
+            fast_forward,
+            paused,
+        );
+        // Bridge to the device clock: micro-resample by the regulator's
+        // stretch ratio (1.0 on healthy hosts).
+        let stretch = self.regulator.audio_stretch();
+        let (mut emulated, mut pump) = (0, false);
+        for _ in 0..if paused { 1 } else { granted } {
+            let step = app.run_frame();
+            pump |= step.pump_workers;
+            emulated += u32::from(step.advanced);
+            if let Some(audio) = self.audio.as_mut()
+                && !step.audio.is_empty()
+            {
+                audio.push_samples(self.stretcher.process(&step.audio, stretch));
+            }
+            // A breakpoint can pause mid-grant; don't burn the rest of the
+            // grant on no-op calls.
+            if app.is_effectively_paused() {
+                break;
+            }
+        }
+        if pump {
+            #[cfg(not(target_os = "android"))]
+            pump_workers(
+                app,
+                self.services.rewind_worker.as_mut(),
+                &mut self.png_worker,
+                &mut self.next_print_index,
+            );
+            #[cfg(target_os = "android")]
+            drain_printer_sheets_unsupported(app);
+        }
+        echo_serial_output(app);
+        self.deliver_fetches();
+        let app = &mut self.app;
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        if let Some(control) = self.services.control.as_mut() {
+            control.poll(app);
+        }
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        if let Some(debug_server) = self.services.debug_server.as_mut() {
+            debug_server.poll(app);
+        }
+        app.session_mut().poll_spectators();
+
+        // Settings → Audio Output Device, and the fall-back/replug handling
+        // behind it; the menu lists whatever the backend last enumerated.
+        if let Some(a) = self.audio.as_mut()
+            && let Some(devices) = a.follow_device(app.session().audio_device())
+        {
+            app.session_mut().set_audio_devices(devices);
+        }
+        // The FPS overlay's audio line: the ring depth and underruns.
+        app.set_audio_stats(self.audio.as_ref().map(|a| a.stats()));
+        app.note_grant(emulated, self.regulator.is_behind());
+        app.note_frames(now, emulated);
+
+        // Breakpoint-hit notification (surface the PC in the status bar).
+        if app.take_breakpoint_hit() {
+            let pc = app.gb().get_cpu_registers().pc;
+            self.outbox.push(PlatformRequest::Status(format!("Breakpoint hit at PC: ${pc:04X}")));
+        }
+
+        // Fast-forward has the session batch its factor's frames per grant,
+        // so grants come once per frame; uncapped batches back to back. A
+        // paused app only needs its debug steps serviced, and commands wake
+        // the thread early anyway.
+        let frame = Duration::from_secs_f64(1.0 / self.regulator.nominal_fps());
+        if paused {
+            frame
+        } else if fast_forward {
+            if app.session().config().ff_uncapped() { Duration::ZERO } else { frame }
+        } else {
+            Duration::from_secs_f64(self.regulator.seconds_until_next_frame()).min(frame)
+        }
+    }
+
+    /// Deliver completed cheat-DB and No-Intro fetches into the session;
+    /// report the outcome in the status bar.
+    fn deliver_fetches(&mut self) {
+        let Some(worker) = self.services.fetch_worker.as_mut() else { return };
+        for done in worker.drain_finished() {
+            use rustyboi_session::FetchPurpose;
+            match (done.purpose, done.result) {
+                (FetchPurpose::Cheats, Ok(body)) => {
+                    let n = self.app.session_mut().finish_fetched_cheats(&body);
+                    let status = if n == 0 {
+                        "No cheats found for this game".to_string()
+                    } else {
+                        format!("Fetched {n} cheats")
+                    };
+                    self.outbox.push(PlatformRequest::Status(status));
+                }
+                (FetchPurpose::NoIntro, Ok(body)) => {
+                    // Cache the downloaded DAT so we don't re-fetch it next
+                    // launch, then feed it into the index and re-resolve the
+                    // current ROM's display name.
+                    if let Some(url) = done.url.as_deref() {
+                        crate::no_intro_cache::store(&save_base(), url, &body);
+                    }
+                    self.app.session_mut().finish_no_intro_dats(std::slice::from_ref(&body));
+                }
+                (FetchPurpose::Cheats, Err(e)) => {
+                    // A failed cheat fetch is not fatal — surface it in the
+                    // status bar, never the crash screen.
+                    self.outbox.push(PlatformRequest::Status(format!("Cheat fetch failed: {e}")));
+                }
+                (FetchPurpose::NoIntro, Err(e)) => {
+                    // No-Intro identification is best-effort; a failed DAT
+                    // download just leaves games on their header titles.
+                    log::warn!("No-Intro DAT fetch failed: {e}");
+                }
+            }
+        }
+    }
+
+    fn update(&mut self) -> Update {
+        let app = &mut self.app;
+        let buffer = self.spare.pop().unwrap_or_default();
+        Update {
+            prep: app.snapshot_ui(self.debug),
+            picture: app.picture(buffer),
+            requests: std::mem::take(&mut self.outbox),
+            title: app.title_if_due(),
+            view: View::of(app),
+        }
+    }
+
+    /// The event loop is exiting: snapshot the machine for the next launch's
+    /// resume offer and finish any recording before the app drops.
+    fn shut_down(mut self) {
+        if let Err(e) = self.app.session_mut().auto_save(now_epoch_secs()) {
+            log::warn!("auto-save failed: {e}");
+        }
+        // A `--record-video` capture ends with the run (unless a GUI toggle
+        // stopped it first). The frames and audio are already on disk; the
+        // writer only has the encoder's tail and the audio mux left.
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        if let Some(writer) = self.services.video_writer.take() {
+            self.app.session_mut().stop_video_recording();
+            match writer.finish() {
+                Ok((written, seconds)) => println!("Video ({seconds:.1} s) written to {}", written.display()),
+                Err(e) => eprintln!("--record-video: {e}"),
+            }
+        }
+    }
+}
+
+/// Perform a fired hotkey on the app, queueing what only the window can do
+/// (fullscreen, exit) for the event loop. Turbo is handled inside the
+/// resolver (it drives the button state), so no dispatch is needed here for
+/// it, nor for the hold fast-forward (its level goes straight to the app).
+/// Rewind is a hold action (fires every active frame); the rest, including
+/// FastForward, are edge-triggered toggles that fire on the rising edge.
+fn dispatch_hotkey(app: &mut App, fired: FiredHotkey, outbox: &mut Vec<PlatformRequest>) {
+    match fired.action {
+        HotkeyAction::FastForward if fired.rising => app.toggle_fast_forward(),
+        HotkeyAction::Rewind if app.rewind_enabled() => app.rewind(),
+        HotkeyAction::Quicksave if fired.rising => match app.quicksave(now_epoch_secs()) {
+            Ok(()) => println!("Quicksaved"),
+            Err(e) => println!("Quicksave failed: {e}"),
+        },
+        HotkeyAction::Quickload if fired.rising => {
+            if let Err(e) = app.quickload() {
+                println!("Quickload failed: {e}");
+            }
+        }
+        HotkeyAction::UndoLoadState if fired.rising => {
+            if let Err(e) = app.undo_load_state() {
+                println!("Undo load state failed: {e}");
+            }
+        }
+        HotkeyAction::SaveSlot(n) if fired.rising => match app.save_slot(n.into(), now_epoch_secs()) {
+            Ok(()) => println!("Saved state to slot {n}"),
+            Err(e) => println!("Saving slot {n} failed: {e}"),
+        },
+        HotkeyAction::LoadSlot(n) if fired.rising => {
+            if let Err(e) = app.load_slot(n.into()) {
+                println!("Loading slot {n} failed: {e}");
+            }
+        }
+        HotkeyAction::FrameAdvance if fired.rising => app.frame_advance(),
+        HotkeyAction::TogglePause if fired.rising => app.toggle_pause(),
+        HotkeyAction::ToggleMute if fired.rising => app.toggle_mute(),
+        HotkeyAction::WindowScale(n) if fired.rising => app.session_mut().set_window_scale(n),
+        HotkeyAction::SpeedUp | HotkeyAction::SlowDown if fired.rising => {
+            let speed = app.session().emulation_speed();
+            let faster = fired.action == HotkeyAction::SpeedUp;
+            app.session_mut().set_emulation_speed(step_emulation_speed(speed, faster));
+        }
+        HotkeyAction::NormalSpeed if fired.rising => app.session_mut().set_emulation_speed(100),
+        HotkeyAction::ToggleFullscreen if fired.rising => outbox.push(PlatformRequest::ToggleFullscreen),
+        HotkeyAction::Exit if fired.rising => outbox.push(PlatformRequest::Exit),
+        _ => {}
+    }
+}
+
+/// Drain a rewind snapshot to the background serializer, push back finished
+/// blobs, and drain any finished printer sheets to the PNG worker. Called once
+/// per emulated frame (native desktop only; Android keeps inline capture).
+#[cfg(not(target_os = "android"))]
+fn pump_workers(
+    app: &mut App,
+    rewind_worker: Option<&mut crate::rewind_worker::RewindWorker>,
+    png_worker: &mut Option<crate::png_worker::PngWorker>,
+    next_print_index: &mut Option<(String, u32)>,
+) {
+    // Rewind: hand the cheap clone off-thread; push back completed serializes.
+    if let Some(worker) = rewind_worker {
+        if let Some((frame, gb)) = app.session_mut().take_pending_snapshot() {
+            worker.submit(frame, gb);
+        }
+        for done in worker.drain_finished() {
+            app.session_mut().push_rewind_bytes(done.frame, done.bytes);
+        }
+    }
+
+    // Printer: drain finished photos (strips already stitched into one long
+    // sheet by the session), encode + write off-thread.
+    let sheets = app.session_mut().take_prints();
+    if sheets.is_empty() {
+        return;
+    }
+    let stem = app
+        .current_rom_path()
+        .map(|p| std::path::Path::new(p).with_extension("").to_string_lossy().into_owned())
+        .unwrap_or_else(|| "rustyboi".to_string());
+    let mut n = match next_print_index.as_ref() {
+        Some((s, i)) if *s == stem => *i,
+        _ => {
+            let mut i = 1u32;
+            while std::path::Path::new(&format!("{stem}-print-{i}.png")).exists() {
+                i += 1;
+            }
+            i
+        }
+    };
+    let worker = png_worker.get_or_insert_with(crate::png_worker::PngWorker::new);
+    for sheet in sheets {
+        let path = format!("{stem}-print-{n}.png");
+        n += 1;
+        worker.write_sheet(std::path::PathBuf::from(path), sheet);
+    }
+    *next_print_index = Some((stem, n));
+}
+
+/// `--serial-stdout`: write this tick's link-port bytes to stdout raw (test
+/// ROMs send ASCII) and flush, so a pipe sees each report line promptly.
+fn echo_serial_output(app: &mut App) {
+    use std::io::Write;
+    let bytes = app.session_mut().drain_serial_output();
+    if !bytes.is_empty() {
+        let mut out = std::io::stdout().lock();
+        let _ = out.write_all(&bytes).and_then(|()| out.flush());
+    }
+}
+
+/// On Android there is no off-thread PNG sink; drain and warn (rewind stays
+/// on the session's inline capture path there, so nothing else to pump).
+#[cfg(target_os = "android")]
+fn drain_printer_sheets_unsupported(app: &mut App) {
+    let sheets = app.session_mut().take_prints();
+    if !sheets.is_empty() {
+        log::warn!("{} print(s) captured but this platform has no print sink", sheets.len());
+    }
+}
@@ -0,0 +1,70 @@
+//! Borderless fullscreen for the desktop window (F11 / Alt+Enter, View →
+//! Toggle Fullscreen).
+//!
+//! Entering remembers the windowed inner size so leaving can put the window
+//! back exactly (some compositors hand back the fullscreen size otherwise).
+//! While fullscreen the cursor hides once the mouse has been still for
+//! `CURSOR_HIDE_DELAY`, and reappears as soon as it moves, so the auto-hide
+//! menu bar stays reachable.
+
+use std::time::{Duration, Instant};
+
+use winit::dpi::PhysicalSize;
+use winit::window::{Fullscreen, Window};
+
+/// How long the mouse must be still in fullscreen before the cursor hides.
+const CURSOR_HIDE_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Default)]
+pub(crate) struct FullscreenState {
+    on: bool,
+    /// The inner size to restore on leaving; `None` while windowed.
+    windowed_size: Option<PhysicalSize<u32>>,
+    /// When the cursor last moved (or fullscreen was entered).
+    cursor_moved_at: Option<Instant>,
+    cursor_hidden: bool,
+}
+
+impl FullscreenState {
+    pub(crate) fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Enter or leave borderless fullscreen on the current monitor.
+    pub(crate) fn toggle(&mut self, window: &Window) {
+        self.on = !self.on;
+        if self.on {
+            self.windowed_size = Some(window.inner_size());
+            self.cursor_moved_at = Some(Instant::now());
+            window.set_fullscreen(Some(Fullscreen::Borderless(None)));
+        } else {
+            window.set_fullscreen(None);
+            if let Some(size) = self.windowed_size.take() {
+                let _ = window.request_inner_size(size);
+            }
+            self.show_cursor(window);
+        }
+    }
+
+    /// The mouse moved: bring the cursor back and restart the idle timer.
+    pub(crate) fn cursor_moved(&mut self, window: &Window) {
+        self.cursor_moved_at = Some(Instant::now());
+        self.show_cursor(window);
+    }
+
+    /// Hide the cursor once it has idled in fullscreen. Called every frame.
+    pub(crate) fn hide_idle_cursor(&mut self, window: &Window) {
+        let idle = self.cursor_moved_at.is_some_and(|t| t.elapsed() >= CURSOR_HIDE_DELAY);
+        if self.on && idle && !self.cursor_hidden {
+            self.cursor_hidden = true;
+            window.set_cursor_visible(false);
+        }
+    }
+
+    fn show_cursor(&mut self, window: &Window) {
+        if self.cursor_hidden {
+            self.cursor_hidden = false;
+            window.set_cursor_visible(true);
+        }
+    }
+}
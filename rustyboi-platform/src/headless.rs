@@ -0,0 +1,258 @@
+//! Headless runs (native desktop): `--bench`, `--frames N --screenshot` and
+//! `--exit-on-break`, command-line modes that drive the loaded machine
+//! straight through the core, with no window, audio device or session, and
+//! exit when done.
+
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use rustyboi_core_lib::gb::GB;
+use rustyboi_session::pacing::DOTS_PER_FRAME;
+use rustyboi_session::{encode_rgb_png, GB_SIZE};
+
+use crate::config::CleanConfig;
+use crate::error::PlatformError;
+
+/// Frames run between wall-clock checks in [`bench`].
+const BENCH_CHECK_EVERY: u64 = 16;
+
+/// What a [`bench`] run measured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BenchReport {
+    pub frames: u64,
+    /// CPU T-cycles executed (twice as many per frame in CGB double speed).
+    pub cycles: u64,
+    pub seconds: f64,
+    /// The machine's real-time clock, to grade the result against.
+    pub cpu_hz: u32,
+}
+
+impl BenchReport {
+    pub fn fps(&self) -> f64 {
+        self.frames as f64 / self.seconds
+    }
+
+    /// Emulated CPU clock per wall second, in MHz.
+    pub fn mhz(&self) -> f64 {
+        self.cycles as f64 / self.seconds / 1e6
+    }
+
+    /// How many times faster than the real machine this ran.
+    pub fn realtime_multiple(&self) -> f64 {
+        self.fps() / (f64::from(self.cpu_hz) / DOTS_PER_FRAME)
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} frames in {:.2} s", self.frames, self.seconds)?;
+        writeln!(f, "{:.1} frames/s ({:.2}× real time)", self.fps(), self.realtime_multiple())?;
+        write!(f, "{:.2} MHz equivalent", self.mhz())
+    }
+}
+
+/// `--bench SECONDS`: run `gb` as fast as the host allows for about
+/// `seconds` of wall time, then print the emulated frame rate and the CPU
+/// clock it amounts to. Audio is generated but not collected.
+pub(crate) fn bench(gb: &mut GB, seconds: f64) -> Result<BenchReport, PlatformError> {
+    if !(seconds.is_finite() && seconds > 0.0) {
+        return Err(PlatformError::new(format!("--bench needs a positive number of seconds, got {seconds}")));
+    }
+    if !gb.has_rom() {
+        eprintln!("--bench: no ROM loaded, timing an empty cartridge slot");
+    }
+    let budget = Duration::from_secs_f64(seconds);
+    let cycles = gb.cycle_count();
+    let mut frames = 0;
+    let start = Instant::now();
+    while start.elapsed() < budget {
+        for _ in 0..BENCH_CHECK_EVERY {
+            gb.run_until_frame(false);
+        }
+        frames += BENCH_CHECK_EVERY;
+    }
+    let report = BenchReport {
+        frames,
+        cycles: gb.cycle_count() - cycles,
+        seconds: start.elapsed().as_secs_f64(),
+        cpu_hz: gb.cpu_hz(),
+    };
+    println!("{report}");
+    Ok(report)
+}
+
+/// Exit status of an `--exit-on-break` run that stopped on a breakpoint, so
+/// scripts can tell it apart from a clean finish (0) and an error (1).
+pub(crate) const BREAK_EXIT_CODE: i32 = 3;
+
+/// A `--screenshot` / `--exit-on-break` run, as parsed from the command line.
+pub(crate) struct Job<'a> {
+    /// Frames to run; `None` runs until a breakpoint.
+    pub frames: Option<u32>,
+    pub breakpoints: &'a [u16],
+    /// Where to write the last frame as a 160x144 PNG.
+    pub screenshot: Option<&'a Path>,
+    /// Where to write registers and memory as JSON when the run stops.
+    pub dump: Option<&'a Path>,
+}
+
+impl<'a> Job<'a> {
+    /// The job the command line asks for. `--break` is only armed under
+    /// `--exit-on-break`: on its own it is meant for the debugger, so a plain
+    /// `--frames N --screenshot` run ignores it and runs every frame.
+    pub fn from_config(config: &'a CleanConfig) -> Self {
+        Job {
+            frames: config.frames,
+            breakpoints: if config.exit_on_break { &config.breakpoints } else { &[] },
+            screenshot: config.screenshot.as_deref().map(Path::new),
+            dump: config.break_dump.as_deref().map(Path::new),
+        }
+    }
+}
+
+/// How a headless [`run`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stop {
+    /// Every requested frame ran.
+    Finished,
+    /// The CPU reached a breakpoint at this address.
+    Breakpoint(u16),
+}
+
+/// Run `gb` from where it stands (power-on, or the loaded state) for the
+/// job's frames or until one of its breakpoints, then write the screenshot
+/// and dump it asked for. Zero frames captures the machine as loaded.
+pub(crate) fn run(gb: &mut GB, job: &Job) -> Result<Stop, PlatformError> {
+    for &address in job.breakpoints {
+        gb.add_breakpoint(address);
+    }
+    let mut frame = gb.get_current_frame();
+    let mut frames = 0;
+    let mut stop = Stop::Finished;
+    while job.frames.is_none_or(|n| frames < n) {
+        let (next, hit) = gb.run_until_frame(false);
+        frame = next;
+        if hit {
+            stop = Stop::Breakpoint(gb.get_cpu_registers().pc);
+            break;
+        }
+        frames += 1;
+    }
+    if let Stop::Breakpoint(pc) = stop {
+        println!("Breakpoint at ${pc:04X} after {frames} frames");
+    }
+    if let Some(path) = job.screenshot {
+        let (width, height) = GB_SIZE;
+        write(path, &encode_rgb_png(width, height, frame.rgb()))?;
+        println!("Wrote frame {frames} to {}", path.display());
+    }
+    if let Some(path) = job.dump {
+        write(path, dump(gb, stop, frames).to_string().as_bytes())?;
+    }
+    Ok(stop)
+}
+
+/// The machine's registers and full 64 KiB memory map as JSON, for scripts
+/// to assert on after a run.
+fn dump(gb: &mut GB, stop: Stop, frames: u32) -> serde_json::Value {
+    gb.sync_lazy_peripherals();
+    let memory: Vec<u8> = (0..=0xFFFF).map(|address| gb.read_memory(address)).collect();
+    serde_json::json!({
+        "breakpoint": match stop {
+            Stop::Breakpoint(pc) => Some(pc),
+            Stop::Finished => None,
+        },
+        "frames": frames,
+        "cycles": gb.cycle_count(),
+        "instructions": gb.instruction_count(),
+        "registers": gb.get_cpu_registers(),
+        "memory": memory,
+    })
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), PlatformError> {
+    std::fs::write(path, bytes).map_err(|e| PlatformError::new(format!("{}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyboi_core_lib::gb::Hardware;
+
+    #[test]
+    fn bench_report_grades_against_the_machine_clock() {
+        let report = BenchReport { frames: 5973, cycles: 5973 * 70_224, seconds: 10.0, cpu_hz: 4_194_304 };
+        assert!((report.fps() - 597.3).abs() < 1e-9);
+        assert!((report.realtime_multiple() - 10.0).abs() < 0.001);
+        assert!((report.mhz() - 41.94).abs() < 0.01);
+        assert!(report.to_string().contains("(10.00× real time)"));
+    }
+
+    #[test]
+    fn bench_runs_for_its_budget_and_rejects_nonsense() {
+        let mut gb = GB::new(Hardware::DMG);
+        let report = bench(&mut gb, 0.05).expect("bench runs");
+        assert!(report.frames > 0 && report.seconds >= 0.05);
+        assert!(report.cycles > 0);
+        assert!(bench(&mut gb, 0.0).is_err());
+        assert!(bench(&mut gb, f64::NAN).is_err());
+    }
+
+    fn job(frames: Option<u32>, breakpoints: &[u16]) -> Job<'_> {
+        Job { frames, breakpoints, screenshot: None, dump: None }
+    }
+
+    #[test]
+    fn screenshot_writes_the_last_frame_as_png() {
+        let path = std::env::temp_dir().join(format!("rustyboi-headless-{}.png", std::process::id()));
+        let mut gb = GB::new(Hardware::DMG);
+        let stop = run(&mut gb, &Job { screenshot: Some(&path), ..job(Some(3), &[]) }).expect("screenshot written");
+        assert_eq!(stop, Stop::Finished);
+        let png = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        // IHDR width and height.
+        assert_eq!(&png[16..24], &[0, 0, 0, 160, 0, 0, 0, 144]);
+        let unwritable = Job { screenshot: Some(Path::new("/nonexistent/dir/out.png")), ..job(Some(1), &[]) };
+        assert!(run(&mut gb, &unwritable).is_err());
+    }
+
+    #[test]
+    fn a_breakpoint_stops_the_run_and_dumps_the_machine() {
+        let path = std::env::temp_dir().join(format!("rustyboi-headless-{}.json", std::process::id()));
+        let mut gb = GB::new(Hardware::DMG);
+        gb.skip_bios();
+        // An empty slot reads 0xFF (RST 38) from $0100 on, so $0038 comes up
+        // within the first few instructions.
+        let stop = run(&mut gb, &Job { dump: Some(&path), ..job(None, &[0x0038]) }).expect("run stops");
+        assert_eq!(stop, Stop::Breakpoint(0x0038));
+        let dump: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(dump["breakpoint"], 0x0038);
+        assert_eq!(dump["registers"]["pc"], 0x0038);
+        assert_eq!(dump["memory"].as_array().map(Vec::len), Some(0x10000));
+    }
+
+    #[test]
+    fn a_screenshot_run_ignores_break_without_exit_on_break() {
+        use clap::Parser;
+        let path = std::env::temp_dir().join(format!("rustyboi-headless-break-{}.png", std::process::id()));
+        let png = path.to_str().unwrap();
+        let args = ["rustyboi", "--frames", "3", "--screenshot", png, "--break", "0038"];
+        let config = crate::config::RawConfig::try_parse_from(args).unwrap().clean();
+        let job = Job::from_config(&config);
+        assert!(job.breakpoints.is_empty());
+        let mut gb = GB::new(Hardware::DMG);
+        gb.skip_bios();
+        // $0038 comes up within a few instructions (see above), yet all three
+        // frames run.
+        assert_eq!(run(&mut gb, &job).expect("screenshot written"), Stop::Finished);
+        let _ = std::fs::remove_file(&path);
+        assert!(gb.get_breakpoints().is_empty());
+
+        let args = ["rustyboi", "--frames", "3", "--break", "0038", "--exit-on-break"];
+        let config = crate::config::RawConfig::try_parse_from(args).unwrap().clean();
+        assert_eq!(Job::from_config(&config).breakpoints, [0x0038]);
+    }
+}
@@ -133,7 +133,7 @@ pub fn present_document_picker(callback: PickFileCallback) {
         return;
     };
 
-    // `UTTypeData` = any file; the user points at their .gb/.gbc/.zip.
+    // `UTTypeData` = any file; the user points at their .gb/.gbc/.zip/.7z/.gz.
     // SAFETY: `UTTypeData` is a framework constant (a `&'static UTType`).
     let data_type = unsafe { UTTypeData };
     let types = NSArray::from_slice(&[data_type]);
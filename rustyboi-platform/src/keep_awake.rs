@@ -0,0 +1,131 @@
+//! Keeps the display and the machine awake while a game runs, so a long
+//! cutscene or an idle grind isn't cut short by the screensaver or sleep.
+//! Held while emulation is unpaused, released on pause and on exit.
+//!
+//! Each OS has its own lever, and none needs a new dependency:
+//! - Windows: `SetThreadExecutionState`, on the event-loop thread that holds it;
+//! - macOS: a `caffeinate -di` child;
+//! - Linux and the BSDs: a `systemd-inhibit --what=idle:sleep` child;
+//! - iOS: the application's idle timer;
+//! - Android: nothing yet (the activity would need `FLAG_KEEP_SCREEN_ON`).
+//!
+//! The helper children run `cat` on a pipe from us, so they exit (dropping
+//! their inhibitor) as soon as the pipe closes: on release, and if this
+//! process dies without releasing.
+
+#[cfg(not(any(target_os = "windows", target_os = "ios", target_os = "android")))]
+use std::process::{Child, Command, Stdio};
+
+/// A sleep/screensaver inhibitor, taken and dropped with [`set`](Self::set).
+#[derive(Default)]
+pub(crate) struct KeepAwake {
+    held: bool,
+    /// The helper process holding the inhibitor.
+    #[cfg(not(any(target_os = "windows", target_os = "ios", target_os = "android")))]
+    child: Option<Child>,
+    /// The helper couldn't be started; don't retry every pause.
+    #[cfg(not(any(target_os = "windows", target_os = "ios", target_os = "android")))]
+    unavailable: bool,
+}
+
+impl KeepAwake {
+    /// Hold the inhibitor while `on`, release it otherwise. Cheap when
+    /// nothing changes, so it can ride every tick.
+    pub(crate) fn set(&mut self, on: bool) {
+        if on != self.held {
+            self.held = on;
+            if on { self.acquire() } else { self.release() }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    #[allow(unsafe_code)]
+    fn acquire(&mut self) {
+        use windows_sys::Win32::System::Power::{
+            ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED, SetThreadExecutionState,
+        };
+        // SAFETY: a flags-only kernel32 call with no memory arguments.
+        unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_DISPLAY_REQUIRED | ES_SYSTEM_REQUIRED) };
+    }
+
+    #[cfg(target_os = "windows")]
+    #[allow(unsafe_code)]
+    fn release(&mut self) {
+        use windows_sys::Win32::System::Power::{ES_CONTINUOUS, SetThreadExecutionState};
+        // SAFETY: as in `acquire`.
+        unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+    }
+
+    #[cfg(target_os = "ios")]
+    fn acquire(&mut self) {
+        set_idle_timer_disabled(true);
+    }
+
+    #[cfg(target_os = "ios")]
+    fn release(&mut self) {
+        set_idle_timer_disabled(false);
+    }
+
+    #[cfg(target_os = "android")]
+    fn acquire(&mut self) {}
+
+    #[cfg(target_os = "android")]
+    fn release(&mut self) {}
+
+    #[cfg(not(any(target_os = "windows", target_os = "ios", target_os = "android")))]
+    fn acquire(&mut self) {
+        if self.unavailable {
+            return;
+        }
+        let mut command = inhibit_command();
+        match command.arg("cat").stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::null()).spawn() {
+            Ok(child) => self.child = Some(child),
+            Err(e) => {
+                self.unavailable = true;
+                log::warn!("could not run {:?} to keep the display awake: {e}", command.get_program());
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "ios", target_os = "android")))]
+    fn release(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            // Closing the pipe ends `cat`; the kill covers a helper that
+            // doesn't pass the EOF on.
+            drop(child.stdin.take());
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for KeepAwake {
+    fn drop(&mut self) {
+        self.set(false);
+    }
+}
+
+/// The helper that holds an inhibitor for as long as the command it wraps runs.
+#[cfg(target_os = "macos")]
+fn inhibit_command() -> Command {
+    let mut command = Command::new("caffeinate");
+    command.arg("-di");
+    command
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "ios", target_os = "android", target_os = "macos")))]
+fn inhibit_command() -> Command {
+    let mut command = Command::new("systemd-inhibit");
+    command.args(["--what=idle:sleep", "--who=RustyBoi", "--why=A game is running", "--mode=block"]);
+    command
+}
+
+#[cfg(target_os = "ios")]
+fn set_idle_timer_disabled(disabled: bool) {
+    use objc2::MainThreadMarker;
+    use objc2_ui_kit::UIApplication;
+    // The event loop runs on the main thread, the only place UIKit allows it.
+    if let Some(mtm) = MainThreadMarker::new() {
+        UIApplication::sharedApplication(mtm).setIdleTimerDisabled(disabled);
+    }
+}
@@ -4,8 +4,9 @@
 // worker moves cloned `GB`s to its thread safely because `GB: Send` (its audio
 // sink is `Box<dyn AudioOutput + Send>`), and wgpu surface creation goes through
 // the safe `Arc<Window>` handle path. Windows gets `deny` instead of `forbid`
-// for exactly one scoped allow: the argumentless `timeBeginPeriod(1)` WinMM
-// FFI call in `display.rs` (the tick throttle's timer-resolution request).
+// for three scoped allows, all FFI calls taking plain integers: `timeBeginPeriod(1)`
+// in `display.rs` (the tick throttle's timer-resolution request) and the two
+// `SetThreadExecutionState` calls in `keep_awake.rs`.
 #![cfg_attr(
     not(any(target_os = "android", target_os = "ios", target_os = "windows")),
     forbid(unsafe_code)
@@ -17,8 +18,11 @@ pub mod android;
 #[cfg(target_os = "ios")]
 pub mod ios;
 mod audio;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod audio_export;
 mod config;
 mod display;
+mod emu_thread;
 mod error;
 #[cfg(target_os = "android")]
 pub mod library;
@@ -29,11 +33,38 @@ mod ports;
 mod png_worker;
 #[cfg(not(target_os = "android"))]
 mod rewind_worker;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod video_export;
+#[cfg(not(target_os = "android"))]
+mod fullscreen;
 // The cheat-DB HTTP fetch worker runs on both desktop and Android (both link
 // ureq).
 mod fetch_worker;
+// The sleep/screensaver inhibitor held while a game runs.
+mod keep_awake;
+// The JSON-RPC automation socket, opened from the desktop CLI.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod control;
+// The WebSocket debugger for external tools, opened from the desktop CLI.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod debug_server;
+// `--bench`, `--screenshot`, `--exit-on-break`: the windowless CLI runs.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod headless;
+// TCP link-cable netplay, joined from the desktop CLI.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod netplay;
 mod no_intro_cache;
 mod run;
+// WebSocket spectator streaming, started from the desktop CLI.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod spectate;
+// Live Game Boy Camera capture through ffmpeg, from the desktop CLI.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod webcam;
+// WebSocket handshake and framing for the spectator and debug servers.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod websocket;
 
 pub use crate::run::run;
 
@@ -5,11 +5,15 @@
 
 #[cfg(not(target_os = "android"))]
 mod audio;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod audio_export;
 #[cfg(not(target_os = "android"))]
 mod config;
 #[cfg(not(target_os = "android"))]
 mod display;
 #[cfg(not(target_os = "android"))]
+mod emu_thread;
+#[cfg(not(target_os = "android"))]
 mod error;
 #[cfg(not(target_os = "android"))]
 mod ports;
@@ -17,12 +21,31 @@ mod ports;
 mod png_worker;
 #[cfg(not(target_os = "android"))]
 mod rewind_worker;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod video_export;
+#[cfg(not(target_os = "android"))]
+mod fullscreen;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod control;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod debug_server;
 #[cfg(not(target_os = "android"))]
 mod fetch_worker;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod headless;
+mod keep_awake;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod netplay;
 #[cfg(not(target_os = "android"))]
 mod no_intro_cache;
 #[cfg(not(target_os = "android"))]
 mod run;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod spectate;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod webcam;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod websocket;
 
 #[cfg(not(target_os = "android"))]
 fn main() -> Result<(), error::PlatformError> {
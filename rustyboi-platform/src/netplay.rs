@@ -0,0 +1,161 @@
+//! Desktop netplay: the link cable carried over one TCP connection
+//! (`--netplay-host PORT` on one machine, `--netplay-join HOST:PORT` on the
+//! other). The session frames and paces the traffic; this is only the pipe.
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+use rustyboi_session::ports::NetTransport;
+
+use crate::config::CleanConfig;
+
+/// Unsent bytes a partner may leave unread before it counts as gone. Records
+/// are a few bytes a frame, so this is minutes of a stalled connection.
+const MAX_PENDING: usize = 64 * 1024;
+
+pub(crate) struct TcpTransport {
+    stream: TcpStream,
+    /// Bytes owed to the partner that the socket hasn't taken yet.
+    pending: Vec<u8>,
+    /// Cleared once the partner hangs up or the socket fails; the link then
+    /// reads as a cable with nobody on the other end.
+    open: bool,
+}
+
+impl TcpTransport {
+    /// Wait on `port` (all interfaces) for one partner to join.
+    pub(crate) fn host(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let (stream, _) = listener.accept()?;
+        Self::new(stream)
+    }
+
+    pub(crate) fn join(addr: &str) -> io::Result<Self> {
+        Self::new(TcpStream::connect(addr)?)
+    }
+
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        // Records are a few bytes and latency-bound: no Nagle batching, and a
+        // read must never stall the frame.
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(true)?;
+        Ok(TcpTransport { stream, pending: Vec::new(), open: true })
+    }
+
+    fn hang_up(&mut self) {
+        if self.open {
+            self.open = false;
+            self.pending.clear();
+            eprintln!("Netplay partner disconnected");
+        }
+    }
+
+    /// Write as much of `pending` as the socket takes right now; the rest
+    /// waits for a later frame. A partner that stops reading (paused,
+    /// suspended, a stalled network) is hung up on once too much piles up.
+    fn flush(&mut self) {
+        while self.open && !self.pending.is_empty() {
+            match self.stream.write(&self.pending) {
+                Ok(0) => self.hang_up(),
+                Ok(n) => {
+                    self.pending.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.hang_up(),
+            }
+        }
+        if self.pending.len() > MAX_PENDING {
+            self.hang_up();
+        }
+    }
+}
+
+impl NetTransport for TcpTransport {
+    fn send(&mut self, bytes: &[u8]) {
+        if self.open {
+            self.pending.extend_from_slice(bytes);
+            self.flush();
+        }
+    }
+
+    fn recv(&mut self) -> Vec<u8> {
+        // Called every frame, so a backlog drains even when nothing new goes out.
+        self.flush();
+        let mut out = Vec::new();
+        let mut buf = [0u8; 512];
+        while self.open {
+            match self.stream.read(&mut buf) {
+                Ok(0) => self.hang_up(),
+                Ok(n) => out.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.hang_up(),
+            }
+        }
+        out
+    }
+}
+
+/// Host or join per the CLI, blocking until the partner is there. `None`
+/// when neither flag was given or the connection failed (reported).
+pub(crate) fn connect(config: &CleanConfig) -> Option<TcpTransport> {
+    let result = if let Some(port) = config.netplay_host {
+        println!("Waiting for a netplay partner on port {port}…");
+        TcpTransport::host(port)
+    } else if let Some(addr) = &config.netplay_join {
+        println!("Joining netplay at {addr}…");
+        TcpTransport::join(addr)
+    } else {
+        return None;
+    };
+    match result {
+        Ok(transport) => {
+            println!("Netplay connected: the link port now reaches your partner");
+            Some(transport)
+        }
+        Err(e) => {
+            eprintln!("Netplay failed: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_cross_a_loopback_connection_without_blocking() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut joined = TcpTransport::join(&addr.to_string()).unwrap();
+        let mut hosted = TcpTransport::new(listener.accept().unwrap().0).unwrap();
+
+        // Nothing sent yet: recv returns at once instead of waiting.
+        assert!(hosted.recv().is_empty());
+        joined.send(b"S\x42\x01");
+        let mut got = Vec::new();
+        while got.len() < 3 {
+            got.extend(hosted.recv());
+        }
+        assert_eq!(got, b"S\x42\x01");
+
+        drop(joined);
+        while hosted.open {
+            hosted.recv();
+        }
+    }
+
+    #[test]
+    fn a_partner_that_stops_reading_is_hung_up_on_instead_of_waited_for() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let mut joined = TcpTransport::join(&listener.local_addr().unwrap().to_string()).unwrap();
+        let _stalled = listener.accept().unwrap().0;
+
+        // Far more than the socket buffers hold: send queues and returns.
+        joined.send(&vec![0; 64 * 1024 * 1024]);
+        assert!(!joined.open);
+        assert!(joined.pending.is_empty());
+    }
+}
@@ -3,10 +3,10 @@
 //!
 //! The session reaches every host touchpoint through these adapters. This
 //! slice ships a real filesystem-backed [`FsStorage`] plus no-op [`NullRumble`]
-//! / [`NullWebcam`] stubs; real gilrs rumble, a webcam source, and a link-cable
-//! transport arrive in the follow-up. Each stub is a standalone type so
-//! swapping in a real implementation is a one-line change at the
-//! [`build_ports`] call site.
+//! / [`NullWebcam`] stubs; real gilrs rumble and a link-cable transport arrive
+//! in the follow-up, and the desktop's live webcam lives in `crate::webcam`.
+//! Each stub is a standalone type so swapping in a real implementation is a
+//! one-line change at the [`build_ports`] call site.
 
 use std::path::{Path, PathBuf};
 
@@ -108,7 +108,9 @@ impl Rumble for NullRumble {
 }
 
 /// No-op webcam adapter: never yields a frame, so the Game Boy Camera holds its
-/// last sensor image. A real capture source replaces this in the follow-up.
+/// last sensor image (the core's test pattern until one arrives).
+/// `--camera-image` swaps in a [`rustyboi_session::StillImage`] instead, and
+/// `--camera-device` a live webcam (`crate::webcam`).
 #[derive(Default)]
 pub(crate) struct NullWebcam;
 
@@ -20,7 +20,48 @@ pub fn run() -> Result<(), PlatformError> {
         }
 
         if let Some(rom) = config.rom.as_ref() {
-            let cartridge = cartridge::Cartridge::load(rom).expect("Failed to load ROM file");
+            if config.archive_entry.is_none()
+                && let Ok(entries) = std::fs::read(rom).and_then(|b| cartridge::Cartridge::archive_rom_entries(&b))
+                && entries.len() > 1
+            {
+                eprintln!(
+                    "{rom} holds {} ROMs ({}); loading the first — pick one with --archive-entry",
+                    entries.len(),
+                    entries.join(", ")
+                );
+            }
+            let default_save_dir = crate::ports::desktop_save_dir().join("saves");
+            // `--save-dir` and `--rtc-format` win for this run; otherwise the
+            // saved settings.
+            let saved = rustyboi_session::Config::load(&crate::ports::FsStorage::new(crate::ports::desktop_save_dir()));
+            let save_dir = config.save_dir.clone().or(saved.save_dir);
+            let mut opts = cartridge::LoadOptions {
+                archive_entry: config.archive_entry.as_deref(),
+                save_dir: save_dir.as_deref().map(std::path::Path::new),
+                rtc_format: config.rtc_format.unwrap_or(saved.rtc_format),
+            };
+            let cartridge = match cartridge::Cartridge::load_with(rom, opts) {
+                // A read-only ROM folder can't hold the `.sav`: fall back to the
+                // app data directory rather than refusing to run the game.
+                Err(e)
+                    if opts.save_dir.is_none()
+                        && matches!(
+                            e.kind(),
+                            std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::ReadOnlyFilesystem
+                        ) =>
+                {
+                    opts.save_dir = Some(&default_save_dir);
+                    eprintln!("{rom}: folder is not writable, keeping saves in {}", default_save_dir.display());
+                    cartridge::Cartridge::load_with(rom, opts)
+                }
+                loaded => loaded,
+            }
+            .expect("Failed to load ROM file");
+            if config.strict_header && !cartridge.header_checksum_valid() {
+                return Err(PlatformError::new(format!(
+                    "{rom}: header checksum mismatch (refusing to boot with --strict-header)"
+                )));
+            }
             // When resuming a savestate the cartridge's RUNTIME state (RAM/bank
             // regs/RTC) came back through serde but its ROM image (`rom_data`) was
             // skipped; re-attach only the ROM so that runtime state is preserved.
@@ -40,6 +81,22 @@ pub fn run() -> Result<(), PlatformError> {
             gb.skip_bios();
         }
 
+        if let Some(seconds) = config.bench {
+            return crate::headless::bench(&mut gb, seconds).map(drop);
+        }
+        if config.screenshot.is_some() || config.exit_on_break {
+            use crate::headless::{self, Stop};
+            gb.set_dmg_palette(config.palette);
+            gb.set_sgb_palette(config.sgb_palette);
+            let stop = headless::run(&mut gb, &headless::Job::from_config(&config))?;
+            // Drop the machine first so its battery save is flushed.
+            drop(gb);
+            if config.exit_on_break && let Stop::Breakpoint(_) = stop {
+                std::process::exit(headless::BREAK_EXIT_CODE);
+            }
+            return Ok(());
+        }
+
         display::run_with_gui(gb, &config)
     }
 
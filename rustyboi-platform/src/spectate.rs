@@ -0,0 +1,254 @@
+//! Spectator streaming (`--spectate PORT`): a small WebSocket server that
+//! fans the session's frames (and, with `--spectate-audio`, its audio) out to
+//! any number of browsers. Opening `http://HOST:PORT/` serves a viewer page
+//! that connects back on the same port. The session encodes the messages;
+//! this is only the server.
+//!
+//! Everything runs on the emulation thread without blocking: connections are
+//! accepted and served on every tick's poll as well as each time a message
+//! goes out. A viewer that can't keep up skips messages instead of stalling
+//! the game, and one that joins while the picture is still (or the game is
+//! paused) gets the last frame at once.
+//!
+//! The port is open to the network, so at most [`MAX_VIEWERS`] connections
+//! are kept, and one that hasn't sent its request within
+//! [`REQUEST_TIMEOUT`] is dropped rather than held open for good.
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use rustyboi_session::ports::Broadcast;
+
+use crate::websocket::{self, upgrade_response, websocket_key};
+
+/// The viewer page served at `/`.
+const VIEWER_PAGE: &str = include_str!("spectate.html");
+/// Longest HTTP request head read before the connection is dropped.
+const MAX_REQUEST: usize = 8 * 1024;
+/// Connections served at once; later ones are closed on arrival.
+const MAX_VIEWERS: usize = 32;
+/// How long a connection may take to send its whole request head.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Viewer {
+    stream: TcpStream,
+    /// The HTTP request read so far; `None` once upgraded to a WebSocket.
+    request: Option<Vec<u8>>,
+    /// When the connection was accepted, to time out a request that never
+    /// completes.
+    accepted: Instant,
+    /// Bytes owed to this viewer that the socket hasn't taken yet.
+    pending: Vec<u8>,
+    /// Hang up once `pending` drains (the viewer page was served).
+    close_when_flushed: bool,
+    gone: bool,
+}
+
+impl Viewer {
+    /// Write as much of `pending` as the socket takes right now.
+    fn flush(&mut self) {
+        while !self.gone && !self.pending.is_empty() {
+            match self.stream.write(&self.pending) {
+                Ok(0) => self.gone = true,
+                Ok(n) => {
+                    self.pending.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => self.gone = true,
+            }
+        }
+        if self.close_when_flushed && self.pending.is_empty() {
+            self.gone = true;
+        }
+    }
+
+    /// Read what arrived: the request head before the upgrade, afterwards
+    /// only whether the viewer hung up (its frames carry nothing we use).
+    fn read(&mut self) -> io::Result<()> {
+        let mut buf = [0u8; 1024];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => {
+                    self.gone = true;
+                    return Ok(());
+                }
+                Ok(n) => {
+                    if let Some(request) = self.request.as_mut() {
+                        request.extend_from_slice(&buf[..n]);
+                        if request.len() > MAX_REQUEST {
+                            self.gone = true;
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+pub(crate) struct SpectatorServer {
+    listener: TcpListener,
+    viewers: Vec<Viewer>,
+    /// The latest picture as a WebSocket message, for viewers that join
+    /// while the screen is still.
+    last_frame: Vec<u8>,
+}
+
+impl SpectatorServer {
+    /// Listen for viewers on `port` (all interfaces).
+    pub(crate) fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(SpectatorServer { listener, viewers: Vec::new(), last_frame: Vec::new() })
+    }
+
+    fn accept(&mut self) {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    // Dropping the stream closes it, so a flood doesn't pile
+                    // up in the listen backlog either.
+                    if self.viewers.len() >= MAX_VIEWERS || stream.set_nonblocking(true).is_err() {
+                        continue;
+                    }
+                    let _ = stream.set_nodelay(true);
+                    self.viewers.push(Viewer {
+                        stream,
+                        request: Some(Vec::new()),
+                        accepted: Instant::now(),
+                        pending: Vec::new(),
+                        close_when_flushed: false,
+                        gone: false,
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                // WouldBlock: nobody waiting. Anything else is one failed
+                // accept; the next message tries again.
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// Accept newcomers, answer finished requests, and flush what each viewer
+    /// is owed, dropping the ones that hung up or never sent a request.
+    fn serve(&mut self) {
+        self.accept();
+        let mut viewers = std::mem::take(&mut self.viewers);
+        for viewer in &mut viewers {
+            if viewer.read().is_err() {
+                viewer.gone = true;
+            }
+            if viewer.request.is_some() {
+                self.answer(viewer);
+                if viewer.request.is_some() && viewer.accepted.elapsed() > REQUEST_TIMEOUT {
+                    viewer.gone = true;
+                }
+            }
+            viewer.flush();
+        }
+        viewers.retain(|v| !v.gone);
+        self.viewers = viewers;
+    }
+
+    /// Answer a complete request head: upgrade a WebSocket request, serve
+    /// the viewer page for anything else.
+    fn answer(&self, viewer: &mut Viewer) {
+        let Some(request) = viewer.request.as_ref() else { return };
+        let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else { return };
+        let head = String::from_utf8_lossy(&request[..end]).into_owned();
+        match websocket_key(&head) {
+            Some(key) => {
+                viewer.pending = upgrade_response(key);
+                viewer.pending.extend_from_slice(&self.last_frame);
+                viewer.request = None;
+                if let Ok(addr) = viewer.stream.peer_addr() {
+                    println!("Spectator joined from {addr}");
+                }
+            }
+            None => {
+                viewer.pending = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{VIEWER_PAGE}",
+                    VIEWER_PAGE.len()
+                )
+                .into_bytes();
+                viewer.request = None;
+                viewer.close_when_flushed = true;
+            }
+        }
+    }
+}
+
+impl Broadcast for SpectatorServer {
+    fn send(&mut self, message: &[u8]) {
+        let frame = websocket::frame(websocket::BINARY, message);
+        for viewer in &mut self.viewers {
+            if viewer.request.is_none() && viewer.pending.is_empty() && !viewer.close_when_flushed {
+                viewer.pending = frame.clone();
+            }
+        }
+        if message.first() == Some(&b'F') {
+            self.last_frame = frame;
+        }
+        self.serve();
+    }
+
+    fn poll(&mut self) {
+        self.serve();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_viewer_joining_a_still_screen_gets_the_last_frame() {
+        let mut server = SpectatorServer::bind(0).unwrap();
+        let addr = server.listener.local_addr().unwrap();
+        server.send(b"Fpicture");
+
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.write_all(b"GET / HTTP/1.1\r\nUpgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n").unwrap();
+        let mut got = Vec::new();
+        let mut buf = [0u8; 256];
+        // Nothing more is sent: the picture is still and audio is off.
+        while !got.windows(8).any(|w| w == b"Fpicture") {
+            server.poll();
+            client.set_read_timeout(Some(std::time::Duration::from_millis(50))).unwrap();
+            if let Ok(n) = client.read(&mut buf) {
+                got.extend_from_slice(&buf[..n]);
+            }
+        }
+        let text = String::from_utf8_lossy(&got);
+        assert!(text.starts_with("HTTP/1.1 101"));
+        assert!(text.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+    }
+
+    #[test]
+    fn idle_connections_are_capped_and_time_out() {
+        let mut server = SpectatorServer::bind(0).unwrap();
+        let addr = server.listener.local_addr().unwrap();
+        let _clients: Vec<_> = (0..MAX_VIEWERS + 4).map(|_| TcpStream::connect(addr).unwrap()).collect();
+        for _ in 0..100 {
+            server.poll();
+            if server.viewers.len() == MAX_VIEWERS {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(server.viewers.len(), MAX_VIEWERS);
+
+        // None of them ever sends a request.
+        for viewer in &mut server.viewers {
+            viewer.accepted -= REQUEST_TIMEOUT * 2;
+        }
+        server.poll();
+        assert!(server.viewers.is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+//! Streams a `--record-video` capture to disk as it runs (native desktop).
+//!
+//! The session hands every video slot and audio chunk to a [`VideoSink`];
+//! the one here only forwards them over a channel, and a worker thread does
+//! everything slow. Frames go into `ffmpeg` on stdin as raw RGB (container
+//! and codecs follow the target's extension) while the audio streams into a
+//! temporary WAV, and once the capture ends the worker muxes the two, copying
+//! the already-encoded video. A `.zip` target gets the frames as PNGs plus
+//! the WAV, written entry by entry; so does `{path}.zip` when ffmpeg is
+//! missing, so the capture is never lost.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+use rustyboi_session::{encode_rgb_png, wav_header, VideoCapture, VideoSink};
+use tempfile::{NamedTempFile, TempPath};
+use zip::write::SimpleFileOptions;
+
+/// One piece of the capture on its way to the worker.
+enum Chunk {
+    Frame { width: u32, height: u32, rgb: Vec<u8>, count: usize },
+    Repeat(usize),
+    Audio(Vec<u8>),
+}
+
+/// The session's end of the stream. Dropping it (the capture stopped) lets
+/// the worker finish the file.
+pub(crate) struct ChannelSink(Sender<Chunk>);
+
+impl VideoSink for ChannelSink {
+    fn frame(&mut self, width: u32, height: u32, rgb: Vec<u8>, count: usize) {
+        let _ = self.0.send(Chunk::Frame { width, height, rgb, count });
+    }
+
+    fn repeat(&mut self, count: usize) {
+        let _ = self.0.send(Chunk::Repeat(count));
+    }
+
+    fn audio(&mut self, pcm: &[u8]) {
+        if !pcm.is_empty() {
+            let _ = self.0.send(Chunk::Audio(pcm.to_vec()));
+        }
+    }
+}
+
+/// The worker writing one capture.
+pub(crate) struct VideoWriter {
+    worker: JoinHandle<Result<(PathBuf, f64), String>>,
+}
+
+impl VideoWriter {
+    /// Start writing a capture at `sample_rate` to `path`. Hand the returned
+    /// sink to the session; [`Self::finish`] once the session has let go of
+    /// it.
+    pub(crate) fn start(path: &str, sample_rate: u32) -> Result<(Self, ChannelSink), String> {
+        let wav = tempfile::Builder::new()
+            .prefix("rustyboi-video-")
+            .suffix(".wav")
+            .tempfile()
+            .map_err(|e| format!("could not create the audio track ({e})"))?;
+        let worker = Worker::new(PathBuf::from(path), sample_rate, wav).map_err(|e| e.to_string())?;
+        let (tx, rx) = mpsc::channel();
+        let worker = std::thread::Builder::new()
+            .name("video-export".into())
+            .spawn(move || worker.run(rx))
+            .map_err(|e| format!("could not start the export thread ({e})"))?;
+        Ok((Self { worker }, ChannelSink(tx)))
+    }
+
+    /// Wait for the worker to write out what the stopped capture sent it,
+    /// returning the file written and its length in seconds.
+    pub(crate) fn finish(self) -> Result<(PathBuf, f64), String> {
+        self.worker.join().map_err(|_| "the export thread panicked".to_string())?
+    }
+}
+
+/// Where the frames are going, chosen when the first one arrives (its size
+/// is ffmpeg's input size).
+enum Video {
+    Ffmpeg { child: Child, stdin: Option<ChildStdin>, encoded: TempPath, last: Vec<u8> },
+    Zip { zip: Box<zip::ZipWriter<File>>, path: PathBuf, last: Vec<u8> },
+}
+
+struct Worker {
+    target: PathBuf,
+    sample_rate: u32,
+    wav: BufWriter<NamedTempFile>,
+    wav_len: u32,
+    video: Option<Video>,
+    slots: u64,
+    /// The first write that failed; the rest of the capture is dropped.
+    error: Option<String>,
+}
+
+impl Worker {
+    fn new(target: PathBuf, sample_rate: u32, wav: NamedTempFile) -> io::Result<Self> {
+        let mut wav = BufWriter::new(wav);
+        // A placeholder until the length is known.
+        wav.write_all(&wav_header(sample_rate, 0))?;
+        Ok(Self { target, sample_rate, wav, wav_len: 0, video: None, slots: 0, error: None })
+    }
+
+    fn run(mut self, rx: Receiver<Chunk>) -> Result<(PathBuf, f64), String> {
+        // Ends once the session drops its sink.
+        for chunk in rx {
+            if self.error.is_none()
+                && let Err(e) = self.take(chunk)
+            {
+                self.error = Some(e);
+            }
+        }
+        let (error, slots) = (self.error.take(), self.slots);
+        let written = self.close();
+        if let Some(e) = error {
+            return Err(e);
+        }
+        Ok((written?, VideoCapture::slots_to_seconds(slots)))
+    }
+
+    fn take(&mut self, chunk: Chunk) -> Result<(), String> {
+        match chunk {
+            Chunk::Audio(pcm) => {
+                self.wav_len = self.wav_len.saturating_add(pcm.len() as u32);
+                self.wav.write_all(&pcm).map_err(|e| format!("audio track: {e}"))
+            }
+            Chunk::Frame { width, height, rgb, count } => {
+                if self.video.is_none() {
+                    self.video = Some(self.open_video(width, height)?);
+                }
+                self.slots += count as u64;
+                match self.video.as_mut() {
+                    Some(Video::Ffmpeg { stdin, last, .. }) => {
+                        *last = rgb;
+                        write_raw(stdin, last, count)
+                    }
+                    Some(Video::Zip { zip, last, .. }) => {
+                        *last = encode_rgb_png(width, height, &rgb);
+                        write_pngs(zip, last, self.slots, count)
+                    }
+                    None => Ok(()),
+                }
+            }
+            Chunk::Repeat(count) => {
+                self.slots += count as u64;
+                match self.video.as_mut() {
+                    Some(Video::Ffmpeg { stdin, last, .. }) => write_raw(stdin, last, count),
+                    Some(Video::Zip { zip, last, .. }) => write_pngs(zip, last, self.slots, count),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Start ffmpeg encoding `width`x`height` frames into a temporary file
+    /// next to the target, or open the zip when the target is one or ffmpeg
+    /// will not start.
+    fn open_video(&self, width: u32, height: u32) -> Result<Video, String> {
+        let ext = self.target.extension().and_then(|e| e.to_str()).unwrap_or_default().to_owned();
+        if !ext.eq_ignore_ascii_case("zip") {
+            match spawn_encoder(&self.target, &ext, width, height) {
+                Ok((child, stdin, encoded)) => {
+                    return Ok(Video::Ffmpeg { child, stdin: Some(stdin), encoded, last: Vec::new() });
+                }
+                Err(e) => eprintln!("--record-video: {e}; writing the frames and audio as a zip instead"),
+            }
+        }
+        let path = if ext.eq_ignore_ascii_case("zip") {
+            self.target.clone()
+        } else {
+            PathBuf::from(format!("{}.zip", self.target.display()))
+        };
+        let file = File::create(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(Video::Zip { zip: Box::new(zip::ZipWriter::new(file)), path, last: Vec::new() })
+    }
+
+    /// Finish both tracks and combine them, returning the file written.
+    fn close(self) -> Result<PathBuf, String> {
+        let wav = finish_wav(self.wav, self.sample_rate, self.wav_len).map_err(|e| format!("audio track: {e}"))?;
+        match self.video {
+            None => Err("no frames were captured".into()),
+            Some(Video::Ffmpeg { mut child, stdin, encoded, .. }) => {
+                // Closing stdin lets the encoder finish.
+                drop(stdin);
+                let status = child.wait().map_err(|e| format!("ffmpeg: {e}"))?;
+                if !status.success() {
+                    return Err(format!("ffmpeg failed ({status})"));
+                }
+                mux(&encoded, wav.path(), &self.target)?;
+                Ok(self.target)
+            }
+            Some(Video::Zip { mut zip, path, .. }) => {
+                let stored = stored();
+                let note = VideoCapture::mux_note();
+                let mut audio = File::open(wav.path()).map_err(|e| format!("audio track: {e}"))?;
+                zip.start_file("audio.wav", stored)
+                    .and_then(|()| io::copy(&mut audio, &mut zip).map_err(Into::into))
+                    .and_then(|_| zip.start_file("ffmpeg.txt", stored))
+                    .and_then(|()| zip.write_all(note.as_bytes()).map_err(Into::into))
+                    .and_then(|()| zip.finish().map(drop))
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+                Ok(path)
+            }
+        }
+    }
+}
+
+/// Stamp the real length into the WAV header and hand back the file.
+fn finish_wav(wav: BufWriter<NamedTempFile>, sample_rate: u32, len: u32) -> io::Result<NamedTempFile> {
+    let mut file = wav.into_inner().map_err(io::IntoInnerError::into_error)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&wav_header(sample_rate, len))?;
+    file.flush()?;
+    Ok(file)
+}
+
+/// PNGs and PCM gain nothing from a second deflate.
+fn stored() -> SimpleFileOptions {
+    SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored)
+}
+
+/// Feed `rgb` to the encoder `count` times. A closed pipe means ffmpeg gave
+/// up; its exit status says why once the capture ends.
+fn write_raw(stdin: &mut Option<ChildStdin>, rgb: &[u8], count: usize) -> Result<(), String> {
+    let Some(pipe) = stdin.as_mut() else { return Ok(()) };
+    for _ in 0..count {
+        if let Err(e) = pipe.write_all(rgb) {
+            *stdin = None;
+            return Err(format!("ffmpeg stopped reading frames ({e})"));
+        }
+    }
+    Ok(())
+}
+
+/// Store `png` as the `count` frames ending at slot `end`.
+fn write_pngs(zip: &mut zip::ZipWriter<File>, png: &[u8], end: u64, count: usize) -> Result<(), String> {
+    for slot in end - count as u64..end {
+        zip.start_file(format!("frames/{slot:06}.png"), stored())
+            .map_err(|e| e.to_string())
+            .and_then(|()| zip.write_all(png).map_err(|e| e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Start ffmpeg encoding raw frames from stdin into a temporary file beside
+/// `target`, in `target`'s format, upscaled 4× nearest neighbour so players
+/// don't blur the pixels.
+fn spawn_encoder(target: &Path, ext: &str, width: u32, height: u32) -> Result<(Child, ChildStdin, TempPath), String> {
+    let dir = target.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let encoded = tempfile::Builder::new()
+        .prefix(".rustyboi-video-")
+        .suffix(&format!(".{ext}"))
+        .tempfile_in(dir)
+        .map_err(|e| format!("{}: {e}", dir.display()))?
+        .into_temp_path();
+    let mut child = Command::new("ffmpeg")
+        .args(encoder_args(width, height, &encoded.to_string_lossy()))
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run ffmpeg ({e})"))?;
+    let stdin = child.stdin.take().ok_or("ffmpeg has no stdin")?;
+    Ok((child, stdin, encoded))
+}
+
+/// ffmpeg arguments that encode `width`x`height` raw RGB frames from stdin
+/// into `output`.
+fn encoder_args(width: u32, height: u32, output: &str) -> Vec<String> {
+    let (num, den) = VideoCapture::FRAME_RATE;
+    let (rate, size) = (format!("{num}/{den}"), format!("{width}x{height}"));
+    [
+        "-y", "-loglevel", "error", "-f", "rawvideo", "-pix_fmt", "rgb24", "-s", &size, "-framerate", &rate, "-i",
+        "-", "-vf", "scale=iw*4:ih*4:flags=neighbor", output,
+    ]
+    .map(str::to_owned)
+    .to_vec()
+}
+
+/// Put the audio from `wav` beside the encoded video in `target`. The video
+/// is copied, not re-encoded, so this is quick however long the capture.
+fn mux(encoded: &Path, wav: &Path, target: &Path) -> Result<(), String> {
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(encoded)
+        .arg("-i")
+        .arg(wav)
+        .args(["-map", "0:v", "-map", "1:a", "-c:v", "copy"])
+        .arg(target)
+        .status()
+        .map_err(|e| format!("could not run ffmpeg ({e})"))?;
+    if status.success() { Ok(()) } else { Err(format!("ffmpeg could not add the audio ({status})")) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `.zip` target is written entry by entry on the worker: one PNG per
+    /// slot, repeats included, then the WAV with its real length.
+    #[test]
+    fn a_zip_target_streams_frames_and_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.zip");
+        let (writer, mut sink) = VideoWriter::start(path.to_str().unwrap(), 48_000).unwrap();
+        sink.audio(&[1, 2, 3, 4]);
+        sink.frame(2, 1, vec![0; 6], 2);
+        sink.repeat(1);
+        drop(sink);
+        let (written, seconds) = writer.finish().unwrap();
+        assert_eq!(written, path);
+        assert!(seconds > 0.0);
+
+        let mut zip = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let frames = zip.file_names().filter(|n| n.starts_with("frames/")).count();
+        assert_eq!(frames, 3);
+        let mut wav = Vec::new();
+        io::Read::read_to_end(&mut zip.by_name("audio.wav").unwrap(), &mut wav).unwrap();
+        assert_eq!(wav.len(), 48);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 4);
+    }
+
+    #[test]
+    fn the_encoder_reads_raw_rgb_at_the_frame_rate() {
+        let args = encoder_args(160, 144, "out.mp4");
+        let at = |flag: &str| args.iter().position(|a| a == flag).map(|i| args[i + 1].as_str());
+        assert_eq!(at("-f"), Some("rawvideo"));
+        assert_eq!(at("-pix_fmt"), Some("rgb24"));
+        assert_eq!(at("-s"), Some("160x144"));
+        assert_eq!(at("-framerate"), Some("4194304/70224"));
+        assert_eq!(args.last().map(String::as_str), Some("out.mp4"));
+    }
+}
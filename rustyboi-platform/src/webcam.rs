@@ -0,0 +1,104 @@
+//! Live Game Boy Camera input from a host webcam (`--camera-device`).
+//!
+//! Capture goes through an `ffmpeg` child process rather than a capture crate:
+//! ffmpeg already knows every platform's camera API (V4L2, AVFoundation,
+//! DirectShow), and it crops, scales and converts to 8-bit gray on its side, so
+//! what arrives on its stdout is exactly the sensor's 128x112 frames. A reader
+//! thread keeps only the newest one; the session picks it up once per
+//! emulated frame, and a frame with nothing new just holds the last image.
+
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use rustyboi_session::ports::{Webcam, WEBCAM_PIXELS};
+
+/// The ffmpeg input format for this platform's camera API.
+#[cfg(target_os = "linux")]
+const INPUT_FORMAT: &str = "v4l2";
+#[cfg(target_os = "macos")]
+const INPUT_FORMAT: &str = "avfoundation";
+#[cfg(target_os = "windows")]
+const INPUT_FORMAT: &str = "dshow";
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+const INPUT_FORMAT: &str = "v4l2";
+
+/// Center-crop to the sensor's 8:7 aspect, scale to 128x112, and emit raw
+/// 8-bit gray — the same framing `StillImage` applies to a picture.
+const FILTER: &str = "crop='min(iw,ih*8/7)':'min(ih,iw*7/8)',scale=128:112,format=gray";
+
+/// A [`Webcam`] fed by ffmpeg capturing `device`.
+pub(crate) struct FfmpegWebcam {
+    child: Child,
+    latest: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+impl FfmpegWebcam {
+    /// Start capturing `device`: a path such as `/dev/video0` on Linux, an
+    /// index or name on macOS (`0`), or `video=<name>` on Windows.
+    pub(crate) fn open(device: &str) -> Result<Self, String> {
+        let mut child = Command::new("ffmpeg")
+            .args(ffmpeg_args(device))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("could not run ffmpeg ({e})"))?;
+        let mut stdout = child.stdout.take().ok_or("ffmpeg has no stdout")?;
+        let latest = Arc::new(Mutex::new(None));
+        let slot = Arc::clone(&latest);
+        std::thread::Builder::new()
+            .name("webcam".into())
+            .spawn(move || {
+                let mut frame = vec![0; WEBCAM_PIXELS];
+                // Ends when ffmpeg exits (device gone, or killed on drop).
+                while stdout.read_exact(&mut frame).is_ok() {
+                    *slot.lock().unwrap() = Some(frame.clone());
+                }
+            })
+            .map_err(|e| format!("could not start the capture thread ({e})"))?;
+        Ok(Self { child, latest })
+    }
+}
+
+impl Webcam for FfmpegWebcam {
+    fn grab(&mut self) -> Option<Vec<u8>> {
+        self.latest.lock().unwrap().take()
+    }
+}
+
+impl Drop for FfmpegWebcam {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// ffmpeg's command line for capturing `device` as sensor frames on stdout.
+fn ffmpeg_args(device: &str) -> Vec<String> {
+    let mut args: Vec<String> = ["-hide_banner", "-loglevel", "error", "-f", INPUT_FORMAT]
+        .map(String::from)
+        .to_vec();
+    if cfg!(target_os = "macos") {
+        // AVFoundation refuses to open without an explicit rate it supports.
+        args.extend(["-framerate", "30"].map(String::from));
+    }
+    args.extend(["-i", device, "-vf", FILTER, "-f", "rawvideo", "-pix_fmt", "gray", "-"].map(String::from));
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_the_device_as_raw_sensor_frames() {
+        let args = ffmpeg_args("/dev/video0");
+        let at = |flag: &str| args.iter().position(|a| a == flag).map(|i| args[i + 1].as_str());
+        assert_eq!(at("-f"), Some(INPUT_FORMAT));
+        assert_eq!(at("-i"), Some("/dev/video0"));
+        assert_eq!(at("-pix_fmt"), Some("gray"));
+        assert!(at("-vf").unwrap().contains("scale=128:112"));
+        assert_eq!(args.last().map(String::as_str), Some("-"));
+    }
+}
@@ -0,0 +1,143 @@
+//! The slice of RFC 6455 the desktop servers need: the upgrade handshake,
+//! framing server messages, and unmasking client ones. Shared by spectator
+//! streaming and the debug server.
+
+use rustyboi_core_lib::checksum::sha1;
+
+/// RFC 6455 §1.3: appended to the client's key before hashing.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+pub(crate) const CONTINUATION: u8 = 0x0;
+pub(crate) const TEXT: u8 = 0x1;
+pub(crate) const BINARY: u8 = 0x2;
+pub(crate) const CLOSE: u8 = 0x8;
+pub(crate) const PING: u8 = 0x9;
+pub(crate) const PONG: u8 = 0xA;
+
+/// One frame read from a client.
+pub(crate) struct Frame {
+    pub fin: bool,
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+/// The value of the request head's first `name` header, matched without
+/// regard to case.
+pub(crate) fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().skip(1).find_map(|line| {
+        let (field, value) = line.split_once(':')?;
+        field.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+/// The `Sec-WebSocket-Key` header's value, when the request asks to upgrade.
+pub(crate) fn websocket_key(head: &str) -> Option<&str> {
+    header(head, "sec-websocket-key")
+}
+
+/// RFC 6455 §4.2.2: base64(SHA-1(key + GUID)).
+pub(crate) fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{WEBSOCKET_GUID}").as_bytes()))
+}
+
+/// The `101 Switching Protocols` response accepting `key`'s upgrade.
+pub(crate) fn upgrade_response(key: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key)
+    )
+    .into_bytes()
+}
+
+/// One unmasked, final frame of `opcode` carrying `payload`.
+pub(crate) fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        n @ 0..=125 => frame.push(n as u8),
+        n @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        n => {
+            frame.push(127);
+            frame.extend_from_slice(&(n as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Split the first complete frame off `buf`, unmasking it. `None` while the
+/// frame hasn't fully arrived.
+pub(crate) fn take_frame(buf: &mut Vec<u8>) -> Option<Frame> {
+    let (&b0, &b1) = (buf.first()?, buf.get(1)?);
+    let masked = b1 & 0x80 != 0;
+    let (len, mut at) = match b1 & 0x7F {
+        126 => (u64::from(u16::from_be_bytes(buf.get(2..4)?.try_into().ok()?)), 4),
+        127 => (u64::from_be_bytes(buf.get(2..10)?.try_into().ok()?), 10),
+        n => (u64::from(n), 2),
+    };
+    let mask: [u8; 4] = if masked {
+        at += 4;
+        buf.get(at - 4..at)?.try_into().ok()?
+    } else {
+        [0; 4]
+    };
+    let end = at.checked_add(usize::try_from(len).ok()?)?;
+    let payload = buf.get(at..end)?.iter().enumerate().map(|(i, &b)| b ^ mask[i % 4]).collect();
+    buf.drain(..end);
+    Some(Frame { fin: b0 & 0x80 != 0, opcode: b0 & 0x0F, payload })
+}
+
+/// Standard padded base64 (RFC 4648 §4).
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handshake_key_matches_the_rfc_example() {
+        let head = "GET / HTTP/1.1\r\nHost: x\r\nUpgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==";
+        let key = websocket_key(head).unwrap();
+        assert_eq!(accept_key(key), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        assert_eq!(websocket_key("GET / HTTP/1.1\r\nHost: x"), None);
+        assert_eq!(base64(b"ab"), "YWI=");
+    }
+
+    #[test]
+    fn frame_lengths_use_the_shortest_encoding() {
+        assert_eq!(frame(BINARY, b"F")[..2], [0x82, 1]);
+        assert_eq!(frame(TEXT, &[0; 300])[..4], [0x81, 126, 0x01, 0x2C]);
+        assert_eq!(frame(BINARY, &[0; 70_000])[..10], [0x82, 127, 0, 0, 0, 0, 0, 1, 0x11, 0x70]);
+    }
+
+    #[test]
+    fn masked_client_frames_unmask_once_complete() {
+        // RFC 6455 §5.7: a masked "Hello".
+        let whole = [0x81, 0x85, 0x37, 0xFA, 0x21, 0x3D, 0x7F, 0x9F, 0x4D, 0x51, 0x58];
+        let mut buf = whole[..6].to_vec();
+        assert!(take_frame(&mut buf).is_none(), "partial frame waits");
+        buf.extend_from_slice(&whole[6..]);
+        buf.push(0x89);
+        let frame = take_frame(&mut buf).unwrap();
+        assert!(frame.fin);
+        assert_eq!((frame.opcode, frame.payload.as_slice()), (TEXT, &b"Hello"[..]));
+        assert_eq!(buf, [0x89], "the next frame's bytes stay");
+    }
+}
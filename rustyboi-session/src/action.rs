@@ -15,7 +15,7 @@
 //! surfaces it everywhere.
 
 use crate::input::GbButton;
-use crate::input_config::InputConfig;
+use crate::input_config::{ConnectedPad, InputConfig};
 use serde::{Deserialize, Serialize};
 
 /// A file handed to the session by the frontend's picker. Desktop passes a path
@@ -47,8 +47,13 @@ pub enum LoadPurpose {
     /// A SNES-side Super Game Boy firmware dump (`sgb1.sfc` / `sgb2.sfc`), the
     /// only source of the SGB system border.
     SgbFirmware,
-    /// A recorded TAS movie (`.rbmovie`), replayed deterministically.
+    /// A recorded TAS movie (`.rbmovie`, or an imported `.vbm` / BGB input
+    /// log), replayed deterministically.
     Movie,
+    /// A user palette (hex colours or JASC-PAL), added and switched to.
+    Palette,
+    /// A controls profile written by Export Controls, replacing the input map.
+    InputProfile,
 }
 
 /// A single ROM discovered by the Android library scanner.
@@ -166,21 +171,26 @@ pub enum HardwareChoice {
 }
 
 /// The texture sampling filter used when the emulator frame is scaled up for
-/// display. `Nearest` (default) keeps the crisp pixel grid; `Linear` smooths.
-/// Presentation-only — a frontend renderer concern, never touches emulation.
-/// Serde-derived so it persists in [`Config`](crate::config::Config).
+/// display. `Nearest` (default) keeps the crisp pixel grid; `Linear` smooths;
+/// `SharpBilinear` scales by the largest whole factor with nearest and blends
+/// only the one-pixel seam between texels, so non-integer scales stay crisp
+/// without nearest's uneven pixel widths. Presentation-only — a frontend
+/// renderer concern, never touches emulation. Serde-derived so it persists in
+/// [`Config`](crate::config::Config).
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextureFilter {
     #[default]
     Nearest,
     Linear,
+    SharpBilinear,
 }
 
 /// An optional LCD post-process effect applied by the renderer. `Auto` (default)
 /// picks the most hardware-accurate look for the current model (see
 /// [`resolve`](LcdEffect::resolve)); `Off` is the plain scaled frame; `Grid`
 /// darkens a subpixel gap between source pixels (a dot-matrix LCD look);
-/// `Scanlines` darkens between source rows (a CRT/TV look). Presentation-only.
+/// `Scanlines` darkens between source rows (a CRT/TV look); `Crt` adds an
+/// RGB aperture-grille mask to deeper scanlines. Presentation-only.
 /// Serde-derived so it persists in [`Config`](crate::config::Config).
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LcdEffect {
@@ -189,6 +199,7 @@ pub enum LcdEffect {
     Off,
     Grid,
     Scanlines,
+    Crt,
 }
 
 impl LcdEffect {
@@ -209,10 +220,119 @@ impl LcdEffect {
     }
 }
 
+/// The olive-green shades the DMG dot-matrix filter shows mono games in,
+/// lightest first (SameBoy's measurement of a DMG-01 panel).
+pub const DOT_MATRIX_SHADES: [[u8; 3]; 4] =
+    [[0xC6, 0xDE, 0x8C], [0x84, 0xA5, 0x63], [0x39, 0x61, 0x39], [0x08, 0x18, 0x10]];
+
+/// The TAS input editor as the GUI shows it: a window of the timeline around
+/// the frame the machine is on. Built by the session each frame the editor is
+/// open.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputEditorView {
+    /// The frame the machine is on: the next one to run.
+    pub frame: u32,
+    /// Frames in the timeline.
+    pub len: u32,
+    /// The frame of `rows[0]`.
+    pub first: u32,
+    /// Buttons held on each frame from `first`, in [`GbButton::ALL`] order.
+    /// The last row may be one past the end of the timeline: the next new
+    /// frame.
+    pub rows: Vec<[bool; 8]>,
+    /// Frames in `rows` with a greenzone snapshot.
+    pub snapshots: Vec<u32>,
+}
+
+/// Where the debug windows were left, so a debugging setup survives restarts.
+/// Recorded by the GUI as windows open, close, move or get tiled; restored
+/// when it starts. Serde-derived so it persists in
+/// [`Config`](crate::config::Config).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DebugLayout {
+    /// Every debug window placed so far, by title.
+    pub windows: Vec<DebugWindowLayout>,
+}
+
+/// One debug window's saved place (see [`DebugLayout`]).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DebugWindowLayout {
+    pub title: String,
+    pub open: bool,
+    /// Top-left corner, in egui points.
+    pub pos: [f32; 2],
+}
+
+/// Per-channel mixer overrides for pulse 1, pulse 2, wave, and noise (in APU
+/// order): mute any of them, or solo some so only those play. Host-side only —
+/// the channels keep running and the core's channel tap still sees them.
+/// Serde-derived so it persists in [`Config`](crate::config::Config).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelMix {
+    pub muted: [bool; 4],
+    pub soloed: [bool; 4],
+}
+
+impl ChannelMix {
+    /// Display names, indexed like `muted`/`soloed`.
+    pub const NAMES: [&'static str; 4] = ["Pulse 1", "Pulse 2", "Wave", "Noise"];
+
+    /// Which channels reach the output: the soloed ones if any are soloed
+    /// (a solo overrides a mute on the same channel), otherwise every channel
+    /// that is not muted.
+    pub fn audible(self) -> [bool; 4] {
+        if self.soloed.contains(&true) {
+            self.soloed
+        } else {
+            self.muted.map(|m| !m)
+        }
+    }
+
+    /// Parse a comma-separated channel list (the desktop `--mute`/`--solo`
+    /// flags): `1`..`4`, or `pulse1`, `pulse2`, `wave`, `noise`. `None` on an
+    /// unknown name.
+    pub fn parse_channels(list: &str) -> Option<[bool; 4]> {
+        let mut set = [false; 4];
+        for name in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let ch = match name.to_ascii_lowercase().as_str() {
+                "1" | "pulse1" | "square1" => 0,
+                "2" | "pulse2" | "square2" => 1,
+                "3" | "wave" => 2,
+                "4" | "noise" => 3,
+                _ => return None,
+            };
+            set[ch] = true;
+        }
+        Some(set)
+    }
+}
+
 /// The integer upscale factors offered for saved Game Boy Printer output — the
 /// single list the Settings menu and the libretro option are built from.
 pub const PRINTER_SCALES: [u8; 6] = [1, 2, 3, 4, 5, 8];
 
+/// The integer upscale factors offered for Record GIF captures.
+pub const GIF_SCALES: [u8; 4] = [1, 2, 3, 4];
+
+/// The integer window scales offered under View → Window Scale and on the
+/// Ctrl+1…6 hotkeys, as multiples of the native 160×144 picture.
+pub const WINDOW_SCALES: [u8; 6] = [1, 2, 3, 4, 5, 6];
+
+/// The Record GIF length caps offered, in seconds of emulated time.
+pub const GIF_LENGTHS: [u32; 4] = [10, 30, 60, 120];
+
+/// The host audio output rates offered in the Settings menu, in Hz. The first
+/// is the default.
+pub const SAMPLE_RATES: [u32; 3] = [44_100, 48_000, 96_000];
+
+/// The audio device buffer sizes offered in the Settings menu, in sample pairs
+/// per callback. `0` leaves the size to the host (its default period).
+pub const AUDIO_BUFFER_SIZES: [u32; 5] = [0, 256, 512, 1024, 2048];
+
+/// The range of the Settings → Audio Latency slider: emulated frames of audio
+/// the pacing regulator keeps queued ahead of the device.
+pub const AUDIO_LATENCY_FRAMES: std::ops::RangeInclusive<u32> = 1..=8;
+
 /// The fast-forward speeds offered in the Settings menu, as
 /// `(fast_forward_factor, label)`. `0` is the uncapped sentinel (see
 /// [`Config::fast_forward_factor`](crate::config::Config::fast_forward_factor));
@@ -226,6 +346,87 @@ pub const FAST_FORWARD_SPEEDS: [(u32, &str); 6] = [
     (0, "Uncapped (muted)"),
 ];
 
+/// The emulation speeds offered in the Emulation menu and stepped through by
+/// the speed hotkeys, in percent of the machine's real-time rate.
+pub const EMULATION_SPEEDS: [u32; 10] = [25, 50, 75, 100, 125, 150, 200, 300, 400, 800];
+
+/// The next of [`EMULATION_SPEEDS`] above `percent` (`faster`) or below it,
+/// staying put at either end.
+pub fn step_emulation_speed(percent: u32, faster: bool) -> u32 {
+    if faster {
+        EMULATION_SPEEDS.into_iter().find(|&s| s > percent).unwrap_or(percent)
+    } else {
+        EMULATION_SPEEDS.into_iter().rev().find(|&s| s < percent).unwrap_or(percent)
+    }
+}
+
+/// Frame-skip: which emulated frames reach the display. Every frame is still
+/// emulated (timing, audio and recordings are untouched); a skipped one is
+/// just never packed and uploaded, so the screen holds the previous picture.
+/// `Fixed(n)` shows one frame in every `n + 1`; `Auto` skips only while the
+/// host is falling behind real time. Serde-derived so it persists in
+/// [`Config`](crate::config::Config).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameSkip {
+    #[default]
+    Off,
+    Fixed(u8),
+    Auto,
+}
+
+/// The frame-skip choices offered in the Settings menu.
+pub const FRAME_SKIPS: [(FrameSkip, &str); 5] = [
+    (FrameSkip::Off, "Off"),
+    (FrameSkip::Fixed(1), "Show 1 in 2"),
+    (FrameSkip::Fixed(2), "Show 1 in 3"),
+    (FrameSkip::Fixed(3), "Show 1 in 4"),
+    (FrameSkip::Auto, "Auto (when behind)"),
+];
+
+/// Named groups of the accuracy toggles (Settings → Accuracy). Choosing one
+/// sets the PPU renderer, the APU output high-pass and the DMG OAM corruption
+/// bug together; changing any of them afterwards leaves a custom mix that
+/// matches no preset. (Unmapped reads always float to 0xFF, so open bus has
+/// no cheaper mode to trade.)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccuracyPreset {
+    /// Everything hardware does: the per-dot renderer, the high-pass and the
+    /// OAM bug. The default.
+    Accuracy,
+    /// The scanline renderer, which is most of the cost; the free hardware
+    /// quirks stay on.
+    Balanced,
+    /// Every optional behavior off.
+    Speed,
+}
+
+impl AccuracyPreset {
+    pub const ALL: [AccuracyPreset; 3] = [AccuracyPreset::Accuracy, AccuracyPreset::Balanced, AccuracyPreset::Speed];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AccuracyPreset::Accuracy => "Accuracy",
+            AccuracyPreset::Balanced => "Balanced",
+            AccuracyPreset::Speed => "Speed",
+        }
+    }
+
+    /// Whether every ROM runs on the scanline renderer.
+    pub fn scanline_ppu(self) -> bool {
+        self != AccuracyPreset::Accuracy
+    }
+
+    /// Whether the APU's output high-pass filter runs.
+    pub fn audio_high_pass(self) -> bool {
+        self != AccuracyPreset::Speed
+    }
+
+    /// Whether the DMG OAM corruption bug is emulated.
+    pub fn oam_bug(self) -> bool {
+        self != AccuracyPreset::Speed
+    }
+}
+
 /// How the emulated frame is fit into its render region (letterboxing policy).
 /// `FitAspect` is the historical behavior (aspect-preserving contain);
 /// `IntegerAspect` snaps to the largest whole-number scale; `Stretch` fills the
@@ -239,6 +440,32 @@ pub enum ScalingMode {
     Stretch,
 }
 
+/// The shape the frame is shown at by the aspect-keeping [`ScalingMode`]s.
+/// `Square` (default) keeps square pixels: 10:9 for the Game Boy screen, 8:7
+/// for the SGB border composite. `TenByNine` shows either source at the
+/// handheld's 10:9; `FourByThree` at the 4:3 of the television an SGB drove.
+/// A non-square shape keeps integer scaling on the vertical axis only.
+/// Serde-derived so it persists in [`Config`](crate::config::Config).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AspectRatio {
+    #[default]
+    Square,
+    TenByNine,
+    FourByThree,
+}
+
+impl AspectRatio {
+    /// The displayed width of a `width`×`height` source at this shape, in
+    /// source-height units: `width` itself for square pixels.
+    pub fn display_width(self, width: f32, height: f32) -> f32 {
+        match self {
+            AspectRatio::Square => width,
+            AspectRatio::TenByNine => height * 10.0 / 9.0,
+            AspectRatio::FourByThree => height * 4.0 / 3.0,
+        }
+    }
+}
+
 /// Which rendering backend the desktop frontend asks wgpu for. `Auto` probes in
 /// preference order (the platform's native API — Vulkan, or Metal on Apple —
 /// then anything hardware, then the CPU software renderer); the explicit
@@ -320,6 +547,15 @@ impl GraphicsBackend {
     }
 }
 
+/// A short notice flashed over the game (slot saves, speed, volume); the UI
+/// fades it out on its own. `id` changes with every notice, so repeating the
+/// same text restarts the fade.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OsdMessage {
+    pub id: u32,
+    pub text: String,
+}
+
 /// A snapshot of session-owned state the menus render current selections from
 /// (checkmarks, radio dots, slot list). The UI never mutates the session
 /// directly; it reads this and emits [`UiAction`]s the session applies.
@@ -329,8 +565,15 @@ impl GraphicsBackend {
 /// in-process and never serialize it.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SessionUiState {
+    /// The ROM paths opened most recently, newest first (File → Recent;
+    /// desktop only, empty elsewhere).
+    pub recent_roms: Vec<String>,
+    /// The latest on-screen notice (see [`OsdMessage`]).
+    pub osd: Option<OsdMessage>,
     pub hardware: HardwareChoice,
     pub palette: DmgPaletteChoice,
+    /// The user palettes, in the order they were added.
+    pub custom_palettes: Vec<crate::palette::CustomPalette>,
     /// CGB colorization scheme for DMG games (Auto / a boot-ROM scheme).
     pub gbc_dmg_palette: GbcDmgPalette,
     /// Whether the DMG palette settings apply to the loaded game (false for a
@@ -359,8 +602,15 @@ pub struct SessionUiState {
     pub rewind_depth: usize,
     /// Master output volume, 0..=100 (scales the session's drained audio copy).
     pub volume: u8,
+    /// Whether the master output is muted (the volume is kept for unmute).
+    pub muted: bool,
     /// How the frame is letterboxed in the render region.
     pub scaling: ScalingMode,
+    /// The shape the frame is shown at.
+    pub aspect_ratio: AspectRatio,
+    /// Desktop window size as a multiple of the native picture, one of
+    /// [`WINDOW_SCALES`].
+    pub window_scale: u8,
     /// Requested rendering backend (desktop; applied at next launch).
     pub graphics_backend: GraphicsBackend,
     pub sgb_border: bool,
@@ -372,29 +622,113 @@ pub struct SessionUiState {
     /// Fast-forward speed setting (GB frames per presented frame; `0` = uncapped),
     /// so the settings menu can show the active choice.
     pub fast_forward_factor: u32,
+    /// Whether fast-forward plays silently.
+    pub fast_forward_muted: bool,
+    /// Whether fast-forward presents without vsync.
+    pub fast_forward_vsync_off: bool,
+    /// Which emulated frames reach the display.
+    pub frame_skip: FrameSkip,
+    /// Emulation speed in percent of real time (100 = normal).
+    pub emulation_speed: u32,
+    /// Whether the shown frame is emulated one frame ahead.
+    pub run_ahead: bool,
     /// Whether the on-screen touch overlay is shown.
     pub touch_controls: bool,
     /// Whether the on-screen FPS overlay is shown (top-right corner).
     pub show_fps: bool,
+    /// Whether the DMG dot-matrix filter is on.
+    pub dot_matrix: bool,
+    /// Whether the loaded ROM runs on the scanline renderer.
+    pub scanline_ppu: bool,
+    /// The accuracy preset the current toggles match (`None` = custom).
+    pub accuracy_preset: Option<AccuracyPreset>,
+    /// Whether cartridge real-time clocks follow the host wall clock.
+    pub rtc_host_clock: bool,
+    /// Whether the APU's output high-pass filter runs (off = raw mix).
+    pub audio_high_pass: bool,
+    /// Per-channel mute/solo state (Settings → Audio Channels).
+    pub channel_mix: ChannelMix,
+    /// Selected audio output rate in Hz (applied at next launch).
+    pub sample_rate: u32,
+    /// Audio device buffer in sample pairs, `0` = host default (applied at
+    /// next launch).
+    pub audio_buffer_size: u32,
+    /// Emulated frames of audio kept queued ahead of the device.
+    pub audio_latency_frames: u32,
+    /// Preferred audio output device by name, `None` = host default.
+    pub audio_device: Option<String>,
+    /// Output devices the platform can open (empty where there is no choice).
+    pub audio_devices: Vec<String>,
+    /// Folder battery saves go in, `None` = next to each ROM.
+    pub save_dir: Option<String>,
+    /// Whether audio recordings also write per-channel stems.
+    pub record_stems: bool,
+    /// Whether audio is being recorded (drives the Record/Stop Audio menu
+    /// label).
+    pub recording_audio: bool,
+    /// Upscale factor GIF captures are written at.
+    pub gif_scale: u8,
+    /// GIF capture length cap in seconds.
+    pub gif_max_seconds: u32,
+    /// Whether a GIF is being captured (drives the Record/Stop GIF menu
+    /// label).
+    pub recording_gif: bool,
+    /// Whether video is being captured (drives the Record/Stop Video menu
+    /// label).
+    pub recording_video: bool,
+    /// Whether the machine is snapshotted to the auto slot on exit.
+    pub auto_save_state: bool,
+    /// Whether emulation pauses while the window is unfocused.
+    pub pause_on_focus_loss: bool,
     /// Whether a Game Boy Printer is currently attached to the link port (drives
     /// the Connect/Disconnect menu label).
     pub printer_attached: bool,
+    /// Whether a second Game Boy is running on the link cable (drives its
+    /// window and the Emulation menu check).
+    pub link_partner: bool,
+    /// Whether live input drives that second Game Boy.
+    pub link_input_partner: bool,
+    /// Whether the link port is plugged into a netplay partner.
+    pub netplay: bool,
     /// Whether a TAS movie is currently being recorded (drives the
     /// Record/Stop-Recording menu label).
     pub recording: bool,
+    /// Whether recorded movies reference their starting state by hash.
+    pub movie_anchor_by_hash: bool,
     /// Whether a TAS movie is currently playing back (gates the Stop-Replay menu
     /// item; live input is suppressed while true).
     pub replaying: bool,
     /// Slot numbers that currently hold a saved state, ascending.
     pub slots: Vec<u32>,
+    /// `(slot, UNIX seconds)` for each filled slot, ascending, shown beside the
+    /// slots in the Emulation menu (0 = unknown).
+    pub slot_times: Vec<(u32, u64)>,
+    /// `(slot, RGB888 screenshot)` for each filled slot whose state carries
+    /// one, ascending; 40x36 (the core's `savestate::THUMBNAIL_*`), drawn in
+    /// the slot menus so slots can be told apart at a glance.
+    pub slot_thumbnails: Vec<(u32, Vec<u8>)>,
+    /// When the just-loaded ROM has an auto-save from an earlier run, its UNIX
+    /// seconds (0 = unknown): the UI offers to resume it until answered.
+    pub resume_offer: Option<u64>,
+    /// Whether the last state load can be undone (gates Undo Load State).
+    pub can_undo_load: bool,
+    /// The frame of the newest crash-recovery snapshot, offered on the crash
+    /// panel; `None` until the first one is taken.
+    pub recovery_snapshot: Option<u64>,
     /// Active cheat codes, in insertion order.
     pub cheats: Vec<String>,
     /// Cheats fetched from the libretro cheat DB awaiting the user's selection
     /// (empty until a `Get cheats` fetch completes; cleared when dismissed).
     pub fetched_cheats: Vec<crate::cheat_db::FetchedCheat>,
+    /// ROM members of a loaded multi-ROM archive awaiting the user's pick (empty
+    /// unless a zip/7z bundle was opened; cleared on a choice or dismiss).
+    pub archive_choices: Vec<String>,
     /// Whether the inserted cartridge has battery-backed SRAM (gates the
     /// Import/Export Battery Save menu items).
     pub has_battery: bool,
+    /// Size in bytes of the cartridge's battery SRAM (0 without one), shown by
+    /// the battery save manager.
+    pub battery_size: usize,
     /// Whether the inserted cartridge has a real-time clock (gates the
     /// Import/Export RTC menu items).
     pub has_rtc: bool,
@@ -406,13 +740,24 @@ pub struct SessionUiState {
     /// The live rebindable input map (GB-button bindings + chord hotkeys) the
     /// keybind editor reads/writes. Mirrors [`Config::input`](crate::config::Config).
     pub input: InputConfig,
+    /// Controllers the host can identify, for per-controller bindings in the
+    /// keybind editor (desktop only, empty elsewhere).
+    pub connected_pads: Vec<ConnectedPad>,
+    /// Where the debug windows were left. Mirrors
+    /// [`Config::debug_layout`](crate::config::Config).
+    pub debug_layout: DebugLayout,
+    /// The TAS input editor, while it is open.
+    pub input_editor: Option<InputEditorView>,
 }
 
 impl Default for SessionUiState {
     fn default() -> Self {
         SessionUiState {
+            recent_roms: Vec::new(),
+            osd: None,
             hardware: HardwareChoice::Cgb,
             palette: DmgPaletteChoice::Green,
+            custom_palettes: Vec::new(),
             gbc_dmg_palette: GbcDmgPalette::Auto,
             dmg_palette_active: true,
             sgb_palette: SgbPaletteChoice::Auto,
@@ -427,25 +772,67 @@ impl Default for SessionUiState {
             rewind_interval_frames: 6,
             rewind_depth: 90,
             volume: 50,
+            muted: false,
             scaling: ScalingMode::FitAspect,
+            aspect_ratio: AspectRatio::Square,
+            window_scale: 5,
             graphics_backend: GraphicsBackend::Auto,
             sgb_border: true,
             paused: false,
             fast_forward: false,
             fast_forward_factor: 4,
+            fast_forward_muted: false,
+            fast_forward_vsync_off: false,
+            frame_skip: FrameSkip::Off,
+            emulation_speed: 100,
+            run_ahead: false,
             touch_controls: cfg!(mobile),
             show_fps: false,
+            dot_matrix: false,
+            scanline_ppu: false,
+            accuracy_preset: Some(AccuracyPreset::Accuracy),
+            rtc_host_clock: false,
+            audio_high_pass: true,
+            channel_mix: ChannelMix::default(),
+            sample_rate: SAMPLE_RATES[0],
+            audio_buffer_size: 0,
+            audio_latency_frames: 2,
+            audio_device: None,
+            audio_devices: Vec::new(),
+            save_dir: None,
+            record_stems: false,
+            recording_audio: false,
+            gif_scale: 2,
+            gif_max_seconds: 30,
+            recording_gif: false,
+            recording_video: false,
+            auto_save_state: false,
+            pause_on_focus_loss: false,
             printer_attached: false,
+            link_partner: false,
+            link_input_partner: false,
+            netplay: false,
             recording: false,
+            movie_anchor_by_hash: false,
             replaying: false,
             slots: Vec::new(),
+            slot_times: Vec::new(),
+            slot_thumbnails: Vec::new(),
+            resume_offer: None,
+            can_undo_load: false,
+            recovery_snapshot: None,
             cheats: Vec::new(),
             fetched_cheats: Vec::new(),
+            archive_choices: Vec::new(),
             has_battery: false,
+            battery_size: 0,
             has_rtc: false,
             has_rom: false,
             game_name: None,
             input: InputConfig::default(),
+            connected_pads: Vec::new(),
+            debug_layout: DebugLayout::default(),
+            input_editor: None,
         }
     }
 }
@@ -467,6 +854,8 @@ pub enum UiAction {
     LoadState(FileData),
     /// Load a ROM from a picked file.
     LoadRom(FileData),
+    /// Forget the File → Recent list.
+    ClearRecentRoms,
     /// Import a savestate from a picked file (explicit File → Import, distinct
     /// from the numbered/quick slots).
     ImportState(FileData),
@@ -474,29 +863,81 @@ pub enum UiAction {
     /// Export). Unlike [`SaveState`](Self::SaveState) this carries no path, so it
     /// works uniformly on web (browser download) as well as desktop/Android.
     ExportState,
+    /// Export the current machine state as versioned JSON (File → Export),
+    /// for reading or diffing; see [`crate::state_json`].
+    ExportStateJson,
     /// Import a battery `.sav` image into the current cartridge.
     ImportBatterySave(FileData),
     /// Export the current cartridge's battery SRAM as a `.sav` file.
     ExportBatterySave,
+    /// Write pending battery SRAM to the save file now rather than at the
+    /// next periodic flush.
+    FlushSaves,
+    /// Blank the current cartridge's battery SRAM (and its save file).
+    EraseBatterySave,
     /// Import an `.rtc` clock blob into the current cartridge.
     ImportRtc(FileData),
     /// Apply an IPS/UPS/BPS ROM patch (romhack/translation) to the loaded ROM.
     ApplyPatch(FileData),
     /// Export the current cartridge's RTC state as a `.rtc` file.
     ExportRtc,
+    /// Put the presented frame (with the SGB border when shown) on the system
+    /// clipboard, ready to paste into a chat or bug report.
+    CopyScreenshot,
     /// Toggle pause / resume.
     TogglePause,
     /// Start recording a TAS movie from the current machine state, or stop the
     /// in-progress recording and hand the finished movie back as a saveable
     /// `.rbmovie` file (File → Export). One toggle drives both.
     ToggleRecording,
+    /// Whether saved movies name their starting savestate by SHA-256 instead
+    /// of embedding it; the state is kept in storage, and playback refuses
+    /// to start unless a stored state matches (persisted).
+    SetMovieAnchorByHash(bool),
+    /// Open the TAS input editor at the current frame (pausing), or close it.
+    /// While open, every frame run takes its input from the editor's timeline.
+    ToggleInputEditor,
+    /// Hold (`true`) or release a button on a frame of the input editor's
+    /// timeline. A frame that already ran is re-simulated from the nearest
+    /// greenzone snapshot.
+    SetTasInput(u32, GbButton, bool),
+    /// Move the machine to a frame of the input editor's timeline.
+    SeekTasFrame(u32),
+    /// Hand the input editor's timeline back as a saveable `.rbmovie`.
+    ExportTasMovie,
+    /// Start capturing the audio output to a WAV, or stop and hand the file
+    /// back for saving (with per-channel stems when [`SetRecordStems`] is on).
+    ///
+    /// [`SetRecordStems`]: UiAction::SetRecordStems
+    ToggleAudioRecording,
+    /// Whether audio recordings also write one WAV per channel (pulse 1,
+    /// pulse 2, wave, noise), tapped before the mixer (persisted).
+    SetRecordStems(bool),
+    /// Start capturing the presented frames to an animated GIF, or stop and
+    /// hand the file back for saving.
+    ToggleGifRecording,
+    /// Upscale factor for GIF captures, one of [`GIF_SCALES`] (persisted).
+    SetGifScale(u8),
+    /// Length cap for GIF captures in seconds, one of [`GIF_LENGTHS`]
+    /// (persisted).
+    SetGifMaxSeconds(u32),
+    /// Start capturing video (the presented frames plus the heard audio,
+    /// kept in sync on emulated time), or stop and hand it back for saving
+    /// as a zip of PNG frames, a WAV, and the ffmpeg command that muxes them.
+    ToggleVideoRecording,
     /// Load a recorded TAS movie from a picked file and begin deterministic
-    /// playback.
+    /// playback. VBA `.vbm` movies and BGB input logs are converted on load.
     LoadMovie(FileData),
     /// Stop movie playback, resuming live input.
     StopReplay,
     /// Plug/unplug a Game Boy Printer on the link port.
     TogglePrinter,
+    /// Power a second Game Boy on the other end of the link cable, or unplug it.
+    ToggleLinkPartner,
+    /// Route live input to the second Game Boy (`true`) or the main one.
+    SetLinkInputPartner(bool),
+    /// Hang up the netplay link (hosting/joining is a platform start-up option).
+    StopNetplay,
     /// Power-cycle the current console.
     Restart,
     /// Clear the crash overlay, keeping CPU state for debugging.
@@ -519,6 +960,8 @@ pub enum UiAction {
     Quicksave,
     /// Quickload from the reserved quick slot.
     Quickload,
+    /// Put back the machine the last state load replaced.
+    UndoLoadState,
     /// Toggle fast-forward / turbo on and off.
     ToggleFastForward,
     /// Advance exactly one frame, then pause.
@@ -529,10 +972,25 @@ pub enum UiAction {
     ToggleTouchControls,
     /// Toggle the on-screen FPS overlay.
     ToggleShowFps,
+    /// Toggle the DMG dot-matrix filter: mono games in the DMG's olive
+    /// green, and each pixel drawn as a dot with the panel showing between
+    /// them at integer scales. Stacks on top of the LCD effect.
+    ToggleDotMatrix,
+    /// Toggle the scanline renderer for the loaded ROM: whole lines drawn at
+    /// the end of mode 3 instead of dot by dot. Faster, but mid-line raster
+    /// effects are lost. Remembered per ROM.
+    ToggleScanlinePpu,
     /// Change the emulated hardware model (rebuilds the machine).
     SetHardware(HardwareChoice),
     /// Change the DMG presentation palette.
     SetPalette(DmgPaletteChoice),
+    /// Add a user palette from a picked file (hex colours or JASC-PAL) and
+    /// switch to it.
+    LoadPaletteFile(FileData),
+    /// Add a user palette from typed hex colours and switch to it.
+    AddPalette(String),
+    /// Forget user palette `n` (an index into `SessionUiState::custom_palettes`).
+    RemovePalette(usize),
     /// Change the CGB colorization for DMG games (Auto / a boot-ROM scheme).
     SetGbcDmgPalette(GbcDmgPalette),
     /// Change the SGB colorization for DMG games (Auto / a system palette /
@@ -558,25 +1016,85 @@ pub enum UiAction {
     LoadSgbFirmware(FileData),
     /// Enable/disable rewind capture.
     SetRewindEnabled(bool),
+    /// Drive cartridge real-time clocks from the host wall clock (true) or
+    /// from emulated cycles (false).
+    SetRtcHostClock(bool),
+    /// Run the APU's output high-pass filter (true, as on hardware) or hand
+    /// out the raw mix (false).
+    SetAudioHighPass(bool),
+    /// Apply an accuracy preset's renderer, high-pass and OAM-bug toggles.
+    SetAccuracyPreset(AccuracyPreset),
+    /// Replace the per-channel mute/solo state.
+    SetChannelMix(ChannelMix),
+    /// Choose the audio output rate in Hz, one of [`SAMPLE_RATES`] (persisted;
+    /// applied at next launch, when the audio device is opened).
+    SetSampleRate(u32),
+    /// Choose the audio device buffer size in sample pairs, one of
+    /// [`AUDIO_BUFFER_SIZES`] (persisted; applied at next launch).
+    SetAudioBufferSize(u32),
+    /// Set how many emulated frames of audio are kept queued ahead of the
+    /// device, within [`AUDIO_LATENCY_FRAMES`]. Takes effect live.
+    SetAudioLatencyFrames(u32),
+    /// Choose the audio output device by name (`None` = host default;
+    /// persisted). The platform falls back to the default while it is
+    /// unplugged and returns to it on replug.
+    SetAudioDevice(Option<String>),
+    /// Choose the folder battery saves go in (`None` = next to each ROM;
+    /// persisted). Applies from the next ROM load on hosts that keep saves
+    /// as files.
+    SetSaveDir(Option<String>),
+    /// Snapshot the machine to the auto slot on exit and offer to resume it
+    /// the next time the same ROM loads.
+    SetAutoSaveState(bool),
+    /// Pause (and so silence) emulation while the window is unfocused,
+    /// resuming on focus unless the user paused in between.
+    SetPauseOnFocusLoss(bool),
     /// Set the rewind snapshot interval (frames between captures).
     SetRewindInterval(u32),
     /// Set how many rewind snapshots are retained.
     SetRewindDepth(usize),
     /// Set the master output volume (0..=100).
     SetVolume(u8),
+    /// Mute or unmute the master output, keeping the volume level (persisted).
+    ToggleMute,
     /// Set the fast-forward speed (GB frames per presented frame; `0` = uncapped,
     /// otherwise the literal multiplier: 2, 4, 6, 8, 10, …).
     SetFastForwardFactor(u32),
+    /// Drop the audio while fast-forwarding instead of resampling it (persisted).
+    SetFastForwardMuted(bool),
+    /// Present without vsync while fast-forwarding so it isn't held to the
+    /// display refresh (persisted; desktop only).
+    SetFastForwardVsyncOff(bool),
+    /// Choose which emulated frames reach the display (persisted).
+    SetFrameSkip(FrameSkip),
+    /// Set the emulation speed in percent of real time (persisted).
+    SetEmulationSpeed(u32),
+    /// Show the frame one ahead of the live machine to cut a frame of input
+    /// lag (persisted).
+    SetRunAhead(bool),
     /// Set how the frame is letterboxed in the render region.
     SetScalingMode(ScalingMode),
+    /// Set the shape the frame is shown at (persisted).
+    SetAspectRatio(AspectRatio),
     /// Choose the rendering backend (persisted; applied at next launch).
     SetGraphicsBackend(GraphicsBackend),
+    /// Resize the desktop window to this multiple of the native picture, one
+    /// of [`WINDOW_SCALES`] (persisted; the web canvas ignores it).
+    SetWindowScale(u8),
     /// Toggle host fullscreen (platform hook: desktop window / web canvas;
     /// Android is already fullscreen). Transient — not persisted config.
     ToggleFullscreen,
     /// Replace the rebindable input map (GB-button bindings + chord hotkeys).
     /// Emitted by the keybind editor; persisted to config in `Session::apply`.
     SetInputConfig(InputConfig),
+    /// Hand the whole input map back as a portable profile file for saving.
+    ExportInputProfile,
+    /// Replace the input map with a profile file picked by the user
+    /// (persisted).
+    ImportInputProfile(FileData),
+    /// Record where the debug windows are. Emitted by the GUI when they
+    /// open, close or move; persisted to config.
+    SetDebugLayout(DebugLayout),
     /// Add a Game Genie / GameShark cheat code (session-lifetime).
     AddCheat(String),
     /// Add several cheat codes at once (the user's selection from the fetched
@@ -589,6 +1107,18 @@ pub enum UiAction {
     GetCheats,
     /// Discard the fetched-cheat list (the user closed the picker).
     ClearFetchedCheats,
+    /// Load the named member of the held multi-ROM archive (the user's pick
+    /// from [`SessionUiState::archive_choices`]).
+    ChooseArchiveEntry(String),
+    /// Discard the held multi-ROM archive (the user closed the chooser).
+    DismissArchiveChoice,
+    /// Resume from the auto slot (the user accepted the resume offer).
+    ResumeAutoSave,
+    /// Decline the resume offer and keep the fresh boot.
+    DismissResume,
+    /// Fall back to the newest crash-recovery snapshot (the crash panel's
+    /// offer, see [`SessionUiState::recovery_snapshot`]).
+    RestoreRecoverySnapshot,
     /// User asked to pick a new ROM library root (SAF tree).
     #[cfg(target_os = "android")]
     OpenRomTree,
@@ -616,18 +1146,37 @@ impl UiAction {
             UiAction::SaveState(_) => ActionKind::SaveState,
             UiAction::LoadState(_) => ActionKind::LoadState,
             UiAction::LoadRom(_) => ActionKind::LoadRom,
+            UiAction::ClearRecentRoms => ActionKind::ClearRecentRoms,
             UiAction::ImportState(_) => ActionKind::ImportState,
             UiAction::ExportState => ActionKind::ExportState,
+            UiAction::ExportStateJson => ActionKind::ExportStateJson,
             UiAction::ImportBatterySave(_) => ActionKind::ImportBatterySave,
             UiAction::ExportBatterySave => ActionKind::ExportBatterySave,
+            UiAction::FlushSaves => ActionKind::FlushSaves,
+            UiAction::EraseBatterySave => ActionKind::EraseBatterySave,
             UiAction::ImportRtc(_) => ActionKind::ImportRtc,
             UiAction::ApplyPatch(_) => ActionKind::ApplyPatch,
             UiAction::ExportRtc => ActionKind::ExportRtc,
+            UiAction::CopyScreenshot => ActionKind::CopyScreenshot,
             UiAction::TogglePause => ActionKind::TogglePause,
             UiAction::ToggleRecording => ActionKind::ToggleRecording,
+            UiAction::SetMovieAnchorByHash(_) => ActionKind::SetMovieAnchorByHash,
+            UiAction::ToggleInputEditor => ActionKind::ToggleInputEditor,
+            UiAction::SetTasInput(..) => ActionKind::SetTasInput,
+            UiAction::SeekTasFrame(_) => ActionKind::SeekTasFrame,
+            UiAction::ExportTasMovie => ActionKind::ExportTasMovie,
+            UiAction::ToggleAudioRecording => ActionKind::ToggleAudioRecording,
+            UiAction::SetRecordStems(_) => ActionKind::SetRecordStems,
+            UiAction::ToggleGifRecording => ActionKind::ToggleGifRecording,
+            UiAction::SetGifScale(_) => ActionKind::SetGifScale,
+            UiAction::SetGifMaxSeconds(_) => ActionKind::SetGifMaxSeconds,
+            UiAction::ToggleVideoRecording => ActionKind::ToggleVideoRecording,
             UiAction::LoadMovie(_) => ActionKind::LoadMovie,
             UiAction::StopReplay => ActionKind::StopReplay,
             UiAction::TogglePrinter => ActionKind::TogglePrinter,
+            UiAction::ToggleLinkPartner => ActionKind::ToggleLinkPartner,
+            UiAction::SetLinkInputPartner(_) => ActionKind::SetLinkInputPartner,
+            UiAction::StopNetplay => ActionKind::StopNetplay,
             UiAction::Restart => ActionKind::Restart,
             UiAction::ClearError => ActionKind::ClearError,
             UiAction::StepCycles(_) => ActionKind::StepCycles,
@@ -639,13 +1188,19 @@ impl UiAction {
             UiAction::LoadSlot(_) => ActionKind::LoadSlot,
             UiAction::Quicksave => ActionKind::Quicksave,
             UiAction::Quickload => ActionKind::Quickload,
+            UiAction::UndoLoadState => ActionKind::UndoLoadState,
             UiAction::ToggleFastForward => ActionKind::ToggleFastForward,
             UiAction::FrameAdvance => ActionKind::FrameAdvance,
             UiAction::ToggleSgbBorder => ActionKind::ToggleSgbBorder,
             UiAction::ToggleTouchControls => ActionKind::ToggleTouchControls,
             UiAction::ToggleShowFps => ActionKind::ToggleShowFps,
+            UiAction::ToggleDotMatrix => ActionKind::ToggleDotMatrix,
+            UiAction::ToggleScanlinePpu => ActionKind::ToggleScanlinePpu,
             UiAction::SetHardware(_) => ActionKind::SetHardware,
             UiAction::SetPalette(_) => ActionKind::SetPalette,
+            UiAction::LoadPaletteFile(_) => ActionKind::LoadPaletteFile,
+            UiAction::AddPalette(_) => ActionKind::AddPalette,
+            UiAction::RemovePalette(_) => ActionKind::RemovePalette,
             UiAction::SetGbcDmgPalette(_) => ActionKind::SetGbcDmgPalette,
             UiAction::SetSgbPalette(_) => ActionKind::SetSgbPalette,
             UiAction::SetColorCorrection(_) => ActionKind::SetColorCorrection,
@@ -657,19 +1212,46 @@ impl UiAction {
             UiAction::LoadBootRom(_) => ActionKind::LoadBootRom,
             UiAction::LoadSgbFirmware(_) => ActionKind::LoadSgbFirmware,
             UiAction::SetRewindEnabled(_) => ActionKind::SetRewindEnabled,
+            UiAction::SetRtcHostClock(_) => ActionKind::SetRtcHostClock,
+            UiAction::SetAudioHighPass(_) => ActionKind::SetAudioHighPass,
+            UiAction::SetAccuracyPreset(_) => ActionKind::SetAccuracyPreset,
+            UiAction::SetChannelMix(_) => ActionKind::SetChannelMix,
+            UiAction::SetSampleRate(_) => ActionKind::SetSampleRate,
+            UiAction::SetAudioBufferSize(_) => ActionKind::SetAudioBufferSize,
+            UiAction::SetAudioLatencyFrames(_) => ActionKind::SetAudioLatencyFrames,
+            UiAction::SetAudioDevice(_) => ActionKind::SetAudioDevice,
+            UiAction::SetSaveDir(_) => ActionKind::SetSaveDir,
+            UiAction::SetAutoSaveState(_) => ActionKind::SetAutoSaveState,
+            UiAction::SetPauseOnFocusLoss(_) => ActionKind::SetPauseOnFocusLoss,
             UiAction::SetRewindInterval(_) => ActionKind::SetRewindInterval,
             UiAction::SetRewindDepth(_) => ActionKind::SetRewindDepth,
             UiAction::SetVolume(_) => ActionKind::SetVolume,
+            UiAction::ToggleMute => ActionKind::ToggleMute,
             UiAction::SetFastForwardFactor(_) => ActionKind::SetFastForwardFactor,
+            UiAction::SetFastForwardMuted(_) => ActionKind::SetFastForwardMuted,
+            UiAction::SetFastForwardVsyncOff(_) => ActionKind::SetFastForwardVsyncOff,
+            UiAction::SetFrameSkip(_) => ActionKind::SetFrameSkip,
+            UiAction::SetEmulationSpeed(_) => ActionKind::SetEmulationSpeed,
+            UiAction::SetRunAhead(_) => ActionKind::SetRunAhead,
             UiAction::SetScalingMode(_) => ActionKind::SetScalingMode,
+            UiAction::SetAspectRatio(_) => ActionKind::SetAspectRatio,
             UiAction::SetGraphicsBackend(_) => ActionKind::SetGraphicsBackend,
+            UiAction::SetWindowScale(_) => ActionKind::SetWindowScale,
             UiAction::ToggleFullscreen => ActionKind::ToggleFullscreen,
             UiAction::SetInputConfig(_) => ActionKind::SetInputConfig,
+            UiAction::ExportInputProfile => ActionKind::ExportInputProfile,
+            UiAction::ImportInputProfile(_) => ActionKind::ImportInputProfile,
+            UiAction::SetDebugLayout(_) => ActionKind::SetDebugLayout,
             UiAction::AddCheat(_) => ActionKind::AddCheat,
             UiAction::AddCheats(_) => ActionKind::AddCheats,
             UiAction::RemoveCheat(_) => ActionKind::RemoveCheat,
             UiAction::GetCheats => ActionKind::GetCheats,
             UiAction::ClearFetchedCheats => ActionKind::ClearFetchedCheats,
+            UiAction::ChooseArchiveEntry(_) => ActionKind::ChooseArchiveEntry,
+            UiAction::DismissArchiveChoice => ActionKind::DismissArchiveChoice,
+            UiAction::ResumeAutoSave => ActionKind::ResumeAutoSave,
+            UiAction::DismissResume => ActionKind::DismissResume,
+            UiAction::RestoreRecoverySnapshot => ActionKind::RestoreRecoverySnapshot,
             #[cfg(target_os = "android")]
             UiAction::OpenRomTree => ActionKind::OpenRomTree,
             #[cfg(target_os = "android")]
@@ -693,18 +1275,37 @@ pub enum ActionKind {
     SaveState,
     LoadState,
     LoadRom,
+    ClearRecentRoms,
     ImportState,
     ExportState,
+    ExportStateJson,
     ImportBatterySave,
     ExportBatterySave,
+    FlushSaves,
+    EraseBatterySave,
     ImportRtc,
     ExportRtc,
     ApplyPatch,
+    CopyScreenshot,
     TogglePause,
     ToggleRecording,
+    SetMovieAnchorByHash,
+    ToggleInputEditor,
+    SetTasInput,
+    SeekTasFrame,
+    ExportTasMovie,
+    ToggleAudioRecording,
+    SetRecordStems,
+    ToggleGifRecording,
+    SetGifScale,
+    SetGifMaxSeconds,
+    ToggleVideoRecording,
     LoadMovie,
     StopReplay,
     TogglePrinter,
+    ToggleLinkPartner,
+    SetLinkInputPartner,
+    StopNetplay,
     Restart,
     ClearError,
     StepCycles,
@@ -716,13 +1317,19 @@ pub enum ActionKind {
     LoadSlot,
     Quicksave,
     Quickload,
+    UndoLoadState,
     ToggleFastForward,
     FrameAdvance,
     ToggleSgbBorder,
     ToggleTouchControls,
     ToggleShowFps,
+    ToggleDotMatrix,
+    ToggleScanlinePpu,
     SetHardware,
     SetPalette,
+    LoadPaletteFile,
+    AddPalette,
+    RemovePalette,
     SetGbcDmgPalette,
     SetSgbPalette,
     SetColorCorrection,
@@ -734,19 +1341,46 @@ pub enum ActionKind {
     LoadBootRom,
     LoadSgbFirmware,
     SetRewindEnabled,
+    SetRtcHostClock,
+    SetAudioHighPass,
+    SetAccuracyPreset,
+    SetChannelMix,
+    SetSampleRate,
+    SetAudioBufferSize,
+    SetAudioLatencyFrames,
+    SetAudioDevice,
+    SetSaveDir,
+    SetAutoSaveState,
+    SetPauseOnFocusLoss,
     SetRewindInterval,
     SetRewindDepth,
     SetVolume,
+    ToggleMute,
     SetFastForwardFactor,
+    SetFastForwardMuted,
+    SetFastForwardVsyncOff,
+    SetFrameSkip,
+    SetEmulationSpeed,
+    SetRunAhead,
     SetScalingMode,
+    SetAspectRatio,
     SetGraphicsBackend,
+    SetWindowScale,
     ToggleFullscreen,
     SetInputConfig,
+    ExportInputProfile,
+    ImportInputProfile,
+    SetDebugLayout,
     AddCheat,
     AddCheats,
     RemoveCheat,
     GetCheats,
     ClearFetchedCheats,
+    ChooseArchiveEntry,
+    DismissArchiveChoice,
+    ResumeAutoSave,
+    DismissResume,
+    RestoreRecoverySnapshot,
     #[cfg(target_os = "android")]
     OpenRomTree,
     #[cfg(target_os = "android")]
@@ -775,11 +1409,13 @@ pub enum MenuCategory {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum KeyBind {
     F5,
+    F6,
     F8,
     Tab,
     Backslash,
     Backspace,
     KeyF,
+    KeyM,
     KeyN,
     Escape,
 }
@@ -811,6 +1447,13 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::ClearRecentRoms,
+        label: "Clear Recent",
+        category: MenuCategory::File,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::SaveState,
         label: "Save State",
@@ -839,6 +1482,13 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::ExportStateJson,
+        label: "Export Save State as JSON…",
+        category: MenuCategory::File,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::ImportBatterySave,
         label: "Import Battery Save…",
@@ -853,6 +1503,20 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::FlushSaves,
+        label: "Flush Saves Now",
+        category: MenuCategory::File,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::EraseBatterySave,
+        label: "Erase Battery Save",
+        category: MenuCategory::File,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::ImportRtc,
         label: "Import RTC…",
@@ -874,6 +1538,13 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::CopyScreenshot,
+        label: "Copy Screenshot",
+        category: MenuCategory::File,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::Quicksave,
         label: "Quicksave",
@@ -888,6 +1559,13 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: Some(KeyBind::F8),
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::UndoLoadState,
+        label: "Undo Load State",
+        category: MenuCategory::File,
+        default_keybind: Some(KeyBind::F6),
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::SaveSlot,
         label: "Save State to Slot",
@@ -951,6 +1629,27 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleLinkPartner,
+        label: "Link Cable: Second Game Boy",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetLinkInputPartner,
+        label: "Control the Second Game Boy",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::StopNetplay,
+        label: "Disconnect Netplay",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::ToggleRecording,
         label: "Record Movie",
@@ -958,6 +1657,69 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::SetMovieAnchorByHash,
+        label: "Reference Movie Start by Hash",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleInputEditor,
+        label: "TAS Input Editor",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ExportTasMovie,
+        label: "Save TAS Movie",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleAudioRecording,
+        label: "Record Audio",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetRecordStems,
+        label: "Record Channel Stems",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleGifRecording,
+        label: "Record GIF",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetGifScale,
+        label: "GIF Scale",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetGifMaxSeconds,
+        label: "GIF Length",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleVideoRecording,
+        label: "Record Video",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::LoadMovie,
         label: "Play Movie…",
@@ -1007,6 +1769,27 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::LoadPaletteFile,
+        label: "Load Palette File…",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ExportInputProfile,
+        label: "Export Controls…",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ImportInputProfile,
+        label: "Import Controls…",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::SetGbcDmgPalette,
         label: "GBC Palette (DMG games)",
@@ -1070,6 +1853,118 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::SetRtcHostClock,
+        label: "Cartridge Clock Follows Real Time",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetAudioHighPass,
+        label: "Audio High-Pass Filter",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetAccuracyPreset,
+        label: "Accuracy",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetChannelMix,
+        label: "Audio Channels",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetSampleRate,
+        label: "Audio Sample Rate",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetAudioBufferSize,
+        label: "Audio Buffer Size",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetAudioLatencyFrames,
+        label: "Audio Latency",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetAudioDevice,
+        label: "Audio Output Device",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetSaveDir,
+        label: "Save Folder",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetAutoSaveState,
+        label: "Auto-Save State on Exit",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetPauseOnFocusLoss,
+        label: "Pause When Unfocused",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetFastForwardMuted,
+        label: "Mute While Fast-Forwarding",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetFastForwardVsyncOff,
+        label: "Vsync Off While Fast-Forwarding",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetFrameSkip,
+        label: "Frame Skip",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetEmulationSpeed,
+        label: "Speed",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::SetRunAhead,
+        label: "Run-Ahead",
+        category: MenuCategory::Emulation,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::ToggleTouchControls,
         label: "On-screen Controls",
@@ -1077,6 +1972,13 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleMute,
+        label: "Mute",
+        category: MenuCategory::Settings,
+        default_keybind: Some(KeyBind::KeyM),
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::ToggleShowFps,
         label: "Show FPS",
@@ -1084,6 +1986,27 @@ pub const COMMANDS: &[CommandDescriptor] = &[
         default_keybind: None,
         overlay_button: None,
     },
+    CommandDescriptor {
+        action_kind: ActionKind::SetWindowScale,
+        label: "Window Scale",
+        category: MenuCategory::View,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleDotMatrix,
+        label: "DMG Dot-Matrix Filter",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
+    CommandDescriptor {
+        action_kind: ActionKind::ToggleScanlinePpu,
+        label: "Fast Scanline Renderer (This Game)",
+        category: MenuCategory::Settings,
+        default_keybind: None,
+        overlay_button: None,
+    },
     CommandDescriptor {
         action_kind: ActionKind::AddCheat,
         label: "Cheats",
@@ -1250,18 +2173,37 @@ mod tests {
             SaveState(std::path::PathBuf::from("s")),
             LoadState(file()),
             LoadRom(file()),
+            ClearRecentRoms,
             ImportState(file()),
             ExportState,
+            ExportStateJson,
             ImportBatterySave(file()),
             ExportBatterySave,
+            FlushSaves,
+            EraseBatterySave,
             ImportRtc(file()),
             ApplyPatch(file()),
             ExportRtc,
+            CopyScreenshot,
             TogglePause,
             ToggleRecording,
+            SetMovieAnchorByHash(true),
+            ToggleInputEditor,
+            SetTasInput(4, GbButton::A, true),
+            SeekTasFrame(2),
+            ExportTasMovie,
+            ToggleAudioRecording,
+            SetRecordStems(true),
+            ToggleGifRecording,
+            SetGifScale(3),
+            SetGifMaxSeconds(60),
+            ToggleVideoRecording,
             LoadMovie(file()),
             StopReplay,
             TogglePrinter,
+            ToggleLinkPartner,
+            SetLinkInputPartner(true),
+            StopNetplay,
             Restart,
             ClearError,
             StepCycles(1),
@@ -1273,13 +2215,19 @@ mod tests {
             LoadSlot(1),
             Quicksave,
             Quickload,
+            UndoLoadState,
             ToggleFastForward,
             FrameAdvance,
             ToggleSgbBorder,
             ToggleTouchControls,
             ToggleShowFps,
+            ToggleDotMatrix,
+            ToggleScanlinePpu,
             SetHardware(HardwareChoice::Dmg),
             SetPalette(DmgPaletteChoice::Green),
+            LoadPaletteFile(file()),
+            AddPalette("#FFFFFF #AAAAAA #555555 #000000".into()),
+            RemovePalette(0),
             SetGbcDmgPalette(GbcDmgPalette::Auto),
             SetSgbPalette(SgbPaletteChoice::System(11)),
             SetColorCorrection(crate::ColorCorrection::Lcd),
@@ -1291,19 +2239,46 @@ mod tests {
             LoadBootRom(file()),
             LoadSgbFirmware(file()),
             SetRewindEnabled(true),
+            SetRtcHostClock(false),
+            SetAudioHighPass(false),
+            SetAccuracyPreset(AccuracyPreset::Balanced),
+            SetChannelMix(ChannelMix { muted: [true, false, false, false], soloed: [false; 4] }),
+            SetSampleRate(48_000),
+            SetAudioBufferSize(512),
+            SetAudioLatencyFrames(4),
+            SetAudioDevice(Some("USB Audio".into())),
+            SetSaveDir(Some("/home/user/saves".into())),
+            SetAutoSaveState(true),
+            SetPauseOnFocusLoss(true),
             SetRewindInterval(3),
             SetRewindDepth(42),
             SetVolume(80),
+            ToggleMute,
             SetFastForwardFactor(6),
+            SetFastForwardMuted(true),
+            SetFastForwardVsyncOff(true),
+            SetFrameSkip(FrameSkip::Fixed(2)),
+            SetEmulationSpeed(50),
+            SetRunAhead(true),
             SetScalingMode(ScalingMode::Stretch),
+            SetAspectRatio(AspectRatio::FourByThree),
             SetGraphicsBackend(GraphicsBackend::Software),
+            SetWindowScale(2),
             ToggleFullscreen,
             SetInputConfig(InputConfig::default()),
+            ExportInputProfile,
+            ImportInputProfile(file()),
+            SetDebugLayout(DebugLayout::default()),
             AddCheat("00A-B7F".into()),
             AddCheats(vec!["00A-B7F".into()]),
             RemoveCheat("00A-B7F".into()),
             GetCheats,
             ClearFetchedCheats,
+            ChooseArchiveEntry("game.gb".into()),
+            DismissArchiveChoice,
+            ResumeAutoSave,
+            DismissResume,
+            RestoreRecoverySnapshot,
         ]
     }
 
@@ -1318,18 +2293,37 @@ mod tests {
                 | UiAction::SaveState(_)
                 | UiAction::LoadState(_)
                 | UiAction::LoadRom(_)
+                | UiAction::ClearRecentRoms
                 | UiAction::ImportState(_)
                 | UiAction::ExportState
+                | UiAction::ExportStateJson
                 | UiAction::ImportBatterySave(_)
                 | UiAction::ExportBatterySave
+                | UiAction::FlushSaves
+                | UiAction::EraseBatterySave
                 | UiAction::ImportRtc(_)
                 | UiAction::ApplyPatch(_)
                 | UiAction::ExportRtc
+                | UiAction::CopyScreenshot
                 | UiAction::TogglePause
                 | UiAction::ToggleRecording
+                | UiAction::SetMovieAnchorByHash(_)
+                | UiAction::ToggleInputEditor
+                | UiAction::SetTasInput(..)
+                | UiAction::SeekTasFrame(_)
+                | UiAction::ExportTasMovie
+                | UiAction::ToggleAudioRecording
+                | UiAction::SetRecordStems(_)
+                | UiAction::ToggleGifRecording
+                | UiAction::SetGifScale(_)
+                | UiAction::SetGifMaxSeconds(_)
+                | UiAction::ToggleVideoRecording
                 | UiAction::LoadMovie(_)
                 | UiAction::StopReplay
                 | UiAction::TogglePrinter
+                | UiAction::ToggleLinkPartner
+                | UiAction::SetLinkInputPartner(_)
+                | UiAction::StopNetplay
                 | UiAction::Restart
                 | UiAction::ClearError
                 | UiAction::StepCycles(_)
@@ -1341,13 +2335,19 @@ mod tests {
                 | UiAction::LoadSlot(_)
                 | UiAction::Quicksave
                 | UiAction::Quickload
+                | UiAction::UndoLoadState
                 | UiAction::ToggleFastForward
                 | UiAction::FrameAdvance
                 | UiAction::ToggleSgbBorder
                 | UiAction::ToggleTouchControls
                 | UiAction::ToggleShowFps
+                | UiAction::ToggleDotMatrix
+                | UiAction::ToggleScanlinePpu
                 | UiAction::SetHardware(_)
                 | UiAction::SetPalette(_)
+                | UiAction::LoadPaletteFile(_)
+                | UiAction::AddPalette(_)
+                | UiAction::RemovePalette(_)
                 | UiAction::SetGbcDmgPalette(_)
                 | UiAction::SetSgbPalette(_)
                 | UiAction::SetColorCorrection(_)
@@ -1359,19 +2359,46 @@ mod tests {
                 | UiAction::LoadBootRom(_)
                 | UiAction::LoadSgbFirmware(_)
                 | UiAction::SetRewindEnabled(_)
+                | UiAction::SetRtcHostClock(_)
+                | UiAction::SetAudioHighPass(_)
+                | UiAction::SetAccuracyPreset(_)
+                | UiAction::SetChannelMix(_)
+                | UiAction::SetSampleRate(_)
+                | UiAction::SetAudioBufferSize(_)
+                | UiAction::SetAudioLatencyFrames(_)
+                | UiAction::SetAudioDevice(_)
+                | UiAction::SetSaveDir(_)
+                | UiAction::SetAutoSaveState(_)
+                | UiAction::SetPauseOnFocusLoss(_)
                 | UiAction::SetRewindInterval(_)
                 | UiAction::SetRewindDepth(_)
                 | UiAction::SetVolume(_)
+                | UiAction::ToggleMute
                 | UiAction::SetFastForwardFactor(_)
+                | UiAction::SetFastForwardMuted(_)
+                | UiAction::SetFastForwardVsyncOff(_)
+                | UiAction::SetFrameSkip(_)
+                | UiAction::SetEmulationSpeed(_)
+                | UiAction::SetRunAhead(_)
                 | UiAction::SetScalingMode(_)
+                | UiAction::SetAspectRatio(_)
                 | UiAction::SetGraphicsBackend(_)
+                | UiAction::SetWindowScale(_)
                 | UiAction::ToggleFullscreen
                 | UiAction::SetInputConfig(_)
+                | UiAction::ExportInputProfile
+                | UiAction::ImportInputProfile(_)
+                | UiAction::SetDebugLayout(_)
                 | UiAction::AddCheat(_)
                 | UiAction::AddCheats(_)
                 | UiAction::RemoveCheat(_)
                 | UiAction::GetCheats
-                | UiAction::ClearFetchedCheats => {}
+                | UiAction::ClearFetchedCheats
+                | UiAction::ChooseArchiveEntry(_)
+                | UiAction::DismissArchiveChoice
+                | UiAction::ResumeAutoSave
+                | UiAction::DismissResume
+                | UiAction::RestoreRecoverySnapshot => {}
                 #[cfg(target_os = "android")]
                 UiAction::OpenRomTree
                 | UiAction::RescanLibrary
@@ -1401,8 +2428,14 @@ mod tests {
     #[test]
     fn session_ui_state_serde_json_round_trips_every_field() {
         let s = SessionUiState {
+            recent_roms: vec!["/roms/tetris.gb".into()],
+            osd: Some(OsdMessage { id: 3, text: "State 3 saved".into() }),
             hardware: HardwareChoice::Agb,
-            palette: DmgPaletteChoice::Pocket,
+            palette: DmgPaletteChoice::Custom([[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]]),
+            custom_palettes: vec![crate::palette::CustomPalette {
+                name: "Custom 1".into(),
+                shades: [[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]],
+            }],
             gbc_dmg_palette: GbcDmgPalette::Scheme(7),
             dmg_palette_active: false,
             sgb_palette: SgbPaletteChoice::System(21),
@@ -1417,25 +2450,75 @@ mod tests {
             rewind_interval_frames: 9,
             rewind_depth: 17,
             volume: 42,
+            muted: true,
             scaling: ScalingMode::IntegerAspect,
+            aspect_ratio: AspectRatio::TenByNine,
+            window_scale: 3,
             graphics_backend: GraphicsBackend::Software,
             sgb_border: false,
             paused: true,
             fast_forward: true,
             fast_forward_factor: 0,
+            fast_forward_muted: true,
+            fast_forward_vsync_off: true,
+            frame_skip: FrameSkip::Auto,
+            emulation_speed: 200,
+            run_ahead: true,
             touch_controls: true,
             show_fps: true,
+            dot_matrix: true,
+            scanline_ppu: true,
+            accuracy_preset: None,
+            rtc_host_clock: false,
+            audio_high_pass: false,
+            channel_mix: ChannelMix { muted: [false, true, false, false], soloed: [false, false, true, false] },
+            sample_rate: 96_000,
+            audio_buffer_size: 1024,
+            audio_latency_frames: 5,
+            audio_device: Some("USB Audio".into()),
+            audio_devices: vec!["Speakers".into(), "USB Audio".into()],
+            save_dir: Some("/home/user/saves".into()),
+            record_stems: true,
+            recording_audio: true,
+            gif_scale: 3,
+            gif_max_seconds: 60,
+            recording_gif: true,
+            recording_video: true,
+            auto_save_state: true,
+            pause_on_focus_loss: true,
             printer_attached: true,
+            link_partner: true,
+            link_input_partner: true,
+            netplay: true,
             recording: true,
+            movie_anchor_by_hash: true,
             replaying: true,
             slots: vec![1, 2, 5],
+            slot_times: vec![(1, 1_700_000_000), (2, 0), (5, 42)],
+            slot_thumbnails: vec![(1, vec![0x20; 40 * 36 * 3])],
+            resume_offer: Some(1_700_000_000),
+            can_undo_load: true,
+            recovery_snapshot: Some(1200),
             cheats: vec!["00A-B7F".into()],
             fetched_cheats: Vec::new(),
+            archive_choices: vec!["a.gb".into(), "b.gbc".into()],
             has_battery: true,
+            battery_size: 0x8000,
             has_rtc: true,
             has_rom: true,
             game_name: Some("Tetris".into()),
             input: InputConfig::default(),
+            connected_pads: vec![ConnectedPad { guid: "0300ab".into(), name: "Pad".into() }],
+            debug_layout: DebugLayout {
+                windows: vec![DebugWindowLayout { title: "APU".into(), open: true, pos: [12.5, 40.0] }],
+            },
+            input_editor: Some(InputEditorView {
+                frame: 2,
+                len: 3,
+                first: 0,
+                rows: vec![[false; 8], [true, false, false, false, false, false, false, true], [false; 8]],
+                snapshots: vec![0],
+            }),
         };
         let json = serde_json::to_string(&s).unwrap();
         let back: SessionUiState = serde_json::from_str(&json).unwrap();
@@ -1491,4 +2574,20 @@ mod tests {
         ids.dedup();
         assert_eq!(ids.len(), DmgPaletteChoice::ALL.len());
     }
+
+    #[test]
+    fn solo_overrides_mute() {
+        let mix = ChannelMix { muted: [true, false, false, true], soloed: [false; 4] };
+        assert_eq!(mix.audible(), [false, true, true, false]);
+        let solo = ChannelMix { soloed: [true, false, false, false], ..mix };
+        assert_eq!(solo.audible(), [true, false, false, false]);
+    }
+
+    #[test]
+    fn channel_lists_parse_numbers_and_names() {
+        assert_eq!(ChannelMix::parse_channels("1, wave"), Some([true, false, true, false]));
+        assert_eq!(ChannelMix::parse_channels("Noise,pulse2"), Some([false, true, false, true]));
+        assert_eq!(ChannelMix::parse_channels(""), Some([false; 4]));
+        assert_eq!(ChannelMix::parse_channels("5"), None);
+    }
 }
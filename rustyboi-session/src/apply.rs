@@ -7,7 +7,7 @@
 //! host can do as [`PlatformRequest`]s the frontend performs after the call.
 
 use crate::action::{LoadPurpose, DmgPaletteChoice, UiAction};
-use crate::session::Session;
+use crate::session::{Session, SessionError};
 
 /// Why a URL is being fetched, so the frontend routes the downloaded bytes back
 /// to the right finisher. Kept typed (not just a bare URL) so the same
@@ -64,6 +64,11 @@ pub enum PlatformRequest {
     Error(String),
     /// Clear any UI error overlay (a load succeeded / error was dismissed).
     ClearError,
+    /// Put a `width` x `height` RGBA8 image on the system clipboard (File →
+    /// Copy Screenshot), then report whether it worked. Desktop writes it
+    /// through arboard, web through the async Clipboard API as a PNG; mobile
+    /// has no clipboard and does not offer the action.
+    CopyImage { width: u32, height: u32, rgba: Vec<u8> },
     /// Toggle host fullscreen. Serviced by the windowed frontend's
     /// [`Frontend::toggle_fullscreen`](crate::apply::PlatformRequest): desktop
     /// flips the winit window, web the canvas Fullscreen API, Android no-ops.
@@ -167,7 +172,10 @@ impl Session {
             UiAction::ToggleRecording => {
                 if self.is_recording() {
                     match self.stop_recording() {
-                        Some(movie) => {
+                        Some(mut movie) => {
+                            if let Err(e) = self.keep_movie_anchor(&mut movie) {
+                                return ActionOutcome::error(format!("Failed to keep the movie's start state: {e}"));
+                            }
                             let frames = movie.inputs.len();
                             let mut o = ActionOutcome::default();
                             o.push(PlatformRequest::SaveBytes {
@@ -190,6 +198,50 @@ impl Session {
                     }
                 }
             }
+            UiAction::SetMovieAnchorByHash(on) => {
+                self.set_movie_anchor_by_hash(on);
+                ActionOutcome::default()
+            }
+            UiAction::ToggleInputEditor => {
+                if self.input_editor().is_some() {
+                    self.close_input_editor();
+                    ActionOutcome::status("Input editor closed")
+                } else {
+                    match self.open_input_editor() {
+                        Ok(()) => ActionOutcome {
+                            requests: vec![PlatformRequest::Status("Input editor opened".into())],
+                            pause_changed: true,
+                        },
+                        Err(e) => ActionOutcome::error(format!("Failed to open the input editor: {e}")),
+                    }
+                }
+            }
+            UiAction::SetTasInput(frame, button, pressed) => {
+                match self.edit_input(frame as usize, button, pressed) {
+                    Ok(()) => ActionOutcome { requests: Vec::new(), pause_changed: true },
+                    Err(e) => ActionOutcome::error(format!("Re-simulation failed: {e}")),
+                }
+            }
+            UiAction::SeekTasFrame(frame) => match self.seek_input_editor(frame as usize) {
+                Ok(()) => ActionOutcome { requests: Vec::new(), pause_changed: true },
+                Err(e) => ActionOutcome::error(format!("Re-simulation failed: {e}")),
+            },
+            UiAction::ExportTasMovie => match self.input_editor_movie() {
+                Some(mut movie) => {
+                    if let Err(e) = self.keep_movie_anchor(&mut movie) {
+                        return ActionOutcome::error(format!("Failed to keep the movie's start state: {e}"));
+                    }
+                    let frames = movie.inputs.len();
+                    let mut o = ActionOutcome::default();
+                    o.push(PlatformRequest::SaveBytes {
+                        suggested_name: "tas.rbmovie".into(),
+                        bytes: movie.to_bytes(),
+                    });
+                    o.push(PlatformRequest::Status(format!("TAS movie saved ({frames} frames)")));
+                    o
+                }
+                None => ActionOutcome::status("The input editor is not open"),
+            },
             UiAction::LoadMovie(file) => ActionOutcome {
                 requests: vec![PlatformRequest::LoadFile {
                     file,
@@ -211,6 +263,10 @@ impl Session {
                     self.gb_mut().detach_serial_device();
                     ActionOutcome::status("Game Boy Printer disconnected")
                 } else {
+                    // The printer takes the link port from any second Game
+                    // Boy or netplay partner.
+                    self.stop_link_partner();
+                    self.stop_netplay();
                     self.gb_mut().attach_printer();
                     ActionOutcome::status(
                         "Game Boy Printer connected - prints are saved next to the ROM",
@@ -218,6 +274,30 @@ impl Session {
                 }
             }
 
+            UiAction::ToggleLinkPartner => {
+                if self.link_partner_running() {
+                    self.stop_link_partner();
+                    ActionOutcome::status("Second Game Boy unplugged")
+                } else {
+                    match self.start_link_partner() {
+                        Ok(()) => ActionOutcome::status("Second Game Boy linked"),
+                        Err(e) => ActionOutcome::status(e),
+                    }
+                }
+            }
+            UiAction::SetLinkInputPartner(partner) => {
+                self.set_link_input_partner(partner);
+                ActionOutcome::default()
+            }
+            UiAction::StopNetplay => {
+                if self.netplay_active() {
+                    self.stop_netplay();
+                    ActionOutcome::status("Netplay disconnected")
+                } else {
+                    ActionOutcome::default()
+                }
+            }
+
             UiAction::StepCycles(count) => {
                 self.request_step_cycles(count);
                 ActionOutcome::default()
@@ -263,6 +343,39 @@ impl Session {
                 }
                 Err(e) => ActionOutcome::error(format!("Quickload failed: {e}")),
             },
+            UiAction::UndoLoadState => match self.undo_load_state() {
+                Ok(()) => {
+                    let mut o = ActionOutcome::status("Load undone");
+                    o.requests.insert(0, PlatformRequest::ClearError);
+                    o
+                }
+                Err(SessionError::NoState) => ActionOutcome::error("No state load to undo"),
+                Err(e) => ActionOutcome::error(format!("Undo load failed: {e}")),
+            },
+            UiAction::ResumeAutoSave => match self.resume_auto_save() {
+                Ok(_) => {
+                    let mut o = ActionOutcome::status("Resumed where you left off");
+                    o.requests.insert(0, PlatformRequest::ClearError);
+                    o
+                }
+                Err(e) => ActionOutcome::error(format!("Failed to resume: {e}")),
+            },
+            UiAction::DismissResume => {
+                self.dismiss_resume();
+                ActionOutcome::default()
+            }
+            // Clears the crash and resumes from the snapshot; `pause_changed`
+            // lets the frontend drop its error state and run again.
+            UiAction::RestoreRecoverySnapshot => match self.restore_recovery_snapshot() {
+                Ok(frame) => {
+                    let mut o = ActionOutcome::status(format!("Restored the snapshot from frame {frame}"));
+                    o.requests.insert(0, PlatformRequest::ClearError);
+                    o.pause_changed = true;
+                    o
+                }
+                Err(SessionError::NoState) => ActionOutcome::error("No recovery snapshot to restore"),
+                Err(e) => ActionOutcome::error(format!("Recovery failed: {e}")),
+            },
 
             UiAction::ToggleFastForward => {
                 self.toggle_fast_forward();
@@ -289,6 +402,14 @@ impl Session {
                 self.set_show_fps(!self.show_fps());
                 ActionOutcome::default()
             }
+            UiAction::ToggleDotMatrix => {
+                self.set_dot_matrix(!self.dot_matrix());
+                ActionOutcome::default()
+            }
+            UiAction::ToggleScanlinePpu => {
+                self.set_scanline_ppu(!self.scanline_ppu());
+                ActionOutcome::default()
+            }
 
             UiAction::SetHardware(choice) => {
                 self.set_hardware_choice(choice);
@@ -308,6 +429,15 @@ impl Session {
                 self.set_palette_choice(choice);
                 ActionOutcome::default()
             }
+            UiAction::LoadPaletteFile(file) => ActionOutcome {
+                requests: vec![PlatformRequest::LoadFile { file, purpose: LoadPurpose::Palette }],
+                pause_changed: false,
+            },
+            UiAction::AddPalette(text) => self.add_palette_outcome(&text),
+            UiAction::RemovePalette(index) => {
+                self.remove_custom_palette(index);
+                ActionOutcome::default()
+            }
             UiAction::SetGbcDmgPalette(choice) => {
                 self.set_gbc_dmg_palette(choice);
                 let (w, h) = self.content_size();
@@ -373,6 +503,108 @@ impl Session {
                 self.set_rewind_enabled(enabled);
                 ActionOutcome::default()
             }
+            UiAction::SetRtcHostClock(on) => {
+                self.set_rtc_host_clock(on);
+                ActionOutcome::default()
+            }
+            UiAction::SetAudioHighPass(on) => {
+                self.set_audio_high_pass(on);
+                ActionOutcome::default()
+            }
+            UiAction::SetAccuracyPreset(preset) => {
+                self.set_accuracy_preset(preset);
+                ActionOutcome::default()
+            }
+            UiAction::SetChannelMix(mix) => {
+                self.set_channel_mix(mix);
+                ActionOutcome::default()
+            }
+            UiAction::SetSampleRate(rate) => {
+                self.set_sample_rate(rate);
+                ActionOutcome::default()
+            }
+            UiAction::SetAudioBufferSize(pairs) => {
+                self.set_audio_buffer_size(pairs);
+                ActionOutcome::default()
+            }
+            UiAction::SetAudioLatencyFrames(frames) => {
+                self.set_audio_latency_frames(frames);
+                ActionOutcome::default()
+            }
+            UiAction::ToggleAudioRecording => match self.stop_audio_recording() {
+                Some((export, seconds)) => {
+                    let mut o = ActionOutcome::default();
+                    if let Some((suggested_name, bytes)) = export {
+                        o.push(PlatformRequest::SaveBytes { suggested_name, bytes });
+                    }
+                    o.push(PlatformRequest::Status(format!("Audio recording stopped ({seconds:.1} s)")));
+                    o
+                }
+                None => match self.start_audio_recording() {
+                    Ok(true) => ActionOutcome::status("Audio recording started"),
+                    Ok(false) => ActionOutcome::default(),
+                    Err(e) => ActionOutcome::error(format!("Could not start audio recording: {e}")),
+                },
+            },
+            UiAction::SetRecordStems(on) => {
+                self.set_record_stems(on);
+                ActionOutcome::default()
+            }
+            UiAction::ToggleGifRecording => match self.stop_gif_recording() {
+                Some((bytes, _)) if bytes.is_empty() => ActionOutcome::status("GIF recording stopped (no frames)"),
+                Some((bytes, seconds)) => {
+                    let mut o = ActionOutcome::default();
+                    o.push(PlatformRequest::SaveBytes { suggested_name: "recording.gif".into(), bytes });
+                    o.push(PlatformRequest::Status(format!("GIF recording stopped ({seconds:.1} s)")));
+                    o
+                }
+                None => {
+                    self.start_gif_recording();
+                    ActionOutcome::status("GIF recording started")
+                }
+            },
+            UiAction::SetGifScale(scale) => {
+                self.set_gif_scale(scale);
+                ActionOutcome::default()
+            }
+            UiAction::SetGifMaxSeconds(seconds) => {
+                self.set_gif_max_seconds(seconds);
+                ActionOutcome::default()
+            }
+            UiAction::ToggleVideoRecording if !self.is_recording_video() => {
+                self.start_video_recording();
+                ActionOutcome::status("Video recording started")
+            }
+            UiAction::ToggleVideoRecording => match self.stop_video_recording() {
+                Some(capture) if capture.frames().is_empty() => {
+                    ActionOutcome::status("Video recording stopped (no frames)")
+                }
+                Some(capture) => {
+                    let mut o = ActionOutcome::default();
+                    let seconds = capture.seconds();
+                    o.push(PlatformRequest::SaveBytes { suggested_name: "video.zip".into(), bytes: capture.to_zip() });
+                    o.push(PlatformRequest::Status(format!("Video recording stopped ({seconds:.1} s)")));
+                    o
+                }
+                // A host sink (`--record-video`) writes its own file.
+                None => ActionOutcome::status("Video recording stopped"),
+            },
+            UiAction::SetAudioDevice(name) => {
+                self.set_audio_device(name);
+                ActionOutcome::default()
+            }
+            UiAction::SetSaveDir(dir) => {
+                self.set_save_dir(dir);
+                ActionOutcome::default()
+            }
+            UiAction::SetAutoSaveState(on) => {
+                self.set_auto_save_state(on);
+                ActionOutcome::default()
+            }
+            UiAction::SetPauseOnFocusLoss(on) => {
+                self.set_pause_on_focus_loss(on);
+                ActionOutcome::default()
+            }
             UiAction::SetRewindInterval(interval) => {
                 self.set_rewind_interval(interval);
                 ActionOutcome::default()
@@ -385,18 +617,50 @@ impl Session {
                 self.set_volume(volume);
                 ActionOutcome::default()
             }
+            UiAction::ToggleMute => {
+                self.toggle_mute();
+                ActionOutcome::status(if self.is_muted() { "Muted" } else { "Unmuted" })
+            }
             UiAction::SetFastForwardFactor(factor) => {
                 self.set_fast_forward_factor(factor);
                 ActionOutcome::default()
             }
+            UiAction::SetFastForwardMuted(muted) => {
+                self.set_fast_forward_muted(muted);
+                ActionOutcome::default()
+            }
+            UiAction::SetFastForwardVsyncOff(off) => {
+                self.set_fast_forward_vsync_off(off);
+                ActionOutcome::default()
+            }
+            UiAction::SetFrameSkip(skip) => {
+                self.set_frame_skip(skip);
+                ActionOutcome::default()
+            }
+            UiAction::SetEmulationSpeed(percent) => {
+                self.set_emulation_speed(percent);
+                ActionOutcome::default()
+            }
+            UiAction::SetRunAhead(on) => {
+                self.set_run_ahead(on);
+                ActionOutcome::default()
+            }
             UiAction::SetScalingMode(scaling) => {
                 self.set_scaling_mode(scaling);
                 ActionOutcome::default()
             }
+            UiAction::SetAspectRatio(aspect) => {
+                self.set_aspect_ratio(aspect);
+                ActionOutcome::default()
+            }
             UiAction::SetGraphicsBackend(backend) => {
                 self.set_graphics_backend(backend);
                 ActionOutcome::default()
             }
+            UiAction::SetWindowScale(scale) => {
+                self.set_window_scale(scale);
+                ActionOutcome::default()
+            }
             UiAction::ToggleFullscreen => ActionOutcome {
                 requests: vec![PlatformRequest::ToggleFullscreen],
                 pause_changed: false,
@@ -406,6 +670,23 @@ impl Session {
                 self.set_input_config(input);
                 ActionOutcome::default()
             }
+            UiAction::ExportInputProfile => {
+                let mut o = ActionOutcome::default();
+                o.push(PlatformRequest::SaveBytes {
+                    suggested_name: "rustyboi-controls.json".into(),
+                    bytes: self.input_config().to_profile(),
+                });
+                o.push(PlatformRequest::Status("Controls exported".into()));
+                o
+            }
+            UiAction::ImportInputProfile(file) => ActionOutcome {
+                requests: vec![PlatformRequest::LoadFile { file, purpose: LoadPurpose::InputProfile }],
+                pause_changed: false,
+            },
+            UiAction::SetDebugLayout(layout) => {
+                self.set_debug_layout(layout);
+                ActionOutcome::default()
+            }
 
             UiAction::AddCheat(code) => match self.add_cheat(&code) {
                 Ok(_) => ActionOutcome::status(format!("Cheat added: {code}")),
@@ -454,9 +735,20 @@ impl Session {
                 self.clear_fetched_cheats();
                 ActionOutcome::default()
             }
+            UiAction::ChooseArchiveEntry(name) => {
+                let loaded = self.take_archive_entry(&name).and_then(|(bytes, patch)| match patch {
+                    Some(patch) => self.finish_load_soft_patched_rom(&bytes, &patch),
+                    None => self.finish_load_rom(&bytes),
+                });
+                self.rom_load_outcome(loaded)
+            }
+            UiAction::DismissArchiveChoice => {
+                self.clear_archive_choice();
+                ActionOutcome::default()
+            }
 
             // OS-requiring: hand off to the frontend.
-            UiAction::SaveState(path) => match self.gb_mut().to_state_bytes() {
+            UiAction::SaveState(path) => match self.state_file_bytes() {
                 Ok(bytes) => {
                     let mut o = ActionOutcome::default();
                     o.push(PlatformRequest::SaveStateBytes { path, bytes });
@@ -468,6 +760,10 @@ impl Session {
                 requests: vec![PlatformRequest::LoadFile { file, purpose: LoadPurpose::Rom }],
                 pause_changed: false,
             },
+            UiAction::ClearRecentRoms => {
+                self.clear_recent_roms();
+                ActionOutcome::default()
+            }
             UiAction::LoadState(file) | UiAction::ImportState(file) => ActionOutcome {
                 requests: vec![PlatformRequest::LoadFile { file, purpose: LoadPurpose::State }],
                 pause_changed: false,
@@ -487,7 +783,7 @@ impl Session {
 
             // Export: produce a path-free SaveBytes request the frontend delivers
             // as a file (download on web, save dialog on desktop/Android).
-            UiAction::ExportState => match self.gb_mut().to_state_bytes() {
+            UiAction::ExportState => match self.state_file_bytes() {
                 Ok(bytes) => {
                     let mut o = ActionOutcome::default();
                     o.push(PlatformRequest::SaveBytes {
@@ -498,6 +794,14 @@ impl Session {
                 }
                 Err(e) => ActionOutcome::error(format!("Failed to export state: {e}")),
             },
+            UiAction::ExportStateJson => match self.state_json_bytes() {
+                Ok(bytes) => {
+                    let mut o = ActionOutcome::default();
+                    o.push(PlatformRequest::SaveBytes { suggested_name: "savestate.json".into(), bytes });
+                    o
+                }
+                Err(e) => ActionOutcome::error(format!("Failed to export state: {e}")),
+            },
             UiAction::ExportBatterySave => match self.export_battery() {
                 Some(bytes) => {
                     let mut o = ActionOutcome::default();
@@ -509,6 +813,22 @@ impl Session {
                 }
                 None => ActionOutcome::error("This cartridge has no battery save"),
             },
+            UiAction::CopyScreenshot => {
+                let (width, height, rgba) = self.screenshot_rgba();
+                let mut o = ActionOutcome::default();
+                // Only the host knows whether the clipboard took it, so the
+                // "copied" status is its to report.
+                o.push(PlatformRequest::CopyImage { width, height, rgba });
+                o
+            }
+            UiAction::FlushSaves => match self.flush_saves() {
+                Ok(()) => ActionOutcome::status("Battery save written"),
+                Err(e) => ActionOutcome::error(format!("Failed to flush saves: {e}")),
+            },
+            UiAction::EraseBatterySave => match self.erase_battery() {
+                Ok(()) => ActionOutcome::status("Battery save erased"),
+                Err(e) => ActionOutcome::error(format!("Failed to erase battery save: {e}")),
+            },
             UiAction::ExportRtc => match self.export_rtc() {
                 Some(bytes) => {
                     let mut o = ActionOutcome::default();
@@ -545,16 +865,12 @@ impl Session {
     /// accepted SGB firmware to IndexedDB).
     pub fn finish_file(&mut self, purpose: LoadPurpose, bytes: &[u8]) -> ActionOutcome {
         match purpose {
-            LoadPurpose::Rom => match self.finish_load_rom(bytes) {
-                Ok(_) => {
-                    let mut o = ActionOutcome::default();
-                    o.push(PlatformRequest::ClearError);
-                    let (width, height) = self.content_size();
-                    o.push(PlatformRequest::ResizeContent { width, height });
-                    o.push(PlatformRequest::Status("ROM loaded".into()));
-                    o
+            LoadPurpose::Rom => match self.offer_archive_choice(bytes, None) {
+                Some(o) => o,
+                None => {
+                    let loaded = self.finish_load_rom(bytes);
+                    self.rom_load_outcome(loaded)
                 }
-                Err(e) => ActionOutcome::error(format!("Failed to load ROM: {e}")),
             },
 
             LoadPurpose::State => {
@@ -584,7 +900,7 @@ impl Session {
                 Ok(_) => {
                     let mut o = ActionOutcome::default();
                     o.push(PlatformRequest::ClearError);
-                    o.push(PlatformRequest::Status("Patch applied".into()));
+                    o.push(PlatformRequest::Status(self.load_status("Patch applied")));
                     o
                 }
                 Err(e) => ActionOutcome::error(format!("Failed to apply patch: {e}")),
@@ -621,11 +937,57 @@ impl Session {
                 }
             }
 
+            LoadPurpose::Palette => self.add_palette_outcome(&String::from_utf8_lossy(bytes)),
+
+            LoadPurpose::InputProfile => match crate::input_config::InputConfig::from_profile(bytes) {
+                Ok(input) => {
+                    self.set_input_config(input);
+                    ActionOutcome::status("Controls imported")
+                }
+                Err(e) => ActionOutcome::error(format!("Failed to import controls: {e}")),
+            },
+
             // No frontend wires a boot-ROM picker yet, so there is nothing to
             // finish; `apply` still emits the request for whichever host adds one.
             LoadPurpose::BootRom => ActionOutcome::default(),
         }
     }
+
+    /// Add a user palette from typed or loaded text and report it.
+    fn add_palette_outcome(&mut self, text: &str) -> ActionOutcome {
+        match self.add_custom_palette(text) {
+            Ok(name) => ActionOutcome::status(format!("Palette \"{name}\" added")),
+            Err(e) => ActionOutcome::error(format!("Not a palette: {e}")),
+        }
+    }
+
+    /// Hold `bytes` for the archive chooser when it is a zip/7z bundle of
+    /// several ROMs, returning the outcome that prompts for a pick; `None` means
+    /// load it as usual. Frontends that load ROMs outside
+    /// [`finish_file`](Self::finish_file) (the desktop app, which also carries
+    /// a sidecar `patch`) call this first so they prompt in the same words.
+    pub fn offer_archive_choice(&mut self, bytes: &[u8], patch: Option<&[u8]>) -> Option<ActionOutcome> {
+        self.hold_archive_bundle(bytes, patch).then(|| {
+            let count = self.archive_choices().len();
+            ActionOutcome::status(format!("Archive holds {count} ROMs — choose one to load"))
+        })
+    }
+
+    /// The ClearError/Resize/Status sequence for a finished ROM load (or the
+    /// error when it failed), shared by the file path and the archive chooser.
+    fn rom_load_outcome(&mut self, loaded: Result<[u8; 32], SessionError>) -> ActionOutcome {
+        match loaded {
+            Ok(_) => {
+                let mut o = ActionOutcome::default();
+                o.push(PlatformRequest::ClearError);
+                let (width, height) = self.content_size();
+                o.push(PlatformRequest::ResizeContent { width, height });
+                o.push(PlatformRequest::Status(self.load_status("ROM loaded")));
+                o
+            }
+            Err(e) => ActionOutcome::error(format!("Failed to load ROM: {e}")),
+        }
+    }
 }
 
 /// The RGBA shades for a base palette composed with the colour-correction, as
@@ -726,8 +1088,18 @@ mod tests {
         let actions = [
             TogglePause,
             ToggleRecording,
+            ToggleAudioRecording,
+            SetRecordStems(true),
+            SetMovieAnchorByHash(true),
+            ToggleGifRecording,
+            SetGifScale(3),
+            SetGifMaxSeconds(60),
+            ToggleVideoRecording,
             StopReplay,
             TogglePrinter,
+            ToggleLinkPartner,
+            SetLinkInputPartner(true),
+            StopNetplay,
             Restart,
             ClearError,
             StepCycles(3),
@@ -737,25 +1109,60 @@ mod tests {
             ClearBreakpoints,
             Quicksave,
             Quickload,
+            UndoLoadState,
             ToggleFastForward,
             FrameAdvance,
             ToggleSgbBorder,
             ToggleTouchControls,
             ToggleShowFps,
+            ToggleDotMatrix,
+            ToggleScanlinePpu,
             SetHardware(HardwareChoice::Dmg),
             SetPalette(DmgPaletteChoice::Pocket),
+            AddPalette("#FFFFFF #AAAAAA #555555 #000000".into()),
+            RemovePalette(0),
             SetSgbPalette(crate::action::SgbPaletteChoice::System(4)),
             SetColorCorrection(crate::ColorCorrection::Lcd),
             SetRealBootRom(false),
             SetTextureFilter(crate::action::TextureFilter::Linear),
             SetLcdEffect(crate::action::LcdEffect::Grid),
             SetRewindEnabled(false),
+            SetRtcHostClock(false),
+            SetAudioHighPass(false),
+            SetAccuracyPreset(crate::action::AccuracyPreset::Speed),
+            SetChannelMix(crate::action::ChannelMix::default()),
+            SetSampleRate(48_000),
+            SetAudioBufferSize(512),
+            SetAudioLatencyFrames(4),
+            SetAudioDevice(None),
+            SetSaveDir(None),
+            SetAutoSaveState(false),
+            SetPauseOnFocusLoss(true),
+            DismissResume,
+            RestoreRecoverySnapshot,
+            ClearRecentRoms,
+            SetDebugLayout(crate::action::DebugLayout::default()),
+            ToggleInputEditor,
+            SetTasInput(3, crate::input::GbButton::Start, true),
+            SeekTasFrame(1),
+            ExportTasMovie,
+            ExportInputProfile,
+            ToggleInputEditor,
             SetRewindInterval(4),
             SetRewindDepth(30),
             SetVolume(50),
+            ToggleMute,
             SetFastForwardFactor(6),
+            SetFastForwardMuted(true),
+            SetFastForwardVsyncOff(true),
+            SetFrameSkip(crate::action::FrameSkip::Auto),
+            SetEmulationSpeed(150),
+            SetRunAhead(true),
             SetScalingMode(crate::action::ScalingMode::IntegerAspect),
+            SetAspectRatio(crate::action::AspectRatio::FourByThree),
+            SetWindowScale(3),
             ToggleFullscreen,
+            CopyScreenshot,
         ];
         let mut s = session();
         for a in actions {
@@ -775,6 +1182,23 @@ mod tests {
             .any(|r| matches!(r, PlatformRequest::ResizeContent { .. })));
     }
 
+    #[test]
+    fn copy_screenshot_hands_over_the_presented_frame() {
+        let mut s = session();
+        let out = s.apply(UiAction::CopyScreenshot, 0);
+        let (width, height, rgba) = out
+            .requests
+            .into_iter()
+            .find_map(|r| match r {
+                PlatformRequest::CopyImage { width, height, rgba } => Some((width, height, rgba)),
+                _ => None,
+            })
+            .expect("a CopyImage request");
+        assert_eq!((width, height), crate::session::GB_SIZE);
+        assert_eq!(rgba.len(), (width * height * 4) as usize);
+        assert!(rgba.chunks_exact(4).all(|px| px[3] == 0xFF), "pixels are opaque");
+    }
+
     #[test]
     fn set_palette_persists_choice() {
         let mut s = session();
@@ -786,6 +1210,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn added_palettes_switch_live_and_fall_back_when_removed() {
+        let mut s = session();
+        let text = "#E0F8D0 #88C070 #346856 #081820";
+        let out = s.apply(UiAction::AddPalette(text.into()), 0);
+        assert!(matches!(out.requests.as_slice(), [PlatformRequest::Status(m)] if m.contains("Custom 1")));
+        let custom = s.custom_palettes()[0].choice();
+        assert_eq!(s.palette(), custom);
+        assert_eq!(s.config().dmg_palette.shades, custom.shades_rgba(s.color_correction()));
+
+        // The same colours again select the existing entry instead of a copy.
+        s.apply(UiAction::SetPalette(DmgPaletteChoice::Grayscale), 0);
+        s.apply(UiAction::AddPalette(text.into()), 0);
+        assert_eq!((s.custom_palettes().len(), s.palette()), (1, custom));
+
+        let out = s.apply(UiAction::AddPalette("#FFFFFF".into()), 0);
+        assert!(matches!(out.requests.as_slice(), [PlatformRequest::Error(_)]));
+
+        s.apply(UiAction::RemovePalette(0), 0);
+        assert!(s.custom_palettes().is_empty());
+        assert_eq!(s.palette(), DmgPaletteChoice::default());
+    }
+
+    #[test]
+    fn exported_controls_import_back_over_a_changed_map() {
+        let mut s = session();
+        let mut input = crate::input_config::InputConfig::default();
+        input.add_wasd_dpad();
+        s.apply(UiAction::SetInputConfig(input.clone()), 0);
+        let out = s.apply(UiAction::ExportInputProfile, 0);
+        let Some(PlatformRequest::SaveBytes { bytes, .. }) = out.requests.first() else {
+            panic!("export hands back a file: {:?}", out.requests);
+        };
+
+        s.apply(UiAction::SetInputConfig(Default::default()), 0);
+        assert!(s.finish_file(LoadPurpose::InputProfile, bytes).succeeded());
+        assert_eq!(s.input_config(), &input);
+        assert!(!s.finish_file(LoadPurpose::InputProfile, b"not json").succeeded());
+        assert_eq!(s.input_config(), &input);
+    }
+
+    #[test]
+    fn dot_matrix_shows_olive_without_touching_the_chosen_palette() {
+        let mut s = session();
+        s.apply(UiAction::SetPalette(DmgPaletteChoice::Pocket), 0);
+        s.apply(UiAction::ToggleDotMatrix, 0);
+        assert!(s.dot_matrix());
+        assert_eq!(s.gb().dmg_palette(), DmgPaletteChoice::Custom(crate::action::DOT_MATRIX_SHADES));
+        assert_eq!(s.palette(), DmgPaletteChoice::Pocket);
+
+        // A palette pick while the filter is on is remembered for later.
+        s.apply(UiAction::SetPalette(DmgPaletteChoice::Grayscale), 0);
+        assert_eq!(s.gb().dmg_palette(), DmgPaletteChoice::Custom(crate::action::DOT_MATRIX_SHADES));
+        s.apply(UiAction::ToggleDotMatrix, 0);
+        assert_eq!(s.gb().dmg_palette(), DmgPaletteChoice::Grayscale);
+    }
+
+    // The scanline renderer is a per-ROM choice: it follows the ROM that
+    // asked for it, and reaches the machine on the next frame.
+    #[test]
+    fn scanline_renderer_is_remembered_per_rom() {
+        use crate::AbstractInput;
+        let rom = |title: u8| {
+            let mut r = vec![0u8; 0x8000];
+            r[0x0134] = title;
+            r
+        };
+        let mut s = session();
+        s.finish_load_rom(&rom(b'A')).expect("load rom");
+        s.apply(UiAction::ToggleScanlinePpu, 0);
+        assert!(s.ui_state().scanline_ppu);
+        s.run_frame(AbstractInput::none());
+        assert!(s.gb().scanline_ppu());
+
+        s.finish_load_rom(&rom(b'B')).expect("load rom");
+        s.run_frame(AbstractInput::none());
+        assert!(!s.scanline_ppu() && !s.gb().scanline_ppu(), "another ROM keeps the dot renderer");
+
+        s.finish_load_rom(&rom(b'A')).expect("load rom");
+        assert!(s.scanline_ppu());
+        s.apply(UiAction::ToggleScanlinePpu, 0);
+        assert!(s.config().scanline_ppu_roms.is_empty());
+    }
+
+    // A preset sets its whole group at once; touching one toggle afterwards
+    // leaves a custom mix, and the per-ROM renderer choice still adds to it.
+    #[test]
+    fn accuracy_presets_group_the_toggles() {
+        use crate::action::AccuracyPreset;
+        use crate::AbstractInput;
+        let mut s = session();
+        assert_eq!(s.ui_state().accuracy_preset, Some(AccuracyPreset::Accuracy));
+
+        s.apply(UiAction::SetAccuracyPreset(AccuracyPreset::Speed), 0);
+        assert_eq!(s.ui_state().accuracy_preset, Some(AccuracyPreset::Speed));
+        assert!(!s.config().audio_high_pass && !s.config().oam_bug);
+        s.run_frame(AbstractInput::none());
+        assert!(s.gb().scanline_ppu(), "every ROM on the scanline renderer");
+
+        s.apply(UiAction::SetAudioHighPass(true), 0);
+        assert_eq!(s.ui_state().accuracy_preset, None, "one toggle off the preset is custom");
+
+        s.apply(UiAction::SetAccuracyPreset(AccuracyPreset::Accuracy), 0);
+        assert_eq!(s.ui_state().accuracy_preset, Some(AccuracyPreset::Accuracy));
+        s.run_frame(AbstractInput::none());
+        assert!(!s.gb().scanline_ppu());
+    }
+
+    #[test]
+    fn recent_roms_keep_the_newest_first_without_repeats() {
+        let mut s = session();
+        for n in 0..12 {
+            s.note_recent_rom(&format!("/roms/{n}.gb"));
+        }
+        s.note_recent_rom("/roms/5.gb");
+        let recent = s.ui_state().recent_roms;
+        assert_eq!(recent.len(), crate::config::MAX_RECENT_ROMS);
+        assert_eq!(&recent[..3], ["/roms/5.gb", "/roms/11.gb", "/roms/10.gb"]);
+        assert!(!recent.contains(&"/roms/1.gb".to_owned()), "the oldest falls off");
+
+        s.apply(UiAction::ClearRecentRoms, 0);
+        assert!(s.recent_roms().is_empty());
+    }
+
+    #[test]
+    fn debug_layout_is_kept_in_the_config() {
+        use crate::action::{DebugLayout, DebugWindowLayout};
+        let mut s = session();
+        let layout = DebugLayout {
+            windows: vec![DebugWindowLayout { title: "Memory Explorer".into(), open: true, pos: [320.0, 24.0] }],
+        };
+        s.apply(UiAction::SetDebugLayout(layout.clone()), 0);
+        assert_eq!(s.ui_state().debug_layout, layout);
+        assert_eq!(s.config().debug_layout, layout);
+    }
+
     #[test]
     fn restart_clears_error_overlay() {
         let mut s = session();
@@ -871,6 +1431,128 @@ mod tests {
         assert!(s.fetched_cheats().is_empty());
     }
 
+    // Erasing blanks the cart's SRAM and reports it; a cart without a battery
+    // gets an error instead.
+    #[test]
+    fn erase_battery_save_blanks_sram() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0147] = 0x03; // MBC1+RAM+BATTERY
+        rom[0x0149] = 0x02; // 8 KiB
+        let mut s = session();
+        s.finish_load_rom(&rom).expect("load rom");
+        s.import_battery(&[0x5A; 0x2000]).unwrap();
+        assert_eq!(s.ui_state().battery_size, 0x2000);
+
+        let out = s.apply(UiAction::EraseBatterySave, 0);
+        assert!(matches!(out.requests.as_slice(), [PlatformRequest::Status(_)]));
+        assert!(s.export_battery().unwrap().iter().all(|&b| b == 0xFF));
+
+        s.finish_load_rom(&vec![0u8; 0x8000]).expect("load rom");
+        assert_eq!(s.ui_state().battery_size, 0);
+        let out = s.apply(UiAction::EraseBatterySave, 0);
+        assert!(matches!(out.requests.as_slice(), [PlatformRequest::Error(_)]));
+    }
+
+    // With auto-save on, the exit snapshot is offered back the next time the
+    // same ROM loads (not for another game), and accepting restores it.
+    #[test]
+    fn auto_save_is_offered_when_the_rom_returns() {
+        use crate::AbstractInput;
+        let rom = |title: u8| {
+            let mut r = vec![0u8; 0x8000];
+            r[0x0134] = title;
+            r
+        };
+        let mut s = session();
+        s.finish_load_rom(&rom(b'A')).expect("load rom");
+        for _ in 0..3 {
+            s.run_frame(AbstractInput::none());
+        }
+        assert!(!s.auto_save(99).unwrap(), "off by default");
+
+        s.apply(UiAction::SetAutoSaveState(true), 0);
+        assert!(s.auto_save(99).unwrap());
+        assert!(s.ui_state().slots.is_empty(), "the auto slot is not a numbered slot");
+
+        s.finish_load_rom(&rom(b'B')).expect("load rom");
+        assert_eq!(s.ui_state().resume_offer, None);
+        s.finish_load_rom(&rom(b'A')).expect("load rom");
+        assert_eq!(s.ui_state().resume_offer, Some(99));
+
+        let out = s.apply(UiAction::ResumeAutoSave, 0);
+        assert!(matches!(out.requests.last(), Some(PlatformRequest::Status(_))));
+        assert_eq!(s.frame_count(), 3);
+        assert_eq!(s.resume_offer(), None);
+
+        // Declining (or restarting) closes the offer without loading.
+        s.finish_load_rom(&rom(b'B')).expect("load rom");
+        s.finish_load_rom(&rom(b'A')).expect("load rom");
+        s.apply(UiAction::DismissResume, 0);
+        assert_eq!(s.resume_offer(), None);
+        assert_eq!(s.frame_count(), 0);
+    }
+
+    // Recovery snapshots accrue every ten seconds of play; restoring one
+    // consumes it and winds the frame counter back to it.
+    #[test]
+    fn recovery_snapshot_restores_and_is_consumed() {
+        use crate::AbstractInput;
+        let mut s = session();
+        for _ in 0..605 {
+            s.run_frame(AbstractInput::none());
+        }
+        assert_eq!(s.ui_state().recovery_snapshot, Some(600));
+
+        let out = s.apply(UiAction::RestoreRecoverySnapshot, 0);
+        assert!(out.pause_changed);
+        assert!(matches!(out.requests.first(), Some(PlatformRequest::ClearError)));
+        assert_eq!(s.frame_count(), 600);
+        assert_eq!(s.recovery_snapshot(), None);
+        let out = s.apply(UiAction::RestoreRecoverySnapshot, 0);
+        assert!(matches!(out.requests.last(), Some(PlatformRequest::Error(_))));
+    }
+
+    // A zip holding two ROMs is held for the chooser instead of loading the
+    // first; the pick loads that member, and a dismiss drops the archive.
+    #[test]
+    fn multi_rom_archive_waits_for_a_choice() {
+        use zip::write::SimpleFileOptions;
+        let rom = |title: u8| {
+            let mut r = vec![0u8; 0x8000];
+            r[0x0134] = title;
+            r[0x014D] = r[0x0134..0x014D].iter().fold(0u8, |a, &b| a.wrapping_sub(b).wrapping_sub(1));
+            r
+        };
+        let mut w = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let opts = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, data) in [("a.gb", rom(b'A')), ("b.gb", rom(b'B'))] {
+            w.start_file(name, opts).unwrap();
+            std::io::Write::write_all(&mut w, &data).unwrap();
+        }
+        let archive = w.finish().unwrap().into_inner();
+
+        let mut s = session();
+        match s.finish_file(LoadPurpose::Rom, &archive).requests.as_slice() {
+            [PlatformRequest::Status(msg)] => assert!(msg.contains("2 ROMs"), "{msg}"),
+            other => panic!("unexpected: {other:?}"),
+        }
+        assert!(!s.gb().has_rom(), "nothing loads until the user picks");
+        assert_eq!(s.ui_state().archive_choices, ["a.gb", "b.gb"]);
+
+        let out = s.apply(UiAction::ChooseArchiveEntry("b.gb".into()), 0);
+        assert!(out.requests.iter().any(|r| matches!(r, PlatformRequest::ClearError)));
+        assert_eq!(s.original_rom_bytes(), Some(&rom(b'B')[..]));
+        assert!(s.ui_state().archive_choices.is_empty());
+
+        // Dismissing drops the archive; a late pick is then an error.
+        assert!(s.finish_file(LoadPurpose::Rom, &archive).succeeded());
+        s.apply(UiAction::DismissArchiveChoice, 0);
+        assert!(s.archive_choices().is_empty());
+        let out = s.apply(UiAction::ChooseArchiveEntry("a.gb".into()), 0);
+        assert!(matches!(out.requests.as_slice(), [PlatformRequest::Error(_)]));
+        assert_eq!(s.original_rom_bytes(), Some(&rom(b'B')[..]), "the loaded game is untouched");
+    }
+
     // Regression: "Clear All" once looped RemoveBreakpoint into a single
     // Option slot, so only the last breakpoint was removed. One
     // ClearBreakpoints must remove every breakpoint.
@@ -8,7 +8,7 @@
 //! into the returned `FrameOutput`. No wall clock, no device — purely a
 //! collector, so it stays WASM-clean.
 
-use rustyboi_core_lib::audio::AudioOutput;
+use rustyboi_core_lib::audio::{Audio, AudioOutput, ChannelSample};
 use std::sync::{Arc, Mutex};
 
 /// Shared, drainable buffer of stereo samples produced since the last drain.
@@ -44,6 +44,208 @@ impl AudioOutput for CaptureSink {
     }
 }
 
+/// File names of the per-channel stems, in APU channel order.
+pub const STEM_NAMES: [&str; 4] = ["pulse1", "pulse2", "wave", "noise"];
+
+/// The APU's output capacitor per 4 194 304 Hz cycle on DMG silicon (blargg),
+/// used to block the stems' DC the way the core's high-pass does the mix's.
+const CHARGE_PER_CYCLE: f32 = 0.999958;
+
+/// A finished capture to save: the suggested file name and its bytes.
+pub type AudioFile = (String, Vec<u8>);
+
+/// Where an audio capture's tracks go as they are recorded.
+pub trait AudioSink: crate::ports::MaybeSend {
+    /// More of the mix: interleaved 16-bit little-endian stereo.
+    fn mix(&mut self, pcm: &[u8]);
+    /// More of each stem, in [`STEM_NAMES`] order, in the mix's format.
+    fn stems(&mut self, pcm: [&[u8]; 4]);
+    /// Close the capture. A sink that kept it in memory hands back the file
+    /// to save (name and bytes); one that streamed it out finishes on its
+    /// own side and returns `None`.
+    fn finish(self: Box<Self>) -> Option<AudioFile> {
+        None
+    }
+}
+
+/// Opens the sink for a new capture at `sample_rate`, with stems when the
+/// flag is set. `Ok(None)` when the user backed out (a cancelled save
+/// dialog); an `Err` is shown as the reason recording did not start.
+#[cfg(not(target_arch = "wasm32"))]
+pub type AudioSinkOpener = Box<dyn FnMut(u32, bool) -> Result<Option<Box<dyn AudioSink>>, String> + Send>;
+/// Opens the sink for a new capture at `sample_rate`, with stems when the
+/// flag is set. `Ok(None)` when the user backed out (a cancelled save
+/// dialog); an `Err` is shown as the reason recording did not start.
+#[cfg(target_arch = "wasm32")]
+pub type AudioSinkOpener = Box<dyn FnMut(u32, bool) -> Result<Option<Box<dyn AudioSink>>, String>>;
+
+/// An in-progress WAV capture (Emulation → Record Audio): the mix exactly as
+/// heard, before master volume, plus optionally one stereo stem per channel
+/// rebuilt from the core's pre-mixer channel tap. Only the stems' filter
+/// state is kept here; the PCM goes to the [`AudioSink`] a frame at a time.
+pub(crate) struct AudioRecording {
+    sample_rate: u32,
+    /// Stereo samples of the mix handed to the sink so far.
+    samples: u64,
+    /// The stems' DC-blocking capacitors, one per side; `None` without stems.
+    caps: Option<[(f32, f32); 4]>,
+    sink: Box<dyn AudioSink>,
+}
+
+impl AudioRecording {
+    pub(crate) fn new(sample_rate: u32, stems: bool, sink: Box<dyn AudioSink>) -> Self {
+        AudioRecording { sample_rate, samples: 0, caps: stems.then_some([(0.0, 0.0); 4]), sink }
+    }
+
+    /// Whether the channel tap must be engaged to feed the stems.
+    pub(crate) fn wants_tap(&self) -> bool {
+        self.caps.is_some()
+    }
+
+    /// Append a frame's worth of mixed output.
+    pub(crate) fn push_mix(&mut self, samples: &[(f32, f32)]) {
+        let mut pcm = Vec::with_capacity(samples.len() * 4);
+        for &(l, r) in samples {
+            push_pcm(&mut pcm, l, r);
+        }
+        self.samples += samples.len() as u64;
+        self.sink.mix(&pcm);
+    }
+
+    /// Append a frame's worth of tap samples to the stems. Each stem is the
+    /// mix with just its channel's share, i.e. the full mix minus the mix
+    /// with that channel's level zeroed, so NR50/NR51 panning and volume are
+    /// kept. The tap is point-sampled at the output rate rather than taken
+    /// through the mix's band-limiting resampler, so a stem carries the
+    /// aliasing of the channel's edges and the four only approximately sum
+    /// to the mix (and never include the AGB's constant unrouted level).
+    /// `high_pass` mirrors Settings → the APU high-pass.
+    pub(crate) fn push_tap(&mut self, tap: &[ChannelSample], agb: bool, high_pass: bool) {
+        let Some(caps) = self.caps.as_mut() else { return };
+        let charge = CHARGE_PER_CYCLE.powf(4_194_304.0 / self.sample_rate as f32);
+        let mut pcm: [Vec<u8>; 4] = std::array::from_fn(|_| Vec::with_capacity(tap.len() * 4));
+        for &sample in tap {
+            let (l, r) = Audio::mix_tap_sample(sample, agb);
+            for (c, (pcm, cap)) in pcm.iter_mut().zip(caps.iter_mut()).enumerate() {
+                let (mut levels, nr50, nr51, enabled) = sample;
+                levels[c] = 0.0;
+                let (without_l, without_r) = Audio::mix_tap_sample((levels, nr50, nr51, enabled), agb);
+                let (mut sl, mut sr) = (l - without_l, r - without_r);
+                if high_pass {
+                    (sl, cap.0) = (sl - cap.0, sl - (sl - cap.0) * charge);
+                    (sr, cap.1) = (sr - cap.1, sr - (sr - cap.1) * charge);
+                }
+                push_pcm(pcm, sl, sr);
+            }
+        }
+        self.sink.stems([&pcm[0], &pcm[1], &pcm[2], &pcm[3]]);
+    }
+
+    /// Seconds of audio captured so far.
+    pub(crate) fn seconds(&self) -> f64 {
+        self.samples as f64 / f64::from(self.sample_rate)
+    }
+
+    /// Close the capture, returning the file to save when the sink kept it
+    /// in memory.
+    pub(crate) fn finish(self) -> Option<AudioFile> {
+        self.sink.finish()
+    }
+}
+
+/// The sink the GUI toggle uses where the host streams nowhere (web,
+/// mobile): the tracks accumulate as PCM and leave as one saveable file,
+/// `recording.wav` for the mix alone or `recording.zip` holding `mix.wav`
+/// plus one WAV per stem (one save instead of five). An hour of the mix
+/// alone is about 690 MB at 48 kHz, five times that with stems.
+pub(crate) struct MemorySink {
+    sample_rate: u32,
+    mix: Vec<u8>,
+    stems: Option<[Vec<u8>; 4]>,
+}
+
+impl MemorySink {
+    pub(crate) fn new(sample_rate: u32, stems: bool) -> Self {
+        MemorySink { sample_rate, mix: Vec::new(), stems: stems.then(Default::default) }
+    }
+}
+
+impl AudioSink for MemorySink {
+    fn mix(&mut self, pcm: &[u8]) {
+        self.mix.extend_from_slice(pcm);
+    }
+
+    fn stems(&mut self, pcm: [&[u8]; 4]) {
+        if let Some(stems) = self.stems.as_mut() {
+            for (stem, pcm) in stems.iter_mut().zip(pcm) {
+                stem.extend_from_slice(pcm);
+            }
+        }
+    }
+
+    fn finish(self: Box<Self>) -> Option<AudioFile> {
+        let rate = self.sample_rate;
+        let mix = wav(rate, &self.mix);
+        let Some(stems) = self.stems else {
+            return Some(("recording.wav".into(), mix));
+        };
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let opts = SimpleFileOptions::default();
+        let files = std::iter::once(("mix", mix)).chain(STEM_NAMES.into_iter().zip(stems.map(|pcm| wav(rate, &pcm))));
+        for (name, wav) in files {
+            // An in-memory writer only fails on a zip64-sized entry, which an
+            // emulator session will not reach; drop the entry rather than
+            // the whole export if it ever does.
+            if zip.start_file(format!("{name}.wav"), opts).is_ok() {
+                let _ = zip.write_all(&wav);
+            }
+        }
+        Some(("recording.zip".into(), zip.finish().map(|c| c.into_inner()).unwrap_or_default()))
+    }
+}
+
+/// Append one stereo sample as 16-bit little-endian PCM, clipping at full
+/// scale.
+pub(crate) fn push_pcm(pcm: &mut Vec<u8>, l: f32, r: f32) {
+    for v in [l, r] {
+        let s = (v.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        pcm.extend_from_slice(&s.to_le_bytes());
+    }
+}
+
+/// Wrap interleaved 16-bit stereo PCM in a canonical 44-byte RIFF/WAVE header.
+pub(crate) fn wav(sample_rate: u32, pcm: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(44 + pcm.len());
+    out.extend_from_slice(&wav_header(sample_rate, pcm.len() as u32));
+    out.extend_from_slice(pcm);
+    out
+}
+
+/// The canonical 44-byte RIFF/WAVE header for `data_len` bytes of
+/// interleaved 16-bit stereo PCM. Hosts that stream a WAV to disk write it
+/// first as a placeholder and again over the start once the length is known.
+pub fn wav_header(sample_rate: u32, data_len: u32) -> [u8; 44] {
+    const CHANNELS: u16 = 2;
+    const BITS: u16 = 16;
+    let block_align = CHANNELS * BITS / 8;
+    let mut out = [0; 44];
+    out[..4].copy_from_slice(b"RIFF");
+    out[4..8].copy_from_slice(&(36u32.wrapping_add(data_len)).to_le_bytes());
+    out[8..16].copy_from_slice(b"WAVEfmt ");
+    out[16..20].copy_from_slice(&16u32.to_le_bytes());
+    out[20..22].copy_from_slice(&1u16.to_le_bytes()); // PCM
+    out[22..24].copy_from_slice(&CHANNELS.to_le_bytes());
+    out[24..28].copy_from_slice(&sample_rate.to_le_bytes());
+    out[28..32].copy_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+    out[32..34].copy_from_slice(&block_align.to_le_bytes());
+    out[34..36].copy_from_slice(&BITS.to_le_bytes());
+    out[36..40].copy_from_slice(b"data");
+    out[40..44].copy_from_slice(&data_len.to_le_bytes());
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,4 +271,63 @@ mod tests {
             vec![(0.25, -0.25), (0.5, -0.5)]
         );
     }
+
+    #[test]
+    fn wav_header_describes_16_bit_stereo() {
+        let mut rec = AudioRecording::new(48_000, false, Box::new(MemorySink::new(48_000, false)));
+        rec.push_mix(&[(1.0, -1.0), (0.0, 2.0)]);
+        let (name, bytes) = rec.finish().unwrap();
+        assert_eq!(name, "recording.wav");
+        assert_eq!(&bytes[..4], b"RIFF");
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 36 + 8);
+        assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 48_000);
+        assert_eq!(u32::from_le_bytes(bytes[40..44].try_into().unwrap()), 8);
+        let samples: Vec<i16> =
+            bytes[44..].chunks(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+        // Full scale both ways; out-of-range input clips.
+        assert_eq!(samples, [i16::MAX, -i16::MAX, 0, i16::MAX]);
+    }
+
+    /// A sink that shares the stems it receives with the test.
+    struct StemTaps(Arc<Mutex<[Vec<u8>; 4]>>);
+
+    impl AudioSink for StemTaps {
+        fn mix(&mut self, _: &[u8]) {}
+        fn stems(&mut self, pcm: [&[u8]; 4]) {
+            for (stem, pcm) in self.0.lock().unwrap().iter_mut().zip(pcm) {
+                stem.extend_from_slice(pcm);
+            }
+        }
+    }
+
+    /// Each stem carries only its channel, panned by NR51: pulse 1 routed
+    /// left-only lands in the left of its stem and nowhere else.
+    #[test]
+    fn stems_isolate_each_channel_with_its_panning() {
+        let stems = Arc::default();
+        let mut rec = AudioRecording::new(44_100, true, Box::new(StemTaps(Arc::clone(&stems))));
+        assert!(rec.wants_tap());
+        // Pulse 1 at 0.8 routed left (NR51 bit 4), the noise at -0.4 routed
+        // both ways, full master volume.
+        let sample: ChannelSample = ([0.8, 0.0, 0.0, -0.4], 0x77, 0x98, true);
+        rec.push_tap(&[sample], false, false);
+        assert!(rec.finish().is_none());
+        let stems = stems.lock().unwrap();
+        let first = |pcm: &Vec<u8>| {
+            let l = i16::from_le_bytes([pcm[0], pcm[1]]);
+            let r = i16::from_le_bytes([pcm[2], pcm[3]]);
+            (l, r)
+        };
+        // Within one LSB: the difference of two mixes rounds differently
+        // from the channel's share computed directly.
+        let near = |got: (i16, i16), want: (f32, f32)| {
+            let lsb = |v: f32| v * f32::from(i16::MAX);
+            (f32::from(got.0) - lsb(want.0)).abs() <= 1.0 && (f32::from(got.1) - lsb(want.1)).abs() <= 1.0
+        };
+        assert!(near(first(&stems[0]), (0.8 / 4.0, 0.0)), "{:?}", first(&stems[0]));
+        assert_eq!(first(&stems[1]), (0, 0));
+        assert_eq!(first(&stems[2]), (0, 0));
+        assert!(near(first(&stems[3]), (-0.4 / 4.0, -0.4 / 4.0)), "{:?}", first(&stems[3]));
+    }
 }
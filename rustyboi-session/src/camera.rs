@@ -0,0 +1,181 @@
+//! Host image sources for the Game Boy Camera sensor.
+//!
+//! The core's M64282FP model takes a 128x112 grayscale frame through
+//! `Cartridge::set_camera_image` and falls back to its built-in test pattern
+//! until one arrives; the session pumps frames from the [`Webcam`] port each
+//! frame. This module holds the source every frontend can offer without a
+//! capture API: a still picture, decoded and fitted to the sensor once and then
+//! served every frame. Live capture is frontend-specific and implements
+//! [`Webcam`] there: the desktop build's `--camera-device` reads a host webcam
+//! through ffmpeg. The web build has no capture backend yet (its port is a
+//! stub that never yields a frame).
+//!
+//! Only binary/ASCII PGM (`P5`/`P2`) and headerless 128x112 raw dumps are
+//! decoded: both are trivially produced by any image tool (`convert in.png
+//! out.pgm`) and keep this crate free of an image-codec dependency.
+
+use crate::ports::{Webcam, WEBCAM_PIXELS};
+
+/// Sensor geometry the session hands the core (matches the core's CAM_W/CAM_H).
+const SENSOR_W: usize = 128;
+const SENSOR_H: usize = 112;
+
+/// A [`Webcam`] that yields the same fitted still image every frame.
+pub struct StillImage {
+    pixels: Vec<u8>,
+}
+
+impl StillImage {
+    /// Decode `bytes` (PGM or a raw 128x112 dump) and fit it to the sensor.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() == WEBCAM_PIXELS && !matches!(bytes.get(..2), Some(b"P5" | b"P2")) {
+            return Ok(Self { pixels: bytes.to_vec() });
+        }
+        let (w, h, gray) = decode_pgm(bytes)?;
+        Ok(Self { pixels: fit_to_sensor(w, h, &gray) })
+    }
+}
+
+impl Webcam for StillImage {
+    fn grab(&mut self) -> Option<Vec<u8>> {
+        Some(self.pixels.clone())
+    }
+}
+
+/// Decode a PGM into `(width, height, 8-bit gray)`. Samples of any maxval
+/// (8- or 16-bit) are rescaled to 0..=255.
+fn decode_pgm(bytes: &[u8]) -> Result<(usize, usize, Vec<u8>), String> {
+    let binary = match bytes.get(..2) {
+        Some(b"P5") => true,
+        Some(b"P2") => false,
+        _ => return Err("not a PGM image (expected a P5/P2 header or a raw 128x112 dump)".into()),
+    };
+    // Header: magic, width, height, maxval, separated by whitespace with
+    // `#` comments running to end of line; one whitespace byte ends it.
+    let mut pos = 2;
+    let mut fields = [0usize; 3];
+    for field in &mut fields {
+        loop {
+            match bytes.get(pos) {
+                Some(b'#') => {
+                    while bytes.get(pos).is_some_and(|&b| b != b'\n') {
+                        pos += 1;
+                    }
+                }
+                Some(b) if b.is_ascii_whitespace() => pos += 1,
+                _ => break,
+            }
+        }
+        let start = pos;
+        while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+            pos += 1;
+        }
+        *field = std::str::from_utf8(&bytes[start..pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or("malformed PGM header")?;
+    }
+    let [w, h, maxval] = fields;
+    if w == 0 || h == 0 || maxval == 0 || maxval > 0xFFFF {
+        return Err(format!("unsupported PGM geometry {w}x{h} (maxval {maxval})"));
+    }
+    pos += 1;
+    let count = w.checked_mul(h).ok_or("PGM too large")?;
+    let scale = |v: usize| (v.min(maxval) * 255 / maxval) as u8;
+    let gray: Vec<u8> = if binary {
+        let wide = maxval > 0xFF;
+        let need = count.checked_mul(if wide { 2 } else { 1 }).ok_or("PGM too large")?;
+        let end = pos.checked_add(need).ok_or("PGM too large")?;
+        let data = bytes.get(pos..end).ok_or("truncated PGM data")?;
+        if wide {
+            data.chunks_exact(2).map(|p| scale(usize::from(u16::from_be_bytes([p[0], p[1]])))).collect()
+        } else {
+            data.iter().map(|&v| scale(v as usize)).collect()
+        }
+    } else {
+        let samples: Vec<u8> = std::str::from_utf8(&bytes[pos.min(bytes.len())..])
+            .map_err(|_| "malformed PGM data")?
+            .split_ascii_whitespace()
+            .take(count)
+            .map(|s| s.parse().map(scale).map_err(|_| "malformed PGM sample"))
+            .collect::<Result<_, _>>()?;
+        if samples.len() < count {
+            return Err("truncated PGM data".into());
+        }
+        samples
+    };
+    Ok((w, h, gray))
+}
+
+/// Center-crop `gray` to the sensor's 8:7 aspect and nearest-neighbour
+/// resample it to 128x112, the way a lens would frame the scene.
+fn fit_to_sensor(w: usize, h: usize, gray: &[u8]) -> Vec<u8> {
+    let (crop_w, crop_h) = if w * SENSOR_H > h * SENSOR_W {
+        (h * SENSOR_W / SENSOR_H, h)
+    } else {
+        (w, w * SENSOR_H / SENSOR_W)
+    };
+    let (crop_w, crop_h) = (crop_w.max(1), crop_h.max(1));
+    let (x0, y0) = ((w - crop_w) / 2, (h - crop_h) / 2);
+    let mut out = Vec::with_capacity(WEBCAM_PIXELS);
+    for y in 0..SENSOR_H {
+        let sy = y0 + y * crop_h / SENSOR_H;
+        for x in 0..SENSOR_W {
+            out.push(gray[sy * w + x0 + x * crop_w / SENSOR_W]);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_sensor_dump_passes_through() {
+        let raw: Vec<u8> = (0..WEBCAM_PIXELS).map(|i| (i % 251) as u8 + 1).collect();
+        let mut cam = StillImage::from_bytes(&raw).unwrap();
+        assert_eq!(cam.grab().unwrap(), raw);
+    }
+
+    #[test]
+    fn binary_pgm_is_cropped_and_scaled() {
+        // 512x224 (twice as wide as 8:7): the centre 256x224 is kept, so the
+        // left quarter (black) and right quarter (white) are cropped away.
+        let (w, h) = (512, 224);
+        let mut bytes = format!("P5\n# test\n{w} {h}\n255\n").into_bytes();
+        bytes.extend((0..w * h).map(|i| match i % w {
+            x if x < 128 => 0,
+            x if x >= 384 => 255,
+            _ => 128,
+        }));
+        let px = StillImage::from_bytes(&bytes).unwrap().grab().unwrap();
+        assert_eq!(px.len(), WEBCAM_PIXELS);
+        assert!(px.iter().all(|&p| p == 128));
+    }
+
+    #[test]
+    fn ascii_pgm_rescales_maxval() {
+        let px = StillImage::from_bytes(b"P2 1 1 15 15").unwrap().grab().unwrap();
+        assert!(px.iter().all(|&p| p == 255));
+    }
+
+    #[test]
+    fn rejects_non_images_and_truncation() {
+        assert!(StillImage::from_bytes(b"GIF89a").is_err());
+        assert!(StillImage::from_bytes(b"P5 4 4 255\n\x00\x00").is_err());
+    }
+
+    #[test]
+    fn rejects_geometry_that_overflows() {
+        // Sample count fits, but doubling it for 16-bit samples does not.
+        let wide = format!("P5 {} {} 65535\n", 1usize << (usize::BITS / 2), 1usize << (usize::BITS / 2 - 1));
+        assert!(StillImage::from_bytes(wide.as_bytes()).is_err());
+        // The data end (header length + usize::MAX samples) does not fit.
+        let half = (1usize << (usize::BITS / 2)) as u128;
+        let (w, h) = (half - 1, half + 1);
+        assert_eq!(w * h, usize::MAX as u128);
+        let huge = format!("P5 {w} {h} 255\n");
+        assert!(StillImage::from_bytes(huge.as_bytes()).is_err());
+    }
+}
@@ -6,12 +6,14 @@
 //! No host key codes, paths, or window state — those belong to the adapter.
 
 use crate::action::{
-    DmgPaletteChoice, GbcDmgPalette, GraphicsBackend, LcdEffect, ScalingMode, SgbPaletteChoice,
+    AspectRatio, ChannelMix, DebugLayout, DmgPaletteChoice, DOT_MATRIX_SHADES, FrameSkip, GbcDmgPalette, GraphicsBackend, LcdEffect, ScalingMode, SgbPaletteChoice,
     TextureFilter,
 };
 use crate::input::InputMap;
 use crate::input_config::InputConfig;
+use crate::palette::CustomPalette;
 use crate::ports::{Storage, StorageError};
+use rustyboi_core_lib::cartridge::RtcFormat;
 use rustyboi_core_lib::gb::{Hardware, Region};
 use rustyboi_core_lib::ppu::ColorCorrection;
 use serde::{Deserialize, Serialize};
@@ -78,6 +80,10 @@ pub struct Config {
     /// `color_correction` to produce `dmg_palette.shades`.
     #[serde(default)]
     pub dmg_palette_choice: DmgPaletteChoice,
+    /// The user palettes offered next to the built-ins, in the order they
+    /// were added.
+    #[serde(default)]
+    pub custom_palettes: Vec<CustomPalette>,
     /// CGB colorization for DMG games on CGB/AGB hardware (Auto / a boot-ROM
     /// scheme). `default` (`Auto`) so older blobs still load.
     #[serde(default)]
@@ -99,13 +105,45 @@ pub struct Config {
     /// frame pacing); any other value is the literal speed multiplier (2, 4, 6,
     /// 8, 10, …). Default 4.
     pub fast_forward_factor: u32,
+    /// Drop the audio while fast-forwarding instead of resampling it down to a
+    /// real-time frame's worth (uncapped speed is always silent). `default`
+    /// (off) so older blobs still load.
+    #[serde(default)]
+    pub fast_forward_muted: bool,
+    /// Present without vsync while fast-forwarding (desktop): ticks run back
+    /// to back instead of once per display refresh, so fast-forward goes as
+    /// fast as the host allows, at the cost of tearing. `default` (off) so
+    /// older blobs still load. Presentation-only.
+    #[serde(default)]
+    pub fast_forward_vsync_off: bool,
+    /// Which emulated frames reach the display (see [`FrameSkip`]). `default`
+    /// (off) so older blobs still load. Presentation-only.
+    #[serde(default)]
+    pub frame_skip: FrameSkip,
+    /// Emulation speed in percent of real time, 25..=800 (Emulation →
+    /// Speed). Fast-forward overrides it while engaged. `default` (100) so
+    /// older blobs still load.
+    #[serde(default = "default_emulation_speed")]
+    pub emulation_speed: u32,
+    /// Show each frame from a clone run one frame past the live machine,
+    /// hiding a frame of input lag for twice the emulation work (Emulation →
+    /// Run-Ahead). `default` (off) so older blobs still load.
+    #[serde(default)]
+    pub run_ahead: bool,
     /// Master output volume, 0..=100. Scales the session's drained audio copy
     /// only; the core/APU are untouched. `default` so older blobs still load.
     #[serde(default = "default_volume")]
     pub volume: u8,
+    /// Master mute, independent of `volume` so unmuting restores the level.
+    /// `default` so older blobs still load.
+    #[serde(default)]
+    pub muted: bool,
     /// Frame letterboxing policy. `default` so older blobs still load.
     #[serde(default)]
     pub scaling: ScalingMode,
+    /// The shape the frame is shown at. `default` so older blobs still load.
+    #[serde(default)]
+    pub aspect_ratio: AspectRatio,
     /// Requested rendering backend (desktop only; applied at next launch).
     /// `default` (`Auto`) so older blobs still load.
     #[serde(default)]
@@ -143,6 +181,133 @@ pub struct Config {
     /// default; `default` so older blobs still load. Presentation-only.
     #[serde(default)]
     pub show_fps: bool,
+    /// Whether the DMG dot-matrix filter is on: mono games show in
+    /// [`DOT_MATRIX_SHADES`] and the renderer draws the pixel grid.
+    /// `default` (off) so older blobs still load. Presentation-only.
+    #[serde(default)]
+    pub dot_matrix: bool,
+    /// ROMs (SHA-256, lowercase hex) that run on the scanline renderer
+    /// instead of the per-dot one: cheaper on weak hosts, but mid-line
+    /// raster effects are lost. Empty by default; `default` so older blobs
+    /// still load.
+    #[serde(default)]
+    pub scanline_ppu_roms: Vec<String>,
+    /// Every ROM on the scanline renderer, on top of `scanline_ppu_roms`
+    /// (Settings → Accuracy). Off by default; `default` so older blobs still
+    /// load.
+    #[serde(default)]
+    pub scanline_ppu: bool,
+    /// Whether the DMG OAM corruption bug is emulated (Settings → Accuracy).
+    /// On by default, as on hardware; `default` so older blobs still load.
+    #[serde(default = "default_oam_bug")]
+    pub oam_bug: bool,
+    /// Whether cartridge real-time clocks (MBC3, HuC-3) follow the host wall
+    /// clock, so they keep real time through pauses and fast-forward. Off by
+    /// default (the deterministic cycle-derived clock); `default` so older
+    /// blobs still load. Movie recording/playback always uses the
+    /// cycle-derived clock.
+    #[serde(default)]
+    pub rtc_host_clock: bool,
+    /// Whether the machine is snapshotted to the auto slot on exit and offered
+    /// back on the next load of the same ROM. Off by default; `default` so
+    /// older blobs still load.
+    #[serde(default)]
+    pub auto_save_state: bool,
+    /// Whether emulation pauses while the window is unfocused (desktop).
+    /// Off by default; `default` so older blobs still load.
+    #[serde(default)]
+    pub pause_on_focus_loss: bool,
+    /// Whether the APU's output high-pass filter runs, as on hardware: it
+    /// takes the DC offset out of the mix so held notes and channel
+    /// enable/disable settle toward silence. On by default; off hands out the
+    /// raw mix for those who prefer it.
+    #[serde(default = "default_audio_high_pass")]
+    pub audio_high_pass: bool,
+    /// Per-channel mute/solo overrides. All channels play by default;
+    /// `default` so older blobs still load.
+    #[serde(default)]
+    pub channel_mix: ChannelMix,
+    /// Audio output rate in Hz, one of [`crate::action::SAMPLE_RATES`]. Read
+    /// once at startup, when the device is opened; `default` so older blobs
+    /// still load.
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: u32,
+    /// Audio device buffer in sample pairs per callback, one of
+    /// [`crate::action::AUDIO_BUFFER_SIZES`]; `0` (the default) leaves it to
+    /// the host. Read once at startup, when the device is opened.
+    #[serde(default)]
+    pub audio_buffer_size: u32,
+    /// Emulated frames of audio the pacing regulator keeps queued ahead of the
+    /// device. Higher rides out a bursty host without crackle at the cost of
+    /// latency; `default` so older blobs still load.
+    #[serde(default = "default_audio_latency_frames")]
+    pub audio_latency_frames: u32,
+    /// Name of the preferred audio output device, `None` (the default) for
+    /// whatever the host calls its default. Platforms fall back to the default
+    /// while the named device is missing and move back when it reappears.
+    #[serde(default)]
+    pub audio_device: Option<String>,
+    /// Folder for battery saves (`.sav`/`.rtc`) on hosts that keep them as
+    /// files, one subfolder per ROM; `None` (the default) keeps them next to
+    /// the ROM. `--save-dir` overrides it for one run; `default` so older
+    /// blobs still load.
+    #[serde(default)]
+    pub save_dir: Option<String>,
+    /// Where RTC carts keep their clock on hosts that keep saves as files:
+    /// the `.sav` footer, an `.rtc` sidecar, or both (the default).
+    /// `--rtc-format` overrides it for one run; `default` so older blobs
+    /// still load.
+    #[serde(default)]
+    pub rtc_format: RtcFormat,
+    /// Whether Emulation → Record Audio also writes one WAV per APU channel
+    /// alongside the mix. Off by default; `default` so older blobs still load.
+    #[serde(default)]
+    pub record_stems: bool,
+    /// Whether recorded movies name their starting savestate by hash (kept
+    /// in storage) instead of embedding it. Off by default; `default` so
+    /// older blobs still load.
+    #[serde(default)]
+    pub movie_anchor_by_hash: bool,
+    /// Desktop window size as a multiple of the native picture (View →
+    /// Window Scale); `default` so older blobs still load.
+    #[serde(default = "default_window_scale")]
+    pub window_scale: u8,
+    /// Integer upscale applied to Emulation → Record GIF captures;
+    /// `default` so older blobs still load.
+    #[serde(default = "default_gif_scale")]
+    pub gif_scale: u8,
+    /// Longest GIF capture in seconds of emulated time; frames past it are
+    /// dropped. `default` so older blobs still load.
+    #[serde(default = "default_gif_max_seconds")]
+    pub gif_max_seconds: u32,
+    /// The ROM paths opened most recently, newest first, at most
+    /// [`MAX_RECENT_ROMS`] (File → Recent). `default` so older blobs still
+    /// load.
+    #[serde(default)]
+    pub recent_roms: Vec<String>,
+    /// Where the debug windows were left (see [`DebugLayout`]). `default`
+    /// so older blobs still load.
+    #[serde(default)]
+    pub debug_layout: DebugLayout,
+}
+
+/// How many ROM paths File → Recent remembers.
+pub const MAX_RECENT_ROMS: usize = 10;
+
+fn default_emulation_speed() -> u32 {
+    100
+}
+
+fn default_window_scale() -> u8 {
+    5
+}
+
+fn default_gif_scale() -> u8 {
+    2
+}
+
+fn default_gif_max_seconds() -> u32 {
+    30
 }
 
 fn default_volume() -> u8 {
@@ -159,6 +324,22 @@ fn default_touch_opacity() -> u8 {
     100
 }
 
+fn default_audio_high_pass() -> bool {
+    true
+}
+
+fn default_oam_bug() -> bool {
+    true
+}
+
+fn default_sample_rate() -> u32 {
+    crate::action::SAMPLE_RATES[0]
+}
+
+fn default_audio_latency_frames() -> u32 {
+    crate::pacing::TARGET_MIN_BACKLOG_FRAMES
+}
+
 /// Frames emulated per presented frame while fast-forward is *uncapped*. A
 /// modest batch amortizes per-present overhead (egui + GPU) so emulation isn't
 /// throttled by the present rate, while the display still refreshes often.
@@ -171,13 +352,21 @@ impl Default for Config {
             region: Region::default(),
             dmg_palette: DmgPalette::default(),
             dmg_palette_choice: DmgPaletteChoice::default(),
+            custom_palettes: Vec::new(),
             gbc_dmg_palette: GbcDmgPalette::default(),
             sgb_palette: SgbPaletteChoice::default(),
             input_map: InputMap::default(),
             rewind: RewindConfig::default(),
             fast_forward_factor: 4,
+            fast_forward_muted: false,
+            fast_forward_vsync_off: false,
+            frame_skip: FrameSkip::Off,
+            emulation_speed: default_emulation_speed(),
+            run_ahead: false,
             volume: 100,
+            muted: false,
             scaling: ScalingMode::default(),
+            aspect_ratio: AspectRatio::default(),
             graphics_backend: GraphicsBackend::default(),
             color_correction: ColorCorrection::default(),
             use_real_boot_rom: false,
@@ -187,6 +376,28 @@ impl Default for Config {
             touch_opacity: default_touch_opacity(),
             input: InputConfig::default(),
             show_fps: false,
+            dot_matrix: false,
+            scanline_ppu_roms: Vec::new(),
+            rtc_host_clock: false,
+            auto_save_state: false,
+            pause_on_focus_loss: false,
+            audio_high_pass: default_audio_high_pass(),
+            scanline_ppu: false,
+            oam_bug: default_oam_bug(),
+            channel_mix: ChannelMix::default(),
+            sample_rate: default_sample_rate(),
+            audio_buffer_size: 0,
+            audio_latency_frames: default_audio_latency_frames(),
+            audio_device: None,
+            save_dir: None,
+            rtc_format: RtcFormat::default(),
+            record_stems: false,
+            movie_anchor_by_hash: false,
+            window_scale: default_window_scale(),
+            gif_scale: default_gif_scale(),
+            gif_max_seconds: default_gif_max_seconds(),
+            recent_roms: Vec::new(),
+            debug_layout: DebugLayout::default(),
         }
     }
 }
@@ -209,6 +420,16 @@ impl Config {
         storage.write(CONFIG_KEY, &bytes)
     }
 
+    /// The mono palette the machine actually shows: the dot-matrix filter's
+    /// olive green while it is on, else the chosen `dmg_palette_choice`.
+    pub fn shown_dmg_palette(&self) -> DmgPaletteChoice {
+        if self.dot_matrix {
+            DmgPaletteChoice::Custom(DOT_MATRIX_SHADES)
+        } else {
+            self.dmg_palette_choice
+        }
+    }
+
     /// Frames emulated per presented frame while fast-forwarding. For a finite
     /// speed this is the multiplier itself; uncapped (`0`) uses a modest batch
     /// so the display keeps refreshing while the platform runs unthrottled.
@@ -225,8 +446,12 @@ impl Config {
         self.fast_forward_factor == 0
     }
 
-    /// Master volume as a 0.0..=1.0 multiplier for the drained audio copy.
+    /// Master volume as a 0.0..=1.0 multiplier for the drained audio copy
+    /// (0.0 while muted).
     pub fn volume_gain(&self) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
         self.volume.min(100) as f32 / 100.0
     }
 }
@@ -17,6 +17,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use rustyboi_core_lib::audio;
 use rustyboi_core_lib::memory::mmio;
 use rustyboi_core_lib::ppu;
 
@@ -38,19 +39,21 @@ pub struct DebugDetail {
     pub palettes: bool,
     /// A stack window around SP (Stack Explorer).
     pub stack: bool,
-    /// Cartridge header facts + CRC/checksums (Cartridge Info). Gated because
+    /// Cartridge header facts + CRC/checksums (ROM Info). Gated because
     /// the CRC/global-checksum scan the whole ROM.
     pub cartridge: bool,
+    /// Channel readouts + the scope's last frame of levels (APU).
+    pub apu: bool,
 }
 
 impl DebugDetail {
     /// Nothing requested — the common case (no debug panel open).
     pub fn is_empty(&self) -> bool {
-        !(self.memory || self.vram || self.oam || self.palettes || self.stack || self.cartridge)
+        !(self.memory || self.vram || self.oam || self.palettes || self.stack || self.cartridge || self.apu)
     }
 
     /// Pack the section flags into a byte bitmask for the compact
-    /// main-thread→worker web message (bit 0 memory … bit 5 cartridge).
+    /// main-thread→worker web message (bit 0 memory … bit 6 apu).
     pub fn to_bits(self) -> u8 {
         (self.memory as u8)
             | (self.vram as u8) << 1
@@ -58,6 +61,7 @@ impl DebugDetail {
             | (self.palettes as u8) << 3
             | (self.stack as u8) << 4
             | (self.cartridge as u8) << 5
+            | (self.apu as u8) << 6
     }
 
     /// Inverse of [`DebugDetail::to_bits`].
@@ -69,6 +73,7 @@ impl DebugDetail {
             palettes: bits & 0x08 != 0,
             stack: bits & 0x10 != 0,
             cartridge: bits & 0x20 != 0,
+            apu: bits & 0x40 != 0,
         }
     }
 
@@ -81,6 +86,7 @@ impl DebugDetail {
             palettes: self.palettes || other.palettes,
             stack: self.stack || other.stack,
             cartridge: self.cartridge || other.cartridge,
+            apu: self.apu || other.apu,
         }
     }
 }
@@ -100,6 +106,10 @@ pub struct CpuState {
     pub pc: u16,
     pub sp: u16,
     pub ime: bool,
+    /// CPU T-cycles since power-on or reset (`GB::cycle_count`).
+    pub cycles: u64,
+    /// Instructions executed since power-on or reset (`GB::instruction_count`).
+    pub instructions: u64,
 }
 
 /// PPU pipeline state (formerly read via `get_ppu_debug_info`'s live `&Ppu`).
@@ -171,7 +181,7 @@ pub struct StackWindow {
     pub bytes: Vec<u8>,
 }
 
-/// Decoded cartridge header + integrity facts for the Cartridge Info panel.
+/// Decoded cartridge header + integrity facts for the ROM Info panel.
 /// Static for the life of a ROM apart from the live bank fields. `DebugDetail::cartridge`.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CartInfo {
@@ -193,13 +203,38 @@ pub struct CartInfo {
     /// "Japanese" | "Overseas", or None if the header is unreadable.
     pub destination: Option<String>,
     pub licensee: Option<String>,
+    /// Mask ROM version ($014C).
+    pub version: u8,
     pub crc32: Option<u32>,
+    pub sha1: Option<[u8; 20]>,
+    /// The SHA-1 looked up in the user's verification DAT; `None` without one.
+    pub dump: Option<crate::no_intro::DumpStatus>,
+    /// Header checksum byte as stored at $014D.
+    pub header_checksum: u8,
     pub header_checksum_ok: bool,
+    /// Global checksum as stored at $014E-$014F.
+    pub stored_global_checksum: u16,
+    /// Global checksum computed over the ROM image.
     pub global_checksum: u16,
     /// Live: currently mapped ROM bank in the $4000-$7FFF window.
     pub cur_rom_bank: usize,
 }
 
+/// The APU panel's read-model. `DebugDetail::apu`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApuState {
+    /// NR52 as the CPU reads it.
+    pub nr52: u8,
+    /// NR50 / NR51: master volume and panning.
+    pub nr50: u8,
+    pub nr51: u8,
+    /// CH1 to CH4.
+    pub channels: [audio::ChannelState; 4],
+    /// The last frame's channel levels, one entry per host sample (see
+    /// `Session::apu_scope`); empty until the scope has run a frame.
+    pub scope: Vec<[f32; 4]>,
+}
+
 /// The complete debug read-model. The baseline fields are always present and
 /// small; the `Option` sections are populated per [`DebugDetail`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -227,6 +262,8 @@ pub struct DebugSnapshot {
     pub stack: Option<StackWindow>,
     /// Cartridge header facts. `DebugDetail::cartridge`.
     pub cartridge: Option<CartInfo>,
+    /// APU channel readouts and scope. `DebugDetail::apu`.
+    pub apu: Option<ApuState>,
 }
 
 /// Start of VRAM in the CPU address space.
@@ -268,6 +305,8 @@ impl Session {
             pc: regs.pc,
             sp: regs.sp,
             ime: regs.ime,
+            cycles: gb.cycle_count(),
+            instructions: gb.instruction_count(),
         };
 
         let (ppu_ref, fetcher_pixels) = gb.get_ppu_debug_info();
@@ -325,11 +364,7 @@ impl Session {
         let memory = detail.memory.then(|| (0u16..=0xFFFF).map(&r).collect());
 
         let vram = detail.vram.then(|| {
-            let bank = |b: u8| {
-                (0..VRAM_LEN)
-                    .map(|i| gb.read_vram_bank(b, VRAM_START + i as u16))
-                    .collect::<Vec<u8>>()
-            };
+            let bank = |b: u8| gb.vram_bank(b).map_or_else(|| vec![0xFF; VRAM_LEN], <[u8]>::to_vec);
             [bank(0), bank(1)]
         });
 
@@ -359,9 +394,20 @@ impl Session {
 
         let cartridge = detail
             .cartridge
-            .then(|| gb.cartridge().map(cart_info))
+            .then(|| {
+                gb.cartridge()
+                    .map(|cart| CartInfo { sha1: self.rom_sha1(), dump: self.dump_status(), ..cart_info(cart) })
+            })
             .flatten();
 
+        let apu = detail.apu.then(|| ApuState {
+            nr52: r(audio::NR52),
+            nr50: r(0xFF24),
+            nr51: r(0xFF25),
+            channels: gb.apu_channel_states(),
+            scope: self.apu_scope().map(<[_]>::to_vec).unwrap_or_default(),
+        });
+
         DebugSnapshot {
             cgb,
             cpu,
@@ -375,14 +421,17 @@ impl Session {
             palettes,
             stack,
             cartridge,
+            apu,
         }
     }
 }
 
-/// Decode a [`CartInfo`] from a live cartridge (Cartridge Info panel).
+/// Decode a [`CartInfo`] from a live cartridge (ROM Info panel). The digest
+/// fields are left empty for the caller to fill from the session's cache.
 fn cart_info(cart: &rustyboi_core_lib::cartridge::Cartridge) -> CartInfo {
     use rustyboi_core_lib::cartridge::{CgbSupport, Destination};
     let (_, hi_base) = cart.rom_bases();
+    let (header_checksum, stored_global_checksum) = cart.stored_checksums().unwrap_or_default();
     CartInfo {
         title: cart.title(),
         mapper: cart.mapper_name().to_string(),
@@ -407,8 +456,13 @@ fn cart_info(cart: &rustyboi_core_lib::cartridge::Cartridge) -> CartInfo {
             Destination::Overseas => "Overseas".to_string(),
         }),
         licensee: cart.licensee().map(str::to_string),
+        version: cart.rom_version(),
         crc32: cart.rom_crc32(),
+        sha1: None,
+        dump: None,
+        header_checksum,
         header_checksum_ok: cart.header_checksum_valid(),
+        stored_global_checksum,
         global_checksum: cart.global_checksum(),
         cur_rom_bank: hi_base / 0x4000,
     }
@@ -533,6 +587,8 @@ mod tests {
         assert_eq!(snap.cpu.pc, regs.pc);
         assert_eq!(snap.cpu.sp, regs.sp);
         assert_eq!(snap.cpu.a, regs.a);
+        assert_eq!(snap.cpu.cycles, gb.cycle_count());
+        assert_eq!(snap.cpu.instructions, gb.instruction_count());
         assert_eq!(snap.mmio.lcdc, gb.read_memory(ppu::LCD_CONTROL));
         assert_eq!(snap.mmio.ly, gb.read_memory(ppu::LY));
         assert_eq!(snap.mmio.ie, gb.read_memory(0xFFFF));
@@ -559,16 +615,42 @@ mod tests {
             palettes: true,
             stack: true,
             cartridge: true,
+            apu: true,
         };
         let snap = session.debug_snapshot(detail);
         assert_eq!(snap.memory.as_ref().map(Vec::len), Some(0x10000));
         let vram = snap.vram.as_ref().expect("vram populated");
         assert_eq!(vram[0].len(), VRAM_LEN);
         assert_eq!(vram[1].len(), VRAM_LEN);
+        for addr in [0x8000u16, 0x8123, 0x9FFF] {
+            assert_eq!(snap.vram_byte(0, addr), session.gb().read_vram_bank(0, addr));
+        }
+        assert!(vram[1].iter().all(|&b| b == 0xFF), "DMG has no bank 1");
         assert_eq!(snap.oam.as_ref().map(Vec::len), Some(OAM_LEN));
         assert!(snap.stack.is_some());
     }
 
+    #[test]
+    fn apu_scope_holds_the_last_frame_while_it_is_on() {
+        use crate::input::AbstractInput;
+        let mut session = booted_session(Hardware::DMG);
+        let detail = DebugDetail { apu: true, ..Default::default() };
+        session.run_frame(AbstractInput::none());
+        let apu = session.debug_snapshot(detail).apu.expect("apu populated");
+        assert!(apu.scope.is_empty(), "no scope before it is switched on");
+        assert_eq!(apu.nr52 & 0x70, 0x70);
+
+        session.set_apu_scope(true);
+        session.run_frame(AbstractInput::none());
+        session.run_frame(AbstractInput::none());
+        let scope = session.debug_snapshot(detail).apu.expect("apu populated").scope;
+        let per_frame = f64::from(crate::pacing::HOST_SAMPLE_RATE) / crate::pacing::NOMINAL_FPS;
+        assert!((scope.len() as f64 - per_frame).abs() < 2.0, "{} levels for one frame", scope.len());
+
+        session.set_apu_scope(false);
+        assert!(session.apu_scope().is_none());
+    }
+
     #[test]
     fn bincode_round_trip_is_lossless() {
         let session = booted_session(Hardware::CGB);
@@ -579,6 +661,7 @@ mod tests {
             palettes: true,
             stack: true,
             cartridge: true,
+            apu: true,
         };
         let snap = session.debug_snapshot(detail);
         let bytes = snap.to_bytes();
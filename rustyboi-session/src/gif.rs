@@ -0,0 +1,416 @@
+//! Animated GIF capture (Emulation → Record GIF).
+//!
+//! Frames arrive at the Game Boy's 59.73 Hz, but GIF delays are whole
+//! centiseconds and browsers clamp anything under 2 cs up to 10 cs, so a
+//! 1.67 cs-per-frame clip cannot be stored frame for frame. Instead each
+//! emulated frame gets its exact start time on a centisecond clock, and a
+//! frame is only written once it starts at least [`MIN_DELAY_CS`] after the
+//! one before; the frames in between are dropped and the previous frame's
+//! delay is the rounded gap. The delays alternate 2/3 cs (about 40 fps) and
+//! sum to the emulated running time, so a long clip does not drift.
+//!
+//! Unchanged frames are merged into the previous frame's delay. Each frame
+//! carries its own palette: a DMG frame has 4 colours and a CGB one at most
+//! 64 (eight 4-colour BG palettes, eight 3-colour OBJ ones), so the exact
+//! colours always fit; a frame that somehow exceeds 256 falls back to a
+//! 3-3-2 cube. No external deps, wasm-safe.
+
+use std::collections::HashMap;
+
+/// Emulated dots per frame, and the dot clock (both DMG-rate).
+const DOTS_PER_FRAME: u64 = 70_224;
+const DOTS_PER_SECOND: u64 = 4_194_304;
+
+/// The shortest delay every viewer honours; shorter ones are clamped to 10 cs.
+const MIN_DELAY_CS: u64 = 2;
+
+/// LZW codes are at most 12 bits wide.
+const MAX_CODES: u16 = 4096;
+
+/// An in-progress GIF: the header and every frame written so far, plus the
+/// newest frame, held back until the next one fixes its delay.
+pub(crate) struct GifRecording {
+    scale: u32,
+    max_frames: u64,
+    /// Emulated frames seen so far, including dropped ones.
+    frames: u64,
+    /// Frame size in source pixels, fixed by the first frame.
+    size: Option<(u32, u32)>,
+    out: Vec<u8>,
+    /// The newest frame (RGB888) and its start on the centisecond clock.
+    held: Option<(Vec<u8>, u64)>,
+}
+
+impl GifRecording {
+    /// Start a capture that upscales by `scale` (nearest neighbour) and
+    /// stops taking frames after `max_seconds` of emulated time.
+    pub(crate) fn new(scale: u8, max_seconds: u32) -> Self {
+        GifRecording {
+            scale: u32::from(scale.max(1)),
+            max_frames: u64::from(max_seconds) * DOTS_PER_SECOND / DOTS_PER_FRAME,
+            frames: 0,
+            size: None,
+            out: Vec::new(),
+            held: None,
+        }
+    }
+
+    /// Offer one emulated frame of `width` x `height` RGB888. A frame of a
+    /// different size from the first (the SGB border toggled mid-capture) is
+    /// dropped, as is everything past the length cap.
+    pub(crate) fn push(&mut self, width: u32, height: u32, rgb: &[u8]) {
+        if self.is_full() {
+            return;
+        }
+        let start = start_cs(self.frames);
+        self.frames += 1;
+        match self.size {
+            None => {
+                self.size = Some((width, height));
+                self.write_header(width * self.scale, height * self.scale);
+            }
+            Some(size) if size != (width, height) => return,
+            Some(_) => {}
+        }
+        match self.held.take() {
+            None => self.held = Some((rgb.to_vec(), start)),
+            Some((pixels, since)) if start - since < MIN_DELAY_CS || pixels == rgb => {
+                self.held = Some((pixels, since));
+            }
+            Some((pixels, since)) => {
+                self.write_frame(&pixels, start - since);
+                self.held = Some((rgb.to_vec(), start));
+            }
+        }
+    }
+
+    /// Whether the length cap has been reached; later frames are ignored.
+    pub(crate) fn is_full(&self) -> bool {
+        self.frames >= self.max_frames
+    }
+
+    /// Seconds of emulated time captured so far.
+    pub(crate) fn seconds(&self) -> f64 {
+        (self.frames * DOTS_PER_FRAME) as f64 / DOTS_PER_SECOND as f64
+    }
+
+    /// Close the capture and return the GIF file, or `None` when no frame
+    /// arrived.
+    pub(crate) fn finish(mut self) -> Option<Vec<u8>> {
+        let (pixels, since) = self.held.take()?;
+        let delay = (start_cs(self.frames) - since).max(MIN_DELAY_CS);
+        self.write_frame(&pixels, delay);
+        self.out.push(0x3B);
+        Some(self.out)
+    }
+
+    /// The signature, logical screen (no global palette) and the NETSCAPE2.0
+    /// extension that makes viewers loop the clip.
+    fn write_header(&mut self, width: u32, height: u32) {
+        self.out.extend_from_slice(b"GIF89a");
+        self.out.extend_from_slice(&(width as u16).to_le_bytes());
+        self.out.extend_from_slice(&(height as u16).to_le_bytes());
+        self.out.extend_from_slice(&[0x00, 0x00, 0x00]);
+        self.out.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        self.out.extend_from_slice(b"NETSCAPE2.0");
+        self.out.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+    }
+
+    /// One frame shown for `delay_cs`: a graphic control extension, then the
+    /// full-screen image with its own colour table.
+    fn write_frame(&mut self, rgb: &[u8], delay_cs: u64) {
+        let Some((width, height)) = self.size else { return };
+        let (palette, indices) = palettize(rgb);
+        let bits = palette.len().next_power_of_two().trailing_zeros().max(1);
+
+        let delay = delay_cs.min(u64::from(u16::MAX)) as u16;
+        self.out.extend_from_slice(&[0x21, 0xF9, 0x04, 0x04]);
+        self.out.extend_from_slice(&delay.to_le_bytes());
+        self.out.extend_from_slice(&[0x00, 0x00]);
+
+        let (w, h) = (width * self.scale, height * self.scale);
+        self.out.extend_from_slice(&[0x2C, 0, 0, 0, 0]);
+        self.out.extend_from_slice(&(w as u16).to_le_bytes());
+        self.out.extend_from_slice(&(h as u16).to_le_bytes());
+        self.out.push(0x80 | (bits as u8 - 1));
+        for i in 0..1usize << bits {
+            self.out.extend_from_slice(&palette.get(i).copied().unwrap_or_default());
+        }
+
+        let scale = self.scale as usize;
+        let rows = indices.chunks(width as usize).flat_map(|row| std::iter::repeat_n(row, scale));
+        let scaled = rows.flat_map(|row| row.iter().flat_map(move |&i| std::iter::repeat_n(i, scale)));
+        let min_code_size = bits.max(2) as u8;
+        self.out.push(min_code_size);
+        let data = lzw(min_code_size, scaled);
+        for block in data.chunks(255) {
+            self.out.push(block.len() as u8);
+            self.out.extend_from_slice(block);
+        }
+        self.out.push(0x00);
+    }
+}
+
+/// Where emulated frame `n` starts on a centisecond clock, rounded.
+fn start_cs(n: u64) -> u64 {
+    (n * DOTS_PER_FRAME * 100 + DOTS_PER_SECOND / 2) / DOTS_PER_SECOND
+}
+
+/// The frame's distinct colours in first-seen order and each pixel's index,
+/// or a fixed 3-3-2 cube when there are more than 256.
+fn palettize(rgb: &[u8]) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let mut palette = Vec::new();
+    let mut lookup = HashMap::new();
+    let mut indices = Vec::with_capacity(rgb.len() / 3);
+    for px in rgb.chunks_exact(3) {
+        let color = [px[0], px[1], px[2]];
+        let index = *lookup.entry(color).or_insert_with(|| {
+            palette.push(color);
+            palette.len() - 1
+        });
+        if index > 255 {
+            return cube(rgb);
+        }
+        indices.push(index as u8);
+    }
+    (palette, indices)
+}
+
+/// 3 bits of red, 3 of green and 2 of blue, each expanded back to 8 bits.
+fn cube(rgb: &[u8]) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let expand = |v: u8, bits: u32| ((u32::from(v) * 255) / ((1 << bits) - 1)) as u8;
+    let palette = (0..=255u8).map(|i| [expand(i >> 5, 3), expand((i >> 2) & 7, 3), expand(i & 3, 2)]).collect();
+    let indices = rgb.chunks_exact(3).map(|px| (px[0] & 0xE0) | ((px[1] >> 3) & 0x1C) | (px[2] >> 6)).collect();
+    (palette, indices)
+}
+
+/// GIF-flavoured LZW: variable-width codes from `min_code_size + 1` bits up
+/// to 12, packed LSB first, with a clear code up front and whenever the table
+/// fills.
+fn lzw(min_code_size: u8, indices: impl Iterator<Item = u8>) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = Vec::new();
+    let (mut acc, mut acc_bits) = (0u32, 0u32);
+    let mut width = u32::from(min_code_size) + 1;
+    let mut next = end + 1;
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+
+    let mut put = |code: u16, width: u32, out: &mut Vec<u8>| {
+        acc |= u32::from(code) << acc_bits;
+        acc_bits += width;
+        while acc_bits >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            acc_bits -= 8;
+        }
+    };
+
+    put(clear, width, &mut out);
+    let mut prefix: Option<u16> = None;
+    for k in indices {
+        let Some(p) = prefix else {
+            prefix = Some(u16::from(k));
+            continue;
+        };
+        if let Some(&code) = table.get(&(p, k)) {
+            prefix = Some(code);
+            continue;
+        }
+        put(p, width, &mut out);
+        // The decoder adds its entry one code late, so it widens after
+        // reading the code that follows the one that filled this width.
+        if next >= 1 << width && width < 12 {
+            width += 1;
+        }
+        if next < MAX_CODES {
+            table.insert((p, k), next);
+            next += 1;
+        } else {
+            put(clear, width, &mut out);
+            table.clear();
+            next = end + 1;
+            width = u32::from(min_code_size) + 1;
+        }
+        prefix = Some(u16::from(k));
+    }
+    if let Some(p) = prefix {
+        put(p, width, &mut out);
+        if next >= 1 << width && width < 12 {
+            width += 1;
+        }
+    }
+    put(end, width, &mut out);
+    put(0, 7, &mut out); // flush the last partial byte
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reference GIF LZW decoder, written from the spec independently of
+    /// the encoder's bookkeeping.
+    fn unlzw(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            table.clear();
+            table.extend((0..clear).map(|i| vec![i as u8]));
+            table.push(Vec::new());
+            table.push(Vec::new());
+        };
+        reset(&mut table);
+        let mut width = min_code_size as usize + 1;
+        let (mut bit, mut out, mut prev): (usize, Vec<u8>, Option<usize>) = (0, Vec::new(), None);
+        loop {
+            let mut code = 0usize;
+            for i in 0..width {
+                let b = data[(bit + i) / 8] >> ((bit + i) % 8) & 1;
+                code |= usize::from(b) << i;
+            }
+            bit += width;
+            if code == clear {
+                reset(&mut table);
+                width = min_code_size as usize + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match (table.get(code), prev) {
+                (Some(e), _) => e.clone(),
+                (None, Some(p)) => {
+                    let mut e = table[p].clone();
+                    e.push(table[p][0]);
+                    e
+                }
+                (None, None) => panic!("first code {code} is not a literal"),
+            };
+            out.extend_from_slice(&entry);
+            if let Some(p) = prev
+                && table.len() < 4096
+            {
+                let mut e = table[p].clone();
+                e.push(entry[0]);
+                table.push(e);
+                if table.len() == 1 << width && width < 12 {
+                    width += 1;
+                }
+            }
+            prev = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips_through_every_code_width_and_a_table_reset() {
+        // Pseudo-random 4-colour data fills the 4096-entry table (and forces
+        // the clear) within a few tens of thousands of pixels.
+        let mut x = 0x1234_5678u32;
+        let data: Vec<u8> = (0..60_000)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                (x & 3) as u8
+            })
+            .collect();
+        assert_eq!(unlzw(2, &lzw(2, data.iter().copied())), data);
+        let flat = vec![1u8; 10_000];
+        assert_eq!(unlzw(2, &lzw(2, flat.iter().copied())), flat);
+        let wide: Vec<u8> = (0..20_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        assert_eq!(unlzw(8, &lzw(8, wide.iter().copied())), wide);
+    }
+
+    #[test]
+    fn frame_starts_land_on_the_59_7_hz_clock() {
+        assert_eq!(start_cs(0), 0);
+        assert_eq!(start_cs(1), 2);
+        assert_eq!(start_cs(2), 3);
+        // One emulated second is 59.73 frames: 60 frames start at 100.5 cs.
+        assert_eq!(start_cs(60), 100);
+    }
+
+    /// Walk the file's blocks and return the logical screen size and each
+    /// frame's delay.
+    fn frames(gif: &[u8]) -> ((u16, u16), Vec<u16>) {
+        assert_eq!(&gif[..6], b"GIF89a");
+        let size = (u16::from_le_bytes([gif[6], gif[7]]), u16::from_le_bytes([gif[8], gif[9]]));
+        let mut delays = Vec::new();
+        let mut i = 13;
+        loop {
+            match gif[i] {
+                0x21 => {
+                    if gif[i + 1] == 0xF9 {
+                        delays.push(u16::from_le_bytes([gif[i + 4], gif[i + 5]]));
+                    }
+                    i += 2;
+                    while gif[i] != 0 {
+                        i += gif[i] as usize + 1;
+                    }
+                    i += 1;
+                }
+                0x2C => {
+                    let packed = gif[i + 9];
+                    i += 10 + 3 * (2 << (packed & 7));
+                    i += 1;
+                    while gif[i] != 0 {
+                        i += gif[i] as usize + 1;
+                    }
+                    i += 1;
+                }
+                0x3B => return (size, delays),
+                b => panic!("unexpected block {b:#04X} at {i}"),
+            }
+        }
+    }
+
+    fn solid(shade: u8) -> Vec<u8> {
+        vec![shade; 4 * 2 * 3]
+    }
+
+    #[test]
+    fn delays_sum_to_the_emulated_time_without_going_under_two() {
+        let mut rec = GifRecording::new(2, 60);
+        for n in 0..120u32 {
+            rec.push(4, 2, &solid(n as u8));
+        }
+        let ((w, h), delays) = frames(&rec.finish().expect("frames were pushed"));
+        assert_eq!((w, h), (8, 4));
+        assert!(delays.iter().all(|&d| d == 2 || d == 3), "{delays:?}");
+        assert_eq!(delays.iter().map(|&d| u64::from(d)).sum::<u64>(), start_cs(120));
+    }
+
+    #[test]
+    fn still_frames_merge_and_the_cap_stops_the_clip() {
+        let mut rec = GifRecording::new(1, 1);
+        for _ in 0..600 {
+            rec.push(4, 2, &solid(7));
+        }
+        assert!(rec.is_full());
+        let (_, delays) = frames(&rec.finish().unwrap());
+        assert_eq!(delays, [start_cs(59) as u16]);
+    }
+
+    #[test]
+    fn a_frame_of_a_different_size_is_dropped() {
+        let mut rec = GifRecording::new(1, 10);
+        rec.push(4, 2, &solid(0));
+        rec.push(2, 2, &[0; 12]);
+        rec.push(4, 2, &solid(0));
+        let ((w, h), _) = frames(&rec.finish().unwrap());
+        assert_eq!((w, h), (4, 2));
+    }
+
+    #[test]
+    fn more_than_256_colours_fall_back_to_the_cube() {
+        let rgb: Vec<u8> = (0..300u32).flat_map(|i| [i as u8, (i >> 8) as u8, 0x80]).collect();
+        let (palette, indices) = palettize(&rgb);
+        assert_eq!(palette.len(), 256);
+        assert_eq!(indices.len(), 300);
+        let (palette, _) = palettize(&solid(9));
+        assert_eq!(palette, [[9, 9, 9]]);
+    }
+}
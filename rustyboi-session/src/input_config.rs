@@ -41,13 +41,13 @@ pub enum KeyName {
     Num0, Num1, Num2, Num3, Num4, Num5, Num6, Num7, Num8, Num9,
     Up, Down, Left, Right,
     Enter, Space, Tab, Backspace, Escape, Backslash,
-    ShiftLeft, ShiftRight,
+    ShiftLeft, ShiftRight, ControlLeft, ControlRight, AltLeft, AltRight,
     F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
 }
 
 impl KeyName {
     /// Every key, in a stable order (editor dropdowns iterate this).
-    pub const ALL: [KeyName; 60] = [
+    pub const ALL: [KeyName; 64] = [
         KeyName::A, KeyName::B, KeyName::C, KeyName::D, KeyName::E, KeyName::F,
         KeyName::G, KeyName::H, KeyName::I, KeyName::J, KeyName::K, KeyName::L,
         KeyName::M, KeyName::N, KeyName::O, KeyName::P, KeyName::Q, KeyName::R,
@@ -58,6 +58,7 @@ impl KeyName {
         KeyName::Up, KeyName::Down, KeyName::Left, KeyName::Right,
         KeyName::Enter, KeyName::Space, KeyName::Tab, KeyName::Backspace,
         KeyName::Escape, KeyName::Backslash, KeyName::ShiftLeft, KeyName::ShiftRight,
+        KeyName::ControlLeft, KeyName::ControlRight, KeyName::AltLeft, KeyName::AltRight,
         KeyName::F1, KeyName::F2, KeyName::F3, KeyName::F4, KeyName::F5, KeyName::F6,
         KeyName::F7, KeyName::F8, KeyName::F9, KeyName::F10, KeyName::F11, KeyName::F12,
     ];
@@ -82,12 +83,24 @@ impl KeyName {
             KeyName::Backspace => "Backspace", KeyName::Escape => "Escape",
             KeyName::Backslash => "Backslash",
             KeyName::ShiftLeft => "LShift", KeyName::ShiftRight => "RShift",
+            KeyName::ControlLeft => "LCtrl", KeyName::ControlRight => "RCtrl",
+            KeyName::AltLeft => "LAlt", KeyName::AltRight => "RAlt",
             KeyName::F1 => "F1", KeyName::F2 => "F2", KeyName::F3 => "F3",
             KeyName::F4 => "F4", KeyName::F5 => "F5", KeyName::F6 => "F6",
             KeyName::F7 => "F7", KeyName::F8 => "F8", KeyName::F9 => "F9",
             KeyName::F10 => "F10", KeyName::F11 => "F11", KeyName::F12 => "F12",
         }
     }
+
+    /// The name a menu shortcut shows: a modifier without its side.
+    fn shortcut_label(self) -> &'static str {
+        match self {
+            KeyName::ShiftLeft | KeyName::ShiftRight => "Shift",
+            KeyName::ControlLeft | KeyName::ControlRight => "Ctrl",
+            KeyName::AltLeft | KeyName::AltRight => "Alt",
+            _ => self.label(),
+        }
+    }
 }
 
 /// Host-agnostic gamepad button vocabulary. Frontends map their native pad
@@ -197,21 +210,37 @@ impl InputTrigger {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HotkeyAction {
     FastForward,
+    /// Fast-forward only while the chord is held.
+    HoldFastForward,
     Rewind,
     Quicksave,
     Quickload,
+    /// Put back the machine the last state load replaced.
+    UndoLoadState,
     FrameAdvance,
     ToggleFullscreen,
     TogglePause,
     Exit,
     Turbo(GbButton),
+    /// Save to numbered state slot N (0-9).
+    SaveSlot(u8),
+    /// Load numbered state slot N (0-9).
+    LoadSlot(u8),
+    /// Mute/unmute the master output.
+    ToggleMute,
+    /// Resize the desktop window to N× the native picture (1-6).
+    WindowScale(u8),
+    /// Step the emulation speed up / down one notch, or back to 100%.
+    SpeedUp,
+    SlowDown,
+    NormalSpeed,
 }
 
 impl HotkeyAction {
     /// Actions whose effect is a level ("held while active"); the rest are
     /// edge-triggered toggles that fire once when the chord becomes active.
     pub fn is_hold(self) -> bool {
-        matches!(self, HotkeyAction::Rewind | HotkeyAction::Turbo(_))
+        matches!(self, HotkeyAction::Rewind | HotkeyAction::HoldFastForward | HotkeyAction::Turbo(_))
     }
 
     /// The GB button this action consumes (suppressed from normal output while
@@ -226,26 +255,41 @@ impl HotkeyAction {
     pub fn label(self) -> String {
         match self {
             HotkeyAction::FastForward => "Fast-forward".to_string(),
+            HotkeyAction::HoldFastForward => "Fast-forward (hold)".to_string(),
             HotkeyAction::Rewind => "Rewind".to_string(),
             HotkeyAction::Quicksave => "Quicksave".to_string(),
             HotkeyAction::Quickload => "Quickload".to_string(),
+            HotkeyAction::UndoLoadState => "Undo load state".to_string(),
             HotkeyAction::FrameAdvance => "Frame-advance".to_string(),
             HotkeyAction::ToggleFullscreen => "Toggle fullscreen".to_string(),
             HotkeyAction::TogglePause => "Toggle pause".to_string(),
             HotkeyAction::Exit => "Exit".to_string(),
+            HotkeyAction::ToggleMute => "Toggle mute".to_string(),
             HotkeyAction::Turbo(b) => format!("Turbo {}", gb_label(b)),
+            HotkeyAction::SaveSlot(n) => format!("Save slot {n}"),
+            HotkeyAction::LoadSlot(n) => format!("Load slot {n}"),
+            HotkeyAction::WindowScale(n) => format!("Window scale {n}×"),
+            HotkeyAction::SpeedUp => "Speed up".to_string(),
+            HotkeyAction::SlowDown => "Slow down".to_string(),
+            HotkeyAction::NormalSpeed => "Normal speed".to_string(),
         }
     }
 
-    /// Non-Turbo actions, for the editor action dropdown.
-    pub const SIMPLE: [HotkeyAction; 8] = [
+    /// Non-Turbo, non-slot, non-scale actions, for the editor action dropdown.
+    pub const SIMPLE: [HotkeyAction; 14] = [
         HotkeyAction::FastForward,
+        HotkeyAction::HoldFastForward,
         HotkeyAction::Rewind,
         HotkeyAction::Quicksave,
         HotkeyAction::Quickload,
+        HotkeyAction::UndoLoadState,
         HotkeyAction::FrameAdvance,
         HotkeyAction::ToggleFullscreen,
         HotkeyAction::TogglePause,
+        HotkeyAction::ToggleMute,
+        HotkeyAction::SpeedUp,
+        HotkeyAction::SlowDown,
+        HotkeyAction::NormalSpeed,
         HotkeyAction::Exit,
     ];
 }
@@ -257,6 +301,106 @@ pub struct Hotkey {
     pub action: HotkeyAction,
 }
 
+/// A controller the host can tell apart from others, as listed in the
+/// keybind editor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectedPad {
+    /// Stable per-model id (the SDL-style GUID on desktop).
+    pub guid: String,
+    pub name: String,
+}
+
+/// GB-button bindings for one controller model, used instead of the shared
+/// `Pad` triggers in [`InputConfig::gb_bindings`] whenever that controller
+/// is the one being pressed. Keys and chords are unaffected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PadProfile {
+    pub guid: String,
+    /// The controller's name when the profile was made, for display.
+    pub name: String,
+    pub bindings: Vec<(GbButton, Vec<PadButton>)>,
+}
+
+impl PadProfile {
+    /// A profile for `pad` starting from the shared controller bindings.
+    pub fn from_shared(pad: &ConnectedPad, config: &InputConfig) -> Self {
+        let bindings = config
+            .gb_bindings
+            .iter()
+            .map(|(gb, triggers)| {
+                let pads = triggers
+                    .iter()
+                    .filter_map(|t| match t {
+                        InputTrigger::Pad(p) => Some(*p),
+                        _ => None,
+                    })
+                    .collect();
+                (*gb, pads)
+            })
+            .collect();
+        Self { guid: pad.guid.clone(), name: pad.name.clone(), bindings }
+    }
+
+    fn pressed(&self, button: GbButton, held: &HashSet<PadButton>) -> bool {
+        self.bindings.iter().any(|(b, pads)| *b == button && pads.iter().any(|p| held.contains(p)))
+    }
+}
+
+/// How the left analog stick turns into the `LStick*` directions, which bind
+/// like the d-pad.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StickConfig {
+    /// Deflection (0..1) the stick must pass before any direction is held.
+    pub deadzone: f32,
+    /// How readily a push counts as a diagonal: 0 only exactly diagonal,
+    /// 0.5 even eight-way sectors, 1 any off-axis push.
+    pub diagonal_bias: f32,
+}
+
+impl Default for StickConfig {
+    fn default() -> Self {
+        Self { deadzone: 0.5, diagonal_bias: 0.5 }
+    }
+}
+
+impl StickConfig {
+    /// The held `LStick*` directions for a stick at `x` (+right) and `y_up`
+    /// (+up), each in -1..1. Past the deadzone the stronger axis is always
+    /// held; the weaker one joins it when the push is within the diagonal
+    /// sector, whose width is set by [`diagonal_bias`](Self::diagonal_bias).
+    pub fn directions(&self, x: f32, y_up: f32) -> Vec<PadButton> {
+        if x.hypot(y_up) <= self.deadzone {
+            return Vec::new();
+        }
+        let (ax, ay) = (x.abs(), y_up.abs());
+        let (major, minor) = (ax.max(ay), ax.min(ay));
+        // The weaker axis counts once the push is this far off the stronger
+        // one's axis: 45° at bias 0, 22.5° at 0.5, 0° at 1.
+        let threshold = (std::f32::consts::FRAC_PI_4 * (1.0 - self.diagonal_bias.clamp(0.0, 1.0))).tan();
+        let both = minor > 0.0 && minor >= major * threshold;
+        let mut held = Vec::with_capacity(2);
+        if ax >= ay || both {
+            held.push(if x > 0.0 { PadButton::LStickRight } else { PadButton::LStickLeft });
+        }
+        if ay > ax || both {
+            held.push(if y_up > 0.0 { PadButton::LStickUp } else { PadButton::LStickDown });
+        }
+        held
+    }
+}
+
+/// Version of the [`InputConfig::to_profile`] file layout.
+const PROFILE_VERSION: u32 = 1;
+
+/// An exported [`InputConfig`]: the map plus a marker naming the format.
+#[derive(Serialize, Deserialize)]
+struct ProfileFile {
+    rustyboi_input_profile: u32,
+    #[serde(flatten)]
+    input: InputConfig,
+}
+
 /// The full, serializable, host-agnostic input map shared by all frontends.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InputConfig {
@@ -265,6 +409,12 @@ pub struct InputConfig {
     pub gb_bindings: Vec<(GbButton, Vec<InputTrigger>)>,
     #[serde(default = "default_hotkeys")]
     pub hotkeys: Vec<Hotkey>,
+    /// Per-controller overrides of the shared pad bindings, by GUID.
+    #[serde(default)]
+    pub pad_profiles: Vec<PadProfile>,
+    /// Left stick deadzone and diagonal handling.
+    #[serde(default)]
+    pub left_stick: StickConfig,
 }
 
 impl Default for InputConfig {
@@ -272,6 +422,8 @@ impl Default for InputConfig {
         Self {
             gb_bindings: default_gb_bindings(),
             hotkeys: default_hotkeys(),
+            pad_profiles: Vec::new(),
+            left_stick: StickConfig::default(),
         }
     }
 }
@@ -293,15 +445,39 @@ fn default_gb_bindings() -> Vec<(GbButton, Vec<InputTrigger>)> {
     ]
 }
 
+/// F1-F10 in slot order: F1-F9 are slots 1-9 and F10 is slot 0, like the
+/// digit row.
+const SLOT_KEYS: [(KeyName, u8); 10] = [
+    (KeyName::F1, 1), (KeyName::F2, 2), (KeyName::F3, 3), (KeyName::F4, 4), (KeyName::F5, 5),
+    (KeyName::F6, 6), (KeyName::F7, 7), (KeyName::F8, 8), (KeyName::F9, 9), (KeyName::F10, 0),
+];
+
 fn default_hotkeys() -> Vec<Hotkey> {
     use HotkeyAction::*;
     use InputTrigger::{Gb, Key, Pad};
-    vec![
+    // Shift+Fn saves and Ctrl+Fn loads the numbered slots, with either
+    // Shift or Ctrl. The bare F5/F8 quick keys don't also fire: the longer
+    // chord wins (see `resolve`).
+    let slots = SLOT_KEYS.into_iter().flat_map(|(f, n)| {
+        [
+            Hotkey { chord: vec![Key(KeyName::ShiftLeft), Key(f)], action: SaveSlot(n) },
+            Hotkey { chord: vec![Key(KeyName::ShiftRight), Key(f)], action: SaveSlot(n) },
+            Hotkey { chord: vec![Key(KeyName::ControlLeft), Key(f)], action: LoadSlot(n) },
+            Hotkey { chord: vec![Key(KeyName::ControlRight), Key(f)], action: LoadSlot(n) },
+        ]
+    });
+    let mut hotkeys = vec![
         Hotkey { chord: vec![Key(KeyName::Tab)], action: FastForward },
+        Hotkey { chord: vec![Key(KeyName::Space)], action: HoldFastForward },
         Hotkey { chord: vec![Key(KeyName::Backspace)], action: Rewind },
         Hotkey { chord: vec![Key(KeyName::F5)], action: Quicksave },
         Hotkey { chord: vec![Key(KeyName::F8)], action: Quickload },
+        Hotkey { chord: vec![Key(KeyName::F6)], action: UndoLoadState },
         Hotkey { chord: vec![Key(KeyName::Backslash)], action: FrameAdvance },
+        Hotkey { chord: vec![Key(KeyName::M)], action: ToggleMute },
+        Hotkey { chord: vec![Key(KeyName::F11)], action: ToggleFullscreen },
+        Hotkey { chord: vec![Key(KeyName::AltLeft), Key(KeyName::Enter)], action: ToggleFullscreen },
+        Hotkey { chord: vec![Key(KeyName::AltRight), Key(KeyName::Enter)], action: ToggleFullscreen },
         // Acceptance examples (chords of mixed trigger kinds):
         Hotkey {
             chord: vec![Gb(GbButton::Start), Gb(GbButton::Select)],
@@ -315,7 +491,15 @@ fn default_hotkeys() -> Vec<Hotkey> {
             chord: vec![Gb(GbButton::Start), Gb(GbButton::A)],
             action: Turbo(GbButton::A),
         },
-    ]
+    ];
+    hotkeys.extend(slots);
+    // Ctrl+1…6 picks the window scale, like View → Window Scale.
+    let digits = [KeyName::Num1, KeyName::Num2, KeyName::Num3, KeyName::Num4, KeyName::Num5, KeyName::Num6];
+    hotkeys.extend(digits.into_iter().zip(1..).flat_map(|(digit, n)| {
+        [KeyName::ControlLeft, KeyName::ControlRight]
+            .map(|ctrl| Hotkey { chord: vec![Key(ctrl), Key(digit)], action: WindowScale(n) })
+    }));
+    hotkeys
 }
 
 /// The set of raw inputs currently held this frame. GB buttons are derived
@@ -323,13 +507,22 @@ fn default_hotkeys() -> Vec<Hotkey> {
 #[derive(Debug, Clone, Default)]
 pub struct HeldInputs {
     pub keys: HashSet<KeyName>,
+    /// Pad buttons from controllers the host can't tell apart.
     pub pad: HashSet<PadButton>,
+    /// Pad buttons per identified controller (GUID, held set). One with a
+    /// [`PadProfile`] resolves through it; the rest act like `pad`.
+    pub pads: Vec<(String, HashSet<PadButton>)>,
 }
 
 impl HeldInputs {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Every pad button held on any controller (the editor's capture set).
+    pub fn all_pad(&self) -> HashSet<PadButton> {
+        self.pads.iter().flat_map(|(_, held)| held.iter().copied()).chain(self.pad.iter().copied()).collect()
+    }
 }
 
 /// Per-frame edge/phase state carried across resolutions by the platform.
@@ -359,43 +552,117 @@ pub struct FiredHotkey {
 }
 
 impl InputConfig {
+    /// The profile for the controller with `guid`, if it has one.
+    pub fn pad_profile(&self, guid: &str) -> Option<&PadProfile> {
+        self.pad_profiles.iter().find(|p| p.guid == guid)
+    }
+
+    /// Add `trigger` to `button`'s bindings alongside the ones it has.
+    /// Returns whether anything changed (it was not bound there already).
+    pub fn add_binding(&mut self, button: GbButton, trigger: InputTrigger) -> bool {
+        let triggers = match self.gb_bindings.iter_mut().find(|(b, _)| *b == button) {
+            Some((_, triggers)) => triggers,
+            None => {
+                self.gb_bindings.push((button, Vec::new()));
+                &mut self.gb_bindings.last_mut().unwrap().1
+            }
+        };
+        if triggers.contains(&trigger) {
+            return false;
+        }
+        triggers.push(trigger);
+        true
+    }
+
+    /// Bind W/A/S/D to the d-pad too, keeping the arrow keys. Returns whether
+    /// any binding was added.
+    pub fn add_wasd_dpad(&mut self) -> bool {
+        [
+            (GbButton::Up, KeyName::W),
+            (GbButton::Left, KeyName::A),
+            (GbButton::Down, KeyName::S),
+            (GbButton::Right, KeyName::D),
+        ]
+        .into_iter()
+        .fold(false, |changed, (button, key)| self.add_binding(button, InputTrigger::Key(key)) | changed)
+    }
+
+    /// The whole map (key and pad bindings, controller profiles, stick,
+    /// hotkeys and turbo chords) as a portable profile file: tagged,
+    /// versioned, pretty-printed JSON.
+    pub fn to_profile(&self) -> Vec<u8> {
+        let file = ProfileFile { rustyboi_input_profile: PROFILE_VERSION, input: self.clone() };
+        serde_json::to_vec_pretty(&file).expect("an input map always serializes")
+    }
+
+    /// The first key chord bound to `action`, for a menu entry to show
+    /// (`"Shift+F1"`). `None` when only pad or GB chords trigger it.
+    pub fn shortcut(&self, action: HotkeyAction) -> Option<String> {
+        self.hotkeys.iter().filter(|h| h.action == action && !h.chord.is_empty()).find_map(|h| {
+            let keys: Option<Vec<_>> = h
+                .chord
+                .iter()
+                .map(|t| match t {
+                    InputTrigger::Key(k) => Some(k.shortcut_label()),
+                    _ => None,
+                })
+                .collect();
+            keys.map(|keys| keys.join("+"))
+        })
+    }
+
+    /// Read a file written by [`to_profile`](Self::to_profile). Fields it
+    /// lacks take their defaults, so older profiles still load.
+    pub fn from_profile(bytes: &[u8]) -> Result<Self, String> {
+        let file: ProfileFile = serde_json::from_slice(bytes).map_err(|e| format!("not a controls profile: {e}"))?;
+        if file.rustyboi_input_profile > PROFILE_VERSION {
+            return Err(format!("profile version {} is newer than this build", file.rustyboi_input_profile));
+        }
+        Ok(file.input)
+    }
+
     fn trigger_held(trigger: InputTrigger, held: &HeldInputs, gb: &ButtonState) -> bool {
         match trigger {
             InputTrigger::Key(k) => held.keys.contains(&k),
-            InputTrigger::Pad(p) => held.pad.contains(&p),
+            InputTrigger::Pad(p) => held.pad.contains(&p) || held.pads.iter().any(|(_, pad)| pad.contains(&p)),
             InputTrigger::Gb(b) => b.get(gb),
         }
     }
 
+    /// Whether a shared `Pad` binding is held: by an anonymous controller or
+    /// an identified one without its own profile.
+    fn shared_pad_held(&self, p: PadButton, held: &HeldInputs) -> bool {
+        held.pad.contains(&p)
+            || held.pads.iter().any(|(guid, pad)| self.pad_profile(guid).is_none() && pad.contains(&p))
+    }
+
     /// Resolve the current raw inputs into a Game Boy button state plus the
     /// list of hotkeys firing this frame.
     ///
     /// Contract:
-    /// - A GB button is pressed if ANY of its bound triggers is held.
+    /// - A GB button is pressed if ANY of its bound triggers is held. A
+    ///   controller with a [`PadProfile`] presses GB buttons only through it.
     /// - A hotkey is active iff ALL of its chord triggers are held (empty chord
     ///   never fires). GB-button triggers are evaluated against the raw
     ///   (pre-suppression) GB state so chords like Start+A see A.
+    /// - A hotkey whose chord is a strict subset of another active chord is
+    ///   treated as inactive, so Shift+F5 fires only its own action and not
+    ///   the bare-F5 one as well.
     /// - Toggle actions fire once on the rising edge; hold actions fire every
     ///   frame they are active. `FiredHotkey.rising` distinguishes them.
     /// - Turbo(btn): while active, `btn` is driven as an autofire square wave
     ///   (on for TURBO_PERIOD frames, off for TURBO_PERIOD) and its normal
     ///   binding is suppressed. Any GB button consumed by an active action is
     ///   removed from normal output.
+    /// - An active hotkey consumes the keys and pad buttons of its chord: they
+    ///   press no GB button while it holds, so Alt+Enter toggles fullscreen
+    ///   without also pressing Start.
     pub fn resolve(
         &self,
         held: &HeldInputs,
         state: &mut ResolveState,
     ) -> (ButtonState, Vec<FiredHotkey>) {
-        // Resolve GB buttons from their direct key/pad triggers. A Gb-typed
-        // trigger inside a gb_binding is unusual; it sees only the empty
-        // baseline here (chords, not bindings, are the place to reference GB
-        // buttons), keeping resolution single-pass and order-independent.
-        let empty = ButtonState::default();
-        let mut raw = ButtonState::default();
-        for (button, triggers) in &self.gb_bindings {
-            let pressed = triggers.iter().any(|t| Self::trigger_held(*t, held, &empty));
-            button.set(&mut raw, pressed);
-        }
+        let raw = self.resolve_buttons(held, &[]);
 
         if state.prev_active.len() != self.hotkeys.len() {
             state.prev_active = vec![false; self.hotkeys.len()];
@@ -403,14 +670,20 @@ impl InputConfig {
         state.turbo_phase = state.turbo_phase.wrapping_add(1);
         let turbo_on = (state.turbo_phase / TURBO_PERIOD).is_multiple_of(2);
 
+        let held_chord: Vec<bool> = self
+            .hotkeys
+            .iter()
+            .map(|h| !h.chord.is_empty() && h.chord.iter().all(|t| Self::trigger_held(*t, held, &raw)))
+            .collect();
         let mut fired = Vec::new();
-        let mut out = raw;
+        let mut consumed = Vec::new();
+        let mut turbo = Vec::new();
         for (i, hotkey) in self.hotkeys.iter().enumerate() {
-            let active = !hotkey.chord.is_empty()
-                && hotkey
-                    .chord
-                    .iter()
-                    .all(|t| Self::trigger_held(*t, held, &raw));
+            let shadowed = self.hotkeys.iter().zip(&held_chord).any(|(other, &on)| {
+                on && other.chord.len() > hotkey.chord.len()
+                    && hotkey.chord.iter().all(|t| other.chord.contains(t))
+            });
+            let active = held_chord[i] && !shadowed;
             let was = state.prev_active[i];
             state.prev_active[i] = active;
 
@@ -418,13 +691,8 @@ impl InputConfig {
                 continue;
             }
 
-            if let Some(consumed) = hotkey.action.consumed_gb() {
-                consumed.set(&mut out, false);
-            }
-            if let HotkeyAction::Turbo(btn) = hotkey.action
-                && turbo_on {
-                    btn.set(&mut out, true);
-                }
+            consumed.extend(hotkey.chord.iter().filter(|t| !matches!(t, InputTrigger::Gb(_))));
+            turbo.extend(hotkey.action.consumed_gb());
 
             if hotkey.action.is_hold() || !was {
                 fired.push(FiredHotkey {
@@ -434,8 +702,33 @@ impl InputConfig {
             }
         }
 
+        let mut out = if consumed.is_empty() { raw } else { self.resolve_buttons(held, &consumed) };
+        for btn in turbo {
+            btn.set(&mut out, turbo_on);
+        }
         (out, fired)
     }
+
+    /// GB buttons from their direct key/pad triggers, ignoring the
+    /// `consumed` ones. A Gb-typed trigger inside a gb_binding is unusual; it
+    /// sees only the empty baseline here (chords, not bindings, are the place
+    /// to reference GB buttons), keeping resolution single-pass and
+    /// order-independent.
+    fn resolve_buttons(&self, held: &HeldInputs, consumed: &[&InputTrigger]) -> ButtonState {
+        let empty = ButtonState::default();
+        let mut state = ButtonState::default();
+        for (button, triggers) in &self.gb_bindings {
+            let pressed = triggers.iter().filter(|t| !consumed.contains(t)).any(|t| match t {
+                InputTrigger::Pad(p) => self.shared_pad_held(*p, held),
+                _ => Self::trigger_held(*t, held, &empty),
+            }) || held
+                .pads
+                .iter()
+                .any(|(guid, pad)| self.pad_profile(guid).is_some_and(|prof| prof.pressed(*button, pad)));
+            button.set(&mut state, pressed);
+        }
+        state
+    }
 }
 
 #[cfg(test)]
@@ -445,7 +738,7 @@ mod tests {
     fn keys(ks: &[KeyName]) -> HeldInputs {
         HeldInputs {
             keys: ks.iter().copied().collect(),
-            pad: HashSet::new(),
+            ..HeldInputs::default()
         }
     }
 
@@ -458,6 +751,38 @@ mod tests {
         assert!(!state.b);
     }
 
+    #[test]
+    fn every_key_bound_to_a_button_presses_it() {
+        let mut cfg = InputConfig::default();
+        assert!(cfg.add_wasd_dpad());
+        assert!(!cfg.add_wasd_dpad(), "already bound");
+        let mut st = ResolveState::new();
+        let (state, _) = cfg.resolve(&keys(&[KeyName::W]), &mut st);
+        assert!(state.up, "W presses Up");
+        let (state, _) = cfg.resolve(&keys(&[KeyName::Up]), &mut st);
+        assert!(state.up, "the arrow still presses Up");
+        let (state, _) = cfg.resolve(&keys(&[KeyName::Up, KeyName::W, KeyName::D]), &mut st);
+        assert!(state.up && state.right && !state.left);
+    }
+
+    #[test]
+    fn stick_directions_honour_the_deadzone_and_diagonal_bias() {
+        use PadButton::*;
+        let stick = StickConfig::default();
+        assert!(stick.directions(0.3, 0.3).is_empty(), "inside the deadzone");
+        assert_eq!(stick.directions(0.9, 0.1), vec![LStickRight]);
+        assert_eq!(stick.directions(-0.7, -0.7), vec![LStickLeft, LStickDown]);
+        assert_eq!(stick.directions(0.1, 0.9), vec![LStickUp]);
+        // 30° off the x axis: a diagonal at the default even sectors, not
+        // with the bias at 0, and a diagonal with a small deadzone too.
+        let (x, y) = (0.866, 0.5);
+        assert_eq!(stick.directions(x, y), vec![LStickRight, LStickUp]);
+        let cardinal = StickConfig { diagonal_bias: 0.0, ..stick };
+        assert_eq!(cardinal.directions(x, y), vec![LStickRight]);
+        let eager = StickConfig { deadzone: 0.2, diagonal_bias: 1.0 };
+        assert_eq!(eager.directions(0.3, 0.01), vec![LStickRight, LStickUp]);
+    }
+
     #[test]
     fn two_trigger_chord_fires_only_when_both_held() {
         let cfg = InputConfig {
@@ -469,6 +794,8 @@ mod tests {
                 ],
                 action: HotkeyAction::Exit,
             }],
+            pad_profiles: Vec::new(),
+            left_stick: StickConfig::default(),
         };
         let mut st = ResolveState::new();
 
@@ -491,6 +818,8 @@ mod tests {
                 chord: vec![InputTrigger::Key(KeyName::P)],
                 action: HotkeyAction::TogglePause,
             }],
+            pad_profiles: Vec::new(),
+            left_stick: StickConfig::default(),
         };
         let mut st = ResolveState::new();
 
@@ -516,6 +845,8 @@ mod tests {
                 ],
                 action: HotkeyAction::Turbo(GbButton::A),
             }],
+            pad_profiles: Vec::new(),
+            left_stick: StickConfig::default(),
         };
         let mut st = ResolveState::new();
 
@@ -554,6 +885,8 @@ mod tests {
                 ],
                 action: HotkeyAction::Turbo(GbButton::A),
             }],
+            pad_profiles: Vec::new(),
+            left_stick: StickConfig::default(),
         };
         let mut st = ResolveState::new();
         // Across a full turbo cycle, A is sometimes off despite Z held; Start on.
@@ -583,6 +916,7 @@ mod tests {
         let held = HeldInputs {
             keys: [KeyName::Enter].into_iter().collect(),
             pad: [PadButton::RightTrigger].into_iter().collect(),
+            ..HeldInputs::default()
         };
         let (_, fired) = cfg.resolve(&held, &mut st2);
         assert!(fired.iter().any(|f| f.action == HotkeyAction::FastForward));
@@ -596,6 +930,8 @@ mod tests {
                 chord: vec![InputTrigger::Key(KeyName::Backspace)],
                 action: HotkeyAction::Rewind,
             }],
+            pad_profiles: Vec::new(),
+            left_stick: StickConfig::default(),
         };
         let mut st = ResolveState::new();
         for _ in 0..3 {
@@ -613,6 +949,8 @@ mod tests {
                 chord: vec![InputTrigger::Key(KeyName::Tab)],
                 action: HotkeyAction::FastForward,
             }],
+            pad_profiles: Vec::new(),
+            left_stick: StickConfig::default(),
         };
         let mut st = ResolveState::new();
         // Rising frame fires; subsequent held frames do not.
@@ -624,4 +962,119 @@ mod tests {
             assert!(fired.is_empty());
         }
     }
+
+    #[test]
+    fn slot_chords_shadow_the_bare_quick_keys() {
+        let cfg = InputConfig::default();
+        let actions = |ks: &[KeyName]| -> Vec<HotkeyAction> {
+            let (_, fired) = cfg.resolve(&keys(ks), &mut ResolveState::new());
+            fired.into_iter().map(|f| f.action).collect()
+        };
+        assert_eq!(actions(&[KeyName::F5]), [HotkeyAction::Quicksave]);
+        assert_eq!(actions(&[KeyName::ShiftLeft, KeyName::F5]), [HotkeyAction::SaveSlot(5)]);
+        assert_eq!(actions(&[KeyName::ControlLeft, KeyName::F8]), [HotkeyAction::LoadSlot(8)]);
+        assert_eq!(actions(&[KeyName::ControlLeft, KeyName::F10]), [HotkeyAction::LoadSlot(0)]);
+        assert_eq!(actions(&[KeyName::ControlLeft, KeyName::F6]), [HotkeyAction::LoadSlot(6)]);
+        assert_eq!(actions(&[KeyName::F6]), [HotkeyAction::UndoLoadState]);
+        assert_eq!(actions(&[KeyName::ShiftRight, KeyName::F2]), [HotkeyAction::SaveSlot(2)]);
+        assert_eq!(actions(&[KeyName::ControlRight, KeyName::F2]), [HotkeyAction::LoadSlot(2)]);
+    }
+
+    /// Left Shift is also Select, but while Shift+Fn holds the game sees no
+    /// Select press.
+    #[test]
+    fn a_slot_chord_does_not_press_select() {
+        let cfg = InputConfig::default();
+        let (state, fired) = cfg.resolve(&keys(&[KeyName::ShiftLeft, KeyName::F3]), &mut ResolveState::new());
+        assert_eq!(fired[0].action, HotkeyAction::SaveSlot(3));
+        assert!(!state.select);
+        let (state, _) = cfg.resolve(&keys(&[KeyName::ShiftLeft]), &mut ResolveState::new());
+        assert!(state.select, "Shift alone is still Select");
+    }
+
+    #[test]
+    fn menu_shortcuts_follow_the_bindings() {
+        let mut cfg = InputConfig::default();
+        assert_eq!(cfg.shortcut(HotkeyAction::SaveSlot(1)).as_deref(), Some("Shift+F1"));
+        assert_eq!(cfg.shortcut(HotkeyAction::LoadSlot(0)).as_deref(), Some("Ctrl+F10"));
+        assert_eq!(cfg.shortcut(HotkeyAction::ToggleFullscreen).as_deref(), Some("F11"));
+        cfg.hotkeys.retain(|h| h.action != HotkeyAction::Quicksave);
+        assert_eq!(cfg.shortcut(HotkeyAction::Quicksave), None);
+        cfg.hotkeys.push(Hotkey {
+            chord: vec![InputTrigger::Key(KeyName::ControlRight), InputTrigger::Key(KeyName::S)],
+            action: HotkeyAction::Quicksave,
+        });
+        assert_eq!(cfg.shortcut(HotkeyAction::Quicksave).as_deref(), Some("Ctrl+S"));
+    }
+
+    #[test]
+    fn a_profiled_controller_replaces_the_shared_pad_bindings() {
+        let pad = ConnectedPad { guid: "030000005e0400008e02000014010000".into(), name: "Xbox".into() };
+        let mut cfg = InputConfig::default();
+        let mut profile = PadProfile::from_shared(&pad, &cfg);
+        assert!(profile.bindings.iter().any(|(b, p)| *b == GbButton::A && p == &[PadButton::South]));
+        // Swap A and B on this controller only.
+        for (b, pads) in &mut profile.bindings {
+            match b {
+                GbButton::A => *pads = vec![PadButton::East],
+                GbButton::B => *pads = vec![PadButton::South],
+                _ => {}
+            }
+        }
+        cfg.pad_profiles.push(profile);
+
+        let press = |guid: &str, p: PadButton| {
+            let held = HeldInputs { pads: vec![(guid.into(), [p].into_iter().collect())], ..HeldInputs::default() };
+            cfg.resolve(&held, &mut ResolveState::new()).0
+        };
+        let swapped = press(&pad.guid, PadButton::South);
+        assert!(swapped.b && !swapped.a, "the profile wins for its controller");
+        let other = press("another", PadButton::South);
+        assert!(other.a && !other.b, "other controllers keep the shared bindings");
+
+        // Chords still see the profiled controller's buttons.
+        let held = HeldInputs {
+            keys: [KeyName::Enter].into_iter().collect(),
+            pads: vec![(pad.guid.clone(), [PadButton::RightTrigger].into_iter().collect())],
+            ..HeldInputs::default()
+        };
+        let (_, fired) = cfg.resolve(&held, &mut ResolveState::new());
+        assert!(fired.iter().any(|f| f.action == HotkeyAction::FastForward));
+    }
+
+    #[test]
+    fn f11_and_alt_enter_toggle_fullscreen_by_default() {
+        let cfg = InputConfig::default();
+        let actions = |ks: &[KeyName]| -> Vec<HotkeyAction> {
+            let (_, fired) = cfg.resolve(&keys(ks), &mut ResolveState::new());
+            fired.into_iter().map(|f| f.action).collect()
+        };
+        assert_eq!(actions(&[KeyName::F11]), [HotkeyAction::ToggleFullscreen]);
+        assert_eq!(actions(&[KeyName::AltLeft, KeyName::Enter]), [HotkeyAction::ToggleFullscreen]);
+        let (state, _) = cfg.resolve(&keys(&[KeyName::AltLeft, KeyName::Enter]), &mut ResolveState::new());
+        assert!(!state.start, "the chord consumes Enter");
+        assert!(actions(&[KeyName::Enter]).is_empty(), "bare Enter is only Start");
+    }
+
+    #[test]
+    fn a_profile_round_trips_the_whole_map() {
+        let mut cfg = InputConfig::default();
+        cfg.add_wasd_dpad();
+        cfg.left_stick.deadzone = 0.3;
+        cfg.hotkeys.push(Hotkey { chord: vec![InputTrigger::Key(KeyName::T)], action: HotkeyAction::Turbo(GbButton::B) });
+        assert_eq!(InputConfig::from_profile(&cfg.to_profile()).unwrap(), cfg);
+        assert!(InputConfig::from_profile(b"{\"gb_bindings\": []}").is_err(), "untagged JSON is refused");
+        assert!(InputConfig::from_profile(b"{\"rustyboi_input_profile\": 99}").unwrap_err().contains("newer"));
+        let sparse = InputConfig::from_profile(b"{\"rustyboi_input_profile\": 1}").unwrap();
+        assert_eq!(sparse, InputConfig::default());
+    }
+
+    #[test]
+    fn ctrl_digits_pick_the_window_scale_by_default() {
+        let cfg = InputConfig::default();
+        let (_, fired) = cfg.resolve(&keys(&[KeyName::ControlLeft, KeyName::Num3]), &mut ResolveState::new());
+        assert_eq!(fired.into_iter().map(|f| f.action).collect::<Vec<_>>(), [HotkeyAction::WindowScale(3)]);
+        let (_, fired) = cfg.resolve(&keys(&[KeyName::Num3]), &mut ResolveState::new());
+        assert!(fired.is_empty(), "a bare digit is not a hotkey");
+    }
 }
@@ -48,45 +48,57 @@
 pub mod action;
 pub mod apply;
 mod audio;
+pub mod camera;
 pub mod cheat_db;
 pub mod cheats;
 pub mod config;
 pub mod debug;
+mod gif;
 pub mod input;
 pub mod input_config;
 pub mod no_intro;
 pub mod overlay;
 pub mod pacing;
-pub mod patch;
+pub mod palette;
 pub mod present;
 pub mod rom_zip;
 pub mod ports;
 pub mod rewind;
 pub mod session;
+pub mod state_json;
 pub mod tas;
+mod video;
 
 #[cfg(target_os = "android")]
 pub use action::LibraryEntry;
 pub use action::{
-    ActionKind, CommandDescriptor, FileData, GbcDmgPalette, GraphicsBackend, HardwareChoice,
-    HardwareFamily, KeyBind, LcdEffect, LoadPurpose, MenuCategory, DmgPaletteChoice, ScalingMode,
-    SessionUiState, SgbPaletteChoice, TextureFilter, UiAction, COMMANDS, PRINTER_SCALES,
+    AccuracyPreset, ActionKind, AspectRatio, ChannelMix, CommandDescriptor, DebugLayout, DebugWindowLayout, InputEditorView, FileData, FrameSkip, GbcDmgPalette, GraphicsBackend, HardwareChoice,
+    HardwareFamily, KeyBind, LcdEffect, LoadPurpose, MenuCategory, DmgPaletteChoice, OsdMessage, ScalingMode,
+    SessionUiState, SgbPaletteChoice, TextureFilter, UiAction, COMMANDS, EMULATION_SPEEDS, FRAME_SKIPS, PRINTER_SCALES,
+    AUDIO_BUFFER_SIZES, AUDIO_LATENCY_FRAMES, GIF_LENGTHS, GIF_SCALES, SAMPLE_RATES, WINDOW_SCALES,
+    step_emulation_speed,
 };
+pub use audio::{wav_header, AudioFile, AudioSink, AudioSinkOpener, STEM_NAMES};
 pub use apply::{ActionOutcome, FetchPurpose, PlatformRequest};
+pub use camera::StillImage;
+pub use video::{encode_rgb_png, VideoCapture, VideoSink};
 pub use present::{frame_to_pixels, rgb_to_pixels, PixelOrder};
 pub use cheat_db::FetchedCheat;
+pub use no_intro::DumpStatus;
 pub use config::Config;
 pub use debug::{CartInfo, DebugDetail, DebugSnapshot};
 pub use input::{AbstractInput, GbButton, InputMap};
 pub use input_config::{
-    FiredHotkey, HeldInputs, Hotkey, HotkeyAction, InputConfig, InputTrigger, KeyName, PadButton,
-    ResolveState,
+    ConnectedPad, FiredHotkey, HeldInputs, Hotkey, HotkeyAction, InputConfig, InputTrigger, KeyName, PadButton, PadProfile,
+    ResolveState, StickConfig,
 };
 pub use overlay::{OverlayButton, OverlayRect, OverlayShape, TouchLayout};
-pub use patch::apply_patch;
-pub use ports::{NetTransport, Rumble, Storage, StorageError, Webcam};
+// IPS/UPS/BPS patching lives in the core (`Cartridge::load` soft-patches from
+// it); re-exported so `rustyboi_session::patch` keeps resolving.
+pub use rustyboi_core_lib::patch::{self, apply_patch};
+pub use ports::{Broadcast, NetTransport, Rumble, Storage, StorageError, Webcam};
 pub use session::{
-    FrameOutput, Ports, RunMode, Session, SessionError, SlotMeta, GB_SIZE, QUICK_SLOT, SGB_SIZE,
+    FrameOutput, Ports, RunMode, Session, SessionError, SlotMeta, AUTO_SLOT, GB_SIZE, QUICK_SLOT, SGB_SIZE,
 };
 
 /// Human label for an identified SGB firmware image, used in the status line
@@ -12,11 +12,17 @@
 //! DATs at runtime (see [`dat_urls`]) and feeds them in via [`load_dats`]. Until
 //! that happens the store is empty and every ROM reports as unidentified —
 //! callers gracefully fall back to the header title.
+//!
+//! Separately, a DAT the user points the config at can be installed as a
+//! per-session verification database ([`parse_dat_sha1`]): the loaded ROM's
+//! SHA-1 is looked up in it to report a [`DumpStatus`].
 
 use std::collections::BTreeMap;
 use std::sync::RwLock;
 
-use crate::patch::crc32;
+use serde::{Deserialize, Serialize};
+
+use rustyboi_core_lib::checksum::crc32;
 
 /// The runtime index as `(crc32, name)` sorted by crc for binary search. Empty
 /// until a frontend downloads the DATs and calls [`load_dats`].
@@ -74,21 +80,69 @@ fn parse_name_line(line: &str) -> Option<String> {
 
 /// The first `crc XXXXXXXX` (8 hex digits, word-boundary before `crc`) in `line`.
 fn parse_crc(line: &str) -> Option<u32> {
+    u32::from_str_radix(hex_field(line, "crc", 8)?, 16).ok()
+}
+
+/// The first `sha1 <40 hex digits>` in `line`, as the 20-byte digest.
+fn parse_sha1(line: &str) -> Option<[u8; 20]> {
+    let hex = hex_field(line, "sha1", 40)?;
+    let mut digest = [0u8; 20];
+    for (i, byte) in digest.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(digest)
+}
+
+/// The `digits` hex digits following the first word-bounded `key ` in `line`.
+fn hex_field<'a>(line: &'a str, key: &str, digits: usize) -> Option<&'a str> {
+    let needle = format!("{key} ");
     let mut from = 0;
-    while let Some(rel) = line[from..].find("crc ") {
+    while let Some(rel) = line[from..].find(&needle) {
         let pos = from + rel;
         let boundary = pos == 0 || !is_word_byte(line.as_bytes()[pos - 1]);
-        if boundary {
-            let hex: String = line[pos + 4..].chars().take(8).collect();
-            if hex.len() == 8 && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
-                return u32::from_str_radix(&hex, 16).ok();
-            }
+        let hex = line[pos + needle.len()..].get(..digits);
+        if boundary
+            && let Some(hex) = hex
+            && hex.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            return Some(hex);
         }
-        from = pos + 4;
+        from = pos + needle.len();
     }
     None
 }
 
+/// How the loaded ROM compares against the verification DAT.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DumpStatus {
+    /// The ROM's SHA-1 is listed under this game name: a verified good dump.
+    Verified(String),
+    /// Not in the DAT: a bad dump or overdump, a hack, homebrew, or simply a
+    /// game the DAT doesn't cover.
+    Unknown,
+}
+
+/// Parse a DAT body into `(sha1, name)` pairs sorted by digest, for dump
+/// verification. The same line rules as [`parse_dat`], keyed by the `rom`
+/// line's `sha1` instead of its `crc`; entries without one are skipped (a CRC32
+/// alone is too weak to call a dump verified).
+pub(crate) fn parse_dat_sha1(text: &str) -> Vec<([u8; 20], String)> {
+    let mut out = Vec::new();
+    let mut pending: Option<String> = None;
+    for line in text.lines() {
+        if let Some(name) = parse_name_line(line) {
+            pending = Some(name);
+        } else if let Some(sha1) = parse_sha1(line)
+            && let Some(name) = pending.take()
+        {
+            out.push((sha1, name));
+        }
+    }
+    out.sort();
+    out.dedup_by(|a, b| a.0 == b.0);
+    out
+}
+
 fn is_word_byte(b: u8) -> bool {
     b.is_ascii_alphanumeric() || b == b'_'
 }
@@ -245,6 +299,28 @@ game (
         assert_eq!(parse_crc("\tname \"Descrc AABBCC44\""), None);
     }
 
+    #[test]
+    fn parse_dat_sha1_keys_by_digest_and_skips_crc_only_roms() {
+        let dat = "\
+game (
+\tname \"Tetris (World) (Rev 1)\"
+\trom ( name \"Tetris (World) (Rev 1).gb\" size 32768 crc 46DF91AD md5 982ED5D2B12A0377EB14BCDC4123744E sha1 74591CC9501AF93873F9A5D3EB12DA12C0723BBC )
+)
+
+game (
+\tname \"Homebrew\"
+\trom ( name \"Homebrew.gb\" size 32768 crc 00000001 )
+)
+";
+        let entries = parse_dat_sha1(dat);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0[..4], [0x74, 0x59, 0x1C, 0xC9]);
+        assert_eq!(entries[0].1, "Tetris (World) (Rev 1)");
+        // A short or non-hex digest is not a digest.
+        assert_eq!(parse_sha1("sha1 74591CC9"), None);
+        assert_eq!(parse_sha1("sha1 74591CC9501AF93873F9A5D3EB12DA12C0723BBZ"), None);
+    }
+
     #[test]
     fn parse_name_line_only_matches_the_tab_anchored_name() {
         // The game's own name line (leading tab, then `name "`).
@@ -280,13 +356,13 @@ game (
         let _g = lock();
         // (1) A No-Intro index hit wins over the header title.
         let indexed = header_rom(b"HEADERTITLE");
-        let crc = crate::patch::crc32(&indexed);
+        let crc = crc32(&indexed);
         set_index(vec![(crc, "Canonical No-Intro Name".to_string())]);
         assert_eq!(resolve_game_name(&indexed).as_deref(), Some("Canonical No-Intro Name"));
 
         // (2) A ROM absent from the index falls back to its header title.
         let headered = header_rom(b"HOMEBREW");
-        assert_ne!(crate::patch::crc32(&headered), crc, "distinct crc so it is unindexed");
+        assert_ne!(crc32(&headered), crc, "distinct crc so it is unindexed");
         assert_eq!(resolve_game_name(&headered).as_deref(), Some("HOMEBREW"));
 
         // (3) Neither indexed nor a usable header title → None.
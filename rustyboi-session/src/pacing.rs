@@ -26,13 +26,16 @@
 //! Android, and the web worker share this one implementation, and its
 //! behavior is provable in ordinary unit tests with no display or audio.
 
+use crate::action::FrameSkip;
+
 /// Dots in one emulated frame (154 scanlines × 456 dots). Fixed on every model
 /// — a machine's clock changes how fast these dots are played back in real
 /// time, never how many there are.
 pub const DOTS_PER_FRAME: f64 = 70_224.0;
 
-/// The host output rate every audio backend consumes.
-pub(crate) const HOST_SAMPLE_RATE: f64 = 44_100.0;
+/// The default host output rate (Settings → Audio Sample Rate offers the rest
+/// of [`crate::action::SAMPLE_RATES`]).
+pub(crate) const HOST_SAMPLE_RATE: u32 = 44_100;
 
 /// Exact emulated frame rate for DMG-rate hardware: 70224 dots at 4.194304 MHz.
 /// Every model but the SGB1 runs at this rate; see [`nominal_fps`] for the
@@ -43,7 +46,7 @@ pub const NOMINAL_FPS: f64 = 4_194_304.0 / DOTS_PER_FRAME;
 /// production consumer after the visibility narrowing, but the pacing tests
 /// assert against it.
 #[cfg(test)]
-pub(crate) const SAMPLES_PER_FRAME_F64: f64 = HOST_SAMPLE_RATE / NOMINAL_FPS;
+pub(crate) const SAMPLES_PER_FRAME_F64: f64 = HOST_SAMPLE_RATE as f64 / NOMINAL_FPS;
 
 /// Emulated frames per real second for a machine clocked at `cpu_hz`. An NTSC
 /// SGB1 (4 295 454 Hz — the host SNES's clock / 5) presents ~61.17 fps, which
@@ -52,16 +55,17 @@ pub(crate) fn nominal_fps(cpu_hz: u32) -> f64 {
     f64::from(cpu_hz) / DOTS_PER_FRAME
 }
 
-/// Stereo sample pairs per emulated frame for a machine clocked at `cpu_hz`.
+/// Stereo sample pairs per emulated frame for a machine clocked at `cpu_hz`,
+/// played out at `sample_rate`.
 ///
-/// This is the counterpart of the core's `cpu_hz / 44100` downsample ratio, and
-/// the pair must stay consistent: the core emits `DOTS_PER_FRAME / (cpu_hz /
-/// 44100)` pairs per frame and this rate presents `cpu_hz / DOTS_PER_FRAME`
-/// frames per second, whose product is exactly 44 100 pairs/second on **every**
-/// model. Change one without the other and the host output rate drifts off
-/// 44.1 kHz.
-pub(crate) fn samples_per_frame(cpu_hz: u32) -> f64 {
-    HOST_SAMPLE_RATE / nominal_fps(cpu_hz)
+/// This is the counterpart of the core's `cpu_hz / sample_rate` downsample
+/// ratio, and the pair must stay consistent: the core emits `DOTS_PER_FRAME /
+/// (cpu_hz / sample_rate)` pairs per frame and this rate presents `cpu_hz /
+/// DOTS_PER_FRAME` frames per second, whose product is exactly `sample_rate`
+/// pairs/second on **every** model. Change one without the other and the host
+/// output rate drifts off the device's.
+pub(crate) fn samples_per_frame(cpu_hz: u32, sample_rate: u32) -> f64 {
+    f64::from(sample_rate) / nominal_fps(cpu_hz)
 }
 
 /// Ring-depth FLOOR (in frames) the stretch steers toward — the control
@@ -74,7 +78,11 @@ pub(crate) fn samples_per_frame(cpu_hz: u32) -> f64 {
 /// Steering the sawtooth's trough to a small margin above empty is
 /// chunk-size-agnostic: no underruns once settled, no bias, and the mean
 /// (latency) stays as low as the host's own cadence permits.
-const TARGET_MIN_BACKLOG_FRAMES: f64 = 2.0;
+///
+/// This is the default margin; Settings → Audio Latency trades it up for
+/// crackle resistance on hosts whose pulls are burstier than it absorbs (see
+/// [`Regulator::set_target_backlog`]).
+pub(crate) const TARGET_MIN_BACKLOG_FRAMES: u32 = 2;
 /// The stretch ratio's authority: covers real audio-clock offsets (crystal
 /// drift ~±100 ppm; sloppy phone audio PLLs a few tenths of a percent) with
 /// margin. ±1% is ±17 cents of pitch — still imperceptible in game audio —
@@ -109,11 +117,20 @@ pub struct Regulator {
     /// Latest audio stretch ratio (output/input pairs) — see
     /// [`Regulator::audio_stretch`].
     stretch: f64,
+    /// The backlog floor (frames) the stretch steers toward; see
+    /// [`TARGET_MIN_BACKLOG_FRAMES`].
+    target_backlog: f64,
     /// The machine's frame rate this regulator paces to, and the matching
     /// sample pairs per frame. Both derive from the model's CPU clock, so an
-    /// SGB1 paces at its true ~61.17 fps instead of a DMG's 59.73.
+    /// SGB1 paces at its true ~61.17 fps instead of a DMG's 59.73; the pairs
+    /// also scale with the host output rate.
     fps: f64,
+    sample_rate: u32,
     samples_per_frame: f64,
+    /// Emulation speed as a multiple of `fps` (Emulation → Speed); 1.0 is
+    /// real time. Scales the token rate, the bank and per-tick ceilings, and
+    /// the audio stretch so the device is still fed at its own rate.
+    speed: f64,
 }
 
 impl Default for Regulator {
@@ -142,8 +159,11 @@ impl Regulator {
             win_min: f64::INFINITY,
             prev_win_min: f64::INFINITY,
             stretch: 1.0,
+            target_backlog: f64::from(TARGET_MIN_BACKLOG_FRAMES),
             fps: nominal_fps(cpu_hz),
-            samples_per_frame: samples_per_frame(cpu_hz),
+            sample_rate: HOST_SAMPLE_RATE,
+            samples_per_frame: samples_per_frame(cpu_hz, HOST_SAMPLE_RATE),
+            speed: 1.0,
         }
     }
 
@@ -154,11 +174,42 @@ impl Regulator {
         let fps = nominal_fps(cpu_hz);
         if fps != self.fps {
             self.fps = fps;
-            self.samples_per_frame = samples_per_frame(cpu_hz);
+            self.samples_per_frame = samples_per_frame(cpu_hz, self.sample_rate);
+        }
+    }
+
+    /// Retune to a device opened at `sample_rate` (the session's
+    /// [`audio_sample_rate`](crate::Session::audio_sample_rate)), so the
+    /// backlog is read in frames of the right length. Idempotent, like
+    /// [`set_cpu_hz`](Self::set_cpu_hz).
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            self.samples_per_frame = f64::from(sample_rate) / self.fps;
         }
     }
 
-    /// The frame rate this regulator is pacing to.
+    /// Steer the ring's backlog floor to `frames` emulated frames instead of
+    /// the default [`TARGET_MIN_BACKLOG_FRAMES`] (the session's
+    /// [`audio_latency_frames`](crate::Session::audio_latency_frames)). More
+    /// frames ride out burstier device pulls at the cost of latency. Cheap and
+    /// idempotent, so the tick loop can call it unconditionally; a change
+    /// settles through the stretch, never by skipping or repeating frames.
+    pub fn set_target_backlog(&mut self, frames: u32) {
+        self.target_backlog = f64::from(frames.max(1));
+    }
+
+    /// Pace at `percent` of the machine's rate (the session's
+    /// [`emulation_speed`](crate::Session::emulation_speed)). Each frame's
+    /// audio is then resampled by the inverse through
+    /// [`audio_stretch`](Self::audio_stretch), so sound stays continuous and
+    /// pitches with the speed like tape. Idempotent, like
+    /// [`set_cpu_hz`](Self::set_cpu_hz).
+    pub fn set_speed(&mut self, percent: u32) {
+        self.speed = f64::from(percent.max(1)) / 100.0;
+    }
+
+    /// The machine's frame rate this regulator paces to (before the speed).
     pub fn nominal_fps(&self) -> f64 {
         self.fps
     }
@@ -218,14 +269,18 @@ impl Regulator {
             let floor = self.win_min.min(self.prev_win_min);
             if floor.is_finite() {
                 self.stretch = 1.0
-                    + (STRETCH_GAIN * (TARGET_MIN_BACKLOG_FRAMES - floor))
+                    + (STRETCH_GAIN * (self.target_backlog - floor))
                         .clamp(-STRETCH_MAX, STRETCH_MAX);
             }
         } else {
             self.stretch = 1.0;
         }
 
-        self.tokens = (self.tokens + dt * self.fps).min(BUCKET_CAP);
+        // Above real time the bank and the tick ceiling scale with the speed,
+        // so an 800% grant still fits a 60 Hz tick and a stall still repays
+        // ~100ms of wall time.
+        let headroom = self.speed.max(1.0);
+        self.tokens = (self.tokens + dt * self.fps * self.speed).min(BUCKET_CAP * headroom);
 
         // Deliberately NO backlog ceiling on production: a host consuming
         // slower than nominal must not be able to command the game to skip
@@ -234,7 +289,7 @@ impl Regulator {
         // is off by more than its ±1% authority, the ring drops/zero-fills —
         // the audio degrades (the host's fault, and the diagnostics show it),
         // the game's timeline never does.
-        let n = (self.tokens.floor() as u32).min(MAX_PER_TICK);
+        let n = (self.tokens.floor() as u32).min((f64::from(MAX_PER_TICK) * headroom).ceil() as u32);
         self.tokens -= f64::from(n);
         n
     }
@@ -242,9 +297,11 @@ impl Regulator {
     /// The audio stretch ratio (output pairs per input pair) that keeps the
     /// device fed at ITS clock while the game runs at exactly the wall
     /// clock's [`NOMINAL_FPS`]. Apply to each frame's samples with a
-    /// [`Stretcher`] before pushing them to the sink.
+    /// [`Stretcher`] before pushing them to the sink. Off real-time speed it
+    /// also folds in the inverse of the speed: half speed doubles every
+    /// frame's samples, 400% keeps a quarter.
     pub fn audio_stretch(&self) -> f64 {
-        self.stretch
+        self.stretch / self.speed
     }
 
     /// Seconds until the bank matures its next whole token at the nominal
@@ -253,17 +310,67 @@ impl Regulator {
     /// ~one per frame instead of oversampling; a late wake is harmless (the
     /// bucket banks the elapsed time).
     pub fn seconds_until_next_frame(&self) -> f64 {
-        ((1.0 - self.tokens) / self.fps).max(0.0)
+        ((1.0 - self.tokens) / (self.fps * self.speed)).max(0.0)
     }
+
+    /// Whether the last grant left a whole frame owed: the per-tick ceiling
+    /// cut it short, so the host is not keeping up with real time. Always
+    /// true while fast-forwarding (that mode banks one token by design) —
+    /// exactly when [`FrameSkip::Auto`] should shed presents.
+    pub fn is_behind(&self) -> bool {
+        self.tokens >= 1.0
+    }
+}
+
+/// Longest run of frames [`FrameSkip::Auto`] drops in a row, so a host that
+/// never catches up still shows motion.
+const AUTO_SKIP_MAX: u32 = 3;
+
+/// Decides, frame by frame, which emulated frames reach the display under a
+/// [`FrameSkip`] policy. Pure like the [`Regulator`]; the platform feeds it
+/// the regulator's [`Regulator::is_behind`] after each tick.
+#[derive(Debug, Default)]
+pub struct FrameSkipper {
+    /// Frames dropped since the last one shown.
+    skipped: u32,
 }
 
+impl FrameSkipper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether this tick's newest frame should be presented. `behind` is
+    /// [`Regulator::is_behind`] for the same tick.
+    pub fn present(&mut self, mode: FrameSkip, behind: bool) -> bool {
+        let skip = match mode {
+            FrameSkip::Off => false,
+            FrameSkip::Fixed(n) => self.skipped < u32::from(n),
+            FrameSkip::Auto => behind && self.skipped < AUTO_SKIP_MAX,
+        };
+        if skip {
+            self.skipped += 1;
+        } else {
+            self.skipped = 0;
+        }
+        !skip
+    }
+}
+
+/// Smallest stretch ratio honored: the 800% top emulation speed, with margin
+/// for the clock-offset trim.
+const MIN_STRETCH_RATIO: f64 = 0.1;
+
 /// Micro-resampler applying the regulator's [`Regulator::audio_stretch`]
 /// ratio to each frame's samples before they reach the sink: linear
 /// interpolation, with fractional position and the previous sample pair
 /// carried across calls so arbitrary push sizes stay artifact-free. At the
 /// ≤±1% ratios the regulator produces, linear interpolation of 44.1kHz game
 /// audio is transparent; the pitch shift equals the host clock's own offset
-/// (≤17 cents at the clamp — imperceptible).
+/// (≤17 cents at the clamp — imperceptible). The larger ratios of an
+/// off-real-time emulation speed pitch the audio with the speed; when
+/// decimating, each output pair averages the input pairs it steps over so
+/// the dropped ones don't alias back in.
 #[derive(Debug, Default)]
 pub struct Stretcher {
     /// Fractional read position within the input stream, relative to `prev`.
@@ -289,7 +396,7 @@ impl Stretcher {
             return input;
         }
         self.out.clear();
-        let step = 1.0 / ratio.max(0.5);
+        let step = 1.0 / ratio.max(MIN_STRETCH_RATIO);
         // Virtual input stream: prev (at index 0) followed by `input` (from 1).
         let first = self.prev.unwrap_or(input[0]);
         let at = |i: usize| -> (f32, f32) {
@@ -298,10 +405,23 @@ impl Stretcher {
         let end = input.len() as f64; // last real index in the virtual stream
         while self.pos < end {
             let i = self.pos as usize;
-            let frac = (self.pos - i as f64) as f32;
-            let (a0, b0) = at(i);
-            let (a1, b1) = at(i + 1);
-            self.out.push((a0 + (a1 - a0) * frac, b0 + (b1 - b0) * frac));
+            if step > 1.0 {
+                // Box filter over the pairs since the previous output.
+                let from = (self.pos - step).ceil().max(0.0) as usize;
+                let (mut l, mut r) = (0.0, 0.0);
+                for j in from..=i {
+                    let (a, b) = at(j);
+                    l += a;
+                    r += b;
+                }
+                let n = (i + 1 - from) as f32;
+                self.out.push((l / n, r / n));
+            } else {
+                let frac = (self.pos - i as f64) as f32;
+                let (a0, b0) = at(i);
+                let (a1, b1) = at(i + 1);
+                self.out.push((a0 + (a1 - a0) * frac, b0 + (b1 - b0) * frac));
+            }
             self.pos += step;
         }
         self.pos -= end;
@@ -464,6 +584,13 @@ impl RateMeter {
         self.ema.unwrap_or(0.0)
     }
 
+    /// The readout as a percentage of the machine's own rate: 100.0 in lock,
+    /// 0.0 until [`fps`](Self::fps) has a reading. An SGB1 at ~61.17 fps reads
+    /// 100%, not 102%.
+    pub fn speed_percent(&self) -> f64 {
+        self.fps() / self.fps * 100.0
+    }
+
     /// Cumulative *game-speed* drift in frames versus a perfect
     /// [`NOMINAL_FPS`] timeline since the first emulated frame. In lock this
     /// oscillates within a couple of frames and mean-reverts; a walk means
@@ -526,7 +653,7 @@ mod tests {
                 reg: Regulator::for_cpu_hz(cpu_hz),
                 now: 0.0,
                 // Primed ring.
-                backlog_pairs: audio.then_some(8.0 * samples_per_frame(cpu_hz)),
+                backlog_pairs: audio.then_some(8.0 * samples_per_frame(cpu_hz, HOST_SAMPLE_RATE)),
                 consume_rate: 44_100.0,
                 frames: 0,
                 cpu_hz,
@@ -539,7 +666,7 @@ mod tests {
         }
 
         fn samples_per_frame(&self) -> f64 {
-            samples_per_frame(self.cpu_hz)
+            samples_per_frame(self.cpu_hz, HOST_SAMPLE_RATE)
         }
 
         /// One tick after `dt` seconds; returns frames run.
@@ -557,7 +684,7 @@ mod tests {
             );
             if let Some(b) = self.backlog_pairs.as_mut() {
                 // Production reaches the ring through the stretcher.
-                *b += f64::from(n) * samples_per_frame(self.cpu_hz) * self.reg.audio_stretch();
+                *b += f64::from(n) * samples_per_frame(self.cpu_hz, HOST_SAMPLE_RATE) * self.reg.audio_stretch();
             }
             self.frames += u64::from(n);
             n
@@ -969,31 +1096,33 @@ mod tests {
     // --- the SGB1 clock model ------------------------------------------------
 
     /// **THE identity that keeps the two coupled sites in balance.** The core
-    /// emits `70224 / (cpu_hz/44100)` sample pairs per frame and the regulator
+    /// emits `70224 / (cpu_hz/rate)` sample pairs per frame and the regulator
     /// presents `cpu_hz / 70224` frames per second. Their product must be
-    /// exactly 44 100 pairs/second on EVERY model — that is what lets an SGB1
-    /// be pitched up 2.4% while the host DAC still receives its true rate.
-    /// Change the pitch site without the cadence site (or vice versa) and this
-    /// fails.
+    /// exactly `rate` pairs/second on EVERY model and every offered rate —
+    /// that is what lets an SGB1 be pitched up 2.4% while the host DAC still
+    /// receives its true rate. Change the pitch site without the cadence site
+    /// (or vice versa) and this fails.
     #[test]
-    fn host_sample_rate_is_44100_on_every_model() {
-        for (name, hz) in [
-            ("DMG", DMG_HZ),
-            ("SGB1 NTSC", SGB_NTSC_HZ),
-            ("SGB1 PAL", SGB_PAL_HZ),
-            ("SGB2", DMG_HZ),
-            ("CGB", DMG_HZ),
-        ] {
-            // Exactly the core's `generate_samples` ratio.
-            let cycles_per_sample = f64::from(hz) / HOST_SAMPLE_RATE;
-            let pairs_per_frame = DOTS_PER_FRAME / cycles_per_sample;
-            let pairs_per_second = pairs_per_frame * nominal_fps(hz);
-            assert!(
-                (pairs_per_second - HOST_SAMPLE_RATE).abs() < 1e-6,
-                "{name}: host output {pairs_per_second:.4} Hz, must be 44100"
-            );
-            // And the session's own helper must agree with that pairs/frame.
-            assert!((samples_per_frame(hz) - pairs_per_frame).abs() < 1e-9, "{name}");
+    fn host_sample_rate_is_exact_on_every_model() {
+        for rate in crate::action::SAMPLE_RATES {
+            for (name, hz) in [
+                ("DMG", DMG_HZ),
+                ("SGB1 NTSC", SGB_NTSC_HZ),
+                ("SGB1 PAL", SGB_PAL_HZ),
+                ("SGB2", DMG_HZ),
+                ("CGB", DMG_HZ),
+            ] {
+                // Exactly the core's `generate_samples` ratio.
+                let cycles_per_sample = f64::from(hz) / f64::from(rate);
+                let pairs_per_frame = DOTS_PER_FRAME / cycles_per_sample;
+                let pairs_per_second = pairs_per_frame * nominal_fps(hz);
+                assert!(
+                    (pairs_per_second - f64::from(rate)).abs() < 1e-6,
+                    "{name}: host output {pairs_per_second:.4} Hz, must be {rate}"
+                );
+                // And the session's own helper must agree with that pairs/frame.
+                assert!((samples_per_frame(hz, rate) - pairs_per_frame).abs() < 1e-9, "{name} @ {rate}");
+            }
         }
     }
 
@@ -1067,6 +1196,40 @@ mod tests {
         assert!((reg.nominal_fps() - NOMINAL_FPS).abs() < 1e-12);
     }
 
+    /// `set_sample_rate` rescales the backlog's frame length and keeps it in
+    /// step with a later clock change.
+    #[test]
+    fn set_sample_rate_retunes_the_backlog_frame() {
+        let mut reg = Regulator::new();
+        reg.set_sample_rate(96_000);
+        assert!((reg.samples_per_frame - samples_per_frame(DMG_HZ, 96_000)).abs() < 1e-9);
+        reg.set_cpu_hz(SGB_NTSC_HZ);
+        assert!((reg.samples_per_frame - samples_per_frame(SGB_NTSC_HZ, 96_000)).abs() < 1e-9);
+    }
+
+    /// A raised latency target moves the ring's floor up by the same number
+    /// of frames.
+    #[test]
+    fn target_backlog_sets_the_ring_floor() {
+        let settled_floor = |frames: u32| {
+            let mut sim = Sim::new(true);
+            sim.reg.set_target_backlog(frames);
+            sim.run(30.0, 60.0, 0.0);
+            let end = sim.now + 10.0;
+            let mut floor = f64::INFINITY;
+            while sim.now < end {
+                sim.tick(1.0 / 60.0);
+                floor = floor.min(sim.backlog_pairs.unwrap() / sim.samples_per_frame());
+            }
+            floor
+        };
+        // Sampled after each tick's production, so both read about a frame
+        // over their target; the difference is what the setting controls.
+        let (low, high) = (settled_floor(TARGET_MIN_BACKLOG_FRAMES), settled_floor(6));
+        let raised = high - low;
+        assert!((raised - 4.0).abs() < 0.5, "floor rose {raised:.2} frames ({low:.2} → {high:.2})");
+    }
+
     /// The drift counter must grade against the machine's own rate: an SGB1
     /// locked at its true 61.17 fps is NOT drifting, and reporting it as such
     /// would bury the signal the counter exists for.
@@ -1085,6 +1248,8 @@ mod tests {
             "meter read {fps:.2} for an SGB1, expected ~61.17"
         );
         assert!(meter.drift_frames(now).abs() < 4.0, "a locked SGB1 read as drifting");
+        let speed = meter.speed_percent();
+        assert!((speed - 100.0).abs() < 0.5, "a locked SGB1 read {speed:.1}% speed");
 
         // The same stream graded as a DMG would look badly fast — proof the
         // rate-awareness is load-bearing and not cosmetic.
@@ -1099,4 +1264,90 @@ mod tests {
             "DMG-graded SGB1 stream should show a large positive drift"
         );
     }
+
+    /// The speed readout is 0 before there is a rate and tracks a session
+    /// running at half speed.
+    #[test]
+    fn meter_reports_speed_as_a_percentage() {
+        let mut meter = RateMeter::new();
+        assert_eq!(meter.speed_percent(), 0.0);
+        let period = 2.0 / NOMINAL_FPS;
+        let mut now = 0.0;
+        for _ in 0..(NOMINAL_FPS * 15.0) as u32 {
+            now += period;
+            meter.record(now, 1);
+        }
+        let speed = meter.speed_percent();
+        assert!((speed - 50.0).abs() < 0.5, "half-rate stream read {speed:.1}%");
+    }
+
+    /// `Fixed(n)` shows exactly one frame in every `n + 1`, regardless of
+    /// whether the host keeps up.
+    #[test]
+    fn fixed_skip_shows_one_in_n_plus_one() {
+        let mut skipper = FrameSkipper::new();
+        let shown: Vec<bool> = (0..9).map(|_| skipper.present(FrameSkip::Fixed(2), false)).collect();
+        assert_eq!(shown, [false, false, true, false, false, true, false, false, true]);
+        let mut off = FrameSkipper::new();
+        assert!((0..10).all(|_| off.present(FrameSkip::Off, true)));
+    }
+
+    /// `Auto` presents every frame on time, sheds frames only while behind,
+    /// and never more than `AUTO_SKIP_MAX` in a row.
+    #[test]
+    fn auto_skip_follows_the_regulator() {
+        let mut skipper = FrameSkipper::new();
+        assert!((0..10).all(|_| skipper.present(FrameSkip::Auto, false)));
+        let shown: Vec<bool> = (0..8).map(|_| skipper.present(FrameSkip::Auto, true)).collect();
+        assert_eq!(shown, [false, false, false, true, false, false, false, true]);
+        assert!(skipper.present(FrameSkip::Auto, false));
+
+        // A tick capped at MAX_PER_TICK leaves the rest owed: behind. A
+        // healthy cadence never is.
+        let mut reg = Regulator::new();
+        reg.frames_to_run(0.0, None, false, false);
+        assert!(!reg.is_behind());
+        assert_eq!(reg.frames_to_run(0.5, None, false, false), MAX_PER_TICK);
+        assert!(reg.is_behind(), "a 500ms stall should leave frames owed");
+        let mut now = 0.5;
+        for _ in 0..120 {
+            now += 1.0 / 60.0;
+            reg.frames_to_run(now, None, false, false);
+        }
+        assert!(!reg.is_behind());
+    }
+
+    /// Every offered emulation speed locks at a 60 Hz tick — 800% included,
+    /// which needs more than `MAX_PER_TICK` frames per tick — and folds the
+    /// inverse speed into the audio stretch.
+    #[test]
+    fn speed_scales_the_timeline_and_the_audio() {
+        for percent in [25, 50, 100, 200, 800] {
+            let mut reg = Regulator::new();
+            reg.set_speed(percent);
+            // The first tick only starts the clock (and spends the seed token).
+            let (mut now, mut frames) = (0.0, u64::from(reg.frames_to_run(0.0, None, false, false)));
+            for _ in 0..600 {
+                now += 1.0 / 60.0;
+                frames += u64::from(reg.frames_to_run(now, None, false, false));
+            }
+            let want = 1.0 + NOMINAL_FPS * f64::from(percent) / 100.0 * now;
+            assert!((frames as f64 - want).abs() <= 2.0, "{percent}%: {frames} frames, want ~{want:.0}");
+            assert!((reg.audio_stretch() - 100.0 / f64::from(percent)).abs() < 1e-9);
+        }
+    }
+
+    /// Decimating for a fast emulation speed averages what it steps over: the
+    /// output count follows the ratio and a constant input stays constant.
+    #[test]
+    fn stretcher_decimates_for_high_speeds() {
+        let mut st = Stretcher::new();
+        let input = vec![(0.5f32, -0.25f32); 2000];
+        let mut out: Vec<(f32, f32)> = Vec::new();
+        for chunk in input.chunks(367) {
+            out.extend_from_slice(st.process(chunk, 0.125));
+        }
+        assert!((out.len() as isize - 250).abs() <= 2, "output {} pairs, expected ~250", out.len());
+        assert!(out.iter().all(|&p| p == (0.5, -0.25)));
+    }
 }
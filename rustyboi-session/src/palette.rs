@@ -0,0 +1,111 @@
+//! User palettes for mono games: four colours, lightest first, loaded from a
+//! small text file or typed as hex, persisted in [`Config`](crate::Config)
+//! and shown as [`DmgPaletteChoice::Custom`].
+//!
+//! Two text forms are accepted:
+//!
+//! - four hex colours (`#9BBC0F 8BAC0F, 0x306230 …`), separated by spaces,
+//!   commas or newlines, with `;` / `//` comment lines skipped;
+//! - a JASC-PAL file (`JASC-PAL`, `0100`, a count, then `R G B` lines), the
+//!   format most palette editors export; its first four colours are used.
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::DmgPaletteChoice;
+
+/// A named user palette.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomPalette {
+    pub name: String,
+    /// Shade 0 (lightest) .. 3 (darkest).
+    pub shades: [[u8; 3]; 4],
+}
+
+impl CustomPalette {
+    /// The choice that shows this palette.
+    pub fn choice(&self) -> DmgPaletteChoice {
+        DmgPaletteChoice::Custom(self.shades)
+    }
+
+    /// The shades as `#RRGGBB` text, the form [`parse_palette`] reads back.
+    pub fn to_hex(&self) -> String {
+        self.shades.map(|[r, g, b]| format!("#{r:02X}{g:02X}{b:02X}")).join(" ")
+    }
+}
+
+/// Parse palette text (hex list or JASC-PAL) into four shades.
+pub fn parse_palette(text: &str) -> Result<[[u8; 3]; 4], String> {
+    let lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    let colours = if lines.clone().next() == Some("JASC-PAL") {
+        parse_jasc(lines.skip(1))?
+    } else {
+        parse_hex(lines)?
+    };
+    let shades: [[u8; 3]; 4] = colours
+        .get(..4)
+        .and_then(|c| c.try_into().ok())
+        .ok_or_else(|| format!("a palette needs 4 colours, found {}", colours.len()))?;
+    Ok(shades)
+}
+
+fn parse_hex<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Vec<[u8; 3]>, String> {
+    lines
+        .filter(|l| !l.starts_with(';') && !l.starts_with("//"))
+        .flat_map(|l| l.split(|c: char| c.is_whitespace() || c == ','))
+        .filter(|t| !t.is_empty())
+        .map(|token| {
+            let digits = token.trim_start_matches('#').trim_start_matches("0x").trim_start_matches("0X");
+            let value = (digits.len() == 6)
+                .then(|| u32::from_str_radix(digits, 16).ok())
+                .flatten()
+                .ok_or_else(|| format!("`{token}` is not an RRGGBB hex colour"))?;
+            Ok([(value >> 16) as u8, (value >> 8) as u8, value as u8])
+        })
+        .collect()
+}
+
+fn parse_jasc<'a>(mut lines: impl Iterator<Item = &'a str>) -> Result<Vec<[u8; 3]>, String> {
+    // Version line, then the declared colour count (the entries are what count).
+    lines.next().ok_or("JASC-PAL file is truncated")?;
+    lines.next().ok_or("JASC-PAL file is truncated")?;
+    lines
+        .map(|l| {
+            let rgb: Vec<u8> = l.split_whitespace().filter_map(|v| v.parse().ok()).collect();
+            <[u8; 3]>::try_from(rgb.as_slice()).map_err(|_| format!("`{l}` is not an `R G B` entry"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREEN: [[u8; 3]; 4] =
+        [[0x9B, 0xBC, 0x0F], [0x8B, 0xAC, 0x0F], [0x30, 0x62, 0x30], [0x0F, 0x38, 0x0F]];
+
+    #[test]
+    fn hex_lists_parse_in_any_separator_style() {
+        assert_eq!(parse_palette("#9BBC0F #8BAC0F #306230 #0F380F"), Ok(GREEN));
+        assert_eq!(parse_palette("; dmg\n9bbc0f,8bac0f\n0x306230, 0X0F380F\n"), Ok(GREEN));
+    }
+
+    #[test]
+    fn jasc_pal_uses_the_first_four_entries() {
+        let text = "JASC-PAL\r\n0100\r\n5\r\n155 188 15\r\n139 172 15\r\n48 98 48\r\n15 56 15\r\n0 0 0\r\n";
+        assert_eq!(parse_palette(text), Ok(GREEN));
+    }
+
+    #[test]
+    fn short_or_malformed_text_is_rejected() {
+        assert!(parse_palette("#FFFFFF #AAAAAA #555555").is_err());
+        assert!(parse_palette("#FFFFFF #AAAAAA #555555 #00000G").is_err());
+        assert!(parse_palette("JASC-PAL\n0100\n4\n1 2\n").is_err());
+    }
+
+    #[test]
+    fn to_hex_round_trips() {
+        let p = CustomPalette { name: "Green".into(), shades: GREEN };
+        assert_eq!(p.to_hex(), "#9BBC0F #8BAC0F #306230 #0F380F");
+        assert_eq!(parse_palette(&p.to_hex()), Ok(GREEN));
+    }
+}
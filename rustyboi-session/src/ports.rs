@@ -40,10 +40,25 @@ impl fmt::Display for StorageError {
 
 impl std::error::Error for StorageError {}
 
+/// `Send` on every target with threads and no bound at all on wasm32, whose
+/// adapters hold JS handles. Ports and sinks take it as a supertrait so a
+/// native `Session` can move onto an emulation thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+/// `Send` on every target with threads and no bound at all on wasm32, whose
+/// adapters hold JS handles. Ports and sinks take it as a supertrait so a
+/// native `Session` can move onto an emulation thread.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
+
 /// A flat, string-keyed blob store: savestates, config, SRAM, movies. The
 /// session composes keys itself (rom id + slot, etc.); the adapter only has to
 /// map a `&str` key to a byte blob in whatever namespace it owns.
-pub trait Storage {
+pub trait Storage: MaybeSend {
     /// Read the blob stored at `key`, or `None` if absent.
     fn read(&self, key: &str) -> Option<Vec<u8>>;
     /// Write (create or overwrite) the blob at `key`.
@@ -57,7 +72,7 @@ pub trait Storage {
 /// The cartridge rumble motor. `set(true)` energizes it, `set(false)` stops
 /// it. The session calls this once per frame with the emulated rumble state so
 /// idempotent adapters are fine.
-pub trait Rumble {
+pub trait Rumble: MaybeSend {
     fn set(&mut self, on: bool);
 }
 
@@ -65,22 +80,34 @@ pub trait Rumble {
 /// as 128x112 (== 14336) grayscale bytes, one byte per pixel, row-major, or
 /// `None` when no frame is available (the session then leaves the last image
 /// in place). The session validates the length before handing it to the core.
-pub trait Webcam {
+pub trait Webcam: MaybeSend {
     fn grab(&mut self) -> Option<Vec<u8>>;
 }
 
 /// Expected pixel count of a [`Webcam::grab`] frame (128 x 112 grayscale).
-pub(crate) const WEBCAM_PIXELS: usize = 128 * 112;
+pub const WEBCAM_PIXELS: usize = 128 * 112;
 
 /// A byte-oriented transport for link cable / IR / Mobile Adapter traffic.
 /// Stub-friendly: a null adapter that never sends and always returns empty is
 /// a valid no-link implementation. `send` enqueues outbound bytes; `recv`
 /// drains whatever inbound bytes have arrived since the last call.
-pub trait NetTransport {
+pub trait NetTransport: MaybeSend {
     fn send(&mut self, bytes: &[u8]);
     fn recv(&mut self) -> Vec<u8>;
 }
 
+/// A one-way feed to remote viewers (spectator streaming). Each `send` is one
+/// whole message; the adapter fans it out to whoever is watching and drops it
+/// when nobody is.
+pub trait Broadcast: MaybeSend {
+    fn send(&mut self, message: &[u8]);
+
+    /// Service viewers between messages (accept and answer newcomers). The
+    /// host calls this every tick, since a still or paused screen sends
+    /// nothing. No-op by default.
+    fn poll(&mut self) {}
+}
+
 // ---------------------------------------------------------------------------
 // In-memory fakes (available to downstream tests + this crate's own tests).
 // ---------------------------------------------------------------------------
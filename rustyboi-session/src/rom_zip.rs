@@ -1,10 +1,11 @@
-//! Extract the ROM image from a `.zip` container.
+//! Extract the ROM image from a `.zip` (or `.7z` / `.gz`) container.
 //!
-//! `Cartridge::from_bytes` already unzips when building the machine, but the
-//! session must unzip too — otherwise `original_rom` (used for game
+//! `Cartridge::from_bytes` already unpacks when building the machine, but the
+//! session must unpack too — otherwise `original_rom` (used for game
 //! identification, cheat-DB lookup, ROM patching, and the rom id) would hold the
-//! archive bytes instead of the ROM, so a zipped game runs but can't be
-//! identified.
+//! archive bytes instead of the ROM, so an archived game runs but can't be
+//! identified. Zip keeps its own extractor (below) because the Android scanner
+//! mirrors it; 7z and gzip go through the core's.
 
 use std::io::{Cursor, Read};
 
@@ -16,9 +17,10 @@ use std::io::{Cursor, Read};
 const EXTS: [&str; 3] = [".gb", ".gbc", ".sgb"];
 
 /// If `bytes` is a zip, return the contained ROM (a `.gb`/`.gbc`/`.sgb` entry, or
-/// else the largest file); otherwise return `bytes` unchanged. A malformed or
-/// unsupported archive falls back to the raw bytes so the cartridge loader
-/// surfaces the error.
+/// else the largest file); a 7z or gzip is unpacked by
+/// `Cartridge::extract_rom_bytes` under the same rule; otherwise return `bytes`
+/// unchanged. A malformed or unsupported archive falls back to the raw bytes so
+/// the cartridge loader surfaces the error.
 ///
 /// KEEP IN SYNC with `RomScan.crcOfRomStream` in
 /// `android/romscan/src/main/kotlin/dev/mcswain/rustyboi/RomScan.kt`: the Android
@@ -28,7 +30,8 @@ const EXTS: [&str; 3] = [".gb", ".gbc", ".sgb"];
 /// Kotlin unit tests mirror them on the same synthetic archives.
 pub(crate) fn extract_rom(bytes: &[u8]) -> Vec<u8> {
     if bytes.len() < 4 || &bytes[..4] != b"PK\x03\x04" {
-        return bytes.to_vec();
+        return rustyboi_core_lib::cartridge::Cartridge::extract_rom_bytes(bytes)
+            .unwrap_or_else(|_| bytes.to_vec());
     }
     extract_from_zip(bytes).unwrap_or_else(|| bytes.to_vec())
 }
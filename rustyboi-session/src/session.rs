@@ -6,28 +6,40 @@
 //! through the boxed service ports; video+audio come back as return values.
 //! No wall clock, no filesystem, no threads: WASM-clean.
 
+mod apu_scope;
+mod audio_recording;
 mod cheat_ops;
+mod gif_recording;
+mod link;
+mod netplay;
+mod osd;
 mod printer;
+mod recovery;
 mod rewind;
 mod save_data;
+mod screenshot;
 mod settings;
 mod slots;
+mod spectate;
 mod tas;
+mod video_recording;
 
 use crate::action::DmgPaletteChoice;
 use crate::audio::{CaptureSink, SampleBuf};
 use crate::cheats::CheatSet;
 use crate::config::Config;
-use crate::input::AbstractInput;
+use crate::input::{AbstractInput, GbButton};
+use crate::no_intro::DumpStatus;
 use crate::ports::{Rumble, Storage, StorageError, Webcam, WEBCAM_PIXELS};
 use crate::rewind::RewindBuffer;
 use crate::tas::{Playback, Recording};
 
-use rustyboi_core_lib::cartridge::Cartridge;
+use rustyboi_core_lib::cartridge::{Cartridge, RtcFormat};
 use rustyboi_core_lib::gb::{Frame, Hardware, GB};
 use rustyboi_core_lib::input::ButtonState;
-use rustyboi_core_lib::movie::Movie;
+use rustyboi_core_lib::movie_import;
 use rustyboi_core_lib::printer::PrintSheet;
+use rustyboi_core_lib::savestate;
 
 use std::sync::{Arc, Mutex};
 
@@ -124,6 +136,9 @@ pub enum SessionError {
     RomMismatch,
     /// A TAS movie file failed to decode.
     Movie(String),
+    /// The movie starts from a savestate it only names by hash, and no
+    /// stored state matches that hash.
+    AnchorMissing,
     /// Operation needs a cartridge but none is inserted.
     NoCartridge,
 }
@@ -142,6 +157,7 @@ impl core::fmt::Display for SessionError {
             SessionError::NoState => write!(f, "no saved state in slot"),
             SessionError::RomMismatch => write!(f, "movie ROM does not match loaded ROM"),
             SessionError::Movie(e) => write!(f, "movie decode error: {e}"),
+            SessionError::AnchorMissing => write!(f, "movie's starting savestate was not found"),
             SessionError::NoCartridge => write!(f, "no cartridge inserted"),
         }
     }
@@ -159,6 +175,21 @@ pub struct Ports {
     pub webcam: Box<dyn Webcam>,
 }
 
+/// A multi-ROM archive held until the user picks which member to load.
+struct PendingArchive {
+    bytes: Vec<u8>,
+    entries: Vec<String>,
+    patch: Option<Vec<u8>>,
+}
+
+// A native host may run the session on its own emulation thread; keep that
+// possible (every port and sink is `MaybeSend`).
+#[cfg(not(target_arch = "wasm32"))]
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<Session>();
+};
+
 /// The frontend-agnostic emulator session.
 pub struct Session {
     // Boxed so the ~207 KB machine (four inline framebuffers) stays heap-
@@ -192,12 +223,62 @@ pub struct Session {
     /// a fetch completes; cleared on dismiss or a fresh fetch.
     fetched_cheats: Vec<crate::cheat_db::FetchedCheat>,
 
+    /// A zip/7z bundle of several ROMs awaiting the user's pick, held by
+    /// [`hold_archive_bundle`](Self::hold_archive_bundle) with the soft-patch
+    /// that arrived alongside it; its member names are surfaced in
+    /// [`SessionUiState`](crate::action::SessionUiState).
+    pending_archive: Option<PendingArchive>,
+
+    /// `(sha1, name)` pairs, sorted, from the DAT the user pointed the config
+    /// at ([`load_verification_dat`](Self::load_verification_dat)); empty when
+    /// none is configured, which turns dump verification off.
+    verify_dat: Vec<([u8; 20], String)>,
+
+    /// SHA-1 of the loaded ROM and its standing against `verify_dat`, worked
+    /// out once per cartridge (see `refresh_rom_digest`) rather than by every
+    /// ROM Info refresh hashing a multi-megabyte image.
+    rom_sha1: Option<[u8; 20]>,
+    dump: Option<DumpStatus>,
+
+    /// `(slot, timestamp)` for each filled numbered slot of the current ROM,
+    /// refreshed when a slot is written or the ROM changes so the per-frame
+    /// [`ui_state`](Self::ui_state) never re-reads the state blobs.
+    slot_times: Vec<(u32, u64)>,
+
+    /// `(slot, RGB888 thumbnail)` for each filled numbered slot whose state
+    /// carries one, cached alongside `slot_times`.
+    slot_thumbnails: Vec<(u32, Vec<u8>)>,
+
+    /// The machine (raw state + frame count) as it was just before the last
+    /// state load, so [`undo_load_state`](Self::undo_load_state) can take a
+    /// mis-pressed load back. Dropped when a different ROM goes in.
+    load_backup: Option<(Vec<u8>, u64)>,
+
+    /// `(frame, machine clone)` crash-recovery snapshots, oldest first (see
+    /// the `recovery` module).
+    recovery: std::collections::VecDeque<(u64, Box<GB>)>,
+
+    /// The auto slot's timestamp while the just-loaded ROM's resume offer is
+    /// open (see [`resume_offer`](Self::resume_offer)); cleared once answered.
+    resume_offer: Option<u64>,
+
+    /// The latest on-screen notice (see the `osd` module).
+    osd: Option<crate::action::OsdMessage>,
+    /// Controllers the host reported connected (not persisted).
+    connected_pads: Vec<crate::input_config::ConnectedPad>,
+
     mode: RunMode,
+    /// While a hold-to-fast-forward key is down: whether its press started
+    /// the fast-forward (so its release ends it). `None` when not held.
+    ff_hold: Option<bool>,
     frame_count: u64,
 
     rewind: RewindBuffer,
     recording: Option<Recording>,
     playback: Option<Playback>,
+    /// The TAS input editor's timeline while it is open; it then owns the
+    /// input of every frame run.
+    input_editor: Option<crate::tas::InputEditor>,
 
     /// When set, `step_one` does NOT serialize the rewind snapshot inline.
     /// Instead a due capture is exposed via [`Session::take_pending_snapshot`]
@@ -242,6 +323,65 @@ pub struct Session {
     /// strips fed out together, so [`take_prints`](Self::take_prints) stitches
     /// them vertically into one long sheet, breaking on the paper-feed margins.
     printer_strips: Vec<PrintSheet>,
+
+    /// The audio output rate the platform opened its device at, fixed for the
+    /// session's life (a changed [`Config::sample_rate`] applies at the next
+    /// launch). Re-applied to the APU every frame, like the other audio
+    /// preferences.
+    audio_sample_rate: u32,
+
+    /// Output devices the platform last enumerated, by name (offered in
+    /// Settings → Audio Output Device). A host resource like `sgb_firmware`:
+    /// empty until a platform that can choose a device pushes its list.
+    audio_devices: Vec<String>,
+
+    /// The WAV capture in progress (Emulation → Record Audio), fed from
+    /// `step_one`. Host output, not machine state: a state load or rewind
+    /// keeps recording across the jump.
+    audio_recording: Option<crate::audio::AudioRecording>,
+
+    /// Where the host streams audio captures, when it does (see
+    /// [`set_audio_sink_opener`](Self::set_audio_sink_opener)); without one
+    /// they collect in memory for a `SaveBytes` export.
+    audio_sink_opener: Option<crate::audio::AudioSinkOpener>,
+
+    /// The GIF capture in progress (Emulation → Record GIF), fed the
+    /// presented frame from `step_one`. Host output like `audio_recording`.
+    gif_recording: Option<crate::gif::GifRecording>,
+
+    /// The video capture in progress (Emulation → Record Video,
+    /// `--record-video`), fed each frame's emulated time, samples, and
+    /// presented frame from `step_one`. Host output like `audio_recording`.
+    video_recording: Option<crate::video::VideoRecording>,
+
+    /// The last frame's channel levels for the APU debug panel's oscilloscope,
+    /// taken from the channel tap. `Some` only while the panel is open (see
+    /// [`set_apu_scope`](Self::set_apu_scope)), so a closed panel costs no tap.
+    apu_scope: Option<Vec<[f32; 4]>>,
+
+    /// Refuse to boot a ROM whose header checksum ($014D) does not match, the
+    /// way the DMG boot ROM would (the `--strict-header` CLI flag). Off by
+    /// default: a mismatch then only produces [`header_warning`](Self::header_warning).
+    strict_header: bool,
+    /// The `--rtc-format` choice for this run, over `config.rtc_format` (see
+    /// [`rtc_format`](Self::rtc_format)). Never persisted.
+    rtc_format_override: Option<RtcFormat>,
+    /// The second Game Boy on the link cable, when one is running (see
+    /// [`start_link_partner`](Self::start_link_partner)).
+    link_partner: Option<link::LinkPartner>,
+    /// The link port's network transport while netplay runs (see
+    /// [`start_netplay`](Self::start_netplay)).
+    netplay: Option<netplay::Netplay>,
+    /// Collect the bytes the link port shifts out for a frontend to echo
+    /// (the `--serial-stdout` CLI flag; see
+    /// [`drain_serial_output`](Self::drain_serial_output)).
+    serial_capture: bool,
+    /// Remote viewers watching this session (see
+    /// [`start_spectating`](Self::start_spectating)).
+    spectating: Option<spectate::Spectating>,
+    /// Buttons an automation client holds on top of live input, and for how
+    /// many more emulated frames (see [`hold_buttons`](Self::hold_buttons)).
+    held_buttons: Option<(ButtonState, u32)>,
 }
 
 impl Session {
@@ -266,16 +406,14 @@ impl Session {
         // enable_audio only errors if a sink was already installed or start()
         // fails; our CaptureSink::start is infallible and gb is fresh here.
         let _ = gb.enable_audio(Box::new(CaptureSink::new(audio_buf.clone())));
-        // Presentation-only machine settings (CGB colour correction) apply to the
-        // caller's already-prepared machine here; every later (re)build funnels
-        // through `apply_presentation`.
-        gb.set_cgb_color_conversion(config.color_correction);
-        gb.set_dmg_palette(config.dmg_palette_choice);
-        gb.set_sgb_palette(config.sgb_palette);
-        gb.set_region(config.region);
         let rewind = RewindBuffer::new(config.rewind.depth, config.rewind.interval_frames);
         let palette = config.dmg_palette_choice;
-        Session {
+        let audio_sample_rate = if crate::action::SAMPLE_RATES.contains(&config.sample_rate) {
+            config.sample_rate
+        } else {
+            crate::action::SAMPLE_RATES[0]
+        };
+        let mut session = Session {
             gb,
             config,
             ports,
@@ -284,11 +422,24 @@ impl Session {
             original_rom: None,
             game_name: None,
             fetched_cheats: Vec::new(),
+            pending_archive: None,
+            verify_dat: Vec::new(),
+            rom_sha1: None,
+            dump: None,
+            slot_times: Vec::new(),
+            slot_thumbnails: Vec::new(),
+            load_backup: None,
+            recovery: std::collections::VecDeque::new(),
+            resume_offer: None,
+            osd: None,
+            connected_pads: Vec::new(),
             mode: RunMode::Normal,
+            ff_hold: None,
             frame_count: 0,
             rewind,
             recording: None,
             playback: None,
+            input_editor: None,
             rewind_offloaded: false,
             pending_snapshot: None,
             audio_buf,
@@ -299,21 +450,60 @@ impl Session {
             pending_step_cycles: None,
             pending_step_frames: None,
             printer_strips: Vec::new(),
+            audio_sample_rate,
+            audio_devices: Vec::new(),
+            audio_recording: None,
+            audio_sink_opener: None,
+            gif_recording: None,
+            video_recording: None,
+            apu_scope: None,
+            strict_header: false,
+            rtc_format_override: None,
+            link_partner: None,
+            netplay: None,
+            serial_capture: false,
+            spectating: None,
+            held_buttons: None,
+        };
+        // The caller's already-prepared machine takes the presentation
+        // settings here; every later (re)build funnels through the same call.
+        session.apply_presentation();
+        session.refresh_rom_digest();
+        session.apply_machine_config();
+        session.refresh_slot_times();
+        session.offer_resume();
+        session
+    }
+
+    /// Apply the presentation-only machine settings (palettes, colour
+    /// correction, the renderer and audio preferences) to the main machine
+    /// and the link partner. Called when one of them changes and from every
+    /// sink that installs a fresh `GB` (a restart, state load, or rewind step
+    /// rebuilds the PPU and APU), so they are not re-pushed every frame.
+    /// Presentation-only: it never affects emulation determinism. (The OAM bug
+    /// does, so it goes through [`apply_machine_config`](Self::apply_machine_config).)
+    pub(super) fn apply_presentation(&mut self) {
+        let (scanline, rate) = (self.config.scanline_ppu || self.scanline_ppu(), self.audio_sample_rate);
+        Self::present_on(&self.config, scanline, rate, &mut self.gb);
+        if let Some(link) = self.link_partner.as_mut() {
+            Self::present_on(&self.config, scanline, rate, link.gb_mut());
         }
     }
 
-    /// Re-apply presentation-only machine settings (currently CGB colour
-    /// correction) after a machine (re)build. Called from every sink that
-    /// installs a fresh `GB` so the setting survives ROM restarts and state
-    /// loads. Presentation-only: it never affects emulation determinism.
-    fn apply_presentation(&mut self) {
-        self.gb.set_cgb_color_conversion(self.config.color_correction);
-        self.gb.set_dmg_palette(self.config.dmg_palette_choice);
-        self.gb.set_sgb_palette(self.config.sgb_palette);
+    /// [`apply_presentation`](Self::apply_presentation) for one machine the
+    /// session drives (the link partner shares the main machine's look).
+    fn present_on(config: &Config, scanline_ppu: bool, sample_rate: u32, gb: &mut GB) {
+        gb.set_cgb_color_conversion(config.color_correction);
+        gb.set_dmg_palette(config.shown_dmg_palette());
+        gb.set_sgb_palette(config.sgb_palette);
         // Real-time mapping, so it is `#[serde(skip)]` in the core and must be
         // re-seeded here after a savestate restore (same contract as the
         // palette above).
-        self.gb.set_region(self.config.region);
+        gb.set_region(config.region);
+        gb.set_scanline_ppu(scanline_ppu);
+        gb.set_audio_high_pass(config.audio_high_pass);
+        gb.set_audible_channels(config.channel_mix.audible());
+        gb.set_audio_sample_rate(sample_rate);
     }
 
     /// The running machine's real-time CPU clock in Hz. An SGB1 derives its
@@ -325,6 +515,13 @@ impl Session {
         self.config.hardware.cpu_hz(self.config.region)
     }
 
+    /// The audio output rate in Hz the core emits at for this session. Platforms
+    /// open their device at this rate and feed it to
+    /// [`crate::pacing::Regulator::set_sample_rate`].
+    pub fn audio_sample_rate(&self) -> u32 {
+        self.audio_sample_rate
+    }
+
     /// Boot a freshly-built machine by seeding the synthetic post-boot state.
     /// (No session path supplies real boot-ROM bytes; the `use_real_boot_rom`
     /// config flag persists but only the platform `--bios` CLI loads a BIOS,
@@ -351,16 +548,19 @@ impl Session {
     /// audio. `raw` is the host's abstract input for this frame. During movie
     /// playback the recorded input overrides `raw`.
     pub fn run_frame(&mut self, raw: AbstractInput) -> FrameOutput {
+        rustyboi_core_lib::profile_span!("run_frame");
         let live_state = raw.button_state();
         self.audio_buf.lock().unwrap_or_else(|e| e.into_inner()).clear();
 
         let (frame, advanced) = match self.mode {
             RunMode::Paused => (self.gb.get_current_frame(), false),
-            RunMode::Normal | RunMode::FrameAdvance => {
+            RunMode::Normal => {
                 let f = self.step_one(live_state);
-                if self.mode == RunMode::FrameAdvance {
-                    self.mode = RunMode::Paused;
-                }
+                (self.frame_ahead(f), true)
+            }
+            RunMode::FrameAdvance => {
+                let f = self.step_one(live_state);
+                self.mode = RunMode::Paused;
                 (f, true)
             }
             RunMode::FastForward(factor) => {
@@ -379,10 +579,10 @@ impl Session {
         // Fast-forward produced `factor`× the samples this frame; resample the
         // output copy back to one real-time frame's worth so it plays cleanly on
         // every platform instead of backing up (uncapped has no fixed ratio, so
-        // it's muted).
+        // it's muted, as is every speed when the user asked for silence).
         let gain = self.config.volume_gain();
         let audio = match self.mode {
-            RunMode::FastForward(_) if self.config.ff_uncapped() => Vec::new(),
+            RunMode::FastForward(_) if self.config.ff_uncapped() || self.config.fast_forward_muted => Vec::new(),
             RunMode::FastForward(n) => {
                 let drained: Vec<(f32, f32)> =
                     self.audio_buf.lock().unwrap_or_else(|e| e.into_inner()).drain(..).collect();
@@ -393,19 +593,47 @@ impl Session {
         FrameOutput { frame, audio, frame_count: self.frame_count, advanced }
     }
 
-    /// Emulate exactly one frame: pick the input (movie playback overrides
-    /// live), pump the webcam/cheats, step the GB, service rumble, record, and
-    /// snapshot for rewind.
+    /// Run-ahead: emulate one frame past the live machine on a throwaway
+    /// clone, with the input `step_one` just latched, and show that frame in
+    /// place of `live`. The live machine never runs speculatively, so an input
+    /// that changes next frame takes effect there as usual; rolling back is
+    /// dropping the clone. A clone carries no audio sink, save file or link
+    /// device, so nothing it does escapes. Off with a link partner or netplay
+    /// peer, which the clone can't take along, and when a breakpoint lies in
+    /// the frame ahead (the live machine stops there next).
+    fn frame_ahead(&self, live: Frame) -> Frame {
+        if !self.config.run_ahead || self.link_partner.is_some() || self.netplay.is_some() {
+            return live;
+        }
+        rustyboi_core_lib::profile_span!("run_ahead");
+        match self.gb.clone().run_until_frame(false) {
+            (ahead, false) => ahead,
+            (_, true) => live,
+        }
+    }
+
+    /// Emulate exactly one frame: pick the input (the input editor's timeline
+    /// or movie playback overrides live), pump the webcam/RTC/cheats, step the
+    /// GB, service rumble, record, and snapshot for rewind.
     fn step_one(&mut self, live_state: ButtonState) -> Frame {
+        let live_state = self.add_held_buttons(live_state);
+        // A second Game Boy on the link cable may take the live input.
+        let (live_state, partner_input) = self.route_link_input(live_state);
+        self.relink_partner();
+        self.receive_netplay();
         // Movie playback overrides live input; when it runs out, live resumes.
-        let input = match self.playback.as_mut().and_then(|p| p.next_input()) {
-            Some(recorded) => recorded,
-            None => {
-                if self.playback.as_ref().is_some_and(|p| p.finished()) {
-                    self.playback = None;
+        let input = match self.input_editor.as_mut() {
+            Some(editor) => editor.advance(),
+            None => match self.playback.as_mut().and_then(|p| p.next_input()) {
+                Some(recorded) => recorded,
+                None => {
+                    if self.playback.as_ref().is_some_and(|p| p.finished()) {
+                        self.playback = None;
+                        self.apply_machine_config();
+                    }
+                    live_state
                 }
-                live_state
-            }
+            },
         };
 
         // Feed the Game Boy Camera sensor if the cart wants it and a frame is
@@ -421,8 +649,40 @@ impl Session {
             }
         }
 
+        // A no-op unless `apply_machine_config` put the RTC on the host clock.
+        if let Some(cart) = self.gb.cartridge_mut() {
+            cart.sync_rtc_host_clock();
+        }
+
+        // The stems and the scope read the channel tap, which a rebuilt
+        // machine starts without; re-engaging also clears last frame's
+        // (already drained) tap.
+        let tapping = self.wants_channel_tap();
+        if tapping {
+            self.gb.set_channel_tap(true);
+        }
+        let recorded_from = self.audio_buf.lock().unwrap_or_else(|e| e.into_inner()).len();
+        let cc_from = self.gb.master_cc();
+
+        // Re-armed every frame: a state load or restart swaps in a machine
+        // with capture off.
+        if self.serial_capture {
+            self.gb.set_serial_capture(true);
+        }
         self.gb.set_input_state(input);
         let (frame, _breakpoint) = self.gb.run_until_frame(true);
+        self.step_link_partner(partner_input);
+        self.send_netplay();
+        let tap = if tapping { self.gb.drain_channel_tap() } else { Vec::new() };
+        self.capture_audio_recording(recorded_from, &tap);
+        self.capture_gif_frame(&frame);
+        let dots = self.gb.master_cc().wrapping_sub(cc_from) >> u8::from(self.gb.is_double_speed());
+        self.capture_video_frame(dots, recorded_from, &frame);
+        self.broadcast_frame(recorded_from, &frame);
+        if let Some(scope) = self.apu_scope.as_mut() {
+            scope.clear();
+            scope.extend(tap.iter().map(|&(levels, ..)| levels));
+        }
 
         // Re-apply GameShark RAM pokes every frame (Game Genie ROM patches are
         // one-shot, applied on insert / cheat change).
@@ -441,9 +701,18 @@ impl Session {
         if let Some(rec) = self.recording.as_mut() {
             rec.push_input(input);
         }
+        self.capture_greenzone();
 
         self.frame_count += 1;
 
+        // Write back battery RAM the game touched. A failed flush keeps its
+        // pages dirty, so the next period retries it.
+        if self.frame_count.is_multiple_of(SAVE_FLUSH_FRAMES)
+            && let Some(cart) = self.gb.cartridge_mut()
+        {
+            let _ = cart.flush_save();
+        }
+
         // Rewind snapshot on the configured cadence. In offloaded mode we only
         // take the cheap `GB::clone` here and stash it for the platform worker
         // to serialize; otherwise serialize inline as before.
@@ -457,6 +726,7 @@ impl Session {
                 self.rewind.push(self.frame_count, crate::rewind::compress_snapshot(state));
             }
         }
+        self.capture_recovery();
 
         frame
     }
@@ -466,7 +736,8 @@ impl Session {
     /// the frontend loads a different cartridge (or a raw state whose ROM id it
     /// knows): the audio capture sink is re-installed, Game Genie ROM patches
     /// re-applied, the frame counter reset, rewind history cleared, and any TAS
-    /// recording/playback dropped (they were bound to the old ROM).
+    /// recording/playback and the input editor dropped (they were bound to the
+    /// old ROM).
     ///
     /// `rom_id` should be the SHA-256 of the new ROM (all-zero for none) so
     /// savestate slots re-key to the new game.
@@ -474,14 +745,37 @@ impl Session {
         let _ = gb.enable_audio(Box::new(CaptureSink::new(self.audio_buf.clone())));
         self.cheats.apply_rom_patches(&mut gb);
         *self.gb = gb;
+        let new_rom = self.rom_id != rom_id;
+        if new_rom {
+            // The partner runs the old cartridge (and saves under its id); a
+            // new game unplugs it.
+            self.stop_link_partner();
+        }
+        // A restart or state load keeps the same ROM, and so its digest; an
+        // unidentified one (all-zero id) could be any cartridge.
+        if new_rom || rom_id == [0; 32] {
+            self.refresh_rom_digest();
+        }
         self.rom_id = rom_id;
+        self.refresh_slot_times();
+        // Only a different game re-opens the offer; a restart or a hardware
+        // change rebuilds the same ROM and answers it with a fresh boot.
+        if new_rom {
+            self.offer_resume();
+            self.load_backup = None;
+        } else {
+            self.resume_offer = None;
+        }
         self.frame_count = 0;
         self.rewind.clear();
+        self.recovery.clear();
         self.recording = None;
         self.playback = None;
+        self.input_editor = None;
         self.mode = RunMode::Normal;
         self.printer_strips.clear();
         self.apply_presentation();
+        self.apply_machine_config();
     }
 
     // --- run mode -----------------------------------------------------------
@@ -517,6 +811,35 @@ impl Session {
         self.frame_count
     }
 
+    // --- scripted input -----------------------------------------------------
+
+    /// Hold `buttons` on top of live input for the next `frames` emulated
+    /// frames: a scripted press from an automation client. Replaces any hold
+    /// still running; `frames == 0` releases.
+    pub fn hold_buttons(&mut self, buttons: ButtonState, frames: u32) {
+        self.held_buttons = (frames > 0).then_some((buttons, frames));
+    }
+
+    /// Emulated frames left on the current [`hold_buttons`](Self::hold_buttons).
+    pub fn held_frames_left(&self) -> u32 {
+        self.held_buttons.map_or(0, |(_, frames)| frames)
+    }
+
+    /// This frame's live input with any held buttons pressed too.
+    fn add_held_buttons(&mut self, mut live: ButtonState) -> ButtonState {
+        let Some((held, frames)) = self.held_buttons.as_mut() else { return live };
+        for button in GbButton::ALL {
+            if button.get(held) {
+                button.set(&mut live, true);
+            }
+        }
+        *frames -= 1;
+        if *frames == 0 {
+            self.held_buttons = None;
+        }
+        live
+    }
+
     /// Power-cycle the current console: rebuild the machine from the session's
     /// hardware model + current cartridge (so every model-derived flag is
     /// re-applied — `GB::new`, not in-place reset), clear rewind, run normally.
@@ -583,12 +906,70 @@ impl Session {
         Ok(rom_id)
     }
 
+    /// [`finish_load_rom`](Self::finish_load_rom) for a ROM that arrives with a
+    /// soft-patch (the `.bps`/`.ups`/`.ips` a host found next to it): the
+    /// patched image boots, while the pristine ROM is retained so a later
+    /// [`apply_rom_patch`](Self::apply_rom_patch) still starts from the original.
+    pub fn finish_load_soft_patched_rom(&mut self, bytes: &[u8], patch: &[u8]) -> Result<[u8; 32], SessionError> {
+        let rom = crate::rom_zip::extract_rom(bytes);
+        let patched = crate::patch::apply_patch(&rom, patch).map_err(SessionError::State)?;
+        let rom_id = self.load_rom_bytes(&patched)?;
+        self.original_rom = Some(rom);
+        Ok(rom_id)
+    }
+
+    /// Hold `bytes` for the archive chooser instead of loading it when it is a
+    /// zip/7z holding more than one ROM, returning `true`; anything else
+    /// returns `false` for the caller to load as usual. `patch` is the
+    /// soft-patch found next to the archive, applied to whichever ROM is picked.
+    pub fn hold_archive_bundle(&mut self, bytes: &[u8], patch: Option<&[u8]>) -> bool {
+        let entries = Cartridge::archive_rom_entries(bytes).unwrap_or_default();
+        if entries.len() < 2 {
+            return false;
+        }
+        self.pending_archive = Some(PendingArchive {
+            bytes: bytes.to_vec(),
+            entries,
+            patch: patch.map(<[u8]>::to_vec),
+        });
+        true
+    }
+
+    /// The ROM members of the held archive bundle, in archive order (empty
+    /// when no choice is pending).
+    pub fn archive_choices(&self) -> &[String] {
+        self.pending_archive.as_ref().map_or(&[], |p| &p.entries)
+    }
+
+    /// Take the member `name` out of the held archive bundle, returning its
+    /// bytes and the bundle's soft-patch for the caller to load. The bundle is
+    /// released either way.
+    pub fn take_archive_entry(&mut self, name: &str) -> Result<(Vec<u8>, Option<Vec<u8>>), SessionError> {
+        let pending = self
+            .pending_archive
+            .take()
+            .ok_or_else(|| SessionError::State("no archive is waiting for a choice".into()))?;
+        let bytes = Cartridge::extract_archive_entry(&pending.bytes, name)
+            .map_err(|e| SessionError::State(e.to_string()))?;
+        Ok((bytes, pending.patch))
+    }
+
+    /// Drop the held archive bundle (the chooser was dismissed).
+    pub(crate) fn clear_archive_choice(&mut self) {
+        self.pending_archive = None;
+    }
+
     /// Shared cartridge (re)build used by both [`finish_load_rom`] and
     /// [`apply_rom_patch`]: insert `bytes`, re-bind the session, and hydrate the
     /// battery image. Does NOT touch `original_rom` (the caller decides whether
     /// these bytes are the pristine ROM or a patched derivative).
     fn load_rom_bytes(&mut self, bytes: &[u8]) -> Result<[u8; 32], SessionError> {
         let cart = Cartridge::from_bytes(bytes).map_err(|e| SessionError::State(e.to_string()))?;
+        if self.strict_header && !cart.header_checksum_valid() {
+            return Err(SessionError::State(
+                "header checksum mismatch (refusing to boot in strict header mode)".into(),
+            ));
+        }
         let mut gb = GB::new(self.config.hardware);
         gb.insert(cart);
         self.boot_or_skip(&mut gb);
@@ -601,6 +982,65 @@ impl Session {
         Ok(rom_id)
     }
 
+    /// Enable or disable strict header mode: when on, loading a ROM whose
+    /// header checksum fails is an error instead of a warning.
+    pub fn set_strict_header(&mut self, strict: bool) {
+        self.strict_header = strict;
+    }
+
+    /// Keep RTC clocks in `format` for this run whatever the saved setting
+    /// says, without changing it (`None` goes back to the saved setting).
+    pub fn set_rtc_format_override(&mut self, format: Option<RtcFormat>) {
+        self.rtc_format_override = format;
+    }
+
+    /// Start or stop collecting the bytes the game sends over the link port,
+    /// e.g. a blargg test ROM's pass/fail report.
+    pub fn set_serial_capture(&mut self, on: bool) {
+        self.serial_capture = on;
+        self.gb.set_serial_capture(on);
+    }
+
+    /// Take the bytes sent over the link port since the last call (empty
+    /// unless [`set_serial_capture`](Self::set_serial_capture) is on).
+    pub fn drain_serial_output(&mut self) -> Vec<u8> {
+        self.gb.drain_serial_output()
+    }
+
+    /// A user-facing warning when the loaded ROM's stored header or global
+    /// checksum does not match its contents — usually an overdump, a bad dump,
+    /// or a patch that did not fix up the header. `None` when both match or no
+    /// ROM is attached. Real hardware only checks the header checksum, so this
+    /// never stops the ROM from running.
+    pub fn header_warning(&self) -> Option<String> {
+        let cart = self.gb.cartridge()?;
+        let (stored_header, stored_global) = cart.stored_checksums()?;
+        let mut problems = Vec::new();
+        if !cart.header_checksum_valid() {
+            problems.push(format!("header checksum ${stored_header:02X} is wrong"));
+        }
+        let global = cart.global_checksum();
+        if global != stored_global {
+            problems.push(format!("global checksum ${stored_global:04X} should be ${global:04X}"));
+        }
+        (!problems.is_empty())
+            .then(|| format!("{} (overdump or bad patch?)", problems.join(", ")))
+    }
+
+    /// The status line for a finished ROM load or patch: `done`, with the
+    /// [`header_warning`](Self::header_warning) appended when there is one.
+    pub fn load_status(&self, done: &str) -> String {
+        let done = match self.dump_status() {
+            Some(DumpStatus::Verified(_)) => format!("{done} (verified good dump)"),
+            Some(DumpStatus::Unknown) => format!("{done} (unknown dump)"),
+            None => done.to_string(),
+        };
+        match self.header_warning() {
+            Some(w) => format!("{done} with a warning: {w}"),
+            None => done,
+        }
+    }
+
     /// Apply an IPS/UPS/BPS `patch` to the pristine ROM and re-load the patched
     /// cartridge (a romhack / translation applied in-app). The original ROM must
     /// have been loaded through [`finish_load_rom`] first. Returns the patched
@@ -616,18 +1056,66 @@ impl Session {
         self.load_rom_bytes(&patched)
     }
 
-    /// Load a savestate from raw bytes, re-binding to `rom_id` (derived by the
-    /// caller from the reload ROM, or the existing id when `None`). The current
-    /// cartridge is re-attached by [`Session::restore_state`] as needed; a
-    /// caller-supplied `reload_rom` is inserted first when the state carried no
-    /// cartridge.
+    /// The current machine as a `.rustyboisave` file: the versioned
+    /// [`savestate`] container, stamped with the loaded ROM's id.
+    pub fn state_file_bytes(&mut self) -> Result<Vec<u8>, SessionError> {
+        savestate::write_state(&mut self.gb, self.rom_id).map_err(|e| SessionError::State(e.to_string()))
+    }
+
+    /// The current machine in the versioned JSON schema of
+    /// [`state_json`](crate::state_json), for reading or diffing.
+    pub fn state_json_bytes(&mut self) -> Result<Vec<u8>, SessionError> {
+        let file = self.state_file_bytes()?;
+        crate::state_json::state_file_to_json(&file).map(String::into_bytes).map_err(SessionError::State)
+    }
+
+    /// Load a `.rustyboisave` file, re-binding to `rom_id` (derived by the
+    /// caller from the reload ROM, or the existing id when `None`). Older
+    /// formats are upgraded and unknown ones refused by the [`savestate`]
+    /// container; without a `reload_rom`, a state stamped with a different ROM
+    /// than the live one is refused too. The current cartridge is re-attached
+    /// as needed; a caller-supplied `reload_rom` is inserted first when the
+    /// state carried no cartridge. The replaced machine is kept for
+    /// [`undo_load_state`](Self::undo_load_state): this is the user's own
+    /// file load.
     pub fn finish_load_state(
         &mut self,
         state: &[u8],
         reload_rom: Option<&[u8]>,
         rom_id: [u8; 32],
     ) -> Result<(), SessionError> {
-        let mut gb = GB::from_state_bytes(state).map_err(|e| SessionError::State(e.to_string()))?;
+        let mut gb = self.decode_state_file(state, reload_rom, rom_id)?;
+        self.back_up_before_load();
+        gb.carry_output_from(&self.gb);
+        self.replace_machine(gb, rom_id);
+        // `replace_machine` already re-applies presentation settings.
+        Ok(())
+    }
+
+    /// [`finish_load_state`](Self::finish_load_state) onto the live ROM for a
+    /// restore the host drives on its own (libretro's `retro_unserialize`,
+    /// behind the frontend's rewind, run-ahead and its own slots). Takes no
+    /// undo backup: the host owns undo there, and a rewind restoring every
+    /// frame would otherwise serialize the machine each time for nothing.
+    pub fn restore_host_state(&mut self, state: &[u8]) -> Result<(), SessionError> {
+        let rom_id = self.rom_id;
+        let mut gb = self.decode_state_file(state, None, rom_id)?;
+        gb.carry_output_from(&self.gb);
+        self.replace_machine(gb, rom_id);
+        Ok(())
+    }
+
+    /// Decode a `.rustyboisave` container and give it its cartridge (see
+    /// [`finish_load_state`](Self::finish_load_state)), leaving the live
+    /// machine untouched.
+    fn decode_state_file(
+        &mut self,
+        state: &[u8],
+        reload_rom: Option<&[u8]>,
+        rom_id: [u8; 32],
+    ) -> Result<GB, SessionError> {
+        let live_rom = (reload_rom.is_none() && rom_id != [0; 32]).then_some(rom_id);
+        let mut gb = savestate::read_state(state, live_rom).map_err(|e| SessionError::State(e.to_string()))?;
         if gb.cartridge_needs_rom() {
             if let Some(rom) = reload_rom {
                 gb.reattach_rom(rom);
@@ -642,19 +1130,20 @@ impl Session {
                 Err(e) => return Err(SessionError::State(format!("failed to reattach ROM: {e}"))),
             }
         }
-        self.replace_machine(gb, rom_id);
-        // `replace_machine` already re-applies presentation settings.
-        Ok(())
+        Ok(gb)
     }
 
     /// Finish loading a TAS movie: decode the `.rbmovie` bytes produced by
-    /// [`stop_recording`](Self::stop_recording) → [`Movie::to_bytes`] and begin
+    /// [`stop_recording`](Self::stop_recording) → [`Movie::to_bytes`] (or
+    /// import a `.vbm` / BGB input log, see [`movie_import`]) and begin
     /// deterministic playback (see [`play_movie`](Self::play_movie)). The parallel
     /// to the other `finish_*` finishers for the `LoadPurpose::Movie` file-resolve
     /// path. Fails if the bytes are not a movie or were recorded against a
     /// different ROM than the one loaded.
     pub fn finish_load_movie(&mut self, bytes: &[u8]) -> Result<(), SessionError> {
-        let movie = Movie::from_bytes(bytes).map_err(|e| SessionError::Movie(e.to_string()))?;
+        let movie =
+            movie_import::import_movie(bytes, self.original_rom.as_deref(), self.rom_id, self.config.hardware)
+                .map_err(SessionError::Movie)?;
         self.play_movie(&movie)
     }
 
@@ -690,6 +1179,14 @@ impl Session {
 /// Reserved slot number for quicksave/quickload.
 pub const QUICK_SLOT: u32 = u32::MAX;
 
+/// Reserved slot number for the snapshot taken on exit when
+/// [`Config::auto_save_state`] is on.
+pub const AUTO_SLOT: u32 = u32::MAX - 1;
+
+/// Emulated frames between write-backs of battery RAM to an attached `.sav`
+/// (about one second at the DMG's 59.7 Hz).
+const SAVE_FLUSH_FRAMES: u64 = 60;
+
 /// The No-Intro game-name data is not embedded in any rustyboi binary; each
 /// frontend downloads it at runtime from the CC-BY-SA-4.0 libretro-database. Log
 /// the attribution whenever a fetch is initiated. `eprintln` on native, dropped
@@ -738,7 +1235,7 @@ mod offload_tests {
     }
 
     /// End-to-end: a loaded cartridge surfaces its header facts through the
-    /// Cartridge Info debug section.
+    /// ROM Info debug section.
     #[test]
     fn cartridge_info_snapshot_decodes_header() {
         use crate::debug::DebugDetail;
@@ -771,7 +1268,151 @@ mod offload_tests {
         assert_eq!(c.destination.as_deref(), Some("Japanese"));
         assert_eq!(c.licensee.as_deref(), Some("Nintendo"));
         assert!(c.header_checksum_ok);
+        assert_eq!(c.header_checksum, sum);
+        assert_eq!(c.version, 0);
         assert!(c.crc32.is_some());
+        assert_eq!(c.sha1, Some(rustyboi_core_lib::checksum::sha1(&rom)));
+        // The stored global checksum is left zero, so it reads as a mismatch.
+        assert_eq!(c.stored_global_checksum, 0);
+        assert_ne!(c.global_checksum, c.stored_global_checksum);
+    }
+
+    /// With a verification DAT installed, a listed ROM reports as a verified
+    /// good dump (status line and ROM Info) and anything else as unknown.
+    #[test]
+    fn verification_dat_marks_listed_roms_good() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x014D] = rom[0x0134..0x014D].iter().fold(0u8, |a, &b| a.wrapping_sub(b).wrapping_sub(1));
+        let global = rom.iter().fold(0u16, |a, &b| a.wrapping_add(b as u16));
+        rom[0x014E..0x0150].copy_from_slice(&global.to_be_bytes());
+        let hex: String =
+            rustyboi_core_lib::checksum::sha1(&rom).iter().map(|b| format!("{b:02X}")).collect();
+        let dat = format!("game (\n\tname \"Blank (World)\"\n\trom ( name \"Blank.gb\" sha1 {hex} )\n)\n");
+
+        let mut s = Session::new(cfg(), test_ports(), [0u8; 32]);
+        s.finish_load_rom(&rom).expect("load rom");
+        assert_eq!(s.dump_status(), None, "no DAT configured: nothing to report");
+        assert_eq!(s.load_status("ROM loaded"), "ROM loaded");
+
+        assert_eq!(s.load_verification_dat(&dat), 1);
+        assert_eq!(s.dump_status(), Some(DumpStatus::Verified("Blank (World)".into())));
+        assert_eq!(s.load_status("ROM loaded"), "ROM loaded (verified good dump)");
+        let snap = s.debug_snapshot(crate::debug::DebugDetail { cartridge: true, ..Default::default() });
+        assert_eq!(snap.cartridge.unwrap().dump, Some(DumpStatus::Verified("Blank (World)".into())));
+
+        rom[0x0200] = 1; // any changed byte is no longer the listed dump
+        s.finish_load_rom(&rom).expect("load rom");
+        assert_eq!(s.dump_status(), Some(DumpStatus::Unknown));
+        assert!(s.load_status("ROM loaded").starts_with("ROM loaded (unknown dump)"));
+    }
+
+    /// Battery RAM reaches an attached `.sav` on the periodic flush and on an
+    /// explicit Flush Saves Now, not on every write.
+    #[test]
+    fn battery_writes_flush_periodically_and_on_demand() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0147] = 0x03; // MBC1+RAM+BATTERY
+        rom[0x0149] = 0x02; // 8 KiB
+        let dir = std::env::temp_dir().join(format!("rustyboi-flush-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sav = dir.join("game.sav");
+
+        let mut s = Session::new(cfg(), test_ports(), [0u8; 32]);
+        s.finish_load_rom(&rom).expect("load rom");
+        s.gb_mut().cartridge_mut().unwrap().attach_save_file(&sav).unwrap();
+        s.gb_mut().write_memory(0x0000, 0x0A);
+        s.gb_mut().write_memory(0xA123, 0x42);
+        assert_ne!(std::fs::read(&sav).unwrap()[0x123], 0x42);
+        for _ in 0..SAVE_FLUSH_FRAMES {
+            s.run_frame(AbstractInput::none());
+        }
+        assert_eq!(std::fs::read(&sav).unwrap()[0x123], 0x42);
+
+        s.gb_mut().write_memory(0x0000, 0x0A);
+        s.gb_mut().write_memory(0xA124, 0x24);
+        assert!(s.flush_saves().is_ok());
+        assert_eq!(std::fs::read(&sav).unwrap()[0x124], 0x24);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    /// Held buttons reach the machine on top of live input for exactly the
+    /// requested emulated frames; paused frames don't count.
+    #[test]
+    fn held_buttons_last_their_frames() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x101] = 0xC3; // JP 0x0100
+        rom[0x103] = 0x01;
+        let mut s = Session::new(cfg(), test_ports(), [0u8; 32]);
+        s.finish_load_rom(&rom).unwrap();
+        let a_pressed = |s: &mut Session| {
+            s.gb_mut().write_memory(0xFF00, 0x10); // select the action buttons
+            s.gb().read_memory(0xFF00) & 0x01 == 0
+        };
+
+        s.hold_buttons(ButtonState { a: true, ..Default::default() }, 2);
+        s.set_mode(RunMode::Paused);
+        s.run_frame(AbstractInput::none());
+        assert_eq!(s.held_frames_left(), 2);
+        s.set_mode(RunMode::Normal);
+        s.run_frame(AbstractInput::none());
+        assert!(a_pressed(&mut s));
+        assert_eq!(s.held_frames_left(), 1);
+        s.run_frame(AbstractInput::none());
+        assert_eq!(s.held_frames_left(), 0);
+        s.run_frame(AbstractInput::none());
+        assert!(!a_pressed(&mut s));
+    }
+
+    /// Checksum mismatches warn on load; strict header mode refuses a bad
+    /// header checksum outright but still only warns on the global one.
+    #[test]
+    fn header_checksums_warn_and_strict_mode_refuses() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0134..0x0138].copy_from_slice(b"TEST");
+        let sum = rom[0x0134..0x014D].iter().fold(0u8, |a, &b| a.wrapping_sub(b).wrapping_sub(1));
+        rom[0x014D] = sum;
+        let global = rom.iter().fold(0u16, |a, &b| a.wrapping_add(b as u16));
+        rom[0x014E..0x0150].copy_from_slice(&global.to_be_bytes());
+
+        let mut s = Session::new(cfg(), test_ports(), [0u8; 32]);
+        s.set_strict_header(true);
+        s.finish_load_rom(&rom).expect("clean ROM loads");
+        assert_eq!(s.header_warning(), None);
+        assert_eq!(s.load_status("ROM loaded"), "ROM loaded");
+
+        // A stale global checksum (e.g. a patch that did not fix it up).
+        let mut patched = rom.clone();
+        patched[0x0200] = 0x42;
+        s.finish_load_rom(&patched).expect("global mismatch is only a warning");
+        let warning = s.header_warning().expect("global checksum warning");
+        assert!(warning.contains("global checksum"), "{warning}");
+
+        // A bad header checksum: a warning normally, a refusal when strict.
+        let mut bad = rom.clone();
+        bad[0x014D] ^= 0xFF;
+        assert!(s.finish_load_rom(&bad).is_err());
+        s.set_strict_header(false);
+        s.finish_load_rom(&bad).expect("lenient mode boots it");
+        assert!(s.header_warning().unwrap().contains("header checksum"));
+    }
+
+    /// A soft-patched load boots the patched image but keeps the pristine ROM,
+    /// so a later in-app patch applies to the original.
+    #[test]
+    fn soft_patched_load_retains_the_original() {
+        use rustyboi_core_lib::memory::Addressable;
+        let mut rom = vec![0u8; 0x8000];
+        let sum = rom[0x0134..0x014D].iter().fold(0u8, |a, &b| a.wrapping_sub(b).wrapping_sub(1));
+        rom[0x014D] = sum;
+        let mut ips = b"PATCH".to_vec();
+        ips.extend_from_slice(&[0x00, 0x02, 0x00, 0x00, 0x01, 0x42]);
+        ips.extend_from_slice(b"EOF");
+
+        let mut s = Session::new(cfg(), test_ports(), [0u8; 32]);
+        let id = s.finish_load_soft_patched_rom(&rom, &ips).expect("patched ROM loads");
+        assert_eq!(s.gb().cartridge().unwrap().read(0x0200), 0x42);
+        assert_ne!(id, rustyboi_core_lib::movie::sha256(&rom), "keyed to the patched image");
+        assert_eq!(s.original_rom.as_deref(), Some(&rom[..]));
     }
 
     // The offloaded capture path must produce byte-identical rewind blobs to the
@@ -921,6 +1562,59 @@ mod volume_tests {
         assert!(matches!(s.mode(), RunMode::FastForward(_)), "still fast-forwarding while uncapped");
     }
 
+    // Vsync is only dropped while fast-forward is actually engaged.
+    #[test]
+    fn fast_forward_vsync_off_applies_only_while_fast_forwarding() {
+        let mut s = Session::new(Config::default(), test_ports(), [0u8; 32]);
+        s.fast_forward();
+        assert!(!s.fast_forward_vsync_off(), "off by default");
+
+        s.set_fast_forward_vsync_off(true);
+        assert!(s.fast_forward_vsync_off());
+        assert!(s.ui_state().fast_forward_vsync_off);
+        s.toggle_fast_forward();
+        assert!(!s.fast_forward_vsync_off(), "normal speed keeps vsync");
+        assert!(s.ui_state().fast_forward_vsync_off, "the setting itself stays on");
+    }
+
+    // The emulation speed clamps to the offered range, steps through it, and
+    // is announced on the OSD.
+    #[test]
+    fn emulation_speed_clamps_and_steps() {
+        use crate::action::step_emulation_speed;
+        let mut s = Session::new(Config::default(), test_ports(), [0u8; 32]);
+        assert_eq!(s.emulation_speed(), 100);
+        s.set_emulation_speed(5);
+        assert_eq!(s.emulation_speed(), 25);
+        s.set_emulation_speed(10_000);
+        assert_eq!(s.emulation_speed(), 800);
+        assert_eq!(s.osd().map(|m| m.text.as_str()), Some("Speed 800%"));
+
+        assert_eq!(step_emulation_speed(100, true), 125);
+        assert_eq!(step_emulation_speed(100, false), 75);
+        assert_eq!(step_emulation_speed(110, false), 100, "an off-list speed steps to its neighbor");
+        assert_eq!(step_emulation_speed(800, true), 800);
+        assert_eq!(step_emulation_speed(25, false), 25);
+    }
+
+    // The hold key fast-forwards only while down, and its release leaves a
+    // fast-forward it didn't start alone.
+    #[test]
+    fn hold_fast_forward_engages_only_while_held() {
+        let mut s = Session::new(Config::default(), test_ports(), [0u8; 32]);
+        s.hold_fast_forward(true);
+        assert!(s.is_fast_forward(), "press engages");
+        s.hold_fast_forward(true);
+        assert!(s.is_fast_forward(), "held ticks keep it engaged");
+        s.hold_fast_forward(false);
+        assert_eq!(s.mode(), RunMode::Normal, "release drops back to normal speed");
+
+        s.toggle_fast_forward();
+        s.hold_fast_forward(true);
+        s.hold_fast_forward(false);
+        assert!(s.is_fast_forward(), "a toggled fast-forward survives a tap of the hold key");
+    }
+
     // The gain `run_frame` uses tracks the config volume, so setting volume 0/50/
     // 100 drives the scaler to 0.0/0.5/1.0 respectively.
     #[test]
@@ -966,6 +1660,52 @@ mod volume_tests {
         assert_eq!(s.config().volume_gain(), 0.0);
     }
 
+    // `--rtc-format` wins for the run but never reaches the saved config, even
+    // when another setting change persists it.
+    #[test]
+    fn rtc_format_override_is_not_persisted() {
+        let cfg = Config { rtc_format: RtcFormat::Sidecar, ..Default::default() };
+        let mut s = Session::new(cfg, test_ports(), [0u8; 32]);
+        s.set_rtc_format_override(Some(RtcFormat::Footer));
+        assert_eq!(s.rtc_format(), RtcFormat::Footer);
+        s.set_volume(50);
+        assert_eq!(Config::load(s.ports.storage.as_ref()).rtc_format, RtcFormat::Sidecar);
+        s.set_rtc_format_override(None);
+        assert_eq!(s.rtc_format(), RtcFormat::Sidecar);
+    }
+
+    // Mute silences the gain without touching the volume, so unmuting comes
+    // back at the same level.
+    #[test]
+    fn toggle_mute_keeps_the_volume() {
+        let mut s = Session::new(Config::default(), test_ports(), [0u8; 32]);
+        s.set_volume(70);
+        s.toggle_mute();
+        assert!(s.is_muted());
+        assert_eq!(s.config().volume_gain(), 0.0);
+        assert_eq!(s.volume(), 70, "mute leaves the level alone");
+        s.toggle_mute();
+        assert_eq!(s.config().volume_gain(), 0.7);
+    }
+
+    // Volume and speed changes flash a notice over the game; each one gets a
+    // fresh id so the UI restarts its fade even for repeated text.
+    #[test]
+    fn volume_and_speed_changes_flash_the_osd() {
+        let mut s = Session::new(Config::default(), test_ports(), [0u8; 32]);
+        assert!(s.osd().is_none());
+        s.set_volume(70);
+        assert_eq!(s.osd().map(|m| m.text.as_str()), Some("Volume 70%"));
+        s.toggle_mute();
+        assert_eq!(s.osd().map(|m| m.text.as_str()), Some("Muted"));
+        s.toggle_fast_forward();
+        assert_eq!(s.osd().map(|m| m.text.as_str()), Some("Fast forward ×4"));
+        let id = s.osd().unwrap().id;
+        s.toggle_fast_forward();
+        s.toggle_fast_forward();
+        assert_eq!(s.ui_state().osd.map(|m| (m.id, m.text)), Some((id + 2, "Fast forward ×4".into())));
+    }
+
     // The scaling-mode setter round-trips through the persisted config.
     #[test]
     fn set_scaling_mode_persists() {
@@ -975,6 +1715,29 @@ mod volume_tests {
         assert_eq!(s.scaling_mode(), ScalingMode::IntegerAspect);
         assert_eq!(s.config().scaling, ScalingMode::IntegerAspect);
     }
+
+    // The aspect setter persists, and the window-driving display size takes
+    // the chosen shape (square pixels leave the content size alone).
+    #[test]
+    fn set_aspect_ratio_persists_and_reshapes_the_display_size() {
+        use crate::action::AspectRatio;
+        let mut s = Session::new(Config::default(), test_ports(), [0u8; 32]);
+        assert_eq!(s.aspect_ratio(), AspectRatio::Square);
+        assert_eq!(s.display_size(), s.content_size());
+        s.set_aspect_ratio(AspectRatio::FourByThree);
+        assert_eq!(s.config().aspect_ratio, AspectRatio::FourByThree);
+        assert_eq!(s.display_size(), (192, 144));
+    }
+
+    // The window scale is remembered in the config and shown in the UI state.
+    #[test]
+    fn set_window_scale_persists() {
+        let mut s = Session::new(Config::default(), test_ports(), [0u8; 32]);
+        assert_eq!(s.window_scale(), 5);
+        s.apply(crate::action::UiAction::SetWindowScale(2), 0);
+        assert_eq!(s.config().window_scale, 2);
+        assert_eq!(s.ui_state().window_scale, 2);
+    }
 }
 
 #[cfg(test)]
@@ -1092,6 +1855,50 @@ mod tas_tests {
         Session::new(Config::default(), test_ports(), [0u8; 32])
     }
 
+    // The host-clock RTC is set up when the cartridge loads, and handed back
+    // to the cycle clock for exactly as long as a movie records.
+    #[test]
+    fn rtc_source_follows_the_setting_and_recording() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x147] = 0x0F; // MBC3+TIMER+BATTERY
+        let host = |s: &Session| s.gb().cartridge().unwrap().rtc_host_clock();
+
+        let mut s = session();
+        s.finish_load_rom(&rom).unwrap();
+        assert!(!host(&s), "the cycle clock is the default");
+
+        let mut s = Session::new(Config { rtc_host_clock: true, ..Config::default() }, test_ports(), [0; 32]);
+        s.finish_load_rom(&rom).unwrap();
+        assert!(host(&s));
+        s.start_recording();
+        assert!(!host(&s));
+        s.stop_recording();
+        assert!(host(&s));
+        s.set_rtc_host_clock(false);
+        assert!(!host(&s));
+    }
+
+    // The OAM bug is recorded with the movie and replayed as recorded, whatever
+    // the accuracy preset is by then; live play goes back to the preset.
+    #[test]
+    fn movies_replay_with_the_oam_bug_they_were_recorded_with() {
+        let mut s = session();
+        s.finish_load_rom(&vec![0u8; 0x8000]).unwrap();
+        s.set_accuracy_preset(crate::action::AccuracyPreset::Speed);
+        assert!(!s.gb().oam_bug());
+        s.start_recording();
+        s.run_frame(AbstractInput::none());
+        let movie = s.stop_recording().unwrap();
+        assert!(!movie.oam_bug);
+
+        s.set_accuracy_preset(crate::action::AccuracyPreset::Accuracy);
+        assert!(s.gb().oam_bug());
+        s.play_movie(&Movie::from_bytes(&movie.to_bytes()).unwrap()).unwrap();
+        assert!(!s.gb().oam_bug(), "replayed as recorded");
+        s.stop_playback();
+        assert!(s.gb().oam_bug(), "back to the preset");
+    }
+
     // One ToggleRecording arms recording; a second stops it and hands back a
     // decodable `.rbmovie` whose frame count matches the frames stepped while
     // armed. Loading those bytes begins playback; StopReplay ends it.
@@ -1149,6 +1956,88 @@ mod tas_tests {
         ));
     }
 
+    // A movie that names its start state by hash plays where that state is
+    // stored (kept at record time, or found in a slot) and is refused where
+    // it is not.
+    #[test]
+    fn hash_anchored_movie_needs_its_start_state() {
+        let mut s = session();
+        s.apply(UiAction::SetMovieAnchorByHash(true), 0);
+        s.apply(UiAction::ToggleRecording, 0);
+        s.run_frame(AbstractInput::none());
+        let out = s.apply(UiAction::ToggleRecording, 0);
+        let bytes = out
+            .requests
+            .iter()
+            .find_map(|r| match r {
+                PlatformRequest::SaveBytes { bytes, .. } => Some(bytes.clone()),
+                _ => None,
+            })
+            .expect("the recording is handed back");
+        let movie = Movie::from_bytes(&bytes).unwrap();
+        assert!(matches!(movie.start, rustyboi_core_lib::movie::MovieStart::SaveStateRef(_)));
+        s.finish_load_movie(&bytes).expect("the start state was kept in storage");
+        assert!(s.is_playing());
+
+        let mut other = session();
+        assert!(matches!(other.finish_load_movie(&bytes), Err(SessionError::AnchorMissing)));
+        other.save_slot(1, 0).unwrap();
+        other.finish_load_movie(&bytes).expect("the same state saved in a slot matches");
+        assert!(other.is_playing());
+    }
+
+    // Changing an input the editor already ran re-simulates from the
+    // greenzone and lands on the same machine as running the edited timeline
+    // straight through; the timeline then exports as a movie.
+    #[test]
+    fn editing_a_past_frame_resimulates_to_the_same_machine() {
+        use crate::input::GbButton;
+        let commit = |s: &mut Session| {
+            s.apply(UiAction::FrameAdvance, 0);
+            s.run_frame(AbstractInput::none());
+        };
+
+        let mut edited = session();
+        edited.apply(UiAction::ToggleInputEditor, 0);
+        for _ in 0..4 {
+            commit(&mut edited);
+        }
+        edited.apply(UiAction::SetTasInput(1, GbButton::A, true), 0);
+        // The re-simulation leaves the last frame queued for the host to show.
+        edited.run_frame(AbstractInput::none());
+
+        let mut direct = session();
+        direct.apply(UiAction::ToggleInputEditor, 0);
+        direct.apply(UiAction::SetTasInput(1, GbButton::A, true), 0);
+        for _ in 0..4 {
+            commit(&mut direct);
+        }
+
+        let editor = edited.input_editor().unwrap();
+        assert_eq!((editor.cursor(), editor.len()), (4, 4));
+        assert!(editor.input(1).a);
+        assert_eq!(edited.gb_mut().to_state_bytes().unwrap(), direct.gb_mut().to_state_bytes().unwrap());
+        let view = edited.ui_state().input_editor.unwrap();
+        assert_eq!((view.frame, view.first, view.rows.len()), (4, 0, 5));
+        assert!(view.rows[1][0], "row 1 holds A");
+
+        let out = edited.apply(UiAction::ExportTasMovie, 0);
+        let bytes = out
+            .requests
+            .iter()
+            .find_map(|r| match r {
+                PlatformRequest::SaveBytes { bytes, .. } => Some(bytes.clone()),
+                _ => None,
+            })
+            .expect("the timeline exports as a movie");
+        let movie = Movie::from_bytes(&bytes).unwrap();
+        assert_eq!(movie.inputs.len(), 4);
+        assert!(matches!(movie.start, rustyboi_core_lib::movie::MovieStart::SaveState(_)));
+
+        edited.apply(UiAction::ToggleInputEditor, 0);
+        assert!(edited.input_editor().is_none());
+    }
+
     // Garbage bytes surface as a decode error, never a panic.
     #[test]
     fn load_movie_rejects_garbage() {
@@ -1157,6 +2046,174 @@ mod tas_tests {
     }
 }
 
+#[cfg(test)]
+mod audio_recording_tests {
+    //! `apply(ToggleAudioRecording)` end to end: the export is one WAV, or
+    //! with stems on a zip of the mix and four stems covering the same frames.
+    use super::*;
+    use crate::action::UiAction;
+    use crate::apply::PlatformRequest;
+    use crate::ports::{MemRumble, MemStorage, MemWebcam};
+
+    fn session() -> Session {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        Session::new(Config::default(), ports, [0u8; 32])
+    }
+
+    /// Record `frames` frames and return the exported file.
+    fn record(s: &mut Session, frames: usize) -> (String, Vec<u8>) {
+        s.apply(UiAction::ToggleAudioRecording, 0);
+        assert!(s.is_recording_audio());
+        for _ in 0..frames {
+            s.run_frame(AbstractInput::none());
+        }
+        let out = s.apply(UiAction::ToggleAudioRecording, 0);
+        assert!(!s.is_recording_audio());
+        out.requests
+            .into_iter()
+            .find_map(|r| match r {
+                PlatformRequest::SaveBytes { suggested_name, bytes } => Some((suggested_name, bytes)),
+                _ => None,
+            })
+            .expect("stopping emits a SaveBytes export")
+    }
+
+    /// The `data` chunk length of a canonical WAV.
+    fn data_len(wav: &[u8]) -> u32 {
+        assert_eq!(&wav[36..40], b"data");
+        u32::from_le_bytes(wav[40..44].try_into().unwrap())
+    }
+
+    #[test]
+    fn mix_only_exports_a_wav_of_every_frame() {
+        let mut s = session();
+        let (name, wav) = record(&mut s, 3);
+        assert_eq!(name, "recording.wav");
+        let pairs = data_len(&wav) / 4;
+        let per_frame = f64::from(crate::pacing::HOST_SAMPLE_RATE) / crate::pacing::NOMINAL_FPS;
+        assert!((f64::from(pairs) - 3.0 * per_frame).abs() < 3.0, "{pairs} pairs for 3 frames");
+    }
+
+    #[test]
+    fn stems_export_a_zip_of_five_equal_length_tracks() {
+        let mut s = session();
+        s.apply(UiAction::SetRecordStems(true), 0);
+        let (name, zip) = record(&mut s, 3);
+        assert_eq!(name, "recording.zip");
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(zip)).expect("a readable zip");
+        let mut lens = Vec::new();
+        for name in ["mix.wav", "pulse1.wav", "pulse2.wav", "wave.wav", "noise.wav"] {
+            let mut wav = Vec::new();
+            std::io::Read::read_to_end(&mut archive.by_name(name).expect(name), &mut wav).unwrap();
+            lens.push(data_len(&wav));
+        }
+        assert!(lens[0] > 0);
+        assert!(lens.iter().all(|&l| l == lens[0]), "tracks differ in length: {lens:?}");
+    }
+}
+
+#[cfg(test)]
+mod gif_recording_tests {
+    //! `apply(ToggleGifRecording)` end to end: the export is a GIF at the
+    //! configured scale.
+    use super::*;
+    use crate::action::UiAction;
+    use crate::apply::PlatformRequest;
+    use crate::ports::{MemRumble, MemStorage, MemWebcam};
+
+    fn session() -> Session {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        Session::new(Config::default(), ports, [0u8; 32])
+    }
+
+    #[test]
+    fn a_gif_recording_exports_the_frames_at_the_chosen_scale() {
+        let mut s = session();
+        s.apply(UiAction::SetGifScale(3), 0);
+        s.apply(UiAction::ToggleGifRecording, 0);
+        assert!(s.is_recording_gif());
+        for _ in 0..3 {
+            s.run_frame(AbstractInput::none());
+        }
+        let out = s.apply(UiAction::ToggleGifRecording, 0);
+        assert!(!s.is_recording_gif());
+        let (name, gif) = out
+            .requests
+            .into_iter()
+            .find_map(|r| match r {
+                PlatformRequest::SaveBytes { suggested_name, bytes } => Some((suggested_name, bytes)),
+                _ => None,
+            })
+            .expect("stopping emits a SaveBytes export");
+        assert_eq!(name, "recording.gif");
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(u16::from_le_bytes([gif[6], gif[7]]), 160 * 3);
+        assert_eq!(u16::from_le_bytes([gif[8], gif[9]]), 144 * 3);
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+}
+
+#[cfg(test)]
+mod video_recording_tests {
+    //! `apply(ToggleVideoRecording)` end to end: the export is a zip of one
+    //! PNG per emulated frame and a WAV of matching length.
+    use super::*;
+    use crate::action::UiAction;
+    use crate::apply::PlatformRequest;
+    use crate::ports::{MemRumble, MemStorage, MemWebcam};
+
+    fn session() -> Session {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        Session::new(Config::default(), ports, [0u8; 32])
+    }
+
+    #[test]
+    fn a_video_recording_exports_frames_and_audio_in_step() {
+        let mut s = session();
+        s.apply(UiAction::ToggleVideoRecording, 0);
+        assert!(s.is_recording_video());
+        for _ in 0..30 {
+            s.run_frame(AbstractInput::none());
+        }
+        let out = s.apply(UiAction::ToggleVideoRecording, 0);
+        assert!(!s.is_recording_video());
+        let (name, bytes) = out
+            .requests
+            .into_iter()
+            .find_map(|r| match r {
+                PlatformRequest::SaveBytes { suggested_name, bytes } => Some((suggested_name, bytes)),
+                _ => None,
+            })
+            .expect("stopping emits a SaveBytes export");
+        assert_eq!(name, "video.zip");
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let frames = archive.file_names().filter(|n| n.starts_with("frames/")).count();
+        assert!((29..=31).contains(&frames), "one slot per emulated frame, got {frames}");
+        let mut png = Vec::new();
+        std::io::Read::read_to_end(&mut archive.by_name("frames/000000.png").unwrap(), &mut png).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        let mut wav = Vec::new();
+        std::io::Read::read_to_end(&mut archive.by_name("audio.wav").unwrap(), &mut wav).unwrap();
+        let samples = u32::from_le_bytes(wav[40..44].try_into().unwrap()) as f64 / 4.0;
+        let video_seconds = frames as f64 * 70_224.0 / 4_194_304.0;
+        let audio_seconds = samples / f64::from(s.audio_sample_rate());
+        assert!((video_seconds - audio_seconds).abs() < 0.02, "{video_seconds} s video vs {audio_seconds} s audio");
+        assert!(archive.by_name("ffmpeg.txt").is_ok());
+    }
+}
+
 #[cfg(test)]
 mod slot_and_import_tests {
     //! Slot storage round-trips against in-memory storage, blob-header
@@ -1184,6 +2241,7 @@ mod slot_and_import_tests {
             s.run_frame(AbstractInput::none());
         }
         s.save_slot(3, 777).unwrap();
+        assert_eq!(s.osd().map(|m| m.text.as_str()), Some("State 3 saved"));
 
         // Metadata is readable without a full load.
         let meta = s.slot_meta(3).expect("slot 3 exists");
@@ -1199,6 +2257,7 @@ mod slot_and_import_tests {
         let loaded = s.load_slot(3).unwrap();
         assert_eq!(loaded, SlotMeta { frame_count: 4, timestamp: 777 });
         assert_eq!(s.frame_count(), 4);
+        assert_eq!(s.osd().map(|m| m.text.as_str()), Some("State 3 loaded"));
     }
 
     #[test]
@@ -1220,6 +2279,57 @@ mod slot_and_import_tests {
         assert_eq!(s.list_slots(), vec![2]);
     }
 
+    #[test]
+    fn slot_times_track_saves_and_format_as_utc() {
+        let mut s = session();
+        s.run_frame(AbstractInput::none());
+        assert!(s.slot_times().is_empty());
+        s.save_slot(4, 1_700_000_000).unwrap();
+        s.quicksave(5).unwrap();
+        assert_eq!(s.slot_times(), &[(4, 1_700_000_000)]);
+        assert_eq!(s.ui_state().slot_times, vec![(4, 1_700_000_000)]);
+        let thumbs = s.slot_thumbnails();
+        assert_eq!(thumbs.len(), 1);
+        assert_eq!(thumbs[0].0, 4);
+        assert_eq!(thumbs[0].1.len(), savestate::THUMBNAIL_WIDTH * savestate::THUMBNAIL_HEIGHT * 3);
+
+        assert_eq!(Session::slot_time_label(1_700_000_000).as_deref(), Some("2023-11-14 22:13"));
+        assert_eq!(Session::slot_time_label(951_782_400).as_deref(), Some("2000-02-29 00:00"));
+        assert_eq!(Session::slot_time_label(0), None);
+    }
+
+    #[test]
+    fn undo_load_state_puts_back_the_replaced_machine() {
+        let mut s = session();
+        assert!(!s.can_undo_load_state());
+        s.run_frame(AbstractInput::none());
+        s.quicksave(0).unwrap();
+        for _ in 0..3 {
+            s.run_frame(AbstractInput::none());
+        }
+        let before = s.gb_mut().to_state_bytes().unwrap();
+        s.quickload().unwrap();
+        assert_eq!(s.frame_count(), 1);
+        assert!(s.ui_state().can_undo_load);
+
+        s.undo_load_state().unwrap();
+        assert_eq!(s.frame_count(), 4);
+        assert_eq!(s.gb_mut().to_state_bytes().unwrap(), before);
+        assert!(matches!(s.undo_load_state(), Err(SessionError::NoState)));
+    }
+
+    // Host-driven restores (libretro rewind) leave the user's undo alone.
+    #[test]
+    fn host_restores_take_no_undo_backup() {
+        let mut s = session();
+        s.run_frame(AbstractInput::none());
+        let state = s.state_file_bytes().unwrap();
+        s.restore_host_state(&state).unwrap();
+        assert!(!s.can_undo_load_state());
+        s.finish_load_state(&state, None, s.rom_id()).unwrap();
+        assert!(s.can_undo_load_state());
+    }
+
     #[test]
     fn load_missing_slot_is_no_state() {
         let mut s = session();
@@ -1263,6 +2373,57 @@ mod slot_and_import_tests {
         ));
     }
 
+    #[test]
+    fn state_files_round_trip_and_refuse_another_rom() {
+        let rom = |title: u8| {
+            let mut r = vec![0u8; 0x8000];
+            r[0x0134] = title;
+            r
+        };
+        let mut s = session();
+        s.finish_load_rom(&rom(b'A')).unwrap();
+        s.run_frame(AbstractInput::none());
+        let state = s.state_file_bytes().unwrap();
+        s.finish_load_state(&state, None, s.rom_id()).unwrap();
+
+        s.finish_load_rom(&rom(b'B')).unwrap();
+        let err = s.finish_load_state(&state, None, s.rom_id()).err().unwrap();
+        assert!(err.to_string().contains("different ROM"), "{err}");
+        // A headerless (pre-versioning) blob gets a clear refusal.
+        let raw = s.gb_mut().to_state_bytes().unwrap();
+        let err = s.finish_load_state(&raw, None, s.rom_id()).err().unwrap();
+        assert!(err.to_string().contains("versioned"), "{err}");
+    }
+
+    // The cartridge's runtime state rides in the state: SRAM contents and the
+    // mapper's bank registers come back, only the ROM image is re-attached.
+    #[test]
+    fn state_files_carry_cart_ram_and_bank_registers() {
+        let mut rom = vec![0u8; 0x10000];
+        rom[0x0147] = 0x03; // MBC1+RAM+BATTERY
+        rom[0x0148] = 0x01; // 64 KiB, four banks
+        rom[0x0149] = 0x03; // 32 KiB RAM, four banks
+        for bank in 1..4 {
+            rom[bank * 0x4000] = bank as u8;
+        }
+        let mut s = session();
+        s.finish_load_rom(&rom).unwrap();
+        let gb = s.gb_mut();
+        gb.write_memory(0x0000, 0x0A);
+        gb.write_memory(0x2000, 2);
+        gb.write_memory(0x6000, 1);
+        gb.write_memory(0x4000, 1);
+        gb.write_memory(0xA000, 0x5A);
+        let state = s.state_file_bytes().unwrap();
+
+        let gb = s.gb_mut();
+        gb.write_memory(0xA000, 0);
+        gb.write_memory(0x2000, 3);
+        s.finish_load_state(&state, None, s.rom_id()).unwrap();
+        assert_eq!(s.gb().read_memory(0x4000), 2, "ROM bank register restored");
+        assert_eq!(s.gb().read_memory(0xA000), 0x5A, "banked SRAM restored");
+    }
+
     #[test]
     fn apply_rom_patch_without_a_loaded_rom_errors() {
         let mut s = session(); // original_rom is None ROM-less
@@ -1537,3 +2698,320 @@ mod sgb_firmware_tests {
         assert!(!s.has_sgb_firmware());
     }
 }
+
+#[cfg(test)]
+mod link_tests {
+    //! The second Game Boy on the link cable: it boots beside the main machine,
+    //! stays plugged in across the machine swaps a restart or state load make,
+    //! and unplugs when a different game loads.
+    use super::*;
+    use crate::ports::{MemRumble, MemStorage, MemWebcam};
+
+    fn session() -> Session {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        Session::new(Config::default(), ports, [0u8; 32])
+    }
+
+    fn rom(title: u8) -> Vec<u8> {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x101] = 0xC3; // JP 0x0100
+        rom[0x103] = 0x01;
+        rom[0x134] = title;
+        let mut checksum: u8 = 0;
+        for &b in &rom[0x134..0x14D] {
+            checksum = checksum.wrapping_sub(b).wrapping_sub(1);
+        }
+        rom[0x14D] = checksum;
+        rom
+    }
+
+    #[test]
+    fn partner_needs_a_cartridge() {
+        let mut s = session();
+        assert!(s.start_link_partner().is_err());
+        assert!(!s.link_partner_running());
+    }
+
+    #[test]
+    fn partner_stays_linked_across_a_restart_and_unplugs_for_a_new_game() {
+        let mut s = session();
+        s.finish_load_rom(&rom(b'A')).unwrap();
+        s.start_link_partner().unwrap();
+        assert!(s.gb.link_connected());
+        s.run_frame(AbstractInput::none());
+        assert!(s.link_partner_frame().is_some());
+
+        // A restart installs a fresh machine; the next frame re-plugs it.
+        s.restart();
+        assert!(!s.gb.link_connected());
+        s.run_frame(AbstractInput::none());
+        assert!(s.gb.link_connected());
+        assert!(s.link_partner_running());
+
+        s.finish_load_rom(&rom(b'B')).unwrap();
+        assert!(!s.link_partner_running());
+        assert!(s.link_partner_frame().is_none());
+    }
+
+    #[test]
+    fn live_input_goes_to_whichever_machine_is_controlled() {
+        let mut s = session();
+        s.finish_load_rom(&rom(b'A')).unwrap();
+        let held = ButtonState { a: true, ..Default::default() };
+        // Without a partner the switch is inert.
+        s.set_link_input_partner(true);
+        assert_eq!(s.route_link_input(held), (held, ButtonState::default()));
+
+        s.start_link_partner().unwrap();
+        s.set_link_input_partner(true);
+        assert!(s.link_input_partner());
+        assert_eq!(s.route_link_input(held), (ButtonState::default(), held));
+        s.set_link_input_partner(false);
+        assert_eq!(s.route_link_input(held), (held, ButtonState::default()));
+    }
+
+    /// A presentation setting reaches both machines when it changes, and a
+    /// restore keeps it without waiting for the next frame.
+    #[test]
+    fn presentation_settings_reach_the_partner_and_survive_a_restore() {
+        let mut s = session();
+        s.finish_load_rom(&rom(b'A')).unwrap();
+        s.start_link_partner().unwrap();
+        s.set_scanline_ppu(true);
+        assert!(s.gb.scanline_ppu());
+        assert!(s.link_partner.as_mut().unwrap().gb_mut().scanline_ppu());
+
+        let state = s.gb.to_state_bytes().unwrap();
+        s.restore_state(&state).unwrap();
+        assert!(s.gb.scanline_ppu());
+    }
+}
+
+#[cfg(test)]
+mod netplay_tests {
+    //! Netplay framing and bookkeeping over an in-memory cable; the byte
+    //! exchange itself is the core's `RemoteLink` (see its serial tests).
+    use super::*;
+    use crate::ports::{MemRumble, MemStorage, MemWebcam, NetTransport};
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    type Pipe = Arc<Mutex<VecDeque<u8>>>;
+
+    /// One end of a two-way in-memory connection.
+    struct End {
+        tx: Pipe,
+        rx: Pipe,
+    }
+
+    impl NetTransport for End {
+        fn send(&mut self, bytes: &[u8]) {
+            self.tx.lock().unwrap().extend(bytes);
+        }
+        fn recv(&mut self) -> Vec<u8> {
+            self.rx.lock().unwrap().drain(..).collect()
+        }
+    }
+
+    fn connection() -> (End, End) {
+        let (ab, ba) = (Pipe::default(), Pipe::default());
+        (End { tx: ab.clone(), rx: ba.clone() }, End { tx: ba, rx: ab })
+    }
+
+    fn session() -> Session {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        let mut s = Session::new(Config::default(), ports, [0u8; 32]);
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x101] = 0xC3; // JP 0x0100
+        rom[0x103] = 0x01;
+        s.finish_load_rom(&rom).unwrap();
+        s
+    }
+
+    #[test]
+    fn partners_greet_and_stay_plugged_across_a_restart() {
+        let (a_end, b_end) = connection();
+        let (mut a, mut b) = (session(), session());
+        a.start_netplay(Box::new(a_end));
+        b.start_netplay(Box::new(b_end));
+        a.run_frame(AbstractInput::none());
+        b.run_frame(AbstractInput::none());
+        assert_eq!(b.osd().map(|m| m.text.as_str()), Some("Netplay partner connected"));
+        a.run_frame(AbstractInput::none());
+        assert_eq!(a.osd().map(|m| m.text.as_str()), Some("Netplay partner connected"));
+
+        a.restart();
+        assert!(!a.gb.link_connected());
+        a.run_frame(AbstractInput::none());
+        assert!(a.gb.link_connected());
+
+        a.stop_netplay();
+        assert!(!a.netplay_active());
+        assert!(!a.gb.link_connected());
+    }
+
+    #[test]
+    fn a_partner_on_another_version_is_reported() {
+        let (a_end, mut far) = connection();
+        let mut a = session();
+        a.start_netplay(Box::new(a_end));
+        // A record split across two reads still lands whole.
+        far.send(&[b'H', 9]);
+        a.run_frame(AbstractInput::none());
+        assert!(a.osd().is_none());
+        far.send(&[0]);
+        a.run_frame(AbstractInput::none());
+        assert!(a.osd().is_some_and(|m| m.text.contains("version 9")));
+        assert_eq!(&far.recv()[..3], b"H\x02\x01");
+    }
+
+    #[test]
+    fn a_partner_with_the_oam_bug_set_differently_is_warned_about() {
+        let (a_end, b_end) = connection();
+        let (mut a, mut b) = (session(), session());
+        b.set_accuracy_preset(crate::action::AccuracyPreset::Speed);
+        a.start_netplay(Box::new(a_end));
+        b.start_netplay(Box::new(b_end));
+        a.run_frame(AbstractInput::none());
+        assert!(a.osd().is_some_and(|m| m.text.contains("OAM bug off")));
+        b.run_frame(AbstractInput::none());
+        assert!(b.osd().is_some_and(|m| m.text.contains("OAM bug on")));
+    }
+}
+
+#[cfg(test)]
+mod spectate_tests {
+    use super::*;
+    use crate::ports::{Broadcast, MemRumble, MemStorage, MemWebcam};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Viewers(Arc<Mutex<Vec<Vec<u8>>>>);
+
+    impl Broadcast for Viewers {
+        fn send(&mut self, message: &[u8]) {
+            self.0.lock().unwrap().push(message.to_vec());
+        }
+    }
+
+    fn session() -> Session {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        let mut s = Session::new(Config::default(), ports, [0u8; 32]);
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x101] = 0xC3; // JP 0x0100
+        rom[0x103] = 0x01;
+        s.finish_load_rom(&rom).unwrap();
+        s
+    }
+
+    #[test]
+    fn a_still_picture_is_sent_once_and_audio_every_frame() {
+        let viewers = Viewers::default();
+        let mut s = session();
+        for _ in 0..3 {
+            s.run_frame(AbstractInput::none());
+        }
+        s.start_spectating(Box::new(viewers.clone()), true);
+        s.run_frame(AbstractInput::none());
+        s.run_frame(AbstractInput::none());
+
+        let sent = viewers.0.lock().unwrap();
+        let frames: Vec<_> = sent.iter().filter(|m| m[0] == b'F').collect();
+        assert_eq!(frames.len(), 1, "an unchanged picture is not resent");
+        assert_eq!(&frames[0][1..5], b"\x89PNG");
+        let audio: Vec<_> = sent.iter().filter(|m| m[0] == b'A').collect();
+        assert_eq!(audio.len(), 2);
+        assert_eq!(audio[0][1..5], s.audio_sample_rate.to_le_bytes());
+        assert_eq!((audio[0].len() - 5) % 4, 0, "whole stereo i16 samples");
+    }
+
+    #[test]
+    fn video_only_streams_leave_audio_out() {
+        let viewers = Viewers::default();
+        let mut s = session();
+        s.start_spectating(Box::new(viewers.clone()), false);
+        s.run_frame(AbstractInput::none());
+        assert!(viewers.0.lock().unwrap().iter().all(|m| m[0] == b'F'));
+
+        s.stop_spectating();
+        assert!(!s.spectating());
+        s.run_frame(AbstractInput::none());
+        assert_eq!(viewers.0.lock().unwrap().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod run_ahead_tests {
+    //! Run-ahead shows the frame a clone reaches one frame past the live
+    //! machine; the live machine, and everything it feeds, runs exactly as it
+    //! would without it.
+    use super::*;
+    use crate::ports::{MemRumble, MemStorage, MemWebcam};
+
+    fn session() -> Session {
+        let ports = Ports {
+            storage: Box::new(MemStorage::new()),
+            rumble: Box::new(MemRumble::default()),
+            webcam: Box::new(MemWebcam::default()),
+        };
+        Session::new(Config::default(), ports, [0u8; 32])
+    }
+
+    /// Copies the action buttons (P1 low nibble) into BGP forever, so the
+    /// screen's shade follows A/B.
+    fn rom() -> Vec<u8> {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // nop; jp 0150
+        rom[0x150..0x15A].copy_from_slice(&[
+            0x3E, 0x10, // ld a, $10 (select the action buttons)
+            0xE0, 0x00, // ldh (P1), a
+            0xF0, 0x00, // ldh a, (P1)
+            0xE0, 0x47, // ldh (BGP), a
+            0x18, 0xF6, // jr 0150
+        ]);
+        rom
+    }
+
+    #[test]
+    fn run_ahead_shows_the_next_frame_and_leaves_the_machine_alone() {
+        let (mut ahead, mut plain) = (session(), session());
+        ahead.finish_load_rom(&rom()).unwrap();
+        plain.finish_load_rom(&rom()).unwrap();
+        ahead.set_run_ahead(true);
+        let press = AbstractInput::from_pressed([GbButton::A]);
+        let mut shown = Vec::new();
+        for input in [AbstractInput::none(), AbstractInput::none(), press, press, AbstractInput::none()] {
+            let out = ahead.run_frame(input);
+            let reference = plain.run_frame(input);
+            assert_eq!(ahead.gb().cycle_count(), plain.gb().cycle_count());
+            assert_eq!(out.audio, reference.audio, "the clone's audio never reaches the host");
+            let next = plain.gb().clone().run_until_frame(false).0;
+            assert_eq!(out.frame.rgb(), next.rgb());
+            shown.push(out.frame);
+        }
+        assert_ne!(shown[1].rgb(), shown[2].rgb(), "the test ROM's screen follows the A button");
+        assert_eq!(shown[1].rgb(), shown[4].rgb());
+    }
+
+    #[test]
+    fn run_ahead_stays_off_for_frame_advance() {
+        let mut s = session();
+        s.finish_load_rom(&rom()).unwrap();
+        s.set_run_ahead(true);
+        s.mode = RunMode::FrameAdvance;
+        let out = s.run_frame(AbstractInput::none());
+        assert_eq!(out.frame.rgb(), s.gb_mut().get_current_frame().rgb());
+    }
+}
@@ -0,0 +1,31 @@
+//! The APU debug panel's oscilloscope: the last frame of per-channel levels,
+//! read off the same channel tap the recording stems use.
+
+use super::Session;
+
+impl Session {
+    /// Start or stop collecting the scope. The frontend calls this every
+    /// frame with whether the APU panel is open; only a change does anything.
+    pub fn set_apu_scope(&mut self, on: bool) {
+        if on == self.apu_scope.is_some() {
+            return;
+        }
+        self.apu_scope = on.then(Vec::new);
+        if !self.wants_channel_tap() {
+            self.gb.set_channel_tap(false);
+        }
+    }
+
+    /// The last emulated frame's channel levels (CH1 to CH4, one entry per
+    /// host sample, each a DAC level in -1..=1 or 0 for an unpowered DAC).
+    /// `None` while the scope is off.
+    pub fn apu_scope(&self) -> Option<&[[f32; 4]]> {
+        self.apu_scope.as_deref()
+    }
+
+    /// Whether anything reads the channel tap this frame: the stems of a
+    /// recording, or the scope.
+    pub(super) fn wants_channel_tap(&self) -> bool {
+        self.apu_scope.is_some() || self.audio_recording.as_ref().is_some_and(|r| r.wants_tap())
+    }
+}
@@ -0,0 +1,62 @@
+//! Audio recording (Emulation → Record Audio): the heard mix to a WAV, and
+//! optionally the four channels as separate stems from the same pass.
+
+use super::Session;
+use crate::audio::{AudioFile, AudioRecording, AudioSinkOpener, MemorySink};
+use rustyboi_core_lib::audio::ChannelSample;
+
+impl Session {
+    /// Have captures stream into sinks from `opener` (the desktop writes the
+    /// WAVs to disk as they grow) instead of collecting in memory for a
+    /// [`PlatformRequest::SaveBytes`](crate::apply::PlatformRequest) export.
+    pub fn set_audio_sink_opener(&mut self, opener: AudioSinkOpener) {
+        self.audio_sink_opener = Some(opener);
+    }
+
+    /// Begin capturing audio at the session's output rate, with per-channel
+    /// stems when Record Channel Stems is on. Restarts any capture already
+    /// running. `Ok(false)` when the host's sink opener was cancelled.
+    pub(crate) fn start_audio_recording(&mut self) -> Result<bool, String> {
+        let (rate, stems) = (self.audio_sample_rate, self.config.record_stems);
+        let sink = match self.audio_sink_opener.as_mut() {
+            Some(open) => match open(rate, stems)? {
+                Some(sink) => sink,
+                None => return Ok(false),
+            },
+            None => Box::new(MemorySink::new(rate, stems)),
+        };
+        self.audio_recording = Some(AudioRecording::new(rate, stems, sink));
+        Ok(true)
+    }
+
+    /// True while audio is being captured.
+    pub fn is_recording_audio(&self) -> bool {
+        self.audio_recording.is_some()
+    }
+
+    /// Stop capturing and return the file to save, when the capture was kept
+    /// in memory (see [`MemorySink`]), with the captured length in seconds.
+    /// `None` when nothing was being captured.
+    pub(crate) fn stop_audio_recording(&mut self) -> Option<(Option<AudioFile>, f64)> {
+        let rec = self.audio_recording.take()?;
+        if !self.wants_channel_tap() {
+            self.gb.set_channel_tap(false);
+        }
+        let seconds = rec.seconds();
+        Some((rec.finish(), seconds))
+    }
+
+    /// Feed one emulated frame to the capture: the samples the core appended
+    /// to the output buffer from `from` on, and the frame's channel tap for
+    /// the stems. Called from `step_one` right after the frame runs.
+    pub(super) fn capture_audio_recording(&mut self, from: usize, tap: &[ChannelSample]) {
+        let Some(rec) = self.audio_recording.as_mut() else { return };
+        {
+            let buf = self.audio_buf.lock().unwrap_or_else(|e| e.into_inner());
+            rec.push_mix(buf.get(from..).unwrap_or_default());
+        }
+        if rec.wants_tap() {
+            rec.push_tap(tap, self.gb.mixes_digitally(), self.config.audio_high_pass);
+        }
+    }
+}
@@ -1,8 +1,9 @@
-//! Cheat codes and the two runtime-fetched databases (libretro cheat DB and
-//! the No-Intro game-name index).
+//! Cheat codes, the two runtime-fetched databases (libretro cheat DB and
+//! the No-Intro game-name index), and the user's dump-verification DAT.
 
 use super::{log_no_intro_attribution, Session};
 use crate::cheats::{Cheat, CheatError};
+use crate::no_intro::DumpStatus;
 
 impl Session {
     /// Add a Game Genie / GameShark code. Game Genie codes patch the ROM
@@ -71,6 +72,44 @@ impl Session {
         }
     }
 
+    /// Install a No-Intro style DAT (clrmamepro text, as the user's config
+    /// points at) as this session's dump-verification database, replacing any
+    /// previous one. Returns how many entries carry a SHA-1; 0 means the file
+    /// verifies nothing and [`dump_status`](Self::dump_status) stays `None`.
+    pub fn load_verification_dat(&mut self, text: &str) -> usize {
+        self.verify_dat = crate::no_intro::parse_dat_sha1(text);
+        self.dump = self.rom_sha1.and_then(|sha1| self.verify_sha1(&sha1));
+        self.verify_dat.len()
+    }
+
+    /// The loaded ROM's standing against the verification DAT, or `None` when
+    /// no DAT is installed or no ROM is loaded.
+    pub fn dump_status(&self) -> Option<DumpStatus> {
+        self.dump.clone()
+    }
+
+    /// SHA-1 of the loaded ROM, `None` without one.
+    pub(crate) fn rom_sha1(&self) -> Option<[u8; 20]> {
+        self.rom_sha1
+    }
+
+    /// Hash the cartridge just loaded and look it up in the verification DAT.
+    pub(super) fn refresh_rom_digest(&mut self) {
+        self.rom_sha1 = self.gb.cartridge().and_then(|cart| cart.rom_sha1());
+        self.dump = self.rom_sha1.and_then(|sha1| self.verify_sha1(&sha1));
+    }
+
+    /// Look a ROM digest up in the verification DAT (`None` without one).
+    fn verify_sha1(&self, sha1: &[u8; 20]) -> Option<DumpStatus> {
+        if self.verify_dat.is_empty() {
+            return None;
+        }
+        Some(match self.verify_dat.binary_search_by(|(d, _)| d.cmp(sha1)) {
+            Ok(i) => DumpStatus::Verified(self.verify_dat[i].1.clone()),
+            Err(_) => DumpStatus::Unknown,
+        })
+    }
+
     /// Parse a downloaded libretro `.cht` body into the pending fetched-cheat
     /// list (replacing any previous fetch). Returns the number of cheats parsed.
     /// The frontend then shows them for the user to pick; selected codes are
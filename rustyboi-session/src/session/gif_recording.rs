@@ -0,0 +1,40 @@
+//! GIF recording (Emulation → Record GIF): the presented frames as an
+//! animated GIF at the configured scale and length cap.
+
+use super::Session;
+use crate::gif::GifRecording;
+use rustyboi_core_lib::gb::Frame;
+
+impl Session {
+    /// Begin capturing frames. Restarts any capture already running.
+    pub(crate) fn start_gif_recording(&mut self) {
+        self.gif_recording = Some(GifRecording::new(self.gif_scale(), self.gif_max_seconds()));
+    }
+
+    /// True while a GIF is being captured, including after it reached its
+    /// length cap and stopped taking frames.
+    pub fn is_recording_gif(&self) -> bool {
+        self.gif_recording.is_some()
+    }
+
+    /// Stop capturing and return the finished GIF and the captured length in
+    /// seconds. `None` when nothing was being captured; an empty file when
+    /// the capture ran no frames.
+    pub(crate) fn stop_gif_recording(&mut self) -> Option<(Vec<u8>, f64)> {
+        let rec = self.gif_recording.take()?;
+        let seconds = rec.seconds();
+        Some((rec.finish().unwrap_or_default(), seconds))
+    }
+
+    /// Feed one emulated frame to the capture, as presented (with the SGB
+    /// border when it is shown). Called from `step_one`.
+    pub(super) fn capture_gif_frame(&mut self, frame: &Frame) {
+        if self.gif_recording.as_ref().is_none_or(|r| r.is_full()) {
+            return;
+        }
+        let (width, height, rgb) = self.presented_rgb(frame);
+        if let Some(rec) = self.gif_recording.as_mut() {
+            rec.push(width, height, &rgb);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! A second, local Game Boy on the other end of the link cable: two-player
+//! Tetris or a Pokémon trade against yourself. The partner runs the same
+//! cartridge as the main machine, one frame per main frame, and its battery
+//! RAM persists under its own storage key so the two save files never mix.
+
+use super::{log_config_error, Session, SessionError, SAVE_FLUSH_FRAMES};
+use rustyboi_core_lib::gb::{Frame, GB};
+use rustyboi_core_lib::input::ButtonState;
+
+/// The partner machine and what the session shows of it.
+pub(super) struct LinkPartner {
+    gb: Box<GB>,
+    frame: Frame,
+    /// Live input drives the partner instead of the main machine.
+    controlled: bool,
+}
+
+impl LinkPartner {
+    pub(super) fn gb_mut(&mut self) -> &mut GB {
+        &mut self.gb
+    }
+}
+
+impl Session {
+    /// Power on a second Game Boy with the running cartridge and plug it into
+    /// the other end of the link cable. The partner boots on the session's
+    /// hardware model with its own battery save (first run: a copy of the
+    /// main machine's). Replaces any printer or netplay on the link port.
+    pub fn start_link_partner(&mut self) -> Result<(), String> {
+        if self.gb.cartridge().is_none() {
+            return Err("load a ROM before linking a second Game Boy".into());
+        }
+        self.stop_netplay();
+        let mut gb = self.rebuild_current_gb();
+        Self::present_on(&self.config, self.scanline_ppu(), self.audio_sample_rate, &mut gb);
+        gb.set_oam_bug(self.oam_bug_in_effect());
+        if let Some(bytes) = self.ports.storage.read(&self.link_battery_key())
+            && let Some(cart) = gb.cartridge_mut()
+            && cart.has_battery()
+        {
+            let _ = cart.import_save_ram(&bytes);
+        }
+        GB::connect_link(&mut self.gb, &mut gb);
+        let frame = gb.get_current_frame();
+        self.link_partner = Some(LinkPartner { gb, frame, controlled: false });
+        Ok(())
+    }
+
+    /// Unplug and power off the second Game Boy, persisting its battery save.
+    pub fn stop_link_partner(&mut self) {
+        if self.link_partner.is_none() {
+            return;
+        }
+        self.persist_link_battery();
+        self.link_partner = None;
+        self.gb.detach_serial_device();
+    }
+
+    pub fn link_partner_running(&self) -> bool {
+        self.link_partner.is_some()
+    }
+
+    /// Route live input to the second Game Boy (`true`) or back to the main
+    /// one. The main machine sees no buttons held while the partner has them.
+    pub fn set_link_input_partner(&mut self, partner: bool) {
+        if let Some(link) = self.link_partner.as_mut() {
+            link.controlled = partner;
+        }
+    }
+
+    pub fn link_input_partner(&self) -> bool {
+        self.link_partner.as_ref().is_some_and(|l| l.controlled)
+    }
+
+    /// The second Game Boy's latest frame (RGB888, 160×144), for the window
+    /// a frontend draws it in. `None` while no partner is running.
+    pub fn link_partner_frame(&self) -> Option<&Frame> {
+        self.link_partner.as_ref().map(|l| &l.frame)
+    }
+
+    /// Split this frame's live input between the two machines: `(main,
+    /// partner)`.
+    pub(super) fn route_link_input(&self, live: ButtonState) -> (ButtonState, ButtonState) {
+        if self.link_input_partner() {
+            (ButtonState::default(), live)
+        } else {
+            (live, ButtonState::default())
+        }
+    }
+
+    /// Re-plug the cable when the main machine was swapped out from under it
+    /// (a state load, rewind step, or restart carries a severed end).
+    pub(super) fn relink_partner(&mut self) {
+        if let Some(link) = self.link_partner.as_mut()
+            && !self.gb.link_connected()
+        {
+            GB::connect_link(&mut self.gb, &mut link.gb);
+        }
+    }
+
+    /// Run the partner for one frame, right after the main machine ran its
+    /// own; the shared cable orders their serial exchanges by cycle.
+    pub(super) fn step_link_partner(&mut self, input: ButtonState) {
+        let Some(link) = self.link_partner.as_mut() else { return };
+        link.gb.set_input_state(input);
+        let (frame, _breakpoint) = link.gb.run_until_frame(true);
+        link.frame = frame;
+        if self.frame_count.is_multiple_of(SAVE_FLUSH_FRAMES) {
+            self.persist_link_battery();
+        }
+    }
+
+    fn persist_link_battery(&mut self) {
+        let Some(cart) = self.link_partner.as_ref().and_then(|l| l.gb.cartridge()) else { return };
+        if !cart.has_battery() {
+            return;
+        }
+        let bytes = cart.battery_image();
+        let key = self.link_battery_key();
+        if let Err(e) = self.ports.storage.write(&key, &bytes) {
+            log_config_error(&SessionError::from(e));
+        }
+    }
+
+    /// The partner's battery lives beside the main one's, under its own key.
+    fn link_battery_key(&self) -> String {
+        format!("link2/{}", self.battery_key())
+    }
+}
@@ -0,0 +1,130 @@
+//! Netplay over the link cable: the serial port talks to a second rustyboi on
+//! another machine through a [`NetTransport`] (TCP on desktop, a WebSocket
+//! relay on the web). The core's [`RemoteLink`] does the latency hiding; this
+//! module frames its messages on the wire and pumps them once per frame.
+//!
+//! Wire format: fixed three-byte records, `[tag, a, b]`. `H` is the hello
+//! (`a` = [`NETPLAY_VERSION`], `b` bit 0 = the OAM bug emulated), sent once
+//! on connect so a partner on other accuracy settings is flagged before the
+//! two games drift apart; `S` is a side's state
+//! (`a` = SB, `b` bit 0 = SC.7 armed, bit 1 = SC.0 internal clock); `D` is a
+//! deposited byte (`a`).
+
+use super::Session;
+use crate::ports::NetTransport;
+use rustyboi_core_lib::serial::{LinkMessage, RemoteLink};
+
+/// Bumped whenever the wire records change meaning.
+const NETPLAY_VERSION: u8 = 2;
+
+/// Hello `b` bits.
+const HELLO_OAM_BUG: u8 = 1;
+
+const RECORD: usize = 3;
+const TAG_HELLO: u8 = b'H';
+const TAG_STATE: u8 = b'S';
+const TAG_DEPOSIT: u8 = b'D';
+
+pub(super) struct Netplay {
+    transport: Box<dyn NetTransport>,
+    link: RemoteLink,
+    /// Received bytes short of a whole record.
+    inbox: Vec<u8>,
+    /// The partner's hello arrived.
+    greeted: bool,
+}
+
+fn encode(message: LinkMessage) -> [u8; RECORD] {
+    match message {
+        LinkMessage::State { sb, armed, internal } => [TAG_STATE, sb, u8::from(armed) | u8::from(internal) << 1],
+        LinkMessage::Deposit(byte) => [TAG_DEPOSIT, byte, 0],
+    }
+}
+
+/// What a received record means to the session.
+enum Record {
+    /// Version and flags.
+    Hello(u8, u8),
+    Link(LinkMessage),
+}
+
+fn decode(record: &[u8]) -> Option<Record> {
+    match *record {
+        [TAG_HELLO, version, flags] => Some(Record::Hello(version, flags)),
+        [TAG_STATE, sb, flags] => {
+            Some(Record::Link(LinkMessage::State { sb, armed: flags & 1 != 0, internal: flags & 2 != 0 }))
+        }
+        [TAG_DEPOSIT, byte, _] => Some(Record::Link(LinkMessage::Deposit(byte))),
+        _ => None,
+    }
+}
+
+impl Session {
+    /// Plug the link port into `transport`, already connected to a partner
+    /// (hosting or joining is the platform's job). Unplugs any second local
+    /// Game Boy or printer first.
+    pub fn start_netplay(&mut self, mut transport: Box<dyn NetTransport>) {
+        self.stop_link_partner();
+        let flags = if self.oam_bug_in_effect() { HELLO_OAM_BUG } else { 0 };
+        transport.send(&[TAG_HELLO, NETPLAY_VERSION, flags]);
+        let mut link = RemoteLink::default();
+        self.gb.plug_remote_link(&mut link);
+        self.netplay = Some(Netplay { transport, link, inbox: Vec::new(), greeted: false });
+    }
+
+    /// Drop the netplay transport and unplug the link port.
+    pub fn stop_netplay(&mut self) {
+        if self.netplay.take().is_some() {
+            self.gb.detach_serial_device();
+        }
+    }
+
+    pub fn netplay_active(&self) -> bool {
+        self.netplay.is_some()
+    }
+
+    /// Before a frame: apply what the partner sent and re-plug a machine a
+    /// state load or restart swapped in.
+    pub(super) fn receive_netplay(&mut self) {
+        let oam_bug = self.oam_bug_in_effect();
+        let Some(net) = self.netplay.as_mut() else { return };
+        if !net.link.connected() {
+            self.gb.plug_remote_link(&mut net.link);
+        }
+        net.inbox.extend(net.transport.recv());
+        let whole = net.inbox.len() - net.inbox.len() % RECORD;
+        let mut notice = None;
+        for record in net.inbox[..whole].chunks_exact(RECORD) {
+            match decode(record) {
+                Some(Record::Link(message)) => net.link.deliver(message),
+                Some(Record::Hello(NETPLAY_VERSION, flags)) if !net.greeted => {
+                    net.greeted = true;
+                    notice = Some(if (flags & HELLO_OAM_BUG != 0) == oam_bug {
+                        "Netplay partner connected".to_owned()
+                    } else {
+                        let theirs = if oam_bug { "off" } else { "on" };
+                        format!("Netplay partner has the OAM bug {theirs}: match accuracy presets or games may desync")
+                    });
+                }
+                Some(Record::Hello(NETPLAY_VERSION, _)) => {}
+                Some(Record::Hello(v, _)) => {
+                    notice = Some(format!("Netplay partner speaks version {v}, expected {NETPLAY_VERSION}"));
+                }
+                None => {}
+            }
+        }
+        net.inbox.drain(..whole);
+        if let Some(text) = notice {
+            self.flash_osd(text);
+        }
+    }
+
+    /// After a frame: send the partner what changed on our side.
+    pub(super) fn send_netplay(&mut self) {
+        let Some(net) = self.netplay.as_mut() else { return };
+        let out: Vec<u8> = net.link.outgoing().into_iter().flat_map(encode).collect();
+        if !out.is_empty() {
+            net.transport.send(&out);
+        }
+    }
+}
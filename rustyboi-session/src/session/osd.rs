@@ -0,0 +1,41 @@
+//! On-screen display: short notices (slot saves, speed, volume) flashed over
+//! the game. The session only keeps the latest one; each UI fades it out on
+//! its own clock, so the message reaches every frontend through
+//! [`SessionUiState::osd`](crate::action::SessionUiState::osd).
+
+use super::Session;
+use crate::action::OsdMessage;
+
+impl Session {
+    /// The latest on-screen notice, if any has been shown.
+    pub fn osd(&self) -> Option<&OsdMessage> {
+        self.osd.as_ref()
+    }
+
+    /// Flash `text` over the game. A fresh `id` restarts the fade even when
+    /// the text repeats. Public so hosts can announce their own events (a
+    /// controller plugged in).
+    pub fn flash_osd(&mut self, text: impl Into<String>) {
+        let id = self.osd.as_ref().map_or(1, |m| m.id.wrapping_add(1));
+        self.osd = Some(OsdMessage { id, text: text.into() });
+    }
+
+    /// The notice for the current fast-forward state.
+    pub(super) fn flash_speed(&mut self) {
+        let text = match self.mode {
+            super::RunMode::FastForward(_) if self.config.fast_forward_factor == 0 => {
+                "Fast forward (uncapped)".to_owned()
+            }
+            super::RunMode::FastForward(_) => format!("Fast forward ×{}", self.config.fast_forward_factor),
+            _ if self.emulation_speed() != 100 => format!("Speed {}%", self.emulation_speed()),
+            _ => "Normal speed".to_owned(),
+        };
+        self.flash_osd(text);
+    }
+
+    /// The notice for the current volume / mute state.
+    pub(super) fn flash_volume(&mut self) {
+        let text = if self.is_muted() { "Muted".to_owned() } else { format!("Volume {}%", self.volume()) };
+        self.flash_osd(text);
+    }
+}
@@ -0,0 +1,51 @@
+//! Crash recovery: a short ring of periodic in-memory snapshots the crash
+//! panel can fall back on when the core errors out mid-frame.
+//!
+//! Independent of rewind (which the user may have turned off, and which a
+//! crash loop would burn through in a second): one snapshot every
+//! [`RECOVERY_INTERVAL_FRAMES`], the newest [`RECOVERY_DEPTH`] kept. Restoring
+//! pops the newest, so if the bug strikes again from there the next restore
+//! reaches further back.
+//!
+//! A snapshot is a plain `GB::clone`, like an offloaded rewind capture, so the
+//! frame it lands on doesn't stall on serializing and compressing the state;
+//! that cost is paid only on the rare restore.
+
+use super::{Session, SessionError};
+
+/// Emulated frames between recovery snapshots (about ten seconds).
+const RECOVERY_INTERVAL_FRAMES: u64 = 600;
+
+/// Recovery snapshots kept, oldest dropped first.
+const RECOVERY_DEPTH: usize = 3;
+
+impl Session {
+    /// Take a recovery snapshot when one is due (called after each frame).
+    pub(super) fn capture_recovery(&mut self) {
+        if !self.frame_count.is_multiple_of(RECOVERY_INTERVAL_FRAMES) {
+            return;
+        }
+        if self.recovery.len() == RECOVERY_DEPTH {
+            self.recovery.pop_front();
+        }
+        self.recovery.push_back((self.frame_count, self.gb.clone()));
+    }
+
+    /// The frame the newest recovery snapshot was taken at, or `None` when
+    /// there is none (offered on the crash panel).
+    pub fn recovery_snapshot(&self) -> Option<u64> {
+        self.recovery.back().map(|&(frame, _)| frame)
+    }
+
+    /// Restore the newest recovery snapshot, consuming it, and return its
+    /// frame.
+    pub fn restore_recovery_snapshot(&mut self) -> Result<u64, SessionError> {
+        let (frame, mut gb) = self.recovery.pop_back().ok_or(SessionError::NoState)?;
+        // Round-trip through the state bytes so the clone is installed the
+        // same way a savestate load would be.
+        let state = gb.to_state_bytes().map_err(|e| SessionError::State(e.to_string()))?;
+        self.restore_state(&state)?;
+        self.frame_count = frame;
+        Ok(frame)
+    }
+}
@@ -21,13 +21,14 @@ impl Session {
     }
 
     /// The cartridge's battery-backed SRAM image (File → Export Battery Save),
-    /// or `None` when the inserted cart has no battery / no cart is loaded.
+    /// with the RTC footer appended for carts with a clock, or `None` when the
+    /// inserted cart has no battery / no cart is loaded.
     pub fn export_battery(&self) -> Option<Vec<u8>> {
         let cart = self.gb.cartridge()?;
         if !cart.has_battery() {
             return None;
         }
-        Some(cart.save_ram().to_vec())
+        Some(cart.battery_image())
     }
 
     /// Import a battery save image into the current cartridge (File → Import
@@ -46,9 +47,48 @@ impl Session {
         Ok(())
     }
 
+    /// Erase the current cartridge's battery save (File → Battery Saves →
+    /// Erase): SRAM is blanked, any attached `.sav` rewritten, and the storage
+    /// port copy replaced so the old save can't hydrate back on reload.
+    pub fn erase_battery(&mut self) -> Result<(), String> {
+        let cart = self
+            .gb
+            .cartridge_mut()
+            .ok_or_else(|| "no cartridge loaded".to_string())?;
+        cart.erase_save_ram()?;
+        self.persist_battery();
+        Ok(())
+    }
+
+    /// Size in bytes of the inserted cartridge's battery RAM, 0 without one
+    /// (shown by the battery save manager).
+    pub fn battery_size(&self) -> usize {
+        self.gb
+            .cartridge()
+            .filter(|c| c.has_battery())
+            .map_or(0, |c| c.save_ram().len())
+    }
+
+    /// Write battery RAM the game has touched to the attached `.sav` right away
+    /// instead of at the next periodic flush (File → Flush Saves Now), then
+    /// mirror the image to the storage port. Errors when no cart is loaded,
+    /// the cart has no battery, or the sidecar write fails.
+    pub fn flush_saves(&mut self) -> Result<(), String> {
+        let cart = self
+            .gb
+            .cartridge_mut()
+            .ok_or_else(|| "no cartridge loaded".to_string())?;
+        if !cart.has_battery() {
+            return Err("this cartridge has no battery save".into());
+        }
+        cart.flush_save().map_err(|e| e.to_string())?;
+        self.persist_battery();
+        Ok(())
+    }
+
     /// Storage key for the cartridge battery image, namespaced by ROM id (mirror
     /// of [`slot_key`](Self::slot_key)).
-    fn battery_key(&self) -> String {
+    pub(super) fn battery_key(&self) -> String {
         let mut hex = String::with_capacity(64);
         for b in self.rom_id {
             hex.push_str(&format!("{b:02x}"));
@@ -65,7 +105,7 @@ impl Session {
         if !cart.has_battery() {
             return;
         }
-        let bytes = cart.save_ram().to_vec();
+        let bytes = cart.battery_image();
         let key = self.battery_key();
         if let Err(e) = self.ports.storage.write(&key, &bytes) {
             log_config_error(&SessionError::from(e));
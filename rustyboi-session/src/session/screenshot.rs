@@ -0,0 +1,40 @@
+//! Copy Screenshot (File → Copy Screenshot): the presented frame as RGBA for
+//! the host to put on the system clipboard, or as a PNG for a control client.
+
+use super::{Session, GB_SIZE, SGB_SIZE};
+use crate::present::{rgb_to_pixels, PixelOrder};
+use crate::video::encode_rgb_png;
+use rustyboi_core_lib::gb::Frame;
+
+impl Session {
+    /// The frame as the user sees it, as `(width, height, rgba)`: the SGB
+    /// composite when the border is shown, else the 160x144 screen.
+    pub fn screenshot_rgba(&mut self) -> (u32, u32, Vec<u8>) {
+        let frame = self.gb.get_current_frame();
+        let (width, height, rgb) = self.presented_rgb(&frame);
+        let mut rgba = vec![0; (width * height * 4) as usize];
+        rgb_to_pixels(&rgb, PixelOrder::Rgba, &mut rgba);
+        (width, height, rgba)
+    }
+
+    /// The frame as the user sees it, encoded as a PNG (an automation
+    /// client's screenshot).
+    pub fn screenshot_png(&mut self) -> Vec<u8> {
+        let frame = self.gb.get_current_frame();
+        let (width, height, rgb) = self.presented_rgb(&frame);
+        encode_rgb_png(width, height, &rgb)
+    }
+
+    /// `frame` as presented, as `(width, height, rgb)`: swapped for the SGB
+    /// composite when the border is shown. Shared with the GIF capture.
+    pub(super) fn presented_rgb(&self, frame: &Frame) -> (u32, u32, Vec<u8>) {
+        if self.sgb_border
+            && let Some(rgb) = self.gb.sgb_composited_frame()
+        {
+            let (width, height) = SGB_SIZE;
+            return (width, height, rgb.to_vec());
+        }
+        let (width, height) = GB_SIZE;
+        (width, height, frame.rgb().to_vec())
+    }
+}
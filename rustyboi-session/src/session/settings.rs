@@ -2,9 +2,16 @@
 //! the getter/setter pairs that persist through the storage port.
 
 use super::{log_config_error, RunMode, Session, SessionError, GB_SIZE, SGB_SIZE};
-use crate::action::{HardwareChoice, DmgPaletteChoice, ScalingMode, SgbPaletteChoice};
+use crate::action::{
+    AccuracyPreset, AspectRatio, DmgPaletteChoice, FrameSkip, HardwareChoice, ScalingMode, SgbPaletteChoice, EMULATION_SPEEDS,
+};
 use crate::apply::palette_shades;
-use crate::config::Config;
+use crate::config::{Config, MAX_RECENT_ROMS};
+use crate::palette::{parse_palette, CustomPalette};
+
+/// The slowest and fastest emulation speeds, in percent.
+const EMULATION_SPEED_MIN: u32 = EMULATION_SPEEDS[0];
+const EMULATION_SPEED_MAX: u32 = EMULATION_SPEEDS[EMULATION_SPEEDS.len() - 1];
 
 impl Session {
     pub fn config(&self) -> &Config {
@@ -30,13 +37,16 @@ impl Session {
         self.original_rom = Some(rom);
     }
 
-    /// Apply an updated config: reconfigures the rewind buffer to match (other
-    /// fields — hardware, palette, remap, ff factor — take effect on their next
-    /// use). Persist separately via [`Session::save_config`].
+    /// Apply an updated config: reconfigures the rewind buffer to match and
+    /// re-applies the presentation settings (other fields — hardware, remap,
+    /// ff factor — take effect on their next use). Persist separately via
+    /// [`Session::save_config`].
     pub fn set_config(&mut self, config: Config) {
         self.rewind
             .reconfigure(config.rewind.depth, config.rewind.interval_frames);
         self.config = config;
+        self.apply_presentation();
+        self.apply_machine_config();
     }
 
     /// Persist the current config through storage.
@@ -89,6 +99,14 @@ impl Session {
         }
     }
 
+    /// [`content_size`](Self::content_size) as shown at the chosen
+    /// [`AspectRatio`]: the width becomes the displayed width, so a window
+    /// sized to it holds the frame without bars.
+    pub fn display_size(&self) -> (u32, u32) {
+        let (w, h) = self.content_size();
+        (self.aspect_ratio().display_width(w as f32, h as f32).round() as u32, h)
+    }
+
     /// Whether fast-forward is currently engaged.
     pub fn is_fast_forward(&self) -> bool {
         matches!(self.mode, RunMode::FastForward(_))
@@ -107,6 +125,32 @@ impl Session {
             RunMode::FastForward(_) => self.mode = RunMode::Normal,
             _ => self.fast_forward(),
         }
+        self.flash_speed();
+    }
+
+    /// Hold-to-fast-forward: call every tick with whether the hold key is
+    /// down. Pressing it engages fast-forward from normal speed; releasing it
+    /// drops back only if the press was what engaged it, so a fast-forward
+    /// already toggled on survives a tap of the hold key.
+    pub fn hold_fast_forward(&mut self, held: bool) {
+        match (held, self.ff_hold) {
+            (true, None) => {
+                let engage = self.mode == RunMode::Normal;
+                if engage {
+                    self.fast_forward();
+                    self.flash_speed();
+                }
+                self.ff_hold = Some(engage);
+            }
+            (false, Some(engaged)) => {
+                if engaged && self.is_fast_forward() {
+                    self.mode = RunMode::Normal;
+                    self.flash_speed();
+                }
+                self.ff_hold = None;
+            }
+            _ => {}
+        }
     }
 
     // --- config-mutating actions (persist through storage) ------------------
@@ -131,6 +175,68 @@ impl Session {
         self.persist_config();
     }
 
+    /// The user palettes, in the order they were added.
+    pub fn custom_palettes(&self) -> &[CustomPalette] {
+        &self.config.custom_palettes
+    }
+
+    /// Add a user palette from `text` (hex colours or JASC-PAL, see
+    /// [`parse_palette`]) and switch to it; persists the config. Colours
+    /// already listed are selected again rather than added twice. Returns the
+    /// palette's name.
+    pub fn add_custom_palette(&mut self, text: &str) -> Result<String, String> {
+        let shades = parse_palette(text)?;
+        let palettes = &mut self.config.custom_palettes;
+        let name = match palettes.iter().find(|p| p.shades == shades) {
+            Some(known) => known.name.clone(),
+            None => {
+                let n = (1..).find(|n| palettes.iter().all(|p| p.name != format!("Custom {n}")));
+                let name = format!("Custom {}", n.unwrap_or_default());
+                palettes.push(CustomPalette { name: name.clone(), shades });
+                name
+            }
+        };
+        self.set_palette_choice(DmgPaletteChoice::Custom(shades));
+        Ok(name)
+    }
+
+    /// Forget user palette `index`; persists the config. When it is the one
+    /// shown, the display falls back to the default palette.
+    pub(crate) fn remove_custom_palette(&mut self, index: usize) {
+        if index >= self.config.custom_palettes.len() {
+            return;
+        }
+        let removed = self.config.custom_palettes.remove(index);
+        if self.palette == removed.choice() {
+            self.set_palette_choice(DmgPaletteChoice::default());
+        } else {
+            self.persist_config();
+        }
+    }
+
+    /// The ROM paths opened most recently, newest first.
+    pub fn recent_roms(&self) -> &[String] {
+        &self.config.recent_roms
+    }
+
+    /// Put `path` at the top of the recent-ROM list (moving it up if it is
+    /// already listed, dropping the oldest past [`MAX_RECENT_ROMS`]);
+    /// persists the config. The frontend calls this after a ROM it can
+    /// reopen by path loads.
+    pub fn note_recent_rom(&mut self, path: &str) {
+        let recent = &mut self.config.recent_roms;
+        recent.retain(|p| p != path);
+        recent.insert(0, path.to_owned());
+        recent.truncate(MAX_RECENT_ROMS);
+        self.persist_config();
+    }
+
+    /// Forget the recent-ROM list; persists the config.
+    pub(crate) fn clear_recent_roms(&mut self) {
+        self.config.recent_roms.clear();
+        self.persist_config();
+    }
+
     /// Seed the presentation palette without persisting (startup, from the
     /// CLI/config-derived choice).
     pub fn init_palette_choice(&mut self, choice: DmgPaletteChoice) {
@@ -138,7 +244,7 @@ impl Session {
         self.config.dmg_palette_choice = choice;
         self.config.dmg_palette.shades = palette_shades(choice, self.config.color_correction);
         // The core applies the palette to mono frames now (unified RGB output).
-        self.gb.set_dmg_palette(choice);
+        self.apply_presentation();
     }
 
     /// The CGB colorization scheme for DMG games (Auto / a boot-ROM scheme).
@@ -179,7 +285,7 @@ impl Session {
     /// CLI/config-derived choice).
     pub fn init_sgb_palette(&mut self, choice: SgbPaletteChoice) {
         self.config.sgb_palette = choice;
-        self.gb.set_sgb_palette(choice);
+        self.apply_presentation();
     }
 
     /// Whether the SGB palette setting applies to the loaded machine: only on
@@ -202,10 +308,10 @@ impl Session {
         conversion: rustyboi_core_lib::ppu::ColorCorrection,
     ) {
         self.config.color_correction = conversion;
-        self.gb.set_cgb_color_conversion(conversion);
         // Correction composes with the DMG base palette, so refresh the cached
         // mono shades (Green/Pocket have distinct raw vs LCD variants).
         self.config.dmg_palette.shades = palette_shades(self.palette, conversion);
+        self.apply_presentation();
         self.persist_config();
     }
 
@@ -305,6 +411,119 @@ impl Session {
         self.persist_config();
     }
 
+    /// Whether the DMG dot-matrix filter is on.
+    pub fn dot_matrix(&self) -> bool {
+        self.config.dot_matrix
+    }
+
+    /// Turn the DMG dot-matrix filter on/off; persists the config. Swaps the
+    /// mono palette live; the renderer reads the flag each frame for the grid.
+    pub(crate) fn set_dot_matrix(&mut self, on: bool) {
+        self.config.dot_matrix = on;
+        self.apply_presentation();
+        self.persist_config();
+    }
+
+    /// Whether the loaded ROM runs on the scanline renderer.
+    pub fn scanline_ppu(&self) -> bool {
+        let hex = self.rom_id_hex();
+        self.config.scanline_ppu_roms.contains(&hex)
+    }
+
+    /// Put the loaded ROM on (or back off) the scanline renderer; persists the
+    /// config. The choice is remembered per ROM and takes effect on the next
+    /// frame. Nothing is remembered with no ROM loaded.
+    pub(crate) fn set_scanline_ppu(&mut self, on: bool) {
+        if self.rom_id == [0; 32] {
+            return;
+        }
+        let hex = self.rom_id_hex();
+        self.config.scanline_ppu_roms.retain(|r| *r != hex);
+        if on {
+            self.config.scanline_ppu_roms.push(hex);
+        }
+        self.apply_presentation();
+        self.persist_config();
+    }
+
+    fn rom_id_hex(&self) -> String {
+        self.rom_id.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Whether cartridge real-time clocks follow the host wall clock.
+    pub fn rtc_host_clock(&self) -> bool {
+        self.config.rtc_host_clock
+    }
+
+    /// Choose the RTC time source (host wall clock vs emulated cycles);
+    /// persists the config.
+    pub(crate) fn set_rtc_host_clock(&mut self, on: bool) {
+        self.config.rtc_host_clock = on;
+        self.apply_machine_config();
+        self.persist_config();
+    }
+
+    /// Apply the machine settings a movie pins down. The cartridge's RTC goes
+    /// on the configured time source: the host wall clock, unless a movie is
+    /// recording or playing back or the input editor is open (those must
+    /// replay bit-exactly, so they keep the cycle-derived clock). The OAM bug
+    /// follows the movie playing back or recording, else the config. Called
+    /// whenever the machine or one of those inputs changes; each frame then
+    /// only syncs the clock.
+    pub(super) fn apply_machine_config(&mut self) {
+        let host_clock = self.config.rtc_host_clock
+            && self.playback.is_none()
+            && self.recording.is_none()
+            && self.input_editor.is_none();
+        if let Some(cart) = self.gb.cartridge_mut() {
+            cart.set_rtc_host_clock(host_clock);
+        }
+        let oam_bug = self.oam_bug_in_effect();
+        self.gb.set_oam_bug(oam_bug);
+        if let Some(link) = self.link_partner.as_mut() {
+            link.gb_mut().set_oam_bug(oam_bug);
+        }
+    }
+
+    /// Whether the DMG OAM corruption bug is emulated right now: as the movie
+    /// playing back or being recorded has it, else as configured. It changes
+    /// what the CPU reads, so unlike the presentation settings it is part of
+    /// what a movie or a netplay partner must agree on.
+    pub(super) fn oam_bug_in_effect(&self) -> bool {
+        match (&self.playback, &self.recording) {
+            (Some(playback), _) => playback.oam_bug(),
+            (None, Some(recording)) => recording.oam_bug(),
+            (None, None) => self.config.oam_bug,
+        }
+    }
+
+    /// Whether the machine is snapshotted to the auto slot on exit.
+    pub fn auto_save_state(&self) -> bool {
+        self.config.auto_save_state
+    }
+
+    /// Whether emulation pauses while the window is unfocused.
+    pub fn pause_on_focus_loss(&self) -> bool {
+        self.config.pause_on_focus_loss
+    }
+
+    /// Turn pausing on focus loss on or off; persists the config. The
+    /// frontend owns the pause itself and reads this on focus changes.
+    pub(crate) fn set_pause_on_focus_loss(&mut self, on: bool) {
+        self.config.pause_on_focus_loss = on;
+        self.persist_config();
+    }
+
+    /// Turn auto-save on exit on or off; persists the config. Turning it off
+    /// also withdraws an open resume offer.
+    pub(crate) fn set_auto_save_state(&mut self, on: bool) {
+        self.config.auto_save_state = on;
+        if !on {
+            self.resume_offer = None;
+        }
+        self.persist_config();
+    }
+
     /// Enable/disable rewind capture; persists the config.
     pub(crate) fn set_rewind_enabled(&mut self, enabled: bool) {
         self.config.rewind.enabled = enabled;
@@ -336,6 +555,7 @@ impl Session {
     pub fn set_volume(&mut self, volume: u8) {
         self.config.volume = volume.min(100);
         self.persist_config();
+        self.flash_volume();
     }
 
     /// Current master volume (0..=100).
@@ -343,6 +563,218 @@ impl Session {
         self.config.volume.min(100)
     }
 
+    /// Whether the master output is muted.
+    pub fn is_muted(&self) -> bool {
+        self.config.muted
+    }
+
+    /// Flip the master mute; persists the config. The volume level is kept,
+    /// so unmuting comes back at the same loudness.
+    pub fn toggle_mute(&mut self) {
+        self.config.muted = !self.config.muted;
+        self.persist_config();
+        self.flash_volume();
+    }
+
+    /// Whether the APU's output high-pass filter runs.
+    pub fn audio_high_pass(&self) -> bool {
+        self.config.audio_high_pass
+    }
+
+    /// Turn the output high-pass filter on or off; persists the config. Takes
+    /// effect on the next frame.
+    pub(crate) fn set_audio_high_pass(&mut self, on: bool) {
+        self.config.audio_high_pass = on;
+        self.apply_presentation();
+        self.persist_config();
+    }
+
+    /// The accuracy preset the renderer, high-pass and OAM-bug settings
+    /// currently match, or `None` for a custom mix.
+    pub fn accuracy_preset(&self) -> Option<AccuracyPreset> {
+        let c = &self.config;
+        AccuracyPreset::ALL.into_iter().find(|p| {
+            p.scanline_ppu() == c.scanline_ppu && p.audio_high_pass() == c.audio_high_pass && p.oam_bug() == c.oam_bug
+        })
+    }
+
+    /// Apply `preset`'s toggles; persists the config. Takes effect on the next
+    /// frame. The per-ROM scanline choices are kept and still add to it.
+    pub(crate) fn set_accuracy_preset(&mut self, preset: AccuracyPreset) {
+        self.config.scanline_ppu = preset.scanline_ppu();
+        self.config.audio_high_pass = preset.audio_high_pass();
+        self.config.oam_bug = preset.oam_bug();
+        self.apply_presentation();
+        self.apply_machine_config();
+        self.persist_config();
+    }
+
+    /// The per-channel mute/solo state.
+    pub fn channel_mix(&self) -> crate::action::ChannelMix {
+        self.config.channel_mix
+    }
+
+    /// Replace the per-channel mute/solo state; persists the config. Takes
+    /// effect on the next frame.
+    pub(crate) fn set_channel_mix(&mut self, mix: crate::action::ChannelMix) {
+        self.config.channel_mix = mix;
+        self.apply_presentation();
+        self.persist_config();
+    }
+
+    /// The selected audio output rate in Hz (what the next launch opens the
+    /// device at; see [`audio_sample_rate`](Self::audio_sample_rate) for the
+    /// live one).
+    pub fn sample_rate(&self) -> u32 {
+        self.config.sample_rate
+    }
+
+    /// Choose the audio output rate; persists the config. The open device
+    /// keeps its rate — the choice applies at the next launch. A rate outside
+    /// [`crate::action::SAMPLE_RATES`] is ignored.
+    pub(crate) fn set_sample_rate(&mut self, rate: u32) {
+        if crate::action::SAMPLE_RATES.contains(&rate) {
+            self.config.sample_rate = rate;
+            self.persist_config();
+        }
+    }
+
+    /// The selected audio device buffer in sample pairs, `0` = host default.
+    /// Platforms read it when they open the device.
+    pub fn audio_buffer_size(&self) -> u32 {
+        self.config.audio_buffer_size
+    }
+
+    /// Choose the audio device buffer size; persists the config. Applies at
+    /// the next launch. A size outside [`crate::action::AUDIO_BUFFER_SIZES`]
+    /// is ignored.
+    pub(crate) fn set_audio_buffer_size(&mut self, pairs: u32) {
+        if crate::action::AUDIO_BUFFER_SIZES.contains(&pairs) {
+            self.config.audio_buffer_size = pairs;
+            self.persist_config();
+        }
+    }
+
+    /// Emulated frames of audio to keep queued ahead of the device. Platforms
+    /// feed this to [`crate::pacing::Regulator::set_target_backlog`] each tick.
+    pub fn audio_latency_frames(&self) -> u32 {
+        self.config.audio_latency_frames
+    }
+
+    /// Set the queued-audio target, clamped to
+    /// [`crate::action::AUDIO_LATENCY_FRAMES`]; persists the config. Takes
+    /// effect on the platform's next tick.
+    pub(crate) fn set_audio_latency_frames(&mut self, frames: u32) {
+        let range = crate::action::AUDIO_LATENCY_FRAMES;
+        self.config.audio_latency_frames = frames.clamp(*range.start(), *range.end());
+        self.persist_config();
+    }
+
+    /// The preferred audio output device by name, `None` for the host default.
+    /// Platforms compare it against the device they have open each tick.
+    pub fn audio_device(&self) -> Option<&str> {
+        self.config.audio_device.as_deref()
+    }
+
+    /// Choose the audio output device (`None` = host default); persists the
+    /// config. The platform switches on its next tick, or when the device
+    /// next appears if it is not plugged in.
+    pub(crate) fn set_audio_device(&mut self, name: Option<String>) {
+        self.config.audio_device = name;
+        self.persist_config();
+    }
+
+    /// The folder battery saves go in, `None` to keep them next to each ROM.
+    /// Hosts that keep saves as files pass it to the cartridge on every load.
+    pub fn save_dir(&self) -> Option<&str> {
+        self.config.save_dir.as_deref()
+    }
+
+    /// Choose the save folder (`None` = next to each ROM); persists the
+    /// config. The ROM already running keeps the files it opened.
+    pub(crate) fn set_save_dir(&mut self, dir: Option<String>) {
+        self.config.save_dir = dir;
+        self.persist_config();
+    }
+
+    /// Where RTC carts keep their clock: this run's override if one is set,
+    /// else the saved choice. Hosts that keep saves as files pass it to the
+    /// cartridge on every load, like [`save_dir`](Self::save_dir).
+    pub fn rtc_format(&self) -> rustyboi_core_lib::cartridge::RtcFormat {
+        self.rtc_format_override.unwrap_or(self.config.rtc_format)
+    }
+
+    /// Whether audio recordings also capture per-channel stems.
+    pub fn record_stems(&self) -> bool {
+        self.config.record_stems
+    }
+
+    /// Choose whether audio recordings capture per-channel stems; persists the
+    /// config. Applies to the next recording started.
+    pub(crate) fn set_record_stems(&mut self, on: bool) {
+        self.config.record_stems = on;
+        self.persist_config();
+    }
+
+    /// Whether saved movies name their starting savestate by hash.
+    pub fn movie_anchor_by_hash(&self) -> bool {
+        self.config.movie_anchor_by_hash
+    }
+
+    /// Choose whether saved movies name their starting savestate by hash
+    /// instead of embedding it; persists the config.
+    pub(crate) fn set_movie_anchor_by_hash(&mut self, on: bool) {
+        self.config.movie_anchor_by_hash = on;
+        self.persist_config();
+    }
+
+    /// The desktop window size as a multiple of the native picture.
+    pub fn window_scale(&self) -> u8 {
+        self.config.window_scale.max(1)
+    }
+
+    /// Set the desktop window scale; persists the config. The desktop host
+    /// notices the change and refits its window.
+    pub fn set_window_scale(&mut self, scale: u8) {
+        self.config.window_scale = scale.max(1);
+        self.persist_config();
+    }
+
+    /// The upscale factor GIF captures are written at.
+    pub fn gif_scale(&self) -> u8 {
+        self.config.gif_scale.max(1)
+    }
+
+    /// Set the GIF capture upscale; persists the config. Applies to the next
+    /// capture started.
+    pub(crate) fn set_gif_scale(&mut self, scale: u8) {
+        self.config.gif_scale = scale.max(1);
+        self.persist_config();
+    }
+
+    /// The GIF capture length cap in seconds.
+    pub fn gif_max_seconds(&self) -> u32 {
+        self.config.gif_max_seconds.max(1)
+    }
+
+    /// Set the GIF capture length cap; persists the config. Applies to the
+    /// next capture started.
+    pub(crate) fn set_gif_max_seconds(&mut self, seconds: u32) {
+        self.config.gif_max_seconds = seconds.max(1);
+        self.persist_config();
+    }
+
+    /// The output devices the platform last saw, by name.
+    pub fn audio_devices(&self) -> &[String] {
+        &self.audio_devices
+    }
+
+    /// Replace the enumerated output device list (pushed by platforms that
+    /// can pick a device, re-polled so a replugged device shows up).
+    pub fn set_audio_devices(&mut self, names: Vec<String>) {
+        self.audio_devices = names;
+    }
+
     /// Set the fast-forward speed (GB frames per presented frame; `0` = uncapped)
     /// and persist it. If fast-forward is already engaged, re-derive the run mode
     /// so the new speed takes effect immediately.
@@ -350,6 +782,7 @@ impl Session {
         self.config.fast_forward_factor = factor;
         if matches!(self.mode, RunMode::FastForward(_)) {
             self.mode = RunMode::FastForward(self.config.ff_factor());
+            self.flash_speed();
         }
         self.persist_config();
     }
@@ -359,6 +792,65 @@ impl Session {
         self.config.fast_forward_factor
     }
 
+    /// Whether fast-forward plays silently.
+    pub fn fast_forward_muted(&self) -> bool {
+        self.config.fast_forward_muted
+    }
+
+    /// Silence (or restore) the audio while fast-forwarding; persists the config.
+    pub(crate) fn set_fast_forward_muted(&mut self, muted: bool) {
+        self.config.fast_forward_muted = muted;
+        self.persist_config();
+    }
+
+    /// Whether the host should present without vsync, and stop throttling
+    /// ticks to the display, right now: fast-forwarding with the setting on.
+    pub fn fast_forward_vsync_off(&self) -> bool {
+        self.config.fast_forward_vsync_off && self.is_fast_forward()
+    }
+
+    /// Drop (or keep) vsync while fast-forwarding; persists the config.
+    pub(crate) fn set_fast_forward_vsync_off(&mut self, off: bool) {
+        self.config.fast_forward_vsync_off = off;
+        self.persist_config();
+    }
+
+    /// Which emulated frames reach the display.
+    pub fn frame_skip(&self) -> FrameSkip {
+        self.config.frame_skip
+    }
+
+    /// Set the frame-skip policy; persists the config.
+    pub(crate) fn set_frame_skip(&mut self, skip: FrameSkip) {
+        self.config.frame_skip = skip;
+        self.persist_config();
+    }
+
+    /// Emulation speed in percent of real time. Platforms feed this to
+    /// [`crate::pacing::Regulator::set_speed`] each tick.
+    pub fn emulation_speed(&self) -> u32 {
+        self.config.emulation_speed.clamp(EMULATION_SPEED_MIN, EMULATION_SPEED_MAX)
+    }
+
+    /// Set the emulation speed (clamped to 25..=800%), announce it, and
+    /// persist the config. Public for the hosts' speed hotkeys.
+    pub fn set_emulation_speed(&mut self, percent: u32) {
+        self.config.emulation_speed = percent.clamp(EMULATION_SPEED_MIN, EMULATION_SPEED_MAX);
+        self.flash_speed();
+        self.persist_config();
+    }
+
+    /// Whether the shown frame is emulated one frame ahead.
+    pub fn run_ahead(&self) -> bool {
+        self.config.run_ahead
+    }
+
+    /// Turn run-ahead on or off; persists the config.
+    pub(crate) fn set_run_ahead(&mut self, on: bool) {
+        self.config.run_ahead = on;
+        self.persist_config();
+    }
+
     /// Set the frame letterboxing policy; persists the config.
     pub fn set_scaling_mode(&mut self, scaling: ScalingMode) {
         self.config.scaling = scaling;
@@ -370,6 +862,17 @@ impl Session {
         self.config.scaling
     }
 
+    /// Set the shape the frame is shown at; persists the config.
+    pub fn set_aspect_ratio(&mut self, aspect: AspectRatio) {
+        self.config.aspect_ratio = aspect;
+        self.persist_config();
+    }
+
+    /// Current shape the frame is shown at.
+    pub fn aspect_ratio(&self) -> AspectRatio {
+        self.config.aspect_ratio
+    }
+
     /// Choose the rendering backend; persists the config. The running window
     /// keeps its current surface/device — the choice applies at the next
     /// launch (see [`crate::action::GraphicsBackend`]).
@@ -390,11 +893,33 @@ impl Session {
         self.persist_config();
     }
 
+    /// Record where the debug windows are; persists the config.
+    pub(crate) fn set_debug_layout(&mut self, layout: crate::action::DebugLayout) {
+        self.config.debug_layout = layout;
+        self.persist_config();
+    }
+
+    /// Where the debug windows were left.
+    pub fn debug_layout(&self) -> &crate::action::DebugLayout {
+        &self.config.debug_layout
+    }
+
     /// The current rebindable input map.
     pub fn input_config(&self) -> &crate::input_config::InputConfig {
         &self.config.input
     }
 
+    /// Controllers the host can identify, for the keybind editor.
+    pub fn connected_pads(&self) -> &[crate::input_config::ConnectedPad] {
+        &self.connected_pads
+    }
+
+    /// Report the identifiable controllers connected now (host-owned; not
+    /// persisted).
+    pub fn set_connected_pads(&mut self, pads: Vec<crate::input_config::ConnectedPad>) {
+        self.connected_pads = pads;
+    }
+
     /// The full UI read-model for the menus, assembled from the session's own
     /// accessors.
     ///
@@ -407,8 +932,11 @@ impl Session {
     pub fn ui_state(&self) -> crate::action::SessionUiState {
         let cfg = &self.config;
         crate::action::SessionUiState {
+            recent_roms: self.recent_roms().to_vec(),
+            osd: self.osd().cloned(),
             hardware: self.hardware_choice(),
             palette: self.palette(),
+            custom_palettes: self.custom_palettes().to_vec(),
             gbc_dmg_palette: self.gbc_dmg_palette(),
             dmg_palette_active: self.dmg_palette_active(),
             sgb_palette: self.sgb_palette(),
@@ -423,25 +951,67 @@ impl Session {
             rewind_interval_frames: cfg.rewind.interval_frames,
             rewind_depth: cfg.rewind.depth,
             volume: self.volume(),
+            muted: self.is_muted(),
             scaling: self.scaling_mode(),
+            aspect_ratio: self.aspect_ratio(),
+            window_scale: self.window_scale(),
             graphics_backend: self.graphics_backend(),
             sgb_border: self.sgb_border(),
             paused: self.is_paused(),
             fast_forward: self.is_fast_forward(),
             fast_forward_factor: self.fast_forward_factor(),
+            fast_forward_muted: self.fast_forward_muted(),
+            fast_forward_vsync_off: self.config.fast_forward_vsync_off,
+            frame_skip: self.frame_skip(),
+            emulation_speed: self.emulation_speed(),
+            run_ahead: self.run_ahead(),
             touch_controls: self.touch_controls(),
             show_fps: self.show_fps(),
+            dot_matrix: self.dot_matrix(),
+            scanline_ppu: self.scanline_ppu(),
+            accuracy_preset: self.accuracy_preset(),
+            rtc_host_clock: self.rtc_host_clock(),
+            audio_high_pass: self.audio_high_pass(),
+            channel_mix: self.channel_mix(),
+            sample_rate: self.sample_rate(),
+            audio_buffer_size: self.audio_buffer_size(),
+            audio_latency_frames: self.audio_latency_frames(),
+            audio_device: self.config.audio_device.clone(),
+            audio_devices: self.audio_devices.clone(),
+            save_dir: self.config.save_dir.clone(),
+            record_stems: self.record_stems(),
+            recording_audio: self.is_recording_audio(),
+            gif_scale: self.gif_scale(),
+            gif_max_seconds: self.gif_max_seconds(),
+            recording_gif: self.is_recording_gif(),
+            recording_video: self.is_recording_video(),
+            auto_save_state: self.auto_save_state(),
+            pause_on_focus_loss: self.pause_on_focus_loss(),
             printer_attached: self.gb().printer_attached(),
+            link_partner: self.link_partner_running(),
+            link_input_partner: self.link_input_partner(),
+            netplay: self.netplay_active(),
             recording: self.is_recording(),
+            movie_anchor_by_hash: self.movie_anchor_by_hash(),
             replaying: self.is_playing(),
             slots: self.list_slots(),
+            slot_times: self.slot_times().to_vec(),
+            slot_thumbnails: self.slot_thumbnails().to_vec(),
+            resume_offer: self.resume_offer(),
+            can_undo_load: self.can_undo_load_state(),
+            recovery_snapshot: self.recovery_snapshot(),
             cheats: self.cheats().map(str::to_owned).collect(),
             fetched_cheats: self.fetched_cheats().to_vec(),
+            archive_choices: self.archive_choices().to_vec(),
             has_battery: self.has_battery(),
+            battery_size: self.battery_size(),
             has_rtc: self.has_rtc(),
             has_rom: self.gb().has_rom(),
             game_name: self.game_name().map(str::to_owned),
             input: self.input_config().clone(),
+            connected_pads: self.connected_pads().to_vec(),
+            debug_layout: self.debug_layout().clone(),
+            input_editor: self.input_editor_view(),
         }
     }
 
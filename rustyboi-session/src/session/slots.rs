@@ -1,11 +1,12 @@
 //! Numbered savestate slots and the quicksave pair.
 //!
-//! Slot blobs are `[SlotMeta header][machine state]` in the session's storage
-//! port, keyed by ROM id so states never collide across games.
+//! Slot blobs are `[SlotMeta header][.rustyboisave container]` in the session's
+//! storage port, keyed by ROM id so states never collide across games.
 
-use super::{Session, SessionError, SlotMeta, QUICK_SLOT};
+use super::{Session, SessionError, SlotMeta, AUTO_SLOT, QUICK_SLOT};
 use crate::audio::CaptureSink;
 use rustyboi_core_lib::gb::GB;
+use rustyboi_core_lib::savestate;
 
 impl Session {
     /// Storage key for a numbered slot, namespaced by ROM id so states never
@@ -18,29 +19,49 @@ impl Session {
         format!("state/{hex}/slot{slot}")
     }
 
-    /// Save the current machine state into `slot` via storage. `timestamp` is
+    /// Save the current machine state into `slot` via storage, in the same
+    /// versioned container as an exported `.rustyboisave`. `timestamp` is
     /// caller-supplied wall-clock (the session never reads a clock); it and the
     /// frame count are prepended as an 8+8 byte little-endian header so a load
     /// can surface [`SlotMeta`] without deserializing the whole machine.
     pub fn save_slot(&mut self, slot: u32, timestamp: u64) -> Result<(), SessionError> {
-        let state = self.gb.to_state_bytes().map_err(|e| SessionError::State(e.to_string()))?;
+        let state = self.state_file_bytes()?;
         let mut blob = Vec::with_capacity(16 + state.len());
         blob.extend_from_slice(&self.frame_count.to_le_bytes());
         blob.extend_from_slice(&timestamp.to_le_bytes());
         blob.extend_from_slice(&state);
         let key = self.slot_key(slot);
         self.ports.storage.write(&key, &blob)?;
+        match slot {
+            AUTO_SLOT => {}
+            QUICK_SLOT => self.flash_osd("Quicksaved"),
+            n => {
+                self.refresh_slot_times();
+                self.flash_osd(format!("State {n} saved"));
+            }
+        }
         Ok(())
     }
 
     /// Load `slot`, replacing the current machine. The audio sink is
-    /// re-installed (deserialization produces a fresh `GB` with no sink).
+    /// re-installed (deserialization produces a fresh `GB` with no sink). A
+    /// slot from an incompatible build fails with the container's message, and
+    /// one stamped with another ROM (a hand-copied storage entry) is refused.
+    /// The replaced machine is kept for [`undo_load_state`](Self::undo_load_state).
     pub fn load_slot(&mut self, slot: u32) -> Result<SlotMeta, SessionError> {
         let key = self.slot_key(slot);
         let blob = self.ports.storage.read(&key).ok_or(SessionError::NoState)?;
         let (meta, state) = Self::split_slot_blob(&blob)?;
-        self.restore_state(state)?;
+        let live_rom = (self.rom_id != [0; 32]).then_some(self.rom_id);
+        let gb = savestate::read_state(state, live_rom).map_err(|e| SessionError::State(e.to_string()))?;
+        self.back_up_before_load();
+        self.install_state(gb);
         self.frame_count = meta.frame_count;
+        match slot {
+            AUTO_SLOT => {}
+            QUICK_SLOT => self.flash_osd("Quickloaded"),
+            n => self.flash_osd(format!("State {n} loaded")),
+        }
         Ok(meta)
     }
 
@@ -51,8 +72,8 @@ impl Session {
     }
 
     /// All slot numbers with a saved state for the current ROM, ascending.
-    /// The reserved quick slot shares the key prefix but is not a numbered
-    /// slot, so it is excluded.
+    /// The reserved quick and auto slots share the key prefix but are not
+    /// numbered slots, so they are excluded.
     pub fn list_slots(&self) -> Vec<u32> {
         let mut hex = String::with_capacity(64);
         for b in self.rom_id {
@@ -65,12 +86,45 @@ impl Session {
             .list(&prefix)
             .into_iter()
             .filter_map(|k| k.rsplit("slot").next().and_then(|n| n.parse().ok()))
-            .filter(|&n| n != QUICK_SLOT)
+            .filter(|&n| !matches!(n, QUICK_SLOT | AUTO_SLOT))
             .collect();
         slots.sort_unstable();
         slots
     }
 
+    /// `(slot, timestamp)` for each filled numbered slot, ascending (the
+    /// Emulation menu's slot list). Cached; see `refresh_slot_times`.
+    pub fn slot_times(&self) -> &[(u32, u64)] {
+        &self.slot_times
+    }
+
+    /// `(slot, RGB888 thumbnail)` for each filled numbered slot whose state
+    /// carries a screenshot ([`savestate::THUMBNAIL_WIDTH`] x
+    /// [`savestate::THUMBNAIL_HEIGHT`]), ascending. Cached with the times.
+    pub fn slot_thumbnails(&self) -> &[(u32, Vec<u8>)] {
+        &self.slot_thumbnails
+    }
+
+    /// Re-read the numbered slots' headers (time and thumbnail) for the
+    /// current ROM.
+    pub(super) fn refresh_slot_times(&mut self) {
+        self.slot_times.clear();
+        self.slot_thumbnails.clear();
+        for n in self.list_slots() {
+            let Some(blob) = self.ports.storage.read(&self.slot_key(n)) else { continue };
+            let Ok((meta, state)) = Self::split_slot_blob(&blob) else {
+                self.slot_times.push((n, 0));
+                continue;
+            };
+            self.slot_times.push((n, meta.timestamp));
+            if let Ok((header, _)) = savestate::read_header(state)
+                && let Some(thumb) = header.thumbnail
+            {
+                self.slot_thumbnails.push((n, thumb));
+            }
+        }
+    }
+
     /// Quicksave to the reserved quick slot (`u32::MAX`).
     pub fn quicksave(&mut self, timestamp: u64) -> Result<(), SessionError> {
         self.save_slot(QUICK_SLOT, timestamp)
@@ -81,6 +135,96 @@ impl Session {
         self.load_slot(QUICK_SLOT)
     }
 
+    /// Whether a state load can be undone (gates Undo Load State).
+    pub fn can_undo_load_state(&self) -> bool {
+        self.load_backup.is_some()
+    }
+
+    /// Put back the machine the last state load replaced (slot, quickload or
+    /// state file). One level deep: the backup is consumed, and the next load
+    /// takes a fresh one.
+    pub fn undo_load_state(&mut self) -> Result<(), SessionError> {
+        let (state, frame_count) = self.load_backup.take().ok_or(SessionError::NoState)?;
+        self.restore_state(&state)?;
+        self.frame_count = frame_count;
+        self.flash_osd("Load undone");
+        Ok(())
+    }
+
+    /// Keep the live machine in memory ahead of a load that has already
+    /// decoded, so a failed load never clobbers the previous backup.
+    pub(super) fn back_up_before_load(&mut self) {
+        self.load_backup = self.gb.to_state_bytes().ok().map(|state| (state, self.frame_count));
+    }
+
+    /// Snapshot the machine to the reserved auto slot, called by the frontend
+    /// as the window closes or the page unloads. A no-op returning `false`
+    /// unless [`Config::auto_save_state`](crate::Config::auto_save_state) is on
+    /// and a ROM is loaded.
+    pub fn auto_save(&mut self, timestamp: u64) -> Result<bool, SessionError> {
+        if !self.config.auto_save_state || self.rom_id == [0; 32] {
+            return Ok(false);
+        }
+        self.save_slot(AUTO_SLOT, timestamp)?;
+        Ok(true)
+    }
+
+    /// The auto slot's timestamp while the resume offer for the just-loaded
+    /// ROM is open: set when a ROM with an auto-save loads (with the option
+    /// on), cleared by [`resume_auto_save`](Self::resume_auto_save),
+    /// [`dismiss_resume`](Self::dismiss_resume) or a restart.
+    pub fn resume_offer(&self) -> Option<u64> {
+        self.resume_offer
+    }
+
+    /// Accept the resume offer: load the auto slot over the fresh boot.
+    pub fn resume_auto_save(&mut self) -> Result<SlotMeta, SessionError> {
+        self.resume_offer = None;
+        self.load_slot(AUTO_SLOT)
+    }
+
+    /// Decline the resume offer. The auto slot stays until the next exit
+    /// overwrites it.
+    pub fn dismiss_resume(&mut self) {
+        self.resume_offer = None;
+    }
+
+    /// Open the resume offer when the option is on and the current ROM has an
+    /// auto-save.
+    pub(super) fn offer_resume(&mut self) {
+        self.resume_offer = if self.config.auto_save_state && self.rom_id != [0; 32] {
+            self.slot_meta(AUTO_SLOT).map(|m| m.timestamp)
+        } else {
+            None
+        };
+    }
+
+    /// A slot timestamp as `YYYY-MM-DD HH:MM` UTC for the slot menus, or
+    /// `None` when it is unknown (0). Lives here so every frontend's menus
+    /// format it the same way without a calendar crate.
+    pub fn slot_time_label(timestamp: u64) -> Option<String> {
+        if timestamp == 0 {
+            return None;
+        }
+        // Howard Hinnant's civil-from-days, for the proleptic Gregorian date.
+        let days = (timestamp / 86_400) as i64;
+        let secs = timestamp % 86_400;
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Some(format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+            secs / 3600,
+            secs % 3600 / 60
+        ))
+    }
+
     pub(super) fn split_slot_blob(blob: &[u8]) -> Result<(SlotMeta, &[u8]), SessionError> {
         if blob.len() < 16 {
             return Err(SessionError::State("slot blob truncated".into()));
@@ -97,16 +241,24 @@ impl Session {
     /// always resume the same ROM); without it the restored machine open-buses the
     /// wrong bank and bricks.
     pub(super) fn restore_state(&mut self, state: &[u8]) -> Result<(), SessionError> {
-        let mut gb = GB::from_state_bytes(state).map_err(|e| SessionError::State(e.to_string()))?;
+        let gb = GB::from_state_bytes(state).map_err(|e| SessionError::State(e.to_string()))?;
+        self.install_state(gb);
+        Ok(())
+    }
+
+    /// The tail of [`restore_state`](Self::restore_state) for a machine that was
+    /// already decoded (a slot's versioned container).
+    fn install_state(&mut self, mut gb: GB) {
         if gb.cartridge_needs_rom()
             && let Some(rom) = self.gb.detach_rom_bytes()
         {
             gb.reattach_rom(&rom);
         }
+        gb.carry_output_from(&self.gb);
         let _ = gb.enable_audio(Box::new(CaptureSink::new(self.audio_buf.clone())));
         self.cheats.apply_rom_patches(&mut gb);
         *self.gb = gb;
         self.apply_presentation();
-        Ok(())
+        self.apply_machine_config();
     }
 }
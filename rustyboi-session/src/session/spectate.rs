@@ -0,0 +1,81 @@
+//! Spectator streaming: the presented picture (and, optionally, the game's
+//! audio) goes out to remote viewers through a [`Broadcast`] port — a
+//! WebSocket server on desktop that a browser page watches.
+//!
+//! Wire format: one message per record, the first byte a tag. `F` is followed
+//! by a PNG of the presented frame, sent only when the picture changed since
+//! the last one. `A` is followed by the output sample rate (u32 LE) and the
+//! frame's samples as interleaved stereo i16 LE, before the host's volume or
+//! mute (viewers set their own).
+
+use super::Session;
+use crate::audio::push_pcm;
+use crate::ports::Broadcast;
+use crate::video::encode_rgb_png;
+use rustyboi_core_lib::gb::Frame;
+
+const TAG_FRAME: u8 = b'F';
+const TAG_AUDIO: u8 = b'A';
+
+pub(super) struct Spectating {
+    sink: Box<dyn Broadcast>,
+    audio: bool,
+    /// The last picture sent, so a still screen costs nothing.
+    last: Vec<u8>,
+}
+
+impl Session {
+    /// Start streaming to viewers through `sink`, with the game's audio when
+    /// `audio` is set. Replaces any stream already running.
+    pub fn start_spectating(&mut self, sink: Box<dyn Broadcast>, audio: bool) {
+        self.spectating = Some(Spectating { sink, audio, last: Vec::new() });
+    }
+
+    pub fn stop_spectating(&mut self) {
+        self.spectating = None;
+    }
+
+    pub fn spectating(&self) -> bool {
+        self.spectating.is_some()
+    }
+
+    /// Let the stream service its viewers. Hosts call this every tick, paused
+    /// or not: frames only go out when the picture changes, so without it a
+    /// viewer joining a still screen would wait for the next change.
+    pub fn poll_spectators(&mut self) {
+        if let Some(spectating) = self.spectating.as_mut() {
+            spectating.sink.poll();
+        }
+    }
+
+    /// Send one emulated frame: the picture as presented, and the samples the
+    /// core appended to the output buffer from `from` on. Called from
+    /// `step_one` right after the frame runs.
+    pub(super) fn broadcast_frame(&mut self, from: usize, frame: &Frame) {
+        let Some(spectating) = self.spectating.as_ref() else { return };
+        if spectating.audio {
+            let mut message = vec![TAG_AUDIO];
+            message.extend_from_slice(&self.audio_sample_rate.to_le_bytes());
+            {
+                let buf = self.audio_buf.lock().unwrap_or_else(|e| e.into_inner());
+                for &(l, r) in buf.get(from..).unwrap_or_default() {
+                    push_pcm(&mut message, l, r);
+                }
+            }
+            if message.len() > 5
+                && let Some(spectating) = self.spectating.as_mut()
+            {
+                spectating.sink.send(&message);
+            }
+        }
+        let (width, height, rgb) = self.presented_rgb(frame);
+        let Some(spectating) = self.spectating.as_mut() else { return };
+        if rgb == spectating.last {
+            return;
+        }
+        let mut message = vec![TAG_FRAME];
+        message.extend_from_slice(&encode_rgb_png(width, height, &rgb));
+        spectating.sink.send(&message);
+        spectating.last = rgb;
+    }
+}
@@ -1,17 +1,26 @@
-//! TAS: recording the live input stream and replaying a `.rbmovie`
-//! deterministically.
+//! TAS: recording the live input stream, replaying a `.rbmovie`
+//! deterministically, and the frame-by-frame input editor.
 
-use super::{Session, SessionError};
+use super::{Session, SessionError, QUICK_SLOT};
+use crate::action::InputEditorView;
 use crate::audio::CaptureSink;
+use crate::input::GbButton;
+use crate::rewind::{compress_snapshot, decompress_snapshot};
 use rustyboi_core_lib::gb::GB;
-use crate::tas::{Playback, Recording};
+use rustyboi_core_lib::input::ButtonState;
+use crate::tas::{InputEditor, Playback, Recording};
 use rustyboi_core_lib::movie::{self, Movie};
+use rustyboi_core_lib::savestate;
+
+/// Frames the editor view shows on each side of the cursor.
+const INPUT_EDITOR_ROWS: usize = 12;
 
 impl Session {
     /// Begin recording a power-on movie from the current input timeline. (For a
     /// re-record-from-here recording, use [`Session::start_recording_from_state`].)
     pub fn start_recording(&mut self) {
-        self.recording = Some(Recording::power_on(self.rom_id, self.config.hardware));
+        self.recording = Some(Recording::power_on(self.rom_id, self.config.hardware).with_oam_bug(self.config.oam_bug));
+        self.apply_machine_config();
     }
 
     /// Begin recording from the current machine state (re-record entry point):
@@ -19,8 +28,10 @@ impl Session {
     /// exactly here.
     pub(crate) fn start_recording_from_state(&mut self) -> Result<(), SessionError> {
         let state = self.gb.to_state_bytes().map_err(|e| SessionError::State(e.to_string()))?;
-        self.recording =
-            Some(Recording::from_savestate(self.rom_id, self.config.hardware, state));
+        self.recording = Some(
+            Recording::from_savestate(self.rom_id, self.config.hardware, state).with_oam_bug(self.config.oam_bug),
+        );
+        self.apply_machine_config();
         Ok(())
     }
 
@@ -32,12 +43,15 @@ impl Session {
     /// Stop recording and return the finished movie (or `None` if not
     /// recording).
     pub fn stop_recording(&mut self) -> Option<Movie> {
-        self.recording.take().map(|r| r.finish())
+        let movie = self.recording.take().map(|r| r.finish());
+        self.apply_machine_config();
+        movie
     }
 
     /// Begin read-only playback of `movie`. Rewinds the machine to the movie's
-    /// start (power-on: fresh boot; savestate: restore the blob) so the
-    /// replay is bit-identical to the recording. Fails on a ROM mismatch.
+    /// start (power-on: fresh boot; savestate: restore the blob, or the stored
+    /// state with the hash the movie names) so the replay is bit-identical to
+    /// the recording. Fails on a ROM mismatch or a missing anchor state.
     pub fn play_movie(&mut self, movie: &Movie) -> Result<(), SessionError> {
         if movie.rom_sha256 != self.rom_id {
             return Err(SessionError::RomMismatch);
@@ -45,12 +59,49 @@ impl Session {
         match &movie.start {
             movie::MovieStart::PowerOn => self.reboot_for_playback()?,
             movie::MovieStart::SaveState(blob) => self.restore_state(blob)?,
+            movie::MovieStart::SaveStateRef(hash) => {
+                let blob = self.find_movie_anchor(hash).ok_or(SessionError::AnchorMissing)?;
+                self.restore_state(&blob)?;
+            }
         }
         self.frame_count = 0;
         self.playback = Some(Playback::new(movie));
+        self.apply_machine_config();
+        Ok(())
+    }
+
+    /// With [`movie_anchor_by_hash`](Self::movie_anchor_by_hash) on, move a
+    /// finished movie's embedded start state into storage and leave the
+    /// movie naming it by hash.
+    pub(crate) fn keep_movie_anchor(&mut self, movie: &mut Movie) -> Result<(), SessionError> {
+        if !self.config.movie_anchor_by_hash {
+            return Ok(());
+        }
+        let Some(blob) = movie.reference_anchor() else {
+            return Ok(());
+        };
+        let key = anchor_key(&movie::sha256(&blob));
+        self.ports.storage.write(&key, &blob)?;
         Ok(())
     }
 
+    /// The savestate blob whose SHA-256 is `hash`: one kept by
+    /// [`keep_movie_anchor`](Self::keep_movie_anchor), else the machine in a
+    /// numbered slot or the quicksave, when it was saved by this build.
+    fn find_movie_anchor(&self, hash: &[u8; 32]) -> Option<Vec<u8>> {
+        if let Some(blob) = self.ports.storage.read(&anchor_key(hash))
+            && movie::sha256(&blob) == *hash
+        {
+            return Some(blob);
+        }
+        self.list_slots().into_iter().chain([QUICK_SLOT]).find_map(|slot| {
+            let blob = self.ports.storage.read(&self.slot_key(slot))?;
+            let (_, state) = Self::split_slot_blob(&blob).ok()?;
+            let (_, payload) = savestate::read_header(state).ok()?;
+            (movie::sha256(payload) == *hash).then(|| payload.to_vec())
+        })
+    }
+
     /// True while a movie is playing back.
     pub fn is_playing(&self) -> bool {
         self.playback.is_some()
@@ -59,6 +110,99 @@ impl Session {
     /// Stop playback, resuming live input.
     pub(crate) fn stop_playback(&mut self) {
         self.playback = None;
+        self.apply_machine_config();
+    }
+
+    /// Open the input editor on an empty timeline rooted at the machine as it
+    /// is now. Any movie playback stops: the editor owns the input from here.
+    pub fn open_input_editor(&mut self) -> Result<(), SessionError> {
+        let state = self.gb.to_state_bytes().map_err(|e| SessionError::State(e.to_string()))?;
+        self.playback = None;
+        self.input_editor = Some(InputEditor::new(compress_snapshot(state)));
+        self.apply_machine_config();
+        Ok(())
+    }
+
+    /// Close the input editor, leaving the machine where it is.
+    pub fn close_input_editor(&mut self) {
+        self.input_editor = None;
+        self.apply_machine_config();
+    }
+
+    /// The input editor's timeline, while it is open.
+    pub fn input_editor(&self) -> Option<&InputEditor> {
+        self.input_editor.as_ref()
+    }
+
+    /// The editor as the GUI shows it: the frames around the cursor, up to
+    /// the next new one.
+    pub fn input_editor_view(&self) -> Option<InputEditorView> {
+        let editor = self.input_editor.as_ref()?;
+        let cursor = editor.cursor();
+        let first = cursor.saturating_sub(INPUT_EDITOR_ROWS);
+        let last = (cursor + INPUT_EDITOR_ROWS).min(editor.len());
+        let rows = (first..=last)
+            .map(|frame| {
+                let input = editor.input(frame);
+                GbButton::ALL.map(|b| b.get(&input))
+            })
+            .collect();
+        let snapshots = editor.snapshot_frames().filter(|f| (first..=last).contains(f)).map(|f| f as u32).collect();
+        Some(InputEditorView { frame: cursor as u32, len: editor.len() as u32, first: first as u32, rows, snapshots })
+    }
+
+    /// Hold or release `button` on `frame` of the editor's timeline. A frame
+    /// that already ran is re-simulated from the greenzone, back to the frame
+    /// the machine was on.
+    pub(crate) fn edit_input(&mut self, frame: usize, button: GbButton, pressed: bool) -> Result<(), SessionError> {
+        let Some(editor) = self.input_editor.as_mut() else { return Ok(()) };
+        let cursor = editor.cursor();
+        if editor.set_button(frame, button, pressed) {
+            self.seek_input_editor(cursor)?;
+        }
+        Ok(())
+    }
+
+    /// Bring the machine to `frame` of the editor's timeline (clamped to its
+    /// end): restore the nearest greenzone snapshot and re-run the frames in
+    /// between. The last frame is queued as a frame advance so the host runs
+    /// and presents it like any other step.
+    pub(crate) fn seek_input_editor(&mut self, frame: usize) -> Result<(), SessionError> {
+        let Some(editor) = self.input_editor.as_mut() else { return Ok(()) };
+        let frame = frame.min(editor.len());
+        let last = frame.saturating_sub(1);
+        let (from, snapshot) = editor.rewind_to_snapshot(last);
+        let state = decompress_snapshot(snapshot)
+            .ok_or_else(|| SessionError::State("corrupt greenzone snapshot".into()))?;
+        self.restore_state(&state)?;
+        for _ in from..last {
+            self.step_one(ButtonState::default());
+        }
+        if frame > 0 {
+            self.frame_advance();
+        }
+        Ok(())
+    }
+
+    /// The editor's timeline as a movie from its root state, or `None` when
+    /// the editor is closed.
+    pub(crate) fn input_editor_movie(&self) -> Option<Movie> {
+        let editor = self.input_editor.as_ref()?;
+        let root = decompress_snapshot(editor.root())?;
+        let mut movie = editor.to_movie(self.rom_id, self.config.hardware, root);
+        movie.oam_bug = self.oam_bug_in_effect();
+        Some(movie)
+    }
+
+    /// Keep a greenzone snapshot when the editor's cursor reaches one.
+    /// Called from `step_one` after the frame runs.
+    pub(super) fn capture_greenzone(&mut self) {
+        if self.input_editor.as_ref().is_some_and(InputEditor::wants_snapshot)
+            && let Ok(state) = self.gb.to_state_bytes()
+            && let Some(editor) = self.input_editor.as_mut()
+        {
+            editor.keep_snapshot(compress_snapshot(state));
+        }
     }
 
     /// Rebuild a fresh, booted machine carrying the same cartridge for a
@@ -86,3 +230,12 @@ impl Session {
         Ok(())
     }
 }
+
+/// Storage key for a movie anchor state, by the hash movies name it with.
+fn anchor_key(hash: &[u8; 32]) -> String {
+    let mut hex = String::with_capacity(64);
+    for b in hash {
+        hex.push_str(&format!("{b:02x}"));
+    }
+    format!("anchor/{hex}")
+}
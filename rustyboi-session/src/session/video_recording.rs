@@ -0,0 +1,53 @@
+//! Video recording (Emulation → Record Video, `--record-video`): the
+//! presented frames and the heard mix, synchronized on emulated time.
+
+use super::Session;
+use crate::video::{MemorySink, VideoCapture, VideoRecording, VideoSink};
+use rustyboi_core_lib::gb::Frame;
+
+impl Session {
+    /// Begin capturing video and audio at the session's output rate, kept in
+    /// memory until [`stop_video_recording`](Self::stop_video_recording)
+    /// returns it. Restarts any capture already running.
+    pub fn start_video_recording(&mut self) {
+        self.start_video_recording_to(Box::new(MemorySink::new(self.audio_sample_rate)));
+    }
+
+    /// Begin capturing into `sink`, which receives each video slot and audio
+    /// chunk as the frames run (the desktop streams `--record-video` to disk
+    /// this way). Restarts any capture already running.
+    pub fn start_video_recording_to(&mut self, sink: Box<dyn VideoSink>) {
+        self.video_recording = Some(VideoRecording::new(self.audio_sample_rate, sink));
+    }
+
+    /// True while video is being captured.
+    pub fn is_recording_video(&self) -> bool {
+        self.video_recording.is_some()
+    }
+
+    /// Stop capturing and return the finished capture. `None` when nothing
+    /// was being captured, or when the capture streamed into a host sink.
+    pub fn stop_video_recording(&mut self) -> Option<VideoCapture> {
+        self.video_recording.take().and_then(VideoRecording::finish)
+    }
+
+    /// Feed one emulated frame to the capture: the `dots` of emulated time
+    /// it took, the samples the core appended to the output buffer from
+    /// `from` on, and the frame as presented (with the SGB border when it
+    /// is shown), encoded only when a video slot is due. Called from
+    /// `step_one` right after the frame runs.
+    pub(super) fn capture_video_frame(&mut self, dots: u64, from: usize, frame: &Frame) {
+        let Some(rec) = self.video_recording.as_mut() else { return };
+        {
+            let buf = self.audio_buf.lock().unwrap_or_else(|e| e.into_inner());
+            rec.push_audio(dots, buf.get(from..).unwrap_or_default());
+        }
+        if rec.frames_due() == 0 {
+            return;
+        }
+        let (width, height, rgb) = self.presented_rgb(frame);
+        if let Some(rec) = self.video_recording.as_mut() {
+            rec.push_frame(width, height, rgb);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! The JSON form of a `.rustyboisave` file (File → Export → Save State as
+//! JSON, `harness state-json`): for reading a state and diffing two of them
+//! as text, never for loading one back.
+//!
+//! Schema, version [`STATE_JSON_VERSION`]:
+//!
+//! ```text
+//! {
+//!   "schema": "rustyboi-state",
+//!   "schema_version": 1,
+//!   "format_version": <u16>,     // the container's savestate::STATE_VERSION
+//!   "core_version": "<string>",  // the core build that wrote the state
+//!   "rom_sha256": "<64 hex>",    // all zeros when the ROM was unknown
+//!   "machine": { ... }           // the machine, field for field
+//! }
+//! ```
+//!
+//! `schema_version` covers the envelope: it is bumped whenever a key is
+//! renamed, removed, or changes meaning (an added key does not bump it). The
+//! shape of `machine` is the serde layout of the machine itself and follows
+//! `format_version`, so a tool comparing two states checks that first. The
+//! header's thumbnail is left out: it is derived from the framebuffer, which
+//! `machine` already carries.
+
+use rustyboi_core_lib::savestate;
+
+/// The `schema` value every export carries.
+pub const STATE_JSON_SCHEMA: &str = "rustyboi-state";
+
+/// The envelope version; see the module docs for when it changes.
+pub const STATE_JSON_VERSION: u32 = 1;
+
+/// Render a `.rustyboisave` file as pretty-printed JSON, upgrading older
+/// formats the way a load would.
+pub fn state_file_to_json(bytes: &[u8]) -> Result<String, String> {
+    let (header, _) = savestate::read_header(bytes).map_err(|e| e.to_string())?;
+    let gb = savestate::read_state(bytes, None).map_err(|e| e.to_string())?;
+    let rom_sha256: String = header.rom_sha256.iter().map(|b| format!("{b:02x}")).collect();
+    let doc = serde_json::json!({
+        "schema": STATE_JSON_SCHEMA,
+        "schema_version": STATE_JSON_VERSION,
+        "format_version": header.version,
+        "core_version": header.core_version,
+        "rom_sha256": rom_sha256,
+        "machine": gb,
+    });
+    serde_json::to_string_pretty(&doc).map(|json| json + "\n").map_err(|e| format!("encode JSON: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyboi_core_lib::gb::GB;
+
+    #[test]
+    fn the_envelope_names_its_schema_and_the_state_format() {
+        let mut gb = GB::new(crate::config::Config::default().hardware);
+        let file = savestate::write_state(&mut gb, [0xab; 32]).unwrap();
+        let json = state_file_to_json(&file).unwrap();
+        let doc: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(doc["schema"], STATE_JSON_SCHEMA);
+        assert_eq!(doc["schema_version"], STATE_JSON_VERSION);
+        assert_eq!(doc["format_version"], savestate::STATE_VERSION);
+        assert_eq!(doc["rom_sha256"], "ab".repeat(32));
+        assert!(doc["machine"].is_object());
+    }
+
+    #[test]
+    fn a_file_that_is_not_a_state_is_refused() {
+        assert!(state_file_to_json(b"not a state").is_err());
+    }
+}
@@ -8,6 +8,9 @@
 //! replay/determinism harness consumes — so a recorded movie replays
 //! bit-identically via `movie::replay`.
 
+use std::collections::BTreeMap;
+
+use crate::input::GbButton;
 use rustyboi_core_lib::gb::Hardware;
 use rustyboi_core_lib::input::ButtonState;
 use rustyboi_core_lib::movie::{Movie, MovieMeta, MovieStart};
@@ -18,6 +21,7 @@ use rustyboi_core_lib::movie::{Movie, MovieMeta, MovieStart};
 pub struct Recording {
     rom_sha256: [u8; 32],
     hardware: Hardware,
+    oam_bug: bool,
     start: MovieStart,
     inputs: Vec<ButtonState>,
     meta: MovieMeta,
@@ -30,6 +34,7 @@ impl Recording {
         Recording {
             rom_sha256,
             hardware,
+            oam_bug: true,
             start: MovieStart::PowerOn,
             inputs: Vec::new(),
             meta: MovieMeta::default(),
@@ -43,6 +48,7 @@ impl Recording {
         Recording {
             rom_sha256,
             hardware,
+            oam_bug: true,
             start: MovieStart::SaveState(savestate),
             inputs: Vec::new(),
             meta: MovieMeta::default(),
@@ -56,6 +62,17 @@ impl Recording {
         self
     }
 
+    /// Record whether the OAM corruption bug is emulated (on unless set).
+    pub fn with_oam_bug(mut self, on: bool) -> Self {
+        self.oam_bug = on;
+        self
+    }
+
+    /// Whether the recorded machine emulates the OAM corruption bug.
+    pub fn oam_bug(&self) -> bool {
+        self.oam_bug
+    }
+
     /// Log the input that was live for the frame just produced. Called by the
     /// session once per emulated frame while recording.
     pub fn push_input(&mut self, input: ButtonState) {
@@ -74,6 +91,7 @@ impl Recording {
         Movie {
             rom_sha256: self.rom_sha256,
             hardware: self.hardware,
+            oam_bug: self.oam_bug,
             start: self.start,
             inputs: self.inputs,
             meta: self.meta,
@@ -87,6 +105,7 @@ impl Recording {
 #[derive(Clone, Debug)]
 pub struct Playback {
     inputs: Vec<ButtonState>,
+    oam_bug: bool,
     cursor: usize,
 }
 
@@ -94,7 +113,7 @@ impl Playback {
     /// Start playing a movie's input timeline. The caller is responsible for
     /// having brought the `GB` to the movie's start condition.
     pub fn new(movie: &Movie) -> Self {
-        Playback { inputs: movie.inputs.clone(), cursor: 0 }
+        Playback { inputs: movie.inputs.clone(), oam_bug: movie.oam_bug, cursor: 0 }
     }
 
     /// The next input to feed, advancing the cursor. `None` once the movie is
@@ -107,6 +126,11 @@ impl Playback {
         input
     }
 
+    /// Whether the movie was recorded with the OAM corruption bug emulated.
+    pub fn oam_bug(&self) -> bool {
+        self.oam_bug
+    }
+
     /// True once every recorded frame has been played back.
     pub fn finished(&self) -> bool {
         self.cursor >= self.inputs.len()
@@ -127,6 +151,112 @@ impl Playback {
     }
 }
 
+/// How often (in frames) the input editor keeps a greenzone snapshot.
+pub const GREENZONE_INTERVAL: usize = 30;
+
+/// The TAS input editor's timeline: one input per frame from a root
+/// savestate, a cursor at the frame the machine is on, and the "greenzone" —
+/// compressed snapshots every [`GREENZONE_INTERVAL`] frames of the timeline
+/// as it stands, so changing a past input only re-simulates from the nearest
+/// snapshot before it. Snapshot `k` is the machine before frame `k` ran.
+#[derive(Clone, Debug)]
+pub struct InputEditor {
+    inputs: Vec<ButtonState>,
+    cursor: usize,
+    greenzone: BTreeMap<usize, Vec<u8>>,
+}
+
+impl InputEditor {
+    /// Start an empty timeline at `root` (a compressed snapshot of the
+    /// machine as it is now).
+    pub fn new(root: Vec<u8>) -> Self {
+        InputEditor { inputs: Vec::new(), cursor: 0, greenzone: BTreeMap::from([(0, root)]) }
+    }
+
+    /// The frame the machine is on: the next one to run.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Frames in the timeline.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// The input for `frame` (nothing held past the end).
+    pub fn input(&self, frame: usize) -> ButtonState {
+        self.inputs.get(frame).copied().unwrap_or_default()
+    }
+
+    /// Hold or release `button` on `frame`, growing the timeline to reach
+    /// it. Returns true when the frame has already run, so the machine must
+    /// be re-simulated; the snapshots after it are dropped as stale.
+    pub fn set_button(&mut self, frame: usize, button: GbButton, pressed: bool) -> bool {
+        if self.inputs.len() <= frame {
+            self.inputs.resize(frame + 1, ButtonState::default());
+        }
+        button.set(&mut self.inputs[frame], pressed);
+        let past = frame < self.cursor;
+        if past {
+            self.greenzone.retain(|&k, _| k <= frame);
+        }
+        past
+    }
+
+    /// The input for the frame at the cursor, stepping past it (appending it
+    /// when the cursor is at the end). Called once per emulated frame.
+    pub fn advance(&mut self) -> ButtonState {
+        let input = self.input(self.cursor);
+        if self.cursor == self.inputs.len() {
+            self.inputs.push(input);
+        }
+        self.cursor += 1;
+        input
+    }
+
+    /// Whether the frame now at the cursor should be snapshotted.
+    pub fn wants_snapshot(&self) -> bool {
+        self.cursor.is_multiple_of(GREENZONE_INTERVAL) && !self.greenzone.contains_key(&self.cursor)
+    }
+
+    /// Keep `snapshot` (compressed) as the machine at the cursor.
+    pub fn keep_snapshot(&mut self, snapshot: Vec<u8>) {
+        self.greenzone.insert(self.cursor, snapshot);
+    }
+
+    /// The latest snapshot at or before `frame` and the frame it holds, with
+    /// the cursor moved there (the caller restores it and steps on).
+    pub fn rewind_to_snapshot(&mut self, frame: usize) -> (usize, &[u8]) {
+        let (&at, snapshot) = self.greenzone.range(..=frame).next_back().expect("the root snapshot is never dropped");
+        self.cursor = at;
+        (at, snapshot)
+    }
+
+    /// Frames with a greenzone snapshot, ascending.
+    pub fn snapshot_frames(&self) -> impl Iterator<Item = usize> + '_ {
+        self.greenzone.keys().copied()
+    }
+
+    /// The timeline as a movie starting from the root snapshot (decompressed
+    /// by the caller).
+    pub fn to_movie(&self, rom_sha256: [u8; 32], hardware: Hardware, root: Vec<u8>) -> Movie {
+        let mut rec = Recording::from_savestate(rom_sha256, hardware, root);
+        for &input in &self.inputs {
+            rec.push_input(input);
+        }
+        rec.finish()
+    }
+
+    /// The root snapshot (compressed).
+    pub fn root(&self) -> &[u8] {
+        &self.greenzone[&0]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,7 +281,7 @@ mod tests {
         let movie = r.finish();
         match movie.start {
             MovieStart::SaveState(bytes) => assert_eq!(bytes, blob),
-            MovieStart::PowerOn => panic!("expected a savestate start"),
+            _ => panic!("expected a savestate start"),
         }
     }
 
@@ -217,6 +347,38 @@ mod tests {
         assert_eq!(pb.position(), 2);
     }
 
+    #[test]
+    fn editor_advances_through_and_past_the_timeline() {
+        let mut ed = InputEditor::new(vec![0]);
+        ed.set_button(1, GbButton::A, true);
+        assert_eq!(ed.len(), 2, "editing ahead grows the timeline");
+        assert_eq!(ed.advance(), ButtonState::default());
+        assert_eq!(ed.advance(), pressed_a());
+        assert_eq!(ed.advance(), ButtonState::default());
+        assert_eq!((ed.cursor(), ed.len()), (3, 3), "running past the end appends frames");
+    }
+
+    #[test]
+    fn editing_a_past_frame_drops_the_later_greenzone() {
+        let mut ed = InputEditor::new(vec![0]);
+        for _ in 0..(GREENZONE_INTERVAL * 2) {
+            ed.advance();
+            if ed.wants_snapshot() {
+                ed.keep_snapshot(vec![ed.cursor() as u8]);
+            }
+        }
+        let at = |n| n * GREENZONE_INTERVAL;
+        assert_eq!(ed.snapshot_frames().collect::<Vec<_>>(), [0, at(1), at(2)]);
+
+        assert!(!ed.set_button(at(2), GbButton::B, true), "the frame at the cursor has not run");
+        assert!(ed.set_button(at(1) + 3, GbButton::B, true), "a frame behind the cursor has");
+        assert_eq!(ed.snapshot_frames().collect::<Vec<_>>(), [0, at(1)]);
+
+        let (from, snapshot) = ed.rewind_to_snapshot(at(2) - 1);
+        assert_eq!((from, snapshot), (at(1), &[at(1) as u8][..]));
+        assert_eq!(ed.cursor(), at(1));
+    }
+
     #[test]
     fn playback_of_zero_input_movie_is_empty_and_finished() {
         let movie = movie_with_inputs(vec![]);
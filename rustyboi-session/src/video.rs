@@ -0,0 +1,375 @@
+//! Video recording (Emulation → Record Video, `--record-video`): the
+//! presented frames plus the heard mix, kept in step on the emulated clock
+//! rather than on whatever the host ran at.
+//!
+//! Both tracks are laid against the dot clock (4 194 304 Hz, 70 224 dots per
+//! LCD frame). The video track has one slot per 70 224 dots, each showing the
+//! first frame presented after the slot opens, so a long frame (LCD off, a
+//! STOP) repeats and a short one (the first after the LCD comes back on) can
+//! be dropped. The audio track is held to the same clock: once the
+//! resampler's output drifts more than a millisecond from the sample count
+//! the elapsed dots call for, it is padded with the last sample or trimmed
+//! back. Muxed at [`VideoCapture::FRAME_RATE`], the two stay in sync however
+//! long the capture runs.
+//!
+//! The recording only keeps the clock; the tracks go to a [`VideoSink`] as
+//! they are produced. The GUI toggle collects them in memory for its zip
+//! export ([`VideoCapture`]); the desktop's `--record-video` streams them to
+//! ffmpeg or disk from a worker thread.
+
+use crate::audio::{push_pcm, wav};
+use rustyboi_core_lib::checksum::crc32;
+
+/// Dots per video slot: one LCD frame.
+const DOTS_PER_FRAME: u64 = 70_224;
+/// The dot clock, which both tracks are timed against.
+const DOTS_PER_SECOND: u64 = 4_194_304;
+
+/// Where a capture's tracks go as they are recorded.
+pub trait VideoSink: crate::ports::MaybeSend {
+    /// `rgb` (8-bit RGB, `width`x`height`) fills the next `count` video slots.
+    fn frame(&mut self, width: u32, height: u32, rgb: Vec<u8>, count: usize);
+    /// The previous frame again for the next `count` slots.
+    fn repeat(&mut self, count: usize);
+    /// More of the audio track: interleaved 16-bit little-endian stereo.
+    fn audio(&mut self, pcm: &[u8]);
+    /// Close the capture. A sink that kept it in memory hands it back; one
+    /// that streamed it out finishes on its own side and returns `None`.
+    fn finish(self: Box<Self>) -> Option<VideoCapture> {
+        None
+    }
+}
+
+/// A capture in progress.
+pub(crate) struct VideoRecording {
+    sample_rate: u32,
+    /// Emulated time captured so far.
+    dots: u64,
+    /// Video slots filled so far.
+    slots: u64,
+    /// Audio samples handed to the sink so far.
+    samples: u64,
+    /// The size of the first frame; the sequence keeps it throughout.
+    size: Option<(u32, u32)>,
+    /// The newest sample, repeated when the audio track needs padding.
+    last: (f32, f32),
+    sink: Box<dyn VideoSink>,
+}
+
+impl VideoRecording {
+    pub(crate) fn new(sample_rate: u32, sink: Box<dyn VideoSink>) -> Self {
+        VideoRecording { sample_rate, dots: 0, slots: 0, samples: 0, size: None, last: (0.0, 0.0), sink }
+    }
+
+    /// Advance the capture by one emulated frame: `dots` of emulated time
+    /// and the samples the core produced over it. Follow with
+    /// [`Self::push_frame`] when [`Self::frames_due`] is non-zero.
+    pub(crate) fn push_audio(&mut self, dots: u64, samples: &[(f32, f32)]) {
+        // A frame never runs past its cycle cap; anything near a second is
+        // a clock discontinuity, not elapsed time.
+        self.dots += dots.min(DOTS_PER_SECOND);
+        if let Some(&last) = samples.last() {
+            self.last = last;
+        }
+        let want = self.dots * u64::from(self.sample_rate) / DOTS_PER_SECOND;
+        let slack = u64::from(self.sample_rate / 1000);
+        // Only this frame's samples are still in hand, so a trim drops from
+        // them; correcting every frame keeps the excess within that.
+        let mut take = samples.len() as u64;
+        if self.samples + take > want + slack {
+            take = want.saturating_sub(self.samples);
+        }
+        let mut pcm = Vec::with_capacity(samples.len() * 4);
+        for &(l, r) in &samples[..take as usize] {
+            push_pcm(&mut pcm, l, r);
+        }
+        self.samples += take;
+        if self.samples + slack < want {
+            for _ in self.samples..want {
+                push_pcm(&mut pcm, self.last.0, self.last.1);
+            }
+            self.samples = want;
+        }
+        self.sink.audio(&pcm);
+    }
+
+    /// Video slots opened since the last frame was pushed: how many times
+    /// the frame just presented must appear. Zero drops it.
+    pub(crate) fn frames_due(&self) -> usize {
+        self.dots.div_ceil(DOTS_PER_FRAME).saturating_sub(self.slots) as usize
+    }
+
+    /// Fill the open video slots with the presented frame. A frame of a
+    /// different size (the SGB border toggled mid-capture) repeats the
+    /// previous one instead, as the sequence cannot change size.
+    pub(crate) fn push_frame(&mut self, width: u32, height: u32, rgb: Vec<u8>) {
+        let due = self.frames_due();
+        if due == 0 {
+            return;
+        }
+        self.slots += due as u64;
+        match self.size {
+            Some(size) if size != (width, height) => self.sink.repeat(due),
+            _ => {
+                self.size = Some((width, height));
+                self.sink.frame(width, height, rgb, due);
+            }
+        }
+    }
+
+    /// Close the capture, returning it when the sink kept it in memory.
+    pub(crate) fn finish(self) -> Option<VideoCapture> {
+        self.sink.finish()
+    }
+}
+
+/// The in-memory [`VideoSink`] behind the GUI toggle: each slot as a PNG and
+/// the audio as PCM, until [`VideoSink::finish`] wraps them up.
+pub(crate) struct MemorySink {
+    sample_rate: u32,
+    frames: Vec<Vec<u8>>,
+    size: (u32, u32),
+    pcm: Vec<u8>,
+}
+
+impl MemorySink {
+    pub(crate) fn new(sample_rate: u32) -> Self {
+        MemorySink { sample_rate, frames: Vec::new(), size: (0, 0), pcm: Vec::new() }
+    }
+}
+
+impl VideoSink for MemorySink {
+    fn frame(&mut self, width: u32, height: u32, rgb: Vec<u8>, count: usize) {
+        self.size = (width, height);
+        let png = encode_rgb_png(width, height, &rgb);
+        self.frames.extend(std::iter::repeat_n(png, count));
+    }
+
+    fn repeat(&mut self, count: usize) {
+        if let Some(prev) = self.frames.last().cloned() {
+            self.frames.extend(std::iter::repeat_n(prev, count));
+        }
+    }
+
+    fn audio(&mut self, pcm: &[u8]) {
+        self.pcm.extend_from_slice(pcm);
+    }
+
+    fn finish(self: Box<Self>) -> Option<VideoCapture> {
+        let (width, height) = self.size;
+        Some(VideoCapture { frames: self.frames, wav: wav(self.sample_rate, &self.pcm), width, height })
+    }
+}
+
+/// A finished in-memory video capture: the frame sequence and the audio
+/// track, ready to be zipped ([`Self::to_zip`]).
+pub struct VideoCapture {
+    frames: Vec<Vec<u8>>,
+    wav: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl VideoCapture {
+    /// The video frame rate as a fraction (~59.73 fps), in the form ffmpeg's
+    /// `-framerate` takes.
+    pub const FRAME_RATE: (u64, u64) = (DOTS_PER_SECOND, DOTS_PER_FRAME);
+
+    /// The frames in order, one PNG each.
+    pub fn frames(&self) -> &[Vec<u8>] {
+        &self.frames
+    }
+
+    /// The audio track as a WAV file, exactly as long as the frames.
+    pub fn wav(&self) -> &[u8] {
+        &self.wav
+    }
+
+    /// Frame size in pixels; `(0, 0)` for a capture with no frames.
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Captured length in seconds.
+    pub fn seconds(&self) -> f64 {
+        Self::slots_to_seconds(self.frames.len() as u64)
+    }
+
+    /// The length in seconds of `slots` video slots.
+    pub fn slots_to_seconds(slots: u64) -> f64 {
+        (slots * DOTS_PER_FRAME) as f64 / DOTS_PER_SECOND as f64
+    }
+
+    /// The `ffmpeg.txt` note shipped in a capture zip: the command that muxes
+    /// `frames/%06d.png` and `audio.wav` into a video.
+    pub fn mux_note() -> String {
+        let (num, den) = Self::FRAME_RATE;
+        format!(
+            "ffmpeg -framerate {num}/{den} -i frames/%06d.png -i audio.wav \
+             -vf scale=iw*4:ih*4:flags=neighbor -pix_fmt yuv420p video.mp4\n"
+        )
+    }
+
+    /// The capture as one zip: `frames/000000.png`…, `audio.wav`, and an
+    /// `ffmpeg.txt` with the command that muxes them.
+    pub fn to_zip(&self) -> Vec<u8> {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+        let note = Self::mux_note();
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        // PNGs and PCM gain nothing from a second deflate.
+        let stored = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        let files = self
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(i, png)| (format!("frames/{i:06}.png"), png.as_slice()))
+            .chain([("audio.wav".to_string(), self.wav.as_slice()), ("ffmpeg.txt".to_string(), note.as_bytes())]);
+        for (name, bytes) in files {
+            // As with the stems zip: an in-memory writer only fails on a
+            // zip64-sized entry; drop that entry rather than the export.
+            if zip.start_file(name, stored).is_ok() {
+                let _ = zip.write_all(bytes);
+            }
+        }
+        zip.finish().map(|c| c.into_inner()).unwrap_or_default()
+    }
+}
+
+/// Encode 8-bit RGB as a PNG: filter 0 rows in one zlib stream.
+pub fn encode_rgb_png(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 3;
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for row in rgb.chunks(stride.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]); // 8-bit truecolour
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    write_chunk(&mut png, b"IHDR", &ihdr);
+    write_chunk(&mut png, b"IDAT", &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 44_100;
+
+    fn frame(shade: u8) -> Vec<u8> {
+        vec![shade; 160 * 144 * 3]
+    }
+
+    fn samples(n: usize) -> Vec<(f32, f32)> {
+        vec![(0.25, -0.25); n]
+    }
+
+    fn recording() -> VideoRecording {
+        VideoRecording::new(RATE, Box::new(MemorySink::new(RATE)))
+    }
+
+    fn pcm_len(capture: &VideoCapture) -> usize {
+        (capture.wav().len() - 44) / 4
+    }
+
+    /// Steady frames fill one slot each and the audio keeps the sample count
+    /// the dots call for.
+    #[test]
+    fn steady_frames_fill_one_slot_each() {
+        let mut rec = recording();
+        for i in 0..600 {
+            // The resampler alternates 738/739 pairs per frame at this rate.
+            rec.push_audio(DOTS_PER_FRAME, &samples(738 + i % 2));
+            assert_eq!(rec.frames_due(), 1);
+            rec.push_frame(160, 144, frame(i as u8));
+        }
+        let capture = rec.finish().unwrap();
+        assert_eq!(capture.frames().len(), 600);
+        let want = 600 * DOTS_PER_FRAME * u64::from(RATE) / DOTS_PER_SECOND;
+        assert!(pcm_len(&capture).abs_diff(want as usize) <= (RATE / 1000) as usize);
+    }
+
+    /// A frame that ran long (LCD off) repeats; one that ran short is
+    /// dropped once the slot it would fill has already been taken.
+    #[test]
+    fn long_frames_repeat_and_short_frames_drop() {
+        let mut rec = recording();
+        rec.push_audio(DOTS_PER_FRAME * 3, &[]);
+        assert_eq!(rec.frames_due(), 3);
+        rec.push_frame(160, 144, frame(1));
+        rec.push_audio(DOTS_PER_FRAME / 2, &[]);
+        assert_eq!(rec.frames_due(), 1);
+        rec.push_frame(160, 144, frame(2));
+        rec.push_audio(DOTS_PER_FRAME / 2, &[]);
+        assert_eq!(rec.frames_due(), 0, "the half frame's slot is already filled");
+        let frames = rec.finish().unwrap().frames;
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0], frames[2]);
+        assert_ne!(frames[2], frames[3]);
+    }
+
+    /// Audio running fast or slow against the emulated clock is trimmed or
+    /// padded back within a millisecond.
+    #[test]
+    fn audio_drift_is_corrected_against_the_dot_clock() {
+        let mut rec = recording();
+        for _ in 0..120 {
+            rec.push_audio(DOTS_PER_FRAME, &samples(800));
+        }
+        let want = (120 * DOTS_PER_FRAME * u64::from(RATE) / DOTS_PER_SECOND) as usize;
+        let capture = rec.finish().unwrap();
+        assert!(pcm_len(&capture).abs_diff(want) <= (RATE / 1000) as usize, "fast audio is trimmed");
+
+        let mut rec = recording();
+        for _ in 0..120 {
+            rec.push_audio(DOTS_PER_FRAME, &samples(600));
+        }
+        let capture = rec.finish().unwrap();
+        assert!(pcm_len(&capture).abs_diff(want) <= (RATE / 1000) as usize, "slow audio is padded");
+        let wav = capture.wav();
+        assert_eq!(&wav[wav.len() - 4..], &wav[44..48], "padding repeats the last sample");
+    }
+
+    /// A size change mid-capture keeps the sequence at its first size.
+    #[test]
+    fn a_size_change_repeats_the_previous_frame() {
+        let mut rec = recording();
+        rec.push_audio(DOTS_PER_FRAME, &[]);
+        rec.push_frame(160, 144, frame(9));
+        rec.push_audio(DOTS_PER_FRAME, &[]);
+        rec.push_frame(256, 224, vec![0; 256 * 224 * 3]);
+        let capture = rec.finish().unwrap();
+        assert_eq!(capture.size(), (160, 144));
+        assert_eq!(capture.frames()[0], capture.frames()[1]);
+    }
+
+    /// The PNG is well-formed: signature, a truecolour IHDR, and an IDAT
+    /// that inflates back to the filtered rows.
+    #[test]
+    fn frames_are_truecolour_pngs() {
+        let png = encode_rgb_png(2, 1, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..29], &[0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+        assert_eq!(u32::from_be_bytes(png[29..33].try_into().unwrap()), crc32(&png[12..29]));
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(&png[37..41], b"IDAT");
+        let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&png[41..41 + idat_len]).unwrap();
+        assert_eq!(raw, [0, 1, 2, 3, 4, 5, 6]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+    }
+}
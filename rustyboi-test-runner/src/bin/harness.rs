@@ -34,6 +34,12 @@
 //!       Drive a ROM headlessly with a printer on the link port and dump
 //!       screen frames plus every captured print as PNGs.
 //!
+//!   harness state-json <savestate.rustyboisave> [--out FILE]
+//!       Decode a save state (walking older formats through the upgrader
+//!       chain) and print its header and the whole machine as pretty JSON in
+//!       the versioned schema of `rustyboi_session::state_json`, so two states
+//!       can be compared with any text diff.
+//!
 //! Input SCRIPT is the shared `frame:BUTTONS` DSL (see shared/script.rs).
 //! Everything is fully deterministic (frame-keyed input, no wall clock), so a
 //! script is a reproducible repro. Unlike the old standalone bins, unknown
@@ -42,6 +48,7 @@
 use rustyboi_core_lib::cartridge::Cartridge;
 use rustyboi_core_lib::gb::{GB, Hardware};
 use rustyboi_core_lib::input::ButtonState;
+use rustyboi_session::state_json;
 use std::path::PathBuf;
 use std::process::ExitCode;
 
@@ -59,6 +66,7 @@ const USAGE_CAMERA: &str = "harness camera-drive --rom <path[.zip]> [--frames N]
                             [--out DIR] [--screens N] [--shots F1,F2,...]";
 const USAGE_PRINTER: &str = "harness printer-drive --rom <path[.zip]> [--mode dmg|cgb] [--frames N] \
                              [--input SCRIPT] [--out DIR] [--screens N]";
+const USAGE_STATE_JSON: &str = "harness state-json <savestate.rustyboisave> [--out FILE]";
 
 fn main() -> ExitCode {
     let args: Vec<String> = std::env::args().collect();
@@ -71,10 +79,11 @@ fn main() -> ExitCode {
         Some("unlboot") => (USAGE_UNLBOOT, cmd_unlboot),
         Some("camera-drive") => (USAGE_CAMERA, cmd_camera_drive),
         Some("printer-drive") => (USAGE_PRINTER, cmd_printer_drive),
+        Some("state-json") => (USAGE_STATE_JSON, cmd_state_json),
         _ => {
             eprintln!(
                 "usage:\n  {USAGE_SRAMDUMP}\n  {USAGE_GLITCH}\n  {USAGE_UNLBOOT}\n  \
-                 {USAGE_CAMERA}\n  {USAGE_PRINTER}"
+                 {USAGE_CAMERA}\n  {USAGE_PRINTER}\n  {USAGE_STATE_JSON}"
             );
             return ExitCode::from(2);
         }
@@ -154,8 +163,7 @@ fn cmd_glitch(args: &[String]) -> Result<(), String> {
 
     let rom_container = std::fs::read(rom_path).expect("read rom");
     let rom = Cartridge::extract_rom_bytes(&rom_container).expect("extract rom");
-    let state = std::fs::read(state_path).expect("read state");
-    let mut gb = GB::from_state_bytes(&state).expect("load state");
+    let mut gb = GB::from_state_file(state_path).expect("load state");
     if gb.cartridge_needs_rom() {
         assert!(gb.reattach_rom(&rom), "state carried no cartridge");
     }
@@ -485,3 +493,22 @@ fn cmd_printer_drive(args: &[String]) -> Result<(), String> {
     println!("done: {prints} print(s), final screen at {}", out.join("screen-final.png").display());
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// state-json
+// ---------------------------------------------------------------------------
+
+fn cmd_state_json(args: &[String]) -> Result<(), String> {
+    let cli = Cli::parse(args, &["--out"], &[])?;
+    let path = match cli.positionals.as_slice() {
+        [path] => path,
+        _ => return Err(format!("usage: {USAGE_STATE_JSON}")),
+    };
+    let bytes = std::fs::read(path).map_err(|e| format!("read {path}: {e}"))?;
+    let json = state_json::state_file_to_json(&bytes).map_err(|e| format!("{path}: {e}"))?;
+    match cli.value("--out") {
+        Some(out) => std::fs::write(out, json).map_err(|e| format!("write {out}: {e}"))?,
+        None => print!("{json}"),
+    }
+    Ok(())
+}
@@ -6,6 +6,13 @@
 //!       to `--movie` (default <rom>.rbmv) and print (or write to `--golden`)
 //!       the final-frame golden hash. Regenerates goldens intentionally.
 //!
+//!   movie replay  --rom R --movie M [--mode dmg|cgb|auto] [--verify-determinism]
+//!       Replay a power-on movie and print its final-frame hash. With
+//!       `--verify-determinism` it is replayed twice on fresh machines and the
+//!       per-frame hashes diffed; the first diverging frame fails the run.
+//!       `record` takes the flag too, checking its own movie the same way and
+//!       against the hashes seen while recording.
+//!
 //! Record/replay live in the deterministic `rustyboi_core_lib::movie` core;
 //! this bin only owns files. Determinism (no wall clock / RTC / threads in
 //! core) makes every hash reproducible: the same ROM + script always yields
@@ -13,29 +20,38 @@
 
 use rustyboi_core_lib::cartridge::Cartridge;
 use rustyboi_core_lib::gb::{Hardware, GB};
-use rustyboi_core_lib::movie::{sha256, MovieMeta, Recorder};
+use rustyboi_core_lib::movie::{sha256, verify_determinism, Movie, MovieMeta, MovieStart, Recorder};
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use rustyboi_test_runner_lib::cli::reject_unknown_flags;
 use rustyboi_test_runner_lib::script::expand_timeline;
 
+/// A subcommand's body, given the arguments after its name.
+type Command = fn(&[String]) -> Result<(), String>;
+
 fn main() -> ExitCode {
     let args: Vec<String> = std::env::args().collect();
     let sub = args.get(1).map(String::as_str);
     let rest = &args[args.len().min(2)..];
     const USAGE_RECORD: &str = "movie record  --rom R [--movie M] [--mode dmg|cgb|auto] \
-                                [--frames N] [--input SCRIPT] [--author A] [--golden FILE]";
-    if sub != Some("record") {
-        eprintln!("usage:\n  {USAGE_RECORD}");
-        return ExitCode::from(2);
-    }
+                                [--frames N] [--input SCRIPT] [--author A] [--golden FILE] \
+                                [--verify-determinism]";
+    const USAGE_REPLAY: &str = "movie replay  --rom R --movie M [--mode dmg|cgb|auto] [--verify-determinism]";
+    let (usage, cmd): (&str, Command) = match sub {
+        Some("record") => (USAGE_RECORD, cmd_record),
+        Some("replay") => (USAGE_REPLAY, cmd_replay),
+        _ => {
+            eprintln!("usage:\n  {USAGE_RECORD}\n  {USAGE_REPLAY}");
+            return ExitCode::from(2);
+        }
+    };
     // Handled before the strict parse, which would reject `--help` as undeclared.
     if rest.iter().any(|a| a == "--help" || a == "-h") {
-        println!("usage: {USAGE_RECORD}");
+        println!("usage: {usage}");
         return ExitCode::SUCCESS;
     }
-    let result = cmd_record(rest);
+    let result = cmd(rest);
     match result {
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
@@ -49,7 +65,7 @@ fn cmd_record(args: &[String]) -> Result<(), String> {
     reject_unknown_flags(
         args,
         &["--rom", "--movie", "--mode", "--frames", "--input", "--author", "--golden"],
-        &[],
+        &["--verify-determinism"],
     )?;
     let rom_path = arg(args, "--rom").ok_or("record: --rom <path> required")?;
     let mode = arg(args, "--mode").unwrap_or_else(|| "auto".into());
@@ -80,10 +96,8 @@ fn cmd_record(args: &[String]) -> Result<(), String> {
         frame_count: 0,
         note: format!("recorded via movie record; mode={mode}"),
     });
-    let mut final_hash = 0u64;
-    for input in &timeline {
-        final_hash = recorder.set_input(*input);
-    }
+    let recorded_hashes: Vec<u64> = timeline.iter().map(|input| recorder.set_input(*input)).collect();
+    let final_hash = recorded_hashes.last().copied().unwrap_or(0);
     let movie = recorder.finish();
     let bytes = movie.to_bytes();
     std::fs::write(&movie_path, &bytes).map_err(|e| format!("write {}: {e}", movie_path.display()))?;
@@ -99,9 +113,67 @@ fn cmd_record(args: &[String]) -> Result<(), String> {
         std::fs::write(&golden_path, format!("{final_hash:016x}\n"))
             .map_err(|e| format!("write golden {golden_path}: {e}"))?;
     }
+    if args.iter().any(|a| a == "--verify-determinism") {
+        let replayed = verify_replays(&movie, &rom_bytes, hardware)?;
+        if let Some(frame) = replayed.iter().zip(&recorded_hashes).position(|(a, b)| a != b) {
+            return Err(format!(
+                "replay diverged from the recording at frame {frame}: {:016x} vs {:016x}",
+                replayed[frame], recorded_hashes[frame]
+            ));
+        }
+        println!("determinism verified: recording and two replays match over {} frames", replayed.len());
+    }
     Ok(())
 }
 
+fn cmd_replay(args: &[String]) -> Result<(), String> {
+    reject_unknown_flags(args, &["--rom", "--movie", "--mode"], &["--verify-determinism"])?;
+    let rom_path = arg(args, "--rom").ok_or("replay: --rom <path> required")?;
+    let movie_path = arg(args, "--movie").ok_or("replay: --movie <path> required")?;
+    let mode = arg(args, "--mode").unwrap_or_else(|| "auto".into());
+
+    let rom_bytes = std::fs::read(&rom_path).map_err(|e| format!("read {rom_path}: {e}"))?;
+    let cart = Cartridge::from_bytes(&rom_bytes).map_err(|e| format!("load ROM: {e}"))?;
+    let hardware = resolve_hardware(&mode, &cart);
+    let movie_bytes = std::fs::read(&movie_path).map_err(|e| format!("read {movie_path}: {e}"))?;
+    let movie = Movie::from_bytes(&movie_bytes).map_err(|e| format!("decode {movie_path}: {e}"))?;
+    if movie.rom_sha256 != sha256(&rom_bytes) {
+        return Err(format!("{movie_path} was recorded against a different ROM"));
+    }
+    if movie.start != MovieStart::PowerOn {
+        return Err(format!("{movie_path} starts from a savestate; only power-on movies replay here"));
+    }
+
+    let hashes = if args.iter().any(|a| a == "--verify-determinism") {
+        let hashes = verify_replays(&movie, &rom_bytes, hardware)?;
+        println!("determinism verified: two replays match over {} frames", hashes.len());
+        hashes
+    } else {
+        let mut gb = boot(&rom_bytes, hardware)?;
+        rustyboi_core_lib::movie::replay(&movie, &mut gb, true).frame_hashes
+    };
+    println!("replayed {} frames ({hardware:?})", hashes.len());
+    println!("final_hash={:016x}", hashes.last().copied().unwrap_or(0));
+    Ok(())
+}
+
+/// Replay a power-on `movie` twice on fresh machines and return the per-frame
+/// hashes, failing on the first frame the two runs disagree on.
+fn verify_replays(movie: &Movie, rom: &[u8], hardware: Hardware) -> Result<Vec<u64>, String> {
+    // The ROM already loaded once, so the fresh boots cannot fail.
+    let result = verify_determinism(movie, || boot(rom, hardware).expect("ROM loaded before"));
+    result.map(|r| r.frame_hashes).map_err(|d| format!("determinism violated: {d}"))
+}
+
+/// A fresh machine with `rom` inserted, past the boot ROM.
+fn boot(rom: &[u8], hardware: Hardware) -> Result<GB, String> {
+    let cart = Cartridge::from_bytes(rom).map_err(|e| format!("load ROM: {e}"))?;
+    let mut gb = GB::new(hardware);
+    gb.insert(cart);
+    gb.skip_bios();
+    Ok(gb)
+}
+
 /// Value of `--flag value`, if present.
 fn arg(args: &[String], name: &str) -> Option<String> {
     args.iter().position(|a| a == name).and_then(|i| args.get(i + 1).cloned())
@@ -1,10 +1,11 @@
 //! WebAudio output: a scheduled `AudioBufferSourceNode` ring.
 //!
-//! The core emits stereo `f32` samples at a fixed 44100 Hz (see
-//! `audio/controller.rs` `CYCLES_PER_SAMPLE`). WebAudio's `AudioContext`
-//! typically runs at 48000 Hz, but an `AudioBuffer` may declare *any* sample
-//! rate and the graph resamples it on playback — so we build 44100 Hz buffers
-//! directly and let the browser resample. No manual rate conversion.
+//! The core emits stereo `f32` samples at the session's output rate (44100 Hz
+//! unless Settings → Audio Sample Rate picks another; the worker reports it
+//! with `Ready`). WebAudio's `AudioContext` typically runs at 48000 Hz, but an
+//! `AudioBuffer` may declare *any* sample rate and the graph resamples it on
+//! playback — so we build buffers at the core's rate directly and let the
+//! browser resample. No manual rate conversion.
 //!
 //! # Scheduling (glitch-tolerant queued buffers)
 //!
@@ -24,8 +25,8 @@
 use wasm_bindgen::prelude::*;
 use web_sys::{AudioContext, AudioContextState, GainNode};
 
-/// Native sample rate of the core's audio output.
-const CORE_SAMPLE_RATE: f32 = 44100.0;
+/// The core's default output rate, used until the worker reports its own.
+const DEFAULT_SAMPLE_RATE: f32 = 44100.0;
 
 /// Seconds of scheduling cushion kept ahead of the audio clock. ~2 frames of
 /// GB audio; trades a little latency for jitter tolerance.
@@ -40,6 +41,8 @@ pub struct AudioPlayer {
     gain: GainNode,
     /// Absolute `AudioContext` time at which the next buffer should start.
     next_time: f64,
+    /// Rate the queued buffers are declared at (the core's output rate).
+    sample_rate: f32,
 }
 
 impl AudioPlayer {
@@ -51,7 +54,7 @@ impl AudioPlayer {
         let gain = ctx.create_gain()?;
         gain.gain().set_value(0.35);
         gain.connect_with_audio_node(&ctx.destination())?;
-        Ok(AudioPlayer { ctx, gain, next_time: 0.0 })
+        Ok(AudioPlayer { ctx, gain, next_time: 0.0, sample_rate: DEFAULT_SAMPLE_RATE })
     }
 
     /// Resume the context (must run inside a user-gesture handler in Firefox).
@@ -74,7 +77,7 @@ impl AudioPlayer {
         let n = frames as u32;
         let buffer = match self
             .ctx
-            .create_buffer(2, n, CORE_SAMPLE_RATE)
+            .create_buffer(2, n, self.sample_rate)
         {
             Ok(b) => b,
             Err(_) => return,
@@ -110,7 +113,7 @@ impl AudioPlayer {
         }
 
         let _ = src.start_with_when(self.next_time);
-        self.next_time += n as f64 / CORE_SAMPLE_RATE as f64;
+        self.next_time += n as f64 / self.sample_rate as f64;
     }
 }
 
@@ -135,6 +138,12 @@ impl WebAudio {
         self.player.resume();
     }
 
+    /// Declare the rate the worker's batches are produced at (its session's
+    /// audio output rate, posted with `Ready`).
+    pub fn set_sample_rate(&mut self, rate: u32) {
+        self.player.sample_rate = rate as f32;
+    }
+
     /// Queue one worker-produced audio batch (`[l0, r0, l1, r1, ...]`).
     pub fn queue(&mut self, interleaved: &[f32]) {
         self.player.queue(interleaved);
@@ -153,7 +162,7 @@ impl WebAudio {
         if ahead <= 0.0 {
             0.0
         } else {
-            ahead * CORE_SAMPLE_RATE as f64
+            ahead * self.player.sample_rate as f64
         }
     }
 }
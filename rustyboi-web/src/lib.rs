@@ -30,7 +30,7 @@ mod storage;
 mod webapp;
 
 use rustyboi_session::config::DmgPalette;
-use rustyboi_session::ports::{Rumble, Storage, Webcam};
+use rustyboi_session::ports::{NetTransport, Rumble, Storage, Webcam};
 use rustyboi_session::{
     AbstractInput, Config, DebugDetail, Frame, GbButton, Hardware, Ports, Session,
 };
@@ -42,6 +42,8 @@ use rustyboi_session::action::LoadPurpose;
 use rustyboi_session::{FileData, PlatformRequest, SessionUiState, UiAction};
 
 use js_sys::Array;
+use std::cell::RefCell;
+use std::rc::Rc;
 
 use storage::IdbStore;
 
@@ -97,6 +99,26 @@ impl Webcam for NullWebcam {
     }
 }
 
+/// The netplay link as the session sees it: `worker.js` owns the WebSocket
+/// (a relay that forwards binary messages between two players) and shuttles
+/// bytes through these queues, since a worker socket is event-driven and the
+/// session pulls once per frame.
+#[derive(Default)]
+struct NetQueues {
+    inbound: Vec<u8>,
+    outbound: Vec<u8>,
+}
+
+struct WebSocketPipe(Rc<RefCell<NetQueues>>);
+impl NetTransport for WebSocketPipe {
+    fn send(&mut self, bytes: &[u8]) {
+        self.0.borrow_mut().outbound.extend_from_slice(bytes);
+    }
+    fn recv(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.0.borrow_mut().inbound)
+    }
+}
+
 /// The worker-side emulator handle exposed to JavaScript. Owns the session,
 /// storage, and the live keyboard/touch-derived input.
 ///
@@ -139,6 +161,8 @@ pub struct Emulator {
     /// Latest audio backlog (stereo pairs scheduled ahead of the context
     /// clock), posted back from the main thread; `None` until audio runs.
     audio_backlog_pairs: Option<usize>,
+    /// The netplay queues while a partner is connected (see [`NetQueues`]).
+    netplay: Option<Rc<RefCell<NetQueues>>>,
 }
 
 #[wasm_bindgen]
@@ -188,6 +212,7 @@ impl Emulator {
             regulator: rustyboi_session::pacing::Regulator::new(),
             stretcher: rustyboi_session::pacing::Stretcher::new(),
             audio_backlog_pairs: None,
+            netplay: None,
         })
     }
 
@@ -198,8 +223,11 @@ impl Emulator {
     /// this (the worker checks `uncapped_fast_forward` first).
     pub fn frames_to_run(&mut self, now_ms: f64) -> u32 {
         // Retune to the running machine (an SGB1 runs ~61.17 fps — its clock is
-        // the host SNES's / 5). Idempotent, so it rides the tick.
+        // the host SNES's / 5) and its speed setting. Idempotent, so it rides
+        // the tick.
         self.regulator.set_cpu_hz(self.session.cpu_hz());
+        self.regulator.set_speed(self.session.emulation_speed());
+        self.regulator.set_sample_rate(self.session.audio_sample_rate());
         self.regulator.frames_to_run(
             now_ms / 1000.0,
             self.audio_backlog_pairs,
@@ -290,6 +318,21 @@ impl Emulator {
         requests_to_js(&reqs)
     }
 
+    /// Add a user palette (hex colours or JASC-PAL text the main thread read
+    /// from a picked file) and switch to it. Returns Status/Error requests.
+    pub fn load_palette(&mut self, bytes: &[u8]) -> Array {
+        let reqs = self.session.finish_file(LoadPurpose::Palette, bytes).requests;
+        requests_to_js(&reqs)
+    }
+
+    /// Replace the input map with a controls profile (Export Controls JSON
+    /// the main thread read from a picked file). Returns Status/Error
+    /// requests.
+    pub fn load_input_profile(&mut self, bytes: &[u8]) -> Array {
+        let reqs = self.session.finish_file(LoadPurpose::InputProfile, bytes).requests;
+        requests_to_js(&reqs)
+    }
+
     /// Install a SNES-side Super Game Boy firmware dump (`sgb1.sfc` /
     /// `sgb2.sfc`) the user picked, so SGB games show the firmware's own system
     /// border. Validated FIRST (length + CRC32): the session keeps whatever it
@@ -390,12 +433,31 @@ impl Emulator {
     /// Export the full machine state (`.rustyboisave`), or an empty array when
     /// serialization fails / no ROM is loaded.
     pub fn export_state(&mut self) -> js_sys::Uint8Array {
-        match self.session.gb_mut().to_state_bytes() {
+        match self.session.state_file_bytes() {
             Ok(bytes) => js_sys::Uint8Array::from(bytes.as_slice()),
             Err(_) => js_sys::Uint8Array::new_with_length(0),
         }
     }
 
+    /// Export the machine state as versioned JSON (see
+    /// `rustyboi_session::state_json`), or an empty array on failure.
+    pub fn export_state_json(&mut self) -> js_sys::Uint8Array {
+        match self.session.state_json_bytes() {
+            Ok(bytes) => js_sys::Uint8Array::from(bytes.as_slice()),
+            Err(_) => js_sys::Uint8Array::new_with_length(0),
+        }
+    }
+
+    /// Snapshot the machine to the auto slot when auto-save is on (the page is
+    /// going hidden or unloading). The slot lands in the IndexedDB-mirrored
+    /// storage, so the next visit can offer to resume it.
+    pub fn auto_save(&mut self) {
+        let now = (js_sys::Date::now() / 1000.0) as u64;
+        if let Err(e) = self.session.auto_save(now) {
+            web_sys::console::warn_1(&format!("auto-save failed: {e}").into());
+        }
+    }
+
     /// Advance one presented frame, fill the RGBA framebuffer, and return this
     /// frame's interleaved stereo audio (`[l0,r0,l1,r1,...]`) as a fresh
     /// `Float32Array` for the worker to transfer to the main-thread audio sink.
@@ -504,6 +566,7 @@ impl Emulator {
     pub fn set_debug_detail(&mut self, active: bool, bits: u8) {
         self.debug_active = active;
         self.debug_detail = DebugDetail::from_bits(bits);
+        self.session.set_apu_scope(active && self.debug_detail.apu);
     }
 
     /// Build the debug snapshot for the current frame and return it bincode-
@@ -533,7 +596,14 @@ impl Emulator {
             self.session.toggle_pause();
             return Ok(Array::new());
         }
-        let outcome = self.session.apply(ui_action, 0);
+        let picked_rom = matches!(ui_action, UiAction::ChooseArchiveEntry(_));
+        // Wall-clock seconds stamp savestate slots for the Emulation menu.
+        let now = (js_sys::Date::now() / 1000.0) as u64;
+        let outcome = self.session.apply(ui_action, now);
+        if picked_rom {
+            // The archive chooser's pick is a ROM load (see `load_rom`).
+            self.has_rom = self.session.gb().has_rom();
+        }
         if self.session.palette() != palette_before {
             self.dmg_palette = self.session.config().dmg_palette;
         }
@@ -567,6 +637,13 @@ impl Emulator {
         state
     }
 
+    /// The audio output rate in Hz the core emits at (the persisted Settings →
+    /// Audio Sample Rate). Posted with `Ready` so the main-thread sink builds
+    /// its buffers at the same rate.
+    pub fn audio_sample_rate(&self) -> u32 {
+        self.session.audio_sample_rate()
+    }
+
     /// The current hardware model as a lowercase string ("dmg" / "cgb").
     pub fn hardware(&self) -> String {
         match self.session.hardware() {
@@ -580,6 +657,39 @@ impl Emulator {
         self.has_rom
     }
 
+    /// The worker's netplay WebSocket opened: plug the link port into it.
+    pub fn netplay_open(&mut self) {
+        let queues = Rc::new(RefCell::new(NetQueues::default()));
+        self.session.start_netplay(Box::new(WebSocketPipe(queues.clone())));
+        self.netplay = Some(queues);
+    }
+
+    /// Bytes a netplay WebSocket message carried, for the next frame.
+    pub fn netplay_receive(&mut self, bytes: &[u8]) {
+        if let Some(queues) = &self.netplay {
+            queues.borrow_mut().inbound.extend_from_slice(bytes);
+        }
+    }
+
+    /// Bytes to send to the netplay partner since the last call (empty when
+    /// there is nothing to say).
+    pub fn netplay_take_outgoing(&mut self) -> js_sys::Uint8Array {
+        let bytes = self.netplay.as_ref().map(|q| std::mem::take(&mut q.borrow_mut().outbound)).unwrap_or_default();
+        js_sys::Uint8Array::from(&bytes[..])
+    }
+
+    /// Whether the link port is still plugged into the netplay partner (Emulation
+    /// → Disconnect Netplay unplugs it; the worker then closes its socket).
+    pub fn netplay_active(&self) -> bool {
+        self.session.netplay_active()
+    }
+
+    /// The netplay WebSocket closed (or the user disconnected): unplug.
+    pub fn netplay_close(&mut self) {
+        self.session.stop_netplay();
+        self.netplay = None;
+    }
+
     /// Number of persisted IndexedDB keys hydrated at startup (diagnostic).
     pub fn stored_key_count(&self) -> usize {
         self.storage.len()
@@ -672,6 +782,14 @@ fn requests_to_js(requests: &[PlatformRequest]) -> Array {
                 set("name", suggested_name.as_str().into());
                 set("bytes", js_sys::Uint8Array::from(bytes.as_slice()).into());
             }
+            // The async Clipboard API lives on the main thread; it encodes the
+            // pixels to PNG there (the only image type browsers accept).
+            PlatformRequest::CopyImage { width, height, rgba } => {
+                set("type", "CopyImage".into());
+                set("width", (*width).into());
+                set("height", (*height).into());
+                set("rgba", js_sys::Uint8Array::from(rgba.as_slice()).into());
+            }
             // Serviced inside the worker for the web frontend and not expected
             // from the actions it issues; surface as a status so nothing is lost.
             PlatformRequest::SaveStateBytes { .. } | PlatformRequest::LoadFile { .. } => {
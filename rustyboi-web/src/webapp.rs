@@ -37,9 +37,9 @@ use winit::window::{Window, WindowId};
 use rustyboi_frontend_lib::renderer::{GameFrame, Present, Renderer, SourceSize};
 use rustyboi_frontend_lib::ui_host::UiHost;
 use rustyboi_session::input_config::{
-    FiredHotkey, HeldInputs, HotkeyAction, InputTrigger, KeyName, PadButton, ResolveState,
+    FiredHotkey, HeldInputs, HotkeyAction, InputTrigger, KeyName, PadButton, ResolveState, StickConfig,
 };
-use rustyboi_session::{DebugSnapshot, GbButton, SessionUiState, UiAction};
+use rustyboi_session::{step_emulation_speed, DebugSnapshot, GbButton, SessionUiState, UiAction};
 
 /// State shared between the JS-facing [`WebApp`] handle and the spawned winit
 /// event loop. The JS shell writes the worker's frames/UI-state/status in; the
@@ -65,6 +65,9 @@ struct Shared {
 
     /// Last GB button bitmask posted to the worker (dedupe: only post changes).
     last_input_mask: u8,
+    /// While the hold-to-fast-forward chord is down: whether its press engaged
+    /// fast-forward (so its release toggles it back off). `None` when not held.
+    ff_hold: Option<bool>,
 
     /// Latest debug read-model from the worker (deserialized), rendered by the
     /// egui debug panels. `None` until the first snapshot arrives / while no
@@ -123,6 +126,7 @@ impl Shared {
             clear_error: false,
             ui_dirty: true,
             last_input_mask: 0,
+            ff_hold: None,
             debug_snapshot: None,
             last_debug_detail: None,
             post_action,
@@ -286,6 +290,10 @@ impl FpsCounter {
     fn fps(&self) -> f32 {
         self.meter.fps() as f32
     }
+
+    fn speed(&self) -> f32 {
+        self.meter.speed_percent() as f32
+    }
 }
 
 /// The winit 0.30 `ApplicationHandler` for the web driver. Creates the window in
@@ -660,7 +668,7 @@ fn draw(
     // Held gamepad buttons, computed before the UI so the keybind editor can
     // capture controller presses (egui never sees pad input); reused for the GB
     // input resolve below.
-    let pad = gamepad_pad_held();
+    let pad = gamepad_pad_held(&ui_state.input.left_stick);
     // The menu bar auto-hides while the canvas is in the Fullscreen API.
     let fullscreen = web_sys::window()
         .and_then(|w| w.document())
@@ -678,6 +686,9 @@ fn draw(
             // repaint continuously while shown (egui geometry is otherwise reused).
             force_repaint: force_repaint || debug_open || ui_state.show_fps,
             fps: fps.fps(),
+            speed: fps.speed(),
+            // The AudioContext lives in JS; it exposes no underrun counter.
+            audio: None,
         },
     );
 
@@ -693,6 +704,7 @@ fn draw(
     let held = HeldInputs {
         keys: held_keys.clone(),
         pad,
+        pads: Vec::new(),
     };
     let (mut button_state, fired) = ui_state.input.resolve(&held, resolve_state);
     // Union the egui on-screen touch overlay on top of the resolved buttons.
@@ -725,8 +737,10 @@ fn draw(
     // the LCD post-process effect. On web the renderer lives on the main thread
     // (the session is in the worker), so these must be pushed here too.
     renderer.set_scaling_mode(ui_state.scaling);
+    renderer.set_aspect_ratio(ui_state.aspect_ratio);
     renderer.set_texture_filter(ui_state.texture_filter);
     renderer.set_lcd_effect(ui_state.lcd_effect.resolve(ui_state.hardware.to_hardware()));
+    renderer.set_dot_matrix(ui_state.dot_matrix);
 
     // Render: the game texture (uploaded above) letterboxed into the central
     // region, egui on top. game: None — the retained texture is drawn via has_game.
@@ -773,6 +787,8 @@ fn dispatch_action(shared: &Rc<RefCell<Shared>>, action: UiAction) {
         UiAction::ImportRtc(file) => post_import(shared, "rtc", file),
         UiAction::ApplyPatch(file) => post_import(shared, "patch", file),
         UiAction::LoadMovie(file) => post_import(shared, "movie", file),
+        UiAction::LoadPaletteFile(file) => post_import(shared, "palette", file),
+        UiAction::ImportInputProfile(file) => post_import(shared, "input_profile", file),
         // The SGB system border's only source is the user's own SNES-side
         // firmware dump; the worker validates it, installs it, and persists it
         // to IndexedDB so it survives a reload.
@@ -780,6 +796,7 @@ fn dispatch_action(shared: &Rc<RefCell<Shared>>, action: UiAction) {
         // Exports: the worker owns the session bytes, so ask it to produce them;
         // it posts them back and the JS shell triggers the browser download.
         UiAction::ExportState => request_export(shared, "state"),
+        UiAction::ExportStateJson => request_export(shared, "state_json"),
         UiAction::ExportBatterySave => request_export(shared, "battery"),
         UiAction::ExportRtc => request_export(shared, "rtc"),
         // Fullscreen is a main-thread DOM op (canvas Fullscreen API); the worker
@@ -810,21 +827,43 @@ fn dispatch_action(shared: &Rc<RefCell<Shared>>, action: UiAction) {
         // routing is decided, rather than being silently dropped.
         serviceable @ (UiAction::TogglePause
         | UiAction::ToggleRecording
+        | UiAction::ToggleInputEditor
+        | UiAction::SetTasInput(..)
+        | UiAction::SeekTasFrame(_)
+        | UiAction::ExportTasMovie
+        | UiAction::ExportInputProfile
+        | UiAction::ToggleAudioRecording
+        | UiAction::SetRecordStems(_)
+        | UiAction::SetMovieAnchorByHash(_)
+        | UiAction::CopyScreenshot
+        | UiAction::ToggleGifRecording
+        | UiAction::SetGifScale(_)
+        | UiAction::SetWindowScale(_)
+        | UiAction::SetGifMaxSeconds(_)
+        | UiAction::ToggleVideoRecording
         | UiAction::StopReplay
         | UiAction::TogglePrinter
+        | UiAction::ToggleLinkPartner
+        | UiAction::SetLinkInputPartner(_)
+        | UiAction::StopNetplay
         | UiAction::Restart
         | UiAction::ClearError
         | UiAction::SaveSlot(_)
         | UiAction::LoadSlot(_)
         | UiAction::Quicksave
         | UiAction::Quickload
+        | UiAction::UndoLoadState
         | UiAction::ToggleFastForward
         | UiAction::FrameAdvance
         | UiAction::ToggleSgbBorder
         | UiAction::ToggleTouchControls
         | UiAction::ToggleShowFps
+        | UiAction::ToggleDotMatrix
+        | UiAction::ToggleScanlinePpu
         | UiAction::SetHardware(_)
         | UiAction::SetPalette(_)
+        | UiAction::AddPalette(_)
+        | UiAction::RemovePalette(_)
         | UiAction::SetGbcDmgPalette(_)
         | UiAction::SetSgbPalette(_)
         | UiAction::SetColorCorrection(_)
@@ -834,18 +873,45 @@ fn dispatch_action(shared: &Rc<RefCell<Shared>>, action: UiAction) {
         | UiAction::SetPrinterScale(_)
         | UiAction::SetTouchOpacity(_)
         | UiAction::SetRewindEnabled(_)
+        | UiAction::SetRtcHostClock(_)
+        | UiAction::SetAudioHighPass(_)
+        | UiAction::SetChannelMix(_)
+        | UiAction::SetSampleRate(_)
+        | UiAction::SetAudioBufferSize(_)
+        | UiAction::SetAudioLatencyFrames(_)
+        | UiAction::SetAudioDevice(_)
+        | UiAction::SetSaveDir(_)
+        | UiAction::SetAutoSaveState(_)
+        | UiAction::SetPauseOnFocusLoss(_)
         | UiAction::SetRewindInterval(_)
         | UiAction::SetRewindDepth(_)
         | UiAction::SetVolume(_)
+        | UiAction::ToggleMute
         | UiAction::SetFastForwardFactor(_)
+        | UiAction::SetFastForwardMuted(_)
+        | UiAction::SetFastForwardVsyncOff(_)
+        | UiAction::SetFrameSkip(_)
+        | UiAction::SetAccuracyPreset(_)
+        | UiAction::SetEmulationSpeed(_)
+        | UiAction::SetRunAhead(_)
         | UiAction::SetScalingMode(_)
+        | UiAction::SetAspectRatio(_)
         | UiAction::SetGraphicsBackend(_)
         | UiAction::SetInputConfig(_)
+        | UiAction::SetDebugLayout(_)
         | UiAction::AddCheat(_)
         | UiAction::AddCheats(_)
         | UiAction::RemoveCheat(_)
         | UiAction::GetCheats
-        | UiAction::ClearFetchedCheats) => {
+        | UiAction::ClearFetchedCheats
+        | UiAction::FlushSaves
+        | UiAction::EraseBatterySave
+        | UiAction::ChooseArchiveEntry(_)
+        | UiAction::DismissArchiveChoice
+        | UiAction::ResumeAutoSave
+        | UiAction::DismissResume
+        | UiAction::RestoreRecoverySnapshot
+        | UiAction::ClearRecentRoms) => {
             if let Ok(json) = serde_json::to_string(&serviceable) {
                 let s = shared.borrow();
                 let cb = s.post_action.clone();
@@ -904,7 +970,8 @@ fn input_mask(state: rustyboi_session::ButtonState) -> u8 {
 }
 
 /// Dispatch the hotkeys the resolver fired this frame. Fast-forward and rewind
-/// have worker paths (reuse `set_rewind` / the `ToggleFastForward` action);
+/// have worker paths (reuse `set_rewind` / the `ToggleFastForward` action; the
+/// hold key toggles on its press and again on its release if it engaged);
 /// quicksave/quickload/pause route through the worker as a `UiAction`; exit and
 /// fullscreen are main-thread DOM ops (exit closes nothing on web, so it's a
 /// no-op; fullscreen calls the canvas bridge). Turbo is baked into the button
@@ -913,6 +980,7 @@ fn dispatch_hotkeys(shared: &Rc<RefCell<Shared>>, fired: &[FiredHotkey], rewind_
     // Rewind is a hold action: engage while active, release when it stops firing.
     let want_rewind = fired.iter().any(|f| matches!(f.action, HotkeyAction::Rewind));
     set_rewind(shared, rewind_held, want_rewind);
+    hold_fast_forward(shared, fired.iter().any(|f| f.action == HotkeyAction::HoldFastForward));
 
     for f in fired {
         match f.action {
@@ -921,8 +989,18 @@ fn dispatch_hotkeys(shared: &Rc<RefCell<Shared>>, fired: &[FiredHotkey], rewind_
             }
             HotkeyAction::Quicksave if f.rising => dispatch_action(shared, UiAction::Quicksave),
             HotkeyAction::Quickload if f.rising => dispatch_action(shared, UiAction::Quickload),
+            HotkeyAction::UndoLoadState if f.rising => dispatch_action(shared, UiAction::UndoLoadState),
+            HotkeyAction::SaveSlot(n) if f.rising => dispatch_action(shared, UiAction::SaveSlot(n.into())),
+            HotkeyAction::LoadSlot(n) if f.rising => dispatch_action(shared, UiAction::LoadSlot(n.into())),
             HotkeyAction::FrameAdvance if f.rising => dispatch_action(shared, UiAction::FrameAdvance),
             HotkeyAction::TogglePause if f.rising => dispatch_action(shared, UiAction::TogglePause),
+            HotkeyAction::ToggleMute if f.rising => dispatch_action(shared, UiAction::ToggleMute),
+            HotkeyAction::SpeedUp | HotkeyAction::SlowDown if f.rising => {
+                let speed = shared.borrow().ui_state.emulation_speed;
+                let faster = f.action == HotkeyAction::SpeedUp;
+                dispatch_action(shared, UiAction::SetEmulationSpeed(step_emulation_speed(speed, faster)));
+            }
+            HotkeyAction::NormalSpeed if f.rising => dispatch_action(shared, UiAction::SetEmulationSpeed(100)),
             HotkeyAction::ToggleFullscreen if f.rising => {
                 dispatch_action(shared, UiAction::ToggleFullscreen);
             }
@@ -931,11 +1009,35 @@ fn dispatch_hotkeys(shared: &Rc<RefCell<Shared>>, fired: &[FiredHotkey], rewind_
     }
 }
 
+/// Hold-to-fast-forward across the worker boundary, mirroring
+/// `Session::hold_fast_forward`: the press toggles fast-forward on from normal
+/// speed, and the release toggles it back off only if that press engaged it.
+fn hold_fast_forward(shared: &Rc<RefCell<Shared>>, held: bool) {
+    let mut s = shared.borrow_mut();
+    let toggle = match (held, s.ff_hold) {
+        (true, None) => {
+            let engage = !s.ui_state.fast_forward && !s.ui_state.paused;
+            s.ff_hold = Some(engage);
+            engage
+        }
+        (false, Some(engaged)) => {
+            s.ff_hold = None;
+            engaged && s.ui_state.fast_forward
+        }
+        _ => false,
+    };
+    drop(s);
+    if toggle {
+        dispatch_action(shared, UiAction::ToggleFastForward);
+    }
+}
+
 /// Poll connected gamepads (the Gamepad API) and collect their held buttons as
 /// abstract [`PadButton`]s. Standard mapping: 0=South,1=East,2=West,3=North,
 /// 8=Select,9=Start,12..15=D-pad,4/5=L1/R1,6/7=L2/R2; D-pad also honors the left
-/// stick (axes 0/1, web Y is +down). Empty with no gamepad; re-polled each frame.
-fn gamepad_pad_held() -> HashSet<PadButton> {
+/// stick (axes 0/1, web Y is +down) through `stick`'s deadzone and diagonal
+/// settings. Empty with no gamepad; re-polled each frame.
+fn gamepad_pad_held(stick: &StickConfig) -> HashSet<PadButton> {
     let mut held = HashSet::new();
     let Some(win) = web_sys::window() else { return held };
     let Ok(pads) = win.navigator().get_gamepads() else { return held };
@@ -973,10 +1075,9 @@ fn gamepad_pad_held() -> HashSet<PadButton> {
         // Analog sticks as discrete directions (web axes: 0/1 = left X/Y, 2/3 =
         // right X/Y, +Y is down). Bound with the d-pad by default, separately
         // mappable — sticks and d-pad interchangeable.
-        hold(axis(1) < -0.5, PadButton::LStickUp);
-        hold(axis(1) > 0.5, PadButton::LStickDown);
-        hold(axis(0) < -0.5, PadButton::LStickLeft);
-        hold(axis(0) > 0.5, PadButton::LStickRight);
+        for b in stick.directions(axis(0) as f32, -axis(1) as f32) {
+            hold(true, b);
+        }
         hold(axis(3) < -0.5, PadButton::RStickUp);
         hold(axis(3) > 0.5, PadButton::RStickDown);
         hold(axis(2) < -0.5, PadButton::RStickLeft);
@@ -11,7 +11,7 @@
 mod common;
 
 use rustyboi_session::action::{
-    GbcDmgPalette, HardwareChoice, LcdEffect, DmgPaletteChoice, ScalingMode, TextureFilter,
+    AspectRatio, GbcDmgPalette, HardwareChoice, LcdEffect, DmgPaletteChoice, ScalingMode, TextureFilter,
 };
 use rustyboi_session::{ColorCorrection, InputConfig, UiAction};
 use rustyboi_web::Emulator;
@@ -99,23 +99,50 @@ fn serviceable_actions() -> Vec<UiAction> {
     vec![
         TogglePause,
         ToggleRecording,
+        ToggleInputEditor,
+        SetTasInput(1, rustyboi_session::GbButton::A, true),
+        SeekTasFrame(0),
+        ExportTasMovie,
+        ExportInputProfile,
+        ToggleAudioRecording,
+        SetRecordStems(true),
+        SetMovieAnchorByHash(true),
+        CopyScreenshot,
+        SetGifScale(1),
+        SetWindowScale(2),
+        SetGifMaxSeconds(10),
+        ToggleGifRecording,
+        ToggleGifRecording, // stop: exports the GIF
+        ToggleVideoRecording,
+        ToggleVideoRecording, // stop: exports the zip
         StopReplay,
         TogglePrinter,
         TogglePrinter, // toggle back
+        ToggleLinkPartner,
+        SetLinkInputPartner(true),
+        ToggleLinkPartner, // unplug again
+        StopNetplay,
         Restart,
         ClearError,
         SaveSlot(1),
         LoadSlot(1),
         Quicksave,
         Quickload,
+        UndoLoadState,
         ToggleFastForward,
         ToggleFastForward,
         FrameAdvance,
         ToggleSgbBorder,
         ToggleTouchControls,
+        ToggleDotMatrix,
+        ToggleDotMatrix,
+        ToggleScanlinePpu,
+        ToggleScanlinePpu,
         SetHardware(HardwareChoice::Dmg),
         SetHardware(HardwareChoice::Cgb),
         SetPalette(DmgPaletteChoice::Green),
+        AddPalette("#FFFFFF #AAAAAA #555555 #000000".into()),
+        RemovePalette(0),
         SetGbcDmgPalette(GbcDmgPalette::Auto),
         SetGbcDmgPalette(GbcDmgPalette::Scheme(5)),
         SetColorCorrection(ColorCorrection::Lcd),
@@ -125,16 +152,36 @@ fn serviceable_actions() -> Vec<UiAction> {
         SetPrinterScale(4),
         SetTouchOpacity(50),
         SetRewindEnabled(true),
+        SetRtcHostClock(true),
+        SetAudioHighPass(true),
+        SetChannelMix(rustyboi_session::ChannelMix::default()),
+        SetSampleRate(48_000),
+        SetAudioBufferSize(512),
+        SetAudioLatencyFrames(4),
+        SetAudioDevice(None),
+        SetAutoSaveState(true),
+        SetPauseOnFocusLoss(true),
         SetRewindInterval(3),
         SetRewindDepth(42),
         SetVolume(80),
+        ToggleMute,
         SetScalingMode(ScalingMode::Stretch),
+        SetAspectRatio(AspectRatio::FourByThree),
         SetInputConfig(InputConfig::default()),
+        SetDebugLayout(Default::default()),
         AddCheat("00A-B7F".into()),
         AddCheats(vec!["00A-B7F".into()]),
         RemoveCheat("00A-B7F".into()),
         GetCheats,
         ClearFetchedCheats,
+        FlushSaves,
+        EraseBatterySave,
+        ChooseArchiveEntry("game.gb".into()),
+        DismissArchiveChoice,
+        ResumeAutoSave,
+        DismissResume,
+        RestoreRecoverySnapshot,
+        ClearRecentRoms,
     ]
 }
 
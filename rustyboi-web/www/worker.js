@@ -23,6 +23,7 @@
 //     SetInput{mask}       GB button bitmask (keyboard ∪ egui touch overlay)
 //     SetDebugDetail{active,bits}  which debug snapshot to build (open panels)
 //     Action{json}         a UiAction (JSON) applied via Session::apply
+//     AutoSave             snapshot to the auto slot (page hidden/unloading)
 //   worker -> main:
 //     Ready{hardware}      emulator constructed, loop running
 //     Frame{rgba,width,height}  transferred RGBA ArrayBuffer + pixel size
@@ -137,6 +138,7 @@ function loop() {
     // Hand any completed Game Boy Printer sheets (PNG bytes) to the main thread
     // as downloads. Prints are rare, so this is an empty array almost every tick.
     if (ran > 0) drainPrints();
+    if (ran > 0) flushNetplay();
   } catch (err) {
     running = false;
     fail(err);
@@ -160,6 +162,49 @@ function drainPrints() {
   }
 }
 
+// Netplay: the link cable over a WebSocket relay (any server that forwards
+// binary messages between the two players). Browsers cannot accept TCP, so
+// both players join the same relay URL; the session frames and paces the
+// traffic, this only moves the bytes.
+let netplaySocket = null;
+
+function joinNetplay(url) {
+  if (netplaySocket) netplaySocket.close();
+  const ws = new WebSocket(url);
+  ws.binaryType = "arraybuffer";
+  ws.onopen = () => {
+    emu.netplay_open();
+    status(`Netplay connected to ${url}`);
+    postUiState();
+  };
+  ws.onmessage = (ev) => emu.netplay_receive(new Uint8Array(ev.data));
+  ws.onclose = () => {
+    if (netplaySocket !== ws) return;
+    netplaySocket = null;
+    emu.netplay_close();
+    status("Netplay disconnected");
+    postUiState();
+  };
+  netplaySocket = ws;
+}
+
+// Send what the session queued for the partner this tick, and hang up once
+// the user disconnected from the Emulation menu.
+function flushNetplay() {
+  if (!netplaySocket || netplaySocket.readyState !== WebSocket.OPEN) return;
+  if (!emu.netplay_active()) {
+    netplaySocket.close();
+    return;
+  }
+  const bytes = emu.netplay_take_outgoing();
+  if (bytes.length > 0) netplaySocket.send(bytes);
+}
+
+function postUiState() {
+  const uiState = emu.take_ui_state();
+  if (uiState) post({ type: "UiState", json: uiState });
+}
+
 function startLoop() {
   if (running) return;
   running = true;
@@ -173,7 +218,7 @@ async function handleInit() {
     emu.set_debug_detail(pendingDebug.active, pendingDebug.bits);
     pendingDebug = null;
   }
-  post({ type: "Ready", hardware: emu.hardware() });
+  post({ type: "Ready", hardware: emu.hardware(), sampleRate: emu.audio_sample_rate() });
   // Push the initial UI-state so the egui menus reflect persisted config.
   const uiState = emu.take_ui_state();
   if (uiState) post({ type: "UiState", json: uiState });
@@ -226,14 +271,16 @@ self.onmessage = async (e) => {
         emit(emu.load_state(new Uint8Array(m.bytes)));
         break;
       case "ImportFile": {
-        // m.purpose ∈ state|battery|rtc|patch|movie|sgb_firmware; m.bytes is a
-        // transferred ArrayBuffer.
+        // m.purpose ∈ state|battery|rtc|patch|movie|palette|input_profile|
+        // sgb_firmware; m.bytes is a transferred ArrayBuffer.
         const data = new Uint8Array(m.bytes);
         if (m.purpose === "state") emit(emu.load_state(data));
         else if (m.purpose === "battery") emit(emu.import_battery(data));
         else if (m.purpose === "rtc") emit(emu.import_rtc(data));
         else if (m.purpose === "patch") emit(emu.apply_patch(data));
         else if (m.purpose === "movie") emit(emu.load_movie(data));
+        else if (m.purpose === "palette") emit(emu.load_palette(data));
+        else if (m.purpose === "input_profile") emit(emu.load_input_profile(data));
         else if (m.purpose === "sgb_firmware") emit(emu.load_sgb_firmware(data));
         break;
       }
@@ -242,6 +289,7 @@ self.onmessage = async (e) => {
         // the main thread, which triggers the browser download.
         let bytes, name;
         if (m.kind === "state") { bytes = emu.export_state(); name = "savestate.rustyboisave"; }
+        else if (m.kind === "state_json") { bytes = emu.export_state_json(); name = "savestate.json"; }
         else if (m.kind === "battery") { bytes = emu.export_battery(); name = "battery.sav"; }
         else if (m.kind === "rtc") { bytes = emu.export_rtc(); name = "clock.rtc"; }
         else break;
@@ -255,6 +303,12 @@ self.onmessage = async (e) => {
       case "SetInput":
         emu.set_input_mask(m.mask & 0xff);
         break;
+      case "AutoSave":
+        emu.auto_save();
+        break;
+      case "NetplayJoin":
+        joinNetplay(m.url);
+        break;
       case "Action": {
         emit(emu.apply_action(m.json));
         // Push fresh UI state immediately: an action can change it (e.g. the